@@ -31,6 +31,7 @@ pub use client::{
 };
 pub use events::Event;
 pub use movement::{
-    PhysicsState, SprintDirection, StartSprintEvent, StartWalkEvent, WalkDirection,
+    DismountEvent, PhysicsState, SneakEvent, SprintDirection, StartFallFlyingEvent,
+    StartSprintEvent, StartWalkEvent, WalkDirection,
 };
 pub use plugins::*;
@@ -251,11 +251,19 @@ pub fn handle_start_use_item_queued(
         &HitResultComponent,
         &LookDirection,
         Option<&Mining>,
+        Option<&Inventory>,
     )>,
     entity_id_query: Query<&MinecraftEntityId>,
 ) {
-    for (entity, start_use_item, mut prediction_handler, hit_result, look_direction, mining) in
-        query
+    for (
+        entity,
+        start_use_item,
+        mut prediction_handler,
+        hit_result,
+        look_direction,
+        mining,
+        inventory,
+    ) in query
     {
         commands.entity(entity).remove::<StartUseItemQueued>();
 
@@ -314,6 +322,26 @@ pub fn handle_start_use_item_queued(
                     // also need to send a SwingArmEvent.
                     // basically, this TODO is for simulating block
                     // interactions/placements on the client-side.
+
+                    // boats don't do anything when they're used on a block, so vanilla uses
+                    // them normally afterwards. they're placed on the water that our hit
+                    // result went through.
+                    let is_holding_boat = matches!(start_use_item.hand, InteractionHand::MainHand)
+                        && inventory.is_some_and(|inventory| {
+                            azalea_registry::tags::items::BOATS
+                                .contains(&inventory.held_item().kind())
+                        });
+                    if is_holding_boat {
+                        commands.trigger(SendPacketEvent::new(
+                            entity,
+                            ServerboundUseItem {
+                                hand: start_use_item.hand,
+                                seq: prediction_handler.start_predicting(),
+                                x_rot: look_direction.x_rot,
+                                y_rot: look_direction.y_rot,
+                            },
+                        ));
+                    }
                 }
             }
             HitResult::Entity(r) => {
@@ -1,6 +1,6 @@
 use azalea_block::{BlockState, BlockTrait, fluid_state::FluidState};
 use azalea_core::{direction::Direction, game_type::GameMode, position::BlockPos, tick::GameTick};
-use azalea_entity::{FluidOnEyes, Physics, Position, mining::get_mine_progress};
+use azalea_entity::{ActiveEffects, FluidOnEyes, Physics, Position, mining::get_mine_progress};
 use azalea_inventory::ItemStack;
 use azalea_physics::{PhysicsSet, collision::BlockWithShape};
 use azalea_protocol::packets::game::s_player_action::{self, ServerboundPlayerAction};
@@ -167,7 +167,7 @@ pub struct StartMiningBlockEvent {
     pub entity: Entity,
     pub position: BlockPos,
 }
-fn handle_start_mining_block_event(
+pub fn handle_start_mining_block_event(
     mut commands: Commands,
     mut events: EventReader<StartMiningBlockEvent>,
     mut query: Query<&HitResultComponent>,
@@ -214,6 +214,7 @@ fn handle_mining_queued(
         &Inventory,
         &FluidOnEyes,
         &Physics,
+        &ActiveEffects,
         Option<&Mining>,
         &mut BlockStatePredictionHandler,
         &mut MineDelay,
@@ -231,6 +232,7 @@ fn handle_mining_queued(
         inventory,
         fluid_on_eyes,
         physics,
+        active_effects,
         mining,
         mut sequence_number,
         mut mine_delay,
@@ -310,6 +312,7 @@ fn handle_mining_queued(
                     &inventory.inventory_menu,
                     fluid_on_eyes,
                     physics,
+                    active_effects,
                 ) >= 1.
             {
                 // block was broken instantly (instamined)
@@ -545,6 +548,7 @@ pub fn continue_mining_block(
         &MineItem,
         &FluidOnEyes,
         &Physics,
+        &ActiveEffects,
         &Mining,
         &mut MineDelay,
         &mut MineProgress,
@@ -564,6 +568,7 @@ pub fn continue_mining_block(
         current_mining_item,
         fluid_on_eyes,
         physics,
+        active_effects,
         mining,
         mut mine_delay,
         mut mine_progress,
@@ -621,6 +626,7 @@ pub fn continue_mining_block(
                 &inventory.inventory_menu,
                 fluid_on_eyes,
                 physics,
+                active_effects,
             );
 
             if **mine_ticks % 4. == 0. {
@@ -24,6 +24,7 @@ pub mod task_pool;
 pub mod tick_broadcast;
 pub mod tick_counter;
 pub mod tick_end;
+pub mod vehicle;
 
 /// This plugin group will add all the default plugins necessary for Azalea to
 /// work.
@@ -45,6 +46,7 @@ impl PluginGroup for DefaultPlugins {
             .add(chat::ChatPlugin)
             .add(disconnect::DisconnectPlugin)
             .add(movement::MovementPlugin)
+            .add(vehicle::VehiclePlugin)
             .add(interact::InteractPlugin)
             .add(respawn::RespawnPlugin)
             .add(mining::MiningPlugin)
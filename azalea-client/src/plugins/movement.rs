@@ -6,8 +6,9 @@ use azalea_core::{
 };
 use azalea_entity::{
     Attributes, InLoadedChunk, Jumping, LastSentPosition, LookDirection, Physics, Position,
-    metadata::Sprinting,
+    metadata::{FallFlying, ShiftKeyDown, Sprinting},
 };
+use azalea_inventory::Menu;
 use azalea_physics::{PhysicsSet, ai_step};
 use azalea_protocol::{
     common::movements::MoveFlags,
@@ -27,7 +28,7 @@ use bevy_app::{App, Plugin, Update};
 use bevy_ecs::prelude::*;
 use thiserror::Error;
 
-use crate::{client::Client, packet::game::SendPacketEvent};
+use crate::{client::Client, inventory::Inventory, packet::game::SendPacketEvent};
 
 #[derive(Error, Debug)]
 pub enum MovePlayerError {
@@ -54,9 +55,19 @@ impl Plugin for MovementPlugin {
         app.add_event::<StartWalkEvent>()
             .add_event::<StartSprintEvent>()
             .add_event::<KnockbackEvent>()
+            .add_event::<DismountEvent>()
+            .add_event::<SneakEvent>()
+            .add_event::<StartFallFlyingEvent>()
             .add_systems(
                 Update,
-                (handle_sprint, handle_walk, handle_knockback)
+                (
+                    handle_sprint,
+                    handle_walk,
+                    handle_knockback,
+                    handle_dismount,
+                    handle_sneak,
+                    handle_start_fall_flying,
+                )
                     .chain()
                     .in_set(MoveEventsSet),
             )
@@ -138,6 +149,11 @@ pub struct PhysicsState {
     // Whether we're going to try to start sprinting this tick. Equivalent to
     // holding down ctrl for a tick.
     pub trying_to_sprint: bool,
+    // Whether we're going to try to get off the entity that we're riding this
+    // tick. Equivalent to tapping shift in vanilla.
+    pub trying_to_dismount: bool,
+    // Whether we're sneaking. Equivalent to holding down shift.
+    pub trying_to_sneak: bool,
 
     pub move_direction: WalkDirection,
     pub move_vector: Vec2,
@@ -254,10 +270,10 @@ pub fn send_position(
 #[derive(Debug, Default, Component, Clone, PartialEq, Eq)]
 pub struct LastSentInput(pub ServerboundPlayerInput);
 pub fn send_player_input_packet(
-    mut query: Query<(Entity, &PhysicsState, &Jumping, Option<&LastSentInput>)>,
+    mut query: Query<(Entity, &mut PhysicsState, &Jumping, Option<&LastSentInput>)>,
     mut commands: Commands,
 ) {
-    for (entity, physics_state, jumping, last_sent_input) in query.iter_mut() {
+    for (entity, mut physics_state, jumping, last_sent_input) in query.iter_mut() {
         let dir = physics_state.move_direction;
         type D = WalkDirection;
         let input = ServerboundPlayerInput {
@@ -266,10 +282,11 @@ pub fn send_player_input_packet(
             left: matches!(dir, D::Left | D::ForwardLeft | D::BackwardLeft),
             right: matches!(dir, D::Right | D::ForwardRight | D::BackwardRight),
             jump: **jumping,
-            // TODO: implement sneaking
-            shift: false,
+            shift: physics_state.trying_to_sneak || physics_state.trying_to_dismount,
             sprint: physics_state.trying_to_sprint,
         };
+        // the server only needs to see shift for one tick to make us dismount
+        physics_state.trying_to_dismount = false;
 
         // if LastSentInput isn't present, we default to assuming we're not pressing any
         // keys and insert it anyways every time it changes
@@ -347,16 +364,36 @@ pub(crate) fn tick_controls(mut query: Query<&mut PhysicsState>) {
 /// automatically by the client.
 pub fn local_player_ai_step(
     mut query: Query<
-        (&PhysicsState, &mut Physics, &mut Sprinting, &mut Attributes),
+        (
+            &PhysicsState,
+            &mut Physics,
+            &mut Sprinting,
+            &mut Attributes,
+            Option<&mut ShiftKeyDown>,
+        ),
         With<InLoadedChunk>,
     >,
 ) {
-    for (physics_state, mut physics, mut sprinting, mut attributes) in query.iter_mut() {
+    for (physics_state, mut physics, mut sprinting, mut attributes, shift_key_down) in
+        query.iter_mut()
+    {
+        if let Some(mut shift_key_down) = shift_key_down {
+            **shift_key_down = physics_state.trying_to_sneak;
+        }
+
         // server ai step
 
-        // TODO: replace those booleans when using items, passengers, and sneaking are
-        // properly implemented
-        let move_vector = modify_input(physics_state.move_vector, false, false, false, &attributes);
+        // TODO: this should also be true while we're crawling
+        let moving_slowly = physics_state.trying_to_sneak;
+        // TODO: replace those booleans when using items and passengers are properly
+        // implemented
+        let move_vector = modify_input(
+            physics_state.move_vector,
+            false,
+            false,
+            moving_slowly,
+            &attributes,
+        );
         physics.x_acceleration = move_vector.x;
         physics.z_acceleration = move_vector.y;
 
@@ -378,6 +415,7 @@ pub fn local_player_ai_step(
                     // && !self.using_item()
                     // && !self.has_effect(MobEffects.BLINDNESS)
                     && trying_to_sprint
+                    && !moving_slowly
             )
         {
             set_sprinting(true, &mut sprinting, &mut attributes);
@@ -473,6 +511,36 @@ impl Client {
             direction,
         });
     }
+
+    /// Get off the entity that we're riding, like a boat or a horse. This does
+    /// nothing if we're not riding anything.
+    pub fn dismount(&self) {
+        let mut ecs = self.ecs.lock();
+        ecs.send_event(DismountEvent {
+            entity: self.entity,
+        });
+    }
+
+    /// Start or stop sneaking. This acts as if you held shift in vanilla, so
+    /// we move slower and won't walk off the edges of blocks.
+    pub fn set_sneaking(&self, sneaking: bool) {
+        let mut ecs = self.ecs.lock();
+        ecs.send_event(SneakEvent {
+            entity: self.entity,
+            sneaking,
+        });
+    }
+
+    /// Start gliding with the elytra that we're wearing. This acts as if you
+    /// pressed jump while in the air in vanilla.
+    ///
+    /// See [`StartFallFlyingEvent`] for when this does nothing.
+    pub fn start_fall_flying(&self) {
+        let mut ecs = self.ecs.lock();
+        ecs.send_event(StartFallFlyingEvent {
+            entity: self.entity,
+        });
+    }
 }
 
 /// An event sent when the client starts walking. This does not get sent for
@@ -522,6 +590,95 @@ pub fn handle_sprint(
     }
 }
 
+/// An event sent when the client wants to get off the entity that it's riding.
+/// The [`Riding`] component gets removed once the server tells us that we
+/// dismounted.
+///
+/// [`Riding`]: azalea_entity::Riding
+#[derive(Event)]
+pub struct DismountEvent {
+    pub entity: Entity,
+}
+/// The system that makes the player press shift for a tick when they receive a
+/// [`DismountEvent`].
+pub fn handle_dismount(
+    mut query: Query<&mut PhysicsState>,
+    mut events: EventReader<DismountEvent>,
+) {
+    for event in events.read() {
+        if let Ok(mut physics_state) = query.get_mut(event.entity) {
+            physics_state.trying_to_dismount = true;
+        }
+    }
+}
+
+/// An event sent when the client starts or stops sneaking.
+#[derive(Event)]
+pub struct SneakEvent {
+    pub entity: Entity,
+    pub sneaking: bool,
+}
+/// The system that makes the player start or stop holding shift when they
+/// receive a [`SneakEvent`].
+pub fn handle_sneak(mut query: Query<&mut PhysicsState>, mut events: EventReader<SneakEvent>) {
+    for event in events.read() {
+        if let Ok(mut physics_state) = query.get_mut(event.entity) {
+            physics_state.trying_to_sneak = event.sneaking;
+        }
+    }
+}
+
+/// An event sent when the client wants to start gliding with an elytra.
+///
+/// This does nothing if we're on the ground, in water, already gliding, or
+/// not wearing an elytra.
+#[derive(Event)]
+pub struct StartFallFlyingEvent {
+    pub entity: Entity,
+}
+/// The system that makes the player start gliding when they receive a
+/// [`StartFallFlyingEvent`].
+pub fn handle_start_fall_flying(
+    mut query: Query<(&MinecraftEntityId, &Physics, &Inventory, &mut FallFlying)>,
+    mut events: EventReader<StartFallFlyingEvent>,
+    mut commands: Commands,
+) {
+    for event in events.read() {
+        let Ok((minecraft_entity_id, physics, inventory, mut fall_flying)) =
+            query.get_mut(event.entity)
+        else {
+            continue;
+        };
+        if **fall_flying
+            || physics.on_ground()
+            || physics.is_in_water()
+            || !is_wearing_elytra(inventory)
+        {
+            continue;
+        }
+
+        // vanilla starts gliding right away instead of waiting for the server to
+        // tell us that we are
+        **fall_flying = true;
+        commands.trigger(SendPacketEvent::new(
+            event.entity,
+            ServerboundPlayerCommand {
+                id: *minecraft_entity_id,
+                action: azalea_protocol::packets::game::s_player_command::Action::StartFallFlying,
+                data: 0,
+            },
+        ));
+    }
+}
+
+fn is_wearing_elytra(inventory: &Inventory) -> bool {
+    let Menu::Player(player) = &inventory.inventory_menu else {
+        return false;
+    };
+    // the armor slots are ordered from head to feet
+    player.armor[1].kind() == azalea_registry::Item::Elytra
+}
+
 /// Change whether we're sprinting by adding an attribute modifier to the
 /// player. You should use the [`walk`] and [`sprint`] methods instead.
 /// Returns if the operation was successful.
@@ -7,8 +7,8 @@ use azalea_core::{
     position::{ChunkPos, Vec3},
 };
 use azalea_entity::{
-    Dead, EntityBundle, EntityKindComponent, LastSentPosition, LoadedBy, LocalEntity,
-    LookDirection, Physics, Position, RelativeEntityUpdate,
+    ActiveEffects, Dead, EntityBundle, EntityKindComponent, LastSentPosition, LoadedBy,
+    LocalEntity, LookDirection, MobEffectData, Physics, Position, RelativeEntityUpdate, Riding,
     indexing::{EntityIdIndex, EntityUuidIndex},
     metadata::{Health, apply_metadata},
 };
@@ -16,7 +16,9 @@ use azalea_protocol::{
     common::movements::MoveFlags,
     packets::{ConnectionProtocol, game::*},
 };
-use azalea_world::{InstanceContainer, InstanceName, MinecraftEntityId, PartialInstance};
+use azalea_world::{
+    InstanceContainer, InstanceName, MinecraftEntityId, PartialInstance, WorldBorder,
+};
 use bevy_ecs::{prelude::*, system::SystemState};
 pub use events::*;
 use tracing::{debug, error, trace, warn};
@@ -765,10 +767,31 @@ impl GamePacketHandler<'_> {
 
     pub fn initialize_border(&mut self, p: &ClientboundInitializeBorder) {
         debug!("Got initialize border packet {p:?}");
+
+        self.update_world_border(|border| {
+            *border = WorldBorder {
+                center_x: p.new_center_x,
+                center_z: p.new_center_z,
+                size: f64::min(p.old_size, p.new_size),
+            };
+        });
+    }
+
+    fn update_world_border(&mut self, f: impl FnOnce(&mut WorldBorder)) {
+        as_system::<Query<&InstanceHolder>>(self.ecs, |mut query| {
+            let instance_holder = query.get_mut(self.player).unwrap();
+            let mut instance = instance_holder.instance.write();
+            f(instance.world_border.get_or_insert_default());
+        });
     }
 
-    pub fn set_time(&mut self, _p: &ClientboundSetTime) {
+    pub fn set_time(&mut self, p: &ClientboundSetTime) {
         // debug!("Got set time packet {p:?}");
+
+        as_system::<Query<&InstanceHolder>>(self.ecs, |mut query| {
+            let instance_holder = query.get_mut(self.player).unwrap();
+            instance_holder.instance.write().day_time = Some(p.day_time);
+        });
     }
 
     pub fn set_default_spawn_position(&mut self, p: &ClientboundSetDefaultSpawnPosition) {
@@ -1116,6 +1139,30 @@ impl GamePacketHandler<'_> {
 
     pub fn update_mob_effect(&mut self, p: &ClientboundUpdateMobEffect) {
         debug!("Got update mob effect packet {p:?}");
+
+        as_system::<(Query<&EntityIdIndex>, Query<&mut ActiveEffects>)>(
+            self.ecs,
+            |(entity_id_index_query, mut effects_query)| {
+                let entity_id_index = entity_id_index_query.get(self.player).unwrap();
+                let Some(entity) = entity_id_index.get_by_minecraft_entity(p.entity_id) else {
+                    debug!(
+                        "Got update mob effect packet for unknown entity id {}",
+                        p.entity_id
+                    );
+                    return;
+                };
+                let Ok(mut effects) = effects_query.get_mut(entity) else {
+                    return;
+                };
+                effects.insert(
+                    p.mob_effect,
+                    MobEffectData {
+                        amplifier: p.effect_amplifier,
+                        duration_ticks: p.effect_duration_ticks,
+                    },
+                );
+            },
+        );
     }
 
     pub fn award_stats(&mut self, _p: &ClientboundAwardStats) {}
@@ -1324,7 +1371,26 @@ impl GamePacketHandler<'_> {
 
     pub fn player_look_at(&mut self, _p: &ClientboundPlayerLookAt) {}
 
-    pub fn remove_mob_effect(&mut self, _p: &ClientboundRemoveMobEffect) {}
+    pub fn remove_mob_effect(&mut self, p: &ClientboundRemoveMobEffect) {
+        debug!("Got remove mob effect packet {p:?}");
+
+        as_system::<(Query<&EntityIdIndex>, Query<&mut ActiveEffects>)>(
+            self.ecs,
+            |(entity_id_index_query, mut effects_query)| {
+                let entity_id_index = entity_id_index_query.get(self.player).unwrap();
+                let Some(entity) = entity_id_index.get_by_minecraft_entity(p.entity_id) else {
+                    debug!(
+                        "Got remove mob effect packet for unknown entity id {}",
+                        p.entity_id
+                    );
+                    return;
+                };
+                if let Ok(mut effects) = effects_query.get_mut(entity) {
+                    effects.remove(p.effect);
+                }
+            },
+        );
+    }
 
     pub fn resource_pack_push(&mut self, p: &ClientboundResourcePackPush) {
         debug!("Got resource pack packet {p:?}");
@@ -1516,15 +1582,59 @@ impl GamePacketHandler<'_> {
 
     pub fn select_advancements_tab(&mut self, _p: &ClientboundSelectAdvancementsTab) {}
     pub fn set_action_bar_text(&mut self, _p: &ClientboundSetActionBarText) {}
-    pub fn set_border_center(&mut self, _p: &ClientboundSetBorderCenter) {}
-    pub fn set_border_lerp_size(&mut self, _p: &ClientboundSetBorderLerpSize) {}
-    pub fn set_border_size(&mut self, _p: &ClientboundSetBorderSize) {}
+    pub fn set_border_center(&mut self, p: &ClientboundSetBorderCenter) {
+        self.update_world_border(|border| {
+            border.center_x = p.new_center_x;
+            border.center_z = p.new_center_z;
+        });
+    }
+    pub fn set_border_lerp_size(&mut self, p: &ClientboundSetBorderLerpSize) {
+        self.update_world_border(|border| border.size = f64::min(p.old_size, p.new_size));
+    }
+    pub fn set_border_size(&mut self, p: &ClientboundSetBorderSize) {
+        self.update_world_border(|border| border.size = p.size);
+    }
     pub fn set_border_warning_delay(&mut self, _p: &ClientboundSetBorderWarningDelay) {}
     pub fn set_border_warning_distance(&mut self, _p: &ClientboundSetBorderWarningDistance) {}
     pub fn set_camera(&mut self, _p: &ClientboundSetCamera) {}
     pub fn set_display_objective(&mut self, _p: &ClientboundSetDisplayObjective) {}
     pub fn set_objective(&mut self, _p: &ClientboundSetObjective) {}
-    pub fn set_passengers(&mut self, _p: &ClientboundSetPassengers) {}
+    pub fn set_passengers(&mut self, p: &ClientboundSetPassengers) {
+        debug!("Got set passengers packet {p:?}");
+
+        as_system::<(Commands, Query<&EntityIdIndex>, Query<(Entity, &Riding)>)>(
+            self.ecs,
+            |(mut commands, entity_id_index_query, riding_query)| {
+                let entity_id_index = entity_id_index_query.get(self.player).unwrap();
+                let Some(vehicle) =
+                    entity_id_index.get_by_minecraft_entity(MinecraftEntityId(p.vehicle as i32))
+                else {
+                    debug!(
+                        "Got set passengers packet for unknown vehicle id {}",
+                        p.vehicle
+                    );
+                    return;
+                };
+                let passengers = p
+                    .passengers
+                    .iter()
+                    .filter_map(|&id| {
+                        entity_id_index.get_by_minecraft_entity(MinecraftEntityId(id as i32))
+                    })
+                    .collect::<Vec<_>>();
+
+                // the packet has every passenger, so anything that isn't in it got off
+                for (entity, riding) in &riding_query {
+                    if **riding == vehicle && !passengers.contains(&entity) {
+                        commands.entity(entity).remove::<Riding>();
+                    }
+                }
+                for passenger in passengers {
+                    commands.entity(passenger).insert(Riding(vehicle));
+                }
+            },
+        );
+    }
     pub fn set_player_team(&mut self, p: &ClientboundSetPlayerTeam) {
         debug!("Got set player team packet {p:?}");
     }
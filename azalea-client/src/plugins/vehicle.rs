@@ -0,0 +1,128 @@
+//! Steering the boats that our clients are riding.
+
+use azalea_core::{position::Vec3, tick::GameTick};
+use azalea_entity::{EntityKindComponent, LocalEntity, LookDirection, Physics, Position, Riding};
+use azalea_physics::{
+    PhysicsSet,
+    boat::{BOAT_PASSENGER_OFFSET, boat_movement, boat_status},
+    collision::collide_with_blocks,
+};
+use azalea_protocol::packets::game::{ServerboundMoveVehicle, ServerboundPaddleBoat};
+use azalea_world::{InstanceContainer, InstanceName};
+use bevy_app::{App, Plugin};
+use bevy_ecs::prelude::*;
+
+use crate::{
+    movement::{PhysicsState, WalkDirection},
+    packet::game::SendPacketEvent,
+};
+
+/// A plugin that moves the boats that our clients are riding, since the
+/// server expects whoever is steering a boat to be the one that moves it.
+pub struct VehiclePlugin;
+impl Plugin for VehiclePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            GameTick,
+            tick_controlled_boats
+                .in_set(PhysicsSet)
+                .after(azalea_physics::travel::travel),
+        );
+    }
+}
+
+/// Paddle the boats that our local players are riding in the direction that
+/// they're walking, and tell the server where the boats moved to.
+///
+/// Boats face wherever their passenger is looking, and our players are kept
+/// sitting in them.
+#[allow(clippy::type_complexity)]
+pub fn tick_controlled_boats(
+    mut riders: Query<
+        (
+            Entity,
+            &Riding,
+            &PhysicsState,
+            &LookDirection,
+            &mut Position,
+            &mut Physics,
+        ),
+        With<LocalEntity>,
+    >,
+    mut vehicles: Query<
+        (
+            &EntityKindComponent,
+            &InstanceName,
+            &mut Position,
+            &mut Physics,
+            &mut LookDirection,
+        ),
+        Without<LocalEntity>,
+    >,
+    instance_container: Res<InstanceContainer>,
+    mut commands: Commands,
+) {
+    for (entity, riding, physics_state, look_direction, mut position, mut physics) in &mut riders {
+        let Ok((kind, instance_name, mut vehicle_position, mut vehicle_physics, mut vehicle_look)) =
+            vehicles.get_mut(riding.0)
+        else {
+            continue;
+        };
+        if !azalea_registry::tags::entities::BOAT.contains(kind) {
+            continue;
+        }
+        let Some(instance) = instance_container.get(instance_name) else {
+            continue;
+        };
+        let instance = instance.read();
+
+        type D = WalkDirection;
+        let dir = physics_state.move_direction;
+        let forward = matches!(dir, D::Forward | D::ForwardLeft | D::ForwardRight);
+        let backward = matches!(dir, D::Backward | D::BackwardLeft | D::BackwardRight);
+
+        vehicle_look.y_rot = look_direction.y_rot;
+        let status = boat_status(&instance, &vehicle_physics);
+        let velocity = boat_movement(
+            vehicle_physics.velocity,
+            vehicle_position.y,
+            vehicle_physics.dimensions.height as f64,
+            vehicle_look.y_rot,
+            status,
+            forward,
+            backward,
+        );
+        let movement = collide_with_blocks(velocity, &vehicle_physics.bounding_box, &instance);
+        // stop going in directions that we ran into something in
+        let keep = |moved: f64, wanted: f64| if moved == wanted { wanted } else { 0. };
+        vehicle_physics.velocity = Vec3 {
+            x: keep(movement.x, velocity.x),
+            y: keep(movement.y, velocity.y),
+            z: keep(movement.z, velocity.z),
+        };
+        **vehicle_position += movement;
+        vehicle_physics.bounding_box = vehicle_physics
+            .dimensions
+            .make_bounding_box(**vehicle_position);
+
+        **position = **vehicle_position;
+        position.y += BOAT_PASSENGER_OFFSET;
+        physics.velocity = Vec3::ZERO;
+        physics.bounding_box = physics.dimensions.make_bounding_box(**position);
+
+        commands.trigger(SendPacketEvent::new(
+            entity,
+            ServerboundPaddleBoat {
+                left: forward,
+                right: forward,
+            },
+        ));
+        commands.trigger(SendPacketEvent::new(
+            entity,
+            ServerboundMoveVehicle {
+                pos: **vehicle_position,
+                look_direction: *vehicle_look,
+            },
+        ));
+    }
+}
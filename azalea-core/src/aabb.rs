@@ -7,6 +7,7 @@ use crate::{
 
 /// A rectangular prism with a starting and ending point.
 #[derive(Copy, Clone, Debug, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct AABB {
     pub min: Vec3,
     pub max: Vec3,
@@ -235,6 +235,16 @@ where
     pub fn set(&mut self, bit_index: usize) {
         self.data[bit_index / 64] |= 1u64 << (bit_index % 64);
     }
+
+    /// Set all `N` bits at once.
+    #[inline]
+    pub fn set_all(&mut self) {
+        self.data = [u64::MAX; bits_to_longs(N)];
+        if N % 64 != 0 {
+            // don't set the bits past the end
+            self.data[N / 64] = (1u64 << (N % 64)) - 1;
+        }
+    }
 }
 impl<const N: usize> Default for FastFixedBitSet<N>
 where
@@ -299,4 +309,17 @@ mod tests {
         assert!(bitset.index(67));
         assert!(bitset.index(68));
     }
+
+    #[test]
+    fn test_fast_set_all() {
+        let mut bitset = FastFixedBitSet::<70>::new();
+        bitset.set_all();
+
+        let mut expected = FastFixedBitSet::<70>::new();
+        for i in 0..70 {
+            expected.set(i);
+        }
+        assert_eq!(bitset, expected);
+        assert!(!bitset.index(70));
+    }
 }
@@ -1,26 +1,48 @@
-// TODO
+use std::collections::HashMap;
 
-// pub struct ActiveEffects(HashMap<azalea_registry::MobEffect, MobEffectData>);
+use azalea_registry::MobEffect;
+use bevy_ecs::component::Component;
 
-/// Returns the level of the given effect, or `None` if the effect is not
-/// active. The lowest level is 0.
-pub fn get_effect(_effect: azalea_registry::MobEffect) -> Option<u32> {
-    // TODO
-    None
+/// The status effects (like potion effects) that are currently active on an
+/// entity.
+///
+/// This is only updated when the server tells us about an effect, so the
+/// durations aren't ticked down.
+#[derive(Component, Clone, Debug, Default, PartialEq)]
+pub struct ActiveEffects(pub HashMap<MobEffect, MobEffectData>);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MobEffectData {
+    /// The level of the effect, where 0 is level I.
+    pub amplifier: u32,
+    /// How many ticks the effect had left when we received it.
+    pub duration_ticks: u32,
 }
 
-pub fn get_dig_speed_amplifier() -> Option<u32> {
-    let effect_plus_one = u32::max(
-        get_effect(azalea_registry::MobEffect::Haste)
-            .map(|x| x + 1)
-            .unwrap_or_default(),
-        get_effect(azalea_registry::MobEffect::ConduitPower)
-            .map(|x| x + 1)
-            .unwrap_or_default(),
-    );
-    if effect_plus_one > 0 {
-        Some(effect_plus_one - 1)
-    } else {
-        None
+impl ActiveEffects {
+    /// Returns the level of the given effect, or `None` if the effect is not
+    /// active. The lowest level is 0.
+    pub fn get_level(&self, effect: MobEffect) -> Option<u32> {
+        self.0.get(&effect).map(|data| data.amplifier)
+    }
+
+    pub fn has(&self, effect: MobEffect) -> bool {
+        self.0.contains_key(&effect)
+    }
+
+    pub fn insert(&mut self, effect: MobEffect, data: MobEffectData) {
+        self.0.insert(effect, data);
+    }
+
+    pub fn remove(&mut self, effect: MobEffect) -> Option<MobEffectData> {
+        self.0.remove(&effect)
+    }
+
+    /// The level of the effect that makes us mine faster, where 0 is level I.
+    /// Conduit Power works the same as Haste for this.
+    pub fn dig_speed_amplifier(&self) -> Option<u32> {
+        let haste = self.get_level(MobEffect::Haste);
+        let conduit_power = self.get_level(MobEffect::ConduitPower);
+        haste.max(conduit_power)
     }
 }
@@ -27,10 +27,11 @@ use azalea_core::{
 };
 use azalea_registry::EntityKind;
 use azalea_world::{ChunkStorage, InstanceName};
-use bevy_ecs::{bundle::Bundle, component::Component};
+use bevy_ecs::{bundle::Bundle, component::Component, entity::Entity};
 pub use data::*;
 use derive_more::{Deref, DerefMut};
 pub use dimensions::EntityDimensions;
+pub use effects::{ActiveEffects, MobEffectData};
 use plugin::indexing::EntityChunkPos;
 use uuid::Uuid;
 use vec_delta_codec::VecDeltaCodec;
@@ -389,6 +390,11 @@ impl Physics {
 #[derive(Component, Copy, Clone, Default)]
 pub struct Dead;
 
+/// A component for entities that are riding another entity, like a boat, horse,
+/// or minecart. The entity that's being ridden is stored here.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Deref)]
+pub struct Riding(pub Entity);
+
 /// A component that contains the offset of the entity's eyes from the entity
 /// coordinates.
 ///
@@ -450,6 +456,7 @@ pub struct EntityBundle {
     pub jumping: Jumping,
     pub fluid_on_eyes: FluidOnEyes,
     pub on_climbable: OnClimbable,
+    pub active_effects: ActiveEffects,
 }
 
 impl EntityBundle {
@@ -483,6 +490,7 @@ impl EntityBundle {
             jumping: Jumping(false),
             fluid_on_eyes: FluidOnEyes(FluidKind::Empty),
             on_climbable: OnClimbable(false),
+            active_effects: ActiveEffects::default(),
         }
     }
 }
@@ -107,7 +107,7 @@ impl Default for AcaciaBoatMetadataBundle {
                         currently_glowing: CurrentlyGlowing(false),
                         invisible: Invisible(false),
                         fall_flying: FallFlying(false),
-                        air_supply: AirSupply(Default::default()),
+                        air_supply: AirSupply(300),
                         custom_name: CustomName(Default::default()),
                         custom_name_visible: CustomNameVisible(Default::default()),
                         silent: Silent(Default::default()),
@@ -164,7 +164,7 @@ impl Default for AcaciaChestBoatMetadataBundle {
                         currently_glowing: CurrentlyGlowing(false),
                         invisible: Invisible(false),
                         fall_flying: FallFlying(false),
-                        air_supply: AirSupply(Default::default()),
+                        air_supply: AirSupply(300),
                         custom_name: CustomName(Default::default()),
                         custom_name_visible: CustomNameVisible(Default::default()),
                         silent: Silent(Default::default()),
@@ -257,7 +257,7 @@ impl Default for AllayMetadataBundle {
                             currently_glowing: CurrentlyGlowing(false),
                             invisible: Invisible(false),
                             fall_flying: FallFlying(false),
-                            air_supply: AirSupply(Default::default()),
+                            air_supply: AirSupply(300),
                             custom_name: CustomName(Default::default()),
                             custom_name_visible: CustomNameVisible(Default::default()),
                             silent: Silent(Default::default()),
@@ -334,7 +334,7 @@ impl Default for AreaEffectCloudMetadataBundle {
                 currently_glowing: CurrentlyGlowing(false),
                 invisible: Invisible(false),
                 fall_flying: FallFlying(false),
-                air_supply: AirSupply(Default::default()),
+                air_supply: AirSupply(300),
                 custom_name: CustomName(Default::default()),
                 custom_name_visible: CustomNameVisible(Default::default()),
                 silent: Silent(Default::default()),
@@ -400,7 +400,7 @@ impl Default for ArmadilloMetadataBundle {
                                     currently_glowing: CurrentlyGlowing(false),
                                     invisible: Invisible(false),
                                     fall_flying: FallFlying(false),
-                                    air_supply: AirSupply(Default::default()),
+                                    air_supply: AirSupply(300),
                                     custom_name: CustomName(Default::default()),
                                     custom_name_visible: CustomNameVisible(Default::default()),
                                     silent: Silent(Default::default()),
@@ -520,7 +520,7 @@ impl Default for ArmorStandMetadataBundle {
                     currently_glowing: CurrentlyGlowing(false),
                     invisible: Invisible(false),
                     fall_flying: FallFlying(false),
-                    air_supply: AirSupply(Default::default()),
+                    air_supply: AirSupply(300),
                     custom_name: CustomName(Default::default()),
                     custom_name_visible: CustomNameVisible(Default::default()),
                     silent: Silent(Default::default()),
@@ -600,7 +600,7 @@ impl Default for ArrowMetadataBundle {
                     currently_glowing: CurrentlyGlowing(false),
                     invisible: Invisible(false),
                     fall_flying: FallFlying(false),
-                    air_supply: AirSupply(Default::default()),
+                    air_supply: AirSupply(300),
                     custom_name: CustomName(Default::default()),
                     custom_name_visible: CustomNameVisible(Default::default()),
                     silent: Silent(Default::default()),
@@ -679,7 +679,7 @@ impl Default for AxolotlMetadataBundle {
                                     currently_glowing: CurrentlyGlowing(false),
                                     invisible: Invisible(false),
                                     fall_flying: FallFlying(false),
-                                    air_supply: AirSupply(Default::default()),
+                                    air_supply: AirSupply(300),
                                     custom_name: CustomName(Default::default()),
                                     custom_name_visible: CustomNameVisible(Default::default()),
                                     silent: Silent(Default::default()),
@@ -748,7 +748,7 @@ impl Default for BambooChestRaftMetadataBundle {
                         currently_glowing: CurrentlyGlowing(false),
                         invisible: Invisible(false),
                         fall_flying: FallFlying(false),
-                        air_supply: AirSupply(Default::default()),
+                        air_supply: AirSupply(300),
                         custom_name: CustomName(Default::default()),
                         custom_name_visible: CustomNameVisible(Default::default()),
                         silent: Silent(Default::default()),
@@ -805,7 +805,7 @@ impl Default for BambooRaftMetadataBundle {
                         currently_glowing: CurrentlyGlowing(false),
                         invisible: Invisible(false),
                         fall_flying: FallFlying(false),
-                        air_supply: AirSupply(Default::default()),
+                        air_supply: AirSupply(300),
                         custom_name: CustomName(Default::default()),
                         custom_name_visible: CustomNameVisible(Default::default()),
                         silent: Silent(Default::default()),
@@ -869,7 +869,7 @@ impl Default for BatMetadataBundle {
                         currently_glowing: CurrentlyGlowing(false),
                         invisible: Invisible(false),
                         fall_flying: FallFlying(false),
-                        air_supply: AirSupply(Default::default()),
+                        air_supply: AirSupply(300),
                         custom_name: CustomName(Default::default()),
                         custom_name_visible: CustomNameVisible(Default::default()),
                         silent: Silent(Default::default()),
@@ -959,7 +959,7 @@ impl Default for BeeMetadataBundle {
                                     currently_glowing: CurrentlyGlowing(false),
                                     invisible: Invisible(false),
                                     fall_flying: FallFlying(false),
-                                    air_supply: AirSupply(Default::default()),
+                                    air_supply: AirSupply(300),
                                     custom_name: CustomName(Default::default()),
                                     custom_name_visible: CustomNameVisible(Default::default()),
                                     silent: Silent(Default::default()),
@@ -1029,7 +1029,7 @@ impl Default for BirchBoatMetadataBundle {
                         currently_glowing: CurrentlyGlowing(false),
                         invisible: Invisible(false),
                         fall_flying: FallFlying(false),
-                        air_supply: AirSupply(Default::default()),
+                        air_supply: AirSupply(300),
                         custom_name: CustomName(Default::default()),
                         custom_name_visible: CustomNameVisible(Default::default()),
                         silent: Silent(Default::default()),
@@ -1086,7 +1086,7 @@ impl Default for BirchChestBoatMetadataBundle {
                         currently_glowing: CurrentlyGlowing(false),
                         invisible: Invisible(false),
                         fall_flying: FallFlying(false),
-                        air_supply: AirSupply(Default::default()),
+                        air_supply: AirSupply(300),
                         custom_name: CustomName(Default::default()),
                         custom_name_visible: CustomNameVisible(Default::default()),
                         silent: Silent(Default::default()),
@@ -1154,7 +1154,7 @@ impl Default for BlazeMetadataBundle {
                                 currently_glowing: CurrentlyGlowing(false),
                                 invisible: Invisible(false),
                                 fall_flying: FallFlying(false),
-                                air_supply: AirSupply(Default::default()),
+                                air_supply: AirSupply(300),
                                 custom_name: CustomName(Default::default()),
                                 custom_name_visible: CustomNameVisible(Default::default()),
                                 silent: Silent(Default::default()),
@@ -1253,7 +1253,7 @@ impl Default for BlockDisplayMetadataBundle {
                     currently_glowing: CurrentlyGlowing(false),
                     invisible: Invisible(false),
                     fall_flying: FallFlying(false),
-                    air_supply: AirSupply(Default::default()),
+                    air_supply: AirSupply(300),
                     custom_name: CustomName(Default::default()),
                     custom_name_visible: CustomNameVisible(Default::default()),
                     silent: Silent(Default::default()),
@@ -1348,7 +1348,7 @@ impl Default for BoggedMetadataBundle {
                                 currently_glowing: CurrentlyGlowing(false),
                                 invisible: Invisible(false),
                                 fall_flying: FallFlying(false),
-                                air_supply: AirSupply(Default::default()),
+                                air_supply: AirSupply(300),
                                 custom_name: CustomName(Default::default()),
                                 custom_name_visible: CustomNameVisible(Default::default()),
                                 silent: Silent(Default::default()),
@@ -1417,7 +1417,7 @@ impl Default for BreezeMetadataBundle {
                                 currently_glowing: CurrentlyGlowing(false),
                                 invisible: Invisible(false),
                                 fall_flying: FallFlying(false),
-                                air_supply: AirSupply(Default::default()),
+                                air_supply: AirSupply(300),
                                 custom_name: CustomName(Default::default()),
                                 custom_name_visible: CustomNameVisible(Default::default()),
                                 silent: Silent(Default::default()),
@@ -1477,7 +1477,7 @@ impl Default for BreezeWindChargeMetadataBundle {
                 currently_glowing: CurrentlyGlowing(false),
                 invisible: Invisible(false),
                 fall_flying: FallFlying(false),
-                air_supply: AirSupply(Default::default()),
+                air_supply: AirSupply(300),
                 custom_name: CustomName(Default::default()),
                 custom_name_visible: CustomNameVisible(Default::default()),
                 silent: Silent(Default::default()),
@@ -1554,7 +1554,7 @@ impl Default for CamelMetadataBundle {
                                         currently_glowing: CurrentlyGlowing(false),
                                         invisible: Invisible(false),
                                         fall_flying: FallFlying(false),
-                                        air_supply: AirSupply(Default::default()),
+                                        air_supply: AirSupply(300),
                                         custom_name: CustomName(Default::default()),
                                         custom_name_visible: CustomNameVisible(Default::default()),
                                         silent: Silent(Default::default()),
@@ -1665,7 +1665,7 @@ impl Default for CatMetadataBundle {
                                         currently_glowing: CurrentlyGlowing(false),
                                         invisible: Invisible(false),
                                         fall_flying: FallFlying(false),
-                                        air_supply: AirSupply(Default::default()),
+                                        air_supply: AirSupply(300),
                                         custom_name: CustomName(Default::default()),
                                         custom_name_visible: CustomNameVisible(Default::default()),
                                         silent: Silent(Default::default()),
@@ -1747,7 +1747,7 @@ impl Default for CaveSpiderMetadataBundle {
                                     currently_glowing: CurrentlyGlowing(false),
                                     invisible: Invisible(false),
                                     fall_flying: FallFlying(false),
-                                    air_supply: AirSupply(Default::default()),
+                                    air_supply: AirSupply(300),
                                     custom_name: CustomName(Default::default()),
                                     custom_name_visible: CustomNameVisible(Default::default()),
                                     silent: Silent(Default::default()),
@@ -1813,7 +1813,7 @@ impl Default for CherryBoatMetadataBundle {
                         currently_glowing: CurrentlyGlowing(false),
                         invisible: Invisible(false),
                         fall_flying: FallFlying(false),
-                        air_supply: AirSupply(Default::default()),
+                        air_supply: AirSupply(300),
                         custom_name: CustomName(Default::default()),
                         custom_name_visible: CustomNameVisible(Default::default()),
                         silent: Silent(Default::default()),
@@ -1870,7 +1870,7 @@ impl Default for CherryChestBoatMetadataBundle {
                         currently_glowing: CurrentlyGlowing(false),
                         invisible: Invisible(false),
                         fall_flying: FallFlying(false),
-                        air_supply: AirSupply(Default::default()),
+                        air_supply: AirSupply(300),
                         custom_name: CustomName(Default::default()),
                         custom_name_visible: CustomNameVisible(Default::default()),
                         silent: Silent(Default::default()),
@@ -1931,7 +1931,7 @@ impl Default for ChestMinecartMetadataBundle {
                         currently_glowing: CurrentlyGlowing(false),
                         invisible: Invisible(false),
                         fall_flying: FallFlying(false),
-                        air_supply: AirSupply(Default::default()),
+                        air_supply: AirSupply(300),
                         custom_name: CustomName(Default::default()),
                         custom_name_visible: CustomNameVisible(Default::default()),
                         silent: Silent(Default::default()),
@@ -1999,7 +1999,7 @@ impl Default for ChickenMetadataBundle {
                                     currently_glowing: CurrentlyGlowing(false),
                                     invisible: Invisible(false),
                                     fall_flying: FallFlying(false),
-                                    air_supply: AirSupply(Default::default()),
+                                    air_supply: AirSupply(300),
                                     custom_name: CustomName(Default::default()),
                                     custom_name_visible: CustomNameVisible(Default::default()),
                                     silent: Silent(Default::default()),
@@ -2072,7 +2072,7 @@ impl Default for CodMetadataBundle {
                                 currently_glowing: CurrentlyGlowing(false),
                                 invisible: Invisible(false),
                                 fall_flying: FallFlying(false),
-                                air_supply: AirSupply(Default::default()),
+                                air_supply: AirSupply(300),
                                 custom_name: CustomName(Default::default()),
                                 custom_name_visible: CustomNameVisible(Default::default()),
                                 silent: Silent(Default::default()),
@@ -2149,7 +2149,7 @@ impl Default for CommandBlockMinecartMetadataBundle {
                         currently_glowing: CurrentlyGlowing(false),
                         invisible: Invisible(false),
                         fall_flying: FallFlying(false),
-                        air_supply: AirSupply(Default::default()),
+                        air_supply: AirSupply(300),
                         custom_name: CustomName(Default::default()),
                         custom_name_visible: CustomNameVisible(Default::default()),
                         silent: Silent(Default::default()),
@@ -2219,7 +2219,7 @@ impl Default for CowMetadataBundle {
                                     currently_glowing: CurrentlyGlowing(false),
                                     invisible: Invisible(false),
                                     fall_flying: FallFlying(false),
-                                    air_supply: AirSupply(Default::default()),
+                                    air_supply: AirSupply(300),
                                     custom_name: CustomName(Default::default()),
                                     custom_name_visible: CustomNameVisible(Default::default()),
                                     silent: Silent(Default::default()),
@@ -2314,7 +2314,7 @@ impl Default for CreakingMetadataBundle {
                                 currently_glowing: CurrentlyGlowing(false),
                                 invisible: Invisible(false),
                                 fall_flying: FallFlying(false),
-                                air_supply: AirSupply(Default::default()),
+                                air_supply: AirSupply(300),
                                 custom_name: CustomName(Default::default()),
                                 custom_name_visible: CustomNameVisible(Default::default()),
                                 silent: Silent(Default::default()),
@@ -2404,7 +2404,7 @@ impl Default for CreeperMetadataBundle {
                                 currently_glowing: CurrentlyGlowing(false),
                                 invisible: Invisible(false),
                                 fall_flying: FallFlying(false),
-                                air_supply: AirSupply(Default::default()),
+                                air_supply: AirSupply(300),
                                 custom_name: CustomName(Default::default()),
                                 custom_name_visible: CustomNameVisible(Default::default()),
                                 silent: Silent(Default::default()),
@@ -2471,7 +2471,7 @@ impl Default for DarkOakBoatMetadataBundle {
                         currently_glowing: CurrentlyGlowing(false),
                         invisible: Invisible(false),
                         fall_flying: FallFlying(false),
-                        air_supply: AirSupply(Default::default()),
+                        air_supply: AirSupply(300),
                         custom_name: CustomName(Default::default()),
                         custom_name_visible: CustomNameVisible(Default::default()),
                         silent: Silent(Default::default()),
@@ -2528,7 +2528,7 @@ impl Default for DarkOakChestBoatMetadataBundle {
                         currently_glowing: CurrentlyGlowing(false),
                         invisible: Invisible(false),
                         fall_flying: FallFlying(false),
-                        air_supply: AirSupply(Default::default()),
+                        air_supply: AirSupply(300),
                         custom_name: CustomName(Default::default()),
                         custom_name_visible: CustomNameVisible(Default::default()),
                         silent: Silent(Default::default()),
@@ -2601,7 +2601,7 @@ impl Default for DolphinMetadataBundle {
                                 currently_glowing: CurrentlyGlowing(false),
                                 invisible: Invisible(false),
                                 fall_flying: FallFlying(false),
-                                air_supply: AirSupply(Default::default()),
+                                air_supply: AirSupply(300),
                                 custom_name: CustomName(Default::default()),
                                 custom_name_visible: CustomNameVisible(Default::default()),
                                 silent: Silent(Default::default()),
@@ -2680,7 +2680,7 @@ impl Default for DonkeyMetadataBundle {
                                             currently_glowing: CurrentlyGlowing(false),
                                             invisible: Invisible(false),
                                             fall_flying: FallFlying(false),
-                                            air_supply: AirSupply(Default::default()),
+                                            air_supply: AirSupply(300),
                                             custom_name: CustomName(Default::default()),
                                             custom_name_visible: CustomNameVisible(
                                                 Default::default(),
@@ -2751,7 +2751,7 @@ impl Default for DragonFireballMetadataBundle {
                 currently_glowing: CurrentlyGlowing(false),
                 invisible: Invisible(false),
                 fall_flying: FallFlying(false),
-                air_supply: AirSupply(Default::default()),
+                air_supply: AirSupply(300),
                 custom_name: CustomName(Default::default()),
                 custom_name_visible: CustomNameVisible(Default::default()),
                 silent: Silent(Default::default()),
@@ -2812,7 +2812,7 @@ impl Default for DrownedMetadataBundle {
                                     currently_glowing: CurrentlyGlowing(false),
                                     invisible: Invisible(false),
                                     fall_flying: FallFlying(false),
-                                    air_supply: AirSupply(Default::default()),
+                                    air_supply: AirSupply(300),
                                     custom_name: CustomName(Default::default()),
                                     custom_name_visible: CustomNameVisible(Default::default()),
                                     silent: Silent(Default::default()),
@@ -2880,7 +2880,7 @@ impl Default for EggMetadataBundle {
                     currently_glowing: CurrentlyGlowing(false),
                     invisible: Invisible(false),
                     fall_flying: FallFlying(false),
-                    air_supply: AirSupply(Default::default()),
+                    air_supply: AirSupply(300),
                     custom_name: CustomName(Default::default()),
                     custom_name_visible: CustomNameVisible(Default::default()),
                     silent: Silent(Default::default()),
@@ -2943,7 +2943,7 @@ impl Default for ElderGuardianMetadataBundle {
                                     currently_glowing: CurrentlyGlowing(false),
                                     invisible: Invisible(false),
                                     fall_flying: FallFlying(false),
-                                    air_supply: AirSupply(Default::default()),
+                                    air_supply: AirSupply(300),
                                     custom_name: CustomName(Default::default()),
                                     custom_name_visible: CustomNameVisible(Default::default()),
                                     silent: Silent(Default::default()),
@@ -3018,7 +3018,7 @@ impl Default for EndCrystalMetadataBundle {
                 currently_glowing: CurrentlyGlowing(false),
                 invisible: Invisible(false),
                 fall_flying: FallFlying(false),
-                air_supply: AirSupply(Default::default()),
+                air_supply: AirSupply(300),
                 custom_name: CustomName(Default::default()),
                 custom_name_visible: CustomNameVisible(Default::default()),
                 silent: Silent(Default::default()),
@@ -3075,7 +3075,7 @@ impl Default for EnderDragonMetadataBundle {
                         currently_glowing: CurrentlyGlowing(false),
                         invisible: Invisible(false),
                         fall_flying: FallFlying(false),
-                        air_supply: AirSupply(Default::default()),
+                        air_supply: AirSupply(300),
                         custom_name: CustomName(Default::default()),
                         custom_name_visible: CustomNameVisible(Default::default()),
                         silent: Silent(Default::default()),
@@ -3136,7 +3136,7 @@ impl Default for EnderPearlMetadataBundle {
                     currently_glowing: CurrentlyGlowing(false),
                     invisible: Invisible(false),
                     fall_flying: FallFlying(false),
-                    air_supply: AirSupply(Default::default()),
+                    air_supply: AirSupply(300),
                     custom_name: CustomName(Default::default()),
                     custom_name_visible: CustomNameVisible(Default::default()),
                     silent: Silent(Default::default()),
@@ -3211,7 +3211,7 @@ impl Default for EndermanMetadataBundle {
                                 currently_glowing: CurrentlyGlowing(false),
                                 invisible: Invisible(false),
                                 fall_flying: FallFlying(false),
-                                air_supply: AirSupply(Default::default()),
+                                air_supply: AirSupply(300),
                                 custom_name: CustomName(Default::default()),
                                 custom_name_visible: CustomNameVisible(Default::default()),
                                 silent: Silent(Default::default()),
@@ -3282,7 +3282,7 @@ impl Default for EndermiteMetadataBundle {
                                 currently_glowing: CurrentlyGlowing(false),
                                 invisible: Invisible(false),
                                 fall_flying: FallFlying(false),
-                                air_supply: AirSupply(Default::default()),
+                                air_supply: AirSupply(300),
                                 custom_name: CustomName(Default::default()),
                                 custom_name_visible: CustomNameVisible(Default::default()),
                                 silent: Silent(Default::default()),
@@ -3358,7 +3358,7 @@ impl Default for EvokerMetadataBundle {
                                         currently_glowing: CurrentlyGlowing(false),
                                         invisible: Invisible(false),
                                         fall_flying: FallFlying(false),
-                                        air_supply: AirSupply(Default::default()),
+                                        air_supply: AirSupply(300),
                                         custom_name: CustomName(Default::default()),
                                         custom_name_visible: CustomNameVisible(Default::default()),
                                         silent: Silent(Default::default()),
@@ -3422,7 +3422,7 @@ impl Default for EvokerFangsMetadataBundle {
                 currently_glowing: CurrentlyGlowing(false),
                 invisible: Invisible(false),
                 fall_flying: FallFlying(false),
-                air_supply: AirSupply(Default::default()),
+                air_supply: AirSupply(300),
                 custom_name: CustomName(Default::default()),
                 custom_name_visible: CustomNameVisible(Default::default()),
                 silent: Silent(Default::default()),
@@ -3469,7 +3469,7 @@ impl Default for ExperienceBottleMetadataBundle {
                     currently_glowing: CurrentlyGlowing(false),
                     invisible: Invisible(false),
                     fall_flying: FallFlying(false),
-                    air_supply: AirSupply(Default::default()),
+                    air_supply: AirSupply(300),
                     custom_name: CustomName(Default::default()),
                     custom_name_visible: CustomNameVisible(Default::default()),
                     silent: Silent(Default::default()),
@@ -3524,7 +3524,7 @@ impl Default for ExperienceOrbMetadataBundle {
                 currently_glowing: CurrentlyGlowing(false),
                 invisible: Invisible(false),
                 fall_flying: FallFlying(false),
-                air_supply: AirSupply(Default::default()),
+                air_supply: AirSupply(300),
                 custom_name: CustomName(Default::default()),
                 custom_name_visible: CustomNameVisible(Default::default()),
                 silent: Silent(Default::default()),
@@ -3576,7 +3576,7 @@ impl Default for EyeOfEnderMetadataBundle {
                 currently_glowing: CurrentlyGlowing(false),
                 invisible: Invisible(false),
                 fall_flying: FallFlying(false),
-                air_supply: AirSupply(Default::default()),
+                air_supply: AirSupply(300),
                 custom_name: CustomName(Default::default()),
                 custom_name_visible: CustomNameVisible(Default::default()),
                 silent: Silent(Default::default()),
@@ -3628,7 +3628,7 @@ impl Default for FallingBlockMetadataBundle {
                 currently_glowing: CurrentlyGlowing(false),
                 invisible: Invisible(false),
                 fall_flying: FallFlying(false),
-                air_supply: AirSupply(Default::default()),
+                air_supply: AirSupply(300),
                 custom_name: CustomName(Default::default()),
                 custom_name_visible: CustomNameVisible(Default::default()),
                 silent: Silent(Default::default()),
@@ -3680,7 +3680,7 @@ impl Default for FireballMetadataBundle {
                 currently_glowing: CurrentlyGlowing(false),
                 invisible: Invisible(false),
                 fall_flying: FallFlying(false),
-                air_supply: AirSupply(Default::default()),
+                air_supply: AirSupply(300),
                 custom_name: CustomName(Default::default()),
                 custom_name_visible: CustomNameVisible(Default::default()),
                 silent: Silent(Default::default()),
@@ -3744,7 +3744,7 @@ impl Default for FireworkRocketMetadataBundle {
                 currently_glowing: CurrentlyGlowing(false),
                 invisible: Invisible(false),
                 fall_flying: FallFlying(false),
-                air_supply: AirSupply(Default::default()),
+                air_supply: AirSupply(300),
                 custom_name: CustomName(Default::default()),
                 custom_name_visible: CustomNameVisible(Default::default()),
                 silent: Silent(Default::default()),
@@ -3804,7 +3804,7 @@ impl Default for FishingBobberMetadataBundle {
                 currently_glowing: CurrentlyGlowing(false),
                 invisible: Invisible(false),
                 fall_flying: FallFlying(false),
-                air_supply: AirSupply(Default::default()),
+                air_supply: AirSupply(300),
                 custom_name: CustomName(Default::default()),
                 custom_name_visible: CustomNameVisible(Default::default()),
                 silent: Silent(Default::default()),
@@ -3910,7 +3910,7 @@ impl Default for FoxMetadataBundle {
                                     currently_glowing: CurrentlyGlowing(false),
                                     invisible: Invisible(false),
                                     fall_flying: FallFlying(false),
-                                    air_supply: AirSupply(Default::default()),
+                                    air_supply: AirSupply(300),
                                     custom_name: CustomName(Default::default()),
                                     custom_name_visible: CustomNameVisible(Default::default()),
                                     silent: Silent(Default::default()),
@@ -4004,7 +4004,7 @@ impl Default for FrogMetadataBundle {
                                     currently_glowing: CurrentlyGlowing(false),
                                     invisible: Invisible(false),
                                     fall_flying: FallFlying(false),
-                                    air_supply: AirSupply(Default::default()),
+                                    air_supply: AirSupply(300),
                                     custom_name: CustomName(Default::default()),
                                     custom_name_visible: CustomNameVisible(Default::default()),
                                     silent: Silent(Default::default()),
@@ -4078,7 +4078,7 @@ impl Default for FurnaceMinecartMetadataBundle {
                         currently_glowing: CurrentlyGlowing(false),
                         invisible: Invisible(false),
                         fall_flying: FallFlying(false),
-                        air_supply: AirSupply(Default::default()),
+                        air_supply: AirSupply(300),
                         custom_name: CustomName(Default::default()),
                         custom_name_visible: CustomNameVisible(Default::default()),
                         silent: Silent(Default::default()),
@@ -4141,7 +4141,7 @@ impl Default for GhastMetadataBundle {
                         currently_glowing: CurrentlyGlowing(false),
                         invisible: Invisible(false),
                         fall_flying: FallFlying(false),
-                        air_supply: AirSupply(Default::default()),
+                        air_supply: AirSupply(300),
                         custom_name: CustomName(Default::default()),
                         custom_name_visible: CustomNameVisible(Default::default()),
                         silent: Silent(Default::default()),
@@ -4208,7 +4208,7 @@ impl Default for GiantMetadataBundle {
                                 currently_glowing: CurrentlyGlowing(false),
                                 invisible: Invisible(false),
                                 fall_flying: FallFlying(false),
-                                air_supply: AirSupply(Default::default()),
+                                air_supply: AirSupply(300),
                                 custom_name: CustomName(Default::default()),
                                 custom_name_visible: CustomNameVisible(Default::default()),
                                 silent: Silent(Default::default()),
@@ -4276,7 +4276,7 @@ impl Default for GlowItemFrameMetadataBundle {
                     currently_glowing: CurrentlyGlowing(false),
                     invisible: Invisible(false),
                     fall_flying: FallFlying(false),
-                    air_supply: AirSupply(Default::default()),
+                    air_supply: AirSupply(300),
                     custom_name: CustomName(Default::default()),
                     custom_name_visible: CustomNameVisible(Default::default()),
                     silent: Silent(Default::default()),
@@ -4341,7 +4341,7 @@ impl Default for GlowSquidMetadataBundle {
                                     currently_glowing: CurrentlyGlowing(false),
                                     invisible: Invisible(false),
                                     fall_flying: FallFlying(false),
-                                    air_supply: AirSupply(Default::default()),
+                                    air_supply: AirSupply(300),
                                     custom_name: CustomName(Default::default()),
                                     custom_name_visible: CustomNameVisible(Default::default()),
                                     silent: Silent(Default::default()),
@@ -4432,7 +4432,7 @@ impl Default for GoatMetadataBundle {
                                     currently_glowing: CurrentlyGlowing(false),
                                     invisible: Invisible(false),
                                     fall_flying: FallFlying(false),
-                                    air_supply: AirSupply(Default::default()),
+                                    air_supply: AirSupply(300),
                                     custom_name: CustomName(Default::default()),
                                     custom_name_visible: CustomNameVisible(Default::default()),
                                     silent: Silent(Default::default()),
@@ -4513,7 +4513,7 @@ impl Default for GuardianMetadataBundle {
                                 currently_glowing: CurrentlyGlowing(false),
                                 invisible: Invisible(false),
                                 fall_flying: FallFlying(false),
-                                air_supply: AirSupply(Default::default()),
+                                air_supply: AirSupply(300),
                                 custom_name: CustomName(Default::default()),
                                 custom_name_visible: CustomNameVisible(Default::default()),
                                 silent: Silent(Default::default()),
@@ -4597,7 +4597,7 @@ impl Default for HappyGhastMetadataBundle {
                                     currently_glowing: CurrentlyGlowing(false),
                                     invisible: Invisible(false),
                                     fall_flying: FallFlying(false),
-                                    air_supply: AirSupply(Default::default()),
+                                    air_supply: AirSupply(300),
                                     custom_name: CustomName(Default::default()),
                                     custom_name_visible: CustomNameVisible(Default::default()),
                                     silent: Silent(Default::default()),
@@ -4677,7 +4677,7 @@ impl Default for HoglinMetadataBundle {
                                     currently_glowing: CurrentlyGlowing(false),
                                     invisible: Invisible(false),
                                     fall_flying: FallFlying(false),
-                                    air_supply: AirSupply(Default::default()),
+                                    air_supply: AirSupply(300),
                                     custom_name: CustomName(Default::default()),
                                     custom_name_visible: CustomNameVisible(Default::default()),
                                     silent: Silent(Default::default()),
@@ -4744,7 +4744,7 @@ impl Default for HopperMinecartMetadataBundle {
                         currently_glowing: CurrentlyGlowing(false),
                         invisible: Invisible(false),
                         fall_flying: FallFlying(false),
-                        air_supply: AirSupply(Default::default()),
+                        air_supply: AirSupply(300),
                         custom_name: CustomName(Default::default()),
                         custom_name_visible: CustomNameVisible(Default::default()),
                         silent: Silent(Default::default()),
@@ -4814,7 +4814,7 @@ impl Default for HorseMetadataBundle {
                                         currently_glowing: CurrentlyGlowing(false),
                                         invisible: Invisible(false),
                                         fall_flying: FallFlying(false),
-                                        air_supply: AirSupply(Default::default()),
+                                        air_supply: AirSupply(300),
                                         custom_name: CustomName(Default::default()),
                                         custom_name_visible: CustomNameVisible(Default::default()),
                                         silent: Silent(Default::default()),
@@ -4892,7 +4892,7 @@ impl Default for HuskMetadataBundle {
                                     currently_glowing: CurrentlyGlowing(false),
                                     invisible: Invisible(false),
                                     fall_flying: FallFlying(false),
-                                    air_supply: AirSupply(Default::default()),
+                                    air_supply: AirSupply(300),
                                     custom_name: CustomName(Default::default()),
                                     custom_name_visible: CustomNameVisible(Default::default()),
                                     silent: Silent(Default::default()),
@@ -4968,7 +4968,7 @@ impl Default for IllusionerMetadataBundle {
                                         currently_glowing: CurrentlyGlowing(false),
                                         invisible: Invisible(false),
                                         fall_flying: FallFlying(false),
-                                        air_supply: AirSupply(Default::default()),
+                                        air_supply: AirSupply(300),
                                         custom_name: CustomName(Default::default()),
                                         custom_name_visible: CustomNameVisible(Default::default()),
                                         silent: Silent(Default::default()),
@@ -5050,7 +5050,7 @@ impl Default for InteractionMetadataBundle {
                 currently_glowing: CurrentlyGlowing(false),
                 invisible: Invisible(false),
                 fall_flying: FallFlying(false),
-                air_supply: AirSupply(Default::default()),
+                air_supply: AirSupply(300),
                 custom_name: CustomName(Default::default()),
                 custom_name_visible: CustomNameVisible(Default::default()),
                 silent: Silent(Default::default()),
@@ -5111,7 +5111,7 @@ impl Default for IronGolemMetadataBundle {
                             currently_glowing: CurrentlyGlowing(false),
                             invisible: Invisible(false),
                             fall_flying: FallFlying(false),
-                            air_supply: AirSupply(Default::default()),
+                            air_supply: AirSupply(300),
                             custom_name: CustomName(Default::default()),
                             custom_name_visible: CustomNameVisible(Default::default()),
                             silent: Silent(Default::default()),
@@ -5177,7 +5177,7 @@ impl Default for ItemMetadataBundle {
                 currently_glowing: CurrentlyGlowing(false),
                 invisible: Invisible(false),
                 fall_flying: FallFlying(false),
-                air_supply: AirSupply(Default::default()),
+                air_supply: AirSupply(300),
                 custom_name: CustomName(Default::default()),
                 custom_name_visible: CustomNameVisible(Default::default()),
                 silent: Silent(Default::default()),
@@ -5237,7 +5237,7 @@ impl Default for ItemDisplayMetadataBundle {
                     currently_glowing: CurrentlyGlowing(false),
                     invisible: Invisible(false),
                     fall_flying: FallFlying(false),
-                    air_supply: AirSupply(Default::default()),
+                    air_supply: AirSupply(300),
                     custom_name: CustomName(Default::default()),
                     custom_name_visible: CustomNameVisible(Default::default()),
                     silent: Silent(Default::default()),
@@ -5331,7 +5331,7 @@ impl Default for ItemFrameMetadataBundle {
                 currently_glowing: CurrentlyGlowing(false),
                 invisible: Invisible(false),
                 fall_flying: FallFlying(false),
-                air_supply: AirSupply(Default::default()),
+                air_supply: AirSupply(300),
                 custom_name: CustomName(Default::default()),
                 custom_name_visible: CustomNameVisible(Default::default()),
                 silent: Silent(Default::default()),
@@ -5383,7 +5383,7 @@ impl Default for JungleBoatMetadataBundle {
                         currently_glowing: CurrentlyGlowing(false),
                         invisible: Invisible(false),
                         fall_flying: FallFlying(false),
-                        air_supply: AirSupply(Default::default()),
+                        air_supply: AirSupply(300),
                         custom_name: CustomName(Default::default()),
                         custom_name_visible: CustomNameVisible(Default::default()),
                         silent: Silent(Default::default()),
@@ -5440,7 +5440,7 @@ impl Default for JungleChestBoatMetadataBundle {
                         currently_glowing: CurrentlyGlowing(false),
                         invisible: Invisible(false),
                         fall_flying: FallFlying(false),
-                        air_supply: AirSupply(Default::default()),
+                        air_supply: AirSupply(300),
                         custom_name: CustomName(Default::default()),
                         custom_name_visible: CustomNameVisible(Default::default()),
                         silent: Silent(Default::default()),
@@ -5493,7 +5493,7 @@ impl Default for LeashKnotMetadataBundle {
                 currently_glowing: CurrentlyGlowing(false),
                 invisible: Invisible(false),
                 fall_flying: FallFlying(false),
-                air_supply: AirSupply(Default::default()),
+                air_supply: AirSupply(300),
                 custom_name: CustomName(Default::default()),
                 custom_name_visible: CustomNameVisible(Default::default()),
                 silent: Silent(Default::default()),
@@ -5538,7 +5538,7 @@ impl Default for LightningBoltMetadataBundle {
                 currently_glowing: CurrentlyGlowing(false),
                 invisible: Invisible(false),
                 fall_flying: FallFlying(false),
-                air_supply: AirSupply(Default::default()),
+                air_supply: AirSupply(300),
                 custom_name: CustomName(Default::default()),
                 custom_name_visible: CustomNameVisible(Default::default()),
                 silent: Silent(Default::default()),
@@ -5585,7 +5585,7 @@ impl Default for LingeringPotionMetadataBundle {
                     currently_glowing: CurrentlyGlowing(false),
                     invisible: Invisible(false),
                     fall_flying: FallFlying(false),
-                    air_supply: AirSupply(Default::default()),
+                    air_supply: AirSupply(300),
                     custom_name: CustomName(Default::default()),
                     custom_name_visible: CustomNameVisible(Default::default()),
                     silent: Silent(Default::default()),
@@ -5660,7 +5660,7 @@ impl Default for LlamaMetadataBundle {
                                             currently_glowing: CurrentlyGlowing(false),
                                             invisible: Invisible(false),
                                             fall_flying: FallFlying(false),
-                                            air_supply: AirSupply(Default::default()),
+                                            air_supply: AirSupply(300),
                                             custom_name: CustomName(Default::default()),
                                             custom_name_visible: CustomNameVisible(
                                                 Default::default(),
@@ -5733,7 +5733,7 @@ impl Default for LlamaSpitMetadataBundle {
                 currently_glowing: CurrentlyGlowing(false),
                 invisible: Invisible(false),
                 fall_flying: FallFlying(false),
-                air_supply: AirSupply(Default::default()),
+                air_supply: AirSupply(300),
                 custom_name: CustomName(Default::default()),
                 custom_name_visible: CustomNameVisible(Default::default()),
                 silent: Silent(Default::default()),
@@ -5786,7 +5786,7 @@ impl Default for MagmaCubeMetadataBundle {
                             currently_glowing: CurrentlyGlowing(false),
                             invisible: Invisible(false),
                             fall_flying: FallFlying(false),
-                            air_supply: AirSupply(Default::default()),
+                            air_supply: AirSupply(300),
                             custom_name: CustomName(Default::default()),
                             custom_name_visible: CustomNameVisible(Default::default()),
                             silent: Silent(Default::default()),
@@ -5850,7 +5850,7 @@ impl Default for MangroveBoatMetadataBundle {
                         currently_glowing: CurrentlyGlowing(false),
                         invisible: Invisible(false),
                         fall_flying: FallFlying(false),
-                        air_supply: AirSupply(Default::default()),
+                        air_supply: AirSupply(300),
                         custom_name: CustomName(Default::default()),
                         custom_name_visible: CustomNameVisible(Default::default()),
                         silent: Silent(Default::default()),
@@ -5907,7 +5907,7 @@ impl Default for MangroveChestBoatMetadataBundle {
                         currently_glowing: CurrentlyGlowing(false),
                         invisible: Invisible(false),
                         fall_flying: FallFlying(false),
-                        air_supply: AirSupply(Default::default()),
+                        air_supply: AirSupply(300),
                         custom_name: CustomName(Default::default()),
                         custom_name_visible: CustomNameVisible(Default::default()),
                         silent: Silent(Default::default()),
@@ -5960,7 +5960,7 @@ impl Default for MarkerMetadataBundle {
                 currently_glowing: CurrentlyGlowing(false),
                 invisible: Invisible(false),
                 fall_flying: FallFlying(false),
-                air_supply: AirSupply(Default::default()),
+                air_supply: AirSupply(300),
                 custom_name: CustomName(Default::default()),
                 custom_name_visible: CustomNameVisible(Default::default()),
                 silent: Silent(Default::default()),
@@ -6009,7 +6009,7 @@ impl Default for MinecartMetadataBundle {
                         currently_glowing: CurrentlyGlowing(false),
                         invisible: Invisible(false),
                         fall_flying: FallFlying(false),
-                        air_supply: AirSupply(Default::default()),
+                        air_supply: AirSupply(300),
                         custom_name: CustomName(Default::default()),
                         custom_name_visible: CustomNameVisible(Default::default()),
                         silent: Silent(Default::default()),
@@ -6077,7 +6077,7 @@ impl Default for MooshroomMetadataBundle {
                                     currently_glowing: CurrentlyGlowing(false),
                                     invisible: Invisible(false),
                                     fall_flying: FallFlying(false),
-                                    air_supply: AirSupply(Default::default()),
+                                    air_supply: AirSupply(300),
                                     custom_name: CustomName(Default::default()),
                                     custom_name_visible: CustomNameVisible(Default::default()),
                                     silent: Silent(Default::default()),
@@ -6154,7 +6154,7 @@ impl Default for MuleMetadataBundle {
                                             currently_glowing: CurrentlyGlowing(false),
                                             invisible: Invisible(false),
                                             fall_flying: FallFlying(false),
-                                            air_supply: AirSupply(Default::default()),
+                                            air_supply: AirSupply(300),
                                             custom_name: CustomName(Default::default()),
                                             custom_name_visible: CustomNameVisible(
                                                 Default::default(),
@@ -6229,7 +6229,7 @@ impl Default for OakBoatMetadataBundle {
                         currently_glowing: CurrentlyGlowing(false),
                         invisible: Invisible(false),
                         fall_flying: FallFlying(false),
-                        air_supply: AirSupply(Default::default()),
+                        air_supply: AirSupply(300),
                         custom_name: CustomName(Default::default()),
                         custom_name_visible: CustomNameVisible(Default::default()),
                         silent: Silent(Default::default()),
@@ -6286,7 +6286,7 @@ impl Default for OakChestBoatMetadataBundle {
                         currently_glowing: CurrentlyGlowing(false),
                         invisible: Invisible(false),
                         fall_flying: FallFlying(false),
-                        air_supply: AirSupply(Default::default()),
+                        air_supply: AirSupply(300),
                         custom_name: CustomName(Default::default()),
                         custom_name_visible: CustomNameVisible(Default::default()),
                         silent: Silent(Default::default()),
@@ -6355,7 +6355,7 @@ impl Default for OcelotMetadataBundle {
                                     currently_glowing: CurrentlyGlowing(false),
                                     invisible: Invisible(false),
                                     fall_flying: FallFlying(false),
-                                    air_supply: AirSupply(Default::default()),
+                                    air_supply: AirSupply(300),
                                     custom_name: CustomName(Default::default()),
                                     custom_name_visible: CustomNameVisible(Default::default()),
                                     silent: Silent(Default::default()),
@@ -6424,7 +6424,7 @@ impl Default for OminousItemSpawnerMetadataBundle {
                 currently_glowing: CurrentlyGlowing(false),
                 invisible: Invisible(false),
                 fall_flying: FallFlying(false),
-                air_supply: AirSupply(Default::default()),
+                air_supply: AirSupply(300),
                 custom_name: CustomName(Default::default()),
                 custom_name_visible: CustomNameVisible(Default::default()),
                 silent: Silent(Default::default()),
@@ -6482,7 +6482,7 @@ impl Default for PaintingMetadataBundle {
                 currently_glowing: CurrentlyGlowing(false),
                 invisible: Invisible(false),
                 fall_flying: FallFlying(false),
-                air_supply: AirSupply(Default::default()),
+                air_supply: AirSupply(300),
                 custom_name: CustomName(Default::default()),
                 custom_name_visible: CustomNameVisible(Default::default()),
                 silent: Silent(Default::default()),
@@ -6533,7 +6533,7 @@ impl Default for PaleOakBoatMetadataBundle {
                         currently_glowing: CurrentlyGlowing(false),
                         invisible: Invisible(false),
                         fall_flying: FallFlying(false),
-                        air_supply: AirSupply(Default::default()),
+                        air_supply: AirSupply(300),
                         custom_name: CustomName(Default::default()),
                         custom_name_visible: CustomNameVisible(Default::default()),
                         silent: Silent(Default::default()),
@@ -6590,7 +6590,7 @@ impl Default for PaleOakChestBoatMetadataBundle {
                         currently_glowing: CurrentlyGlowing(false),
                         invisible: Invisible(false),
                         fall_flying: FallFlying(false),
-                        air_supply: AirSupply(Default::default()),
+                        air_supply: AirSupply(300),
                         custom_name: CustomName(Default::default()),
                         custom_name_visible: CustomNameVisible(Default::default()),
                         silent: Silent(Default::default()),
@@ -6702,7 +6702,7 @@ impl Default for PandaMetadataBundle {
                                     currently_glowing: CurrentlyGlowing(false),
                                     invisible: Invisible(false),
                                     fall_flying: FallFlying(false),
-                                    air_supply: AirSupply(Default::default()),
+                                    air_supply: AirSupply(300),
                                     custom_name: CustomName(Default::default()),
                                     custom_name_visible: CustomNameVisible(Default::default()),
                                     silent: Silent(Default::default()),
@@ -6791,7 +6791,7 @@ impl Default for ParrotMetadataBundle {
                                         currently_glowing: CurrentlyGlowing(false),
                                         invisible: Invisible(false),
                                         fall_flying: FallFlying(false),
-                                        air_supply: AirSupply(Default::default()),
+                                        air_supply: AirSupply(300),
                                         custom_name: CustomName(Default::default()),
                                         custom_name_visible: CustomNameVisible(Default::default()),
                                         silent: Silent(Default::default()),
@@ -6868,7 +6868,7 @@ impl Default for PhantomMetadataBundle {
                         currently_glowing: CurrentlyGlowing(false),
                         invisible: Invisible(false),
                         fall_flying: FallFlying(false),
-                        air_supply: AirSupply(Default::default()),
+                        air_supply: AirSupply(300),
                         custom_name: CustomName(Default::default()),
                         custom_name_visible: CustomNameVisible(Default::default()),
                         silent: Silent(Default::default()),
@@ -6949,7 +6949,7 @@ impl Default for PigMetadataBundle {
                                     currently_glowing: CurrentlyGlowing(false),
                                     invisible: Invisible(false),
                                     fall_flying: FallFlying(false),
-                                    air_supply: AirSupply(Default::default()),
+                                    air_supply: AirSupply(300),
                                     custom_name: CustomName(Default::default()),
                                     custom_name_visible: CustomNameVisible(Default::default()),
                                     silent: Silent(Default::default()),
@@ -7043,7 +7043,7 @@ impl Default for PiglinMetadataBundle {
                                     currently_glowing: CurrentlyGlowing(false),
                                     invisible: Invisible(false),
                                     fall_flying: FallFlying(false),
-                                    air_supply: AirSupply(Default::default()),
+                                    air_supply: AirSupply(300),
                                     custom_name: CustomName(Default::default()),
                                     custom_name_visible: CustomNameVisible(Default::default()),
                                     silent: Silent(Default::default()),
@@ -7118,7 +7118,7 @@ impl Default for PiglinBruteMetadataBundle {
                                     currently_glowing: CurrentlyGlowing(false),
                                     invisible: Invisible(false),
                                     fall_flying: FallFlying(false),
-                                    air_supply: AirSupply(Default::default()),
+                                    air_supply: AirSupply(300),
                                     custom_name: CustomName(Default::default()),
                                     custom_name_visible: CustomNameVisible(Default::default()),
                                     silent: Silent(Default::default()),
@@ -7196,7 +7196,7 @@ impl Default for PillagerMetadataBundle {
                                     currently_glowing: CurrentlyGlowing(false),
                                     invisible: Invisible(false),
                                     fall_flying: FallFlying(false),
-                                    air_supply: AirSupply(Default::default()),
+                                    air_supply: AirSupply(300),
                                     custom_name: CustomName(Default::default()),
                                     custom_name_visible: CustomNameVisible(Default::default()),
                                     silent: Silent(Default::default()),
@@ -7297,7 +7297,7 @@ impl Default for PlayerMetadataBundle {
                     currently_glowing: CurrentlyGlowing(false),
                     invisible: Invisible(false),
                     fall_flying: FallFlying(false),
-                    air_supply: AirSupply(Default::default()),
+                    air_supply: AirSupply(300),
                     custom_name: CustomName(Default::default()),
                     custom_name_visible: CustomNameVisible(Default::default()),
                     silent: Silent(Default::default()),
@@ -7373,7 +7373,7 @@ impl Default for PolarBearMetadataBundle {
                                     currently_glowing: CurrentlyGlowing(false),
                                     invisible: Invisible(false),
                                     fall_flying: FallFlying(false),
-                                    air_supply: AirSupply(Default::default()),
+                                    air_supply: AirSupply(300),
                                     custom_name: CustomName(Default::default()),
                                     custom_name_visible: CustomNameVisible(Default::default()),
                                     silent: Silent(Default::default()),
@@ -7454,7 +7454,7 @@ impl Default for PufferfishMetadataBundle {
                             currently_glowing: CurrentlyGlowing(false),
                             invisible: Invisible(false),
                             fall_flying: FallFlying(false),
-                            air_supply: AirSupply(Default::default()),
+                            air_supply: AirSupply(300),
                             custom_name: CustomName(Default::default()),
                             custom_name_visible: CustomNameVisible(Default::default()),
                             silent: Silent(Default::default()),
@@ -7531,7 +7531,7 @@ impl Default for RabbitMetadataBundle {
                                     currently_glowing: CurrentlyGlowing(false),
                                     invisible: Invisible(false),
                                     fall_flying: FallFlying(false),
-                                    air_supply: AirSupply(Default::default()),
+                                    air_supply: AirSupply(300),
                                     custom_name: CustomName(Default::default()),
                                     custom_name_visible: CustomNameVisible(Default::default()),
                                     silent: Silent(Default::default()),
@@ -7604,7 +7604,7 @@ impl Default for RavagerMetadataBundle {
                                     currently_glowing: CurrentlyGlowing(false),
                                     invisible: Invisible(false),
                                     fall_flying: FallFlying(false),
-                                    air_supply: AirSupply(Default::default()),
+                                    air_supply: AirSupply(300),
                                     custom_name: CustomName(Default::default()),
                                     custom_name_visible: CustomNameVisible(Default::default()),
                                     silent: Silent(Default::default()),
@@ -7680,7 +7680,7 @@ impl Default for SalmonMetadataBundle {
                                 currently_glowing: CurrentlyGlowing(false),
                                 invisible: Invisible(false),
                                 fall_flying: FallFlying(false),
-                                air_supply: AirSupply(Default::default()),
+                                air_supply: AirSupply(300),
                                 custom_name: CustomName(Default::default()),
                                 custom_name_visible: CustomNameVisible(Default::default()),
                                 silent: Silent(Default::default()),
@@ -7759,7 +7759,7 @@ impl Default for SheepMetadataBundle {
                                     currently_glowing: CurrentlyGlowing(false),
                                     invisible: Invisible(false),
                                     fall_flying: FallFlying(false),
-                                    air_supply: AirSupply(Default::default()),
+                                    air_supply: AirSupply(300),
                                     custom_name: CustomName(Default::default()),
                                     custom_name_visible: CustomNameVisible(Default::default()),
                                     silent: Silent(Default::default()),
@@ -7846,7 +7846,7 @@ impl Default for ShulkerMetadataBundle {
                             currently_glowing: CurrentlyGlowing(false),
                             invisible: Invisible(false),
                             fall_flying: FallFlying(false),
-                            air_supply: AirSupply(Default::default()),
+                            air_supply: AirSupply(300),
                             custom_name: CustomName(Default::default()),
                             custom_name_visible: CustomNameVisible(Default::default()),
                             silent: Silent(Default::default()),
@@ -7908,7 +7908,7 @@ impl Default for ShulkerBulletMetadataBundle {
                 currently_glowing: CurrentlyGlowing(false),
                 invisible: Invisible(false),
                 fall_flying: FallFlying(false),
-                air_supply: AirSupply(Default::default()),
+                air_supply: AirSupply(300),
                 custom_name: CustomName(Default::default()),
                 custom_name_visible: CustomNameVisible(Default::default()),
                 silent: Silent(Default::default()),
@@ -7961,7 +7961,7 @@ impl Default for SilverfishMetadataBundle {
                                 currently_glowing: CurrentlyGlowing(false),
                                 invisible: Invisible(false),
                                 fall_flying: FallFlying(false),
-                                air_supply: AirSupply(Default::default()),
+                                air_supply: AirSupply(300),
                                 custom_name: CustomName(Default::default()),
                                 custom_name_visible: CustomNameVisible(Default::default()),
                                 silent: Silent(Default::default()),
@@ -8035,7 +8035,7 @@ impl Default for SkeletonMetadataBundle {
                                 currently_glowing: CurrentlyGlowing(false),
                                 invisible: Invisible(false),
                                 fall_flying: FallFlying(false),
-                                air_supply: AirSupply(Default::default()),
+                                air_supply: AirSupply(300),
                                 custom_name: CustomName(Default::default()),
                                 custom_name_visible: CustomNameVisible(Default::default()),
                                 silent: Silent(Default::default()),
@@ -8108,7 +8108,7 @@ impl Default for SkeletonHorseMetadataBundle {
                                         currently_glowing: CurrentlyGlowing(false),
                                         invisible: Invisible(false),
                                         fall_flying: FallFlying(false),
-                                        air_supply: AirSupply(Default::default()),
+                                        air_supply: AirSupply(300),
                                         custom_name: CustomName(Default::default()),
                                         custom_name_visible: CustomNameVisible(Default::default()),
                                         silent: Silent(Default::default()),
@@ -8183,7 +8183,7 @@ impl Default for SlimeMetadataBundle {
                         currently_glowing: CurrentlyGlowing(false),
                         invisible: Invisible(false),
                         fall_flying: FallFlying(false),
-                        air_supply: AirSupply(Default::default()),
+                        air_supply: AirSupply(300),
                         custom_name: CustomName(Default::default()),
                         custom_name_visible: CustomNameVisible(Default::default()),
                         silent: Silent(Default::default()),
@@ -8248,7 +8248,7 @@ impl Default for SmallFireballMetadataBundle {
                 currently_glowing: CurrentlyGlowing(false),
                 invisible: Invisible(false),
                 fall_flying: FallFlying(false),
-                air_supply: AirSupply(Default::default()),
+                air_supply: AirSupply(300),
                 custom_name: CustomName(Default::default()),
                 custom_name_visible: CustomNameVisible(Default::default()),
                 silent: Silent(Default::default()),
@@ -8316,7 +8316,7 @@ impl Default for SnifferMetadataBundle {
                                     currently_glowing: CurrentlyGlowing(false),
                                     invisible: Invisible(false),
                                     fall_flying: FallFlying(false),
-                                    air_supply: AirSupply(Default::default()),
+                                    air_supply: AirSupply(300),
                                     custom_name: CustomName(Default::default()),
                                     custom_name_visible: CustomNameVisible(Default::default()),
                                     silent: Silent(Default::default()),
@@ -8393,7 +8393,7 @@ impl Default for SnowGolemMetadataBundle {
                             currently_glowing: CurrentlyGlowing(false),
                             invisible: Invisible(false),
                             fall_flying: FallFlying(false),
-                            air_supply: AirSupply(Default::default()),
+                            air_supply: AirSupply(300),
                             custom_name: CustomName(Default::default()),
                             custom_name_visible: CustomNameVisible(Default::default()),
                             silent: Silent(Default::default()),
@@ -8455,7 +8455,7 @@ impl Default for SnowballMetadataBundle {
                     currently_glowing: CurrentlyGlowing(false),
                     invisible: Invisible(false),
                     fall_flying: FallFlying(false),
-                    air_supply: AirSupply(Default::default()),
+                    air_supply: AirSupply(300),
                     custom_name: CustomName(Default::default()),
                     custom_name_visible: CustomNameVisible(Default::default()),
                     silent: Silent(Default::default()),
@@ -8508,7 +8508,7 @@ impl Default for SpawnerMinecartMetadataBundle {
                         currently_glowing: CurrentlyGlowing(false),
                         invisible: Invisible(false),
                         fall_flying: FallFlying(false),
-                        air_supply: AirSupply(Default::default()),
+                        air_supply: AirSupply(300),
                         custom_name: CustomName(Default::default()),
                         custom_name_visible: CustomNameVisible(Default::default()),
                         silent: Silent(Default::default()),
@@ -8562,7 +8562,7 @@ impl Default for SpectralArrowMetadataBundle {
                     currently_glowing: CurrentlyGlowing(false),
                     invisible: Invisible(false),
                     fall_flying: FallFlying(false),
-                    air_supply: AirSupply(Default::default()),
+                    air_supply: AirSupply(300),
                     custom_name: CustomName(Default::default()),
                     custom_name_visible: CustomNameVisible(Default::default()),
                     silent: Silent(Default::default()),
@@ -8625,7 +8625,7 @@ impl Default for SpiderMetadataBundle {
                                 currently_glowing: CurrentlyGlowing(false),
                                 invisible: Invisible(false),
                                 fall_flying: FallFlying(false),
-                                air_supply: AirSupply(Default::default()),
+                                air_supply: AirSupply(300),
                                 custom_name: CustomName(Default::default()),
                                 custom_name_visible: CustomNameVisible(Default::default()),
                                 silent: Silent(Default::default()),
@@ -8688,7 +8688,7 @@ impl Default for SplashPotionMetadataBundle {
                     currently_glowing: CurrentlyGlowing(false),
                     invisible: Invisible(false),
                     fall_flying: FallFlying(false),
-                    air_supply: AirSupply(Default::default()),
+                    air_supply: AirSupply(300),
                     custom_name: CustomName(Default::default()),
                     custom_name_visible: CustomNameVisible(Default::default()),
                     silent: Silent(Default::default()),
@@ -8741,7 +8741,7 @@ impl Default for SpruceBoatMetadataBundle {
                         currently_glowing: CurrentlyGlowing(false),
                         invisible: Invisible(false),
                         fall_flying: FallFlying(false),
-                        air_supply: AirSupply(Default::default()),
+                        air_supply: AirSupply(300),
                         custom_name: CustomName(Default::default()),
                         custom_name_visible: CustomNameVisible(Default::default()),
                         silent: Silent(Default::default()),
@@ -8798,7 +8798,7 @@ impl Default for SpruceChestBoatMetadataBundle {
                         currently_glowing: CurrentlyGlowing(false),
                         invisible: Invisible(false),
                         fall_flying: FallFlying(false),
-                        air_supply: AirSupply(Default::default()),
+                        air_supply: AirSupply(300),
                         custom_name: CustomName(Default::default()),
                         custom_name_visible: CustomNameVisible(Default::default()),
                         silent: Silent(Default::default()),
@@ -8859,7 +8859,7 @@ impl Default for SquidMetadataBundle {
                                 currently_glowing: CurrentlyGlowing(false),
                                 invisible: Invisible(false),
                                 fall_flying: FallFlying(false),
-                                air_supply: AirSupply(Default::default()),
+                                air_supply: AirSupply(300),
                                 custom_name: CustomName(Default::default()),
                                 custom_name_visible: CustomNameVisible(Default::default()),
                                 silent: Silent(Default::default()),
@@ -8928,7 +8928,7 @@ impl Default for StrayMetadataBundle {
                                 currently_glowing: CurrentlyGlowing(false),
                                 invisible: Invisible(false),
                                 fall_flying: FallFlying(false),
-                                air_supply: AirSupply(Default::default()),
+                                air_supply: AirSupply(300),
                                 custom_name: CustomName(Default::default()),
                                 custom_name_visible: CustomNameVisible(Default::default()),
                                 silent: Silent(Default::default()),
@@ -9010,7 +9010,7 @@ impl Default for StriderMetadataBundle {
                                     currently_glowing: CurrentlyGlowing(false),
                                     invisible: Invisible(false),
                                     fall_flying: FallFlying(false),
-                                    air_supply: AirSupply(Default::default()),
+                                    air_supply: AirSupply(300),
                                     custom_name: CustomName(Default::default()),
                                     custom_name_visible: CustomNameVisible(Default::default()),
                                     silent: Silent(Default::default()),
@@ -9086,7 +9086,7 @@ impl Default for TadpoleMetadataBundle {
                             currently_glowing: CurrentlyGlowing(false),
                             invisible: Invisible(false),
                             fall_flying: FallFlying(false),
-                            air_supply: AirSupply(Default::default()),
+                            air_supply: AirSupply(300),
                             custom_name: CustomName(Default::default()),
                             custom_name_visible: CustomNameVisible(Default::default()),
                             silent: Silent(Default::default()),
@@ -9178,7 +9178,7 @@ impl Default for TextDisplayMetadataBundle {
                     currently_glowing: CurrentlyGlowing(false),
                     invisible: Invisible(false),
                     fall_flying: FallFlying(false),
-                    air_supply: AirSupply(Default::default()),
+                    air_supply: AirSupply(300),
                     custom_name: CustomName(Default::default()),
                     custom_name_visible: CustomNameVisible(Default::default()),
                     silent: Silent(Default::default()),
@@ -9275,7 +9275,7 @@ impl Default for TntMetadataBundle {
                 currently_glowing: CurrentlyGlowing(false),
                 invisible: Invisible(false),
                 fall_flying: FallFlying(false),
-                air_supply: AirSupply(Default::default()),
+                air_supply: AirSupply(300),
                 custom_name: CustomName(Default::default()),
                 custom_name_visible: CustomNameVisible(Default::default()),
                 silent: Silent(Default::default()),
@@ -9326,7 +9326,7 @@ impl Default for TntMinecartMetadataBundle {
                         currently_glowing: CurrentlyGlowing(false),
                         invisible: Invisible(false),
                         fall_flying: FallFlying(false),
-                        air_supply: AirSupply(Default::default()),
+                        air_supply: AirSupply(300),
                         custom_name: CustomName(Default::default()),
                         custom_name_visible: CustomNameVisible(Default::default()),
                         silent: Silent(Default::default()),
@@ -9394,7 +9394,7 @@ impl Default for TraderLlamaMetadataBundle {
                                                 currently_glowing: CurrentlyGlowing(false),
                                                 invisible: Invisible(false),
                                                 fall_flying: FallFlying(false),
-                                                air_supply: AirSupply(Default::default()),
+                                                air_supply: AirSupply(300),
                                                 custom_name: CustomName(Default::default()),
                                                 custom_name_visible: CustomNameVisible(
                                                     Default::default(),
@@ -9484,7 +9484,7 @@ impl Default for TridentMetadataBundle {
                     currently_glowing: CurrentlyGlowing(false),
                     invisible: Invisible(false),
                     fall_flying: FallFlying(false),
-                    air_supply: AirSupply(Default::default()),
+                    air_supply: AirSupply(300),
                     custom_name: CustomName(Default::default()),
                     custom_name_visible: CustomNameVisible(Default::default()),
                     silent: Silent(Default::default()),
@@ -9550,7 +9550,7 @@ impl Default for TropicalFishMetadataBundle {
                                 currently_glowing: CurrentlyGlowing(false),
                                 invisible: Invisible(false),
                                 fall_flying: FallFlying(false),
-                                air_supply: AirSupply(Default::default()),
+                                air_supply: AirSupply(300),
                                 custom_name: CustomName(Default::default()),
                                 custom_name_visible: CustomNameVisible(Default::default()),
                                 silent: Silent(Default::default()),
@@ -9634,7 +9634,7 @@ impl Default for TurtleMetadataBundle {
                                     currently_glowing: CurrentlyGlowing(false),
                                     invisible: Invisible(false),
                                     fall_flying: FallFlying(false),
-                                    air_supply: AirSupply(Default::default()),
+                                    air_supply: AirSupply(300),
                                     custom_name: CustomName(Default::default()),
                                     custom_name_visible: CustomNameVisible(Default::default()),
                                     silent: Silent(Default::default()),
@@ -9712,7 +9712,7 @@ impl Default for VexMetadataBundle {
                                 currently_glowing: CurrentlyGlowing(false),
                                 invisible: Invisible(false),
                                 fall_flying: FallFlying(false),
-                                air_supply: AirSupply(Default::default()),
+                                air_supply: AirSupply(300),
                                 custom_name: CustomName(Default::default()),
                                 custom_name_visible: CustomNameVisible(Default::default()),
                                 silent: Silent(Default::default()),
@@ -9791,7 +9791,7 @@ impl Default for VillagerMetadataBundle {
                                     currently_glowing: CurrentlyGlowing(false),
                                     invisible: Invisible(false),
                                     fall_flying: FallFlying(false),
-                                    air_supply: AirSupply(Default::default()),
+                                    air_supply: AirSupply(300),
                                     custom_name: CustomName(Default::default()),
                                     custom_name_visible: CustomNameVisible(Default::default()),
                                     silent: Silent(Default::default()),
@@ -9869,7 +9869,7 @@ impl Default for VindicatorMetadataBundle {
                                     currently_glowing: CurrentlyGlowing(false),
                                     invisible: Invisible(false),
                                     fall_flying: FallFlying(false),
-                                    air_supply: AirSupply(Default::default()),
+                                    air_supply: AirSupply(300),
                                     custom_name: CustomName(Default::default()),
                                     custom_name_visible: CustomNameVisible(Default::default()),
                                     silent: Silent(Default::default()),
@@ -9941,7 +9941,7 @@ impl Default for WanderingTraderMetadataBundle {
                                     currently_glowing: CurrentlyGlowing(false),
                                     invisible: Invisible(false),
                                     fall_flying: FallFlying(false),
-                                    air_supply: AirSupply(Default::default()),
+                                    air_supply: AirSupply(300),
                                     custom_name: CustomName(Default::default()),
                                     custom_name_visible: CustomNameVisible(Default::default()),
                                     silent: Silent(Default::default()),
@@ -10018,7 +10018,7 @@ impl Default for WardenMetadataBundle {
                                 currently_glowing: CurrentlyGlowing(false),
                                 invisible: Invisible(false),
                                 fall_flying: FallFlying(false),
-                                air_supply: AirSupply(Default::default()),
+                                air_supply: AirSupply(300),
                                 custom_name: CustomName(Default::default()),
                                 custom_name_visible: CustomNameVisible(Default::default()),
                                 silent: Silent(Default::default()),
@@ -10079,7 +10079,7 @@ impl Default for WindChargeMetadataBundle {
                 currently_glowing: CurrentlyGlowing(false),
                 invisible: Invisible(false),
                 fall_flying: FallFlying(false),
-                air_supply: AirSupply(Default::default()),
+                air_supply: AirSupply(300),
                 custom_name: CustomName(Default::default()),
                 custom_name_visible: CustomNameVisible(Default::default()),
                 silent: Silent(Default::default()),
@@ -10140,7 +10140,7 @@ impl Default for WitchMetadataBundle {
                                     currently_glowing: CurrentlyGlowing(false),
                                     invisible: Invisible(false),
                                     fall_flying: FallFlying(false),
-                                    air_supply: AirSupply(Default::default()),
+                                    air_supply: AirSupply(300),
                                     custom_name: CustomName(Default::default()),
                                     custom_name_visible: CustomNameVisible(Default::default()),
                                     silent: Silent(Default::default()),
@@ -10235,7 +10235,7 @@ impl Default for WitherMetadataBundle {
                                 currently_glowing: CurrentlyGlowing(false),
                                 invisible: Invisible(false),
                                 fall_flying: FallFlying(false),
-                                air_supply: AirSupply(Default::default()),
+                                air_supply: AirSupply(300),
                                 custom_name: CustomName(Default::default()),
                                 custom_name_visible: CustomNameVisible(Default::default()),
                                 silent: Silent(Default::default()),
@@ -10307,7 +10307,7 @@ impl Default for WitherSkeletonMetadataBundle {
                                 currently_glowing: CurrentlyGlowing(false),
                                 invisible: Invisible(false),
                                 fall_flying: FallFlying(false),
-                                air_supply: AirSupply(Default::default()),
+                                air_supply: AirSupply(300),
                                 custom_name: CustomName(Default::default()),
                                 custom_name_visible: CustomNameVisible(Default::default()),
                                 silent: Silent(Default::default()),
@@ -10373,7 +10373,7 @@ impl Default for WitherSkullMetadataBundle {
                 currently_glowing: CurrentlyGlowing(false),
                 invisible: Invisible(false),
                 fall_flying: FallFlying(false),
-                air_supply: AirSupply(Default::default()),
+                air_supply: AirSupply(300),
                 custom_name: CustomName(Default::default()),
                 custom_name_visible: CustomNameVisible(Default::default()),
                 silent: Silent(Default::default()),
@@ -10461,7 +10461,7 @@ impl Default for WolfMetadataBundle {
                                         currently_glowing: CurrentlyGlowing(false),
                                         invisible: Invisible(false),
                                         fall_flying: FallFlying(false),
-                                        air_supply: AirSupply(Default::default()),
+                                        air_supply: AirSupply(300),
                                         custom_name: CustomName(Default::default()),
                                         custom_name_visible: CustomNameVisible(Default::default()),
                                         silent: Silent(Default::default()),
@@ -10546,7 +10546,7 @@ impl Default for ZoglinMetadataBundle {
                                 currently_glowing: CurrentlyGlowing(false),
                                 invisible: Invisible(false),
                                 fall_flying: FallFlying(false),
-                                air_supply: AirSupply(Default::default()),
+                                air_supply: AirSupply(300),
                                 custom_name: CustomName(Default::default()),
                                 custom_name_visible: CustomNameVisible(Default::default()),
                                 silent: Silent(Default::default()),
@@ -10627,7 +10627,7 @@ impl Default for ZombieMetadataBundle {
                                 currently_glowing: CurrentlyGlowing(false),
                                 invisible: Invisible(false),
                                 fall_flying: FallFlying(false),
-                                air_supply: AirSupply(Default::default()),
+                                air_supply: AirSupply(300),
                                 custom_name: CustomName(Default::default()),
                                 custom_name_visible: CustomNameVisible(Default::default()),
                                 silent: Silent(Default::default()),
@@ -10702,7 +10702,7 @@ impl Default for ZombieHorseMetadataBundle {
                                         currently_glowing: CurrentlyGlowing(false),
                                         invisible: Invisible(false),
                                         fall_flying: FallFlying(false),
-                                        air_supply: AirSupply(Default::default()),
+                                        air_supply: AirSupply(300),
                                         custom_name: CustomName(Default::default()),
                                         custom_name_visible: CustomNameVisible(Default::default()),
                                         silent: Silent(Default::default()),
@@ -10791,7 +10791,7 @@ impl Default for ZombieVillagerMetadataBundle {
                                     currently_glowing: CurrentlyGlowing(false),
                                     invisible: Invisible(false),
                                     fall_flying: FallFlying(false),
-                                    air_supply: AirSupply(Default::default()),
+                                    air_supply: AirSupply(300),
                                     custom_name: CustomName(Default::default()),
                                     custom_name_visible: CustomNameVisible(Default::default()),
                                     silent: Silent(Default::default()),
@@ -10871,7 +10871,7 @@ impl Default for ZombifiedPiglinMetadataBundle {
                                     currently_glowing: CurrentlyGlowing(false),
                                     invisible: Invisible(false),
                                     fall_flying: FallFlying(false),
-                                    air_supply: AirSupply(Default::default()),
+                                    air_supply: AirSupply(300),
                                     custom_name: CustomName(Default::default()),
                                     custom_name_visible: CustomNameVisible(Default::default()),
                                     silent: Silent(Default::default()),
@@ -10945,7 +10945,7 @@ impl Default for AbstractAgeableMetadataBundle {
                             currently_glowing: CurrentlyGlowing(false),
                             invisible: Invisible(false),
                             fall_flying: FallFlying(false),
-                            air_supply: AirSupply(Default::default()),
+                            air_supply: AirSupply(300),
                             custom_name: CustomName(Default::default()),
                             custom_name_visible: CustomNameVisible(Default::default()),
                             silent: Silent(Default::default()),
@@ -11013,7 +11013,7 @@ impl Default for AbstractAnimalMetadataBundle {
                                 currently_glowing: CurrentlyGlowing(false),
                                 invisible: Invisible(false),
                                 fall_flying: FallFlying(false),
-                                air_supply: AirSupply(Default::default()),
+                                air_supply: AirSupply(300),
                                 custom_name: CustomName(Default::default()),
                                 custom_name_visible: CustomNameVisible(Default::default()),
                                 silent: Silent(Default::default()),
@@ -11089,7 +11089,7 @@ impl Default for AbstractArrowMetadataBundle {
                 currently_glowing: CurrentlyGlowing(false),
                 invisible: Invisible(false),
                 fall_flying: FallFlying(false),
-                air_supply: AirSupply(Default::default()),
+                air_supply: AirSupply(300),
                 custom_name: CustomName(Default::default()),
                 custom_name_visible: CustomNameVisible(Default::default()),
                 silent: Silent(Default::default()),
@@ -11152,7 +11152,7 @@ impl Default for AbstractBoatMetadataBundle {
                     currently_glowing: CurrentlyGlowing(false),
                     invisible: Invisible(false),
                     fall_flying: FallFlying(false),
-                    air_supply: AirSupply(Default::default()),
+                    air_supply: AirSupply(300),
                     custom_name: CustomName(Default::default()),
                     custom_name_visible: CustomNameVisible(Default::default()),
                     silent: Silent(Default::default()),
@@ -11220,7 +11220,7 @@ impl Default for AbstractChestedHorseMetadataBundle {
                                         currently_glowing: CurrentlyGlowing(false),
                                         invisible: Invisible(false),
                                         fall_flying: FallFlying(false),
-                                        air_supply: AirSupply(Default::default()),
+                                        air_supply: AirSupply(300),
                                         custom_name: CustomName(Default::default()),
                                         custom_name_visible: CustomNameVisible(Default::default()),
                                         silent: Silent(Default::default()),
@@ -11292,7 +11292,7 @@ impl Default for AbstractCreatureMetadataBundle {
                         currently_glowing: CurrentlyGlowing(false),
                         invisible: Invisible(false),
                         fall_flying: FallFlying(false),
-                        air_supply: AirSupply(Default::default()),
+                        air_supply: AirSupply(300),
                         custom_name: CustomName(Default::default()),
                         custom_name_visible: CustomNameVisible(Default::default()),
                         silent: Silent(Default::default()),
@@ -11412,7 +11412,7 @@ impl Default for AbstractDisplayMetadataBundle {
                 currently_glowing: CurrentlyGlowing(false),
                 invisible: Invisible(false),
                 fall_flying: FallFlying(false),
-                air_supply: AirSupply(Default::default()),
+                air_supply: AirSupply(300),
                 custom_name: CustomName(Default::default()),
                 custom_name_visible: CustomNameVisible(Default::default()),
                 silent: Silent(Default::default()),
@@ -11532,7 +11532,7 @@ impl Default for AbstractEntityMetadataBundle {
             currently_glowing: CurrentlyGlowing(false),
             invisible: Invisible(false),
             fall_flying: FallFlying(false),
-            air_supply: AirSupply(Default::default()),
+            air_supply: AirSupply(300),
             custom_name: CustomName(Default::default()),
             custom_name_visible: CustomNameVisible(Default::default()),
             silent: Silent(Default::default()),
@@ -11586,7 +11586,7 @@ impl Default for AbstractFishMetadataBundle {
                             currently_glowing: CurrentlyGlowing(false),
                             invisible: Invisible(false),
                             fall_flying: FallFlying(false),
-                            air_supply: AirSupply(Default::default()),
+                            air_supply: AirSupply(300),
                             custom_name: CustomName(Default::default()),
                             custom_name_visible: CustomNameVisible(Default::default()),
                             silent: Silent(Default::default()),
@@ -11667,7 +11667,7 @@ impl Default for AbstractHorseMetadataBundle {
                                     currently_glowing: CurrentlyGlowing(false),
                                     invisible: Invisible(false),
                                     fall_flying: FallFlying(false),
-                                    air_supply: AirSupply(Default::default()),
+                                    air_supply: AirSupply(300),
                                     custom_name: CustomName(Default::default()),
                                     custom_name_visible: CustomNameVisible(Default::default()),
                                     silent: Silent(Default::default()),
@@ -11744,7 +11744,7 @@ impl Default for AbstractInsentientMetadataBundle {
                     currently_glowing: CurrentlyGlowing(false),
                     invisible: Invisible(false),
                     fall_flying: FallFlying(false),
-                    air_supply: AirSupply(Default::default()),
+                    air_supply: AirSupply(300),
                     custom_name: CustomName(Default::default()),
                     custom_name_visible: CustomNameVisible(Default::default()),
                     silent: Silent(Default::default()),
@@ -11832,7 +11832,7 @@ impl Default for AbstractLivingMetadataBundle {
                 currently_glowing: CurrentlyGlowing(false),
                 invisible: Invisible(false),
                 fall_flying: FallFlying(false),
-                air_supply: AirSupply(Default::default()),
+                air_supply: AirSupply(300),
                 custom_name: CustomName(Default::default()),
                 custom_name_visible: CustomNameVisible(Default::default()),
                 silent: Silent(Default::default()),
@@ -11895,7 +11895,7 @@ impl Default for AbstractMinecartMetadataBundle {
                     currently_glowing: CurrentlyGlowing(false),
                     invisible: Invisible(false),
                     fall_flying: FallFlying(false),
-                    air_supply: AirSupply(Default::default()),
+                    air_supply: AirSupply(300),
                     custom_name: CustomName(Default::default()),
                     custom_name_visible: CustomNameVisible(Default::default()),
                     silent: Silent(Default::default()),
@@ -11952,7 +11952,7 @@ impl Default for AbstractMonsterMetadataBundle {
                             currently_glowing: CurrentlyGlowing(false),
                             invisible: Invisible(false),
                             fall_flying: FallFlying(false),
-                            air_supply: AirSupply(Default::default()),
+                            air_supply: AirSupply(300),
                             custom_name: CustomName(Default::default()),
                             custom_name_visible: CustomNameVisible(Default::default()),
                             silent: Silent(Default::default()),
@@ -12023,7 +12023,7 @@ impl Default for AbstractPiglinMetadataBundle {
                                 currently_glowing: CurrentlyGlowing(false),
                                 invisible: Invisible(false),
                                 fall_flying: FallFlying(false),
-                                air_supply: AirSupply(Default::default()),
+                                air_supply: AirSupply(300),
                                 custom_name: CustomName(Default::default()),
                                 custom_name_visible: CustomNameVisible(Default::default()),
                                 silent: Silent(Default::default()),
@@ -12096,7 +12096,7 @@ impl Default for AbstractRaiderMetadataBundle {
                                 currently_glowing: CurrentlyGlowing(false),
                                 invisible: Invisible(false),
                                 fall_flying: FallFlying(false),
-                                air_supply: AirSupply(Default::default()),
+                                air_supply: AirSupply(300),
                                 custom_name: CustomName(Default::default()),
                                 custom_name_visible: CustomNameVisible(Default::default()),
                                 silent: Silent(Default::default()),
@@ -12171,7 +12171,7 @@ impl Default for AbstractSpellcasterIllagerMetadataBundle {
                                     currently_glowing: CurrentlyGlowing(false),
                                     invisible: Invisible(false),
                                     fall_flying: FallFlying(false),
-                                    air_supply: AirSupply(Default::default()),
+                                    air_supply: AirSupply(300),
                                     custom_name: CustomName(Default::default()),
                                     custom_name_visible: CustomNameVisible(Default::default()),
                                     silent: Silent(Default::default()),
@@ -12255,7 +12255,7 @@ impl Default for AbstractTameableMetadataBundle {
                                     currently_glowing: CurrentlyGlowing(false),
                                     invisible: Invisible(false),
                                     fall_flying: FallFlying(false),
-                                    air_supply: AirSupply(Default::default()),
+                                    air_supply: AirSupply(300),
                                     custom_name: CustomName(Default::default()),
                                     custom_name_visible: CustomNameVisible(Default::default()),
                                     silent: Silent(Default::default()),
@@ -12326,7 +12326,7 @@ impl Default for AbstractThrownItemProjectileMetadataBundle {
                 currently_glowing: CurrentlyGlowing(false),
                 invisible: Invisible(false),
                 fall_flying: FallFlying(false),
-                air_supply: AirSupply(Default::default()),
+                air_supply: AirSupply(300),
                 custom_name: CustomName(Default::default()),
                 custom_name_visible: CustomNameVisible(Default::default()),
                 silent: Silent(Default::default()),
@@ -12386,7 +12386,7 @@ impl Default for AbstractVehicleMetadataBundle {
                 currently_glowing: CurrentlyGlowing(false),
                 invisible: Invisible(false),
                 fall_flying: FallFlying(false),
-                air_supply: AirSupply(Default::default()),
+                air_supply: AirSupply(300),
                 custom_name: CustomName(Default::default()),
                 custom_name_visible: CustomNameVisible(Default::default()),
                 silent: Silent(Default::default()),
@@ -12446,7 +12446,7 @@ impl Default for AbstractVillagerMetadataBundle {
                                 currently_glowing: CurrentlyGlowing(false),
                                 invisible: Invisible(false),
                                 fall_flying: FallFlying(false),
-                                air_supply: AirSupply(Default::default()),
+                                air_supply: AirSupply(300),
                                 custom_name: CustomName(Default::default()),
                                 custom_name_visible: CustomNameVisible(Default::default()),
                                 silent: Silent(Default::default()),
@@ -1,8 +1,8 @@
-use azalea_block::{BlockBehavior, BlockTrait};
+use azalea_block::{BlockBehavior, BlockTrait, fluid_state::FluidKind};
 use azalea_core::tier::get_item_tier;
 use azalea_registry as registry;
 
-use crate::{FluidOnEyes, Physics, effects};
+use crate::{ActiveEffects, FluidOnEyes, Physics};
 
 /// How much progress is made towards mining the block per tick, as a
 /// percentage. If this is 1 then the block gets broken instantly.
@@ -11,13 +11,15 @@ use crate::{FluidOnEyes, Physics, effects};
 /// takes to mine the block.
 ///
 /// The player inventory is needed to check your armor and offhand for modifiers
-/// to your mining speed.
+/// to your mining speed, and the active effects are needed for Haste and Mining
+/// Fatigue.
 pub fn get_mine_progress(
     block: &dyn BlockTrait,
     held_item: registry::Item,
     player_inventory: &azalea_inventory::Menu,
     fluid_on_eyes: &FluidOnEyes,
     physics: &Physics,
+    active_effects: &ActiveEffects,
 ) -> f32 {
     let block_behavior: BlockBehavior = block.behavior();
 
@@ -37,6 +39,7 @@ pub fn get_mine_progress(
         player_inventory,
         fluid_on_eyes,
         physics,
+        active_effects,
     ) / destroy_time)
         / divider as f32
 }
@@ -77,8 +80,9 @@ fn destroy_speed(
     block: registry::Block,
     tool: registry::Item,
     _player_inventory: &azalea_inventory::Menu,
-    _fluid_on_eyes: &FluidOnEyes,
+    fluid_on_eyes: &FluidOnEyes,
     physics: &Physics,
+    active_effects: &ActiveEffects,
 ) -> f32 {
     let mut base_destroy_speed = base_destroy_speed(block, tool);
 
@@ -92,11 +96,11 @@ fn destroy_speed(
     // efficiency_level + 1) as f32;     }
     // }
 
-    if let Some(dig_speed_amplifier) = effects::get_dig_speed_amplifier() {
+    if let Some(dig_speed_amplifier) = active_effects.dig_speed_amplifier() {
         base_destroy_speed *= 1. + (dig_speed_amplifier + 1) as f32 * 0.2;
     }
 
-    if let Some(dig_slowdown) = effects::get_effect(registry::MobEffect::MiningFatigue) {
+    if let Some(dig_slowdown) = active_effects.get_level(registry::MobEffect::MiningFatigue) {
         let multiplier = match dig_slowdown {
             0 => 0.3,
             1 => 0.09,
@@ -106,13 +110,11 @@ fn destroy_speed(
         base_destroy_speed *= multiplier;
     }
 
-    // TODO
-    // if **fluid_on_eyes == FluidKind::Water
-    //     && enchantments::get_enchant_level(registry::Enchantment::AquaAffinity,
-    // player_inventory)         == 0
-    // {
-    //     base_destroy_speed /= 5.;
-    // }
+    // TODO: aqua affinity removes this penalty, but enchantments are a data
+    // registry so we can't check for it here
+    if **fluid_on_eyes == FluidKind::Water {
+        base_destroy_speed /= 5.;
+    }
 
     if !physics.on_ground {
         base_destroy_speed /= 5.;
@@ -168,3 +170,104 @@ fn base_destroy_speed(block: registry::Block, tool: registry::Item) -> f32 {
         1.
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use azalea_block::BlockState;
+    use azalea_inventory::{Menu, Player};
+    use azalea_registry::{Block, Item, MobEffect};
+
+    use super::*;
+    use crate::MobEffectData;
+
+    /// How much breaking dirt with our hand progresses every tick.
+    fn dirt_progress(fluid: FluidKind, on_ground: bool, effects: &[(MobEffect, u32)]) -> f32 {
+        let block = Box::<dyn BlockTrait>::from(BlockState::from(Block::Dirt));
+        let mut physics = Physics::default();
+        physics.set_on_ground(on_ground);
+        let mut active_effects = ActiveEffects::default();
+        for &(effect, amplifier) in effects {
+            active_effects.insert(
+                effect,
+                MobEffectData {
+                    amplifier,
+                    duration_ticks: 600,
+                },
+            );
+        }
+        get_mine_progress(
+            block.as_ref(),
+            Item::Air,
+            &Menu::Player(Player::default()),
+            &FluidOnEyes::new(fluid),
+            &physics,
+            &active_effects,
+        )
+    }
+
+    /// Dirt takes 0.5 seconds to break and doesn't need a tool.
+    const BASE: f32 = 1. / 0.5 / 30.;
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!(
+            (actual - expected).abs() <= expected * 1e-5,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn test_no_modifiers() {
+        assert_close(dirt_progress(FluidKind::Empty, true, &[]), BASE);
+    }
+
+    #[test]
+    fn test_haste() {
+        let haste_2 = [(MobEffect::Haste, 1)];
+        assert_close(dirt_progress(FluidKind::Empty, true, &haste_2), BASE * 1.4);
+    }
+
+    #[test]
+    fn test_conduit_power_is_like_haste() {
+        let conduit_power = [(MobEffect::ConduitPower, 0)];
+        assert_close(
+            dirt_progress(FluidKind::Empty, true, &conduit_power),
+            BASE * 1.2,
+        );
+    }
+
+    #[test]
+    fn test_mining_fatigue() {
+        let fatigue_1 = [(MobEffect::MiningFatigue, 0)];
+        assert_close(
+            dirt_progress(FluidKind::Empty, true, &fatigue_1),
+            BASE * 0.3,
+        );
+        let fatigue_3 = [(MobEffect::MiningFatigue, 2)];
+        assert_close(
+            dirt_progress(FluidKind::Empty, true, &fatigue_3),
+            BASE * 0.0027,
+        );
+    }
+
+    #[test]
+    fn test_underwater() {
+        assert_close(dirt_progress(FluidKind::Water, true, &[]), BASE / 5.);
+        // only water slows us down
+        assert_close(dirt_progress(FluidKind::Lava, true, &[]), BASE);
+    }
+
+    #[test]
+    fn test_not_on_ground() {
+        assert_close(dirt_progress(FluidKind::Empty, false, &[]), BASE / 5.);
+    }
+
+    #[test]
+    fn test_all_modifiers() {
+        // what an elder guardian does to us while we're swimming
+        let effects = [(MobEffect::Haste, 1), (MobEffect::MiningFatigue, 2)];
+        assert_close(
+            dirt_progress(FluidKind::Water, false, &effects),
+            BASE * 1.4 * 0.0027 / 5. / 5.,
+        );
+    }
+}
@@ -0,0 +1,176 @@
+//! Moving boats that one of our players is controlling.
+//!
+//! The server lets whoever's steering a boat decide where it goes, so we have
+//! to simulate it ourselves. This is a simplified version of vanilla's boat
+//! physics: boats float on water, slide on land (and much further on ice),
+//! fall through the air, and paddling pushes them in the direction that
+//! they're facing.
+
+use std::f32::consts::PI;
+
+use azalea_block::{BlockTrait, fluid_state::FluidKind};
+use azalea_core::{
+    aabb::AABB,
+    math,
+    position::{BlockPos, Vec3},
+};
+use azalea_entity::Physics;
+use azalea_world::Instance;
+
+use crate::collision::{BlockWithShape, collide_with_blocks};
+
+/// How much paddling forward speeds the boat up every tick.
+pub const BOAT_FORWARD_ACCELERATION: f64 = 0.04;
+/// How much paddling backward slows the boat down every tick.
+pub const BOAT_BACKWARD_ACCELERATION: f64 = 0.005;
+/// How far above the boat's position its passenger sits.
+pub const BOAT_PASSENGER_OFFSET: f64 = 0.1875;
+
+const BOAT_GRAVITY: f64 = 0.04;
+/// What the boat's velocity is multiplied by every tick while it's floating.
+const WATER_FRICTION: f64 = 0.9;
+/// The friction of almost every block that isn't ice, which is what we use if
+/// we can't find the blocks that a boat is resting on.
+const LAND_FRICTION: f64 = 0.6;
+const AIR_FRICTION: f64 = 0.9;
+/// How strongly water pushes the boat up when it's below the surface.
+const BUOYANCY: f64 = 0.06153846;
+
+/// Where a boat is, which decides how it moves.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BoatStatus {
+    /// The bottom of the boat is in water. `water_level` is the height of the
+    /// top of the water.
+    InWater {
+        water_level: f64,
+    },
+    /// The boat is resting on blocks. `friction` is what its velocity is
+    /// multiplied by every tick, which is the average friction of the blocks
+    /// under it. It's much higher on ice, which is why boats are so fast there.
+    OnLand {
+        friction: f64,
+    },
+    InAir,
+}
+
+/// Find out whether the boat is floating, on the ground, or in the air.
+///
+/// This is `AbstractBoat.getStatus` in vanilla, without the check for being
+/// under water.
+pub fn boat_status(world: &Instance, physics: &Physics) -> BoatStatus {
+    let bounding_box = physics.bounding_box;
+    let min_x = bounding_box.min.x.floor() as i32;
+    let max_x = bounding_box.max.x.ceil() as i32;
+    let min_z = bounding_box.min.z.floor() as i32;
+    let max_z = bounding_box.max.z.ceil() as i32;
+
+    let bottom_y = bounding_box.min.y.floor() as i32;
+    let mut water_level = None::<f64>;
+    for x in min_x..max_x {
+        for z in min_z..max_z {
+            for y in bottom_y..=bottom_y + 1 {
+                let pos = BlockPos::new(x, y, z);
+                let Some(fluid) = world.get_fluid_state(pos) else {
+                    continue;
+                };
+                if fluid.kind != FluidKind::Water {
+                    continue;
+                }
+                let top = y as f64 + fluid.height() as f64;
+                water_level = Some(water_level.map_or(top, |level| level.max(top)));
+            }
+        }
+    }
+    if let Some(water_level) = water_level
+        && bounding_box.min.y < water_level
+    {
+        return BoatStatus::InWater { water_level };
+    }
+
+    let below = collide_with_blocks(Vec3::new(0., -0.001, 0.), &bounding_box, world);
+    if below.y > -0.001 {
+        BoatStatus::OnLand {
+            friction: ground_friction(world, &bounding_box),
+        }
+    } else {
+        BoatStatus::InAir
+    }
+}
+
+/// The average friction of the blocks that the boat is resting on.
+///
+/// This is `AbstractBoat.getGroundFriction` in vanilla, except that it only
+/// looks at the blocks directly under the boat.
+fn ground_friction(world: &Instance, bounding_box: &AABB) -> f64 {
+    let min_x = bounding_box.min.x.floor() as i32;
+    let max_x = bounding_box.max.x.ceil() as i32;
+    let min_z = bounding_box.min.z.floor() as i32;
+    let max_z = bounding_box.max.z.ceil() as i32;
+    let y = (bounding_box.min.y - 0.001).floor() as i32;
+
+    let mut total_friction = 0.;
+    let mut block_count = 0;
+    for x in min_x..max_x {
+        for z in min_z..max_z {
+            let block_state = world
+                .get_block_state(BlockPos::new(x, y, z))
+                .unwrap_or_default();
+            if block_state.is_collision_shape_empty() {
+                continue;
+            }
+            let block: Box<dyn BlockTrait> = block_state.into();
+            total_friction += block.behavior().friction as f64;
+            block_count += 1;
+        }
+    }
+    if block_count == 0 {
+        LAND_FRICTION
+    } else {
+        total_friction / block_count as f64
+    }
+}
+
+/// Get the boat's new velocity after a tick of its passenger paddling it.
+///
+/// `y` is the boat's position, which is also the bottom of its bounding box.
+///
+/// This is `AbstractBoat.floatBoat` and `AbstractBoat.controlBoat` in vanilla.
+pub fn boat_movement(
+    mut velocity: Vec3,
+    y: f64,
+    bounding_box_height: f64,
+    y_rot: f32,
+    status: BoatStatus,
+    forward: bool,
+    backward: bool,
+) -> Vec3 {
+    let friction = match status {
+        BoatStatus::InWater { .. } => WATER_FRICTION,
+        BoatStatus::OnLand { friction } => friction,
+        BoatStatus::InAir => AIR_FRICTION,
+    };
+    velocity.x *= friction;
+    velocity.z *= friction;
+    velocity.y -= BOAT_GRAVITY;
+
+    // float up to the surface
+    if let BoatStatus::InWater { water_level } = status {
+        let submerged = (water_level - y) / bounding_box_height;
+        if submerged > 0. {
+            velocity.y = (velocity.y + submerged * BUOYANCY) * 0.75;
+        }
+    }
+
+    let mut acceleration = 0.;
+    if forward {
+        acceleration += BOAT_FORWARD_ACCELERATION;
+    }
+    if backward {
+        acceleration -= BOAT_BACKWARD_ACCELERATION;
+    }
+    let y_rot_radians = y_rot * (PI / 180.);
+    velocity.x += math::sin(-y_rot_radians) as f64 * acceleration;
+    velocity.z += math::cos(y_rot_radians) as f64 * acceleration;
+
+    velocity
+}
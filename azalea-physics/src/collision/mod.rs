@@ -108,14 +108,19 @@ fn collide(
 
 /// Move an entity by a given delta, checking for collisions.
 ///
+/// If `is_staying_on_ground_surface` is true (i.e. the player is sneaking),
+/// then the movement is cut short instead of letting the entity go off the
+/// edge of a block.
+///
 /// In Mojmap, this is `Entity.move`.
 #[allow(clippy::too_many_arguments)]
 pub fn move_colliding(
-    _mover_type: MoverType,
+    mover_type: MoverType,
     movement: Vec3,
     world: &Instance,
     position: &mut Mut<azalea_entity::Position>,
     physics: &mut azalea_entity::Physics,
+    is_staying_on_ground_surface: bool,
     source_entity: Option<Entity>,
     physics_query: &PhysicsQuery,
     collidable_entity_query: &CollidableEntityQuery,
@@ -139,7 +144,13 @@ pub fn move_colliding(
     //     this.setDeltaMovement(Vec3.ZERO);
     // }
 
-    // movement = this.maybeBackOffFromEdge(movement, moverType);
+    let movement = maybe_back_off_from_edge(
+        movement,
+        mover_type,
+        world,
+        physics,
+        is_staying_on_ground_surface,
+    );
 
     let collide_result = collide(
         movement,
@@ -238,6 +249,101 @@ pub fn move_colliding(
     Ok(())
 }
 
+// Player.maybeBackOffFromEdge
+fn maybe_back_off_from_edge(
+    movement: Vec3,
+    mover_type: MoverType,
+    world: &Instance,
+    physics: &azalea_entity::Physics,
+    is_staying_on_ground_surface: bool,
+) -> Vec3 {
+    let max_up_step = 0.6;
+    // TODO: check whether we're flying
+    if movement.y > 0.
+        || !matches!(mover_type, MoverType::Own | MoverType::Player)
+        || !is_staying_on_ground_surface
+        || !is_above_ground(world, physics, max_up_step)
+    {
+        return movement;
+    }
+
+    const STEP: f64 = 0.05;
+    let mut x = movement.x;
+    let mut z = movement.z;
+    let x_step = x.signum() * STEP;
+    let z_step = z.signum() * STEP;
+
+    while x != 0. && can_fall_at_least(world, physics, x, 0., max_up_step) {
+        if x.abs() <= STEP {
+            x = 0.;
+            break;
+        }
+        x -= x_step;
+    }
+    while z != 0. && can_fall_at_least(world, physics, 0., z, max_up_step) {
+        if z.abs() <= STEP {
+            z = 0.;
+            break;
+        }
+        z -= z_step;
+    }
+    while x != 0. && z != 0. && can_fall_at_least(world, physics, x, z, max_up_step) {
+        if x.abs() <= STEP {
+            x = 0.;
+        } else {
+            x -= x_step;
+        }
+        if z.abs() <= STEP {
+            z = 0.;
+        } else {
+            z -= z_step;
+        }
+    }
+
+    Vec3::new(x, movement.y, z)
+}
+
+// Entity.isAboveGround
+fn is_above_ground(world: &Instance, physics: &azalea_entity::Physics, max_up_step: f64) -> bool {
+    let fall_distance = physics.fall_distance as f64;
+    physics.on_ground()
+        || fall_distance < max_up_step
+            && !can_fall_at_least(world, physics, 0., 0., max_up_step - fall_distance)
+}
+
+/// Whether there's nothing for the entity to land on if it was moved by the
+/// given x and z and then fell by `distance`.
+///
+/// Unlike vanilla, this ignores collisions with entities.
+// Entity.canFallAtLeast
+fn can_fall_at_least(
+    world: &Instance,
+    physics: &azalea_entity::Physics,
+    x: f64,
+    z: f64,
+    distance: f64,
+) -> bool {
+    let aabb = physics.bounding_box;
+    let below = AABB {
+        min: Vec3::new(
+            aabb.min.x + 1.0e-7 + x,
+            aabb.min.y - distance - 1.0e-7,
+            aabb.min.z + 1.0e-7 + z,
+        ),
+        max: Vec3::new(aabb.max.x - 1.0e-7 + x, aabb.min.y, aabb.max.z - 1.0e-7 + z),
+    };
+    get_block_collisions(world, &below).is_empty()
+}
+
+/// Get how far a bounding box can move by the given delta before it runs
+/// into a block.
+///
+/// Unlike [`move_colliding`], this doesn't collide with entities and doesn't
+/// step up blocks.
+pub fn collide_with_blocks(movement: Vec3, bounding_box: &AABB, world: &Instance) -> Vec3 {
+    collide_bounding_box(movement, bounding_box, world, &[])
+}
+
 fn collide_bounding_box(
     movement: Vec3,
     entity_bounding_box: &AABB,
@@ -0,0 +1,115 @@
+//! Gliding with an elytra, and getting boosted by firework rockets while doing
+//! it.
+
+use azalea_core::{math, position::Vec3};
+use azalea_entity::{
+    LocalEntity, LookDirection, OnClimbable, Physics,
+    metadata::{AttachedToTarget, FallFlying},
+    view_vector,
+};
+use azalea_world::{InstanceName, MinecraftEntityId};
+use bevy_ecs::prelude::*;
+
+/// Get our new velocity after a tick of gliding with an elytra, before we're
+/// moved by it.
+///
+/// Looking down makes us go faster, and looking up trades that speed for
+/// height.
+///
+/// This is `LivingEntity.updateFallFlyingMovement` in vanilla.
+pub fn fall_flying_movement(
+    mut velocity: Vec3,
+    look_direction: LookDirection,
+    gravity: f64,
+) -> Vec3 {
+    let look = view_vector(look_direction);
+    let x_rot = look_direction.x_rot * 0.017453292;
+    let look_horizontal_length = (look.x * look.x + look.z * look.z).sqrt();
+    let horizontal_speed = velocity.horizontal_distance_squared().sqrt();
+    let lift = (x_rot as f64).cos().powi(2);
+
+    velocity.y += gravity * (-1. + lift * 0.75);
+    if velocity.y < 0. && look_horizontal_length > 0. {
+        let convert = velocity.y * -0.1 * lift;
+        velocity.x += look.x * convert / look_horizontal_length;
+        velocity.y += convert;
+        velocity.z += look.z * convert / look_horizontal_length;
+    }
+    if x_rot < 0. && look_horizontal_length > 0. {
+        let convert = horizontal_speed * -math::sin(x_rot) as f64 * 0.04;
+        velocity.x -= look.x * convert / look_horizontal_length;
+        velocity.y += convert * 3.2;
+        velocity.z -= look.z * convert / look_horizontal_length;
+    }
+    if look_horizontal_length > 0. {
+        velocity.x += (look.x / look_horizontal_length * horizontal_speed - velocity.x) * 0.1;
+        velocity.z += (look.z / look_horizontal_length * horizontal_speed - velocity.z) * 0.1;
+    }
+
+    Vec3 {
+        x: velocity.x * 0.99f32 as f64,
+        y: velocity.y * 0.98f32 as f64,
+        z: velocity.z * 0.99f32 as f64,
+    }
+}
+
+/// Get our new velocity after a tick of being boosted by a firework rocket
+/// while we're gliding.
+///
+/// This is from `FireworkRocketEntity.tick` in vanilla.
+pub fn firework_boost(velocity: Vec3, look_direction: LookDirection) -> Vec3 {
+    let look = view_vector(look_direction);
+    velocity
+        + Vec3 {
+            x: look.x * 0.1 + (look.x * 1.5 - velocity.x) * 0.5,
+            y: look.y * 0.1 + (look.y * 1.5 - velocity.y) * 0.5,
+            z: look.z * 0.1 + (look.z * 1.5 - velocity.z) * 0.5,
+        }
+}
+
+/// Boost our local players while they have a firework rocket attached to them
+/// and they're gliding.
+///
+/// The server is the one that spawns the rocket, but we're the ones that move
+/// our player, so the boost has to be applied by us.
+pub fn apply_firework_boosts(
+    fireworks: Query<(&AttachedToTarget, &InstanceName)>,
+    mut query: Query<
+        (
+            &MinecraftEntityId,
+            &InstanceName,
+            &mut Physics,
+            &LookDirection,
+            &FallFlying,
+        ),
+        With<LocalEntity>,
+    >,
+) {
+    for (attached_to_target, firework_instance_name) in &fireworks {
+        let Some(target_id) = attached_to_target.0.0 else {
+            continue;
+        };
+        for (entity_id, instance_name, mut physics, look_direction, fall_flying) in &mut query {
+            if entity_id.0 as u32 == target_id
+                && instance_name == firework_instance_name
+                && **fall_flying
+            {
+                physics.velocity = firework_boost(physics.velocity, *look_direction);
+            }
+        }
+    }
+}
+
+/// Stop gliding once we land, or when we touch water or a ladder.
+///
+/// The server does this too, but we'd keep gliding until it tells us that we
+/// stopped.
+pub fn stop_fall_flying(
+    mut query: Query<(&Physics, &OnClimbable, &mut FallFlying), With<LocalEntity>>,
+) {
+    for (physics, on_climbable, mut fall_flying) in &mut query {
+        if **fall_flying && (physics.on_ground() || physics.is_in_water() || **on_climbable) {
+            **fall_flying = false;
+        }
+    }
+}
@@ -1,8 +1,10 @@
 #![doc = include_str!("../README.md")]
 #![feature(trait_alias)]
 
+pub mod boat;
 pub mod clip;
 pub mod collision;
+pub mod elytra;
 pub mod fluids;
 pub mod travel;
 
@@ -15,10 +17,10 @@ use azalea_core::{
     tick::GameTick,
 };
 use azalea_entity::{
-    Attributes, EntityKindComponent, InLoadedChunk, Jumping, LocalEntity, LookDirection,
-    OnClimbable, Physics, Pose, Position, metadata::Sprinting, move_relative,
+    ActiveEffects, Attributes, EntityKindComponent, InLoadedChunk, Jumping, LocalEntity,
+    LookDirection, OnClimbable, Physics, Pose, Position, metadata::Sprinting, move_relative,
 };
-use azalea_registry::{Block, EntityKind};
+use azalea_registry::{Block, EntityKind, MobEffect};
 use azalea_world::{Instance, InstanceContainer, InstanceName};
 use bevy_app::{App, Plugin};
 use bevy_ecs::prelude::*;
@@ -42,8 +44,10 @@ impl Plugin for PhysicsPlugin {
                 fluids::update_in_water_state_and_do_fluid_pushing,
                 update_old_position,
                 fluids::update_swimming,
+                elytra::apply_firework_boosts,
                 ai_step,
                 travel::travel,
+                elytra::stop_fall_flying,
                 apply_effects_from_blocks,
             )
                 .chain()
@@ -67,13 +71,22 @@ pub fn ai_step(
             &Sprinting,
             &InstanceName,
             &EntityKindComponent,
+            Option<&ActiveEffects>,
         ),
         (With<LocalEntity>, With<InLoadedChunk>),
     >,
     instance_container: Res<InstanceContainer>,
 ) {
-    for (mut physics, jumping, position, look_direction, sprinting, instance_name, entity_kind) in
-        &mut query
+    for (
+        mut physics,
+        jumping,
+        position,
+        look_direction,
+        sprinting,
+        instance_name,
+        entity_kind,
+        active_effects,
+    ) in &mut query
     {
         let is_player = **entity_kind == EntityKind::Player;
 
@@ -135,6 +148,7 @@ pub fn ai_step(
                             *sprinting,
                             instance_name,
                             &instance_container,
+                            active_effects,
                         );
                         physics.no_jump_delay = 10;
                     }
@@ -326,13 +340,14 @@ pub fn jump_from_ground(
     sprinting: Sprinting,
     instance_name: &InstanceName,
     instance_container: &InstanceContainer,
+    active_effects: Option<&ActiveEffects>,
 ) {
     let world_lock = instance_container
         .get(instance_name)
         .expect("All entities should be in a valid world");
     let world = world_lock.read();
 
-    let jump_power: f64 = jump_power(&world, position) as f64 + jump_boost_power();
+    let jump_power: f64 = jump_power(&world, position) as f64 + jump_boost_power(active_effects);
     let old_delta_movement = physics.velocity;
     physics.velocity = Vec3 {
         x: old_delta_movement.x,
@@ -379,6 +394,7 @@ struct HandleRelativeFrictionAndCalculateMovementOpts<'a, 'b, 'world, 'state> {
     on_climbable: OnClimbable,
     pose: Option<Pose>,
     jumping: Jumping,
+    is_staying_on_ground_surface: bool,
     entity: Entity,
     physics_query: &'a PhysicsQuery<'world, 'state, 'b>,
     collidable_entity_query: &'a CollidableEntityQuery<'world, 'state>,
@@ -395,6 +411,7 @@ fn handle_relative_friction_and_calculate_movement(
         on_climbable,
         pose,
         jumping,
+        is_staying_on_ground_surface,
         entity,
         physics_query,
         collidable_entity_query,
@@ -419,6 +436,7 @@ fn handle_relative_friction_and_calculate_movement(
         world,
         &mut position,
         physics,
+        is_staying_on_ground_surface,
         Some(entity),
         physics_query,
         collidable_entity_query,
@@ -534,16 +552,9 @@ fn jump_power(world: &Instance, position: Position) -> f32 {
     0.42 * block_jump_factor(world, position)
 }
 
-fn jump_boost_power() -> f64 {
-    // TODO: potion effects
-    // if let Some(effects) = entity.effects() {
-    //     if let Some(jump_effect) = effects.get(&Effect::Jump) {
-    //         0.1 * (jump_effect.amplifier + 1) as f32
-    //     } else {
-    //         0.
-    //     }
-    // } else {
-    //     0.
-    // }
-    0.
+pub fn jump_boost_power(active_effects: Option<&ActiveEffects>) -> f64 {
+    active_effects
+        .and_then(|effects| effects.get_level(MobEffect::JumpBoost))
+        .map(|amplifier| (0.1 * (amplifier + 1) as f32) as f64)
+        .unwrap_or(0.)
 }
@@ -4,9 +4,12 @@ use azalea_core::{
     position::{BlockPos, Vec3},
 };
 use azalea_entity::{
-    Attributes, InLoadedChunk, Jumping, LocalEntity, LookDirection, OnClimbable, Physics, Pose,
-    Position, metadata::Sprinting, move_relative,
+    ActiveEffects, Attributes, InLoadedChunk, Jumping, LocalEntity, LookDirection, OnClimbable,
+    Physics, Pose, Position,
+    metadata::{FallFlying, ShiftKeyDown, Sprinting},
+    move_relative,
 };
+use azalea_registry::MobEffect;
 use azalea_world::{Instance, InstanceContainer, InstanceName};
 use bevy_ecs::prelude::*;
 
@@ -17,6 +20,7 @@ use crate::{
         entity_collisions::{CollidableEntityQuery, PhysicsQuery, get_entity_collisions},
         move_colliding,
     },
+    elytra::fall_flying_movement,
     get_block_pos_below_that_affects_movement, handle_relative_friction_and_calculate_movement,
 };
 
@@ -36,6 +40,9 @@ pub fn travel(
             &InstanceName,
             &OnClimbable,
             &Jumping,
+            Option<&ActiveEffects>,
+            Option<&ShiftKeyDown>,
+            Option<&FallFlying>,
         ),
         (With<LocalEntity>, With<InLoadedChunk>),
     >,
@@ -54,6 +61,9 @@ pub fn travel(
         world_name,
         on_climbable,
         jumping,
+        active_effects,
+        shift_key_down,
+        fall_flying,
     ) in &mut query
     {
         let Some(world_lock) = instance_container.get(world_name) else {
@@ -62,8 +72,8 @@ pub fn travel(
         let world = world_lock.read();
 
         let sprinting = *sprinting.unwrap_or(&Sprinting(false));
-
-        // TODO: elytras
+        let is_staying_on_ground_surface = shift_key_down.is_some_and(|s| **s);
+        let gravity = get_effective_gravity(&physics, active_effects);
 
         if physics.is_in_water() || physics.is_in_lava() {
             // minecraft also checks for `this.isAffectedByFluids() &&
@@ -78,6 +88,20 @@ pub fn travel(
                 attributes,
                 sprinting,
                 on_climbable,
+                gravity,
+                is_staying_on_ground_surface,
+                &physics_query,
+                &collidable_entity_query,
+            );
+        } else if fall_flying.is_some_and(|fall_flying| **fall_flying) && !**on_climbable {
+            travel_fall_flying(
+                &world,
+                entity,
+                &mut physics,
+                *direction,
+                position,
+                gravity,
+                is_staying_on_ground_surface,
                 &physics_query,
                 &collidable_entity_query,
             );
@@ -93,6 +117,8 @@ pub fn travel(
                 *on_climbable,
                 pose,
                 *jumping,
+                gravity,
+                is_staying_on_ground_surface,
                 &physics_query,
                 &collidable_entity_query,
             );
@@ -113,11 +139,11 @@ fn travel_in_air(
     on_climbable: OnClimbable,
     pose: Option<&Pose>,
     jumping: Jumping,
+    gravity: f64,
+    is_staying_on_ground_surface: bool,
     physics_query: &PhysicsQuery,
     collidable_entity_query: &CollidableEntityQuery,
 ) {
-    let gravity = get_effective_gravity();
-
     let block_pos_below = get_block_pos_below_that_affects_movement(*position);
 
     let block_state_below = world
@@ -146,6 +172,7 @@ fn travel_in_air(
             on_climbable,
             pose: pose.copied(),
             jumping,
+            is_staying_on_ground_surface,
             entity,
             physics_query,
             collidable_entity_query,
@@ -172,6 +199,34 @@ fn travel_in_air(
     }
 }
 
+/// Glide with an elytra.
+#[allow(clippy::too_many_arguments)]
+fn travel_fall_flying(
+    world: &Instance,
+    entity: Entity,
+    physics: &mut Physics,
+    direction: LookDirection,
+    mut position: Mut<Position>,
+    gravity: f64,
+    is_staying_on_ground_surface: bool,
+    physics_query: &PhysicsQuery,
+    collidable_entity_query: &CollidableEntityQuery,
+) {
+    physics.velocity = fall_flying_movement(physics.velocity, direction, gravity);
+    move_colliding(
+        MoverType::Own,
+        physics.velocity,
+        world,
+        &mut position,
+        physics,
+        is_staying_on_ground_surface,
+        Some(entity),
+        physics_query,
+        collidable_entity_query,
+    )
+    .expect("Entity should exist");
+}
+
 #[allow(clippy::too_many_arguments)]
 fn travel_in_fluid(
     world: &Instance,
@@ -182,12 +237,13 @@ fn travel_in_fluid(
     attributes: &Attributes,
     sprinting: Sprinting,
     on_climbable: &OnClimbable,
+    gravity: f64,
+    is_staying_on_ground_surface: bool,
     physics_query: &PhysicsQuery,
     collidable_entity_query: &CollidableEntityQuery,
 ) {
     let moving_down = physics.velocity.y <= 0.;
     let y = position.y;
-    let gravity = get_effective_gravity();
 
     let acceleration = Vec3::new(
         physics.x_acceleration as f64,
@@ -219,6 +275,7 @@ fn travel_in_fluid(
             world,
             &mut position,
             physics,
+            is_staying_on_ground_surface,
             Some(entity),
             physics_query,
             collidable_entity_query,
@@ -243,6 +300,7 @@ fn travel_in_fluid(
             world,
             &mut position,
             physics,
+            is_staying_on_ground_surface,
             Some(entity),
             physics_query,
             collidable_entity_query,
@@ -404,9 +462,14 @@ fn contains_any_liquid(world: &Instance, bounding_box: AABB) -> bool {
     false
 }
 
-fn get_effective_gravity() -> f64 {
-    // TODO: slow falling effect
-    0.08
+pub fn get_effective_gravity(physics: &Physics, active_effects: Option<&ActiveEffects>) -> f64 {
+    let gravity = 0.08;
+    let is_falling = physics.velocity.y <= 0.;
+    if is_falling && active_effects.is_some_and(|effects| effects.has(MobEffect::SlowFalling)) {
+        f64::min(gravity, 0.01)
+    } else {
+        gravity
+    }
 }
 
 pub fn fluid_jump_threshold() -> f64 {
@@ -558,3 +558,51 @@ fn test_afk_pool() {
     );
     assert_eq!(loops_done, 1);
 }
+
+#[test]
+fn test_boat_is_faster_on_blue_ice() {
+    use azalea_entity::EntityDimensions;
+    use azalea_physics::boat::{BoatStatus, boat_movement, boat_status};
+
+    let mut app = make_test_app();
+    let world_lock = insert_overworld(&mut app);
+    let mut partial_world = PartialInstance::default();
+    partial_world.chunks.set(
+        &ChunkPos { x: 0, z: 0 },
+        Some(Chunk::default()),
+        &mut world_lock.write().chunks,
+    );
+    for x in 0..=2 {
+        for z in 0..=2 {
+            partial_world.chunks.set_block_state(
+                BlockPos { x, y: 69, z },
+                azalea_registry::Block::BlueIce.into(),
+                &world_lock.write().chunks,
+            );
+        }
+    }
+
+    let dimensions = EntityDimensions::new(1.375, 0.5625);
+    let boat_physics = Physics::new(dimensions, Vec3::new(1.5, 70., 1.5));
+    let status = boat_status(&world_lock.read(), &boat_physics);
+    let BoatStatus::OnLand { friction } = status else {
+        panic!("the boat should be resting on the ice, but it's {status:?}");
+    };
+    assert!((friction - 0.989).abs() < 1e-6, "{friction}");
+
+    // paddle forward until the boat stops speeding up
+    let top_speed = |status: BoatStatus| {
+        let mut velocity = Vec3::ZERO;
+        for _ in 0..1000 {
+            velocity = boat_movement(velocity, 70., 0.5625, 0., status, true, false);
+            // the ground stops us from falling
+            velocity.y = 0.;
+        }
+        velocity.z
+    };
+    let ice_speed = top_speed(status);
+    let stone_speed = top_speed(BoatStatus::OnLand { friction: 0.6 });
+    // about 70 blocks per second
+    assert!(ice_speed > 3.4, "{ice_speed}");
+    assert!(stone_speed < 0.2, "{stone_speed}");
+}
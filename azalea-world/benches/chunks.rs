@@ -1,7 +1,8 @@
 use std::hint::black_box;
 
-use azalea_core::position::ChunkBlockPos;
-use azalea_world::{BitStorage, Chunk};
+use azalea_block::BlockStates;
+use azalea_core::position::{BlockPos, ChunkBlockPos, ChunkPos};
+use azalea_world::{BitStorage, Chunk, Instance, PartialChunkStorage};
 use criterion::{Criterion, criterion_group, criterion_main};
 
 fn bench_chunks(c: &mut Criterion) {
@@ -34,5 +35,46 @@ fn bench_bitstorage(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, bench_chunks, bench_bitstorage);
+fn bench_find_blocks(c: &mut Criterion) {
+    const RADIUS: i32 = 16;
+
+    let mut instance = Instance::default();
+    let mut partial_chunks = PartialChunkStorage::new(RADIUS as u32);
+    let min_y = instance.chunks.min_y;
+    for chunk_x in -RADIUS..=RADIUS {
+        for chunk_z in -RADIUS..=RADIUS {
+            // stone with a few diamonds in it at the bottom of the world, and air above
+            let mut chunk = Chunk::default();
+            for y in min_y..0 {
+                for x in 0..16 {
+                    for z in 0..16 {
+                        let block = if (x as i32 + y + z as i32 * 3) % 97 == 0 {
+                            azalea_registry::Block::DeepslateDiamondOre
+                        } else {
+                            azalea_registry::Block::Deepslate
+                        };
+                        chunk.set_block_state(&ChunkBlockPos::new(x, y, z), block.into(), min_y);
+                    }
+                }
+            }
+            partial_chunks.set(
+                &ChunkPos::new(chunk_x, chunk_z),
+                Some(chunk),
+                &mut instance.chunks,
+            );
+        }
+    }
+
+    let block_states = BlockStates::from([azalea_registry::Block::DeepslateDiamondOre]);
+    c.bench_function("Instance::find_blocks", |b| {
+        b.iter(|| {
+            let found = instance
+                .find_blocks(BlockPos::new(0, 0, 0), &block_states)
+                .count();
+            black_box(found);
+        });
+    });
+}
+
+criterion_group!(benches, bench_chunks, bench_bitstorage, bench_find_blocks);
 criterion_main!(benches);
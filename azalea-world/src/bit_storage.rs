@@ -223,13 +223,21 @@ impl BitStorage {
         BitStorageIter {
             storage: self,
             index: 0,
+            cell_index: 0,
+            bit_index: 0,
         }
     }
 }
 
+/// An iterator over the entries in a [`BitStorage`].
+///
+/// This goes through the cells in order instead of calling
+/// [`BitStorage::get`] for every entry, so it's faster than indexing.
 pub struct BitStorageIter<'a> {
     storage: &'a BitStorage,
     index: usize,
+    cell_index: usize,
+    bit_index: usize,
 }
 
 impl Iterator for BitStorageIter<'_> {
@@ -239,9 +247,20 @@ impl Iterator for BitStorageIter<'_> {
         if self.index >= self.storage.size {
             return None;
         }
-
-        let value = self.storage.get(self.index);
         self.index += 1;
+
+        // 0 bit storage
+        if self.storage.data.is_empty() {
+            return Some(0);
+        }
+
+        let value = (self.storage.data[self.cell_index] >> self.bit_index) & self.storage.mask;
+        self.bit_index += self.storage.bits;
+        // entries never span two cells, so move on when the next one wouldn't fit
+        if self.bit_index + self.storage.bits > 64 {
+            self.cell_index += 1;
+            self.bit_index = 0;
+        }
         Some(value)
     }
 }
@@ -262,4 +281,20 @@ mod tests {
             assert_eq!(storage.get(i), *expected);
         }
     }
+
+    #[test]
+    fn test_iter_matches_get() {
+        for bits in 1..=32 {
+            let mut storage = BitStorage::new(bits, 4096, None).unwrap();
+            for i in 0..4096 {
+                storage.set(i, (i as u64 * 31) & storage.mask);
+            }
+
+            let values = storage.iter().collect::<Vec<_>>();
+            assert_eq!(values.len(), 4096);
+            for (i, value) in values.into_iter().enumerate() {
+                assert_eq!(value, storage.get(i), "bits: {bits}, index: {i}");
+            }
+        }
+    }
 }
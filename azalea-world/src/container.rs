@@ -76,6 +76,8 @@ impl InstanceContainer {
                     entities_by_chunk: HashMap::new(),
                     entity_by_id: IntMap::default(),
                     registries: default_registries.clone(),
+                    day_time: None,
+                    world_border: None,
                 }));
                 debug!("Added new instance {name}");
                 self.instances.insert(name, Arc::downgrade(&world));
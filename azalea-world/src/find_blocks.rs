@@ -1,7 +1,10 @@
 use azalea_block::{BlockState, BlockStates};
 use azalea_core::position::{BlockPos, ChunkPos};
 
-use crate::{Chunk, ChunkStorage, Instance, iterators::ChunkIterator, palette::Palette};
+use crate::{
+    Chunk, ChunkStorage, Instance, heightmap::HeightmapKind, iterators::ChunkIterator,
+    palette::Palette,
+};
 
 impl Instance {
     /// Find the coordinates of a block in the world.
@@ -214,45 +217,99 @@ pub fn find_blocks_in_chunk(
     min_y: i32,
     mut cb: impl FnMut(BlockPos),
 ) {
+    // everything above the surface is air, so we don't have to look there unless
+    // we're looking for air
+    let surface_y = if block_states.set.iter().any(|state| state.is_air()) {
+        None
+    } else {
+        chunk
+            .heightmaps
+            .get(&HeightmapKind::WorldSurface)
+            .and_then(|heightmap| heightmap.iter_first_available().map(|pos| pos.y).max())
+    };
+    // only made if there's a section that needs it, since it's relatively big
+    let mut global_ids = None::<BlockStateIdSet>;
+
     for (section_index, section) in chunk.sections.iter().enumerate() {
-        let maybe_has_block = palette_maybe_has_block(&section.states.palette, block_states);
-        if !maybe_has_block {
-            continue;
+        let section_min_y = min_y + (section_index * 16) as i32;
+        if surface_y.is_some_and(|surface_y| section_min_y >= surface_y) {
+            break;
         }
 
-        for i in 0..4096 {
-            let block_state = section.states.get_at_index(i);
-
-            if block_states.contains(&block_state) {
-                let section_pos = section.states.coords_from_index(i);
-                let (x, y, z) = (
-                    chunk_pos.x * 16 + (section_pos.x as i32),
-                    min_y + (section_index * 16) as i32 + section_pos.y as i32,
-                    chunk_pos.z * 16 + (section_pos.z as i32),
-                );
-                let this_block_pos = BlockPos { x, y, z };
-
-                cb(this_block_pos);
+        let mut cb_at_index = |i: usize| {
+            let section_pos = section.states.coords_from_index(i);
+            cb(BlockPos {
+                x: chunk_pos.x * 16 + (section_pos.x as i32),
+                y: section_min_y + section_pos.y as i32,
+                z: chunk_pos.z * 16 + (section_pos.z as i32),
+            });
+        };
+
+        // the values in the storage are indexes into the palette, so we check the
+        // palette once and then only have to compare the raw values
+        match &section.states.palette {
+            Palette::SingleValue(id) => {
+                if block_states.contains(id) {
+                    (0..4096).for_each(&mut cb_at_index);
+                }
+            }
+            Palette::Linear(ids) | Palette::Hashmap(ids) => {
+                let is_target = ids
+                    .iter()
+                    .map(|id| block_states.contains(id))
+                    .collect::<Vec<_>>();
+                if !is_target.contains(&true) {
+                    continue;
+                }
+                for (i, id) in section.states.storage.iter().enumerate() {
+                    if is_target.get(id as usize).copied().unwrap_or_default() {
+                        cb_at_index(i);
+                    }
+                }
+            }
+            Palette::Global => {
+                let global_ids =
+                    global_ids.get_or_insert_with(|| BlockStateIdSet::new(block_states));
+                for (i, id) in section.states.storage.iter().enumerate() {
+                    if global_ids.contains(id) {
+                        cb_at_index(i);
+                    }
+                }
             }
         }
     }
 }
 
-fn palette_maybe_has_block(palette: &Palette<BlockState>, block_states: &BlockStates) -> bool {
-    match &palette {
-        Palette::SingleValue(id) => block_states.contains(id),
-        Palette::Linear(ids) => ids.iter().any(|id| block_states.contains(id)),
-        Palette::Hashmap(ids) => ids.iter().any(|id| block_states.contains(id)),
-        Palette::Global => true,
+/// A bitset of block state IDs, for checking the raw values in sections that
+/// use the global palette without making a [`BlockState`] for every block.
+struct BlockStateIdSet {
+    words: Box<[u64]>,
+}
+impl BlockStateIdSet {
+    fn new(block_states: &BlockStates) -> Self {
+        let mut words = vec![0; BlockState::MAX_STATE as usize / 64 + 1].into_boxed_slice();
+        for state in &block_states.set {
+            let id = state.id() as usize;
+            words[id / 64] |= 1 << (id % 64);
+        }
+        Self { words }
+    }
+
+    fn contains(&self, id: u64) -> bool {
+        let id = id as usize;
+        self.words
+            .get(id / 64)
+            .is_some_and(|word| word & (1 << (id % 64)) != 0)
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use azalea_core::math;
     use azalea_registry::Block;
 
     use super::*;
-    use crate::{Chunk, PartialChunkStorage};
+    use crate::{Chunk, PartialChunkStorage, heightmap::Heightmap};
 
     #[test]
     fn find_block() {
@@ -307,4 +364,81 @@ mod tests {
         let pos = instance.find_block(BlockPos { x: 0, y: 0, z: 0 }, &Block::Stone.into());
         assert_eq!(pos, Some(BlockPos { x: -1, y: 0, z: 0 }));
     }
+
+    #[test]
+    fn find_blocks_matches_naive_scan() {
+        let mut instance = Instance::default();
+
+        let chunk_storage = &mut instance.chunks;
+        let mut partial_chunk_storage = PartialChunkStorage::default();
+
+        let min_y = chunk_storage.min_y;
+        let height = chunk_storage.height;
+        let mut chunk = Chunk::default();
+        let bits = math::ceil_log2(height + 1) as usize;
+        chunk.heightmaps.insert(
+            HeightmapKind::WorldSurface,
+            Heightmap::new(
+                HeightmapKind::WorldSurface,
+                height,
+                min_y,
+                vec![0; 256usize.div_ceil(64 / bits)].into(),
+            ),
+        );
+        partial_chunk_storage.set(&ChunkPos { x: 0, z: 0 }, Some(chunk), chunk_storage);
+
+        // a few ores in stone, which uses a small palette
+        for y in min_y..min_y + 32 {
+            for x in 0..16 {
+                for z in 0..16 {
+                    let block = if (x + y + z) % 7 == 0 {
+                        Block::DeepslateDiamondOre
+                    } else {
+                        Block::Stone
+                    };
+                    chunk_storage.set_block_state(BlockPos { x, y, z }, block.into());
+                }
+            }
+        }
+        // too many different blocks for anything but the global palette
+        let mut id: u32 = 0;
+        for y in 0..16 {
+            for x in 0..16 {
+                for z in 0..16 {
+                    let state = BlockState::try_from(id).unwrap();
+                    chunk_storage.set_block_state(BlockPos { x, y, z }, state);
+                    id += 5;
+                }
+            }
+        }
+
+        let block_states = BlockStates::from([
+            Block::DeepslateDiamondOre,
+            Block::Stone,
+            Block::OakLog,
+            Block::RedWool,
+        ]);
+
+        let mut found = instance
+            .find_blocks(BlockPos { x: 0, y: 0, z: 0 }, &block_states)
+            .collect::<Vec<_>>();
+        found.sort_by_key(|pos| (pos.x, pos.y, pos.z));
+
+        let mut expected = Vec::new();
+        for x in 0..16 {
+            for y in min_y..min_y + height as i32 {
+                for z in 0..16 {
+                    let pos = BlockPos { x, y, z };
+                    if let Some(state) = instance.chunks.get_block_state(pos)
+                        && block_states.contains(&state)
+                    {
+                        expected.push(pos);
+                    }
+                }
+            }
+        }
+
+        assert!(expected.iter().any(|pos| pos.y >= 0));
+        assert_eq!(found, expected);
+    }
 }
@@ -165,6 +165,57 @@ pub struct Instance {
     pub entity_by_id: IntMap<MinecraftEntityId, Entity>,
 
     pub registries: RegistryHolder,
+
+    /// The time of day in ticks, from the last time packet that the server
+    /// sent us. This isn't updated between packets, and it keeps counting up
+    /// past 24000.
+    pub day_time: Option<u64>,
+
+    /// The world border from the last border packets that the server sent us,
+    /// or `None` if the server never told us about it.
+    pub world_border: Option<WorldBorder>,
+}
+
+/// The square that players aren't allowed to leave.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WorldBorder {
+    pub center_x: f64,
+    pub center_z: f64,
+    /// The length of each side of the border. If the border is moving, this is
+    /// the smaller of the sizes that it's moving between.
+    pub size: f64,
+}
+impl Default for WorldBorder {
+    fn default() -> Self {
+        Self {
+            center_x: 0.,
+            center_z: 0.,
+            // vanilla's default size
+            size: 59_999_968.,
+        }
+    }
+}
+impl WorldBorder {
+    /// Whether the whole block is inside the border.
+    pub fn contains_block(&self, pos: BlockPos) -> bool {
+        self.distance_to_edge(pos) >= 0.
+    }
+
+    /// How far (in blocks) the block is from the closest edge of the border.
+    /// This is 0 if the block is touching the edge, and negative if any part
+    /// of it is outside the border.
+    pub fn distance_to_edge(&self, pos: BlockPos) -> f64 {
+        let half_size = self.size / 2.;
+        let (x, z) = (pos.x as f64, pos.z as f64);
+        [
+            x - (self.center_x - half_size),
+            self.center_x + half_size - (x + 1.),
+            z - (self.center_z - half_size),
+            self.center_z + half_size - (z + 1.),
+        ]
+        .into_iter()
+        .fold(f64::INFINITY, f64::min)
+    }
 }
 
 impl Instance {
@@ -224,6 +275,8 @@ impl From<ChunkStorage> for Instance {
             entities_by_chunk: HashMap::new(),
             entity_by_id: IntMap::default(),
             registries: RegistryHolder::default(),
+            day_time: None,
+            world_border: None,
         }
     }
 }
@@ -1,6 +1,7 @@
 use std::hint::black_box;
 
 use azalea::pathfinder::mining::MiningCache;
+use azalea_inventory::Menu;
 pub use azalea_registry as registry;
 use criterion::{Criterion, criterion_group, criterion_main};
 
@@ -30,6 +31,13 @@ fn benchmark(c: &mut Criterion) {
     c.bench_function("is_liquid waterlogged slab", |b| {
         b.iter(|| mining_cache.is_liquid(black_box(waterlogged_slab)));
     });
+
+    let mining_cache = MiningCache::new(Some(Menu::Player(azalea_inventory::Player::default())));
+    // the first call calculates the cost, and the rest are cached
+    mining_cache.cost_for(stone);
+    c.bench_function("cost_for cached stone", |b| {
+        b.iter(|| mining_cache.cost_for(black_box(stone)));
+    });
 }
 
 criterion_group!(benches, benchmark);
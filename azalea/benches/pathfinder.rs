@@ -7,14 +7,16 @@ use azalea::{
         custom_state::CustomPathfinderStateRef,
         goals::{BlockPosGoal, Goal},
         mining::MiningCache,
+        moves::water::{LOW_AIR_THRESHOLD, SwimmingState, next_swimming_state},
         rel_block_pos::RelBlockPos,
-        world::CachedWorld,
+        settings::MoveSettings,
+        world::{CachedWorld, is_block_state_passable},
     },
 };
 use azalea_core::position::{ChunkBlockPos, ChunkPos};
 use azalea_inventory::Menu;
 use azalea_world::{Chunk, ChunkStorage, PartialChunkStorage};
-use criterion::{Bencher, Criterion, criterion_group, criterion_main};
+use criterion::{Bencher, Criterion, Throughput, criterion_group, criterion_main};
 use parking_lot::RwLock;
 use rand::{Rng, SeedableRng, rngs::StdRng};
 
@@ -117,14 +119,70 @@ fn generate_mining_world(
     (chunks, start, end)
 }
 
+/// A 500 block long strip of flat ground with some pillars on it, for
+/// benchmarking long searches.
+fn generate_long_world(
+    partial_chunks: &mut PartialChunkStorage,
+    size: u32,
+) -> (ChunkStorage, BlockPos, BlockPos) {
+    let size = size as i32;
+
+    let mut chunks = ChunkStorage::default();
+    for chunk_x in -size..size {
+        for chunk_z in -2..2 {
+            let chunk_pos = ChunkPos::new(chunk_x, chunk_z);
+            partial_chunks.set(&chunk_pos, Some(Chunk::default()), &mut chunks);
+        }
+    }
+
+    let mut rng = StdRng::seed_from_u64(0);
+
+    for chunk_x in -size..size {
+        for chunk_z in -2..2 {
+            let chunk_pos = ChunkPos::new(chunk_x, chunk_z);
+            let chunk = chunks.get(&chunk_pos).unwrap();
+            let mut chunk = chunk.write();
+            for x in 0..16_u8 {
+                for z in 0..16_u8 {
+                    chunk.set_block_state(
+                        &ChunkBlockPos::new(x, 1, z),
+                        azalea_registry::Block::Bedrock.into(),
+                        chunks.min_y,
+                    );
+                    if rng.random_bool(0.05) {
+                        for y in 2..=3 {
+                            chunk.set_block_state(
+                                &ChunkBlockPos::new(x, y, z),
+                                azalea_registry::Block::Bedrock.into(),
+                                chunks.min_y,
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    let start = BlockPos::new(-250, 2, 0);
+    let end = BlockPos::new(250, 2, 0);
+    for pos in [start, end] {
+        for y in 2..=3 {
+            chunks.set_block_state(pos.with_y(y), azalea_block::BlockState::AIR);
+        }
+    }
+
+    (chunks, start, end)
+}
+
 fn run_pathfinder_benchmark(
     b: &mut Bencher<'_>,
     generate_world: fn(&mut PartialChunkStorage, u32) -> (ChunkStorage, BlockPos, BlockPos),
+    size: u32,
 ) {
     let mut partial_chunks = PartialChunkStorage::new(32);
     let successors_fn = azalea::pathfinder::moves::default_move;
 
-    let (world, start, end) = generate_world(&mut partial_chunks, 4);
+    let (world, start, end) = generate_world(&mut partial_chunks, size);
 
     let origin = start;
 
@@ -134,24 +192,34 @@ fn run_pathfinder_benchmark(
             MiningCache::new(Some(Menu::Player(azalea_inventory::Player::default())));
         let goal = BlockPosGoal(end);
 
-        let successors = |pos: RelBlockPos| {
-            azalea::pathfinder::call_successors_fn(
+        let custom_state = CustomPathfinderStateRef::default();
+        let successors = |pos: RelBlockPos, swimming: SwimmingState, edges: &mut Vec<_>| {
+            azalea::pathfinder::fill_successors(
                 &cached_world,
                 &mining_cache,
-                &CustomPathfinderStateRef::default(),
+                1.,
+                LOW_AIR_THRESHOLD,
+                MoveSettings::default(),
+                &custom_state,
                 successors_fn,
+                swimming,
                 pos,
+                edges,
             )
         };
 
         let astar::Path {
             movements,
             is_partial: partial,
+            ..
         } = a_star(
             RelBlockPos::get_origin(origin),
+            SwimmingState::default(),
             |n| goal.heuristic(n.apply(origin)),
             successors,
             |n| goal.success(n.apply(origin)),
+            azalea::pathfinder::turn_cost,
+            |swimming, pos| next_swimming_state(&cached_world, swimming, pos),
             PathfinderTimeout::Time(Duration::MAX),
             PathfinderTimeout::Time(Duration::MAX),
         );
@@ -162,17 +230,89 @@ fn run_pathfinder_benchmark(
     })
 }
 
+/// Find the moves from every position in a cube, like A* does when it expands
+/// nodes, so we can see how fast the world is checked.
+fn run_node_expansion_benchmark(b: &mut Bencher<'_>, positions: &[RelBlockPos]) {
+    let mut partial_chunks = PartialChunkStorage::new(32);
+    let (world, origin, _) = generate_mining_world(&mut partial_chunks, 4);
+    let world = Arc::new(RwLock::new(world.into()));
+    let mining_cache = MiningCache::new(Some(Menu::Player(azalea_inventory::Player::default())));
+    let custom_state = CustomPathfinderStateRef::default();
+
+    b.iter(|| {
+        let cached_world = CachedWorld::new(world.clone(), origin);
+        let mut edges = Vec::new();
+        for &pos in positions {
+            azalea::pathfinder::fill_successors(
+                &cached_world,
+                &mining_cache,
+                1.,
+                LOW_AIR_THRESHOLD,
+                MoveSettings::default(),
+                &custom_state,
+                azalea::pathfinder::moves::default_move,
+                SwimmingState::default(),
+                pos,
+                &mut edges,
+            );
+            black_box(&edges);
+        }
+    })
+}
+
+/// Check whether every block in a cube is passable, either with the bitsets
+/// that `CachedWorld` keeps for each section or by getting each block state
+/// from the palette like it used to.
+fn run_passable_benchmark(b: &mut Bencher<'_>, positions: &[RelBlockPos], use_bitsets: bool) {
+    let mut partial_chunks = PartialChunkStorage::new(32);
+    let (world, origin, _) = generate_mining_world(&mut partial_chunks, 4);
+    let cached_world = CachedWorld::new(Arc::new(RwLock::new(world.into())), origin);
+
+    b.iter(|| {
+        let passable = positions
+            .iter()
+            .filter(|&&pos| {
+                if use_bitsets {
+                    cached_world.is_block_passable(pos)
+                } else {
+                    is_block_state_passable(cached_world.get_block_state(pos))
+                }
+            })
+            .count();
+        black_box(passable);
+    })
+}
+
 fn bench_pathfinder(c: &mut Criterion) {
     // c.bench_function("bedrock", |b| {
-    //     run_pathfinder_benchmark(b, generate_bedrock_world);
+    //     run_pathfinder_benchmark(b, generate_bedrock_world, 4);
     // });
     let mut slow_group = c.benchmark_group("slow");
     slow_group.sample_size(10);
     slow_group.bench_function("mining", |b| {
-        run_pathfinder_benchmark(b, generate_mining_world);
+        run_pathfinder_benchmark(b, generate_mining_world, 4);
+    });
+    slow_group.bench_function("500 blocks", |b| {
+        run_pathfinder_benchmark(b, generate_long_world, 16);
     });
     slow_group.finish();
 
+    let positions = (-16..16)
+        .flat_map(|x| (-8..8).flat_map(move |y| (-16..16).map(move |z| RelBlockPos::new(x, y, z))))
+        .collect::<Vec<_>>();
+    let mut world_group = c.benchmark_group("world");
+    world_group.throughput(Throughput::Elements(positions.len() as u64));
+    world_group.bench_function("node expansion", |b| {
+        run_node_expansion_benchmark(b, &positions);
+    });
+    world_group.bench_function("passable (bitsets)", |b| {
+        run_passable_benchmark(b, &positions, true);
+    });
+    world_group.bench_function("passable (palette)", |b| {
+        run_passable_benchmark(b, &positions, false);
+    });
+    world_group.finish();
+
     c.bench_function("weighted_node_le g_score", |b| {
         b.iter(|| {
             WeightedNode::le(
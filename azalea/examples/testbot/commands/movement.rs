@@ -54,10 +54,9 @@ pub fn register(commands: &mut CommandDispatcher<Mutex<CommandSource>>) {
                         let z = get_integer(ctx, "z").unwrap();
                         println!("goto radius {radius}, position: {x} {y} {z}");
                         source.reply("ok");
-                        source.bot.start_goto(RadiusGoal {
-                            pos: BlockPos::new(x, y, z).center(),
-                            radius,
-                        });
+                        source
+                            .bot
+                            .start_goto(RadiusGoal::new(BlockPos::new(x, y, z).center(), radius));
                         1
                     }),
                 )),
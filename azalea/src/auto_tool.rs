@@ -1,7 +1,7 @@
 use azalea_block::{BlockState, BlockTrait, fluid_state::FluidKind};
 use azalea_client::{Client, inventory::Inventory};
 use azalea_core::position::BlockPos;
-use azalea_entity::{FluidOnEyes, Physics};
+use azalea_entity::{ActiveEffects, FluidOnEyes, Physics};
 use azalea_inventory::{ItemStack, Menu, components};
 
 use crate::BotClientExt;
@@ -20,11 +20,18 @@ pub trait AutoToolClientExt {
 impl AutoToolClientExt for Client {
     fn best_tool_in_hotbar_for_block(&self, block: BlockState) -> BestToolResult {
         let mut ecs = self.ecs.lock();
-        let (inventory, physics, fluid_on_eyes) =
-            self.query::<(&Inventory, &Physics, &FluidOnEyes)>(&mut ecs);
+        let (inventory, physics, fluid_on_eyes, active_effects) =
+            self.query::<(&Inventory, &Physics, &FluidOnEyes, &ActiveEffects)>(&mut ecs);
         let menu = &inventory.inventory_menu;
 
-        accurate_best_tool_in_hotbar_for_block(block, menu, physics, fluid_on_eyes)
+        accurate_best_tool_in_hotbar_for_block(
+            block,
+            menu,
+            physics,
+            fluid_on_eyes,
+            active_effects,
+            DEFAULT_MIN_TOOL_DURABILITY,
+        )
     }
 
     async fn mine_with_auto_tool(&self, block_pos: BlockPos) {
@@ -39,11 +46,13 @@ impl AutoToolClientExt for Client {
     }
 }
 
-/// Returns the best tool in the hotbar for the given block.
+/// Returns the best tool in the hotbar for the given block, skipping tools
+/// that are about to break.
 ///
 /// Note that this doesn't take into account whether the player is on the ground
-/// or in water, use [`accurate_best_tool_in_hotbar_for_block`] instead if you
-/// care about those things.
+/// or in water, or their effects, use
+/// [`accurate_best_tool_in_hotbar_for_block`] instead if you care about those
+/// things.
 pub fn best_tool_in_hotbar_for_block(block: BlockState, menu: &Menu) -> BestToolResult {
     let mut physics = Physics::default();
     physics.set_on_ground(true);
@@ -53,20 +62,33 @@ pub fn best_tool_in_hotbar_for_block(block: BlockState, menu: &Menu) -> BestTool
         menu,
         &physics,
         &FluidOnEyes::new(FluidKind::Empty),
+        &ActiveEffects::default(),
+        DEFAULT_MIN_TOOL_DURABILITY,
     )
 }
 
+/// Tools with this many uses left or fewer are only used when nothing else in
+/// the hotbar can break the block, so we don't break them.
+///
+/// This is what [`best_tool_in_hotbar_for_block`] uses, see
+/// [`accurate_best_tool_in_hotbar_for_block`] for using a different number.
+pub const DEFAULT_MIN_TOOL_DURABILITY: i32 = 10;
+
+/// Returns the best tool in the hotbar for the given block.
+///
+/// Tools that have `min_durability` uses left or fewer are skipped, even if
+/// that means using a slower tool or our hand, unless they're the only thing
+/// that can break the block.
 pub fn accurate_best_tool_in_hotbar_for_block(
     block: BlockState,
     menu: &Menu,
     physics: &Physics,
     fluid_on_eyes: &FluidOnEyes,
+    active_effects: &ActiveEffects,
+    min_durability: i32,
 ) -> BestToolResult {
     let hotbar_slots = &menu.slots()[menu.hotbar_slots_range()];
 
-    let mut best_speed = 0.;
-    let mut best_slot = None;
-
     let block = Box::<dyn BlockTrait>::from(block);
     let registry_block = block.as_registry_block();
 
@@ -81,62 +103,105 @@ pub fn accurate_best_tool_in_hotbar_for_block(
         };
     }
 
-    // find the first slot that has an item without durability
+    // the best slot and its speed, and the same for tools that are about to break
+    let mut best = None::<(usize, f32)>;
+    let mut best_worn = None::<(usize, f32)>;
     for (i, item_slot) in hotbar_slots.iter().enumerate() {
-        let this_item_speed;
-        match item_slot {
-            ItemStack::Empty => {
-                this_item_speed = Some(azalea_entity::mining::get_mine_progress(
-                    block.as_ref(),
-                    azalea_registry::Item::Air,
-                    menu,
-                    fluid_on_eyes,
-                    physics,
-                ));
-            }
-            ItemStack::Present(item_stack) => {
-                // lazy way to avoid checking durability since azalea doesn't have durability
-                // data yet
-                if !item_stack.component_patch.has::<components::Damage>() {
-                    this_item_speed = Some(azalea_entity::mining::get_mine_progress(
-                        block.as_ref(),
-                        item_stack.kind,
-                        menu,
-                        fluid_on_eyes,
-                        physics,
-                    ));
-                } else {
-                    this_item_speed = None;
-                }
-            }
-        }
-        if let Some(this_item_speed) = this_item_speed
-            && this_item_speed > best_speed
-        {
-            best_slot = Some(i);
-            best_speed = this_item_speed;
+        let this_item_speed = azalea_entity::mining::get_mine_progress(
+            block.as_ref(),
+            item_slot.kind(),
+            menu,
+            fluid_on_eyes,
+            physics,
+            active_effects,
+        );
+        let is_worn =
+            remaining_durability(item_slot).is_some_and(|durability| durability <= min_durability);
+        let best = if is_worn { &mut best_worn } else { &mut best };
+        if this_item_speed > best.map_or(0., |(_, speed)| speed) {
+            *best = Some((i, this_item_speed));
         }
     }
 
-    // now check every item
-    for (i, item_slot) in hotbar_slots.iter().enumerate() {
-        if let ItemStack::Present(item_slot) = item_slot {
-            let this_item_speed = azalea_entity::mining::get_mine_progress(
-                block.as_ref(),
-                item_slot.kind,
-                menu,
-                fluid_on_eyes,
-                physics,
-            );
-            if this_item_speed > best_speed {
-                best_slot = Some(i);
-                best_speed = this_item_speed;
-            }
+    let (index, percentage_per_tick) = best.or(best_worn).unwrap_or((0, 0.));
+    BestToolResult {
+        index,
+        percentage_per_tick,
+    }
+}
+
+/// How many more times the item can be used before it breaks, or `None` if it
+/// doesn't have durability or is unbreakable.
+pub fn remaining_durability(item: &ItemStack) -> Option<i32> {
+    if item.get_component::<components::Unbreakable>().is_some() {
+        return None;
+    }
+    let max_damage = item.get_component::<components::MaxDamage>()?.amount;
+    let damage = item
+        .get_component::<components::Damage>()
+        .map_or(0, |damage| damage.amount);
+    Some(max_damage - damage)
+}
+
+#[cfg(test)]
+mod tests {
+    use azalea_inventory::{ItemStackData, Player, components::DataComponent};
+    use azalea_registry::{Block, Item};
+
+    use super::*;
+
+    fn pickaxe_with_durability(durability: i32) -> ItemStack {
+        let mut item = ItemStackData::from(Item::DiamondPickaxe);
+        let max_damage = item
+            .get_component::<components::MaxDamage>()
+            .unwrap()
+            .amount;
+        item.component_patch.components.insert(
+            components::Damage::KIND,
+            Some(Box::new(components::Damage {
+                amount: max_damage - durability,
+            })),
+        );
+        ItemStack::Present(item)
+    }
+
+    fn hotbar(items: impl IntoIterator<Item = ItemStack>) -> Menu {
+        let mut menu = Menu::Player(Player::default());
+        for (slot, item) in Player::HOTBAR_SLOTS.zip(items) {
+            *menu.slot_mut(slot).unwrap() = item;
         }
+        menu
     }
 
-    BestToolResult {
-        index: best_slot.unwrap_or(0),
-        percentage_per_tick: best_speed,
+    #[test]
+    fn test_skip_nearly_broken_tool() {
+        let menu = hotbar([pickaxe_with_durability(5), pickaxe_with_durability(1000)]);
+        let result = best_tool_in_hotbar_for_block(Block::Stone.into(), &menu);
+        assert_eq!(result.index, 1);
+    }
+
+    #[test]
+    fn test_prefer_slower_tool_over_nearly_broken_one() {
+        let menu = hotbar([pickaxe_with_durability(5), Item::WoodenPickaxe.into()]);
+        let result = best_tool_in_hotbar_for_block(Block::Stone.into(), &menu);
+        assert_eq!(result.index, 1);
+
+        // unless we're fine with using it
+        let result = accurate_best_tool_in_hotbar_for_block(
+            Block::Stone.into(),
+            &menu,
+            &Physics::default(),
+            &FluidOnEyes::new(FluidKind::Empty),
+            &ActiveEffects::default(),
+            0,
+        );
+        assert_eq!(result.index, 0);
+    }
+
+    #[test]
+    fn test_use_nearly_broken_tool_if_its_the_only_option() {
+        let menu = hotbar(std::iter::repeat_n(pickaxe_with_durability(5), 9));
+        let result = best_tool_in_hotbar_for_block(Block::Stone.into(), &menu);
+        assert!(result.percentage_per_tick > 0.);
     }
 }
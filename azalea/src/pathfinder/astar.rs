@@ -1,22 +1,28 @@
 use std::{
     cmp::{self},
-    collections::BinaryHeap,
+    collections::{BinaryHeap, hash_map},
     fmt::{self, Debug},
-    hash::{BuildHasherDefault, Hash},
+    hash::Hash,
     time::{Duration, Instant},
 };
 
-use indexmap::IndexMap;
+use nohash_hasher::{IntMap, IsEnabled};
 use num_format::ToFormattedString;
-use rustc_hash::FxHasher;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use tracing::{debug, trace, warn};
 
+use super::metrics::PathfinderMetrics;
+
 pub struct Path<P, M>
 where
     P: Eq + Hash + Copy + Debug,
 {
     pub movements: Vec<Movement<P, M>>,
     pub is_partial: bool,
+    /// The counters from the search. The ones about the world aren't filled in
+    /// here since the search doesn't know about the world.
+    pub metrics: PathfinderMetrics,
 }
 
 // used for better results when timing out
@@ -25,25 +31,58 @@ const COEFFICIENTS: [f32; 7] = [1.5, 2., 2.5, 3., 4., 5., 10.];
 
 const MIN_IMPROVEMENT: f32 = 0.01;
 
-type FxIndexMap<K, V> = IndexMap<K, V, BuildHasherDefault<FxHasher>>;
+/// The `came_from` of the start node.
+const NO_NODE: u32 = u32::MAX;
+
+/// Only one in this many nodes has its heap operations and successors timed,
+/// since reading the clock for every node would slow the search down. The
+/// times for those nodes are multiplied by this to estimate the totals.
+const TIMING_SAMPLE_INTERVAL: usize = 256;
 
 // Sources:
 // - https://en.wikipedia.org/wiki/A*_search_algorithm
 // - https://github.com/evenfurther/pathfinding/blob/main/src/directed/astar.rs
 // - https://github.com/cabaletta/baritone/blob/1.19.4/src/main/java/baritone/pathing/calc/AbstractNodeCostSearch.java
-pub fn a_star<P, M, HeuristicFn, SuccessorsFn, SuccessFn>(
+//
+// `turn_cost` is called with the previous node, the current node, and the
+// neighbor, and its result is added to the cost of the edge. The nodes only
+// keep track of their position, so this is only a tiebreaker and doesn't make
+// the search direction-aware.
+//
+// Every node also carries some extra state (like how long we've been swimming
+// for) that's passed to `successors`, starting with `start_state`. The state of
+// a neighbor is calculated with `next_state` from the state of the node that we
+// got to it from. Like
+// with `turn_cost`, nodes with the same position but a different state are
+// still treated as the same node.
+//
+// `successors` adds the edges from a node to the Vec that it's given, which is
+// always empty. The same Vec is reused for every node so we don't have to
+// allocate a new one every time.
+//
+// The nodes are stored in a Vec and referred to by their index in it, and the
+// map from positions to indexes uses the position's hash directly, so `P`
+// should have a `Hash` implementation that mixes its bits well.
+#[allow(clippy::too_many_arguments)]
+pub fn a_star<P, S, M, HeuristicFn, SuccessorsFn, SuccessFn, TurnCostFn, NextStateFn>(
     start: P,
+    start_state: S,
     heuristic: HeuristicFn,
     mut successors: SuccessorsFn,
     success: SuccessFn,
+    turn_cost: TurnCostFn,
+    next_state: NextStateFn,
     min_timeout: PathfinderTimeout,
     max_timeout: PathfinderTimeout,
 ) -> Path<P, M>
 where
-    P: Eq + Hash + Copy + Debug,
+    P: Eq + Hash + IsEnabled + Copy + Debug,
+    S: Copy,
     HeuristicFn: Fn(P) -> f32,
-    SuccessorsFn: FnMut(P) -> Vec<Edge<P, M>>,
+    SuccessorsFn: FnMut(P, S, &mut Vec<Edge<P, M>>),
     SuccessFn: Fn(P) -> bool,
+    TurnCostFn: Fn(P, P, P) -> f32,
+    NextStateFn: Fn(S, P) -> S,
 {
     let start_time = Instant::now();
 
@@ -53,73 +92,107 @@ where
         f_score: 0.,
         index: 0,
     });
-    let mut nodes: FxIndexMap<P, Node> = IndexMap::default();
-    nodes.insert(
-        start,
-        Node {
-            came_from: usize::MAX,
-            g_score: 0.,
-        },
-    );
+    let mut nodes = vec![Node {
+        position: start,
+        came_from: NO_NODE,
+        g_score: 0.,
+        state: start_state,
+    }];
+    let mut node_indexes = IntMap::<P, u32>::default();
+    node_indexes.insert(start, 0);
 
-    let mut best_paths: [usize; 7] = [0; 7];
+    let mut best_paths: [u32; 7] = [0; 7];
     let mut best_path_scores: [f32; 7] = [heuristic(start); 7];
 
     let mut num_nodes = 0_usize;
     let mut num_movements = 0;
+    let mut metrics = PathfinderMetrics::default();
 
-    while let Some(WeightedNode { index, g_score, .. }) = open_set.pop() {
+    let mut edges = Vec::with_capacity(16);
+    let mut sampled_heap_time = Duration::ZERO;
+    let mut sampled_world_access_time = Duration::ZERO;
+
+    loop {
+        let is_timed = num_nodes.is_multiple_of(TIMING_SAMPLE_INTERVAL);
+        let popped = timed(is_timed, &mut sampled_heap_time, || open_set.pop());
+        let Some(WeightedNode { index, g_score, .. }) = popped else {
+            break;
+        };
         num_nodes += 1;
 
-        let (&node, node_data) = nodes.get_index(index).unwrap();
+        let node_data = &nodes[index as usize];
+        let node = node_data.position;
         if success(node) {
             debug!("Nodes considered: {num_nodes}");
 
+            metrics.nodes_expanded = num_nodes;
+            metrics.nodes_generated = num_movements;
+            metrics.open_set_size = open_set.len();
+            metrics.set_sampled_times(
+                sampled_world_access_time,
+                sampled_heap_time,
+                TIMING_SAMPLE_INTERVAL,
+            );
             return Path {
-                movements: reconstruct_path(nodes, index, successors),
+                movements: reconstruct_path(&nodes, index, successors),
                 is_partial: false,
+                metrics,
             };
         }
 
         if g_score > node_data.g_score {
             continue;
         }
+        let previous_node =
+            (node_data.came_from != NO_NODE).then(|| nodes[node_data.came_from as usize].position);
+        let state = node_data.state;
 
-        for neighbor in successors(node) {
-            let tentative_g_score = g_score + neighbor.cost;
-            // let neighbor_heuristic = heuristic(neighbor.movement.target);
-            let neighbor_heuristic;
+        edges.clear();
+        timed(is_timed, &mut sampled_world_access_time, || {
+            successors(node, state, &mut edges)
+        });
+        for neighbor in edges.drain(..) {
+            let neighbor_position = neighbor.movement.target;
+            let mut tentative_g_score = g_score + neighbor.cost;
+            if let Some(previous_node) = previous_node {
+                tentative_g_score += turn_cost(previous_node, node, neighbor_position);
+            }
+            let neighbor_state = next_state(state, neighbor_position);
             let neighbor_index;
 
             num_movements += 1;
 
-            match nodes.entry(neighbor.movement.target) {
-                indexmap::map::Entry::Occupied(mut e) => {
-                    if e.get().g_score > tentative_g_score {
-                        neighbor_heuristic = heuristic(*e.key());
-                        neighbor_index = e.index();
-                        e.insert(Node {
-                            came_from: index,
-                            g_score: tentative_g_score,
-                        });
+            let new_node = Node {
+                position: neighbor_position,
+                came_from: index,
+                g_score: tentative_g_score,
+                state: neighbor_state,
+            };
+            match node_indexes.entry(neighbor_position) {
+                hash_map::Entry::Occupied(e) => {
+                    neighbor_index = *e.get();
+                    let neighbor_node = &mut nodes[neighbor_index as usize];
+                    if neighbor_node.g_score > tentative_g_score {
+                        *neighbor_node = new_node;
                     } else {
                         continue;
                     }
                 }
-                indexmap::map::Entry::Vacant(e) => {
-                    neighbor_heuristic = heuristic(*e.key());
-                    neighbor_index = e.index();
-                    e.insert(Node {
-                        came_from: index,
-                        g_score: tentative_g_score,
-                    });
+                hash_map::Entry::Vacant(e) => {
+                    neighbor_index = nodes.len() as u32;
+                    e.insert(neighbor_index);
+                    nodes.push(new_node);
                 }
             }
+            let neighbor_heuristic = heuristic(neighbor_position);
 
-            open_set.push(WeightedNode {
+            let weighted_node = WeightedNode {
                 index: neighbor_index,
                 g_score: tentative_g_score,
                 f_score: tentative_g_score + neighbor_heuristic,
+            };
+            timed(is_timed, &mut sampled_heap_time, || {
+                open_set.push(weighted_node)
             });
 
             for (coefficient_i, &coefficient) in COEFFICIENTS.iter().enumerate() {
@@ -130,7 +203,6 @@ where
                 }
             }
         }
-
         // check for timeout every ~10ms
         if num_nodes.is_multiple_of(10_000) {
             let min_timeout_reached = match min_timeout {
@@ -173,13 +245,35 @@ where
         num_movements_per_second.to_formatted_string(&num_format::Locale::en),
     );
 
+    metrics.nodes_expanded = num_nodes;
+    metrics.nodes_generated = num_movements;
+    metrics.open_set_size = open_set.len();
+    metrics.set_sampled_times(
+        sampled_world_access_time,
+        sampled_heap_time,
+        TIMING_SAMPLE_INTERVAL,
+    );
     Path {
-        movements: reconstruct_path(nodes, best_path, successors),
+        movements: reconstruct_path(&nodes, best_path, successors),
         is_partial: true,
+        metrics,
+    }
+}
+
+/// Run `f`, and add how long it took to `total` if `is_timed` is true.
+#[inline]
+fn timed<T>(is_timed: bool, total: &mut Duration, f: impl FnOnce() -> T) -> T {
+    if is_timed {
+        let start = Instant::now();
+        let result = f();
+        *total += start.elapsed();
+        result
+    } else {
+        f()
     }
 }
 
-fn determine_best_path(best_paths: [usize; 7], start: usize) -> usize {
+fn determine_best_path(best_paths: [u32; 7], start: u32) -> u32 {
     // this basically makes sure we don't create a path that's really short
 
     for node in best_paths {
@@ -191,27 +285,32 @@ fn determine_best_path(best_paths: [usize; 7], start: usize) -> usize {
     best_paths[0]
 }
 
-fn reconstruct_path<P, M, SuccessorsFn>(
-    nodes: FxIndexMap<P, Node>,
-    mut current_index: usize,
+fn reconstruct_path<P, S, M, SuccessorsFn>(
+    nodes: &[Node<P, S>],
+    mut current_index: u32,
     mut successors: SuccessorsFn,
 ) -> Vec<Movement<P, M>>
 where
     P: Eq + Hash + Copy + Debug,
-    SuccessorsFn: FnMut(P) -> Vec<Edge<P, M>>,
+    S: Copy,
+    SuccessorsFn: FnMut(P, S, &mut Vec<Edge<P, M>>),
 {
     let mut path = Vec::new();
-    while let Some((&node_position, node)) = nodes.get_index(current_index) {
-        if node.came_from == usize::MAX {
+    let mut edges = Vec::new();
+    while let Some(node) = nodes.get(current_index as usize) {
+        if node.came_from == NO_NODE {
             break;
         }
-        let came_from_position = *nodes.get_index(node.came_from).unwrap().0;
+        let node_position = node.position;
+        let came_from_node = &nodes[node.came_from as usize];
 
         // find the movement data for this successor, we have to do this again because
         // we don't include the movement data in the Node (as an optimization)
         let mut best_successor = None;
         let mut best_successor_cost = f32::INFINITY;
-        for successor in successors(came_from_position) {
+        edges.clear();
+        successors(came_from_node.position, came_from_node.state, &mut edges);
+        for successor in edges.drain(..) {
             if successor.movement.target == node_position && successor.cost < best_successor_cost {
                 best_successor_cost = successor.cost;
                 best_successor = Some(successor);
@@ -235,9 +334,13 @@ where
     path
 }
 
-pub struct Node {
-    pub came_from: usize,
+pub struct Node<P, S> {
+    pub position: P,
+    /// The index of the node that we got here from, or `u32::MAX` for the
+    /// start node.
+    pub came_from: u32,
     pub g_score: f32,
+    pub state: S,
 }
 
 #[derive(Clone, Debug)]
@@ -275,7 +378,8 @@ pub struct WeightedNode {
     pub f_score: f32,
     /// The actual cost to get to this node
     pub g_score: f32,
-    pub index: usize,
+    /// The index of the node in the Vec of nodes.
+    pub index: u32,
 }
 
 impl Ord for WeightedNode {
@@ -297,6 +401,7 @@ impl PartialOrd for WeightedNode {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum PathfinderTimeout {
     /// Time out after a certain duration has passed. This is a good default so
     /// you don't waste too much time calculating a path if you're on a slow
@@ -0,0 +1,393 @@
+//! Capturing pathfinder failures so they can be reproduced in tests.
+//!
+//! Pathfinding bugs on real servers are usually impossible to reproduce
+//! without the exact world the bot was in. If you add the
+//! [`CapturePathfinderFailures`] component to a bot, then every time it fails
+//! to find a path or gets stuck while executing one, the parts of the world
+//! that the pathfinder looked at are written to a file together with the
+//! parameters of the search.
+//!
+//! The file can then be loaded with [`PathfinderCapture::read`] and turned
+//! back into a [`Simulation`] or a [`CachedWorld`] to re-run the search.
+
+use std::{
+    collections::{BTreeSet, HashMap},
+    fs,
+    io::{self, Cursor, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use azalea_buf::{AzBuf, AzaleaRead, AzaleaWrite, BufReadError};
+use azalea_client::inventory::Inventory;
+use azalea_core::position::{BlockPos, ChunkPos, ChunkSectionPos, Vec3};
+use azalea_entity::Position;
+use azalea_inventory::ItemStack;
+use azalea_world::{
+    Chunk, ChunkStorage, Instance, PartialChunkStorage, Section, chunk_storage::section_index,
+};
+use bevy_ecs::prelude::*;
+use parking_lot::RwLock;
+use tracing::{info, warn};
+
+use super::{
+    CalculatePathOpts, GotoEvent, GotoOptions, PathFoundEvent, Pathfinder,
+    astar::PathfinderTimeout,
+    goals::Goal,
+    mining::MiningCache,
+    moves::SuccessorsFn,
+    requirements::Consumable,
+    simulation::{SimulatedPlayerBundle, Simulation},
+    world::{CachedWorld, PathEquipment},
+};
+
+/// A component that makes the pathfinder write a [`PathfinderCapture`] to the
+/// given directory whenever it fails to find a path or gets stuck.
+///
+/// This is meant for debugging, since capturing makes path calculations a bit
+/// slower.
+#[derive(Component, Clone, Debug)]
+pub struct CapturePathfinderFailures {
+    pub directory: PathBuf,
+}
+
+#[derive(AzBuf, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CaptureReason {
+    /// The pathfinder couldn't find any path to the goal.
+    NoPath,
+    /// The bot stopped making progress while executing a path.
+    Stalled,
+}
+
+/// A snapshot of everything that's needed to re-run a pathfinder search.
+///
+/// Only the chunk sections that were looked at by the search are included, so
+/// captures stay small.
+#[derive(AzBuf, Clone, Debug)]
+pub struct PathfinderCapture {
+    pub reason: CaptureReason,
+    /// The position that the search started from.
+    pub start: BlockPos,
+    /// The exact position of the bot when the capture was made.
+    pub position: Vec3,
+    /// The `Debug` representation of the goal.
+    ///
+    /// Goals can't be serialized, so you'll have to recreate the goal from this
+    /// when replaying the capture.
+    pub goal: String,
+    pub allow_mining: bool,
+    pub retry_on_no_path: bool,
+    pub careful_mode_multiplier: f32,
+    pub min_timeout: PathfinderTimeout,
+    pub max_timeout: PathfinderTimeout,
+    /// Every slot in the bot's inventory menu.
+    pub inventory: Vec<ItemStack>,
+    pub min_y: i32,
+    pub height: u32,
+    pub sections: Vec<CapturedSection>,
+}
+
+#[derive(AzBuf, Clone, Debug)]
+pub struct CapturedSection {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+    pub section: Section,
+}
+
+impl PathfinderCapture {
+    /// Create a capture of the given search parameters and sections of the
+    /// world.
+    ///
+    /// The position and inventory of the bot are left empty, you should set
+    /// them afterwards if they're available.
+    pub fn new(
+        reason: CaptureReason,
+        opts: &CalculatePathOpts,
+        instance: &Instance,
+        section_positions: impl IntoIterator<Item = ChunkSectionPos>,
+    ) -> Self {
+        Self {
+            reason,
+            start: opts.start,
+            position: opts.start.center_bottom(),
+            goal: format!("{:?}", opts.goal),
+            allow_mining: opts.allow_mining,
+            retry_on_no_path: opts.retry_on_no_path,
+            careful_mode_multiplier: opts.careful_mode_multiplier,
+            min_timeout: opts.min_timeout,
+            max_timeout: opts.max_timeout,
+            inventory: Vec::new(),
+            min_y: instance.chunks.min_y,
+            height: instance.chunks.height,
+            sections: capture_sections(instance, section_positions),
+        }
+    }
+
+    /// Create a capture for a bot that got stuck while executing a path.
+    pub fn stalled(
+        pathfinder: &Pathfinder,
+        start: BlockPos,
+        position: Vec3,
+        inventory: &Inventory,
+        instance: &Instance,
+        section_positions: impl IntoIterator<Item = ChunkSectionPos>,
+    ) -> Self {
+        Self {
+            reason: CaptureReason::Stalled,
+            start,
+            position,
+            goal: pathfinder
+                .goal
+                .as_ref()
+                .map(|goal| format!("{goal:?}"))
+                .unwrap_or_default(),
+            allow_mining: pathfinder.allow_mining,
+            retry_on_no_path: pathfinder.retry_on_no_path,
+            careful_mode_multiplier: pathfinder.careful_mode_multiplier,
+            min_timeout: pathfinder.min_timeout.unwrap_or_default(),
+            max_timeout: pathfinder.max_timeout.unwrap_or_default(),
+            inventory: inventory.inventory_menu.slots(),
+            min_y: instance.chunks.min_y,
+            height: instance.chunks.height,
+            sections: capture_sections(instance, section_positions),
+        }
+    }
+
+    pub fn read(bytes: &[u8]) -> Result<Self, BufReadError> {
+        Self::azalea_read(&mut Cursor::new(bytes))
+    }
+    pub fn write(&self, buf: &mut impl Write) -> io::Result<()> {
+        self.azalea_write(buf)
+    }
+
+    /// Recreate the captured chunks.
+    ///
+    /// The [`ChunkStorage`] only holds weak references to the chunks, so the
+    /// returned [`PartialChunkStorage`] has to be kept alive for as long as the
+    /// chunks are used.
+    pub fn load_chunks(&self) -> (PartialChunkStorage, ChunkStorage) {
+        let view_center = ChunkPos::from(self.start);
+
+        let mut chunks = HashMap::<ChunkPos, Chunk>::new();
+        for captured in &self.sections {
+            let chunk_pos = ChunkPos::new(captured.x, captured.z);
+            let chunk = chunks.entry(chunk_pos).or_insert_with(|| Chunk {
+                sections: vec![Section::default(); (self.height / 16) as usize].into(),
+                heightmaps: Default::default(),
+            });
+            let index = section_index(captured.y * 16, self.min_y) as usize;
+            if let Some(section) = chunk.sections.get_mut(index) {
+                *section = captured.section.clone();
+            }
+        }
+
+        let chunk_radius = chunks
+            .keys()
+            .map(|pos| u32::max(pos.x.abs_diff(view_center.x), pos.z.abs_diff(view_center.z)))
+            .max()
+            .unwrap_or_default();
+
+        let mut partial_chunks = PartialChunkStorage::new(chunk_radius);
+        partial_chunks.update_view_center(view_center);
+        let mut chunk_storage = ChunkStorage::new(self.height, self.min_y);
+        for (chunk_pos, chunk) in chunks {
+            partial_chunks.set(&chunk_pos, Some(chunk), &mut chunk_storage);
+        }
+
+        (partial_chunks, chunk_storage)
+    }
+
+    /// Create a [`Simulation`] with the bot at the captured position and with
+    /// the captured inventory.
+    pub fn simulation(&self, chunks: ChunkStorage) -> Simulation {
+        let mut player = SimulatedPlayerBundle::new(self.position);
+        player.inventory.inventory_menu = self.inventory_menu();
+        Simulation::new(chunks, player)
+    }
+
+    /// Create a [`CachedWorld`] like the one that was used for the search.
+    pub fn cached_world(&self, chunks: ChunkStorage) -> CachedWorld {
+        CachedWorld::new(Arc::new(RwLock::new(chunks.into())), self.start)
+            .with_equipment(captured_equipment(&self.inventory_menu()))
+    }
+
+    /// The options to pass to [`calculate_path`](super::calculate_path) to
+    /// re-run the exact search that was captured.
+    pub fn calculate_path_opts(
+        &self,
+        chunks: ChunkStorage,
+        goal: Arc<dyn Goal>,
+        successors_fn: SuccessorsFn,
+    ) -> CalculatePathOpts {
+        let inventory_menu = self.inventory_menu();
+        let opts = CalculatePathOpts::new(
+            Entity::PLACEHOLDER,
+            self.start,
+            goal,
+            Arc::new(RwLock::new(chunks.into())),
+        )
+        .successors_fn(successors_fn)
+        .retry_on_no_path(self.retry_on_no_path)
+        .careful_mode_multiplier(self.careful_mode_multiplier)
+        .equipment(captured_equipment(&inventory_menu))
+        .min_timeout(self.min_timeout)
+        .max_timeout(self.max_timeout);
+        if self.allow_mining {
+            opts.mining(MiningCache::new(Some(inventory_menu)))
+        } else {
+            opts
+        }
+    }
+
+    /// A [`GotoEvent`] with the same parameters as the captured search, for
+    /// replaying it in a [`Simulation`].
+    pub fn goto_event(
+        &self,
+        entity: Entity,
+        goal: Arc<dyn Goal>,
+        successors_fn: SuccessorsFn,
+    ) -> GotoEvent {
+        GotoEvent {
+            entity,
+            goal,
+            options: GotoOptions::default()
+                .successors_fn(successors_fn)
+                .allow_mining(self.allow_mining)
+                .retry_on_no_path(self.retry_on_no_path)
+                .min_timeout(self.min_timeout)
+                .max_timeout(self.max_timeout)
+                .careful_mode_multiplier(self.careful_mode_multiplier),
+        }
+    }
+
+    fn inventory_menu(&self) -> azalea_inventory::Menu {
+        let mut menu = Inventory::default().inventory_menu;
+        for (i, item) in self.inventory.iter().enumerate() {
+            if let Some(slot) = menu.slot_mut(i) {
+                *slot = item.clone();
+            }
+        }
+        menu
+    }
+}
+
+/// The equipment that a captured search used.
+fn captured_equipment(inventory_menu: &azalea_inventory::Menu) -> PathEquipment {
+    // whether placing blocks was allowed isn't captured, so assume that it wasn't,
+    // like the default
+    PathEquipment::from_menu(inventory_menu).without(&[Consumable::ThrowawayBlock])
+}
+
+fn capture_sections(
+    instance: &Instance,
+    section_positions: impl IntoIterator<Item = ChunkSectionPos>,
+) -> Vec<CapturedSection> {
+    let mut captured = Vec::new();
+    let mut seen = BTreeSet::new();
+    for section_pos in section_positions {
+        if !seen.insert(section_pos) {
+            continue;
+        }
+        let Some(chunk) = instance.chunks.get(&ChunkPos::from(section_pos)) else {
+            continue;
+        };
+        let chunk = chunk.read();
+        let Some(section) = chunk
+            .sections
+            .get(section_index(section_pos.y * 16, instance.chunks.min_y) as usize)
+        else {
+            continue;
+        };
+        captured.push(CapturedSection {
+            x: section_pos.x,
+            y: section_pos.y,
+            z: section_pos.z,
+            section: section.clone(),
+        });
+    }
+    captured
+}
+
+/// The sections in a 3x3x3 area around each of the given positions.
+pub fn sections_around(
+    positions: impl IntoIterator<Item = BlockPos>,
+) -> impl Iterator<Item = ChunkSectionPos> {
+    positions.into_iter().flat_map(|pos| {
+        let center = ChunkSectionPos::from(pos);
+        (-1..=1).flat_map(move |x| {
+            (-1..=1)
+                .flat_map(move |y| (-1..=1).map(move |z| center + ChunkSectionPos::new(x, y, z)))
+        })
+    })
+}
+
+/// Write the capture to a new file in the directory.
+pub fn write_capture_to_directory(directory: &Path, capture: &PathfinderCapture) {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    let path = directory.join(format!("pathfinder-capture-{timestamp}.bin"));
+
+    let mut buf = Vec::new();
+    let result = capture
+        .write(&mut buf)
+        .and_then(|()| fs::create_dir_all(directory))
+        .and_then(|()| fs::write(&path, buf));
+    match result {
+        Ok(()) => info!("wrote pathfinder capture to {}", path.display()),
+        Err(err) => warn!("failed to write pathfinder capture: {err}"),
+    }
+}
+
+/// Write the captures from failed path calculations for bots that have
+/// [`CapturePathfinderFailures`].
+pub fn write_failed_path_captures(
+    mut events: EventReader<PathFoundEvent>,
+    query: Query<(&CapturePathfinderFailures, &Position, &Inventory)>,
+) {
+    for event in events.read() {
+        let Some(capture) = &event.capture else {
+            continue;
+        };
+        if event.path.as_ref().is_some_and(|path| !path.is_empty()) {
+            continue;
+        }
+        let Ok((capture_failures, position, inventory)) = query.get(event.entity) else {
+            continue;
+        };
+
+        let mut capture = (**capture).clone();
+        capture.position = **position;
+        capture.inventory = inventory.inventory_menu.slots();
+        write_capture_to_directory(&capture_failures.directory, &capture);
+    }
+}
+
+impl AzaleaRead for PathfinderTimeout {
+    fn azalea_read(buf: &mut Cursor<&[u8]>) -> Result<Self, BufReadError> {
+        let is_time = bool::azalea_read(buf)?;
+        let value = u64::azalea_read(buf)?;
+        Ok(if is_time {
+            PathfinderTimeout::Time(Duration::from_millis(value))
+        } else {
+            PathfinderTimeout::Nodes(value as usize)
+        })
+    }
+}
+impl AzaleaWrite for PathfinderTimeout {
+    fn azalea_write(&self, buf: &mut impl Write) -> io::Result<()> {
+        match self {
+            PathfinderTimeout::Time(duration) => {
+                true.azalea_write(buf)?;
+                (duration.as_millis() as u64).azalea_write(buf)
+            }
+            PathfinderTimeout::Nodes(nodes) => {
+                false.azalea_write(buf)?;
+                (*nodes as u64).azalea_write(buf)
+            }
+        }
+    }
+}
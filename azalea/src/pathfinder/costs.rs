@@ -19,6 +19,138 @@ pub const COST_HEURISTIC: f32 = 3.563;
 // breaking blocks if it can be avoided
 pub const BLOCK_BREAK_ADDITIONAL_PENALTY: f32 = 2.;
 
+// added when walking next to a drop, so equal-cost routes that don't hug a
+// cliff edge win. this gets multiplied by the careful mode multiplier.
+pub const LEDGE_PENALTY: f32 = WALK_ONE_BLOCK_COST / 4.;
+/// Added when the path turns by more than 45°, since we lose some of our
+/// sprinting speed when we turn. This makes straight routes win over zig-zags
+/// that would otherwise cost the same.
+pub const TURN_PENALTY: f32 = SPRINT_ONE_BLOCK_COST / 4.;
+/// A drop of more than this many blocks next to a node makes it count as a
+/// ledge. Falling this far doesn't do any damage.
+pub const LEDGE_DROP_THRESHOLD: u32 = 3;
+/// The horizontal speed (in blocks per tick) above which we stop pressing
+/// forward when we're next to a ledge. This is a bit under the walking speed.
+pub const LEDGE_WALK_SPEED: f64 = 0.2;
+/// How far sideways from the middle of the block (in blocks) we can drift
+/// towards a ledge before we start sneaking. At 0.3 our hitbox is already
+/// hanging 0.1 blocks over the edge.
+pub const LEDGE_SNEAK_DISTANCE: f64 = 0.3;
+/// How many ticks ahead we look when guessing whether our sideways momentum
+/// will carry us too close to a ledge.
+pub const LEDGE_SNEAK_LOOKAHEAD_TICKS: f64 = 4.;
+/// How many blocks the path has to keep going straight for before we sprint.
+pub const MIN_SPRINT_RUN_LENGTH: f64 = 3.;
+/// How far (in blocks) before the end of a straight run we stop sprinting, so
+/// we don't overshoot a corner or the end of the path.
+pub const SPRINT_STOP_DISTANCE: f64 = 1.;
+/// How many nodes after the one that we're going to we look at when deciding
+/// whether to sprint.
+pub const SPRINT_LOOKAHEAD_NODES: usize = 4;
+/// The sideways speed (in blocks per tick) towards a drop above which we stop
+/// pressing forward at the start of a move, so momentum from the move before a
+/// corner doesn't carry us off the edge.
+pub const CORNER_BRAKE_SPEED: f64 = 0.1;
+
+/// Added when standing on the tip of upward-pointing dripstone. Walking on it
+/// is harmless, but it's easy to fall onto it from there.
+pub const DRIPSTONE_STANDING_PENALTY: f32 = WALK_ONE_BLOCK_COST;
+/// Added when falling onto upward-pointing dripstone, since landing on it does
+/// a lot of extra damage.
+pub const DRIPSTONE_FALL_PENALTY: f32 = 100.;
+/// Added when breaking a block that has a stalactite (downward-pointing
+/// dripstone) hanging under it, since the stalactite will fall when its
+/// support is gone.
+pub const STALACTITE_PENALTY: f32 = 50.;
+/// The tallest column of sand or gravel that we'll mine out from under.
+/// Every block in it falls into the gap and has to be mined again, so taller
+/// columns are treated like they can't be broken.
+pub const MAX_FALLING_COLUMN_HEIGHT: i32 = 8;
+/// How many ticks we wait after breaking a block that has sand or gravel on
+/// top, so it can land before we walk into the gap. The server waits two ticks
+/// before the block starts falling, and falling one block takes about seven.
+pub const FALLING_BLOCK_WAIT_TICKS: u32 = 10;
+
+/// The cost of placing a boat at the start of an ice highway segment or a
+/// water crossing, getting in, and getting out again at the end.
+pub const BOAT_MOUNT_COST: f32 = 20.;
+/// Boats go about 40 blocks per second on packed ice.
+pub const ICE_BOAT_ONE_BLOCK_COST: f32 = 20. / 40.;
+/// Boats go about 70 blocks per second on blue ice.
+pub const BLUE_ICE_BOAT_ONE_BLOCK_COST: f32 = 20. / 70.;
+/// Boats go about 8 blocks per second on water.
+pub const WATER_BOAT_ONE_BLOCK_COST: f32 = 20. / 8.;
+/// Roughly how many ticks it takes to stop sliding on ice after we let go of
+/// the movement keys.
+pub const ICE_SLIDE_TICKS: f64 = 10.;
+/// Sprinting and jumping on ice or packed ice is faster than on other blocks,
+/// since we barely slow down between steps.
+pub const ICE_SPRINT_ONE_BLOCK_COST: f32 = SPRINT_ONE_BLOCK_COST * 0.7;
+/// Blue ice is even more slippery than normal ice.
+pub const BLUE_ICE_SPRINT_ONE_BLOCK_COST: f32 = SPRINT_ONE_BLOCK_COST * 0.5;
+/// Added for walking onto frosted ice, since it melts and might be gone by the
+/// time we get there.
+pub const FROSTED_ICE_PENALTY: f32 = WALK_ONE_BLOCK_COST;
+/// Soul sand slows us down to 40% of our normal speed, unless we have Soul
+/// Speed.
+pub const SOUL_SAND_SPRINT_ONE_BLOCK_COST: f32 = SPRINT_ONE_BLOCK_COST / 0.4;
+/// Honey blocks slow us down as much as soul sand.
+pub const HONEY_BLOCK_SPRINT_ONE_BLOCK_COST: f32 = SPRINT_ONE_BLOCK_COST / 0.4;
+/// Slime blocks slow us down when we walk on them, and they make us bounce
+/// instead of letting us sprint-jump.
+pub const SLIME_BLOCK_SPRINT_ONE_BLOCK_COST: f32 = SPRINT_ONE_BLOCK_COST / 0.4;
+
+/// The cost of walking into a water elevator and back out of it.
+pub const WATER_ELEVATOR_ENTER_COST: f32 = WALK_ONE_BLOCK_COST * 2.;
+/// Bubble columns above soul sand push us up at up to 0.7 blocks per tick.
+pub const WATER_ELEVATOR_UP_ONE_BLOCK_COST: f32 = 1. / 0.7;
+/// Bubble columns above magma blocks pull us down at up to 0.3 blocks per tick.
+pub const WATER_ELEVATOR_DOWN_ONE_BLOCK_COST: f32 = 1. / 0.3;
+
+/// Added for moving through a cobweb, since they slow us down to a crawl.
+pub const COBWEB_PENALTY: f32 = WALK_ONE_BLOCK_COST * 10.;
+/// Added for moving through a sweet berry bush, since they slow us down and
+/// hurt us.
+pub const SWEET_BERRY_BUSH_PENALTY: f32 = WALK_ONE_BLOCK_COST * 3.;
+
+/// Added for standing on blocks that hurt us, like magma blocks and lit
+/// campfires, when [`GotoOptions::avoid_damage`] is false.
+///
+/// [`GotoOptions::avoid_damage`]: crate::pathfinder::GotoOptions::avoid_damage
+pub const DAMAGING_SURFACE_PENALTY: f32 = 40.;
+
+/// Holding jump inside powder snow while wearing leather boots moves us up at
+/// 0.2 blocks per tick, like a ladder.
+pub const POWDER_SNOW_CLIMB_ONE_BLOCK_COST: f32 = 1. / 0.2;
+/// Added for standing right under powder snow without leather boots, since
+/// jumping there would get us stuck in it and freezing.
+pub const UNDER_POWDER_SNOW_PENALTY: f32 = WALK_ONE_BLOCK_COST * 2.;
+
+/// Added for opening a door, fence gate, or trapdoor that's in the way, since
+/// we have to stop, look at it, and wait for the server to open it.
+pub const OPEN_DOOR_PENALTY: f32 = 5.;
+/// How many ticks we wait after right-clicking a block before we're allowed to
+/// do it again, so we don't close a door while the server is still opening it.
+pub const INTERACT_COOLDOWN_TICKS: u32 = 10;
+/// Added for placing a block under us, like to bridge over a gap or to pillar
+/// up. Getting into position and placing the block takes a while, and it also
+/// uses up one of our blocks.
+pub const BLOCK_PLACE_PENALTY: f32 = 20.;
+
+/// Added for every block in a chunk that isn't loaded that the path goes
+/// through. We don't know what's there, so this makes routes through terrain
+/// that we know about win unless they're a lot longer.
+pub const UNKNOWN_TERRAIN_PENALTY: f32 = WALK_ONE_BLOCK_COST;
+
+/// The furthest that we can fall without taking any damage.
+pub const MAX_SAFE_FALL_DISTANCE: u32 = 3;
+/// Slow Falling stops fall damage entirely, but we still don't want to drop
+/// further than this in case the effect runs out.
+pub const MAX_SLOW_FALL_DISTANCE: u32 = 64;
+/// Landing in water stops fall damage, but for falls further than this we want
+/// at least 2 blocks of water in case we don't land exactly where we expected.
+pub const SHALLOW_WATER_MAX_FALL_DISTANCE: u32 = 12;
+
 // Water-related movement costs
 // Based on Minecraft's actual swimming mechanics (1.97 m/s swimming vs 4.32 m/s sprinting)
 // Optimized for efficient water traversal rather than avoidance
@@ -28,6 +160,16 @@ pub const WATER_ASCENT_COST: f32 = SWIMMING_COST * 1.3; // Swimming upward
 pub const WATER_DESCENT_COST: f32 = SWIMMING_COST * 0.9; // Swimming downward is easier
 pub const SPRINT_SWIMMING_COST: f32 = WALK_ONE_BLOCK_COST * 1.5; // Sprint swimming underwater
 pub const FLOW_RESISTANCE_COST: f32 = SWIMMING_COST * 0.2; // Reduced resistance penalty
+pub const FLOW_ASSIST_DISCOUNT: f32 = SWIMMING_COST * 0.1; // Swimming with the current
+/// Swimming costs get multiplied by this with the highest level of Depth
+/// Strider, which makes us swim about as fast as we walk.
+pub const DEPTH_STRIDER_SWIMMING_MULTIPLIER: f32 = WALK_ONE_BLOCK_COST / SWIMMING_COST;
+/// Swimming costs get multiplied by this with Dolphin's Grace, which makes us
+/// swim even faster than we can sprint.
+pub const DOLPHINS_GRACE_SWIMMING_MULTIPLIER: f32 = SPRINT_ONE_BLOCK_COST / SWIMMING_COST;
+/// Added for swimming through kelp or seagrass when we can't break it, since
+/// it slows us down.
+pub const AQUATIC_PLANT_PENALTY: f32 = SWIMMING_COST * 0.5;
 pub const WATER_ENTRY_COST: f32 = 2.0; // Lower entry cost to encourage water use
 pub const WATER_EXIT_COST: f32 = 1.5; // Lower exit cost
 pub const AIR_DEPLETION_PENALTY: f32 = 10.0; // Heavy penalty for running out of air
@@ -63,6 +205,56 @@ pub static FALL_N_BLOCKS_COST: LazyLock<[f32; 4097]> = LazyLock::new(|| {
     fall_n_blocks_cost
 });
 
+/// How high (in blocks) a jump from the ground goes, with the given level of
+/// Jump Boost.
+pub fn jump_height(jump_boost: Option<u32>) -> f32 {
+    let mut velocity = 0.42
+        + jump_boost
+            .map(|amplifier| 0.1 * (amplifier + 1) as f32)
+            .unwrap_or(0.);
+    let mut height = 0.;
+    while velocity > 0. {
+        height += velocity;
+        velocity = (velocity - 0.08) * 0.98;
+    }
+    height
+}
+
+/// How much faster we walk on soul sand and soul soil with the given level of
+/// Soul Speed.
+pub fn soul_speed_multiplier(level: u32) -> f32 {
+    // soul speed adds 0.03 * (1 + level * 0.35) to our base movement speed of 0.1
+    1. + 0.3 * (1. + level as f32 * 0.35)
+}
+
+/// Like [`FALL_N_BLOCKS_COST`], but with Slow Falling, where gravity is 0.01
+/// instead of 0.08.
+pub static SLOW_FALL_N_BLOCKS_COST: LazyLock<[f32; MAX_SLOW_FALL_DISTANCE as usize + 1]> =
+    LazyLock::new(|| {
+        let mut slow_fall_n_blocks_cost = [0.; MAX_SLOW_FALL_DISTANCE as usize + 1];
+
+        let mut distance = 1;
+        let mut fallen_distance = 0.;
+        let mut tick_count = 0;
+        while distance < slow_fall_n_blocks_cost.len() {
+            let velocity = slow_fall_velocity(tick_count);
+            if fallen_distance + velocity >= distance as f32 {
+                slow_fall_n_blocks_cost[distance] =
+                    tick_count as f32 + (distance as f32 - fallen_distance) / velocity;
+                distance += 1;
+                continue;
+            }
+            fallen_distance += velocity;
+            tick_count += 1;
+        }
+
+        slow_fall_n_blocks_cost
+    });
+
+fn slow_fall_velocity(ticks: usize) -> f32 {
+    (0.98.powi(ticks.try_into().unwrap()) - 1.) * -0.49
+}
+
 fn velocity(ticks: usize) -> f32 {
     (0.98.powi(ticks.try_into().unwrap()) - 1.) * -3.92
 }
@@ -0,0 +1,505 @@
+//! Flying to faraway goals with an elytra.
+//!
+//! When [`PathfinderDefaults::allow_elytra`] is true, we're wearing an elytra,
+//! we have firework rockets in our hotbar, and the goal is further away than
+//! [`PathfinderDefaults::elytra_threshold`], we fly most of the way there in a
+//! straight line instead of walking. The normal pathfinder takes over again
+//! once we land, and it might decide to fly again from there if the goal is
+//! still far away.
+//!
+//! Flights are conservative on purpose. We only take off if every block along
+//! the flight line is loaded and out of the way, and if something turns up in
+//! front of us anyways we give up on the flight and glide down.
+//!
+//! [`PathfinderDefaults::allow_elytra`]: super::settings::PathfinderDefaults::allow_elytra
+//! [`PathfinderDefaults::elytra_threshold`]: super::settings::PathfinderDefaults::elytra_threshold
+
+use azalea_block::{BlockState, fluid_state::FluidState};
+use azalea_client::{
+    StartFallFlyingEvent, StartWalkEvent,
+    interact::{StartUseItemEvent, pick::HitResultComponent},
+    inventory::{Inventory, SetSelectedHotbarSlotEvent},
+};
+use azalea_core::position::{BlockPos, Vec3};
+use azalea_entity::{EyeHeight, Physics, Position, metadata::FallFlying};
+use azalea_inventory::Menu;
+use azalea_protocol::packets::game::s_interact::InteractionHand;
+use azalea_registry::Item;
+use azalea_world::{Instance, InstanceContainer, InstanceName};
+use bevy_ecs::prelude::*;
+use tracing::{debug, warn};
+
+use super::{
+    GotoEvent, Pathfinder,
+    goals::Goal,
+    long_distance::is_long_distance,
+    world::{is_block_state_passable, is_block_state_standable},
+};
+use crate::{
+    WalkDirection,
+    bot::{JumpEvent, LookAtEvent},
+};
+
+/// The default for [`PathfinderDefaults::elytra_threshold`].
+///
+/// [`PathfinderDefaults::elytra_threshold`]: super::settings::PathfinderDefaults::elytra_threshold
+pub const DEFAULT_ELYTRA_THRESHOLD: f32 = 100.;
+
+/// The shortest flight that's worth taking off for, in blocks. If we can't
+/// find somewhere to land that's at least this far away then we walk instead.
+const MIN_FLIGHT_DISTANCE: f64 = 48.;
+/// How many blocks above the higher of the takeoff and landing positions we
+/// fly at.
+const CRUISE_HEIGHT: f64 = 12.;
+/// How many blocks we go up for every block forward while climbing to the
+/// cruising height.
+const CLIMB_SLOPE: f64 = 0.5;
+/// How many blocks we go down for every block forward while descending to the
+/// landing position.
+const DESCENT_SLOPE: f64 = 0.25;
+/// How fast (in blocks per tick) we want to be going while cruising. We use a
+/// rocket whenever we're slower than this.
+const CRUISE_SPEED: f64 = 1.2;
+/// How many ticks we wait after using a rocket before we can use another one,
+/// so we don't waste them while the last one is still boosting us.
+const ROCKET_COOLDOWN_TICKS: u32 = 25;
+/// How many blocks ahead of us we look for anything in the way while flying.
+const OBSTACLE_LOOKAHEAD: u32 = 6;
+/// How far ahead on the flight line we look while steering.
+const STEERING_LOOKAHEAD: f64 = 8.;
+/// How long we can spend trying to take off before giving up on the flight.
+const TAKEOFF_TIMEOUT_TICKS: u32 = 40;
+/// How far apart the positions that we try landing at are.
+const LANDING_SEARCH_STEP: f64 = 4.;
+
+/// Whether we can fly with what's in our inventory, which is an elytra in our
+/// chest slot and firework rockets in our hotbar.
+pub fn can_fly(inventory: &Inventory) -> bool {
+    let Menu::Player(player) = &inventory.inventory_menu else {
+        return false;
+    };
+    // the armor slots are ordered from head to feet
+    player.armor[1].kind() == Item::Elytra && rocket_hotbar_slot(inventory).is_some()
+}
+
+fn rocket_hotbar_slot(inventory: &Inventory) -> Option<u8> {
+    let menu = &inventory.inventory_menu;
+    menu.slots()[menu.hotbar_slots_range()]
+        .iter()
+        .position(|item| item.kind() == Item::FireworkRocket)
+        .map(|slot| slot as u8)
+}
+
+/// A component that's present while we're flying to the goal with an elytra
+/// instead of following a path.
+#[derive(Component, Clone, Debug)]
+pub struct ElytraFlight {
+    /// Where we were when we started the flight.
+    pub takeoff: Vec3,
+    /// The bottom center of the block that we're going to land in.
+    pub landing: Vec3,
+    /// The height that we fly at between climbing and descending.
+    pub cruise_y: f64,
+    pub phase: FlightPhase,
+    /// Set if the flight went wrong in a way that means that we shouldn't try
+    /// flying to the same goal again.
+    pub failed: bool,
+    pub ticks: u32,
+    pub ticks_since_rocket: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FlightPhase {
+    /// Jumping and opening the elytra.
+    TakingOff,
+    /// Following the flight line and using rockets to keep our speed up.
+    Flying,
+    /// Gliding down to wherever's in front of us, either because we're at the
+    /// end of the flight or because we gave up on it.
+    Landing,
+}
+
+/// A marker component that's added when a flight to the current goal failed,
+/// so we walk to it instead of trying again. It's removed when we get a new
+/// goal.
+#[derive(Component, Clone, Debug)]
+pub struct ElytraFlightFailed;
+
+impl ElytraFlight {
+    fn new(takeoff: Vec3, landing: Vec3) -> Self {
+        Self {
+            takeoff,
+            landing,
+            cruise_y: takeoff.y.max(landing.y) + CRUISE_HEIGHT,
+            phase: FlightPhase::TakingOff,
+            failed: false,
+            ticks: 0,
+            ticks_since_rocket: ROCKET_COOLDOWN_TICKS,
+        }
+    }
+
+    /// The horizontal distance between the takeoff and landing positions.
+    pub fn length(&self) -> f64 {
+        (self.landing - self.takeoff)
+            .horizontal_distance_squared()
+            .sqrt()
+    }
+
+    /// The horizontal direction of the flight, as a unit vector.
+    fn direction(&self) -> Vec3 {
+        let delta = self.landing - self.takeoff;
+        let length = self.length();
+        Vec3::new(delta.x / length, 0., delta.z / length)
+    }
+
+    /// How far along the flight line the position is, in blocks.
+    pub fn progress(&self, position: Vec3) -> f64 {
+        let delta = position - self.takeoff;
+        let direction = self.direction();
+        delta.x * direction.x + delta.z * direction.z
+    }
+
+    /// The position on the flight line that's `distance` blocks from the
+    /// takeoff position, at the height that we want to be at there.
+    pub fn point_at(&self, distance: f64) -> Vec3 {
+        let direction = self.direction();
+        Vec3::new(
+            self.takeoff.x + direction.x * distance,
+            self.altitude_at(distance),
+            self.takeoff.z + direction.z * distance,
+        )
+    }
+
+    /// The height that we want to be at after going `distance` blocks along
+    /// the flight line.
+    pub fn altitude_at(&self, distance: f64) -> f64 {
+        let climbing = self.takeoff.y + distance * CLIMB_SLOPE;
+        let descending = self.landing.y + (self.length() - distance) * DESCENT_SLOPE;
+        climbing.min(self.cruise_y).min(descending)
+    }
+
+    /// The most ticks that the flight should take, after which we assume
+    /// that something went wrong.
+    fn max_ticks(&self) -> u32 {
+        (self.length() / 0.5) as u32 + 200
+    }
+
+    /// Whether every block along the flight line is loaded and out of our way.
+    ///
+    /// We also check a block below the line, since we don't follow it exactly.
+    pub fn is_clear(&self, instance: &Instance) -> bool {
+        let length = self.length();
+        let takeoff_y = self.takeoff.y.floor() as i32;
+        let landing_y = self.landing.y.floor() as i32;
+        (0..=length.ceil() as u32).all(|distance| {
+            let distance = (distance as f64).min(length);
+            let point = self.point_at(distance);
+            // we're standing on the ground at the start and end of the flight
+            let ground_y = if distance < length / 2. {
+                takeoff_y
+            } else {
+                landing_y
+            };
+            let bottom = (point.y.floor() as i32 - 1).max(ground_y);
+            let top = point.y.floor() as i32 + 2;
+            (bottom..=top).all(|y| {
+                let pos = BlockPos::new(point.x.floor() as i32, y, point.z.floor() as i32);
+                instance
+                    .get_block_state(pos)
+                    .is_some_and(is_block_state_clear)
+            })
+        })
+    }
+
+    /// Whether something is in front of us at our height, so we'd crash into
+    /// it if we kept flying.
+    fn is_obstructed(&self, instance: &Instance, position: Vec3) -> bool {
+        let direction = self.direction();
+        (1..=OBSTACLE_LOOKAHEAD).any(|distance| {
+            let ahead = position + direction * distance as f64;
+            let pos = BlockPos::from(ahead);
+            [pos, pos.up(1)].iter().any(|&pos| {
+                !instance
+                    .get_block_state(pos)
+                    .is_some_and(is_block_state_clear)
+            })
+        })
+    }
+}
+
+/// Whether we can fly through the block without hitting anything or slowing
+/// down.
+fn is_block_state_clear(block: BlockState) -> bool {
+    block.is_air()
+        || (block.is_collision_shape_empty()
+            && FluidState::from(block).is_empty()
+            && is_block_state_passable(block))
+}
+
+/// Find a position that's roughly where the goal is, by following its
+/// heuristic downhill from `start`.
+///
+/// Goals don't say where they are, so this is the best that we can do. Only
+/// the X and Z of the result are meaningful.
+pub fn approximate_goal_position(goal: &dyn Goal, start: BlockPos) -> BlockPos {
+    let mut position = start;
+    let mut step = 256;
+    while step > 0 {
+        let best_neighbor = [
+            (1, 0),
+            (-1, 0),
+            (0, 1),
+            (0, -1),
+            (1, 1),
+            (1, -1),
+            (-1, 1),
+            (-1, -1),
+        ]
+        .into_iter()
+        .map(|(x, z)| position + BlockPos::new(x * step, 0, z * step))
+        .min_by(|a, b| goal.heuristic(*a).total_cmp(&goal.heuristic(*b)))
+        .expect("there are always neighbors");
+        if goal.heuristic(best_neighbor) < goal.heuristic(position) {
+            position = best_neighbor;
+        } else {
+            step /= 2;
+        }
+    }
+    position
+}
+
+/// Find the feet position that we'd land at if we glided down at the given
+/// column, or `None` if it's not somewhere that we can land safely.
+///
+/// Landing in water is fine, since it means that we can't take fall damage.
+fn find_landing(instance: &Instance, x: i32, z: i32) -> Option<BlockPos> {
+    let min_y = instance.chunks.min_y;
+    let max_y = min_y + instance.chunks.height as i32 - 1;
+    for y in (min_y..=max_y).rev() {
+        let pos = BlockPos::new(x, y, z);
+        let state = instance.get_block_state(pos)?;
+        if is_block_state_clear(state) {
+            continue;
+        }
+        let is_water = azalea_registry::Block::from(state) == azalea_registry::Block::Water;
+        return (is_water || is_block_state_standable(state)).then(|| pos.up(1));
+    }
+    None
+}
+
+/// Plan a flight from `position` towards the goal, if it's far enough away
+/// and there's a clear line to somewhere that we can land near it.
+///
+/// We try to land as close to the goal as we can, and then at positions that
+/// are closer to us until the flight would be too short to be worth it.
+pub fn plan_flight(
+    instance: &Instance,
+    position: Vec3,
+    goal: &dyn Goal,
+    inventory: &Inventory,
+    threshold: f32,
+) -> Option<ElytraFlight> {
+    let start = BlockPos::from(position);
+    if !can_fly(inventory) || !is_long_distance(goal, start, threshold) {
+        return None;
+    }
+
+    let target = approximate_goal_position(goal, start);
+    let delta = Vec3::new((target.x - start.x) as f64, 0., (target.z - start.z) as f64);
+    let distance = delta.horizontal_distance_squared().sqrt();
+    if distance < MIN_FLIGHT_DISTANCE {
+        return None;
+    }
+    let direction = delta / distance;
+
+    let mut flight_distance = distance;
+    while flight_distance >= MIN_FLIGHT_DISTANCE {
+        let column = position + direction * flight_distance;
+        if let Some(landing) =
+            find_landing(instance, column.x.floor() as i32, column.z.floor() as i32)
+        {
+            let flight = ElytraFlight::new(position, landing.center_bottom());
+            if flight.is_clear(instance) {
+                return Some(flight);
+            }
+        }
+        flight_distance -= LANDING_SEARCH_STEP;
+    }
+    None
+}
+
+/// Take off, follow the flight line, and land for the bots that are doing an
+/// [`ElytraFlight`], then go back to the normal pathfinder.
+#[allow(clippy::type_complexity)]
+pub fn tick_elytra_flight(
+    mut query: Query<(
+        Entity,
+        &mut ElytraFlight,
+        &mut Pathfinder,
+        &Position,
+        &Physics,
+        &FallFlying,
+        &EyeHeight,
+        &Inventory,
+        &InstanceName,
+        Option<&HitResultComponent>,
+    )>,
+    instance_container: Res<InstanceContainer>,
+    mut look_at_events: EventWriter<LookAtEvent>,
+    mut jump_events: EventWriter<JumpEvent>,
+    mut walk_events: EventWriter<StartWalkEvent>,
+    mut start_fall_flying_events: EventWriter<StartFallFlyingEvent>,
+    mut set_selected_hotbar_slot_events: EventWriter<SetSelectedHotbarSlotEvent>,
+    mut start_use_item_events: EventWriter<StartUseItemEvent>,
+    mut goto_events: EventWriter<GotoEvent>,
+    mut commands: Commands,
+) {
+    for (
+        entity,
+        mut flight,
+        mut pathfinder,
+        position,
+        physics,
+        fall_flying,
+        eye_height,
+        inventory,
+        instance_name,
+        hit_result,
+    ) in &mut query
+    {
+        let Some(instance) = instance_container.get(instance_name) else {
+            continue;
+        };
+        let instance = instance.read();
+
+        flight.ticks += 1;
+        flight.ticks_since_rocket += 1;
+        let position = **position;
+        let progress = flight.progress(position);
+        let length = flight.length();
+        let has_landed = !**fall_flying && (physics.on_ground() || physics.is_in_water());
+
+        if pathfinder.goal.is_none() && flight.phase != FlightPhase::Landing {
+            debug!("the pathfinder was stopped during a flight, landing");
+            flight.phase = FlightPhase::Landing;
+        }
+
+        let is_finished = if flight.ticks > flight.max_ticks() {
+            warn!("flight to {} is taking too long, giving up", flight.landing);
+            flight.failed = true;
+            true
+        } else {
+            match flight.phase {
+                FlightPhase::TakingOff => {
+                    walk_events.write(StartWalkEvent {
+                        entity,
+                        direction: WalkDirection::None,
+                    });
+                    let aim = flight.point_at(STEERING_LOOKAHEAD);
+                    look_at_events.write(LookAtEvent {
+                        entity,
+                        position: aim.up(**eye_height as f64),
+                    });
+
+                    if **fall_flying {
+                        debug!("took off, flying to {}", flight.landing);
+                        flight.phase = FlightPhase::Flying;
+                        false
+                    } else if flight.ticks > TAKEOFF_TIMEOUT_TICKS {
+                        warn!("couldn't take off with the elytra, walking instead");
+                        flight.failed = true;
+                        true
+                    } else {
+                        if physics.on_ground() {
+                            jump_events.write(JumpEvent { entity });
+                        } else if physics.velocity.y < 0. {
+                            // we can only open the elytra once we're in the air
+                            start_fall_flying_events.write(StartFallFlyingEvent { entity });
+                        }
+                        false
+                    }
+                }
+                FlightPhase::Flying => {
+                    if has_landed {
+                        true
+                    } else {
+                        if !**fall_flying {
+                            start_fall_flying_events.write(StartFallFlyingEvent { entity });
+                        }
+
+                        let is_near_landing = length - progress < OBSTACLE_LOOKAHEAD as f64 + 2.;
+                        if progress >= length {
+                            debug!("reached the end of the flight, landing");
+                            flight.phase = FlightPhase::Landing;
+                        } else if !is_near_landing && flight.is_obstructed(&instance, position) {
+                            debug!("something is in the way of the flight, landing");
+                            flight.phase = FlightPhase::Landing;
+                        } else {
+                            let aim = flight.point_at((progress + STEERING_LOOKAHEAD).min(length));
+                            look_at_events.write(LookAtEvent {
+                                entity,
+                                position: aim.up(**eye_height as f64),
+                            });
+
+                            let is_descending =
+                                flight.altitude_at(progress + 1.) < flight.altitude_at(progress);
+                            let is_too_slow = physics.velocity.length() < CRUISE_SPEED;
+                            let is_too_low = position.y < flight.altitude_at(progress) - 2.;
+                            // using a rocket while looking at a block would place it instead
+                            let is_looking_at_block = hit_result.is_some_and(|h| !h.miss());
+                            if flight.ticks_since_rocket >= ROCKET_COOLDOWN_TICKS
+                                && !is_descending
+                                && (is_too_slow || is_too_low)
+                                && !is_looking_at_block
+                                && let Some(slot) = rocket_hotbar_slot(inventory)
+                            {
+                                set_selected_hotbar_slot_events
+                                    .write(SetSelectedHotbarSlotEvent { entity, slot });
+                                start_use_item_events.write(StartUseItemEvent {
+                                    entity,
+                                    hand: InteractionHand::MainHand,
+                                    force_block: None,
+                                });
+                                flight.ticks_since_rocket = 0;
+                            }
+                        }
+                        false
+                    }
+                }
+                FlightPhase::Landing => {
+                    if !has_landed {
+                        // glide forwards and down until we hit the ground
+                        let ahead = position + flight.direction() * 4.;
+                        look_at_events.write(LookAtEvent {
+                            entity,
+                            position: ahead.down(2.).up(**eye_height as f64),
+                        });
+                    }
+                    has_landed
+                }
+            }
+        };
+        if !is_finished {
+            continue;
+        }
+
+        debug!("flight finished at {position}, going back to walking");
+        walk_events.write(StartWalkEvent {
+            entity,
+            direction: WalkDirection::None,
+        });
+        let mut entity_commands = commands.entity(entity);
+        entity_commands.remove::<ElytraFlight>();
+        if flight.failed {
+            entity_commands.insert(ElytraFlightFailed);
+        }
+        if let Some(goal) = pathfinder.goal.clone() {
+            goto_events.write(GotoEvent {
+                entity,
+                goal,
+                options: pathfinder.goto_options(),
+            });
+            pathfinder.is_calculating = true;
+        }
+    }
+}
@@ -0,0 +1,153 @@
+//! Following entities that move around.
+//!
+//! A [`FollowEntityGoal`] only knows where the entity was when the path was
+//! planned, so while a bot has a [`FollowEntity`] component we keep watching
+//! the entity and plan a new path whenever it moves too far from there. Once
+//! we're close enough we stop, and we start moving again when the entity walks
+//! away.
+
+use std::sync::Arc;
+
+use azalea_core::position::{BlockPos, Vec3};
+use azalea_entity::Position;
+use azalea_world::InstanceName;
+use bevy_ecs::prelude::*;
+use tracing::{debug, warn};
+
+use super::{
+    ExecutingPath, GotoEvent, GotoOptions, GotoOutcome, Pathfinder,
+    goals::{FollowEntityGoal, Goal},
+    hazard::EscapingHazard,
+};
+
+/// How far (in blocks) the entity that we're following has to move from where
+/// the current path was planned to before we plan a new one.
+pub const REPLAN_DISTANCE: f64 = 2.;
+
+/// A component that makes the bot keep following an entity.
+///
+/// This is inserted by [`PathfinderClientExt::follow`], and it's removed when
+/// the pathfinder is stopped, when the bot is given a different goal, or when
+/// the entity stops existing.
+///
+/// [`PathfinderClientExt::follow`]: super::PathfinderClientExt::follow
+#[derive(Component, Clone)]
+pub struct FollowEntity {
+    /// The entity that we're following.
+    pub entity: Entity,
+    /// How close we try to get to the entity, in blocks.
+    pub distance: f32,
+    /// The options that the paths to the entity are calculated with.
+    pub options: GotoOptions,
+
+    /// The last goal that we sent, so we can tell when it gets replaced.
+    goal: Option<Arc<dyn Goal>>,
+    /// Where the entity was when we sent that goal.
+    planned_target: Option<Vec3>,
+}
+impl FollowEntity {
+    pub fn new(entity: Entity, distance: f32) -> Self {
+        Self {
+            entity,
+            distance,
+            options: GotoOptions::default(),
+            goal: None,
+            planned_target: None,
+        }
+    }
+
+    pub fn with_options(mut self, options: GotoOptions) -> Self {
+        self.options = options;
+        self
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn follow_entities(
+    mut commands: Commands,
+    mut query: Query<(
+        Entity,
+        &mut FollowEntity,
+        &Pathfinder,
+        Option<&mut ExecutingPath>,
+        &Position,
+        &InstanceName,
+        Has<EscapingHazard>,
+    )>,
+    targets: Query<(&Position, &InstanceName)>,
+    mut goto_events: EventWriter<GotoEvent>,
+) {
+    for (
+        entity,
+        mut follow,
+        pathfinder,
+        executing_path,
+        position,
+        instance_name,
+        is_escaping_hazard,
+    ) in &mut query
+    {
+        let Ok((target_position, target_instance_name)) = targets.get(follow.entity) else {
+            warn!("the entity that we were following is gone, not following it anymore");
+            commands.entity(entity).remove::<FollowEntity>();
+            continue;
+        };
+        if target_instance_name != instance_name {
+            warn!("the entity that we were following went to another world, not following it");
+            commands.entity(entity).remove::<FollowEntity>();
+            continue;
+        }
+        // the hazard goal gets replaced with ours again once we're out
+        if is_escaping_hazard {
+            continue;
+        }
+
+        if let Some(goal) = &pathfinder.goal
+            && let Some(our_goal) = &follow.goal
+            && !Arc::ptr_eq(goal, our_goal)
+        {
+            debug!(
+                "got a different goal, not following {} anymore",
+                follow.entity
+            );
+            commands.entity(entity).remove::<FollowEntity>();
+            continue;
+        }
+
+        let goal = FollowEntityGoal::new(follow.entity, **target_position, follow.distance);
+        let has_moved =
+            |planned_target: Vec3| planned_target.distance_to(**target_position) > REPLAN_DISTANCE;
+        let should_replan = match follow.planned_target {
+            None => true,
+            // we're still going to where the entity was
+            Some(planned_target) if pathfinder.goal.is_some() => {
+                !pathfinder.is_calculating && has_moved(planned_target)
+            }
+            // we stopped next to the entity, so only start again once it walks away
+            Some(_) if pathfinder.outcome == Some(GotoOutcome::Reached) => {
+                !goal.still_reached(BlockPos::from(position))
+            }
+            // we gave up, but the entity might be somewhere that we can get to now
+            Some(planned_target) => has_moved(planned_target),
+        };
+        if !should_replan {
+            continue;
+        }
+
+        if let Some(mut executing_path) = executing_path {
+            // finish the move that we're in the middle of and continue from there, the
+            // rest of the old path goes to where the entity used to be
+            executing_path.path.truncate(1);
+            executing_path.queued_path = None;
+        }
+
+        let goal: Arc<dyn Goal> = Arc::new(goal);
+        follow.goal = Some(goal.clone());
+        follow.planned_target = Some(**target_position);
+        goto_events.write(GotoEvent {
+            entity,
+            goal,
+            options: follow.options.clone(),
+        });
+    }
+}
@@ -1,12 +1,15 @@
 //! The goals that a pathfinder can try to reach.
 
 use std::{
+    collections::HashSet,
     f32::consts::SQRT_2,
     fmt::{self, Debug},
+    sync::Arc,
 };
 
-use azalea_core::position::{BlockPos, Vec3};
+use azalea_core::position::{BlockPos, ChunkPos, Vec3};
 use azalea_world::ChunkStorage;
+use bevy_ecs::entity::Entity;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -17,6 +20,32 @@ pub trait Goal: Debug + Send + Sync {
     fn heuristic(&self, n: BlockPos) -> f32;
     #[must_use]
     fn success(&self, n: BlockPos) -> bool;
+    /// Whether the goal should still count as reached at this position, given
+    /// that we had already reached it.
+    ///
+    /// Goals with a fuzzy boundary can make this more lenient than
+    /// [`Self::success`], so a target that jitters back and forth across the
+    /// boundary doesn't make us keep starting and stopping.
+    #[must_use]
+    fn still_reached(&self, n: BlockPos) -> bool {
+        self.success(n)
+    }
+}
+
+/// This lets goals that were already put in an `Arc`, like the one in a
+/// [`GotoEvent`], be used in [`OrGoals`] and the other combinators.
+///
+/// [`GotoEvent`]: super::GotoEvent
+impl<T: Goal + ?Sized> Goal for Arc<T> {
+    fn heuristic(&self, n: BlockPos) -> f32 {
+        (**self).heuristic(n)
+    }
+    fn success(&self, n: BlockPos) -> bool {
+        (**self).success(n)
+    }
+    fn still_reached(&self, n: BlockPos) -> bool {
+        (**self).still_reached(n)
+    }
 }
 
 /// Move to the given block position.
@@ -104,31 +133,129 @@ impl Goal for YGoal {
 pub struct RadiusGoal {
     pub pos: Vec3,
     pub radius: f32,
+    /// How much further than the radius we can be before we start moving
+    /// again, once we've already reached the goal. This stops us from stepping
+    /// back and forth when the position is moving around the edge of the
+    /// radius.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub hysteresis: f32,
 }
 impl RadiusGoal {
+    /// The default [`Self::hysteresis`] for goals made with [`Self::new`].
+    pub const DEFAULT_HYSTERESIS: f32 = 1.;
+
     pub fn new(pos: Vec3, radius: f32) -> Self {
-        Self { pos, radius }
+        Self {
+            pos,
+            radius,
+            hysteresis: Self::DEFAULT_HYSTERESIS,
+        }
     }
-}
-impl Goal for RadiusGoal {
-    fn heuristic(&self, n: BlockPos) -> f32 {
+
+    pub fn with_hysteresis(mut self, hysteresis: f32) -> Self {
+        self.hysteresis = hysteresis;
+        self
+    }
+
+    fn distance_squared_to(&self, n: BlockPos) -> f32 {
         let n = n.center();
         let dx = (self.pos.x - n.x) as f32;
         let dy = (self.pos.y - n.y) as f32;
         let dz = (self.pos.z - n.z) as f32;
         dx.powi(2) + dy.powi(2) + dz.powi(2)
     }
+}
+impl Goal for RadiusGoal {
+    fn heuristic(&self, n: BlockPos) -> f32 {
+        // the distance to the edge of the sphere. this can't be squared, since the
+        // move costs aren't either and it'd overestimate for faraway goals
+        let distance = self.distance_squared_to(n).sqrt();
+        (distance - self.radius).max(0.) * COST_HEURISTIC
+    }
     fn success(&self, n: BlockPos) -> bool {
-        let n = n.center();
-        let dx = (self.pos.x - n.x) as f32;
-        let dy = (self.pos.y - n.y) as f32;
-        let dz = (self.pos.z - n.z) as f32;
-        dx.powi(2) + dy.powi(2) + dz.powi(2) <= self.radius.powi(2)
+        self.distance_squared_to(n) <= self.radius.powi(2)
+    }
+    fn still_reached(&self, n: BlockPos) -> bool {
+        self.distance_squared_to(n) <= (self.radius + self.hysteresis).powi(2)
+    }
+}
+
+/// Get at least the given distance away from a position.
+///
+/// If there's nowhere that far away that we can get to, like when we're stuck
+/// in a small room, the pathfinder runs out of positions to check and goes to
+/// the furthest one that it found instead. It gives up on the goal once going
+/// there stops getting us any further away, like for any other goal that
+/// can't be reached (see [`PathfinderDefaults::max_stalled_partial_paths`]).
+///
+/// [`PathfinderDefaults::max_stalled_partial_paths`]: crate::pathfinder::settings::PathfinderDefaults::max_stalled_partial_paths
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct FleeGoal {
+    pub from: Vec3,
+    pub min_distance: f32,
+}
+impl FleeGoal {
+    fn distance_to(&self, n: BlockPos) -> f32 {
+        self.from.distance_to(n.center_bottom()) as f32
+    }
+}
+impl Goal for FleeGoal {
+    fn heuristic(&self, n: BlockPos) -> f32 {
+        // every block that we still have to go costs at least as much as sprinting
+        // one block, so this never overestimates
+        (self.min_distance - self.distance_to(n)).max(0.) * COST_HEURISTIC
+    }
+    fn success(&self, n: BlockPos) -> bool {
+        self.distance_to(n) >= self.min_distance
+    }
+}
+
+/// Get within the given distance of an entity.
+///
+/// The goal only knows where the entity was when it was created, so on its own
+/// this is a one-off goto. Use [`FollowEntity`] to keep following the entity
+/// as it moves around, which replaces the goal whenever the entity gets too far
+/// from [`Self::target`].
+///
+/// [`FollowEntity`]: crate::pathfinder::follow::FollowEntity
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FollowEntityGoal {
+    pub entity: Entity,
+    pub distance: f32,
+    /// Where the entity was when the goal was created.
+    pub target: Vec3,
+}
+impl FollowEntityGoal {
+    pub fn new(entity: Entity, target: Vec3, distance: f32) -> Self {
+        Self {
+            entity,
+            distance,
+            target,
+        }
+    }
+
+    fn radius_goal(&self) -> RadiusGoal {
+        RadiusGoal::new(self.target, self.distance)
+    }
+}
+impl Goal for FollowEntityGoal {
+    fn heuristic(&self, n: BlockPos) -> f32 {
+        self.radius_goal().heuristic(n)
+    }
+    fn success(&self, n: BlockPos) -> bool {
+        self.radius_goal().success(n)
+    }
+    fn still_reached(&self, n: BlockPos) -> bool {
+        self.radius_goal().still_reached(n)
     }
 }
 
 /// Do the opposite of the given goal.
-#[derive(Debug)]
+///
+/// This is useful for getting away from something, since the heuristic gets
+/// better the further away we are from the inner goal.
+#[derive(Clone, Debug)]
 pub struct InverseGoal<T: Goal>(pub T);
 impl<T: Goal> Goal for InverseGoal<T> {
     fn heuristic(&self, n: BlockPos) -> f32 {
@@ -140,7 +267,7 @@ impl<T: Goal> Goal for InverseGoal<T> {
 }
 
 /// Do either of the given goals, whichever is closer.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct OrGoal<T: Goal, U: Goal>(pub T, pub U);
 impl<T: Goal, U: Goal> Goal for OrGoal<T, U> {
     fn heuristic(&self, n: BlockPos) -> f32 {
@@ -149,26 +276,35 @@ impl<T: Goal, U: Goal> Goal for OrGoal<T, U> {
     fn success(&self, n: BlockPos) -> bool {
         self.0.success(n) || self.1.success(n)
     }
+    fn still_reached(&self, n: BlockPos) -> bool {
+        self.0.still_reached(n) || self.1.still_reached(n)
+    }
 }
 
 /// Do any of the given goals, whichever is closest.
-#[derive(Debug)]
+///
+/// To combine goals of different types, use `OrGoals<Arc<dyn Goal>>`.
+#[derive(Clone, Debug)]
 pub struct OrGoals<T: Goal>(pub Vec<T>);
 impl<T: Goal> Goal for OrGoals<T> {
     fn heuristic(&self, n: BlockPos) -> f32 {
         self.0
             .iter()
             .map(|goal| goal.heuristic(n))
-            .min_by(|a, b| a.partial_cmp(b).unwrap())
+            .reduce(f32::min)
             .unwrap_or(f32::INFINITY)
     }
     fn success(&self, n: BlockPos) -> bool {
         self.0.iter().any(|goal| goal.success(n))
     }
+    fn still_reached(&self, n: BlockPos) -> bool {
+        self.0.iter().any(|goal| goal.still_reached(n))
+    }
 }
 
-/// Try to reach both of the given goals.
-#[derive(Debug)]
+/// Try to reach both of the given goals, like getting to a certain y level
+/// while staying within a radius.
+#[derive(Clone, Debug)]
 pub struct AndGoal<T: Goal, U: Goal>(pub T, pub U);
 impl<T: Goal, U: Goal> Goal for AndGoal<T, U> {
     fn heuristic(&self, n: BlockPos) -> f32 {
@@ -177,29 +313,56 @@ impl<T: Goal, U: Goal> Goal for AndGoal<T, U> {
     fn success(&self, n: BlockPos) -> bool {
         self.0.success(n) && self.1.success(n)
     }
+    fn still_reached(&self, n: BlockPos) -> bool {
+        self.0.still_reached(n) && self.1.still_reached(n)
+    }
 }
 
 /// Try to reach all the given goals.
-#[derive(Debug)]
+///
+/// To combine goals of different types, use `AndGoals<Arc<dyn Goal>>`.
+#[derive(Clone, Debug)]
 pub struct AndGoals<T: Goal>(pub Vec<T>);
 impl<T: Goal> Goal for AndGoals<T> {
     fn heuristic(&self, n: BlockPos) -> f32 {
         self.0
             .iter()
             .map(|goal| goal.heuristic(n))
-            .max_by(|a, b| a.partial_cmp(b).unwrap())
+            .reduce(f32::max)
             .unwrap_or(f32::INFINITY)
     }
     fn success(&self, n: BlockPos) -> bool {
         self.0.iter().all(|goal| goal.success(n))
     }
+    fn still_reached(&self, n: BlockPos) -> bool {
+        self.0.iter().all(|goal| goal.still_reached(n))
+    }
 }
 
 /// Move to a position where we can reach the given block.
+///
+/// The block counts as reachable when our eyes are within [`Self::distance`]
+/// of the closest point on it and, if [`Self::require_line_of_sight`] is true,
+/// we could look at one of its faces without anything in the way. This means
+/// that a block that's embedded in a wall is reached from in front of its
+/// exposed face rather than from on top of the wall.
 #[derive(Clone)]
 pub struct ReachBlockPosGoal {
     pub pos: BlockPos,
+    /// How far away from our eyes the block can be, in blocks.
     pub distance: f64,
+    /// How much further than [`Self::distance`] the block can be before we
+    /// start moving again, once we've already reached the goal.
+    ///
+    /// This is 0 by default, since `distance` is usually the furthest that we
+    /// can reach. Lower the distance if you want to set this.
+    pub hysteresis: f64,
+    /// Whether we have to be able to see the block to count as reaching it.
+    ///
+    /// This is true by default. Turn it off if you only care about the
+    /// distance, like when the block is going to be mined through other
+    /// blocks anyways.
+    pub require_line_of_sight: bool,
     pub chunk_storage: ChunkStorage,
 
     max_check_distance: i32,
@@ -213,16 +376,54 @@ impl ReachBlockPosGoal {
         Self {
             pos,
             distance,
+            hysteresis: 0.,
+            require_line_of_sight: true,
             chunk_storage,
             max_check_distance: (distance + 2.).ceil() as i32,
         }
     }
-}
-impl Goal for ReachBlockPosGoal {
-    fn heuristic(&self, n: BlockPos) -> f32 {
-        BlockPosGoal(self.pos).heuristic(n)
+
+    pub fn with_hysteresis(mut self, hysteresis: f64) -> Self {
+        self.hysteresis = hysteresis;
+        self.max_check_distance = (self.distance + hysteresis + 2.).ceil() as i32;
+        self
     }
-    fn success(&self, n: BlockPos) -> bool {
+
+    pub fn with_line_of_sight(mut self, require_line_of_sight: bool) -> Self {
+        self.require_line_of_sight = require_line_of_sight;
+        self
+    }
+
+    /// The distance from the given position to the closest point on the
+    /// block.
+    fn distance_to_block(&self, position: Vec3) -> f64 {
+        let min = self.pos.to_vec3_floored();
+        let closest = Vec3::new(
+            position.x.clamp(min.x, min.x + 1.),
+            position.y.clamp(min.y, min.y + 1.),
+            position.z.clamp(min.z, min.z + 1.),
+        );
+        position.distance_to(closest)
+    }
+
+    /// The points that we'd try to look at to see the block from the given eye
+    /// position.
+    ///
+    /// This is the center of the block and the centers of the faces that are
+    /// facing us, so a block that's only visible from the side can still be
+    /// seen when the ray to its center would be blocked.
+    fn look_targets(&self, eye_position: Vec3) -> impl Iterator<Item = Vec3> {
+        let center = self.pos.center();
+        let offset = eye_position - center;
+        let faces = [
+            (offset.x.abs() > 0.5).then(|| Vec3::new(offset.x.signum() * 0.5, 0., 0.)),
+            (offset.y.abs() > 0.5).then(|| Vec3::new(0., offset.y.signum() * 0.5, 0.)),
+            (offset.z.abs() > 0.5).then(|| Vec3::new(0., 0., offset.z.signum() * 0.5)),
+        ];
+        std::iter::once(center).chain(faces.into_iter().flatten().map(move |face| center + face))
+    }
+
+    fn can_reach_from(&self, n: BlockPos, distance: f64) -> bool {
         if n.up(1) == self.pos {
             // our head is in the block, assume it's always reachable (to reduce the amount
             // of impossible goals)
@@ -236,15 +437,37 @@ impl Goal for ReachBlockPosGoal {
         }
 
         let eye_position = n.center_bottom().up(1.62);
-        let look_direction = crate::direction_looking_at(eye_position, self.pos.center());
-        let block_hit_result = azalea_client::interact::pick::pick_block(
-            look_direction,
-            eye_position,
-            &self.chunk_storage,
-            self.distance,
-        );
+        if self.distance_to_block(eye_position) > distance {
+            return false;
+        }
+        if !self.require_line_of_sight {
+            return true;
+        }
 
-        block_hit_result.block_pos == self.pos
+        self.look_targets(eye_position).any(|target| {
+            let look_direction = crate::direction_looking_at(eye_position, target);
+            let block_hit_result = azalea_client::interact::pick::pick_block(
+                look_direction,
+                eye_position,
+                &self.chunk_storage,
+                distance,
+            );
+            block_hit_result.block_pos == self.pos
+        })
+    }
+}
+impl Goal for ReachBlockPosGoal {
+    fn heuristic(&self, n: BlockPos) -> f32 {
+        // we only have to get our eyes close enough to the block, not stand in it
+        let eye_position = n.center_bottom().up(1.62);
+        let remaining = self.distance_to_block(eye_position) - self.distance;
+        remaining.max(0.) as f32 * COST_HEURISTIC
+    }
+    fn success(&self, n: BlockPos) -> bool {
+        self.can_reach_from(n, self.distance)
+    }
+    fn still_reached(&self, n: BlockPos) -> bool {
+        self.can_reach_from(n, self.distance + self.hysteresis)
     }
 }
 impl Debug for ReachBlockPosGoal {
@@ -252,7 +475,159 @@ impl Debug for ReachBlockPosGoal {
         f.debug_struct("ReachBlockPosGoal")
             .field("pos", &self.pos)
             .field("distance", &self.distance)
+            .field("hysteresis", &self.hysteresis)
+            .field("require_line_of_sight", &self.require_line_of_sight)
             .field("max_check_distance", &self.max_check_distance)
             .finish()
     }
 }
+
+/// Walk towards the edge of the loaded world so that more chunks get loaded.
+///
+/// The target is a loaded chunk on the frontier (i.e. next to a chunk that was
+/// never loaded), preferring the ones with the most unexplored chunks around
+/// them. This makes the bot keep going in roughly the same direction instead
+/// of going back and forth.
+///
+/// This is useful for bots that are trying to map out an area or find certain
+/// blocks.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ExploreGoal {
+    pub target: ChunkPos,
+}
+impl ExploreGoal {
+    /// The radius (in chunks) around a frontier chunk that's checked to see how
+    /// much unexplored area there is in that direction.
+    const UNEXPLORED_AREA_RADIUS: i32 = 2;
+
+    /// Pick a frontier chunk that's at most `max_distance` chunks away from the
+    /// given position.
+    ///
+    /// `explored` should contain every chunk that was ever loaded while
+    /// exploring, so the bot doesn't try to go back to chunks that were
+    /// unloaded after it walked away from them. Returns `None` if there's
+    /// nothing left to explore within `max_distance`.
+    pub fn new(
+        position: BlockPos,
+        chunk_storage: &ChunkStorage,
+        explored: &HashSet<ChunkPos>,
+        max_distance: u32,
+    ) -> Option<Self> {
+        Self::new_with_loaded_fn(
+            position,
+            |chunk_pos| chunk_storage.get(&chunk_pos).is_some(),
+            explored,
+            max_distance,
+        )
+    }
+
+    /// Same as [`Self::new`], but with a function to check whether a chunk is
+    /// loaded instead of a [`ChunkStorage`].
+    pub fn new_with_loaded_fn(
+        position: BlockPos,
+        is_loaded: impl Fn(ChunkPos) -> bool,
+        explored: &HashSet<ChunkPos>,
+        max_distance: u32,
+    ) -> Option<Self> {
+        let current = ChunkPos::from(position);
+        let max_distance = max_distance as i32;
+        let is_unexplored =
+            |chunk_pos: ChunkPos| !is_loaded(chunk_pos) && !explored.contains(&chunk_pos);
+
+        // (unexplored chunks around it, distance from us, chunk)
+        let mut best: Option<(usize, i32, ChunkPos)> = None;
+        for x in -max_distance..=max_distance {
+            for z in -max_distance..=max_distance {
+                let chunk_pos = ChunkPos::new(current.x + x, current.z + z);
+                if chunk_pos == current || !is_loaded(chunk_pos) {
+                    continue;
+                }
+
+                let is_frontier = [(1, 0), (-1, 0), (0, 1), (0, -1)]
+                    .into_iter()
+                    .any(|(dx, dz)| is_unexplored(chunk_pos + ChunkPos::new(dx, dz)));
+                if !is_frontier {
+                    continue;
+                }
+
+                let r = Self::UNEXPLORED_AREA_RADIUS;
+                let unexplored_around = (-r..=r)
+                    .flat_map(|dx| (-r..=r).map(move |dz| ChunkPos::new(dx, dz)))
+                    .filter(|&offset| is_unexplored(chunk_pos + offset))
+                    .count();
+                let distance = x.abs().max(z.abs());
+
+                let is_better = match best {
+                    None => true,
+                    Some((best_unexplored, best_distance, best_pos)) => {
+                        (unexplored_around, -distance, chunk_pos.x, chunk_pos.z)
+                            > (best_unexplored, -best_distance, best_pos.x, best_pos.z)
+                    }
+                };
+                if is_better {
+                    best = Some((unexplored_around, distance, chunk_pos));
+                }
+            }
+        }
+
+        best.map(|(_, _, target)| Self { target })
+    }
+}
+impl Goal for ExploreGoal {
+    fn heuristic(&self, n: BlockPos) -> f32 {
+        // measure to the closest block in the chunk, since any of them counts
+        let min_x = self.target.x * 16;
+        let min_z = self.target.z * 16;
+        let dx = (n.x.clamp(min_x, min_x + 15) - n.x) as f32;
+        let dz = (n.z.clamp(min_z, min_z + 15) - n.z) as f32;
+        xz_heuristic(dx, dz)
+    }
+    fn success(&self, n: BlockPos) -> bool {
+        ChunkPos::from(n) == self.target
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_or_goals_heuristic_is_the_lowest() {
+        let goals: Vec<Arc<dyn Goal>> = vec![
+            Arc::new(BlockPosGoal(BlockPos::new(10, 70, 0))),
+            Arc::new(YGoal { y: 40 }),
+            Arc::new(XZGoal { x: -5, z: 3 }),
+        ];
+        let or_goals = OrGoals(goals.clone());
+
+        for x in -10..=10 {
+            for y in [30, 70, 100] {
+                let n = BlockPos::new(x, y, x / 2);
+                let heuristic = or_goals.heuristic(n);
+                // if every goal's heuristic is admissible then the lowest one is too
+                assert!(goals.iter().all(|goal| heuristic <= goal.heuristic(n)));
+                assert!(goals.iter().any(|goal| heuristic == goal.heuristic(n)));
+                assert_eq!(
+                    or_goals.success(n),
+                    goals.iter().any(|goal| goal.success(n))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_explore_goal_heuristic_is_zero_in_the_chunk() {
+        let goal = ExploreGoal {
+            target: ChunkPos::new(2, -1),
+        };
+        for x in 16..48 {
+            for z in -16..0 {
+                let n = BlockPos::new(x, 64, z);
+                assert_eq!(goal.heuristic(n) == 0., goal.success(n), "{n:?}");
+            }
+        }
+        // just outside of the chunk, it's only one block away
+        let next_to_chunk = BlockPos::new(40, 64, 0);
+        assert_eq!(goal.heuristic(next_to_chunk), xz_heuristic(0., -1.));
+    }
+}
@@ -1,17 +1,32 @@
-use std::{sync::Arc, time::Duration};
+use std::sync::Arc;
 
+use azalea_core::{aabb::AABB, resource_location::ResourceLocation};
+use azalea_registry::Item;
 use bevy_ecs::{entity::Entity, event::Event};
 
 use crate::pathfinder::{
     astar::PathfinderTimeout,
     goals::Goal,
     moves::{self, SuccessorsFn},
+    regions::RegionAvoidance,
 };
 
 /// Send this event to start pathfinding to the given goal.
 ///
 /// Also see [`PathfinderClientExt::goto`].
 ///
+/// ```
+/// # use azalea::pathfinder::{GotoEvent, GotoOptions, astar::PathfinderTimeout, goals::BlockPosGoal};
+/// # use azalea::{BlockPos, ecs::entity::Entity};
+/// # fn example(entity: Entity) -> GotoEvent {
+/// GotoEvent::new(entity, BlockPosGoal(BlockPos::new(0, 70, 0))).with_options(
+///     GotoOptions::default()
+///         .allow_mining(false)
+///         .timeout(PathfinderTimeout::Nodes(1_000_000)),
+/// )
+/// # }
+/// ```
+///
 /// This event is read by [`goto_listener`].
 #[derive(Event)]
 #[non_exhaustive]
@@ -19,66 +34,262 @@ pub struct GotoEvent {
     /// The local bot entity that will do the pathfinding and execute the path.
     pub entity: Entity,
     pub goal: Arc<dyn Goal>,
-    /// The function that's used for checking what moves are possible. Usually
-    /// [`moves::default_move`].
+    pub options: GotoOptions,
+}
+impl GotoEvent {
+    pub fn new(entity: Entity, goal: impl Goal + 'static) -> Self {
+        Self {
+            entity,
+            goal: Arc::new(goal),
+            options: GotoOptions::default(),
+        }
+    }
+    pub fn with_options(mut self, options: GotoOptions) -> Self {
+        self.options = options;
+        self
+    }
+    #[deprecated(note = "use `GotoEvent::with_options` and `GotoOptions::successors_fn` instead")]
+    pub fn with_successors_fn(mut self, successors_fn: SuccessorsFn) -> Self {
+        self.options = self.options.successors_fn(successors_fn);
+        self
+    }
+    #[deprecated(note = "use `GotoEvent::with_options` and `GotoOptions::allow_mining` instead")]
+    pub fn with_allow_mining(mut self, allow_mining: bool) -> Self {
+        self.options = self.options.allow_mining(allow_mining);
+        self
+    }
+    #[deprecated(
+        note = "use `GotoEvent::with_options` and `GotoOptions::retry_on_no_path` instead"
+    )]
+    pub fn with_retry_on_no_path(mut self, retry_on_no_path: bool) -> Self {
+        self.options = self.options.retry_on_no_path(retry_on_no_path);
+        self
+    }
+    #[deprecated(note = "use `GotoEvent::with_options` and `GotoOptions::min_timeout` instead")]
+    pub fn with_min_timeout(mut self, min_timeout: PathfinderTimeout) -> Self {
+        self.options = self.options.min_timeout(min_timeout);
+        self
+    }
+    #[deprecated(note = "use `GotoEvent::with_options` and `GotoOptions::max_timeout` instead")]
+    pub fn with_max_timeout(mut self, max_timeout: PathfinderTimeout) -> Self {
+        self.options = self.options.max_timeout(max_timeout);
+        self
+    }
+}
+
+/// The options for a [`GotoEvent`].
+///
+/// The options that are `None` are taken from the bot's
+/// [`PathfinderSettings`] if it has them, or from the [`PathfinderDefaults`]
+/// resource otherwise.
+///
+/// [`PathfinderSettings`]: crate::pathfinder::settings::PathfinderSettings
+/// [`PathfinderDefaults`]: crate::pathfinder::settings::PathfinderDefaults
+#[derive(Clone)]
+#[non_exhaustive]
+pub struct GotoOptions {
+    /// The function that's used for checking what moves are possible.
+    ///
+    /// Defaults to [`moves::default_move`].
     pub successors_fn: SuccessorsFn,
 
     /// Whether the bot is allowed to break blocks while pathfinding.
-    pub allow_mining: bool,
+    ///
+    /// Defaults to true.
+    pub allow_mining: Option<bool>,
 
     /// Whether we should recalculate the path when the pathfinder timed out and
     /// there's no partial path to try.
     ///
-    /// Should usually be set to true.
-    pub retry_on_no_path: bool,
+    /// Should usually be set to true, which is the default.
+    pub retry_on_no_path: Option<bool>,
 
     /// The minimum amount of time that should pass before the A* pathfinder
     /// function can return a timeout. It may take up to [`Self::max_timeout`]
     /// if it can't immediately find a usable path.
     ///
-    /// A good default value for this is
-    /// `PathfinderTimeout::Time(Duration::from_secs(1))`.
+    /// Defaults to `PathfinderTimeout::Time(Duration::from_secs(1))`.
     ///
     /// Also see [`PathfinderTimeout::Nodes`]
-    pub min_timeout: PathfinderTimeout,
+    pub min_timeout: Option<PathfinderTimeout>,
     /// The absolute maximum amount of time that the pathfinder function can
     /// take to find a path. If it takes this long, it means no usable path was
     /// found (so it might be impossible).
     ///
-    /// A good default value for this is
-    /// `PathfinderTimeout::Time(Duration::from_secs(5))`.
-    pub max_timeout: PathfinderTimeout,
+    /// Defaults to `PathfinderTimeout::Time(Duration::from_secs(5))`.
+    pub max_timeout: Option<PathfinderTimeout>,
+
+    /// How careful the bot should be when walking next to ledges.
+    ///
+    /// This multiplies the extra cost that the pathfinder adds for walking next
+    /// to a drop, and makes the bot slow down earlier when it does. Setting it
+    /// to 0 disables careful mode entirely, and values above 1 make the bot
+    /// more careful.
+    ///
+    /// Defaults to 1.
+    pub careful_mode_multiplier: Option<f32>,
+
+    /// Whether the bot should never stand on blocks that hurt it, like magma
+    /// blocks and lit campfires.
+    ///
+    /// If this is false, standing on them is only heavily penalized, so
+    /// they'll still be walked over if there's no other way.
+    ///
+    /// Defaults to false.
+    pub avoid_damage: Option<bool>,
+
+    /// Whether the bot is allowed to place blocks to bridge over gaps.
+    ///
+    /// The blocks that get placed are the ones in [`Self::throwaway_blocks`],
+    /// and they have to be in the bot's hotbar.
+    ///
+    /// Defaults to false.
+    pub allow_placing: Option<bool>,
+    /// The items that the bot is allowed to place when
+    /// [`Self::allow_placing`] is true.
+    ///
+    /// Defaults to [`DEFAULT_THROWAWAY_BLOCKS`].
+    ///
+    /// [`DEFAULT_THROWAWAY_BLOCKS`]: crate::pathfinder::requirements::DEFAULT_THROWAWAY_BLOCKS
+    pub throwaway_blocks: Option<Vec<Item>>,
+
+    /// The most fall damage that the bot is willing to take from a single
+    /// fall, as a fraction of its current health.
+    ///
+    /// Feather Falling and landing on blocks like hay bales are taken into
+    /// account. Setting this to 0 makes the bot never take fall damage.
+    ///
+    /// Defaults to 0.5, so a fall never takes more than half of the bot's
+    /// health.
+    pub max_fall_damage: Option<f32>,
+
+    /// Whether the path should be smoothed after it's calculated, so the bot
+    /// walks in straight lines across open ground instead of following the
+    /// path block by block.
+    ///
+    /// Only plain walking on solid ground is ever smoothed, so this doesn't
+    /// change anything about parkour, swimming, mining, or placing blocks.
+    ///
+    /// Defaults to true.
+    pub smooth_path: Option<bool>,
+
+    /// Parts of the world that the bot should stay out of, like protected
+    /// claims or the spawn area.
+    ///
+    /// A position counts as being in a region if the center of the block that
+    /// we'd be standing in is inside of it.
+    ///
+    /// Defaults to no regions.
+    pub avoid_regions: Option<Vec<AABB>>,
+    /// Whether the [`Self::avoid_regions`] can never be gone into, or whether
+    /// they're only expensive to go through.
+    ///
+    /// Defaults to [`RegionAvoidance::Hard`].
+    pub region_avoidance: Option<RegionAvoidance>,
+    /// A part of the world that the bot has to stay inside of.
+    ///
+    /// If we start outside of it then we can't go anywhere that's also
+    /// outside of it, so this should usually include where we are.
+    ///
+    /// Defaults to `None`, which means that we can go anywhere.
+    pub confine_to: Option<AABB>,
+
+    /// The dimension that the goal is in, like `minecraft:the_nether`.
+    ///
+    /// If this isn't the dimension that the bot is in, then it goes through a
+    /// nether portal that it's seen to get there. See the [`portals`] module.
+    ///
+    /// Defaults to `None`, which means that the goal is in the dimension that
+    /// the bot is in.
+    ///
+    /// [`portals`]: crate::pathfinder::portals
+    pub dimension: Option<ResourceLocation>,
 }
-impl GotoEvent {
-    pub fn new(entity: Entity, goal: impl Goal + 'static) -> Self {
+impl Default for GotoOptions {
+    fn default() -> Self {
         Self {
-            entity,
-            goal: Arc::new(goal),
             successors_fn: moves::default_move,
-            allow_mining: true,
-            retry_on_no_path: true,
-            min_timeout: PathfinderTimeout::Time(Duration::from_secs(1)),
-            max_timeout: PathfinderTimeout::Time(Duration::from_secs(5)),
+            allow_mining: None,
+            retry_on_no_path: None,
+            min_timeout: None,
+            max_timeout: None,
+            careful_mode_multiplier: None,
+            avoid_damage: None,
+            allow_placing: None,
+            throwaway_blocks: None,
+            max_fall_damage: None,
+            smooth_path: None,
+            avoid_regions: None,
+            region_avoidance: None,
+            confine_to: None,
+            dimension: None,
         }
     }
-    pub fn with_successors_fn(mut self, successors_fn: SuccessorsFn) -> Self {
+}
+impl GotoOptions {
+    pub fn successors_fn(mut self, successors_fn: SuccessorsFn) -> Self {
         self.successors_fn = successors_fn;
         self
     }
-    pub fn with_allow_mining(mut self, allow_mining: bool) -> Self {
-        self.allow_mining = allow_mining;
+    pub fn allow_mining(mut self, allow_mining: bool) -> Self {
+        self.allow_mining = Some(allow_mining);
         self
     }
-    pub fn with_retry_on_no_path(mut self, retry_on_no_path: bool) -> Self {
-        self.retry_on_no_path = retry_on_no_path;
+    pub fn retry_on_no_path(mut self, retry_on_no_path: bool) -> Self {
+        self.retry_on_no_path = Some(retry_on_no_path);
         self
     }
-    pub fn with_min_timeout(mut self, min_timeout: PathfinderTimeout) -> Self {
-        self.min_timeout = min_timeout;
+    pub fn min_timeout(mut self, min_timeout: PathfinderTimeout) -> Self {
+        self.min_timeout = Some(min_timeout);
         self
     }
-    pub fn with_max_timeout(mut self, max_timeout: PathfinderTimeout) -> Self {
-        self.max_timeout = max_timeout;
+    pub fn max_timeout(mut self, max_timeout: PathfinderTimeout) -> Self {
+        self.max_timeout = Some(max_timeout);
+        self
+    }
+    /// Set both [`Self::min_timeout`] and [`Self::max_timeout`], so the
+    /// pathfinder always gives up after exactly this long.
+    pub fn timeout(self, timeout: PathfinderTimeout) -> Self {
+        self.min_timeout(timeout).max_timeout(timeout)
+    }
+    pub fn careful_mode_multiplier(mut self, careful_mode_multiplier: f32) -> Self {
+        self.careful_mode_multiplier = Some(careful_mode_multiplier);
+        self
+    }
+    pub fn avoid_damage(mut self, avoid_damage: bool) -> Self {
+        self.avoid_damage = Some(avoid_damage);
+        self
+    }
+    pub fn allow_placing(mut self, allow_placing: bool) -> Self {
+        self.allow_placing = Some(allow_placing);
+        self
+    }
+    pub fn throwaway_blocks(mut self, throwaway_blocks: Vec<Item>) -> Self {
+        self.throwaway_blocks = Some(throwaway_blocks);
+        self
+    }
+    pub fn max_fall_damage(mut self, max_fall_damage: f32) -> Self {
+        self.max_fall_damage = Some(max_fall_damage);
+        self
+    }
+    pub fn smooth_path(mut self, smooth_path: bool) -> Self {
+        self.smooth_path = Some(smooth_path);
+        self
+    }
+    pub fn avoid_regions(mut self, avoid_regions: Vec<AABB>) -> Self {
+        self.avoid_regions = Some(avoid_regions);
+        self
+    }
+    pub fn region_avoidance(mut self, region_avoidance: RegionAvoidance) -> Self {
+        self.region_avoidance = Some(region_avoidance);
+        self
+    }
+    pub fn confine_to(mut self, confine_to: AABB) -> Self {
+        self.confine_to = Some(confine_to);
+        self
+    }
+    pub fn dimension(mut self, dimension: ResourceLocation) -> Self {
+        self.dimension = Some(dimension);
         self
     }
 }
@@ -0,0 +1,211 @@
+//! Getting out of lava and fire while executing a path.
+//!
+//! The pathfinder never plans paths through lava or fire, but we can still end
+//! up in them from knockback, a lava flow update, or just from the path being
+//! outdated. When that happens, following the old path would just keep us
+//! burning, so instead we cancel it and walk to the closest safe position
+//! before resuming the original goal.
+
+use std::sync::Arc;
+
+use azalea_block::BlockState;
+use azalea_client::{StartWalkEvent, WalkDirection, local_player::InstanceHolder};
+use azalea_core::{
+    aabb::AABB,
+    position::{BlockPos, Vec3},
+};
+use azalea_entity::{Physics, Position};
+use azalea_world::Instance;
+use bevy_ecs::prelude::*;
+use tracing::{info, warn};
+
+use super::{
+    ComputePath, ExecutingPath, GotoEvent, GotoOptions, Pathfinder,
+    astar::PathfinderTimeout,
+    goals::{BlockPosGoal, Goal},
+    world::{is_block_state_passable, is_block_state_standable},
+};
+
+/// How far away (in blocks) we look for a safe position when escaping from a
+/// hazard.
+pub const HAZARD_ESCAPE_RADIUS: i32 = 4;
+
+/// A component that's present on bots that are currently getting out of lava
+/// or fire.
+///
+/// The goal that was being pathfound to when the hazard was hit is stored here
+/// so it can be resumed afterwards.
+#[derive(Component, Clone)]
+pub struct EscapingHazard {
+    pub original_goal: Arc<dyn Goal>,
+    pub options: GotoOptions,
+}
+
+/// Sent when a bot got out of lava or fire that it ended up in while executing
+/// a path.
+///
+/// The original goal gets resumed automatically when this is sent, so if you
+/// don't want the bot to keep going you should stop pathfinding when you
+/// receive this event.
+#[derive(Event, Clone, Debug)]
+pub struct HazardEscapedEvent {
+    pub entity: Entity,
+    /// The goal that we were pathfinding to before we had to escape.
+    pub original_goal: Arc<dyn Goal>,
+}
+
+#[allow(clippy::type_complexity)]
+pub fn escape_hazards(
+    mut commands: Commands,
+    mut query: Query<(
+        Entity,
+        &mut Pathfinder,
+        &Position,
+        &Physics,
+        &InstanceHolder,
+        Option<&EscapingHazard>,
+    )>,
+    mut goto_events: EventWriter<GotoEvent>,
+    mut walk_events: EventWriter<StartWalkEvent>,
+    mut hazard_escaped_events: EventWriter<HazardEscapedEvent>,
+) {
+    for (entity, mut pathfinder, position, physics, instance_holder, escaping) in &mut query {
+        let instance = instance_holder.instance.read();
+        let is_touching_hazard =
+            physics.is_in_lava() || is_touching_hazard_block(&instance, &physics.bounding_box);
+
+        if let Some(escaping) = escaping {
+            if pathfinder.goal.is_some() || pathfinder.is_calculating {
+                // still escaping
+                continue;
+            }
+
+            if !is_touching_hazard {
+                info!("escaped from hazard, resuming original goal");
+                hazard_escaped_events.write(HazardEscapedEvent {
+                    entity,
+                    original_goal: escaping.original_goal.clone(),
+                });
+                goto_events.write(GotoEvent {
+                    entity,
+                    goal: escaping.original_goal.clone(),
+                    options: escaping.options.clone(),
+                });
+                pathfinder.is_calculating = true;
+                commands.entity(entity).remove::<EscapingHazard>();
+                continue;
+            }
+            // the escape path finished or failed but we're still burning, so
+            // try again
+        } else if !is_touching_hazard || pathfinder.goal.is_none() {
+            // we only handle hazards while we're pathfinding
+            continue;
+        }
+
+        let Some(safe_pos) = find_safe_position(&instance, **position, HAZARD_ESCAPE_RADIUS) else {
+            warn!("touching a hazard, but there's no safe position nearby");
+            continue;
+        };
+        warn!(
+            "touching a hazard at {}, escaping to {safe_pos}",
+            **position
+        );
+
+        if escaping.is_none()
+            && let Some(original_goal) = pathfinder.goal.clone()
+        {
+            commands.entity(entity).insert(EscapingHazard {
+                original_goal,
+                options: pathfinder.goto_options(),
+            });
+        }
+
+        // cancel the current path immediately, it's not safe to let the current move
+        // finish
+        commands
+            .entity(entity)
+            .remove::<ExecutingPath>()
+            .remove::<ComputePath>();
+        walk_events.write(StartWalkEvent {
+            entity,
+            direction: WalkDirection::None,
+        });
+
+        goto_events.write(
+            GotoEvent::new(entity, BlockPosGoal(safe_pos)).with_options(
+                GotoOptions::default()
+                    .allow_mining(false)
+                    .retry_on_no_path(false)
+                    .min_timeout(PathfinderTimeout::Nodes(5_000))
+                    .max_timeout(PathfinderTimeout::Nodes(20_000)),
+            ),
+        );
+        // make sure the original goal doesn't get recalculated in the meantime
+        pathfinder.goal = None;
+        pathfinder.is_calculating = true;
+    }
+}
+
+fn is_hazard(block_state: BlockState) -> bool {
+    matches!(
+        azalea_registry::Block::from(block_state),
+        azalea_registry::Block::Lava
+            | azalea_registry::Block::Fire
+            | azalea_registry::Block::SoulFire
+    )
+}
+
+fn is_touching_hazard_block(instance: &Instance, bounding_box: &AABB) -> bool {
+    AABB::between_closed_aabb(bounding_box)
+        .into_iter()
+        .any(|pos| is_hazard(instance.get_block_state(pos).unwrap_or_default()))
+}
+
+/// Find the closest position within the given radius that we can stand in
+/// without touching lava or fire.
+///
+/// Water counts as safe too, since it puts out the fire.
+pub fn find_safe_position(instance: &Instance, position: Vec3, radius: i32) -> Option<BlockPos> {
+    let get_block_state = |pos: BlockPos| instance.get_block_state(pos).unwrap_or_default();
+    let origin = BlockPos::from(position);
+
+    let mut closest: Option<(f64, BlockPos)> = None;
+    for x in -radius..=radius {
+        for y in -radius..=radius {
+            for z in -radius..=radius {
+                let pos = origin + BlockPos::new(x, y, z);
+
+                let feet = get_block_state(pos);
+                let head = get_block_state(pos.up(1));
+                if !is_block_state_passable(feet) || !is_block_state_passable(head) {
+                    continue;
+                }
+                let is_water = azalea_registry::Block::from(feet) == azalea_registry::Block::Water;
+                if !is_water && !is_block_state_standable(get_block_state(pos.down(1))) {
+                    continue;
+                }
+
+                let is_next_to_hazard = [
+                    pos.down(1),
+                    pos.up(2),
+                    pos.north(1),
+                    pos.east(1),
+                    pos.south(1),
+                    pos.west(1),
+                ]
+                .into_iter()
+                .any(|pos| is_hazard(get_block_state(pos)));
+                if is_next_to_hazard {
+                    continue;
+                }
+
+                let distance = pos.center().distance_squared_to(position);
+                if closest.is_none_or(|(closest_distance, _)| distance < closest_distance) {
+                    closest = Some((distance, pos));
+                }
+            }
+        }
+    }
+
+    closest.map(|(_, pos)| pos)
+}
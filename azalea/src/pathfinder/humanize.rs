@@ -0,0 +1,146 @@
+//! Making path execution look less robotic.
+//!
+//! Some servers kick or flag players that break blocks back-to-back with no
+//! delay or that snap their rotation instantly. Adding [`HumanizeSettings`] to
+//! a bot makes the pathfinder pace its block breaking and turn its head over a
+//! few ticks instead.
+
+use azalea_core::position::BlockPos;
+use azalea_entity::LookDirection;
+use bevy_ecs::prelude::*;
+
+/// Settings for pacing the actions that the pathfinder does while executing a
+/// path.
+///
+/// Everything is disabled by default.
+#[derive(Component, Clone, Debug, Default, PartialEq)]
+pub struct HumanizeSettings {
+    /// The minimum number of ticks between starting to break two blocks.
+    pub min_ticks_between_breaks: u32,
+    /// The maximum number of blocks that we can start breaking every second.
+    pub max_breaks_per_second: Option<f32>,
+    /// The maximum number of degrees that we can turn every tick, both
+    /// horizontally and vertically.
+    ///
+    /// `None` means we'll snap to look at things instantly.
+    pub max_rotation_per_tick: Option<f32>,
+}
+
+impl HumanizeSettings {
+    /// The number of ticks that have to pass after we start breaking a block
+    /// before we can start breaking the next one.
+    pub fn break_interval(&self) -> u32 {
+        let from_breaks_per_second = self
+            .max_breaks_per_second
+            .map(|max_breaks_per_second| (20. / max_breaks_per_second).ceil() as u32)
+            .unwrap_or_default();
+        u32::max(self.min_ticks_between_breaks, from_breaks_per_second)
+    }
+}
+
+/// Keeps track of when we last started breaking a block, for
+/// [`HumanizeSettings`].
+///
+/// This is added automatically to bots that have [`HumanizeSettings`].
+#[derive(Component, Clone, Debug, Default)]
+pub struct HumanizeState {
+    /// The number of ticks since this component was added.
+    pub tick: u64,
+    /// The tick that we last started breaking a block on.
+    pub last_break_start: Option<u64>,
+    /// The block that we last started breaking.
+    pub last_break_pos: Option<BlockPos>,
+}
+
+impl HumanizeState {
+    pub fn can_start_break(&self, settings: &HumanizeSettings) -> bool {
+        match self.last_break_start {
+            Some(last_break_start) => {
+                self.tick - last_break_start >= settings.break_interval() as u64
+            }
+            None => true,
+        }
+    }
+
+    pub fn record_break_start(&mut self, pos: BlockPos) {
+        self.last_break_start = Some(self.tick);
+        self.last_break_pos = Some(pos);
+    }
+}
+
+/// Turn from `current` towards `target`, by at most `max_degrees` on each
+/// axis.
+pub fn rotate_towards(
+    current: LookDirection,
+    target: LookDirection,
+    max_degrees: f32,
+) -> LookDirection {
+    // take the shortest way around
+    let y_rot_delta = (target.y_rot - current.y_rot + 180.).rem_euclid(360.) - 180.;
+    let x_rot_delta = target.x_rot - current.x_rot;
+
+    // snap to the target when we're close enough so floating point errors don't
+    // keep us from ever reaching it
+    let y_rot = if y_rot_delta.abs() <= max_degrees {
+        target.y_rot
+    } else {
+        (current.y_rot + y_rot_delta.signum() * max_degrees).rem_euclid(360.)
+    };
+    let x_rot = if x_rot_delta.abs() <= max_degrees {
+        target.x_rot
+    } else {
+        current.x_rot + x_rot_delta.signum() * max_degrees
+    };
+
+    LookDirection { y_rot, x_rot }
+}
+
+pub fn add_humanize_state(
+    mut commands: Commands,
+    query: Query<Entity, (With<HumanizeSettings>, Without<HumanizeState>)>,
+) {
+    for entity in &query {
+        commands.entity(entity).insert(HumanizeState::default());
+    }
+}
+
+pub fn tick_humanize_state(mut query: Query<&mut HumanizeState>) {
+    for mut state in &mut query {
+        state.tick += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_break_interval() {
+        let settings = HumanizeSettings {
+            min_ticks_between_breaks: 3,
+            max_breaks_per_second: Some(2.),
+            ..Default::default()
+        };
+        assert_eq!(settings.break_interval(), 10);
+        assert_eq!(HumanizeSettings::default().break_interval(), 0);
+    }
+
+    #[test]
+    fn test_rotate_towards_wraps_around() {
+        let current = LookDirection {
+            y_rot: 350.,
+            x_rot: 0.,
+        };
+        let target = LookDirection {
+            y_rot: 20.,
+            x_rot: -60.,
+        };
+        let rotated = rotate_towards(current, target, 15.);
+        assert_eq!(rotated.y_rot, 5.);
+        assert_eq!(rotated.x_rot, -15.);
+
+        let rotated = rotate_towards(rotated, target, 15.);
+        assert_eq!(rotated.y_rot, 20.);
+        assert_eq!(rotated.x_rot, -30.);
+    }
+}
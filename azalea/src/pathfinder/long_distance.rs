@@ -0,0 +1,302 @@
+//! Finding paths to goals that are very far away.
+//!
+//! A* has to look at every position that could be on a cheaper path than the
+//! one that it ends up finding, which gets very slow for long paths (like when
+//! there's a long wall in the way). So when the goal is further away than
+//! [`PathfinderDefaults::long_distance_threshold`], we first find a rough
+//! route through chunks, where a chunk is connected to its neighbor if there's
+//! somewhere that we can walk across the border between them. Then the normal
+//! search is done a few chunks at a time, and it can only go into the chunks
+//! that are on the route or next to it.
+//!
+//! [`PathfinderDefaults::long_distance_threshold`]: super::settings::PathfinderDefaults::long_distance_threshold
+
+use std::{
+    collections::{HashMap, HashSet},
+    time::Instant,
+};
+
+use azalea_core::position::{BlockPos, ChunkPos};
+use tracing::{debug, trace};
+
+use super::{
+    astar::{self, Edge, PathfinderTimeout, a_star},
+    costs::{COST_HEURISTIC, MAX_SAFE_FALL_DISTANCE, SPRINT_ONE_BLOCK_COST},
+    goals::{Goal, XZGoal},
+    metrics::PathfinderMetrics,
+    moves::{MoveData, water::SwimmingState},
+    rel_block_pos::RelBlockPos,
+    turn_cost,
+    world::CachedWorld,
+};
+
+/// The default for [`PathfinderDefaults::long_distance_threshold`].
+///
+/// [`PathfinderDefaults::long_distance_threshold`]: super::settings::PathfinderDefaults::long_distance_threshold
+pub const DEFAULT_LONG_DISTANCE_THRESHOLD: f32 = 256.;
+
+/// How many chunks along the route we go in each of the normal searches.
+const SEGMENT_CHUNKS: usize = 4;
+/// The cost of going from a chunk to its neighbor in the route.
+const CHUNK_COST: f32 = 16. * SPRINT_ONE_BLOCK_COST;
+/// The most chunks that we look at while finding a route, so it doesn't take
+/// forever when the goal is somewhere that we can't get to.
+const MAX_ROUTE_NODES: usize = 10_000;
+
+/// Whether the goal is far enough away that we should use [`find_path`]
+/// instead of a normal search.
+pub fn is_long_distance(goal: &dyn Goal, start: BlockPos, threshold: f32) -> bool {
+    goal.heuristic(start) > threshold * COST_HEURISTIC
+}
+
+/// Find a path by doing the normal search through a rough route of chunks.
+///
+/// `successors` and `next_state` are the same functions that the normal
+/// search uses. This returns `None` if we couldn't find a route or couldn't
+/// start following it, in which case the normal search should be done
+/// instead.
+pub fn find_path(
+    world: &CachedWorld,
+    goal: &dyn Goal,
+    start_swimming: SwimmingState,
+    successors: impl Fn(RelBlockPos, SwimmingState, &mut Vec<Edge<RelBlockPos, MoveData>>),
+    next_state: impl Fn(SwimmingState, RelBlockPos) -> SwimmingState,
+    min_timeout: PathfinderTimeout,
+    max_timeout: PathfinderTimeout,
+) -> Option<astar::Path<RelBlockPos, MoveData>> {
+    let origin = world.origin();
+    let start_time = Instant::now();
+
+    let (route, reaches_goal) = find_route(world, goal, origin);
+    if route.len() < 2 {
+        debug!("couldn't find a route through chunks, doing a normal search instead");
+        return None;
+    }
+    debug!(
+        "found a route through {} chunks (reaches goal: {reaches_goal})",
+        route.len()
+    );
+
+    // the normal search can go into the chunks next to the route too, so it has
+    // some room to get around things
+    let mut allowed_chunks = HashSet::new();
+    for chunk_pos in &route {
+        for dx in -1..=1 {
+            for dz in -1..=1 {
+                allowed_chunks.insert(ChunkPos::new(chunk_pos.x + dx, chunk_pos.z + dz));
+            }
+        }
+    }
+    let successors_in_route = |pos: RelBlockPos, swimming: SwimmingState, edges: &mut Vec<_>| {
+        successors(pos, swimming, edges);
+        edges.retain(|edge| {
+            allowed_chunks.contains(&ChunkPos::from(edge.movement.target.apply(origin)))
+        });
+    };
+
+    let mut movements = Vec::new();
+    let mut metrics = PathfinderMetrics::default();
+    let mut position = RelBlockPos::get_origin(origin);
+    let mut swimming = start_swimming;
+    let mut route_index = 0;
+    loop {
+        let waypoint_index = (route_index + SEGMENT_CHUNKS).min(route.len() - 1);
+        let waypoint = route[waypoint_index];
+        let is_last_segment = reaches_goal && waypoint_index == route.len() - 1;
+        trace!("searching from {position:?} to chunk {waypoint:?}");
+
+        let segment = a_star(
+            position,
+            swimming,
+            |n| {
+                let n = n.apply(origin);
+                if is_last_segment {
+                    goal.heuristic(n)
+                } else {
+                    chunk_heuristic(waypoint, n)
+                }
+            },
+            &successors_in_route,
+            |n| {
+                let n = n.apply(origin);
+                goal.success(n) || (!is_last_segment && ChunkPos::from(n) == waypoint)
+            },
+            turn_cost,
+            &next_state,
+            min_timeout,
+            max_timeout,
+        );
+        metrics.add(&segment.metrics);
+
+        if segment.movements.is_empty() {
+            if movements.is_empty() {
+                debug!("couldn't start following the route, doing a normal search instead");
+                return None;
+            }
+            break;
+        }
+        for movement in &segment.movements {
+            swimming = next_state(swimming, movement.target);
+        }
+        position = segment.movements.last().unwrap().target;
+        movements.extend(segment.movements);
+        // the path gets cut off where it goes into a chunk that isn't loaded, so
+        // there's no point in looking any further
+        if world.is_block_unknown(position) {
+            break;
+        }
+
+        if !segment.is_partial && goal.success(position.apply(origin)) {
+            return Some(astar::Path {
+                movements,
+                is_partial: false,
+                metrics,
+            });
+        }
+        if segment.is_partial || waypoint_index == route.len() - 1 {
+            break;
+        }
+        // if we've already spent as long as a normal search would have, the rest of
+        // the path is found after we start walking
+        if is_timeout_reached(min_timeout, start_time, metrics.nodes_expanded) {
+            debug!("ran out of time while following the route");
+            break;
+        }
+        route_index = waypoint_index;
+    }
+
+    Some(astar::Path {
+        movements,
+        is_partial: true,
+        metrics,
+    })
+}
+
+/// Find the chunks that we should go through to get to the goal, starting with
+/// the one that we're in.
+///
+/// The route is also returned if it doesn't reach the goal, in which case it
+/// ends at the chunk that seemed to be closest to it. The bool is whether it
+/// reaches the goal.
+fn find_route(world: &CachedWorld, goal: &dyn Goal, origin: BlockPos) -> (Vec<ChunkPos>, bool) {
+    let start = ChunkPos::from(origin);
+    let chunk_center =
+        |chunk_pos: ChunkPos| BlockPos::new(chunk_pos.x * 16 + 8, origin.y, chunk_pos.z * 16 + 8);
+
+    let mut crossings = Crossings::new(world);
+    let route = a_star(
+        start,
+        (),
+        |chunk_pos| goal.heuristic(chunk_center(chunk_pos)),
+        |chunk_pos, (), edges: &mut Vec<_>| {
+            edges.extend(
+                [(1, 0), (-1, 0), (0, 1), (0, -1)]
+                    .into_iter()
+                    .map(|(dx, dz)| ChunkPos::new(chunk_pos.x + dx, chunk_pos.z + dz))
+                    .filter(|&neighbor| crossings.can_cross(chunk_pos, neighbor))
+                    .map(|neighbor| Edge {
+                        movement: astar::Movement {
+                            target: neighbor,
+                            data: (),
+                        },
+                        cost: CHUNK_COST,
+                    }),
+            )
+        },
+        // the goal is somewhere in this chunk or next to it
+        |chunk_pos| goal.heuristic(chunk_center(chunk_pos)) < CHUNK_COST,
+        |_, _, _| 0.,
+        |(), _| (),
+        PathfinderTimeout::Nodes(MAX_ROUTE_NODES),
+        PathfinderTimeout::Nodes(MAX_ROUTE_NODES),
+    );
+
+    let mut chunks = vec![start];
+    chunks.extend(route.movements.into_iter().map(|movement| movement.target));
+    (chunks, !route.is_partial)
+}
+
+/// Which chunks we can walk between, cached so we only check each border once.
+struct Crossings<'a> {
+    world: &'a CachedWorld,
+    can_cross: HashMap<(ChunkPos, ChunkPos), bool>,
+}
+impl<'a> Crossings<'a> {
+    fn new(world: &'a CachedWorld) -> Self {
+        Self {
+            world,
+            can_cross: HashMap::new(),
+        }
+    }
+
+    /// Whether there's somewhere that we can walk from the chunk into its
+    /// neighbor.
+    ///
+    /// We don't know what's in chunks that aren't loaded, so we assume that we
+    /// can walk through them.
+    fn can_cross(&mut self, from: ChunkPos, to: ChunkPos) -> bool {
+        if let Some(&can_cross) = self.can_cross.get(&(from, to)) {
+            return can_cross;
+        }
+        let can_cross = !self.world.is_chunk_loaded(from)
+            || !self.world.is_chunk_loaded(to)
+            || border_columns(from, to).any(|(from_column, to_column)| {
+                let from_ys = self.standable_ys(from_column);
+                let to_ys = self.standable_ys(to_column);
+                from_ys.iter().any(|&from_y| {
+                    to_ys.iter().any(|&to_y| {
+                        to_y - from_y <= 1 && from_y - to_y <= MAX_SAFE_FALL_DISTANCE as i32
+                    })
+                })
+            });
+        self.can_cross.insert((from, to), can_cross);
+        can_cross
+    }
+
+    /// The y coordinates of the positions in the column that we can stand in.
+    fn standable_ys(&self, (x, z): (i32, i32)) -> Vec<i32> {
+        let origin = self.world.origin();
+        self.world
+            .y_range()
+            .map(|y| RelBlockPos::from_origin(origin, BlockPos::new(x, y, z)))
+            // checking whether it's passable first is a lot faster for solid blocks
+            .filter(|&pos| self.world.is_passable(pos) && self.world.is_standable(pos))
+            .map(|pos| pos.y)
+            .collect()
+    }
+}
+
+/// The pairs of block columns that are next to each other on the border
+/// between two neighboring chunks.
+fn border_columns(from: ChunkPos, to: ChunkPos) -> impl Iterator<Item = ((i32, i32), (i32, i32))> {
+    let (dx, dz) = (to.x - from.x, to.z - from.z);
+    // the column in `from` that's furthest in the direction of `to`
+    let edge = |chunk: i32, d: i32| if d > 0 { chunk * 16 + 15 } else { chunk * 16 };
+    (0..16).map(move |i| {
+        let from_column = if dx != 0 {
+            (edge(from.x, dx), from.z * 16 + i)
+        } else {
+            (from.x * 16 + i, edge(from.z, dz))
+        };
+        (from_column, (from_column.0 + dx, from_column.1 + dz))
+    })
+}
+
+/// The heuristic for getting into the chunk, which is the distance to the
+/// closest block in it.
+fn chunk_heuristic(chunk_pos: ChunkPos, n: BlockPos) -> f32 {
+    let min_x = chunk_pos.x * 16;
+    let min_z = chunk_pos.z * 16;
+    XZGoal {
+        x: n.x.clamp(min_x, min_x + 15),
+        z: n.z.clamp(min_z, min_z + 15),
+    }
+    .heuristic(n)
+}
+
+fn is_timeout_reached(timeout: PathfinderTimeout, start_time: Instant, num_nodes: usize) -> bool {
+    match timeout {
+        PathfinderTimeout::Time(max_duration) => start_time.elapsed() >= max_duration,
+        PathfinderTimeout::Nodes(max_nodes) => num_nodes >= max_nodes,
+    }
+}
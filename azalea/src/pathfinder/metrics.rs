@@ -0,0 +1,85 @@
+//! Statistics about path calculations, for figuring out why a path took a long
+//! time to find.
+//!
+//! These are only plain counters, and the timers only look at the clock for a
+//! small sample of the nodes, so collecting them doesn't slow the search down
+//! noticeably.
+
+use std::time::Duration;
+
+use bevy_ecs::prelude::*;
+
+use super::PathComputedEvent;
+
+/// What happened during a path calculation.
+///
+/// This is included in every [`PathComputedEvent`], and the bot also has the
+/// metrics for the last path that it calculated as a component.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq)]
+pub struct PathfinderMetrics {
+    /// How many nodes A* expanded, which is how many times it looked for the
+    /// moves from a node.
+    pub nodes_expanded: usize,
+    /// How many moves to other nodes were found while expanding nodes. This is
+    /// at least as many as `nodes_expanded` unless the search got stuck.
+    pub nodes_generated: usize,
+    /// How many nodes were still in the open set when the search ended.
+    pub open_set_size: usize,
+    /// About how long was spent finding the moves from nodes, which is mostly
+    /// looking at blocks in the world.
+    ///
+    /// Only some of the nodes are timed, so this is an estimate.
+    pub world_access_time: Duration,
+    /// About how long was spent adding nodes to and taking them from the open
+    /// set. Like `world_access_time`, this is an estimate.
+    pub heap_time: Duration,
+    /// How many times a chunk section that was already cached in the
+    /// [`CachedWorld`] was looked at.
+    ///
+    /// [`CachedWorld`]: super::world::CachedWorld
+    pub section_cache_hits: usize,
+    /// How many chunk sections had to be copied from the world into the
+    /// [`CachedWorld`].
+    ///
+    /// [`CachedWorld`]: super::world::CachedWorld
+    pub section_cache_misses: usize,
+    /// How many times we checked how long a block would take to break.
+    pub mining_cost_lookups: usize,
+}
+impl PathfinderMetrics {
+    /// Estimate the total times from the ones that were measured for one in
+    /// every `sample_interval` nodes.
+    pub fn set_sampled_times(
+        &mut self,
+        world_access_time: Duration,
+        heap_time: Duration,
+        sample_interval: usize,
+    ) {
+        self.world_access_time = world_access_time * sample_interval as u32;
+        self.heap_time = heap_time * sample_interval as u32;
+    }
+
+    /// Add the counts from another search to these, like when a path is found
+    /// in multiple parts.
+    ///
+    /// The open set size is replaced, since only the last search's open set is
+    /// left when we're done.
+    pub fn add(&mut self, other: &Self) {
+        self.nodes_expanded += other.nodes_expanded;
+        self.nodes_generated += other.nodes_generated;
+        self.open_set_size = other.open_set_size;
+        self.world_access_time += other.world_access_time;
+        self.heap_time += other.heap_time;
+        self.section_cache_hits += other.section_cache_hits;
+        self.section_cache_misses += other.section_cache_misses;
+        self.mining_cost_lookups += other.mining_cost_lookups;
+    }
+}
+
+/// Keep the [`PathfinderMetrics`] component up to date with the last path that
+/// each bot calculated.
+pub fn record_path_metrics(mut events: EventReader<PathComputedEvent>, mut commands: Commands) {
+    for event in events.read() {
+        commands.entity(event.entity).try_insert(event.metrics);
+    }
+}
@@ -1,22 +1,40 @@
-use std::{cell::UnsafeCell, ops::RangeInclusive};
+use std::{ops::RangeInclusive, sync::LazyLock};
 
 use azalea_block::{
-    BlockState, BlockStates, block_state::BlockStateIntegerRepr, properties::Waterlogged,
+    BlockState, BlockStates, block_state::BlockStateIntegerRepr, fluid_state::FluidKind,
+    properties::Waterlogged,
 };
+use azalea_entity::{ActiveEffects, FluidOnEyes, Physics};
 use azalea_inventory::Menu;
 use nohash_hasher::IntMap;
+use parking_lot::RwLock;
 
 use super::costs::BLOCK_BREAK_ADDITIONAL_PENALTY;
-use crate::auto_tool::best_tool_in_hotbar_for_block;
+use crate::auto_tool::{DEFAULT_MIN_TOOL_DURABILITY, accurate_best_tool_in_hotbar_for_block};
 
+/// Calculates how expensive it is for the pathfinder to break blocks.
+///
+/// The costs are cached per block state behind a lock, so the cache can be
+/// shared between threads.
 pub struct MiningCache {
-    block_state_id_costs: UnsafeCell<IntMap<BlockStateIntegerRepr, f32>>,
+    block_state_id_costs: RwLock<IntMap<BlockStateIntegerRepr, f32>>,
     inventory_menu: Option<Menu>,
+    /// The effects that we had when the cache was made, for Haste and Mining
+    /// Fatigue.
+    active_effects: ActiveEffects,
 
     water_block_state_range: RangeInclusive<BlockStateIntegerRepr>,
     lava_block_state_range: RangeInclusive<BlockStateIntegerRepr>,
 
-    falling_blocks: Vec<BlockState>,
+    /// The minimum number of ticks that breaking a block takes, see
+    /// [`HumanizeSettings::break_interval`].
+    ///
+    /// [`HumanizeSettings::break_interval`]: super::humanize::HumanizeSettings::break_interval
+    break_interval: f32,
+    /// See [`PathfinderDefaults::mining_cost_multiplier`].
+    ///
+    /// [`PathfinderDefaults::mining_cost_multiplier`]: super::settings::PathfinderDefaults::mining_cost_multiplier
+    cost_multiplier: f32,
 }
 
 impl MiningCache {
@@ -40,40 +58,62 @@ impl MiningCache {
         }
         let lava_block_state_range = lava_block_state_range_min..=lava_block_state_range_max;
 
-        let mut falling_blocks: Vec<BlockState> = vec![
-            azalea_registry::Block::Sand.into(),
-            azalea_registry::Block::RedSand.into(),
-            azalea_registry::Block::Gravel.into(),
-            azalea_registry::Block::Anvil.into(),
-            azalea_registry::Block::ChippedAnvil.into(),
-            azalea_registry::Block::DamagedAnvil.into(),
-            // concrete powders
-            azalea_registry::Block::WhiteConcretePowder.into(),
-            azalea_registry::Block::OrangeConcretePowder.into(),
-            azalea_registry::Block::MagentaConcretePowder.into(),
-            azalea_registry::Block::LightBlueConcretePowder.into(),
-            azalea_registry::Block::YellowConcretePowder.into(),
-            azalea_registry::Block::LimeConcretePowder.into(),
-            azalea_registry::Block::PinkConcretePowder.into(),
-            azalea_registry::Block::GrayConcretePowder.into(),
-            azalea_registry::Block::LightGrayConcretePowder.into(),
-            azalea_registry::Block::CyanConcretePowder.into(),
-            azalea_registry::Block::PurpleConcretePowder.into(),
-            azalea_registry::Block::BlueConcretePowder.into(),
-            azalea_registry::Block::BrownConcretePowder.into(),
-            azalea_registry::Block::GreenConcretePowder.into(),
-            azalea_registry::Block::RedConcretePowder.into(),
-            azalea_registry::Block::BlackConcretePowder.into(),
-        ];
-        falling_blocks.sort_unstable_by_key(|block| block.id());
-
         Self {
-            block_state_id_costs: UnsafeCell::new(IntMap::default()),
+            block_state_id_costs: RwLock::new(IntMap::default()),
             inventory_menu,
+            active_effects: ActiveEffects::default(),
             water_block_state_range,
             lava_block_state_range,
-            falling_blocks,
+            break_interval: 0.,
+            cost_multiplier: 1.,
+        }
+    }
+
+    /// Make every block take at least this many ticks to break, to account for
+    /// the delays added by [`HumanizeSettings`].
+    ///
+    /// [`HumanizeSettings`]: super::humanize::HumanizeSettings
+    pub fn with_break_interval(mut self, ticks: u32) -> Self {
+        self.break_interval = ticks as f32;
+        self
+    }
+
+    /// Replace the inventory that the costs are calculated with, like after
+    /// picking up a better tool or breaking one.
+    ///
+    /// The costs that were already calculated are only forgotten if something
+    /// in the hotbar changed, since those are the only slots that we mine with.
+    pub fn set_inventory(&mut self, inventory_menu: Option<Menu>) {
+        let hotbar = |menu: &Option<Menu>| {
+            menu.as_ref()
+                .map(|menu| menu.slots()[menu.hotbar_slots_range()].to_vec())
+        };
+        if hotbar(&self.inventory_menu) != hotbar(&inventory_menu) {
+            self.block_state_id_costs.get_mut().clear();
         }
+        self.inventory_menu = inventory_menu;
+    }
+
+    /// Calculate the costs with these effects, so Haste makes mining cheaper
+    /// and Mining Fatigue makes it much more expensive.
+    pub fn with_active_effects(mut self, active_effects: ActiveEffects) -> Self {
+        self.active_effects = active_effects;
+        self
+    }
+
+    /// Replace the effects that the costs are calculated with, like after
+    /// drinking a potion or getting Mining Fatigue from an elder guardian.
+    pub fn set_active_effects(&mut self, active_effects: ActiveEffects) {
+        if self.active_effects != active_effects {
+            self.block_state_id_costs.get_mut().clear();
+        }
+        self.active_effects = active_effects;
+    }
+
+    /// Multiply the cost of breaking every block by this much.
+    pub fn with_cost_multiplier(mut self, multiplier: f32) -> Self {
+        self.cost_multiplier = multiplier;
+        self
     }
 
     pub fn cost_for(&self, block: BlockState) -> f32 {
@@ -81,18 +121,35 @@ impl MiningCache {
             return f32::INFINITY;
         };
 
-        // SAFETY: mining is single-threaded, so this is safe
-        let block_state_id_costs = unsafe { &mut *self.block_state_id_costs.get() };
-
-        if let Some(cost) = block_state_id_costs.get(&block.id()) {
-            *cost
+        // the guard is dropped at the end of this statement, so we don't hold the lock
+        // while calculating the cost
+        let cached_cost = self.block_state_id_costs.read().get(&block.id()).copied();
+        if let Some(cost) = cached_cost {
+            cost
         } else {
-            let best_tool_result = best_tool_in_hotbar_for_block(block, inventory_menu);
-            let mut cost = 1. / best_tool_result.percentage_per_tick;
+            // almost all of the blocks in a path are broken while standing on the ground
+            // and out of water, so the cost assumes that
+            let mut physics = Physics::default();
+            physics.set_on_ground(true);
+            let best_tool_result = accurate_best_tool_in_hotbar_for_block(
+                block,
+                inventory_menu,
+                &physics,
+                &FluidOnEyes::new(FluidKind::Empty),
+                &self.active_effects,
+                DEFAULT_MIN_TOOL_DURABILITY,
+            );
+            // we have to wait between starting to break blocks if we're being humanized
+            let mut cost = f32::max(
+                1. / best_tool_result.percentage_per_tick,
+                self.break_interval,
+            );
 
             cost += BLOCK_BREAK_ADDITIONAL_PENALTY;
+            cost *= self.cost_multiplier;
 
-            block_state_id_costs.insert(block.id(), cost);
+            // if another thread got here first, it calculated the same cost
+            self.block_state_id_costs.write().insert(block.id(), cost);
             cost
         }
     }
@@ -107,12 +164,147 @@ impl MiningCache {
     }
 
     pub fn is_falling_block(&self, block: BlockState) -> bool {
-        self.falling_blocks
-            .binary_search_by_key(&block.id(), |block| block.id())
-            .is_ok()
+        is_falling_block(block)
     }
 }
 
+/// Blocks that fall when the block under them is broken, sorted by their ID.
+static FALLING_BLOCKS: LazyLock<Vec<BlockState>> = LazyLock::new(|| {
+    let mut falling_blocks: Vec<BlockState> = vec![
+        azalea_registry::Block::Sand.into(),
+        azalea_registry::Block::RedSand.into(),
+        azalea_registry::Block::Gravel.into(),
+        azalea_registry::Block::Anvil.into(),
+        azalea_registry::Block::ChippedAnvil.into(),
+        azalea_registry::Block::DamagedAnvil.into(),
+        // concrete powders
+        azalea_registry::Block::WhiteConcretePowder.into(),
+        azalea_registry::Block::OrangeConcretePowder.into(),
+        azalea_registry::Block::MagentaConcretePowder.into(),
+        azalea_registry::Block::LightBlueConcretePowder.into(),
+        azalea_registry::Block::YellowConcretePowder.into(),
+        azalea_registry::Block::LimeConcretePowder.into(),
+        azalea_registry::Block::PinkConcretePowder.into(),
+        azalea_registry::Block::GrayConcretePowder.into(),
+        azalea_registry::Block::LightGrayConcretePowder.into(),
+        azalea_registry::Block::CyanConcretePowder.into(),
+        azalea_registry::Block::PurpleConcretePowder.into(),
+        azalea_registry::Block::BlueConcretePowder.into(),
+        azalea_registry::Block::BrownConcretePowder.into(),
+        azalea_registry::Block::GreenConcretePowder.into(),
+        azalea_registry::Block::RedConcretePowder.into(),
+        azalea_registry::Block::BlackConcretePowder.into(),
+    ];
+    falling_blocks.sort_unstable_by_key(|block| block.id());
+    falling_blocks
+});
+
+/// Whether this block falls when the block under it is broken, like sand and
+/// gravel.
+pub fn is_falling_block(block: BlockState) -> bool {
+    FALLING_BLOCKS
+        .binary_search_by_key(&block.id(), |block| block.id())
+        .is_ok()
+}
+
 pub fn is_waterlogged(block: BlockState) -> bool {
     block.property::<Waterlogged>().unwrap_or_default()
 }
+
+#[cfg(test)]
+mod tests {
+    use azalea_entity::MobEffectData;
+    use azalea_inventory::Player;
+    use azalea_registry::{Block, Item, MobEffect};
+
+    use super::*;
+
+    fn menu_with_tool(tool: Item) -> Menu {
+        let mut menu = Menu::Player(Player::default());
+        *menu.slot_mut(*Player::HOTBAR_SLOTS.start()).unwrap() = tool.into();
+        menu
+    }
+
+    #[test]
+    fn test_set_inventory_forgets_costs() {
+        let stone = Block::Stone.into();
+        let mut mining_cache = MiningCache::new(Some(menu_with_tool(Item::WoodenPickaxe)));
+        let wooden_cost = mining_cache.cost_for(stone);
+
+        mining_cache.set_inventory(Some(menu_with_tool(Item::NetheritePickaxe)));
+        let netherite_cost = mining_cache.cost_for(stone);
+        assert!(netherite_cost < wooden_cost);
+
+        // the hotbar didn't change, so the cost is still cached
+        let mut menu = menu_with_tool(Item::NetheritePickaxe);
+        let inventory_slot = *Player::INVENTORY_WITHOUT_HOTBAR_SLOTS.start();
+        *menu.slot_mut(inventory_slot).unwrap() = Item::Dirt.into();
+        mining_cache.set_inventory(Some(menu));
+        assert_eq!(mining_cache.block_state_id_costs.get_mut().len(), 1);
+        assert_eq!(mining_cache.cost_for(stone), netherite_cost);
+    }
+
+    #[test]
+    fn test_effects_change_costs() {
+        let stone = Block::Stone.into();
+        let effect = |effect: MobEffect| {
+            let mut active_effects = ActiveEffects::default();
+            active_effects.insert(
+                effect,
+                MobEffectData {
+                    amplifier: 0,
+                    duration_ticks: 6000,
+                },
+            );
+            active_effects
+        };
+        let menu = Some(menu_with_tool(Item::IronPickaxe));
+        let normal_cost = MiningCache::new(menu.clone()).cost_for(stone);
+
+        let mut mining_cache = MiningCache::new(menu).with_active_effects(effect(MobEffect::Haste));
+        let haste_cost = mining_cache.cost_for(stone);
+        assert!(haste_cost < normal_cost);
+
+        mining_cache.set_active_effects(effect(MobEffect::MiningFatigue));
+        let mining_fatigue_cost = mining_cache.cost_for(stone);
+        assert!(mining_fatigue_cost > normal_cost * 2.);
+    }
+
+    #[test]
+    fn test_cost_for_from_multiple_threads() {
+        let blocks: Vec<BlockState> = [
+            Block::Stone,
+            Block::Dirt,
+            Block::OakLog,
+            Block::Obsidian,
+            Block::DiamondOre,
+            Block::Sand,
+        ]
+        .into_iter()
+        .map(BlockState::from)
+        .collect();
+        let menu = Some(menu_with_tool(Item::IronPickaxe));
+        let expected: Vec<f32> = {
+            let mining_cache = MiningCache::new(menu.clone());
+            blocks
+                .iter()
+                .map(|&block| mining_cache.cost_for(block))
+                .collect()
+        };
+
+        let mining_cache = MiningCache::new(menu);
+        std::thread::scope(|scope| {
+            for thread in 0..4 {
+                let (mining_cache, blocks, expected) = (&mining_cache, &blocks, &expected);
+                scope.spawn(move || {
+                    for i in 0..1000 {
+                        // every thread goes through the blocks in a different order
+                        let index = (i * (thread + 1)) % blocks.len();
+                        assert_eq!(mining_cache.cost_for(blocks[index]), expected[index]);
+                    }
+                });
+            }
+        });
+        assert_eq!(mining_cache.block_state_id_costs.read().len(), blocks.len());
+    }
+}
@@ -0,0 +1,199 @@
+//! An optional cost layer that keeps the pathfinder away from hostile mobs,
+//! like creepers that are about to explode or skeletons that would shoot us.
+//!
+//! Mobs move, so the positions of the ones near us are copied from the ECS
+//! before every search. Checking the distance to every mob for every node
+//! would be slow, so the penalty for every position near each mob is
+//! calculated once when the layer is made, and looking up a node is a single
+//! hash map lookup.
+
+use azalea_core::position::{BlockPos, Vec3};
+use azalea_registry::EntityKind;
+use bevy_ecs::{component::Component, entity::Entity};
+use rustc_hash::FxHashMap;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{
+    astar::Edge, costs::WALK_ONE_BLOCK_COST, moves::MoveData, rel_block_pos::RelBlockPos,
+    world::CachedWorld,
+};
+
+/// How far away from us (in blocks) mobs can be for them to be avoided. Mobs
+/// that are further away than this will have moved by the time we get there.
+pub const MOB_SNAPSHOT_DISTANCE: f64 = 64.;
+
+/// Some reasonable values for [`PathfinderDefaults::avoid_mobs`]. Creepers
+/// are avoided at almost any cost, and skeletons are avoided when it's easy to
+/// stay out of their range.
+///
+/// [`PathfinderDefaults::avoid_mobs`]: super::settings::PathfinderDefaults::avoid_mobs
+pub const DEFAULT_AVOIDED_MOBS: [MobAvoidance; 4] = [
+    MobAvoidance {
+        kind: EntityKind::Creeper,
+        penalty: WALK_ONE_BLOCK_COST * 100.,
+        radius: 5,
+    },
+    MobAvoidance {
+        kind: EntityKind::Skeleton,
+        penalty: WALK_ONE_BLOCK_COST * 3.,
+        radius: 16,
+    },
+    MobAvoidance {
+        kind: EntityKind::Stray,
+        penalty: WALK_ONE_BLOCK_COST * 3.,
+        radius: 16,
+    },
+    MobAvoidance {
+        kind: EntityKind::Bogged,
+        penalty: WALK_ONE_BLOCK_COST * 3.,
+        radius: 16,
+    },
+];
+
+/// How much the pathfinder should stay away from one kind of mob.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct MobAvoidance {
+    pub kind: EntityKind,
+    /// The cost that's added for standing right where the mob is. This falls
+    /// off the further away from the mob we are, down to nothing at
+    /// [`Self::radius`].
+    pub penalty: f32,
+    pub radius: i32,
+}
+impl MobAvoidance {
+    /// The cost for standing `distance` blocks away from the mob.
+    pub fn cost_at(&self, distance: f32) -> f32 {
+        if distance < self.radius as f32 {
+            self.penalty * (1. - distance / self.radius as f32)
+        } else {
+            0.
+        }
+    }
+}
+
+/// A mob that's close enough to us that it should be avoided.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NearbyMob {
+    pub entity: Entity,
+    pub kind: EntityKind,
+    pub pos: BlockPos,
+}
+
+/// The mobs that the current path was calculated to avoid, and where they
+/// were at the time.
+///
+/// This is used for noticing when a mob moves closer to the path, so it can be
+/// rerouted.
+#[derive(Component, Clone, Debug, Default)]
+pub struct AvoidedMobs(pub Vec<NearbyMob>);
+
+/// A marker component that's added when a mob that we're avoiding moves near
+/// the part of the path that we haven't executed yet.
+#[derive(Component, Clone, Debug)]
+pub struct MobMovedNearPath;
+
+/// The precomputed penalties around the mobs that we're avoiding.
+#[derive(Clone, Debug, Default)]
+pub struct MobLayer {
+    penalties: FxHashMap<BlockPos, f32>,
+}
+
+impl MobLayer {
+    /// Make the penalty grid around the given mobs. Mobs that aren't in
+    /// `avoid` are ignored.
+    pub fn new(avoid: &[MobAvoidance], mobs: &[NearbyMob]) -> Self {
+        let mut penalties = FxHashMap::<BlockPos, f32>::default();
+        for mob in mobs {
+            let Some(avoidance) = avoid.iter().find(|avoidance| avoidance.kind == mob.kind) else {
+                continue;
+            };
+            let radius = avoidance.radius;
+            for x in -radius..=radius {
+                for y in -radius..=radius {
+                    for z in -radius..=radius {
+                        let offset = BlockPos::new(x, y, z);
+                        let cost = avoidance.cost_at((offset.length_squared() as f32).sqrt());
+                        if cost > 0. {
+                            *penalties.entry(mob.pos + offset).or_default() += cost;
+                        }
+                    }
+                }
+            }
+        }
+        Self { penalties }
+    }
+
+    /// Whether there are no mobs near us, so the layer doesn't do anything.
+    pub fn is_empty(&self) -> bool {
+        self.penalties.is_empty()
+    }
+
+    /// The extra cost for standing at this position.
+    pub fn cost(&self, pos: BlockPos) -> f32 {
+        self.penalties.get(&pos).copied().unwrap_or_default()
+    }
+}
+
+/// Get the positions of the mobs in `avoid` that are close enough to `origin`
+/// that they should be avoided.
+pub fn nearby_mobs(
+    avoid: &[MobAvoidance],
+    entities: impl Iterator<Item = (Entity, EntityKind, Vec3)>,
+    origin: Vec3,
+) -> Vec<NearbyMob> {
+    entities
+        .filter(|(_, kind, _)| avoid.iter().any(|avoidance| avoidance.kind == *kind))
+        .filter(|(_, _, position)| {
+            position.distance_squared_to(origin) < MOB_SNAPSHOT_DISTANCE.powi(2)
+        })
+        .map(|(entity, kind, position)| NearbyMob {
+            entity,
+            kind,
+            pos: BlockPos::from(position),
+        })
+        .collect()
+}
+
+/// Whether any of the mobs is in range of one of the `nodes` and closer to
+/// them than it was when the path was calculated.
+///
+/// Mobs that move away from the path don't count, since the path is only
+/// worse if they get closer.
+pub fn has_mob_approached(
+    avoid: &[MobAvoidance],
+    avoided: &AvoidedMobs,
+    mobs: &[NearbyMob],
+    nodes: &[BlockPos],
+) -> bool {
+    let distance_to_path = |pos: BlockPos| {
+        nodes
+            .iter()
+            .map(|node| node.distance_squared_to(pos))
+            .min()
+            .unwrap_or(i32::MAX)
+    };
+    mobs.iter().any(|mob| {
+        let Some(avoidance) = avoid.iter().find(|avoidance| avoidance.kind == mob.kind) else {
+            return false;
+        };
+        let distance = distance_to_path(mob.pos);
+        if distance >= avoidance.radius.pow(2) {
+            return false;
+        }
+        let previous_distance = avoided
+            .0
+            .iter()
+            .find(|avoided_mob| avoided_mob.entity == mob.entity)
+            .map_or(i32::MAX, |avoided_mob| distance_to_path(avoided_mob.pos));
+        distance < previous_distance
+    })
+}
+
+/// Add the cost of being near mobs to the edges that were just generated.
+pub fn apply_mob_costs(world: &CachedWorld, edges: &mut [Edge<RelBlockPos, MoveData>]) {
+    for edge in edges {
+        edge.cost += world.mob_cost(edge.movement.target);
+    }
+}
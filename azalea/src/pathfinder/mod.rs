@@ -3,15 +3,30 @@
 //! Much of this code is based on [Baritone](https://github.com/cabaletta/baritone).
 
 pub mod astar;
+pub mod capture;
 pub mod costs;
 pub mod custom_state;
 pub mod debug;
+pub mod elytra;
+pub mod follow;
 pub mod goals;
 mod goto_event;
+pub mod hazard;
+pub mod humanize;
+pub mod long_distance;
+pub mod metrics;
 pub mod mining;
+pub mod mobs;
 pub mod moves;
+pub mod portals;
+pub mod regions;
 pub mod rel_block_pos;
+pub mod requirements;
+pub mod risk;
+pub mod settings;
 pub mod simulation;
+pub mod smoothing;
+pub mod stuck;
 #[cfg(test)]
 mod tests;
 pub mod world;
@@ -19,10 +34,13 @@ pub mod world;
 use std::{
     cmp,
     collections::VecDeque,
+    f32::consts::FRAC_1_SQRT_2,
+    iter,
     ops::RangeInclusive,
     sync::{
         Arc,
         atomic::{self, AtomicUsize},
+        mpsc,
     },
     thread,
     time::{Duration, Instant},
@@ -30,33 +48,71 @@ use std::{
 
 use astar::{Edge, PathfinderTimeout};
 use azalea_client::{
-    StartSprintEvent, StartWalkEvent,
+    DismountEvent, PhysicsState, SneakEvent, StartSprintEvent, StartWalkEvent,
+    block_update::{QueuedServerBlockUpdates, handle_block_update_event},
+    interact::StartUseItemEvent,
     inventory::{Inventory, InventorySet, SetSelectedHotbarSlotEvent},
     local_player::InstanceHolder,
-    mining::{Mining, MiningSet, StartMiningBlockEvent},
+    mining::{
+        FinishMiningBlockEvent, Mining, MiningQueued, MiningSet, StartMiningBlockEvent,
+        StopMiningBlockEvent, handle_start_mining_block_event, handle_stop_mining_block_event,
+    },
     movement::MoveEventsSet,
 };
-use azalea_core::{position::BlockPos, tick::GameTick};
-use azalea_entity::{LocalEntity, Physics, Position, metadata::Player};
+use azalea_core::{
+    aabb::AABB,
+    position::{BlockPos, ChunkPos},
+    registry_holder::RegistryHolder,
+    resource_location::ResourceLocation,
+    tick::GameTick,
+};
+use azalea_entity::{
+    ActiveEffects, EntityKindComponent, EyeHeight, LocalEntity, LookDirection, Physics, Position,
+    Riding,
+    metadata::{AirSupply, Health, Player},
+};
 use azalea_physics::PhysicsSet;
-use azalea_world::{InstanceContainer, InstanceName};
+use azalea_registry::Item;
+use azalea_world::{InstanceContainer, InstanceName, WorldBorder};
 use bevy_app::{PreUpdate, Update};
 use bevy_ecs::prelude::*;
 use bevy_tasks::{AsyncComputeTaskPool, Task};
 use custom_state::{CustomPathfinderState, CustomPathfinderStateRef};
-use futures_lite::future;
 use goals::BlockPosGoal;
-pub use goto_event::GotoEvent;
-use parking_lot::RwLock;
+pub use goto_event::{GotoEvent, GotoOptions};
+use parking_lot::{Mutex, RwLock};
 use rel_block_pos::RelBlockPos;
 use tokio::sync::broadcast::error::RecvError;
 use tracing::{debug, error, info, trace, warn};
 
 use self::{
+    capture::{
+        CapturePathfinderFailures, CaptureReason, PathfinderCapture, sections_around,
+        write_capture_to_directory, write_failed_path_captures,
+    },
+    costs::{SPRINT_LOOKAHEAD_NODES, TURN_PENALTY},
     debug::debug_render_path_with_particles,
+    elytra::{ElytraFlight, ElytraFlightFailed, FlightPhase, plan_flight, tick_elytra_flight},
+    follow::{FollowEntity, follow_entities},
     goals::Goal,
+    hazard::{EscapingHazard, HazardEscapedEvent, escape_hazards},
+    humanize::{HumanizeSettings, HumanizeState, add_humanize_state, tick_humanize_state},
+    metrics::{PathfinderMetrics, record_path_metrics},
     mining::MiningCache,
-    moves::{ExecuteCtx, IsReachedCtx, SuccessorsFn},
+    mobs::{AvoidedMobs, MobAvoidance, MobLayer, MobMovedNearPath, NearbyMob},
+    moves::{
+        ExecuteCtx, IsReachedCtx, SuccessorsFn,
+        water::{MAX_AIR_SUPPLY, SwimmingState, next_swimming_state},
+    },
+    portals::{
+        DimensionTravel, KnownPortals, WaitingInPortal, continue_goto_in_new_dimension,
+        wait_in_portal,
+    },
+    regions::{RegionAvoidance, RegionLayer},
+    requirements::{Consumable, ConsumableCounts},
+    risk::{AvoidDarknessAtNight, RiskLayer},
+    settings::{MoveSettings, PathfinderDefaults, PathfinderSettings, resolve_settings},
+    stuck::{PathStuckEvent, detect_stuck},
 };
 use crate::{
     BotClientExt, WalkDirection,
@@ -69,7 +125,11 @@ use crate::{
         query::{With, Without},
         system::{Commands, Query, Res},
     },
-    pathfinder::{astar::a_star, moves::PathfinderCtx, world::CachedWorld},
+    pathfinder::{
+        astar::a_star,
+        moves::PathfinderCtx,
+        world::{CachedWorld, PathEffects, PathEquipment, SharedCachedWorld},
+    },
 };
 
 #[derive(Clone, Default)]
@@ -78,17 +138,31 @@ impl Plugin for PathfinderPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<GotoEvent>()
             .add_event::<PathFoundEvent>()
+            .add_event::<PathComputedEvent>()
+            .add_event::<PathExecutionFinishedEvent>()
             .add_event::<StopPathfindingEvent>()
+            .add_event::<HazardEscapedEvent>()
+            .add_event::<PathStuckEvent>()
+            .add_event::<GoalUnreachableEvent>()
+            .init_resource::<PathfinderDefaults>()
+            .init_resource::<KnownPortals>()
             .add_systems(
                 // putting systems in the GameTick schedule makes them run every Minecraft tick
                 // (every 50 milliseconds).
                 GameTick,
                 (
+                    tick_humanize_state,
+                    escape_hazards,
                     timeout_movement,
+                    detect_stuck,
+                    mark_mobs_near_path,
                     check_for_path_obstruction,
                     check_node_reached,
                     tick_execute_path,
+                    tick_elytra_flight,
                     debug_render_path_with_particles,
+                    wait_for_unknown_terrain,
+                    wait_in_portal,
                     recalculate_near_end_of_path,
                     recalculate_if_has_goal_but_no_path,
                 )
@@ -97,19 +171,43 @@ impl Plugin for PathfinderPlugin {
                     .after(azalea_client::movement::send_position)
                     .after(MiningSet),
             )
-            .add_systems(PreUpdate, add_default_pathfinder)
+            .add_systems(PreUpdate, (add_default_pathfinder, add_humanize_state))
             .add_systems(
                 Update,
                 (
+                    follow_entities,
                     goto_listener,
                     handle_tasks,
                     stop_pathfinding_on_instance_change,
+                    continue_goto_in_new_dimension,
                     path_found_listener,
+                    record_path_metrics,
+                    write_failed_path_captures,
                     handle_stop_pathfinding_event,
+                    stop_sneaking_when_path_ends,
+                    send_path_execution_finished_events,
                 )
                     .chain()
                     .before(MoveEventsSet)
                     .before(InventorySet),
+            )
+            .add_systems(
+                Update,
+                // the queued block updates are cleared once they're applied
+                (
+                    mark_block_updates_near_path,
+                    invalidate_cached_world_on_block_updates,
+                )
+                    .before(handle_block_update_event),
+            )
+            .add_observer(invalidate_cached_world_on_mined_block)
+            .add_systems(
+                Update,
+                // this has to run after any queued mining for this tick was started, so we can
+                // abort it before it sends any packets
+                abort_mining_on_stop_pathfinding
+                    .after(handle_start_mining_block_event)
+                    .before(handle_stop_mining_block_event),
             );
     }
 }
@@ -123,11 +221,133 @@ pub struct Pathfinder {
     pub is_calculating: bool,
     pub allow_mining: bool,
     pub retry_on_no_path: bool,
+    pub careful_mode_multiplier: f32,
+    pub avoid_damage: bool,
+    pub allow_placing: bool,
+    pub throwaway_blocks: Vec<Item>,
+    pub max_fall_damage: f32,
+    pub smooth_path: bool,
+    pub avoid_regions: Vec<AABB>,
+    pub region_avoidance: RegionAvoidance,
+    pub confine_to: Option<AABB>,
+    /// See [`GotoOptions::dimension`].
+    pub dimension: Option<ResourceLocation>,
+    /// See [`PathfinderDefaults::low_air_threshold`].
+    pub low_air_threshold: i32,
+    /// See [`MoveSettings`].
+    pub move_settings: MoveSettings,
+    /// See [`PathfinderDefaults::avoid_mobs`].
+    pub avoid_mobs: Vec<MobAvoidance>,
 
     pub min_timeout: Option<PathfinderTimeout>,
     pub max_timeout: Option<PathfinderTimeout>,
 
     pub goto_id: Arc<AtomicUsize>,
+
+    /// Consumables that a path needed more of than we had, so they're left out
+    /// when recalculating the path to the current goal.
+    pub excluded_consumables: Vec<Consumable>,
+
+    /// Whether we stopped because we reached the last goal. While this is true,
+    /// new goals are checked with [`Goal::still_reached`] instead of
+    /// [`Goal::success`].
+    pub is_at_goal: bool,
+
+    /// How the last goto ended, or `None` if it hasn't ended yet.
+    pub outcome: Option<GotoOutcome>,
+
+    /// See [`PathfinderDefaults::max_stalled_partial_paths`].
+    pub max_stalled_partial_paths: u32,
+    /// The best heuristic that the end of a partial path has had for the
+    /// current goal.
+    pub closest_partial_heuristic: Option<f32>,
+    /// How many partial paths in a row didn't get closer to the goal than
+    /// [`Self::closest_partial_heuristic`].
+    pub stalled_partial_paths: u32,
+}
+impl Pathfinder {
+    /// The options that the current goal was started with, for sending
+    /// another [`GotoEvent`] to the same goal.
+    fn goto_options(&self) -> GotoOptions {
+        GotoOptions {
+            successors_fn: self.successors_fn.unwrap_or(moves::default_move),
+            allow_mining: Some(self.allow_mining),
+            retry_on_no_path: Some(self.retry_on_no_path),
+            min_timeout: self.min_timeout,
+            max_timeout: self.max_timeout,
+            careful_mode_multiplier: Some(self.careful_mode_multiplier),
+            avoid_damage: Some(self.avoid_damage),
+            allow_placing: Some(self.allow_placing),
+            throwaway_blocks: Some(self.throwaway_blocks.clone()),
+            max_fall_damage: Some(self.max_fall_damage),
+            smooth_path: Some(self.smooth_path),
+            avoid_regions: Some(self.avoid_regions.clone()),
+            region_avoidance: Some(self.region_avoidance),
+            confine_to: self.confine_to,
+            dimension: self.dimension.clone(),
+        }
+    }
+
+    /// The [`RegionLayer`] for the regions that the current goal was started
+    /// with.
+    fn region_layer(&self) -> Option<RegionLayer> {
+        let regions = RegionLayer::new(
+            self.avoid_regions.clone(),
+            self.region_avoidance,
+            self.confine_to,
+        );
+        (!regions.is_empty()).then_some(regions)
+    }
+}
+
+/// How a goto ended. This is returned by [`PathfinderClientExt::goto`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum GotoOutcome {
+    /// We reached the goal.
+    Reached,
+    /// We couldn't find a path to the goal and
+    /// [`GotoOptions::retry_on_no_path`] was false.
+    NoPath,
+    /// The pathfinder was stopped with a [`StopPathfindingEvent`] before we
+    /// reached the goal.
+    Cancelled,
+    /// The goal is somewhere that we can't get to, so we went as close as we
+    /// could instead. A [`GoalUnreachableEvent`] is also sent when this
+    /// happens.
+    Unreachable {
+        closest: BlockPos,
+        reason: UnreachableReason,
+    },
+}
+
+/// Why we gave up on getting to a goal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UnreachableReason {
+    /// The last [`PathfinderDefaults::max_stalled_partial_paths`] partial
+    /// paths didn't get us any closer to the goal, which usually means that
+    /// the chunks in the way aren't going to load.
+    NoProgress,
+    /// The closest position that we could get to is at the world border.
+    WorldBorder,
+    /// We stopped getting any closer to the next node in the path too many
+    /// times in a row. A [`PathStuckEvent`] is sent every time this happens.
+    Stuck,
+    /// The goal is in another dimension, and we don't know about a nether
+    /// portal that goes there or the one that we went into didn't take us
+    /// anywhere.
+    NoPortal,
+}
+
+/// An event that's sent when we give up on a goal because it can't be reached.
+///
+/// We still walk to `closest` after this is sent, but the goal is removed so
+/// no more paths get calculated for it.
+#[derive(Event, Clone, Debug)]
+pub struct GoalUnreachableEvent {
+    pub entity: Entity,
+    pub closest: BlockPos,
+    pub reason: UnreachableReason,
 }
 
 /// A component that's present on clients that are actively following a
@@ -139,6 +359,26 @@ pub struct ExecutingPath {
     pub last_reached_node: BlockPos,
     pub last_node_reached_at: Instant,
     pub is_path_partial: bool,
+    /// How many more ticks until the path is allowed to interact with a block
+    /// again, so we don't open a door and then immediately close it. See
+    /// [`ExecuteCtx::interact`].
+    pub interact_cooldown: u32,
+    /// How many more ticks we wait for sand or gravel to fall into the gap
+    /// left by a block that we mined. See [`ExecuteCtx::mine_while_at_start`].
+    pub falling_block_wait: u32,
+}
+impl ExecutingPath {
+    /// Where we'll be when we're done walking the path and the queued path.
+    ///
+    /// This is `None` if the queued path is empty, since that means that we
+    /// stop after the current move.
+    pub fn end(&self) -> Option<BlockPos> {
+        match &self.queued_path {
+            Some(queued_path) => queued_path.back(),
+            None => self.path.back(),
+        }
+        .map(|edge| edge.movement.target)
+    }
 }
 
 #[derive(Event, Clone, Debug)]
@@ -150,6 +390,103 @@ pub struct PathFoundEvent {
     pub is_partial: bool,
     pub successors_fn: SuccessorsFn,
     pub allow_mining: bool,
+    /// How many of each consumable item the path uses. The path won't be
+    /// executed if the bot doesn't have enough of them.
+    pub requirements: ConsumableCounts,
+    /// A snapshot of the search, only present if the bot has the
+    /// [`CapturePathfinderFailures`] component.
+    pub capture: Option<Arc<PathfinderCapture>>,
+    /// How many nodes A* expanded while looking for the path.
+    pub nodes_expanded: usize,
+    /// How long the A* search took.
+    pub compute_time: Duration,
+    /// More details about the search.
+    pub metrics: PathfinderMetrics,
+    /// Whether this path continues the previous partial path for the same
+    /// goto, so it starts where that one ends.
+    pub is_continuation: bool,
+    /// Whether we're still searching for the rest of the path, in which case
+    /// this path is partial and the rest is sent in another event.
+    pub is_search_continuing: bool,
+    /// The chunk that the path would've gone into next if it was loaded.
+    ///
+    /// Paths are cut off where they go into terrain that isn't loaded, and
+    /// then we wait for this chunk to load before recalculating. See
+    /// [`moves::unknown`].
+    pub unknown_chunk: Option<ChunkPos>,
+}
+
+/// A node in a path from a [`PathComputedEvent`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PathNode {
+    pub pos: BlockPos,
+    /// The cost of the movement that gets us to this node from the previous
+    /// one.
+    pub cost: f32,
+}
+
+/// Sent every time a path is calculated, including partial paths and the
+/// patches that are made when the path gets obstructed.
+///
+/// This is only for inspecting the path, like for rendering it or debugging.
+/// Changing it won't change what the bot does.
+///
+/// This is a Bevy event, so it has to be read from a system with an
+/// `EventReader`. It isn't sent to the [`Client`](crate::Client) event stream.
+#[derive(Event, Clone, Debug)]
+pub struct PathComputedEvent {
+    pub entity: Entity,
+    /// Where the path starts. This isn't included in `path`.
+    pub start: BlockPos,
+    pub path: Vec<PathNode>,
+    /// The sum of the costs of every node in the path.
+    pub total_cost: f32,
+    pub is_partial: bool,
+    /// How many nodes A* expanded while looking for the path.
+    pub nodes_expanded: usize,
+    pub compute_time: Duration,
+    /// More details about the search, for figuring out why it took as long as
+    /// it did.
+    pub metrics: PathfinderMetrics,
+}
+impl PathComputedEvent {
+    /// Returns `None` if the calculation was interrupted before it found a
+    /// path.
+    pub fn from_path_found(event: &PathFoundEvent) -> Option<Self> {
+        let path = event
+            .path
+            .as_ref()?
+            .iter()
+            .map(|edge| PathNode {
+                pos: edge.movement.target,
+                cost: edge.cost,
+            })
+            .collect::<Vec<_>>();
+        Some(Self {
+            entity: event.entity,
+            start: event.start,
+            total_cost: path.iter().map(|node| node.cost).sum(),
+            path,
+            is_partial: event.is_partial,
+            nodes_expanded: event.nodes_expanded,
+            compute_time: event.compute_time,
+            metrics: event.metrics,
+        })
+    }
+}
+
+/// Sent when we stop executing a path because the goto is over, whether or
+/// not we reached the goal.
+///
+/// [`Pathfinder::outcome`] has more details about why it ended. Like
+/// [`PathComputedEvent`], this is only a Bevy event.
+#[derive(Event, Clone, Debug)]
+pub struct PathExecutionFinishedEvent {
+    pub entity: Entity,
+    /// Whether we reached the goal.
+    pub success: bool,
+    /// The block that we ended up in.
+    pub position: BlockPos,
 }
 
 #[allow(clippy::type_complexity)]
@@ -163,9 +500,10 @@ pub fn add_default_pathfinder(
 }
 
 pub trait PathfinderClientExt {
-    fn goto(&self, goal: impl Goal + 'static) -> impl Future<Output = ()>;
+    fn goto(&self, goal: impl Goal + 'static) -> impl Future<Output = GotoOutcome>;
     fn start_goto(&self, goal: impl Goal + 'static);
     fn start_goto_without_mining(&self, goal: impl Goal + 'static);
+    fn follow(&self, entity: Entity, distance: f32);
     fn stop_pathfinding(&self);
     fn force_stop_pathfinding(&self);
     fn wait_until_goto_target_reached(&self) -> impl Future<Output = ()>;
@@ -176,6 +514,8 @@ impl PathfinderClientExt for azalea_client::Client {
     /// Pathfind to the given goal and wait until either the target is reached
     /// or the pathfinding is canceled.
     ///
+    /// The returned [`GotoOutcome`] says which one of those happened.
+    ///
     /// You can use [`Self::start_goto`] instead if you don't want to wait.
     ///
     /// ```
@@ -185,9 +525,13 @@ impl PathfinderClientExt for azalea_client::Client {
     /// bot.goto(BlockPosGoal(BlockPos::new(0, 70, 0))).await;
     /// # }
     /// ```
-    async fn goto(&self, goal: impl Goal + 'static) {
+    async fn goto(&self, goal: impl Goal + 'static) -> GotoOutcome {
         self.start_goto(goal);
         self.wait_until_goto_target_reached().await;
+        self.map_get_component::<Pathfinder, _>(|p| p.outcome)
+            .flatten()
+            // the pathfinder is gone if we got disconnected
+            .unwrap_or(GotoOutcome::Cancelled)
     }
 
     /// Start pathfinding to a given goal.
@@ -208,9 +552,30 @@ impl PathfinderClientExt for azalea_client::Client {
     /// Same as [`start_goto`](Self::start_goto). but the bot won't break any
     /// blocks while executing the path.
     fn start_goto_without_mining(&self, goal: impl Goal + 'static) {
+        self.ecs.lock().send_event(
+            GotoEvent::new(self.entity, goal)
+                .with_options(GotoOptions::default().allow_mining(false)),
+        );
+    }
+
+    /// Keep following the given entity until the pathfinder is stopped or
+    /// given a different goal.
+    ///
+    /// We stop once we're within `distance` blocks of the entity, and start
+    /// moving again when it walks away. See [`FollowEntity`] for more details.
+    ///
+    /// ```
+    /// # use azalea::prelude::*;
+    /// # use azalea::ecs::entity::Entity;
+    /// # fn example(bot: &Client, owner: Entity) {
+    /// bot.follow(owner, 3.);
+    /// # }
+    /// ```
+    fn follow(&self, entity: Entity, distance: f32) {
         self.ecs
             .lock()
-            .send_event(GotoEvent::new(self.entity, goal).with_allow_mining(false));
+            .entity_mut(self.entity)
+            .insert(FollowEntity::new(entity, distance));
     }
 
     /// Stop calculating a path, and stop moving once the current movement is
@@ -259,8 +624,17 @@ impl PathfinderClientExt for azalea_client::Client {
     }
 }
 
+/// A component that's present while a path is being calculated in the
+/// background.
 #[derive(Component)]
-pub struct ComputePath(Task<Option<PathFoundEvent>>);
+pub struct ComputePath {
+    /// This isn't used, but dropping it cancels the calculation.
+    _task: Task<()>,
+    /// The paths that were found, in the order that they should be used in.
+    /// Every path except for the last one has
+    /// [`PathFoundEvent::is_search_continuing`] set.
+    paths: Mutex<mpsc::Receiver<PathFoundEvent>>,
+}
 
 #[allow(clippy::type_complexity)]
 pub fn goto_listener(
@@ -272,36 +646,142 @@ pub fn goto_listener(
         &Position,
         &InstanceName,
         &Inventory,
+        Option<&ActiveEffects>,
+        Option<&Health>,
         Option<&CustomPathfinderState>,
+        Option<&CapturePathfinderFailures>,
+        Option<&HumanizeSettings>,
+        Option<&AvoidDarknessAtNight>,
+        Option<&PathfinderSettings>,
+        Option<&Riding>,
+        Option<&AirSupply>,
+        Option<&SharedCachedWorld>,
     )>,
+    entities: Query<(Entity, &EntityKindComponent, &Position, &InstanceName)>,
+    travels: Query<&DimensionTravel>,
+    mut flights: Query<(&Physics, Option<&mut ElytraFlight>, Has<ElytraFlightFailed>)>,
     instance_container: Res<InstanceContainer>,
+    defaults: Res<PathfinderDefaults>,
+    mut known_portals: ResMut<KnownPortals>,
+    mut goal_unreachable_events: EventWriter<GoalUnreachableEvent>,
 ) {
     let thread_pool = AsyncComputeTaskPool::get();
 
     for event in events.read() {
-        let Ok((mut pathfinder, executing_path, position, instance_name, inventory, custom_state)) =
-            query.get_mut(event.entity)
+        let Ok((
+            mut pathfinder,
+            executing_path,
+            position,
+            instance_name,
+            inventory,
+            active_effects,
+            health,
+            custom_state,
+            capture_failures,
+            humanize,
+            avoid_darkness,
+            client_settings,
+            riding,
+            air_supply,
+            shared_world,
+        )) = query.get_mut(event.entity)
         else {
             warn!("got goto event for an entity that can't pathfind");
             continue;
         };
 
-        if event.goal.success(BlockPos::from(position)) {
+        // the goal might be in another dimension, which we have to go through a portal
+        // to get to first
+        let target_dimension = event
+            .options
+            .dimension
+            .clone()
+            .filter(|dimension| *dimension != **instance_name);
+
+        let is_at_goal = if target_dimension.is_some() {
+            false
+        } else if pathfinder.is_at_goal && executing_path.is_none() {
+            // if we're standing still at the last goal, give moving goals some leeway so we
+            // don't stutter-step around their boundary
+            event.goal.still_reached(BlockPos::from(position))
+        } else {
+            event.goal.success(BlockPos::from(position))
+        };
+        if is_at_goal {
             // we're already at the goal, nothing to do
             pathfinder.goal = None;
             pathfinder.successors_fn = None;
             pathfinder.is_calculating = false;
+            pathfinder.is_at_goal = true;
+            pathfinder.outcome = Some(GotoOutcome::Reached);
             debug!("already at goal, not pathfinding");
             continue;
         }
+        pathfinder.is_at_goal = false;
+        pathfinder.outcome = None;
+        // the new path decides whether we still have to wait for a chunk
+        commands.entity(event.entity).remove::<WaitingForChunk>();
+
+        let is_same_goal = pathfinder
+            .goal
+            .as_ref()
+            .is_some_and(|goal| Arc::ptr_eq(goal, &event.goal));
+        // items that we were missing for the last goal might be enough for this one
+        if !is_same_goal {
+            pathfinder.excluded_consumables.clear();
+            pathfinder.closest_partial_heuristic = None;
+            pathfinder.stalled_partial_paths = 0;
+            commands.entity(event.entity).remove::<ElytraFlightFailed>();
+        }
+        // recalculating the path to the same goal can reuse the world that the last
+        // calculation cached
+        let shared_world = match shared_world {
+            Some(shared_world) if is_same_goal => shared_world.clone(),
+            _ => {
+                let shared_world = SharedCachedWorld::default();
+                commands.entity(event.entity).insert(shared_world.clone());
+                shared_world
+            }
+        };
+
+        let settings = resolve_settings(event, client_settings, &defaults);
 
         // we store the goal so it can be recalculated later if necessary
         pathfinder.goal = Some(event.goal.clone());
-        pathfinder.successors_fn = Some(event.successors_fn);
+        pathfinder.successors_fn = Some(event.options.successors_fn);
         pathfinder.is_calculating = true;
-        pathfinder.allow_mining = event.allow_mining;
-        pathfinder.min_timeout = Some(event.min_timeout);
-        pathfinder.max_timeout = Some(event.max_timeout);
+        pathfinder.allow_mining = settings.allow_mining;
+        pathfinder.retry_on_no_path = settings.retry_on_no_path;
+        pathfinder.careful_mode_multiplier = settings.careful_mode_multiplier;
+        pathfinder.avoid_damage = settings.avoid_damage;
+        pathfinder.allow_placing = settings.allow_placing;
+        pathfinder.throwaway_blocks = settings.throwaway_blocks.clone();
+        pathfinder.max_fall_damage = settings.max_fall_damage;
+        pathfinder.smooth_path = settings.smooth_path;
+        pathfinder.avoid_regions = settings.avoid_regions.clone();
+        pathfinder.region_avoidance = settings.region_avoidance;
+        pathfinder.confine_to = settings.confine_to;
+        pathfinder.dimension = event.options.dimension.clone();
+        pathfinder.low_air_threshold = settings.low_air_threshold;
+        pathfinder.move_settings = settings.move_settings();
+        pathfinder.avoid_mobs = settings.avoid_mobs.clone();
+        pathfinder.min_timeout = Some(settings.min_timeout);
+        pathfinder.max_timeout = Some(settings.max_timeout);
+        pathfinder.max_stalled_partial_paths = settings.max_stalled_partial_paths;
+
+        let Ok((physics, flight, has_failed_flight)) = flights.get_mut(event.entity) else {
+            warn!("got goto event for an entity that doesn't have physics");
+            continue;
+        };
+        if let Some(mut flight) = flight {
+            // the flight resends the goto once we land, and a different goal means that
+            // we should land early
+            if !is_same_goal {
+                flight.phase = FlightPhase::Landing;
+            }
+            pathfinder.is_calculating = false;
+            continue;
+        }
 
         let start = if let Some(executing_path) = executing_path
             && let Some(final_node) = executing_path.path.back()
@@ -327,48 +807,184 @@ pub fn goto_listener(
             );
         }
 
-        let successors_fn: moves::SuccessorsFn = event.successors_fn;
+        let successors_fn: moves::SuccessorsFn = event.options.successors_fn;
+
+        // we have to get off before we can start walking from where we are
+        let is_riding = riding.is_some() && start == BlockPos::from(position);
+        // we can only know how much air we'll have if we're starting from where we are
+        let air_supply = air_supply
+            .filter(|_| start == BlockPos::from(position))
+            .map_or(MAX_AIR_SUPPLY, |air_supply| **air_supply);
 
         let world_lock = instance_container
             .get(instance_name)
             .expect("Entity tried to pathfind but the entity isn't in a valid world");
 
-        let goal = event.goal.clone();
+        let goal = if let Some(dimension) = target_dimension {
+            let travel = travels
+                .get(event.entity)
+                .ok()
+                .filter(|travel| is_same_goal && travel.dimension == dimension);
+            let portal = match travel {
+                // keep going to the same portal so we don't switch between two that are
+                // about as good
+                Some(travel) => Some(travel.portal),
+                None => {
+                    known_portals.scan(instance_name, &world_lock.read(), BlockPos::from(position));
+                    known_portals.best_portal(
+                        instance_name,
+                        BlockPos::from(position),
+                        &dimension,
+                        event.goal.as_ref(),
+                    )
+                }
+            };
+            let Some(portal) = portal else {
+                warn!("don't know about any portals that go to {dimension}, giving up");
+                let closest = BlockPos::from(position);
+                let reason = UnreachableReason::NoPortal;
+                pathfinder.goal = None;
+                pathfinder.successors_fn = None;
+                pathfinder.is_calculating = false;
+                pathfinder.outcome = Some(GotoOutcome::Unreachable { closest, reason });
+                commands.entity(event.entity).remove::<DimensionTravel>();
+                goal_unreachable_events.write(GoalUnreachableEvent {
+                    entity: event.entity,
+                    closest,
+                    reason,
+                });
+                continue;
+            };
+            debug!("going to the portal at {portal} to get to {dimension}");
+            commands
+                .entity(event.entity)
+                .insert(DimensionTravel { dimension, portal });
+            Arc::new(BlockPosGoal(portal)) as Arc<dyn Goal>
+        } else {
+            commands.entity(event.entity).remove::<DimensionTravel>();
+            event.goal.clone()
+        };
+
+        // faraway goals are faster to fly to if we have an elytra
+        if settings.allow_elytra
+            && !has_failed_flight
+            && riding.is_none()
+            && !physics.is_in_water()
+            && let Some(flight) = plan_flight(
+                &world_lock.read(),
+                **position,
+                goal.as_ref(),
+                inventory,
+                settings.elytra_threshold,
+            )
+        {
+            info!("flying from {} to {}", flight.takeoff, flight.landing);
+            commands
+                .entity(event.entity)
+                .remove::<(ExecutingPath, ComputePath)>()
+                .insert(flight);
+            pathfinder.is_calculating = false;
+            continue;
+        }
+
         let entity = event.entity;
 
         let goto_id_atomic = pathfinder.goto_id.clone();
 
-        let allow_mining = event.allow_mining;
-        let retry_on_no_path = event.retry_on_no_path;
-        let mining_cache = MiningCache::new(if allow_mining {
-            Some(inventory.inventory_menu.clone())
-        } else {
-            None
-        });
+        let allow_mining = settings.allow_mining;
+        let retry_on_no_path = settings.retry_on_no_path;
+        let careful_mode_multiplier = settings.careful_mode_multiplier;
+        let avoid_damage = settings.avoid_damage;
+        let low_air_threshold = settings.low_air_threshold;
+        let move_settings = settings.move_settings();
+        let smooth_path = settings.smooth_path;
+        let long_distance_threshold = settings.long_distance_threshold;
+        let regions = pathfinder.region_layer();
+        let mining_cache = create_mining_cache(
+            allow_mining,
+            move_settings,
+            inventory,
+            active_effects,
+            humanize,
+        );
+
+        let equipment = path_equipment(
+            &pathfinder,
+            inventory,
+            active_effects,
+            health,
+            &world_lock.read().registries,
+        );
+        let capture = capture_failures.is_some();
 
         let custom_state = custom_state.cloned().unwrap_or_default();
 
-        let min_timeout = event.min_timeout;
-        let max_timeout = event.max_timeout;
+        let risk = avoid_darkness
+            .filter(|_| world_lock.read().day_time.is_some_and(risk::is_night))
+            .map(|&settings| {
+                let hostiles = entities
+                    .iter()
+                    .filter(|(_, kind, _, entity_instance_name)| {
+                        risk::is_hostile(***kind) && *entity_instance_name == instance_name
+                    })
+                    .map(|(_, _, position, _)| BlockPos::from(position))
+                    .collect();
+                RiskLayer::new(settings, hostiles)
+            });
 
+        let mobs = if settings.avoid_mobs.is_empty() {
+            commands.entity(event.entity).remove::<AvoidedMobs>();
+            None
+        } else {
+            let nearby_mobs =
+                nearby_avoided_mobs(&settings.avoid_mobs, &entities, instance_name, position);
+            let mobs = MobLayer::new(&settings.avoid_mobs, &nearby_mobs);
+            commands
+                .entity(event.entity)
+                .insert(AvoidedMobs(nearby_mobs));
+            Some(mobs)
+        };
+
+        let min_timeout = settings.min_timeout;
+        let max_timeout = settings.max_timeout;
+
+        let (paths_sender, paths) = mpsc::channel();
         let task = thread_pool.spawn(async move {
-            calculate_path(CalculatePathOpts {
+            let opts = CalculatePathOpts {
                 entity,
                 start,
                 goal,
                 successors_fn,
                 world_lock,
+                shared_world: Some(shared_world),
                 goto_id_atomic,
                 allow_mining,
                 mining_cache,
                 retry_on_no_path,
+                careful_mode_multiplier,
+                avoid_damage,
+                equipment,
+                capture,
+                risk,
+                regions,
+                mobs,
+                is_riding,
+                air_supply,
+                low_air_threshold,
+                move_settings,
+                smooth_path,
+                long_distance_threshold,
                 custom_state,
                 min_timeout,
                 max_timeout,
-            })
+            };
+            calculate_path_in_parts(opts, &paths_sender);
         });
 
-        commands.entity(event.entity).insert(ComputePath(task));
+        commands.entity(event.entity).insert(ComputePath {
+            _task: task,
+            paths: Mutex::new(paths),
+        });
     }
 }
 
@@ -378,18 +994,188 @@ pub struct CalculatePathOpts {
     pub goal: Arc<dyn Goal>,
     pub successors_fn: SuccessorsFn,
     pub world_lock: Arc<RwLock<azalea_world::Instance>>,
+    /// The world that was cached by earlier calculations for the same goto, so
+    /// it can be reused instead of being cached again. A new [`CachedWorld`]
+    /// is made if this is `None`.
+    pub shared_world: Option<SharedCachedWorld>,
     pub goto_id_atomic: Arc<AtomicUsize>,
     pub allow_mining: bool,
     pub mining_cache: MiningCache,
-    /// See [`GotoEvent::retry_on_no_path`].
+    /// See [`GotoOptions::retry_on_no_path`].
     pub retry_on_no_path: bool,
-
-    /// See [`GotoEvent::min_timeout`].
+    /// See [`GotoOptions::careful_mode_multiplier`].
+    pub careful_mode_multiplier: f32,
+    /// See [`GotoOptions::avoid_damage`].
+    pub avoid_damage: bool,
+    /// What the bot is wearing, for checking whether it can stand on blocks
+    /// like powder snow.
+    pub equipment: PathEquipment,
+    /// Whether a [`PathfinderCapture`] of the search should be included in the
+    /// [`PathFoundEvent`].
+    pub capture: bool,
+    /// Extra costs for dark or dangerous positions, from
+    /// [`AvoidDarknessAtNight`]. This is only set at night.
+    pub risk: Option<RiskLayer>,
+    /// The regions that we have to stay out of or inside of, from
+    /// [`GotoOptions::avoid_regions`] and [`GotoOptions::confine_to`].
+    pub regions: Option<RegionLayer>,
+    /// Extra costs for positions near hostile mobs, from
+    /// [`PathfinderDefaults::avoid_mobs`].
+    pub mobs: Option<MobLayer>,
+    /// Whether we're riding something at the start, in which case a move to
+    /// get off is added to the start of the path.
+    pub is_riding: bool,
+    /// How much air we have at the start, in ticks.
+    pub air_supply: i32,
+    /// See [`PathfinderDefaults::low_air_threshold`].
+    pub low_air_threshold: i32,
+    /// See [`MoveSettings`].
+    pub move_settings: MoveSettings,
+    /// See [`GotoOptions::smooth_path`].
+    pub smooth_path: bool,
+    /// See [`PathfinderDefaults::long_distance_threshold`].
+    pub long_distance_threshold: f32,
+
+    /// See [`GotoOptions::min_timeout`].
     pub min_timeout: PathfinderTimeout,
     pub max_timeout: PathfinderTimeout,
 
     pub custom_state: CustomPathfinderState,
 }
+impl CalculatePathOpts {
+    /// Options for finding a path from `start` to `goal` in the given world.
+    ///
+    /// Everything else is the same as in the default [`PathfinderDefaults`],
+    /// except that mining is off since there's no inventory to mine with, and
+    /// can be changed with the other methods.
+    pub fn new(
+        entity: Entity,
+        start: BlockPos,
+        goal: Arc<dyn Goal>,
+        world_lock: Arc<RwLock<azalea_world::Instance>>,
+    ) -> Self {
+        let defaults = PathfinderDefaults::default();
+        Self {
+            entity,
+            start,
+            goal,
+            successors_fn: moves::default_move,
+            world_lock,
+            shared_world: None,
+            goto_id_atomic: Arc::new(AtomicUsize::new(0)),
+            allow_mining: false,
+            mining_cache: MiningCache::new(None),
+            retry_on_no_path: defaults.retry_on_no_path,
+            careful_mode_multiplier: defaults.careful_mode_multiplier,
+            avoid_damage: defaults.avoid_damage,
+            equipment: PathEquipment::default(),
+            capture: false,
+            risk: None,
+            regions: None,
+            mobs: None,
+            is_riding: false,
+            air_supply: MAX_AIR_SUPPLY,
+            low_air_threshold: defaults.low_air_threshold,
+            move_settings: defaults.move_settings(),
+            smooth_path: defaults.smooth_path,
+            long_distance_threshold: defaults.long_distance_threshold,
+            min_timeout: defaults.min_timeout,
+            max_timeout: defaults.max_timeout,
+            custom_state: CustomPathfinderState::default(),
+        }
+    }
+
+    pub fn successors_fn(mut self, successors_fn: SuccessorsFn) -> Self {
+        self.successors_fn = successors_fn;
+        self
+    }
+    pub fn shared_world(mut self, shared_world: SharedCachedWorld) -> Self {
+        self.shared_world = Some(shared_world);
+        self
+    }
+    pub fn goto_id_atomic(mut self, goto_id_atomic: Arc<AtomicUsize>) -> Self {
+        self.goto_id_atomic = goto_id_atomic;
+        self
+    }
+    /// Allow breaking blocks, with the costs from the given [`MiningCache`].
+    pub fn mining(mut self, mining_cache: MiningCache) -> Self {
+        self.allow_mining = true;
+        self.mining_cache = mining_cache;
+        self
+    }
+    pub fn retry_on_no_path(mut self, retry_on_no_path: bool) -> Self {
+        self.retry_on_no_path = retry_on_no_path;
+        self
+    }
+    pub fn careful_mode_multiplier(mut self, careful_mode_multiplier: f32) -> Self {
+        self.careful_mode_multiplier = careful_mode_multiplier;
+        self
+    }
+    pub fn avoid_damage(mut self, avoid_damage: bool) -> Self {
+        self.avoid_damage = avoid_damage;
+        self
+    }
+    pub fn equipment(mut self, equipment: PathEquipment) -> Self {
+        self.equipment = equipment;
+        self
+    }
+    pub fn capture(mut self, capture: bool) -> Self {
+        self.capture = capture;
+        self
+    }
+    pub fn risk(mut self, risk: RiskLayer) -> Self {
+        self.risk = Some(risk);
+        self
+    }
+    pub fn regions(mut self, regions: RegionLayer) -> Self {
+        self.regions = Some(regions);
+        self
+    }
+    pub fn mobs(mut self, mobs: MobLayer) -> Self {
+        self.mobs = Some(mobs);
+        self
+    }
+    pub fn is_riding(mut self, is_riding: bool) -> Self {
+        self.is_riding = is_riding;
+        self
+    }
+    pub fn air_supply(mut self, air_supply: i32) -> Self {
+        self.air_supply = air_supply;
+        self
+    }
+    pub fn low_air_threshold(mut self, low_air_threshold: i32) -> Self {
+        self.low_air_threshold = low_air_threshold;
+        self
+    }
+    pub fn move_settings(mut self, move_settings: MoveSettings) -> Self {
+        self.move_settings = move_settings;
+        self
+    }
+    pub fn smooth_path(mut self, smooth_path: bool) -> Self {
+        self.smooth_path = smooth_path;
+        self
+    }
+    pub fn long_distance_threshold(mut self, long_distance_threshold: f32) -> Self {
+        self.long_distance_threshold = long_distance_threshold;
+        self
+    }
+    pub fn min_timeout(mut self, min_timeout: PathfinderTimeout) -> Self {
+        self.min_timeout = min_timeout;
+        self
+    }
+    pub fn max_timeout(mut self, max_timeout: PathfinderTimeout) -> Self {
+        self.max_timeout = max_timeout;
+        self
+    }
+    /// Set both [`Self::min_timeout`] and [`Self::max_timeout`].
+    pub fn timeout(self, timeout: PathfinderTimeout) -> Self {
+        self.min_timeout(timeout).max_timeout(timeout)
+    }
+    pub fn custom_state(mut self, custom_state: CustomPathfinderState) -> Self {
+        self.custom_state = custom_state;
+        self
+    }
+}
 
 /// Calculate the [`PathFoundEvent`] for the given pathfinder options.
 ///
@@ -400,37 +1186,184 @@ pub struct CalculatePathOpts {
 /// calling this function. `None` will be returned if the pathfinding was
 /// interrupted by another path calculation.
 pub fn calculate_path(opts: CalculatePathOpts) -> Option<PathFoundEvent> {
-    debug!("start: {:?}", opts.start);
+    let goto_id = opts.goto_id_atomic.fetch_add(1, atomic::Ordering::SeqCst) + 1;
+    let start_swimming = SwimmingState {
+        estimated_air: opts.air_supply,
+        ..Default::default()
+    };
+    let (path_found_event, _) = calculate_path_for_goto(&opts, goto_id, start_swimming)?;
+    if path_found_event.is_partial {
+        // wait a bit so it's not a busy loop
+        thread::sleep(Duration::from_millis(100));
+    }
+    Some(path_found_event)
+}
 
+/// Like [`calculate_path`], but partial paths are sent as soon as they're found
+/// so we can start walking them, and then we keep searching from the end of
+/// them for the rest of the path.
+///
+/// Every path is sent to `paths`, and all of them except for the last one have
+/// [`PathFoundEvent::is_search_continuing`] set. We stop continuing once a
+/// partial path doesn't get any closer to the goal, and nothing more is sent
+/// if another path calculation is started.
+///
+/// Each part is searched with the consumables and air that the parts before it
+/// leave us with.
+pub fn calculate_path_in_parts(mut opts: CalculatePathOpts, paths: &mpsc::Sender<PathFoundEvent>) {
     let goto_id = opts.goto_id_atomic.fetch_add(1, atomic::Ordering::SeqCst) + 1;
 
+    let mut closest_heuristic = f32::INFINITY;
+    let mut is_continuation = false;
+    let mut start_swimming = SwimmingState {
+        estimated_air: opts.air_supply,
+        ..Default::default()
+    };
+    while let Some((mut path_found_event, end_swimming)) =
+        calculate_path_for_goto(&opts, goto_id, start_swimming)
+    {
+        path_found_event.is_continuation = is_continuation;
+
+        // only keep going if the partial path got us closer to the goal, and not if
+        // we have to wait for a chunk to load first
+        let continue_from = path_found_event
+            .path
+            .as_ref()
+            .and_then(|path| path.back())
+            .filter(|_| path_found_event.is_partial && path_found_event.unknown_chunk.is_none())
+            .map(|edge| edge.movement.target)
+            .map(|end| (end, opts.goal.heuristic(end)))
+            .filter(|&(_, heuristic)| heuristic < closest_heuristic);
+        let Some((end, end_heuristic)) = continue_from else {
+            if path_found_event.is_partial {
+                // wait a bit so it's not a busy loop
+                thread::sleep(Duration::from_millis(100));
+            }
+            let _ = paths.send(path_found_event);
+            return;
+        };
+        closest_heuristic = end_heuristic;
+
+        // the rest of the path can't use the items that this part uses up
+        let requirements = path_found_event.requirements;
+        path_found_event.is_search_continuing = true;
+        if paths.send(path_found_event).is_err() {
+            // the calculation was cancelled
+            return;
+        }
+
+        debug!("continuing the search from the end of the partial path at {end}");
+        opts.start = end;
+        opts.equipment.consumables = opts.equipment.consumables.after_using(&requirements);
+        // we're only riding at the start of the first part
+        opts.is_riding = false;
+        start_swimming = end_swimming;
+        is_continuation = true;
+    }
+}
+
+/// Find a path, and also return the [`SwimmingState`] that we expect to have
+/// at the end of it.
+fn calculate_path_for_goto(
+    opts: &CalculatePathOpts,
+    goto_id: usize,
+    start_swimming: SwimmingState,
+) -> Option<(PathFoundEvent, SwimmingState)> {
+    debug!("start: {:?}", opts.start);
+
     let origin = opts.start;
-    let cached_world = CachedWorld::new(opts.world_lock, origin);
-    let successors = |pos: RelBlockPos| {
-        call_successors_fn(
-            &cached_world,
+    let mut shared_world = opts
+        .shared_world
+        .as_ref()
+        .and_then(|shared_world| shared_world.try_lock(&opts.world_lock, origin));
+    let new_world;
+    let cached_world = if let Some(cached_world) = &mut shared_world {
+        cached_world.reuse_for_search(
+            origin,
+            opts.equipment,
+            opts.avoid_damage,
+            opts.risk.clone(),
+            opts.regions.clone(),
+            opts.mobs.clone(),
+        );
+        &**cached_world
+    } else {
+        new_world = CachedWorld::new(opts.world_lock.clone(), origin)
+            .with_equipment(opts.equipment)
+            .with_risk(opts.risk.clone())
+            .with_regions(opts.regions.clone())
+            .with_mobs(opts.mobs.clone())
+            .with_avoid_damage(opts.avoid_damage);
+        &new_world
+    };
+    let successors = |pos: RelBlockPos, swimming: SwimmingState, edges: &mut Vec<_>| {
+        fill_successors(
+            cached_world,
             &opts.mining_cache,
+            opts.careful_mode_multiplier,
+            opts.low_air_threshold,
+            opts.move_settings,
             &opts.custom_state.0.read(),
             opts.successors_fn,
+            swimming,
             pos,
+            edges,
         )
     };
+    let next_state = |swimming: SwimmingState, pos: RelBlockPos| {
+        next_swimming_state(cached_world, swimming, pos)
+    };
 
     let start_time = Instant::now();
 
+    // for goals that are far away, first try going through a rough route of chunks
+    let is_long_distance =
+        long_distance::is_long_distance(&*opts.goal, opts.start, opts.long_distance_threshold);
+    let long_distance_path = is_long_distance
+        .then(|| {
+            long_distance::find_path(
+                cached_world,
+                &*opts.goal,
+                start_swimming,
+                successors,
+                next_state,
+                opts.min_timeout,
+                opts.max_timeout,
+            )
+        })
+        .flatten();
     let astar::Path {
-        movements,
+        mut movements,
         is_partial,
-    } = a_star(
-        RelBlockPos::get_origin(origin),
-        |n| opts.goal.heuristic(n.apply(origin)),
-        successors,
-        |n| opts.goal.success(n.apply(origin)),
-        opts.min_timeout,
-        opts.max_timeout,
-    );
+        mut metrics,
+    } = long_distance_path.unwrap_or_else(|| {
+        a_star(
+            RelBlockPos::get_origin(origin),
+            start_swimming,
+            |n| opts.goal.heuristic(n.apply(origin)),
+            successors,
+            |n| opts.goal.success(n.apply(origin)),
+            turn_cost,
+            next_state,
+            opts.min_timeout,
+            opts.max_timeout,
+        )
+    });
     let end_time = Instant::now();
-    debug!("partial: {is_partial:?}");
+    cached_world.record_metrics(&mut metrics);
+
+    // we don't know what's in chunks that aren't loaded, so we only go up to the
+    // first one and then wait for it to load
+    let unknown_chunk = movements
+        .iter()
+        .position(|movement| cached_world.is_block_unknown(movement.target))
+        .map(|index| {
+            let chunk_pos = ChunkPos::from(movements[index].target.apply(origin));
+            movements.truncate(index);
+            chunk_pos
+        });
+    let is_partial = is_partial || unknown_chunk.is_some();
+    debug!("partial: {is_partial:?}, unknown chunk: {unknown_chunk:?}");
     let duration = end_time - start_time;
     if is_partial {
         if movements.is_empty() {
@@ -438,8 +1371,6 @@ pub fn calculate_path(opts: CalculatePathOpts) -> Option<PathFoundEvent> {
         } else {
             info!("Pathfinder took {duration:?} (incomplete path)");
         }
-        // wait a bit so it's not a busy loop
-        thread::sleep(Duration::from_millis(100));
     } else {
         info!("Pathfinder took {duration:?}");
     }
@@ -464,19 +1395,22 @@ pub fn calculate_path(opts: CalculatePathOpts) -> Option<PathFoundEvent> {
 
     let mut mapped_path = VecDeque::with_capacity(path.len());
     let mut current_position = RelBlockPos::get_origin(origin);
+    let mut current_swimming = start_swimming;
+    let mut edges = Vec::new();
     for movement in path {
-        let mut found_edge = None;
-        for edge in successors(current_position) {
-            if edge.movement.target == movement.target {
-                found_edge = Some(edge);
-                break;
-            }
-        }
+        // this has to be the same edge that the search picked, which is the cheapest
+        // one to the target
+        successors(current_position, current_swimming, &mut edges);
+        let found_edge = edges
+            .drain(..)
+            .filter(|edge| edge.movement.target == movement.target)
+            .min_by(|a, b| a.cost.total_cmp(&b.cost));
 
         let found_edge = found_edge.expect(
             "path should always still be possible because we're using the same world cache",
         );
         current_position = found_edge.movement.target;
+        current_swimming = next_state(current_swimming, current_position);
 
         // we don't just clone the found_edge because we're using BlockPos instead of
         // RelBlockPos as the target type
@@ -489,28 +1423,73 @@ pub fn calculate_path(opts: CalculatePathOpts) -> Option<PathFoundEvent> {
         });
     }
 
-    Some(PathFoundEvent {
+    if opts.smooth_path {
+        mapped_path = smoothing::smooth_path(cached_world, opts.start, mapped_path);
+    }
+
+    if opts.is_riding && !mapped_path.is_empty() {
+        // azalea can't steer vehicles, so we get off and walk
+        mapped_path.push_front(moves::dismount::dismount_edge(opts.start));
+    }
+
+    let requirements = ConsumableCounts::from_path(&mapped_path);
+
+    let capture = if opts.capture {
+        Some(Arc::new(PathfinderCapture::new(
+            CaptureReason::NoPath,
+            opts,
+            &opts.world_lock.read(),
+            cached_world.cached_section_positions(),
+        )))
+    } else {
+        None
+    };
+
+    let path_found_event = PathFoundEvent {
         entity: opts.entity,
         start: opts.start,
         path: Some(mapped_path),
         is_partial,
         successors_fn: opts.successors_fn,
         allow_mining: opts.allow_mining,
-    })
+        requirements,
+        capture,
+        nodes_expanded: metrics.nodes_expanded,
+        compute_time: duration,
+        metrics,
+        is_continuation: false,
+        is_search_continuing: false,
+        unknown_chunk,
+    };
+    Some((path_found_event, current_swimming))
 }
 
-// poll the tasks and send the PathFoundEvent if they're done
+// poll the tasks and send the PathFoundEvents that they found
 pub fn handle_tasks(
     mut commands: Commands,
-    mut transform_tasks: Query<(Entity, &mut ComputePath)>,
+    transform_tasks: Query<(Entity, &ComputePath)>,
     mut path_found_events: EventWriter<PathFoundEvent>,
+    mut path_computed_events: EventWriter<PathComputedEvent>,
 ) {
-    for (entity, mut task) in &mut transform_tasks {
-        if let Some(optional_path_found_event) = future::block_on(future::poll_once(&mut task.0)) {
-            if let Some(path_found_event) = optional_path_found_event {
+    for (entity, task) in &transform_tasks {
+        // only one path per tick, so a continuation is never handled before the
+        // path that it continues
+        let is_done = match task.paths.lock().try_recv() {
+            Ok(path_found_event) => {
+                let is_done = !path_found_event.is_search_continuing;
+                if let Some(path_computed_event) =
+                    PathComputedEvent::from_path_found(&path_found_event)
+                {
+                    path_computed_events.write(path_computed_event);
+                }
                 path_found_events.write(path_found_event);
+                is_done
             }
-
+            Err(mpsc::TryRecvError::Empty) => false,
+            // the task finished without sending anything else
+            Err(mpsc::TryRecvError::Disconnected) => true,
+        };
+        if is_done {
             // Task is complete, so remove task component from entity
             commands.entity(entity).remove::<ComputePath>();
         }
@@ -526,17 +1505,110 @@ pub fn path_found_listener(
         Option<&mut ExecutingPath>,
         &InstanceName,
         &Inventory,
+        Option<&ActiveEffects>,
+        Option<&Health>,
         Option<&CustomPathfinderState>,
+        Option<&HumanizeSettings>,
+        Option<&DimensionTravel>,
     )>,
     instance_container: Res<InstanceContainer>,
     mut commands: Commands,
+    mut goal_unreachable_events: EventWriter<GoalUnreachableEvent>,
 ) {
     for event in events.read() {
-        let (mut pathfinder, executing_path, instance_name, inventory, custom_state) = query
+        let (
+            mut pathfinder,
+            executing_path,
+            instance_name,
+            inventory,
+            active_effects,
+            health,
+            custom_state,
+            humanize,
+            travel,
+        ) = query
             .get_mut(event.entity)
             .expect("Path found for an entity that doesn't have a pathfinder");
         if let Some(path) = &event.path {
-            if let Some(mut executing_path) = executing_path {
+            let shortfall = requirements_shortfall(
+                &event.requirements,
+                inventory,
+                &pathfinder.throwaway_blocks,
+            );
+            if !shortfall.is_empty() {
+                warn!("the path needs more {shortfall:?} than we have, recalculating without them");
+                pathfinder.excluded_consumables.extend(shortfall);
+                if let Some(mut executing_path) = executing_path {
+                    // stop after the current move so the path gets recalculated
+                    executing_path.queued_path = Some(VecDeque::new());
+                }
+                // the rest of the search would have the same problem
+                commands.entity(event.entity).remove::<ComputePath>();
+                pathfinder.is_calculating = false;
+                continue;
+            }
+
+            if event.is_partial {
+                let closest = path
+                    .back()
+                    .map(|edge| edge.movement.target)
+                    .unwrap_or(event.start);
+                let world_border = instance_container
+                    .get(instance_name)
+                    .and_then(|world_lock| world_lock.read().world_border);
+                // while we're going to a portal, the paths are trying to get closer to it
+                let progress_goal = match travel {
+                    Some(travel) if pathfinder.goal.is_some() => {
+                        Some(Arc::new(BlockPosGoal(travel.portal)) as Arc<dyn Goal>)
+                    }
+                    _ => pathfinder.goal.clone(),
+                };
+                if let Some(progress_goal) = progress_goal
+                    && let Some(reason) = check_partial_path_progress(
+                        &mut pathfinder,
+                        progress_goal.as_ref(),
+                        closest,
+                        world_border,
+                    )
+                {
+                    warn!("giving up on the goal ({reason:?}), going to {closest:?} instead");
+                    // the partial path still gets executed below, so we end up at the closest
+                    // position
+                    pathfinder.goal = None;
+                    pathfinder.outcome = Some(GotoOutcome::Unreachable { closest, reason });
+                    commands
+                        .entity(event.entity)
+                        .remove::<(ComputePath, DimensionTravel)>();
+                    pathfinder.is_calculating = false;
+                    goal_unreachable_events.write(GoalUnreachableEvent {
+                        entity: event.entity,
+                        closest,
+                        reason,
+                    });
+                }
+            }
+            if let Some(chunk) = event.unknown_chunk
+                && pathfinder.goal.is_some()
+            {
+                debug!("waiting for chunk {chunk:?} to load before going any further");
+                commands
+                    .entity(event.entity)
+                    .insert(WaitingForChunk { chunk, ticks: 0 });
+            }
+
+            if let Some(mut executing_path) = executing_path
+                && event.is_continuation
+                && executing_path.end() == Some(event.start)
+            {
+                // the path continues the one that we're already walking, so it can be
+                // added onto the end of it without checking anything
+                debug!("continuing the path from {}", event.start);
+                match &mut executing_path.queued_path {
+                    Some(queued_path) => queued_path.extend(path.iter().cloned()),
+                    None => executing_path.path.extend(path.iter().cloned()),
+                }
+                executing_path.is_path_partial = event.is_partial;
+            } else if let Some(mut executing_path) = executing_path {
                 let mut new_path = VecDeque::new();
 
                 // combine the old and new paths if the first node of the new path is a
@@ -547,20 +1619,37 @@ pub fn path_found_listener(
                         .expect("Entity tried to pathfind but the entity isn't in a valid world");
                     let origin = event.start;
                     let successors_fn: moves::SuccessorsFn = event.successors_fn;
-                    let cached_world = CachedWorld::new(world_lock, origin);
-                    let mining_cache = MiningCache::new(if event.allow_mining {
-                        Some(inventory.inventory_menu.clone())
-                    } else {
-                        None
-                    });
+                    let equipment = path_equipment(
+                        &pathfinder,
+                        inventory,
+                        active_effects,
+                        health,
+                        &world_lock.read().registries,
+                    );
+                    let cached_world = CachedWorld::new(world_lock, origin)
+                        .with_equipment(equipment)
+                        .with_regions(pathfinder.region_layer())
+                        .with_avoid_damage(pathfinder.avoid_damage);
+                    let mining_cache = create_mining_cache(
+                        event.allow_mining,
+                        pathfinder.move_settings,
+                        inventory,
+                        active_effects,
+                        humanize,
+                    );
                     let custom_state = custom_state.cloned().unwrap_or_default();
                     let custom_state_ref = custom_state.0.read();
+                    // we only check which edges exist, so the swimming state doesn't matter
                     let successors = |pos: RelBlockPos| {
                         call_successors_fn(
                             &cached_world,
                             &mining_cache,
+                            pathfinder.careful_mode_multiplier,
+                            pathfinder.low_air_threshold,
+                            pathfinder.move_settings,
                             &custom_state_ref,
                             successors_fn,
+                            SwimmingState::default(),
                             pos,
                         )
                     };
@@ -602,9 +1691,11 @@ pub fn path_found_listener(
                 executing_path.is_path_partial = event.is_partial;
             } else if path.is_empty() {
                 debug!("calculated path is empty, so didn't add ExecutingPath");
-                if !pathfinder.retry_on_no_path {
+                // we're already at the edge of the loaded chunks, so we just have to wait
+                if !pathfinder.retry_on_no_path && event.unknown_chunk.is_none() {
                     debug!("retry_on_no_path is set to false, removing goal");
                     pathfinder.goal = None;
+                    pathfinder.outcome = Some(GotoOutcome::NoPath);
                 }
             } else {
                 commands.entity(event.entity).insert(ExecutingPath {
@@ -613,6 +1704,8 @@ pub fn path_found_listener(
                     last_reached_node: event.start,
                     last_node_reached_at: Instant::now(),
                     is_path_partial: event.is_partial,
+                    interact_cooldown: 0,
+                    falling_block_wait: 0,
                 });
                 debug!("set path to {:?}", path.iter().take(10).collect::<Vec<_>>());
                 debug!("partial: {}", event.is_partial);
@@ -626,10 +1719,41 @@ pub fn path_found_listener(
                 // wasn't executing a path, don't need to do anything
             }
         }
-        pathfinder.is_calculating = false;
+        if !event.is_search_continuing {
+            pathfinder.is_calculating = false;
+        }
     }
 }
 
+/// Keep track of how close the partial paths for the current goal are getting,
+/// and return why we should give up on it if they stopped making progress.
+fn check_partial_path_progress(
+    pathfinder: &mut Pathfinder,
+    goal: &dyn Goal,
+    closest: BlockPos,
+    world_border: Option<WorldBorder>,
+) -> Option<UnreachableReason> {
+    let heuristic = goal.heuristic(closest);
+    if pathfinder
+        .closest_partial_heuristic
+        .is_none_or(|best| heuristic < best)
+    {
+        pathfinder.closest_partial_heuristic = Some(heuristic);
+        pathfinder.stalled_partial_paths = 0;
+    } else {
+        pathfinder.stalled_partial_paths += 1;
+    }
+
+    // waiting for more chunks won't help if the world border is in the way
+    if world_border.is_some_and(|border| border.distance_to_edge(closest) < 2.) {
+        return Some(UnreachableReason::WorldBorder);
+    }
+    if pathfinder.stalled_partial_paths >= pathfinder.max_stalled_partial_paths {
+        return Some(UnreachableReason::NoProgress);
+    }
+    None
+}
+
 #[allow(clippy::type_complexity)]
 pub fn timeout_movement(
     mut query: Query<(
@@ -640,9 +1764,17 @@ pub fn timeout_movement(
         Option<&Mining>,
         &InstanceName,
         &Inventory,
+        Option<&ActiveEffects>,
+        Option<&Health>,
         Option<&CustomPathfinderState>,
+        Option<&CapturePathfinderFailures>,
+        Option<&HumanizeSettings>,
+        Option<&HumanizeState>,
+        Option<&Riding>,
+        Option<&AvoidedMobs>,
     )>,
     instance_container: Res<InstanceContainer>,
+    mut path_computed_events: EventWriter<PathComputedEvent>,
 ) {
     for (
         entity,
@@ -652,7 +1784,14 @@ pub fn timeout_movement(
         mining,
         instance_name,
         inventory,
+        active_effects,
+        health,
         custom_state,
+        capture_failures,
+        humanize,
+        humanize_state,
+        riding,
+        avoided_mobs,
     ) in &mut query
     {
         // don't timeout if we're mining
@@ -665,6 +1804,19 @@ pub fn timeout_movement(
                 continue;
             }
         }
+        // or if we're waiting to be allowed to mine again
+        if let (Some(humanize), Some(humanize_state)) = (humanize, humanize_state)
+            && !humanize_state.can_start_break(humanize)
+        {
+            executing_path.last_node_reached_at = Instant::now();
+            continue;
+        }
+        // or if we're riding something, since crossing water in a boat takes a lot
+        // longer than a normal move
+        if riding.is_some() {
+            executing_path.last_node_reached_at = Instant::now();
+            continue;
+        }
 
         if executing_path.last_node_reached_at.elapsed() > Duration::from_secs(2)
             && !pathfinder.is_calculating
@@ -684,21 +1836,49 @@ pub fn timeout_movement(
                 return;
             };
 
+            if let Some(capture_failures) = capture_failures {
+                let capture = PathfinderCapture::stalled(
+                    &pathfinder,
+                    BlockPos::from(position),
+                    **position,
+                    inventory,
+                    &world_lock.read(),
+                    sections_around(
+                        iter::once(BlockPos::from(position)).chain(
+                            executing_path
+                                .path
+                                .iter()
+                                .take(20)
+                                .map(|edge| edge.movement.target),
+                        ),
+                    ),
+                );
+                write_capture_to_directory(&capture_failures.directory, &capture);
+            }
+
             let custom_state = custom_state.cloned().unwrap_or_default();
+            let mobs = avoided_mobs
+                .map(|avoided_mobs| MobLayer::new(&pathfinder.avoid_mobs, &avoided_mobs.0));
 
             // try to fix the path without recalculating everything.
             // (though, it'll still get fully recalculated by `recalculate_near_end_of_path`
             // if the new path is too short)
-            patch_path(
+            if let Some(path_computed_event) = patch_path(
                 0..=cmp::min(20, executing_path.path.len() - 1),
                 &mut executing_path,
                 &mut pathfinder,
                 inventory,
+                active_effects,
+                health,
+                humanize,
                 entity,
                 successors_fn,
                 world_lock,
+                mobs,
                 custom_state,
-            );
+            ) {
+                path_computed_events.write(path_computed_event);
+            }
             // reset last_node_reached_at so we don't immediately try to patch again
             executing_path.last_node_reached_at = Instant::now();
         }
@@ -712,11 +1892,24 @@ pub fn check_node_reached(
         &mut ExecutingPath,
         &Position,
         &Physics,
+        &Inventory,
+        Option<&Riding>,
+        Option<&DimensionTravel>,
     )>,
     mut walk_events: EventWriter<StartWalkEvent>,
     mut commands: Commands,
 ) {
-    for (entity, mut pathfinder, mut executing_path, position, physics) in &mut query {
+    for (
+        entity,
+        mut pathfinder,
+        mut executing_path,
+        position,
+        physics,
+        inventory,
+        riding,
+        travel,
+    ) in &mut query
+    {
         'skip: loop {
             // we check if the goal was reached *before* actually executing the movement so
             // we don't unnecessarily execute a movement when it wasn't necessary
@@ -730,12 +1923,18 @@ pub fn check_node_reached(
                 .take(20)
                 .rev()
             {
+                if riding.is_some() && i > 0 {
+                    // we can't have reached any moves after getting off yet
+                    continue;
+                }
+
                 let movement = edge.movement;
                 let is_reached_ctx = IsReachedCtx {
                     target: movement.target,
                     start: executing_path.last_reached_node,
                     position: **position,
                     physics,
+                    is_riding: riding.is_some(),
                 };
                 let extra_strict_if_last = if i == executing_path.path.len() - 1 {
                     let x_difference_from_center = position.x - (movement.target.x as f64 + 0.5);
@@ -785,12 +1984,42 @@ pub fn check_node_reached(
                             direction: WalkDirection::None,
                         });
                         commands.entity(entity).remove::<ExecutingPath>();
+                        // the goal is in another dimension while we're going to a portal
                         if let Some(goal) = pathfinder.goal.clone()
+                            && travel.is_none()
                             && goal.success(movement.target)
                         {
                             info!("goal was reached!");
+                            commands
+                                .entity(entity)
+                                .remove::<SharedCachedWorld>()
+                                .remove::<WaitingForChunk>();
                             pathfinder.goal = None;
                             pathfinder.successors_fn = None;
+                            pathfinder.is_at_goal = true;
+                            pathfinder.outcome = Some(GotoOutcome::Reached);
+                        }
+                    } else if let Some(next_edge) = executing_path.path.front()
+                        && !next_edge.movement.data.consumes.is_empty()
+                    {
+                        // make sure we still have enough items for the rest of the path before
+                        // we start using them up
+                        let requirements = ConsumableCounts::from_path(&executing_path.path);
+                        let shortfall = requirements_shortfall(
+                            &requirements,
+                            inventory,
+                            &pathfinder.throwaway_blocks,
+                        );
+                        if !shortfall.is_empty() {
+                            warn!(
+                                "ran out of {shortfall:?} while executing the path, recalculating without them"
+                            );
+                            pathfinder.excluded_consumables.extend(shortfall);
+                            walk_events.write(StartWalkEvent {
+                                entity,
+                                direction: WalkDirection::None,
+                            });
+                            commands.entity(entity).remove::<ExecutingPath>();
                         }
                     }
 
@@ -802,21 +2031,168 @@ pub fn check_node_reached(
     }
 }
 
+/// How close a block update has to be to a node that we haven't reached yet
+/// for us to check the edge that we're currently on again.
+///
+/// Smoothed edges can be up to [`smoothing::MAX_SMOOTHED_DISTANCE`] blocks
+/// long, so this is enough to cover the middle of them.
+pub const BLOCK_UPDATE_PATH_RADIUS: i32 = 3;
+
+/// A marker component that's added when the server changes a block near the
+/// part of the path that we haven't executed yet.
+///
+/// [`check_for_path_obstruction`] usually ignores the edge that we're
+/// currently on, but this makes it check that one too so we can reroute
+/// immediately instead of walking into the new block until we time out.
+#[derive(Component, Clone, Debug)]
+pub struct BlockChangedNearPath;
+
+pub fn mark_block_updates_near_path(
+    mut commands: Commands,
+    query: Query<(Entity, &QueuedServerBlockUpdates, &ExecutingPath)>,
+) {
+    for (entity, queued, executing_path) in &query {
+        let mut nodes = iter::once(executing_path.last_reached_node)
+            .chain(executing_path.path.iter().map(|edge| edge.movement.target));
+        let radius_squared = BLOCK_UPDATE_PATH_RADIUS.pow(2);
+        let is_near_path = nodes.any(|node| {
+            queued
+                .list
+                .iter()
+                .any(|(pos, _)| pos.distance_squared_to(node) <= radius_squared)
+        });
+        if is_near_path {
+            trace!("block changed near the path of {entity}");
+            commands.entity(entity).insert(BlockChangedNearPath);
+        }
+    }
+}
+
+/// Invalidate the blocks that the server changed in the world that we're
+/// keeping for recalculating the path, so it doesn't go through blocks that
+/// aren't there anymore.
+pub fn invalidate_cached_world_on_block_updates(
+    query: Query<(&QueuedServerBlockUpdates, &SharedCachedWorld)>,
+) {
+    for (queued, shared_world) in &query {
+        for &(pos, _) in &queued.list {
+            shared_world.invalidate_block(pos);
+        }
+    }
+}
+
+/// Invalidate the blocks that we mine in the world that we're keeping for
+/// recalculating the path, since the server might not tell us about them.
+pub fn invalidate_cached_world_on_mined_block(
+    trigger: Trigger<FinishMiningBlockEvent>,
+    query: Query<&SharedCachedWorld>,
+) {
+    if let Ok(shared_world) = query.get(trigger.target()) {
+        shared_world.invalidate_block(trigger.event().position);
+    }
+}
+
+/// The mobs in `avoid` that are near `position` and in the same instance as
+/// us.
+fn nearby_avoided_mobs(
+    avoid: &[MobAvoidance],
+    entities: &Query<(Entity, &EntityKindComponent, &Position, &InstanceName)>,
+    instance_name: &InstanceName,
+    position: &Position,
+) -> Vec<NearbyMob> {
+    let entities = entities
+        .iter()
+        .filter(|(_, _, _, entity_instance_name)| *entity_instance_name == instance_name)
+        .map(|(entity, kind, position, _)| (entity, **kind, **position));
+    mobs::nearby_mobs(avoid, entities, **position)
+}
+
+/// Add [`MobMovedNearPath`] when one of the mobs that we're avoiding gets
+/// closer to the part of the path that we haven't executed yet, so
+/// [`check_for_path_obstruction`] can route around it.
+#[allow(clippy::type_complexity)]
+pub fn mark_mobs_near_path(
+    mut commands: Commands,
+    query: Query<(
+        Entity,
+        &Pathfinder,
+        &ExecutingPath,
+        &AvoidedMobs,
+        &Position,
+        &InstanceName,
+    )>,
+    entities: Query<(Entity, &EntityKindComponent, &Position, &InstanceName)>,
+) {
+    for (entity, pathfinder, executing_path, avoided_mobs, position, instance_name) in &query {
+        if pathfinder.avoid_mobs.is_empty() {
+            continue;
+        }
+        let mobs = nearby_avoided_mobs(&pathfinder.avoid_mobs, &entities, instance_name, position);
+        let nodes = iter::once(executing_path.last_reached_node)
+            .chain(executing_path.path.iter().map(|edge| edge.movement.target))
+            .collect::<Vec<_>>();
+        if mobs::has_mob_approached(&pathfinder.avoid_mobs, avoided_mobs, &mobs, &nodes) {
+            trace!("a mob moved near the path of {entity}");
+            commands.entity(entity).insert(MobMovedNearPath);
+        }
+    }
+}
+
 #[allow(clippy::type_complexity)]
 pub fn check_for_path_obstruction(
+    mut commands: Commands,
     mut query: Query<(
         Entity,
         &mut Pathfinder,
         &mut ExecutingPath,
         &InstanceName,
         &Inventory,
+        Option<&ActiveEffects>,
+        Option<&Health>,
         Option<&CustomPathfinderState>,
+        Option<&HumanizeSettings>,
+        Option<&AvoidedMobs>,
+        &Position,
+        Has<BlockChangedNearPath>,
+        Has<MobMovedNearPath>,
     )>,
+    entities: Query<(Entity, &EntityKindComponent, &Position, &InstanceName)>,
     instance_container: Res<InstanceContainer>,
+    mut path_computed_events: EventWriter<PathComputedEvent>,
 ) {
-    for (entity, mut pathfinder, mut executing_path, instance_name, inventory, custom_state) in
-        &mut query
+    for (
+        entity,
+        mut pathfinder,
+        mut executing_path,
+        instance_name,
+        inventory,
+        active_effects,
+        health,
+        custom_state,
+        humanize,
+        avoided_mobs,
+        position,
+        block_changed_near_path,
+        mob_moved_near_path,
+    ) in &mut query
     {
+        if block_changed_near_path {
+            commands.entity(entity).remove::<BlockChangedNearPath>();
+        }
+        // the edges near mobs only cost more than they did if we check them with where
+        // the mobs are now, so the mobs are only looked at when one of them moved
+        let moved_mobs = if mob_moved_near_path {
+            let mobs =
+                nearby_avoided_mobs(&pathfinder.avoid_mobs, &entities, instance_name, position);
+            commands
+                .entity(entity)
+                .remove::<MobMovedNearPath>()
+                .insert(AvoidedMobs(mobs.clone()));
+            Some(MobLayer::new(&pathfinder.avoid_mobs, &mobs))
+        } else {
+            None
+        };
+
         let Some(successors_fn) = pathfinder.successors_fn else {
             continue;
         };
@@ -825,22 +2201,43 @@ pub fn check_for_path_obstruction(
             .get(instance_name)
             .expect("Entity tried to pathfind but the entity isn't in a valid world");
 
-        // obstruction check (the path we're executing isn't possible anymore)
+        // obstruction check (the path we're executing isn't possible anymore). the
+        // CachedWorld is made from scratch every time, so it always has the blocks
+        // from the latest updates
         let origin = executing_path.last_reached_node;
-        let cached_world = CachedWorld::new(world_lock, origin);
-        let mining_cache = MiningCache::new(if pathfinder.allow_mining {
-            Some(inventory.inventory_menu.clone())
-        } else {
-            None
-        });
+        let equipment = path_equipment(
+            &pathfinder,
+            inventory,
+            active_effects,
+            health,
+            &world_lock.read().registries,
+        );
+        let cached_world = CachedWorld::new(world_lock, origin)
+            .with_equipment(equipment)
+            .with_regions(pathfinder.region_layer())
+            .with_mobs(moved_mobs.clone())
+            .with_avoid_damage(pathfinder.avoid_damage);
+        let mining_cache = create_mining_cache(
+            pathfinder.allow_mining,
+            pathfinder.move_settings,
+            inventory,
+            active_effects,
+            humanize,
+        );
         let custom_state = custom_state.cloned().unwrap_or_default();
         let custom_state_ref = custom_state.0.read();
+        // we don't know how long we've been swimming for at each node, so assume the
+        // best so a swimming discount that we lost isn't mistaken for an obstruction
         let successors = |pos: RelBlockPos| {
             call_successors_fn(
                 &cached_world,
                 &mining_cache,
+                pathfinder.careful_mode_multiplier,
+                pathfinder.low_air_threshold,
+                pathfinder.move_settings,
                 &custom_state_ref,
                 successors_fn,
+                SwimmingState::best_case(),
                 pos,
             )
         };
@@ -850,6 +2247,10 @@ pub fn check_for_path_obstruction(
             RelBlockPos::from_origin(origin, executing_path.last_reached_node),
             &executing_path.path,
             successors,
+            |from, to| smoothing::is_straight_walkable(&cached_world, from, to),
+            // if a block changed or a mob showed up right in front of us then it's
+            // worth rerouting even though we're in the middle of the move
+            !block_changed_near_path && !mob_moved_near_path,
         ) else {
             continue;
         };
@@ -881,19 +2282,29 @@ pub fn check_for_path_obstruction(
             .get(instance_name)
             .expect("Entity tried to pathfind but the entity isn't in a valid world");
 
+        let mobs = moved_mobs.or_else(|| {
+            avoided_mobs.map(|avoided_mobs| MobLayer::new(&pathfinder.avoid_mobs, &avoided_mobs.0))
+        });
+
         // patch up to 20 nodes
         let patch_end_index = cmp::min(obstructed_index + 20, executing_path.path.len() - 1);
 
-        patch_path(
+        if let Some(path_computed_event) = patch_path(
             obstructed_index..=patch_end_index,
             &mut executing_path,
             &mut pathfinder,
             inventory,
+            active_effects,
+            health,
+            humanize,
             entity,
             successors_fn,
             world_lock,
+            mobs,
             custom_state.clone(),
-        );
+        ) {
+            path_computed_events.write(path_computed_event);
+        }
     }
 }
 
@@ -902,17 +2313,24 @@ pub fn check_for_path_obstruction(
 ///
 /// You should avoid making the range too large, since the timeout for the A*
 /// calculation is very low. About 20 nodes is a good amount.
+///
+/// Returns the [`PathComputedEvent`] for the patch, which should be sent by
+/// the caller.
 #[allow(clippy::too_many_arguments)]
 fn patch_path(
     patch_nodes: RangeInclusive<usize>,
     executing_path: &mut ExecutingPath,
     pathfinder: &mut Pathfinder,
     inventory: &Inventory,
+    active_effects: Option<&ActiveEffects>,
+    health: Option<&Health>,
+    humanize: Option<&HumanizeSettings>,
     entity: Entity,
     successors_fn: SuccessorsFn,
     world_lock: Arc<RwLock<azalea_world::Instance>>,
+    mobs: Option<MobLayer>,
     custom_state: CustomPathfinderState,
-) {
+) -> Option<PathComputedEvent> {
     let patch_start = if *patch_nodes.start() == 0 {
         executing_path.last_reached_node
     } else {
@@ -930,12 +2348,25 @@ fn patch_path(
     let goto_id_atomic = pathfinder.goto_id.clone();
     let allow_mining = pathfinder.allow_mining;
     let retry_on_no_path = pathfinder.retry_on_no_path;
+    let careful_mode_multiplier = pathfinder.careful_mode_multiplier;
+    let avoid_damage = pathfinder.avoid_damage;
+    let low_air_threshold = pathfinder.low_air_threshold;
+    let move_settings = pathfinder.move_settings;
 
-    let mining_cache = MiningCache::new(if allow_mining {
-        Some(inventory.inventory_menu.clone())
-    } else {
-        None
-    });
+    let mining_cache = create_mining_cache(
+        allow_mining,
+        move_settings,
+        inventory,
+        active_effects,
+        humanize,
+    );
+    let equipment = path_equipment(
+        pathfinder,
+        inventory,
+        active_effects,
+        health,
+        &world_lock.read().registries,
+    );
 
     // the timeout is small enough that this doesn't need to be async
     let path_found_event = calculate_path(CalculatePathOpts {
@@ -944,10 +2375,26 @@ fn patch_path(
         goal,
         successors_fn,
         world_lock,
+        shared_world: None,
         goto_id_atomic,
         allow_mining,
         mining_cache,
         retry_on_no_path,
+        careful_mode_multiplier,
+        avoid_damage,
+        equipment,
+        capture: false,
+        // patches are short enough that avoiding the dark isn't worth it
+        risk: None,
+        regions: pathfinder.region_layer(),
+        mobs,
+        is_riding: false,
+        // the patch starts somewhere ahead of us, so we can't know how much air we'll have there
+        air_supply: MAX_AIR_SUPPLY,
+        low_air_threshold,
+        move_settings,
+        smooth_path: pathfinder.smooth_path,
+        long_distance_threshold: f32::INFINITY,
 
         custom_state,
         min_timeout: PathfinderTimeout::Nodes(10_000),
@@ -958,6 +2405,9 @@ fn patch_path(
     pathfinder.is_calculating = false;
 
     debug!("obstruction patch: {path_found_event:?}");
+    let path_computed_event = path_found_event
+        .as_ref()
+        .and_then(PathComputedEvent::from_path_found);
 
     let mut new_path = VecDeque::new();
     if *patch_nodes.start() > 0 {
@@ -993,10 +2443,12 @@ fn patch_path(
     if !is_patch_complete {
         executing_path.is_path_partial = true;
     }
+
+    path_computed_event
 }
 
 pub fn recalculate_near_end_of_path(
-    mut query: Query<(Entity, &mut Pathfinder, &mut ExecutingPath)>,
+    mut query: Query<(Entity, &mut Pathfinder, &mut ExecutingPath), Without<WaitingForChunk>>,
     mut walk_events: EventWriter<StartWalkEvent>,
     mut goto_events: EventWriter<GotoEvent>,
     mut commands: Commands,
@@ -1018,20 +2470,20 @@ pub fn recalculate_near_end_of_path(
                         "recalculate_near_end_of_path executing_path.is_path_partial: {}",
                         executing_path.is_path_partial
                     );
+                    let min_timeout = if executing_path.path.len() == 50 {
+                        // we have quite some time until the node is reached, soooo we might as
+                        // well burn some cpu cycles to get a good path
+                        PathfinderTimeout::Time(Duration::from_secs(5))
+                    } else {
+                        PathfinderTimeout::Time(Duration::from_secs(1))
+                    };
                     goto_events.write(GotoEvent {
                         entity,
                         goal,
-                        successors_fn,
-                        allow_mining: pathfinder.allow_mining,
-                        retry_on_no_path: pathfinder.retry_on_no_path,
-                        min_timeout: if executing_path.path.len() == 50 {
-                            // we have quite some time until the node is reached, soooo we might as
-                            // well burn some cpu cycles to get a good path
-                            PathfinderTimeout::Time(Duration::from_secs(5))
-                        } else {
-                            PathfinderTimeout::Time(Duration::from_secs(1))
-                        },
-                        max_timeout: pathfinder.max_timeout.expect("max_timeout should be set"),
+                        options: pathfinder
+                            .goto_options()
+                            .successors_fn(successors_fn)
+                            .min_timeout(min_timeout),
                     });
                     pathfinder.is_calculating = true;
 
@@ -1076,12 +2528,19 @@ pub fn recalculate_near_end_of_path(
 pub fn tick_execute_path(
     mut query: Query<(
         Entity,
+        &Pathfinder,
         &mut ExecutingPath,
         &Position,
         &Physics,
         Option<&Mining>,
         &InstanceHolder,
         &Inventory,
+        &LookDirection,
+        &EyeHeight,
+        Option<&HumanizeSettings>,
+        Option<&mut HumanizeState>,
+        &PhysicsState,
+        Option<&Riding>,
     )>,
     mut look_at_events: EventWriter<LookAtEvent>,
     mut sprint_events: EventWriter<StartSprintEvent>,
@@ -1089,20 +2548,76 @@ pub fn tick_execute_path(
     mut jump_events: EventWriter<JumpEvent>,
     mut start_mining_events: EventWriter<StartMiningBlockEvent>,
     mut set_selected_hotbar_slot_events: EventWriter<SetSelectedHotbarSlotEvent>,
+    mut dismount_events: EventWriter<DismountEvent>,
+    mut start_use_item_events: EventWriter<StartUseItemEvent>,
+    mut sneak_events: EventWriter<SneakEvent>,
 ) {
-    for (entity, executing_path, position, physics, mining, instance_holder, inventory_component) in
-        &mut query
+    for (
+        entity,
+        pathfinder,
+        mut executing_path,
+        position,
+        physics,
+        mining,
+        instance_holder,
+        inventory_component,
+        look_direction,
+        eye_height,
+        humanize,
+        humanize_state,
+        physics_state,
+        riding,
+    ) in &mut query
     {
-        if let Some(edge) = executing_path.path.front() {
+        if executing_path.interact_cooldown > 0 {
+            executing_path.interact_cooldown -= 1;
+        }
+        if executing_path.falling_block_wait > 0 {
+            executing_path.falling_block_wait -= 1;
+        }
+
+        if let Some(edge) = executing_path.path.front().cloned() {
+            let upcoming = executing_path
+                .path
+                .iter()
+                .skip(1)
+                .chain(executing_path.queued_path.iter().flatten())
+                .take(SPRINT_LOOKAHEAD_NODES)
+                .map(|edge| edge.movement.target)
+                .collect::<Vec<_>>();
+            trace!(
+                "executing move, position: {}, last_reached_node: {}",
+                **position, executing_path.last_reached_node
+            );
+            // moves that want to sneak ask for it again every tick, so we don't
+            // keep sneaking for the moves after them
+            if physics_state.trying_to_sneak {
+                sneak_events.write(SneakEvent {
+                    entity,
+                    sneaking: false,
+                });
+            }
             let ctx = ExecuteCtx {
                 entity,
                 target: edge.movement.target,
                 position: **position,
                 start: executing_path.last_reached_node,
+                upcoming: &upcoming,
                 physics,
                 is_currently_mining: mining.is_some(),
+                is_riding: riding.is_some(),
                 instance: instance_holder.instance.clone(),
                 menu: inventory_component.inventory_menu.clone(),
+                allow_mining: pathfinder.allow_mining,
+                throwaway_blocks: &pathfinder.throwaway_blocks,
+                careful_mode_multiplier: pathfinder.careful_mode_multiplier,
+                allow_sprint: pathfinder.move_settings.sprint,
+                look_direction: *look_direction,
+                eye_height: eye_height.into(),
+                humanize,
+                humanize_state: humanize_state.map(Mut::into_inner),
+                interact_cooldown: &mut executing_path.interact_cooldown,
+                falling_block_wait: &mut executing_path.falling_block_wait,
 
                 look_at_events: &mut look_at_events,
                 sprint_events: &mut sprint_events,
@@ -1110,18 +2625,26 @@ pub fn tick_execute_path(
                 jump_events: &mut jump_events,
                 start_mining_events: &mut start_mining_events,
                 set_selected_hotbar_slot_events: &mut set_selected_hotbar_slot_events,
+                dismount_events: &mut dismount_events,
+                start_use_item_events: &mut start_use_item_events,
+                sneak_events: &mut sneak_events,
             };
-            trace!(
-                "executing move, position: {}, last_reached_node: {}",
-                **position, executing_path.last_reached_node
-            );
             (edge.movement.data.execute)(ctx);
         }
     }
 }
 
+#[allow(clippy::type_complexity)]
 pub fn recalculate_if_has_goal_but_no_path(
-    mut query: Query<(Entity, &mut Pathfinder), Without<ExecutingPath>>,
+    mut query: Query<
+        (Entity, &mut Pathfinder),
+        (
+            Without<ExecutingPath>,
+            Without<WaitingForChunk>,
+            Without<WaitingInPortal>,
+            Without<ElytraFlight>,
+        ),
+    >,
     mut goto_events: EventWriter<GotoEvent>,
 ) {
     for (entity, mut pathfinder) in &mut query {
@@ -1133,41 +2656,147 @@ pub fn recalculate_if_has_goal_but_no_path(
             goto_events.write(GotoEvent {
                 entity,
                 goal,
-                successors_fn: pathfinder.successors_fn.unwrap(),
-                allow_mining: pathfinder.allow_mining,
-                retry_on_no_path: pathfinder.retry_on_no_path,
-                min_timeout: pathfinder.min_timeout.expect("min_timeout should be set"),
-                max_timeout: pathfinder.max_timeout.expect("max_timeout should be set"),
+                options: pathfinder.goto_options(),
             });
             pathfinder.is_calculating = true;
         }
     }
 }
 
+/// How many ticks we wait at the edge of the loaded chunks for the next chunk
+/// to load before recalculating the path anyway.
+///
+/// Every path that we recalculate without getting any closer counts towards
+/// [`PathfinderDefaults::max_stalled_partial_paths`], so we eventually give
+/// up if the chunk never loads.
+pub const UNKNOWN_TERRAIN_WAIT_TICKS: u32 = 100;
+
+/// A component that's present while our path stops at a chunk that isn't
+/// loaded, because we don't know what's in it yet.
+///
+/// See [`PathFoundEvent::unknown_chunk`].
+#[derive(Component, Clone, Debug)]
+pub struct WaitingForChunk {
+    pub chunk: ChunkPos,
+    /// How many ticks we've been waiting at the end of the path for.
+    pub ticks: u32,
+}
+
+/// Recalculate the path once the chunk that we're waiting for loads, or when
+/// we've waited at the end of the path for [`UNKNOWN_TERRAIN_WAIT_TICKS`].
+///
+/// The chunk might've been loaded by another client in the same world, so this
+/// checks the world instead of waiting for us to receive the chunk.
+pub fn wait_for_unknown_terrain(
+    mut query: Query<(
+        Entity,
+        &mut Pathfinder,
+        &mut WaitingForChunk,
+        Option<&ExecutingPath>,
+        &InstanceName,
+    )>,
+    instance_container: Res<InstanceContainer>,
+    mut goto_events: EventWriter<GotoEvent>,
+    mut commands: Commands,
+) {
+    for (entity, mut pathfinder, mut waiting, executing_path, instance_name) in &mut query {
+        let Some(goal) = pathfinder.goal.clone() else {
+            commands.entity(entity).remove::<WaitingForChunk>();
+            continue;
+        };
+        if pathfinder.is_calculating {
+            continue;
+        }
+
+        let is_loaded = instance_container
+            .get(instance_name)
+            .is_some_and(|instance| instance.read().chunks.get(&waiting.chunk).is_some());
+        if is_loaded {
+            debug!("chunk {:?} loaded, recalculating path", waiting.chunk);
+        } else {
+            // only start counting once we're done walking to the edge
+            if executing_path.is_some_and(|executing_path| !executing_path.path.is_empty()) {
+                continue;
+            }
+            waiting.ticks += 1;
+            if waiting.ticks < UNKNOWN_TERRAIN_WAIT_TICKS {
+                continue;
+            }
+            debug!(
+                "chunk {:?} still isn't loaded, recalculating path anyway",
+                waiting.chunk
+            );
+        }
+
+        commands.entity(entity).remove::<WaitingForChunk>();
+        goto_events.write(GotoEvent {
+            entity,
+            goal,
+            options: pathfinder.goto_options(),
+        });
+        pathfinder.is_calculating = true;
+    }
+}
+
+/// Stop the pathfinder and everything that it was doing.
+///
+/// This stops any path calculation, aborts any block that we were in the
+/// middle of mining, and makes the [`PathfinderClientExt::goto`] future resolve
+/// with [`GotoOutcome::Cancelled`].
 #[derive(Event)]
 pub struct StopPathfindingEvent {
     pub entity: Entity,
     /// If false, then let the current movement finish before stopping. If true,
     /// then stop moving immediately. This might cause the bot to fall if it was
     /// in the middle of parkouring.
+    ///
+    /// Movements that are mining a block are always stopped immediately, since
+    /// that's safe to do.
     pub force: bool,
 }
 
+#[allow(clippy::type_complexity)]
 pub fn handle_stop_pathfinding_event(
     mut events: EventReader<StopPathfindingEvent>,
-    mut query: Query<(&mut Pathfinder, &mut ExecutingPath)>,
+    mut query: Query<(
+        &mut Pathfinder,
+        Option<&mut ExecutingPath>,
+        Option<&Mining>,
+        Option<&MiningQueued>,
+    )>,
     mut walk_events: EventWriter<StartWalkEvent>,
     mut commands: Commands,
 ) {
     for event in events.read() {
-        // stop computing any path that's being computed
-        commands.entity(event.entity).remove::<ComputePath>();
-
-        let Ok((mut pathfinder, mut executing_path)) = query.get_mut(event.entity) else {
+        // stop computing any path that's being computed, and don't resume the old goal
+        // if we were escaping from lava or following an entity
+        commands
+            .entity(event.entity)
+            .remove::<ComputePath>()
+            .remove::<SharedCachedWorld>()
+            .remove::<WaitingForChunk>()
+            .remove::<(DimensionTravel, WaitingInPortal)>()
+            .remove::<EscapingHazard>()
+            .remove::<FollowEntity>();
+
+        let Ok((mut pathfinder, executing_path, mining, mining_queued)) =
+            query.get_mut(event.entity)
+        else {
             continue;
         };
+        // make sure the result of a path calculation that's still running gets ignored
+        pathfinder.goto_id.fetch_add(1, atomic::Ordering::SeqCst);
         pathfinder.goal = None;
-        if event.force {
+        pathfinder.successors_fn = None;
+        pathfinder.is_calculating = false;
+        pathfinder.is_at_goal = false;
+        pathfinder.excluded_consumables.clear();
+        pathfinder.outcome = Some(GotoOutcome::Cancelled);
+
+        let Some(mut executing_path) = executing_path else {
+            continue;
+        };
+        if event.force || mining.is_some() || mining_queued.is_some() {
             executing_path.path.clear();
             executing_path.queued_path = None;
         } else {
@@ -1187,11 +2816,83 @@ pub fn handle_stop_pathfinding_event(
     }
 }
 
+/// Send a [`PathExecutionFinishedEvent`] when we stop executing a path because
+/// the goto is over.
+pub fn send_path_execution_finished_events(
+    mut removed: RemovedComponents<ExecutingPath>,
+    query: Query<(&Pathfinder, &Position)>,
+    mut finished_events: EventWriter<PathExecutionFinishedEvent>,
+) {
+    for entity in removed.read() {
+        let Ok((pathfinder, position)) = query.get(entity) else {
+            continue;
+        };
+        // the path is also removed while we're waiting for the next one
+        if pathfinder.goal.is_some() || pathfinder.is_calculating {
+            continue;
+        }
+        finished_events.write(PathExecutionFinishedEvent {
+            entity,
+            success: pathfinder.outcome == Some(GotoOutcome::Reached),
+            position: BlockPos::from(position),
+        });
+    }
+}
+
+/// Stop sneaking when we're done executing a path, since the last move might've
+/// been sneaking.
+pub fn stop_sneaking_when_path_ends(
+    mut removed: RemovedComponents<ExecutingPath>,
+    query: Query<&PhysicsState>,
+    mut sneak_events: EventWriter<SneakEvent>,
+) {
+    for entity in removed.read() {
+        if let Ok(physics_state) = query.get(entity)
+            && physics_state.trying_to_sneak
+        {
+            sneak_events.write(SneakEvent {
+                entity,
+                sneaking: false,
+            });
+        }
+    }
+}
+
+/// Abort the block that we were mining when the pathfinder was stopped, so it
+/// doesn't keep getting mined after we stop executing the path.
+pub fn abort_mining_on_stop_pathfinding(
+    mut events: EventReader<StopPathfindingEvent>,
+    query: Query<(), With<Mining>>,
+    mut stop_mining_events: EventWriter<StopMiningBlockEvent>,
+    mut commands: Commands,
+) {
+    for event in events.read() {
+        commands.entity(event.entity).remove::<MiningQueued>();
+        if query.contains(event.entity) {
+            stop_mining_events.write(StopMiningBlockEvent {
+                entity: event.entity,
+            });
+        }
+    }
+}
+
 pub fn stop_pathfinding_on_instance_change(
-    mut query: Query<(Entity, &mut ExecutingPath), Changed<InstanceName>>,
+    mut query: Query<
+        (
+            Entity,
+            &mut ExecutingPath,
+            &InstanceName,
+            Option<&DimensionTravel>,
+        ),
+        Changed<InstanceName>,
+    >,
     mut stop_pathfinding_events: EventWriter<StopPathfindingEvent>,
 ) {
-    for (entity, mut executing_path) in &mut query {
+    for (entity, mut executing_path, instance_name, travel) in &mut query {
+        // continue_goto_in_new_dimension handles going through a portal on purpose
+        if travel.is_some_and(|travel| **instance_name == travel.dimension) {
+            continue;
+        }
         if !executing_path.path.is_empty() {
             debug!("instance changed, clearing path");
             executing_path.path.clear();
@@ -1205,26 +2906,41 @@ pub fn stop_pathfinding_on_instance_change(
 
 /// Checks whether the path has been obstructed, and returns Some(index) if it
 /// has been. The index is of the first obstructed node.
-pub fn check_path_obstructed<SuccessorsFn>(
+///
+/// Edges that were merged by [`smoothing::smooth_path`] aren't returned by the
+/// successors function, so they're checked with `is_straight_walkable`
+/// instead.
+///
+/// If `ignore_current_edge` is true, then the edge that we're currently
+/// executing is never counted as obstructed.
+pub fn check_path_obstructed<SuccessorsFn, StraightWalkableFn>(
     origin: BlockPos,
     mut current_position: RelBlockPos,
     path: &VecDeque<astar::Edge<BlockPos, moves::MoveData>>,
     successors_fn: SuccessorsFn,
+    is_straight_walkable: StraightWalkableFn,
+    ignore_current_edge: bool,
 ) -> Option<usize>
 where
     SuccessorsFn: Fn(RelBlockPos) -> Vec<astar::Edge<RelBlockPos, moves::MoveData>>,
+    StraightWalkableFn: Fn(RelBlockPos, RelBlockPos) -> bool,
 {
     for (i, edge) in path.iter().enumerate() {
         let movement_target = RelBlockPos::from_origin(origin, edge.movement.target);
 
-        let mut found_edge = None;
-        for candidate_edge in successors_fn(current_position) {
-            if candidate_edge.movement.target == movement_target {
-                found_edge = Some(candidate_edge);
-                break;
-            }
+        let is_neighbor = (movement_target.x - current_position.x).abs() <= 1
+            && (movement_target.z - current_position.z).abs() <= 1;
+        if !is_neighbor && is_straight_walkable(current_position, movement_target) {
+            current_position = movement_target;
+            continue;
         }
 
+        // paths are made of the cheapest edges, so compare with the cheapest one
+        let found_edge = successors_fn(current_position)
+            .into_iter()
+            .filter(|candidate_edge| candidate_edge.movement.target == movement_target)
+            .min_by(|a, b| a.cost.total_cmp(&b.cost));
+
         current_position = movement_target;
         // if found_edge is None or the cost increased, then return the index
         if found_edge
@@ -1233,7 +2949,7 @@ where
         {
             // if the node that we're currently executing was obstructed then it's often too
             // late to change the path, so it's usually better to just ignore this case :/
-            if i == 0 {
+            if i == 0 && ignore_current_edge {
                 warn!("path obstructed at index 0, ignoring");
                 continue;
             }
@@ -1245,20 +2961,156 @@ where
     None
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn call_successors_fn(
     cached_world: &CachedWorld,
     mining_cache: &MiningCache,
+    careful_mode_multiplier: f32,
+    low_air_threshold: i32,
+    move_settings: MoveSettings,
     custom_state: &CustomPathfinderStateRef,
     successors_fn: SuccessorsFn,
+    swimming: SwimmingState,
     pos: RelBlockPos,
 ) -> Vec<astar::Edge<RelBlockPos, moves::MoveData>> {
     let mut edges = Vec::with_capacity(16);
+    fill_successors(
+        cached_world,
+        mining_cache,
+        careful_mode_multiplier,
+        low_air_threshold,
+        move_settings,
+        custom_state,
+        successors_fn,
+        swimming,
+        pos,
+        &mut edges,
+    );
+    edges
+}
+
+/// Like [`call_successors_fn`], but the edges are put in `edges` (which is
+/// cleared first) so the same Vec can be reused for every node in a search.
+#[allow(clippy::too_many_arguments)]
+pub fn fill_successors(
+    cached_world: &CachedWorld,
+    mining_cache: &MiningCache,
+    careful_mode_multiplier: f32,
+    low_air_threshold: i32,
+    move_settings: MoveSettings,
+    custom_state: &CustomPathfinderStateRef,
+    successors_fn: SuccessorsFn,
+    swimming: SwimmingState,
+    pos: RelBlockPos,
+    edges: &mut Vec<astar::Edge<RelBlockPos, moves::MoveData>>,
+) {
+    edges.clear();
     let mut ctx = PathfinderCtx {
-        edges: &mut edges,
+        edges,
         world: cached_world,
         mining_cache,
+        careful_mode_multiplier,
+        low_air_threshold,
+        move_settings,
+        swimming,
         custom_state,
     };
     successors_fn(&mut ctx, pos);
-    edges
+    moves::water::apply_water_settings(&mut ctx, pos);
+    if cached_world.has_regions() {
+        regions::apply_region_costs(cached_world, ctx.edges);
+    }
+    if cached_world.has_mobs() {
+        mobs::apply_mob_costs(cached_world, ctx.edges);
+    }
+}
+
+/// The extra cost of changing direction at `current`, since turning sharply
+/// makes us lose our sprinting momentum.
+///
+/// Moves that only go up or down don't count as having a direction.
+pub fn turn_cost(previous: RelBlockPos, current: RelBlockPos, next: RelBlockPos) -> f32 {
+    let horizontal_offset =
+        |from: RelBlockPos, to: RelBlockPos| ((to.x - from.x) as f32, (to.z - from.z) as f32);
+    let (dx1, dz1) = horizontal_offset(previous, current);
+    let (dx2, dz2) = horizontal_offset(current, next);
+    let length_squared_1 = dx1 * dx1 + dz1 * dz1;
+    let length_squared_2 = dx2 * dx2 + dz2 * dz2;
+    if length_squared_1 == 0. || length_squared_2 == 0. {
+        return 0.;
+    }
+
+    // the cosine of the angle between the two directions
+    let cos_angle = (dx1 * dx2 + dz1 * dz2) / (length_squared_1 * length_squared_2).sqrt();
+    // a little bit of leeway so exactly 45° doesn't count because of rounding
+    if cos_angle < FRAC_1_SQRT_2 - 0.001 {
+        TURN_PENALTY
+    } else {
+        0.
+    }
+}
+
+fn create_mining_cache(
+    allow_mining: bool,
+    move_settings: MoveSettings,
+    inventory: &Inventory,
+    active_effects: Option<&ActiveEffects>,
+    humanize: Option<&HumanizeSettings>,
+) -> MiningCache {
+    MiningCache::new(if allow_mining {
+        Some(inventory.inventory_menu.clone())
+    } else {
+        None
+    })
+    .with_break_interval(
+        humanize
+            .map(HumanizeSettings::break_interval)
+            .unwrap_or_default(),
+    )
+    .with_cost_multiplier(move_settings.mining_cost_multiplier)
+    .with_active_effects(active_effects.cloned().unwrap_or_default())
+}
+
+/// The equipment that paths should be calculated with, which leaves out the
+/// consumables that we recently found out we don't have enough of.
+fn path_equipment(
+    pathfinder: &Pathfinder,
+    inventory: &Inventory,
+    active_effects: Option<&ActiveEffects>,
+    health: Option<&Health>,
+    registries: &RegistryHolder,
+) -> PathEquipment {
+    // the throwaway blocks are only counted if we're allowed to place them
+    let throwaway_blocks: &[Item] = if pathfinder.allow_placing {
+        &pathfinder.throwaway_blocks
+    } else {
+        &[]
+    };
+    PathEquipment::from_menu(&inventory.inventory_menu)
+        .with_throwaway_blocks(&inventory.inventory_menu, throwaway_blocks)
+        .with_enchantments(&inventory.inventory_menu, registries)
+        .without(&pathfinder.excluded_consumables)
+        .with_effects(
+            active_effects
+                .map(PathEffects::from_active_effects)
+                .unwrap_or_default(),
+        )
+        .with_fall_damage_budget(
+            health.map_or(0., |health| **health),
+            pathfinder.max_fall_damage,
+        )
+}
+
+/// The consumables that we need more of than what's in our inventory.
+fn requirements_shortfall(
+    requirements: &ConsumableCounts,
+    inventory: &Inventory,
+    throwaway_blocks: &[Item],
+) -> Vec<Consumable> {
+    if requirements.is_empty() {
+        return Vec::new();
+    }
+    let menu = &inventory.inventory_menu;
+    let available = ConsumableCounts::from_menu(menu).with_throwaway_blocks(menu, throwaway_blocks);
+    requirements.shortfall(&available)
 }
@@ -1,18 +1,24 @@
 use std::f32::consts::SQRT_2;
 
 use azalea_block::{BlockState, properties};
-use azalea_client::{SprintDirection, WalkDirection};
+use azalea_client::WalkDirection;
 use azalea_core::{
     direction::CardinalDirection,
     position::{BlockPos, Vec3},
 };
 
-use super::{Edge, ExecuteCtx, IsReachedCtx, MoveData, PathfinderCtx, default_is_reached};
-use crate::pathfinder::{astar, costs::*, rel_block_pos::RelBlockPos};
+use super::{
+    Edge, ExecuteCtx, IsReachedCtx, MoveData, PathfinderCtx, default_is_reached,
+    water::{WaterType, classify_water, classify_water_at},
+};
+use crate::pathfinder::{
+    astar, costs::*, rel_block_pos::RelBlockPos, world::is_block_state_passable,
+};
 
 pub fn basic_move(ctx: &mut PathfinderCtx, node: RelBlockPos) {
     forward_move(ctx, node);
     ascend_move(ctx, node);
+    high_ascend_move(ctx, node);
     descend_move(ctx, node);
     diagonal_move(ctx, node);
     descend_forward_1_move(ctx, node);
@@ -23,13 +29,15 @@ fn forward_move(ctx: &mut PathfinderCtx, pos: RelBlockPos) {
     for dir in CardinalDirection::iter() {
         let offset = RelBlockPos::new(dir.x(), 0, dir.z());
 
-        let mut cost = SPRINT_ONE_BLOCK_COST;
+        let mut cost =
+            (sprint_one_block_cost_on(ctx, pos) + sprint_one_block_cost_on(ctx, pos + offset)) / 2.;
 
         let break_cost = ctx.world.cost_for_standing(pos + offset, ctx.mining_cache);
         if break_cost == f32::INFINITY {
             continue;
         }
         cost += break_cost;
+        cost += ledge_penalty(ctx, pos + offset, dir.x(), dir.z());
 
         ctx.edges.push(Edge {
             movement: astar::Movement {
@@ -37,6 +45,7 @@ fn forward_move(ctx: &mut PathfinderCtx, pos: RelBlockPos) {
                 data: MoveData {
                     execute: &execute_forward_move,
                     is_reached: &default_is_reached,
+                    consumes: &[],
                 },
             },
             cost,
@@ -56,7 +65,90 @@ fn execute_forward_move(mut ctx: ExecuteCtx) {
 
     ctx.look_at(center);
     ctx.jump_if_in_water();
-    ctx.sprint(SprintDirection::Forward);
+
+    // ice is slippery, so let go of the movement keys early enough that we
+    // don't slide past the target
+    if is_standing_on_ice(&ctx) {
+        let remaining_distance = (center - ctx.position).horizontal_distance_squared().sqrt();
+        let speed = ctx.physics.velocity.horizontal_distance_squared().sqrt();
+        if remaining_distance < speed * ICE_SLIDE_TICKS {
+            ctx.walk(WalkDirection::None);
+            return;
+        }
+    }
+
+    ctx.sprint_unless_near_ledge();
+    ctx.sneak_if_near_ledge();
+}
+
+fn is_standing_on_ice(ctx: &ExecuteCtx) -> bool {
+    let below = ctx.get_block_state(BlockPos::from(ctx.position).down(1));
+    is_ice(azalea_registry::Block::from(below))
+}
+
+fn is_ice(block: azalea_registry::Block) -> bool {
+    matches!(
+        block,
+        azalea_registry::Block::Ice
+            | azalea_registry::Block::PackedIce
+            | azalea_registry::Block::BlueIce
+            | azalea_registry::Block::FrostedIce
+    )
+}
+
+/// The cost of sprinting one block while standing at `pos`, which depends on
+/// how slippery or sticky the block below us is.
+fn sprint_one_block_cost_on(ctx: &PathfinderCtx, pos: RelBlockPos) -> f32 {
+    let soul_speed = ctx.world.equipment().soul_speed;
+    match azalea_registry::Block::from(ctx.world.get_block_state(pos.down(1))) {
+        azalea_registry::Block::Ice | azalea_registry::Block::PackedIce => {
+            ICE_SPRINT_ONE_BLOCK_COST
+        }
+        azalea_registry::Block::BlueIce => BLUE_ICE_SPRINT_ONE_BLOCK_COST,
+        // we don't get any faster on frosted ice, since we don't want to rely on it
+        azalea_registry::Block::FrostedIce => SPRINT_ONE_BLOCK_COST + FROSTED_ICE_PENALTY,
+        azalea_registry::Block::SoulSand | azalea_registry::Block::SoulSoil if soul_speed > 0 => {
+            SPRINT_ONE_BLOCK_COST / soul_speed_multiplier(soul_speed)
+        }
+        azalea_registry::Block::SoulSand => SOUL_SAND_SPRINT_ONE_BLOCK_COST,
+        azalea_registry::Block::HoneyBlock => HONEY_BLOCK_SPRINT_ONE_BLOCK_COST,
+        azalea_registry::Block::SlimeBlock => SLIME_BLOCK_SPRINT_ONE_BLOCK_COST,
+        _ => SPRINT_ONE_BLOCK_COST,
+    }
+}
+
+/// The extra cost for ending up at the given position while travelling in the
+/// given direction, if it's next to a ledge that we could fall off of.
+fn ledge_penalty(ctx: &PathfinderCtx, pos: RelBlockPos, dx: i16, dz: i16) -> f32 {
+    if ctx.careful_mode_multiplier <= 0. || !ctx.world.has_ledge_beside(pos, dx, dz) {
+        return 0.;
+    }
+    LEDGE_PENALTY * ctx.careful_mode_multiplier
+}
+
+/// How long it takes to fall the given number of blocks.
+fn fall_cost(ctx: &PathfinderCtx, fall_distance: u32) -> f32 {
+    let fall_n_blocks_cost: &[f32] = if ctx.world.equipment().effects.slow_falling {
+        &*SLOW_FALL_N_BLOCKS_COST
+    } else {
+        &*FALL_N_BLOCKS_COST
+    };
+    fall_n_blocks_cost
+        .get(fall_distance as usize)
+        .copied()
+        // avoid panicking if we fall more than the size of the cost table
+        // probably not possible but just in case
+        .unwrap_or(f32::INFINITY)
+}
+
+/// The extra cost for falling to the given position, if we'd land on the tip of
+/// upward-pointing dripstone.
+fn dripstone_fall_penalty(ctx: &PathfinderCtx, pos: RelBlockPos) -> f32 {
+    if ctx.world.is_dripstone_tip(pos.down(1)) {
+        DRIPSTONE_FALL_PENALTY
+    } else {
+        0.
+    }
 }
 
 fn ascend_move(ctx: &mut PathfinderCtx, pos: RelBlockPos) {
@@ -113,6 +205,7 @@ fn ascend_move(ctx: &mut PathfinderCtx, pos: RelBlockPos) {
                 data: MoveData {
                     execute: &execute_ascend_move,
                     is_reached: &ascend_is_reached,
+                    consumes: &[],
                 },
             },
             cost,
@@ -189,6 +282,68 @@ fn execute_ascend_move(mut ctx: ExecuteCtx) {
         }
     }
 }
+/// Jumping up more than one block, which is only possible with Jump Boost.
+fn high_ascend_move(ctx: &mut PathfinderCtx, pos: RelBlockPos) {
+    let max_height = ctx.world.equipment().effects.max_ascend_height();
+    if max_height < 2 || !ctx.world.is_block_solid(pos.down(1)) {
+        return;
+    }
+
+    'dir: for dir in CardinalDirection::iter() {
+        for height in 2..=max_height {
+            let target = pos + RelBlockPos::new(dir.x(), height, dir.z());
+            if !ctx.world.is_standable(target) {
+                continue;
+            }
+
+            // make sure there's room above us for the whole jump
+            for y in 2..=height + 1 {
+                if !ctx.world.is_block_passable(pos.up(y)) {
+                    continue 'dir;
+                }
+            }
+
+            let cost = SPRINT_ONE_BLOCK_COST
+                + JUMP_PENALTY
+                + *JUMP_ONE_BLOCK_COST * height as f32
+                + ctx.world.damaging_surface_cost(target)
+                + ctx.world.risk_cost(target);
+
+            ctx.edges.push(Edge {
+                movement: astar::Movement {
+                    target,
+                    data: MoveData {
+                        execute: &execute_high_ascend_move,
+                        is_reached: &default_is_reached,
+                        consumes: &[],
+                    },
+                },
+                cost,
+            });
+            // we can't jump past the first block that we can stand on
+            continue 'dir;
+        }
+    }
+}
+fn execute_high_ascend_move(mut ctx: ExecuteCtx) {
+    let ExecuteCtx {
+        target,
+        start,
+        position,
+        physics,
+        ..
+    } = ctx;
+
+    ctx.look_at(target.center());
+    ctx.walk(WalkDirection::Forward);
+
+    // jump as soon as we're against the wall, and keep pressing forward so we get
+    // pushed onto the top of it
+    if BlockPos::from(position) == start && physics.on_ground() {
+        ctx.jump();
+    }
+}
+
 #[must_use]
 pub fn ascend_is_reached(
     IsReachedCtx {
@@ -228,7 +383,28 @@ fn descend_move(ctx: &mut PathfinderCtx, pos: RelBlockPos) {
         }
 
         let mut fall_distance = ctx.world.fall_distance(new_horizontal_position);
-        if fall_distance > 3 {
+        let lands_in_cobweb = lands_in_cobweb(ctx, new_horizontal_position, fall_distance);
+        if !lands_in_cobweb && !is_fall_acceptable(ctx, new_horizontal_position, fall_distance) {
+            // too far to fall onto the ground, but landing in water is fine
+            if let Some((surface, fall_distance)) =
+                water_landing(ctx, new_horizontal_position, fall_distance)
+            {
+                let cost = WALK_OFF_BLOCK_COST
+                    + fall_cost(ctx, fall_distance)
+                    + WATER_EXIT_COST
+                    + break_cost_1;
+                ctx.edges.push(Edge {
+                    movement: astar::Movement {
+                        target: surface,
+                        data: MoveData {
+                            execute: &execute_descend_move,
+                            is_reached: &water_landing_is_reached,
+                            consumes: &[],
+                        },
+                    },
+                    cost,
+                });
+            }
             continue;
         }
 
@@ -247,25 +423,23 @@ fn descend_move(ctx: &mut PathfinderCtx, pos: RelBlockPos) {
                 continue;
             }
         } else {
-            // check whether we can stand on the target position
+            // check whether we can stand on the target position. note that this also
+            // refuses to land on powder snow if we're not wearing leather boots.
             if !ctx.world.is_standable(new_position) {
                 continue;
             }
-            break_cost_2 = 0.;
+            // cost_for_standing would've included these
+            break_cost_2 =
+                ctx.world.damaging_surface_cost(new_position) + ctx.world.risk_cost(new_position);
         }
+        let cobweb_cost = if lands_in_cobweb { COBWEB_PENALTY } else { 0. };
 
         let cost = WALK_OFF_BLOCK_COST
-            + f32::max(
-                FALL_N_BLOCKS_COST
-                    .get(fall_distance as usize)
-                    .copied()
-                    // avoid panicking if we fall more than the size of FALL_N_BLOCKS_COST
-                    // probably not possible but just in case
-                    .unwrap_or(f32::INFINITY),
-                CENTER_AFTER_FALL_COST,
-            )
+            + f32::max(fall_cost(ctx, fall_distance), CENTER_AFTER_FALL_COST)
             + break_cost_1
-            + break_cost_2;
+            + break_cost_2
+            + cobweb_cost
+            + dripstone_fall_penalty(ctx, new_position);
 
         ctx.edges.push(Edge {
             movement: astar::Movement {
@@ -273,6 +447,7 @@ fn descend_move(ctx: &mut PathfinderCtx, pos: RelBlockPos) {
                 data: MoveData {
                     execute: &execute_descend_move,
                     is_reached: &descend_is_reached,
+                    consumes: &[],
                 },
             },
             cost,
@@ -287,8 +462,15 @@ fn execute_descend_move(mut ctx: ExecuteCtx) {
         ..
     } = ctx;
 
+    let is_long_fall = start.y - target.y > 1;
     for i in (0..=(start.y - target.y + 1)).rev() {
-        if ctx.mine_while_at_start(target.up(i)) {
+        let block = target.up(i);
+        if is_long_fall && block.y < start.y && is_block_state_passable(ctx.get_block_state(block))
+        {
+            // cobwebs that we're falling into break our fall, so don't break them
+            continue;
+        }
+        if ctx.mine_while_at_start(block) {
             return;
         }
     }
@@ -307,6 +489,17 @@ fn execute_descend_move(mut ctx: ExecuteCtx) {
         start_center.z + (center.z - start_center.z) * 1.5,
     );
 
+    // sneaking would stop us from walking off the edge, so only do it until
+    // we're close to it
+    let dir = Vec3::new(
+        (target.x - start.x).signum() as f64,
+        0.,
+        (target.z - start.z).signum() as f64,
+    );
+    if BlockPos::from(position) == start && (position - start_center).dot(dir) < 0.2 {
+        ctx.sneak_if_near_ledge();
+    }
+
     if BlockPos::from(position) != target || horizontal_distance_from_target > 0.25 {
         if horizontal_distance_from_start < 1.25 {
             // this basically just exists to avoid doing spins while we're falling
@@ -320,6 +513,89 @@ fn execute_descend_move(mut ctx: ExecuteCtx) {
         ctx.walk(WalkDirection::None);
     }
 }
+/// Whether we're willing to fall from `pos` onto whatever is below it,
+/// depending on how much damage we'd take.
+fn is_fall_acceptable(ctx: &PathfinderCtx, pos: RelBlockPos, fall_distance: u32) -> bool {
+    if fall_distance == u32::MAX {
+        // there's nothing below us
+        return false;
+    }
+    let landing_pos = pos.down(fall_distance as i32 + 1);
+    let landing_block = ctx.world.get_block_state(landing_pos);
+    ctx.world
+        .equipment()
+        .is_fall_acceptable(fall_distance, landing_block)
+}
+
+/// Whether we'd go through a cobweb near the end of a fall from `pos`, which
+/// resets how far we've fallen so we don't take any fall damage.
+fn lands_in_cobweb(ctx: &PathfinderCtx, pos: RelBlockPos, fall_distance: u32) -> bool {
+    if fall_distance == u32::MAX {
+        // there's nothing below us
+        return false;
+    }
+    // we only have to look at the blocks that are close enough to the ground that
+    // falling the rest of the way is safe
+    let max_fall_distance = ctx.world.equipment().max_fall_distance();
+    (0..=u32::min(max_fall_distance, fall_distance)).any(|distance_from_ground| {
+        let block_pos = pos.down((fall_distance - distance_from_ground) as i32);
+        azalea_registry::Block::from(ctx.world.get_block_state(block_pos))
+            == azalea_registry::Block::Cobweb
+    })
+}
+
+/// If there's enough water below `pos` for us to fall into without taking any
+/// damage, returns the top block of the water and how far we'd fall to get
+/// there.
+///
+/// `fall_distance` is the result of [`CachedWorld::fall_distance`] for `pos`,
+/// which goes through the water to the bottom.
+///
+/// [`CachedWorld::fall_distance`]: crate::pathfinder::world::CachedWorld::fall_distance
+fn water_landing(
+    ctx: &PathfinderCtx,
+    pos: RelBlockPos,
+    fall_distance: u32,
+) -> Option<(RelBlockPos, u32)> {
+    if fall_distance == u32::MAX {
+        // there's nothing below us
+        return None;
+    }
+    let is_water = |distance: u32| {
+        classify_water(ctx.world.get_block_state(pos.down(distance as i32))).is_some()
+    };
+    let surface_distance = (1..=fall_distance).find(|&distance| is_water(distance))?;
+    let surface = pos.down(surface_distance as i32);
+    if classify_water_at(ctx, surface) == Some(WaterType::Dangerous) {
+        return None;
+    }
+
+    let required_depth = if surface_distance > SHALLOW_WATER_MAX_FALL_DISTANCE {
+        2
+    } else {
+        1
+    };
+    let depth = (surface_distance..=fall_distance)
+        .take_while(|&distance| is_water(distance))
+        .count();
+    if depth < required_depth {
+        return None;
+    }
+
+    Some((surface, surface_distance))
+}
+
+/// We might sink below the surface of the water before we stop falling, which
+/// is fine since we'll be swimming from there anyway.
+fn water_landing_is_reached(
+    IsReachedCtx {
+        target, position, ..
+    }: IsReachedCtx,
+) -> bool {
+    let block_pos = BlockPos::from(position);
+    block_pos.x == target.x && block_pos.z == target.z && block_pos.y <= target.y
+}
+
 #[must_use]
 pub fn descend_is_reached(
     IsReachedCtx {
@@ -355,7 +631,10 @@ fn descend_forward_1_move(ctx: &mut PathfinderCtx, pos: RelBlockPos) {
         let gap_fall_distance = ctx.world.fall_distance(gap_horizontal_position);
         let fall_distance = ctx.world.fall_distance(new_horizontal_position);
 
-        if fall_distance == 0 || fall_distance > 3 || gap_fall_distance < fall_distance {
+        if fall_distance == 0
+            || !is_fall_acceptable(ctx, new_horizontal_position, fall_distance)
+            || gap_fall_distance < fall_distance
+        {
             continue;
         }
 
@@ -375,15 +654,10 @@ fn descend_forward_1_move(ctx: &mut PathfinderCtx, pos: RelBlockPos) {
 
         let cost = WALK_OFF_BLOCK_COST
             + WALK_ONE_BLOCK_COST
-            + f32::max(
-                FALL_N_BLOCKS_COST
-                    .get(fall_distance as usize)
-                    .copied()
-                    // avoid panicking if we fall more than the size of FALL_N_BLOCKS_COST
-                    // probably not possible but just in case
-                    .unwrap_or(f32::INFINITY),
-                CENTER_AFTER_FALL_COST,
-            );
+            + f32::max(fall_cost(ctx, fall_distance), CENTER_AFTER_FALL_COST)
+            + dripstone_fall_penalty(ctx, new_position)
+            + ctx.world.damaging_surface_cost(new_position)
+            + ctx.world.risk_cost(new_position);
 
         ctx.edges.push(Edge {
             movement: astar::Movement {
@@ -391,6 +665,7 @@ fn descend_forward_1_move(ctx: &mut PathfinderCtx, pos: RelBlockPos) {
                 data: MoveData {
                     execute: &execute_descend_move,
                     is_reached: &descend_is_reached,
+                    consumes: &[],
                 },
             },
             cost,
@@ -399,14 +674,19 @@ fn descend_forward_1_move(ctx: &mut PathfinderCtx, pos: RelBlockPos) {
 }
 
 fn diagonal_move(ctx: &mut PathfinderCtx, pos: RelBlockPos) {
+    if !ctx.move_settings.allow_diagonal {
+        return;
+    }
     for dir in CardinalDirection::iter() {
         let right = dir.right();
         let offset = RelBlockPos::new(dir.x() + right.x(), 0, dir.z() + right.z());
         let left_pos = RelBlockPos::new(pos.x + dir.x(), pos.y, pos.z + dir.z());
         let right_pos = RelBlockPos::new(pos.x + right.x(), pos.y, pos.z + right.z());
 
+        let sprint_one_block_cost =
+            (sprint_one_block_cost_on(ctx, pos) + sprint_one_block_cost_on(ctx, pos + offset)) / 2.;
         // +0.001 so it doesn't unnecessarily go diagonal sometimes
-        let mut cost = SPRINT_ONE_BLOCK_COST * SQRT_2 + 0.001;
+        let mut cost = sprint_one_block_cost * SQRT_2 + 0.001;
 
         let left_passable = ctx.world.is_passable(left_pos);
         let right_passable = ctx.world.is_passable(right_pos);
@@ -424,12 +704,17 @@ fn diagonal_move(ctx: &mut PathfinderCtx, pos: RelBlockPos) {
             continue;
         }
 
+        cost += ledge_penalty(ctx, pos + offset, offset.x, offset.z);
+        cost += ctx.world.damaging_surface_cost(pos + offset);
+        cost += ctx.world.risk_cost(pos + offset);
+
         ctx.edges.push(Edge {
             movement: astar::Movement {
                 target: pos + offset,
                 data: MoveData {
                     execute: &execute_diagonal_move,
                     is_reached: &default_is_reached,
+                    consumes: &[],
                 },
             },
             cost,
@@ -440,7 +725,7 @@ fn execute_diagonal_move(mut ctx: ExecuteCtx) {
     let target_center = ctx.target.center();
 
     ctx.look_at(target_center);
-    ctx.sprint(SprintDirection::Forward);
+    ctx.sprint_unless_near_ledge();
     ctx.jump_if_in_water();
 }
 
@@ -466,6 +751,7 @@ fn downward_move(ctx: &mut PathfinderCtx, pos: RelBlockPos) {
             data: MoveData {
                 execute: &execute_downward_move,
                 is_reached: &default_is_reached,
+                consumes: &[],
             },
         },
         cost,
@@ -0,0 +1,162 @@
+//! Moves for crossing lakes and oceans in a boat.
+//!
+//! Swimming is slow, so crossing a large body of water that way makes a path
+//! very expensive. If we have a boat, we can put it on the water at the shore,
+//! get in, paddle straight across to the other side, and get out there
+//! instead. The whole crossing is a single edge.
+//!
+//! The boat is left behind on the far shore.
+
+use azalea_client::{WalkDirection, inventory::SetSelectedHotbarSlotEvent};
+use azalea_core::{
+    direction::CardinalDirection,
+    position::{BlockPos, Vec3},
+};
+use azalea_inventory::Menu;
+
+use super::{Edge, ExecuteCtx, IsReachedCtx, MoveData, PathfinderCtx};
+use crate::pathfinder::{astar, costs::*, rel_block_pos::RelBlockPos, requirements::Consumable};
+
+/// The narrowest body of water that's worth getting into a boat for.
+pub const MIN_CROSSING_LENGTH: i16 = 16;
+/// The furthest that we'll look across the water for the other shore from a
+/// single node.
+pub const MAX_CROSSING_LENGTH: i16 = 256;
+/// How close to the far shore we have to be, in blocks, before we get out of
+/// the boat. Boats stop about this far from the block in front of them.
+pub(super) const DISMOUNT_DISTANCE: f64 = 1.5;
+/// How far from the shore we put the boat on the water, in blocks. It has to
+/// be far enough away that it doesn't run into us.
+pub(super) const BOAT_PLACE_DISTANCE: i32 = 2;
+
+pub fn boat_move(ctx: &mut PathfinderCtx, node: RelBlockPos) {
+    if !ctx.move_settings.allow_boats
+        || !ctx.world.equipment().consumables.has(Consumable::Boat)
+        // we have to get in from the shore
+        || ctx.world.is_block_water(node)
+        || ctx.world.is_block_water(node.down(1))
+    {
+        return;
+    }
+
+    for dir in CardinalDirection::iter() {
+        let offset = RelBlockPos::new(dir.x(), 0, dir.z());
+
+        // the boat goes straight across the surface until it gets to the other shore
+        let mut length = 1;
+        while length <= MAX_CROSSING_LENGTH && is_water_surface(ctx, node + offset * length) {
+            length += 1;
+        }
+        let far_shore = node + offset * length;
+        if length <= MIN_CROSSING_LENGTH
+            || length > MAX_CROSSING_LENGTH
+            || !is_shore(ctx, far_shore)
+        {
+            continue;
+        }
+
+        let cost = BOAT_MOUNT_COST + WATER_BOAT_ONE_BLOCK_COST * length as f32;
+
+        ctx.edges.push(Edge {
+            movement: astar::Movement {
+                target: far_shore,
+                data: MoveData {
+                    execute: &execute_boat_move,
+                    is_reached: &boat_is_reached,
+                    consumes: &[Consumable::Boat],
+                },
+            },
+            cost,
+        })
+    }
+}
+
+/// Whether a boat can float through this position, which means that there's
+/// water under it and nothing in the way above it.
+fn is_water_surface(ctx: &PathfinderCtx, pos: RelBlockPos) -> bool {
+    ctx.world.is_block_water(pos.down(1))
+        && !ctx.world.is_block_water(pos)
+        && ctx.world.is_passable(pos)
+}
+
+/// Whether we can get out of the boat onto this position.
+fn is_shore(ctx: &PathfinderCtx, pos: RelBlockPos) -> bool {
+    !ctx.world.is_block_water(pos.down(1))
+        && !ctx.world.is_block_water(pos)
+        && ctx.world.is_standable(pos)
+}
+
+fn execute_boat_move(mut ctx: ExecuteCtx) {
+    let target = ctx.target.center();
+    let remaining_distance = (target - ctx.position).horizontal_distance_squared().sqrt();
+
+    if ctx.is_riding {
+        ctx.look_at(target);
+        if remaining_distance < DISMOUNT_DISTANCE {
+            ctx.walk(WalkDirection::None);
+            ctx.dismount();
+        } else {
+            ctx.walk(WalkDirection::Forward);
+        }
+        return;
+    }
+    if remaining_distance < DISMOUNT_DISTANCE * 2. {
+        // we already got out at the other side, but we're not on the ground yet
+        ctx.look_at(target);
+        ctx.walk(WalkDirection::Forward);
+        return;
+    }
+
+    let dx = (ctx.target.x - ctx.start.x).signum();
+    let dz = (ctx.target.z - ctx.start.z).signum();
+    let water =
+        ctx.start.down(1) + BlockPos::new(dx * BOAT_PLACE_DISTANCE, 0, dz * BOAT_PLACE_DISTANCE);
+    // the top of a full water block is a bit lower than the top of the block
+    let surface = Vec3::new(
+        water.x as f64 + 0.5,
+        water.y as f64 + 8. / 9.,
+        water.z as f64 + 0.5,
+    );
+    place_and_mount_boat(&mut ctx, surface);
+}
+
+/// Put a boat down at `surface` and then get into it. Both are done by
+/// right-clicking the same spot, since the boat ends up where we're looking.
+pub(super) fn place_and_mount_boat(ctx: &mut ExecuteCtx, surface: Vec3) {
+    ctx.walk(WalkDirection::None);
+    // if we don't have a boat anymore, it's probably already been put down
+    if let Some(slot) = boat_hotbar_slot(&ctx.menu) {
+        ctx.set_selected_hotbar_slot_events
+            .write(SetSelectedHotbarSlotEvent {
+                entity: ctx.entity,
+                slot,
+            });
+    }
+
+    let is_already_looking = ctx.is_looking_at(surface);
+    if ctx.look_at_exact(surface) && is_already_looking {
+        ctx.interact_with_target();
+    }
+}
+
+fn boat_hotbar_slot(menu: &Menu) -> Option<u8> {
+    menu.slots()[menu.hotbar_slots_range()]
+        .iter()
+        .position(|item| Consumable::Boat.matches(item.kind()))
+        .map(|slot| slot as u8)
+}
+
+fn boat_is_reached(
+    IsReachedCtx {
+        target,
+        position,
+        physics,
+        is_riding,
+        ..
+    }: IsReachedCtx,
+) -> bool {
+    // getting out of the boat might not put us exactly on the target
+    !is_riding
+        && physics.on_ground()
+        && (target.center() - position).horizontal_distance_squared() < DISMOUNT_DISTANCE.powi(2)
+}
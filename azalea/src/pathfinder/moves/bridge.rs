@@ -0,0 +1,134 @@
+//! The move for placing a block to walk over a gap, which is only used when
+//! [`GotoOptions::allow_placing`] is true.
+//!
+//! Players usually sneak to the edge of the block they're standing on before
+//! placing against its side, but azalea can't sneak yet. Instead, we carefully
+//! walk up to the edge and stop once our eyes are past it, which is the only
+//! place where we can see the side of the block without falling off.
+//!
+//! [`GotoOptions::allow_placing`]: crate::pathfinder::GotoOptions::allow_placing
+
+use azalea_client::WalkDirection;
+use azalea_core::{
+    direction::CardinalDirection,
+    position::{BlockPos, Vec3},
+};
+
+use super::{Edge, ExecuteCtx, IsReachedCtx, MoveData, PathfinderCtx};
+use crate::pathfinder::{
+    astar, costs::*, rel_block_pos::RelBlockPos, requirements::Consumable,
+    world::is_block_state_standable,
+};
+
+/// How far past the edge of the block that we're standing on our position has
+/// to be before we can see its side. Our hitbox is 0.6 blocks wide, so we fall
+/// off if we go more than 0.3 past it.
+const MIN_DISTANCE_PAST_EDGE: f64 = 0.05;
+/// Roughly how much further than our current velocity we slide after letting
+/// go of the movement keys on a normal block.
+const SLIDE_MULTIPLIER: f64 = 1.2;
+
+pub fn bridge_move(ctx: &mut PathfinderCtx, node: RelBlockPos) {
+    let consumables = &ctx.world.equipment().consumables;
+    if !consumables.has(Consumable::ThrowawayBlock) {
+        return;
+    }
+
+    // if there's air under us then we must've placed it in an earlier move
+    let floor = node.down(1);
+    if !ctx.world.is_block_standable(floor) && !ctx.world.get_block_state(floor).is_air() {
+        return;
+    }
+
+    for dir in CardinalDirection::iter() {
+        let target = node + RelBlockPos::new(dir.x(), 0, dir.z());
+        if !ctx.world.is_passable(target) {
+            continue;
+        }
+        // we can only place in air, since things like water or tall grass would
+        // have to be dealt with first
+        if !ctx.world.get_block_state(target.down(1)).is_air() {
+            continue;
+        }
+
+        let cost = WALK_ONE_BLOCK_COST + BLOCK_PLACE_PENALTY;
+
+        ctx.edges.push(Edge {
+            movement: astar::Movement {
+                target,
+                data: MoveData {
+                    execute: &execute_bridge_move,
+                    is_reached: &bridge_is_reached,
+                    consumes: &[Consumable::ThrowawayBlock],
+                },
+            },
+            cost,
+        });
+    }
+}
+
+fn execute_bridge_move(mut ctx: ExecuteCtx) {
+    let support = ctx.start.down(1);
+    let new_block = ctx.target.down(1);
+
+    if is_block_state_standable(ctx.get_block_state(new_block)) {
+        ctx.look_at(ctx.target.center());
+        ctx.walk(WalkDirection::Forward);
+        return;
+    }
+
+    let dir = direction_vec(ctx.start, ctx.target);
+    let distance_past_edge = (ctx.position - ctx.start.center()).dot(dir) - 0.5;
+    let speed = ctx.physics.velocity.dot(dir);
+    let is_stopped = ctx.physics.velocity.horizontal_distance_squared() < 0.005 * 0.005;
+
+    if distance_past_edge + speed * SLIDE_MULTIPLIER < -0.1 {
+        // walk up to the edge, but let go early enough that we don't slide off
+        ctx.look_at(ctx.target.center());
+        ctx.walk(WalkDirection::Forward);
+        return;
+    }
+    if distance_past_edge < MIN_DISTANCE_PAST_EDGE {
+        // we stopped a bit too early, so tap forward to get past the edge
+        if is_stopped {
+            ctx.look_at(ctx.target.center());
+            ctx.walk(WalkDirection::Forward);
+        } else {
+            ctx.walk(WalkDirection::None);
+        }
+        return;
+    }
+    ctx.walk(WalkDirection::None);
+    if !is_stopped {
+        return;
+    }
+
+    // the middle of the side of the block we're standing on
+    let face = support.center() + dir * 0.5;
+    // if we ran out then the pathfinder will notice and find a path without
+    // placing
+    ctx.place_block_against(support, face);
+}
+
+/// Our position goes past the edge while we're placing the block, so wait
+/// until we're standing on the new block before moving on to the next node.
+fn bridge_is_reached(
+    IsReachedCtx {
+        target,
+        start,
+        position,
+        physics,
+        ..
+    }: IsReachedCtx,
+) -> bool {
+    let distance_past_edge = (position - start.center()).dot(direction_vec(start, target)) - 0.5;
+    BlockPos::from(position) == target && distance_past_edge > 0.3 && physics.on_ground()
+}
+
+fn direction_vec(start: BlockPos, target: BlockPos) -> Vec3 {
+    Vec3::new(
+        (target.x - start.x).signum() as f64,
+        0.,
+        (target.z - start.z).signum() as f64,
+    )
+}
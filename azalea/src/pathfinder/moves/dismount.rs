@@ -0,0 +1,47 @@
+//! The move for getting off of a boat, horse, or minecart before we start
+//! walking.
+//!
+//! This isn't a normal move since it's never generated by the successors
+//! function. Instead, it's added to the start of the path when we're riding
+//! something while the path is calculated.
+
+use azalea_client::WalkDirection;
+use azalea_core::position::BlockPos;
+
+use super::{ExecuteCtx, IsReachedCtx, MoveData};
+use crate::pathfinder::{astar, costs::WALK_ONE_BLOCK_COST};
+
+/// About how long it takes to get off and land on the ground.
+pub const DISMOUNT_COST: f32 = WALK_ONE_BLOCK_COST * 2.;
+
+/// The edge that makes us get off of whatever we're riding. `start` is the
+/// position that we're expecting to be dismounted at, which is where the rest
+/// of the path starts from.
+pub fn dismount_edge(start: BlockPos) -> astar::Edge<BlockPos, MoveData> {
+    astar::Edge {
+        movement: astar::Movement {
+            target: start,
+            data: MoveData {
+                execute: &execute_dismount_move,
+                is_reached: &dismount_is_reached,
+                consumes: &[],
+            },
+        },
+        cost: DISMOUNT_COST,
+    }
+}
+
+fn execute_dismount_move(mut ctx: ExecuteCtx) {
+    // keep pressing shift until the server tells us that we got off
+    ctx.walk(WalkDirection::None);
+    ctx.dismount();
+}
+
+fn dismount_is_reached(
+    IsReachedCtx {
+        is_riding, physics, ..
+    }: IsReachedCtx,
+) -> bool {
+    // wait until we've landed, so the next move doesn't start in the air
+    !is_riding && physics.on_ground()
+}
@@ -0,0 +1,83 @@
+//! The move for walking through a closed door, fence gate, or trapdoor by
+//! opening it first.
+//!
+//! Open doorways are already passable (see [`is_block_state_open_doorway`]), so
+//! this is only for the ones that are closed. Iron doors and trapdoors can't be
+//! opened by hand, so they're still treated like walls.
+//!
+//! [`is_block_state_open_doorway`]: crate::pathfinder::world::is_block_state_open_doorway
+
+use azalea_block::BlockState;
+use azalea_client::WalkDirection;
+use azalea_core::direction::CardinalDirection;
+
+use super::{Edge, ExecuteCtx, MoveData, PathfinderCtx, default_is_reached};
+use crate::pathfinder::{
+    astar,
+    costs::*,
+    rel_block_pos::RelBlockPos,
+    world::{is_block_state_open_doorway, is_block_state_openable},
+};
+
+pub fn door_move(ctx: &mut PathfinderCtx, node: RelBlockPos) {
+    for dir in CardinalDirection::iter() {
+        let target = node + RelBlockPos::new(dir.x(), 0, dir.z());
+
+        // checking the passable bitset first is much faster than getting the
+        // block states
+        let feet_is_passable = ctx.world.is_block_passable(target);
+        let head_is_passable = ctx.world.is_block_passable(target.up(1));
+        if feet_is_passable && head_is_passable {
+            continue;
+        }
+        if !feet_is_passable && !is_closed_door(ctx.world.get_block_state(target)) {
+            continue;
+        }
+        if !head_is_passable && !is_closed_door(ctx.world.get_block_state(target.up(1))) {
+            continue;
+        }
+        if !ctx.world.is_block_standable(target.down(1)) {
+            continue;
+        }
+
+        let cost = WALK_ONE_BLOCK_COST
+            + OPEN_DOOR_PENALTY
+            + ctx.world.damaging_surface_cost(target)
+            + ctx.world.risk_cost(target);
+
+        ctx.edges.push(Edge {
+            movement: astar::Movement {
+                target,
+                data: MoveData {
+                    execute: &execute_door_move,
+                    is_reached: &default_is_reached,
+                    consumes: &[],
+                },
+            },
+            cost,
+        });
+    }
+}
+
+fn is_closed_door(block: BlockState) -> bool {
+    is_block_state_openable(block) && !is_block_state_open_doorway(block)
+}
+
+fn execute_door_move(mut ctx: ExecuteCtx) {
+    // for doors, clicking either half opens both of them
+    for pos in [ctx.target, ctx.target.up(1)] {
+        if !is_closed_door(ctx.get_block_state(pos)) {
+            continue;
+        }
+
+        // stop and wait until the server tells us that it's open
+        ctx.walk(WalkDirection::None);
+        if ctx.look_at_exact(pos.center()) {
+            ctx.interact(pos);
+        }
+        return;
+    }
+
+    ctx.look_at(ctx.target.center());
+    ctx.walk(WalkDirection::Forward);
+}
@@ -0,0 +1,153 @@
+//! Moves for travelling along ice highways, like the long strips of blue ice
+//! that are commonly built in the nether.
+//!
+//! Boats go about 70 blocks per second on blue ice, so if we have one, a long
+//! straight strip is a single edge that's priced far below walking. We put the
+//! boat down at the start of the strip, get in, paddle along the middle of the
+//! strip, and get out on the last block before the strip ends. After that the
+//! normal moves take over again.
+//!
+//! Like with [water crossings](super::boat), the boat is left behind.
+
+use azalea_client::WalkDirection;
+use azalea_core::{
+    direction::CardinalDirection,
+    position::{BlockPos, Vec3},
+};
+
+use super::{
+    Edge, ExecuteCtx, IsReachedCtx, MoveData, PathfinderCtx,
+    boat::{BOAT_PLACE_DISTANCE, DISMOUNT_DISTANCE, place_and_mount_boat},
+    default_is_reached,
+};
+use crate::pathfinder::{astar, costs::*, rel_block_pos::RelBlockPos, requirements::Consumable};
+
+/// The shortest strip of ice that's worth getting into a boat for.
+pub const MIN_SEGMENT_LENGTH: i16 = 8;
+/// The furthest that we'll look ahead along a strip of ice from a single node.
+pub const MAX_SEGMENT_LENGTH: i16 = 256;
+/// How far ahead of us along the strip we aim the boat, in blocks. Aiming at a
+/// point on the middle of the strip instead of at the end of it pulls us back
+/// towards the middle if we drift to the side.
+const STEERING_DISTANCE: f64 = 8.;
+
+pub fn ice_highway_move(ctx: &mut PathfinderCtx, node: RelBlockPos) {
+    if !ctx.world.equipment().consumables.has(Consumable::Boat)
+        || !is_ice_highway_block(ctx, node.down(1))
+    {
+        return;
+    }
+
+    for dir in CardinalDirection::iter() {
+        let offset = RelBlockPos::new(dir.x(), 0, dir.z());
+
+        // find where the strip ends, so we can get out of the boat before the gap or
+        // obstruction
+        let mut length = 0;
+        let mut cost = BOAT_MOUNT_COST;
+        while length < MAX_SEGMENT_LENGTH {
+            let next = node + offset * (length + 1);
+            let Some(one_block_cost) = ice_boat_one_block_cost(ctx, next.down(1)) else {
+                break;
+            };
+            if !ctx.world.is_passable(next) {
+                break;
+            }
+            length += 1;
+            cost += one_block_cost;
+        }
+        if length < MIN_SEGMENT_LENGTH {
+            continue;
+        }
+
+        ctx.edges.push(Edge {
+            movement: astar::Movement {
+                target: node + offset * length,
+                data: MoveData {
+                    execute: &execute_ice_highway_move,
+                    is_reached: &ice_highway_is_reached,
+                    consumes: &[Consumable::Boat],
+                },
+            },
+            cost,
+        })
+    }
+}
+
+fn is_ice_highway_block(ctx: &PathfinderCtx, pos: RelBlockPos) -> bool {
+    ice_boat_one_block_cost(ctx, pos).is_some()
+}
+
+/// The cost of going one block in a boat on top of the block at `pos`, or
+/// `None` if it isn't part of an ice highway.
+fn ice_boat_one_block_cost(ctx: &PathfinderCtx, pos: RelBlockPos) -> Option<f32> {
+    match azalea_registry::Block::from(ctx.world.get_block_state(pos)) {
+        azalea_registry::Block::PackedIce => Some(ICE_BOAT_ONE_BLOCK_COST),
+        azalea_registry::Block::BlueIce => Some(BLUE_ICE_BOAT_ONE_BLOCK_COST),
+        _ => None,
+    }
+}
+
+fn execute_ice_highway_move(mut ctx: ExecuteCtx) {
+    let start = ctx.start.center();
+    let target = ctx.target.center();
+    let direction = Vec3::new(
+        (ctx.target.x - ctx.start.x).signum() as f64,
+        0.,
+        (ctx.target.z - ctx.start.z).signum() as f64,
+    );
+    let length = (target - start).horizontal_distance_squared().sqrt();
+    let progress = (ctx.position - start).dot(direction);
+    let remaining_distance = (target - ctx.position).horizontal_distance_squared().sqrt();
+
+    if ctx.is_riding {
+        // the boat moves us, so how far we moved in the last tick is how fast the boat
+        // is going
+        let speed = (ctx.position - ctx.physics.old_position)
+            .horizontal_distance_squared()
+            .sqrt();
+        // boats barely slow down on ice, and we stop as soon as we get out, so we get
+        // out before the next tick would take us too close to the end of the strip
+        if remaining_distance < speed + DISMOUNT_DISTANCE {
+            ctx.walk(WalkDirection::None);
+            ctx.dismount();
+            return;
+        }
+
+        // boats face wherever we're looking, so aim at the middle of the strip a bit
+        // ahead of us to stay on it
+        let aim = start + direction * (progress + STEERING_DISTANCE).min(length);
+        ctx.look_at(aim);
+        ctx.walk(WalkDirection::Forward);
+        return;
+    }
+
+    if progress > BOAT_PLACE_DISTANCE as f64 + 0.5 {
+        // we already got out of the boat, so walk the rest of the way. ice is
+        // slippery, so stop pressing forward early enough that we don't slide past
+        // the end of the strip.
+        ctx.look_at(target);
+        let speed = ctx.physics.velocity.horizontal_distance_squared().sqrt();
+        if remaining_distance < speed * ICE_SLIDE_TICKS {
+            ctx.walk(WalkDirection::None);
+        } else {
+            ctx.walk(WalkDirection::Forward);
+        }
+        return;
+    }
+
+    // boats land on top of the block that we're looking at
+    let ice = ctx.start.down(1)
+        + BlockPos::new(
+            direction.x as i32 * BOAT_PLACE_DISTANCE,
+            0,
+            direction.z as i32 * BOAT_PLACE_DISTANCE,
+        );
+    let surface = Vec3::new(ice.x as f64 + 0.5, ice.y as f64 + 1., ice.z as f64 + 0.5);
+    place_and_mount_boat(&mut ctx, surface);
+}
+
+fn ice_highway_is_reached(ctx: IsReachedCtx) -> bool {
+    // we aren't there until we've gotten out of the boat
+    !ctx.is_riding && default_is_reached(ctx)
+}
@@ -1,6 +1,15 @@
 pub mod basic;
+pub mod boat;
+pub mod bridge;
+pub mod dismount;
+pub mod door;
+pub mod ice_highway;
 pub mod parkour;
+pub mod pillar;
+pub mod powder_snow;
+pub mod unknown;
 pub mod water;
+pub mod water_elevator;
 
 use std::{
     fmt::{self, Debug},
@@ -9,23 +18,37 @@ use std::{
 
 use azalea_block::BlockState;
 use azalea_client::{
-    SprintDirection, StartSprintEvent, StartWalkEvent, WalkDirection,
-    inventory::SetSelectedHotbarSlotEvent, mining::StartMiningBlockEvent,
+    DismountEvent, SneakEvent, SprintDirection, StartSprintEvent, StartWalkEvent, WalkDirection,
+    interact::StartUseItemEvent, inventory::SetSelectedHotbarSlotEvent,
+    mining::StartMiningBlockEvent,
 };
 use azalea_core::position::{BlockPos, Vec3};
+use azalea_entity::{LookDirection, view_vector};
 use azalea_inventory::Menu;
+use azalea_protocol::packets::game::s_interact::InteractionHand;
 use azalea_world::Instance;
 use bevy_ecs::{entity::Entity, event::EventWriter};
 use parking_lot::RwLock;
 
+use self::water::SwimmingState;
 use super::{
     astar,
+    costs::{
+        BLOCK_BREAK_ADDITIONAL_PENALTY, CORNER_BRAKE_SPEED, FALLING_BLOCK_WAIT_TICKS,
+        INTERACT_COOLDOWN_TICKS, LEDGE_DROP_THRESHOLD, LEDGE_SNEAK_DISTANCE,
+        LEDGE_SNEAK_LOOKAHEAD_TICKS, LEDGE_WALK_SPEED, MIN_SPRINT_RUN_LENGTH, SPRINT_STOP_DISTANCE,
+    },
     custom_state::CustomPathfinderStateRef,
-    mining::MiningCache,
+    humanize::{HumanizeSettings, HumanizeState, rotate_towards},
+    mining::{MiningCache, is_falling_block},
     rel_block_pos::RelBlockPos,
-    world::{CachedWorld, is_block_state_passable},
+    requirements::Consumable,
+    settings::MoveSettings,
+    world::{CachedWorld, is_block_state_passable, passable_block_penalty},
+};
+use crate::{
+    JumpEvent, LookAtEvent, auto_tool::best_tool_in_hotbar_for_block, bot::direction_looking_at,
 };
-use crate::{JumpEvent, LookAtEvent, auto_tool::best_tool_in_hotbar_for_block};
 
 type Edge = astar::Edge<RelBlockPos, MoveData>;
 
@@ -35,6 +58,20 @@ pub fn default_move(ctx: &mut PathfinderCtx, node: RelBlockPos) {
     basic::basic_move(ctx, node);
     parkour::parkour_move(ctx, node);
     water::water_moves(ctx, node);
+    water_elevator::water_elevator_move(ctx, node);
+    powder_snow::powder_snow_climb_move(ctx, node);
+    door::door_move(ctx, node);
+    bridge::bridge_move(ctx, node);
+    pillar::pillar_move(ctx, node);
+    boat::boat_move(ctx, node);
+    unknown::unknown_terrain_move(ctx, node);
+}
+
+/// The same as [`default_move`], but it can also travel along long strips of
+/// packed or blue ice. See [`ice_highway`] for more details.
+pub fn ice_highway_move(ctx: &mut PathfinderCtx, node: RelBlockPos) {
+    default_move(ctx, node);
+    ice_highway::ice_highway_move(ctx, node);
 }
 
 #[derive(Clone)]
@@ -44,6 +81,9 @@ pub struct MoveData {
     pub execute: &'static (dyn Fn(ExecuteCtx) + Send + Sync),
     /// Whether we've reached the target.
     pub is_reached: &'static (dyn Fn(IsReachedCtx) -> bool + Send + Sync),
+    /// The items that this movement uses up. These are added together to get
+    /// the requirements for the whole path.
+    pub consumes: &'static [Consumable],
 }
 impl Debug for MoveData {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -53,17 +93,51 @@ impl Debug for MoveData {
     }
 }
 
-pub struct ExecuteCtx<'w1, 'w2, 'w3, 'w4, 'w5, 'w6, 'a> {
+pub struct ExecuteCtx<'w1, 'w2, 'w3, 'w4, 'w5, 'w6, 'w7, 'w8, 'w9, 'a> {
     pub entity: Entity,
     /// The node that we're trying to reach.
     pub target: BlockPos,
     /// The last node that we reached.
     pub start: BlockPos,
+    /// The nodes that come after [`Self::target`] in the path, up to
+    /// [`SPRINT_LOOKAHEAD_NODES`] of them. This is empty if the target is the
+    /// last node that we know about.
+    ///
+    /// [`SPRINT_LOOKAHEAD_NODES`]: crate::pathfinder::costs::SPRINT_LOOKAHEAD_NODES
+    pub upcoming: &'a [BlockPos],
     pub position: Vec3,
     pub physics: &'a azalea_entity::Physics,
     pub is_currently_mining: bool,
+    /// Whether we're riding an entity, like a boat or a horse.
+    pub is_riding: bool,
     pub instance: Arc<RwLock<Instance>>,
     pub menu: Menu,
+    /// See [`GotoOptions::allow_mining`].
+    ///
+    /// [`GotoOptions::allow_mining`]: crate::pathfinder::GotoOptions::allow_mining
+    pub allow_mining: bool,
+    /// See [`GotoOptions::throwaway_blocks`].
+    ///
+    /// [`GotoOptions::throwaway_blocks`]: crate::pathfinder::GotoOptions::throwaway_blocks
+    pub throwaway_blocks: &'a [azalea_registry::Item],
+    /// See [`GotoOptions::careful_mode_multiplier`].
+    ///
+    /// [`GotoOptions::careful_mode_multiplier`]: crate::pathfinder::GotoOptions::careful_mode_multiplier
+    pub careful_mode_multiplier: f32,
+    /// See [`PathfinderDefaults::sprint`].
+    ///
+    /// [`PathfinderDefaults::sprint`]: crate::pathfinder::settings::PathfinderDefaults::sprint
+    pub allow_sprint: bool,
+    pub look_direction: LookDirection,
+    pub eye_height: f64,
+    pub humanize: Option<&'a HumanizeSettings>,
+    pub humanize_state: Option<&'a mut HumanizeState>,
+    /// How many more ticks we have to wait before we can interact with a block
+    /// again. See [`Self::interact`].
+    pub interact_cooldown: &'a mut u32,
+    /// How many more ticks we wait for sand or gravel to land after mining the
+    /// block under it. See [`Self::mine_while_at_start`].
+    pub falling_block_wait: &'a mut u32,
 
     pub look_at_events: &'a mut EventWriter<'w1, LookAtEvent>,
     pub sprint_events: &'a mut EventWriter<'w2, StartSprintEvent>,
@@ -71,26 +145,42 @@ pub struct ExecuteCtx<'w1, 'w2, 'w3, 'w4, 'w5, 'w6, 'a> {
     pub jump_events: &'a mut EventWriter<'w4, JumpEvent>,
     pub start_mining_events: &'a mut EventWriter<'w5, StartMiningBlockEvent>,
     pub set_selected_hotbar_slot_events: &'a mut EventWriter<'w6, SetSelectedHotbarSlotEvent>,
+    pub dismount_events: &'a mut EventWriter<'w7, DismountEvent>,
+    pub start_use_item_events: &'a mut EventWriter<'w8, StartUseItemEvent>,
+    pub sneak_events: &'a mut EventWriter<'w9, SneakEvent>,
 }
 
-impl ExecuteCtx<'_, '_, '_, '_, '_, '_, '_> {
+impl ExecuteCtx<'_, '_, '_, '_, '_, '_, '_, '_, '_, '_> {
     pub fn look_at(&mut self, position: Vec3) {
-        self.look_at_events.write(LookAtEvent {
-            entity: self.entity,
-            position: Vec3 {
-                x: position.x,
-                // look forward
-                y: self.position.up(1.53).y,
-                z: position.z,
-            },
+        self.look_at_exact(Vec3 {
+            x: position.x,
+            // look forward
+            y: self.position.up(1.53).y,
+            z: position.z,
         });
     }
 
-    pub fn look_at_exact(&mut self, position: Vec3) {
+    /// Look at the given position. Returns whether we're now looking directly
+    /// at it, which is only false if
+    /// [`HumanizeSettings::max_rotation_per_tick`] is set and we're still
+    /// turning.
+    pub fn look_at_exact(&mut self, position: Vec3) -> bool {
+        let Some(max_rotation) = self.humanize.and_then(|h| h.max_rotation_per_tick) else {
+            self.look_at_events.write(LookAtEvent {
+                entity: self.entity,
+                position,
+            });
+            return true;
+        };
+
+        let eye_position = self.position.up(self.eye_height);
+        let target_direction = direction_looking_at(eye_position, position);
+        let new_direction = rotate_towards(self.look_direction, target_direction, max_rotation);
         self.look_at_events.write(LookAtEvent {
             entity: self.entity,
-            position,
+            position: eye_position + view_vector(new_direction),
         });
+        new_direction == target_direction
     }
 
     pub fn sprint(&mut self, direction: SprintDirection) {
@@ -113,6 +203,275 @@ impl ExecuteCtx<'_, '_, '_, '_, '_, '_, '_> {
         });
     }
 
+    pub fn dismount(&mut self) {
+        self.dismount_events.write(DismountEvent {
+            entity: self.entity,
+        });
+    }
+
+    /// Hold shift so we can't walk off the edge of the block that we're on.
+    ///
+    /// Moves have to call this every tick that they want to sneak, since we
+    /// stop sneaking before every tick of executing the path.
+    pub fn sneak(&mut self, sneaking: bool) {
+        self.sneak_events.write(SneakEvent {
+            entity: self.entity,
+            sneaking,
+        });
+    }
+
+    /// Right-click the block at the given position, like to open a door.
+    ///
+    /// Returns whether the interaction was sent, which is false if we already
+    /// interacted with a block recently and the server might not have
+    /// responded yet.
+    pub fn interact(&mut self, block: BlockPos) -> bool {
+        self.use_item(Some(block))
+    }
+
+    /// Right-click whatever we're looking at, like to put a boat on the water
+    /// or to get into it.
+    ///
+    /// This has the same cooldown as [`Self::interact`].
+    pub fn interact_with_target(&mut self) -> bool {
+        self.use_item(None)
+    }
+
+    fn use_item(&mut self, force_block: Option<BlockPos>) -> bool {
+        if *self.interact_cooldown > 0 {
+            return false;
+        }
+        *self.interact_cooldown = INTERACT_COOLDOWN_TICKS;
+        self.start_use_item_events.write(StartUseItemEvent {
+            entity: self.entity,
+            hand: InteractionHand::MainHand,
+            force_block,
+        });
+        true
+    }
+
+    /// Place one of our [`Self::throwaway_blocks`] against the given block, on
+    /// the side of it that `face` is on. Returns false if we don't have any
+    /// left in our hotbar.
+    ///
+    /// Our hit result is only updated after we turn, so this waits until we're
+    /// already looking at `face` or else the block could end up on the wrong
+    /// side.
+    pub fn place_block_against(&mut self, block: BlockPos, face: Vec3) -> bool {
+        let hotbar = &self.menu.slots()[self.menu.hotbar_slots_range()];
+        let Some(slot) = hotbar
+            .iter()
+            .position(|item| self.throwaway_blocks.contains(&item.kind()))
+        else {
+            return false;
+        };
+        self.set_selected_hotbar_slot_events
+            .write(SetSelectedHotbarSlotEvent {
+                entity: self.entity,
+                slot: slot as u8,
+            });
+
+        let is_already_looking = self.is_looking_at(face);
+        if self.look_at_exact(face) && is_already_looking {
+            self.interact(block);
+        }
+        true
+    }
+
+    /// Whether we're already looking at the given position, which means that
+    /// our hit result has caught up with where we're looking.
+    pub fn is_looking_at(&self, position: Vec3) -> bool {
+        let eye_position = self.position.up(self.eye_height);
+        view_vector(self.look_direction)
+            .dot(view_vector(direction_looking_at(eye_position, position)))
+            > 0.9999
+    }
+
+    /// Sprint forward if [`Self::should_sprint`] says so, unless the start or
+    /// target of this move is next to a ledge. In that case we only walk, and
+    /// we let go of the movement keys if we're already going fast so our
+    /// momentum doesn't carry us off the edge.
+    pub fn sprint_unless_near_ledge(&mut self) {
+        if self.careful_mode_multiplier <= 0. {
+            self.sprint_or_walk();
+            return;
+        }
+
+        let dx = (self.target.x - self.start.x).signum();
+        let dz = (self.target.z - self.start.z).signum();
+        if !self.has_ledge_beside(self.start, dx, dz) && !self.has_ledge_beside(self.target, dx, dz)
+        {
+            self.sprint_or_walk();
+            return;
+        }
+
+        // the faster we're going the earlier we have to stop pressing forward
+        let max_speed = LEDGE_WALK_SPEED / self.careful_mode_multiplier as f64;
+        if self.physics.velocity.horizontal_distance_squared() > max_speed.powi(2) {
+            self.walk(WalkDirection::None);
+        } else {
+            self.walk(WalkDirection::Forward);
+        }
+    }
+
+    /// Sneak if we're drifting sideways towards a drop next to the target of
+    /// this move that's too far to fall safely, so our momentum can't carry us
+    /// off the edge.
+    ///
+    /// Sneaking makes us a lot slower, so we don't do it when we're walking
+    /// down the middle of the path. We also never sneak on scaffolding, since
+    /// that would make us sink through it.
+    pub fn sneak_if_near_ledge(&mut self) {
+        let dx = (self.target.x - self.start.x).signum();
+        let dz = (self.target.z - self.start.z).signum();
+        let side = BlockPos::new(-dz, 0, dx);
+        let side_vec = Vec3::new(side.x as f64, 0., side.z as f64);
+
+        let offset = (self.position - self.target.center()).dot(side_vec);
+        let speed = self.physics.velocity.dot(side_vec);
+        let predicted_offset = offset + speed * LEDGE_SNEAK_LOOKAHEAD_TICKS;
+        if predicted_offset.abs() < LEDGE_SNEAK_DISTANCE {
+            return;
+        }
+
+        let is_on_scaffolding = [BlockPos::from(self.position).down(1), self.target.down(1)]
+            .into_iter()
+            .any(|pos| {
+                azalea_registry::Block::from(self.get_block_state(pos))
+                    == azalea_registry::Block::Scaffolding
+            });
+        if is_on_scaffolding {
+            return;
+        }
+
+        let side_pos = if predicted_offset > 0. {
+            self.target + side
+        } else {
+            self.target - side
+        };
+        if self.is_ledge(side_pos) {
+            self.sneak(true);
+        }
+    }
+
+    /// Go forward, and sprint if [`Self::should_sprint`] says so.
+    pub fn sprint_or_walk(&mut self) {
+        if self.should_sprint() {
+            self.sprint(SprintDirection::Forward);
+        } else {
+            self.walk(WalkDirection::Forward);
+        }
+    }
+
+    /// Whether we should sprint for this move instead of walking.
+    ///
+    /// We only sprint when the path keeps going in about the same direction for
+    /// at least [`MIN_SPRINT_RUN_LENGTH`] blocks, and we go back to walking
+    /// [`SPRINT_STOP_DISTANCE`] blocks before the end of that straight run.
+    /// The run ends at turns of 90 degrees or more, before descends that are
+    /// next to a drop, and at the end of the path, so we don't overshoot any
+    /// of them. We never sprint when a head hitter or a 1-wide bridge is
+    /// coming up.
+    pub fn should_sprint(&self) -> bool {
+        if !self.allow_sprint {
+            return false;
+        }
+
+        let direction =
+            |from: BlockPos, to: BlockPos| ((to.x - from.x).signum(), (to.z - from.z).signum());
+        let horizontal_distance =
+            |from: Vec3, to: Vec3| (to - from).horizontal_distance_squared().sqrt();
+
+        let (dx, dz) = direction(self.start, self.target);
+        if (dx, dz) == (0, 0) || self.is_risky_to_sprint_to(self.start, self.target) {
+            return false;
+        }
+        let mut run_length = horizontal_distance(self.start.center(), self.target.center());
+        let mut run_end = self.target;
+        for &node in self.upcoming {
+            let (next_dx, next_dz) = direction(run_end, node);
+            let is_sharp_turn = next_dx * dx + next_dz * dz <= 0;
+            let is_descend_next_to_drop =
+                node.y < run_end.y && self.has_ledge_beside(node, next_dx, next_dz);
+            if is_sharp_turn || is_descend_next_to_drop {
+                break;
+            }
+            if self.is_risky_to_sprint_to(run_end, node) {
+                return false;
+            }
+            run_length += horizontal_distance(run_end.center(), node.center());
+            run_end = node;
+        }
+
+        run_length >= MIN_SPRINT_RUN_LENGTH
+            && horizontal_distance(self.position, run_end.center()) > SPRINT_STOP_DISTANCE
+    }
+
+    /// Stop pressing forward if momentum from the last move is carrying us
+    /// sideways towards a drop next to where this move starts, like after a
+    /// sprint jump that lands right before a corner.
+    ///
+    /// Returns whether we're braking, in which case the move shouldn't press
+    /// any movement keys this tick.
+    pub fn brake_for_corner(&mut self) -> bool {
+        let dx = (self.target.x - self.start.x).signum();
+        let dz = (self.target.z - self.start.z).signum();
+        let side = BlockPos::new(-dz, 0, dx);
+        let side_vec = Vec3::new(side.x as f64, 0., side.z as f64);
+
+        let speed = self.physics.velocity.dot(side_vec);
+        if speed.abs() < CORNER_BRAKE_SPEED {
+            return false;
+        }
+        let side_pos = if speed > 0. {
+            self.start + side
+        } else {
+            self.start - side
+        };
+        if !self.is_ledge(side_pos) {
+            return false;
+        }
+        self.walk(WalkDirection::None);
+        true
+    }
+
+    /// Whether the move from `from` to `to` is a jump up with a block right
+    /// above our head, or goes onto a 1-wide bridge with drops on both sides.
+    fn is_risky_to_sprint_to(&self, from: BlockPos, to: BlockPos) -> bool {
+        let is_head_hitter =
+            to.y > from.y && !is_block_state_passable(self.get_block_state(from.up(2)));
+        let dx = (to.x - from.x).signum();
+        let dz = (to.z - from.z).signum();
+        let is_one_wide_bridge = self.is_ledge(to + BlockPos::new(-dz, 0, dx))
+            && self.is_ledge(to + BlockPos::new(dz, 0, -dx));
+        is_head_hitter || is_one_wide_bridge
+    }
+
+    /// Whether there's a drop to the side of the given position, relative to
+    /// the direction we're moving in.
+    ///
+    /// This is the same check as [`CachedWorld::has_ledge_beside`], but it's
+    /// done on the real world since we're executing the path.
+    pub fn has_ledge_beside(&self, pos: BlockPos, dx: i32, dz: i32) -> bool {
+        [BlockPos::new(-dz, 0, dx), BlockPos::new(dz, 0, -dx)]
+            .into_iter()
+            .any(|side| self.is_ledge(pos + side))
+    }
+
+    /// Whether we could walk into the given position and then fall further
+    /// than [`LEDGE_DROP_THRESHOLD`].
+    pub fn is_ledge(&self, pos: BlockPos) -> bool {
+        let instance = self.instance.read();
+        let is_passable = |pos: BlockPos| {
+            is_block_state_passable(instance.get_block_state(pos).unwrap_or_default())
+        };
+
+        if !is_passable(pos) || !is_passable(pos.up(1)) {
+            return false;
+        }
+        (1..=LEDGE_DROP_THRESHOLD as i32 + 1).all(|y| is_passable(pos.down(y)))
+    }
+
     pub fn jump_if_in_water(&mut self) {
         if self.physics.is_in_water() {
             self.jump();
@@ -126,7 +485,7 @@ impl ExecuteCtx<'_, '_, '_, '_, '_, '_, '_> {
             .read()
             .get_block_state(block)
             .unwrap_or_default();
-        if is_block_state_passable(block_state) {
+        if is_block_state_passable(block_state) && !self.is_worth_breaking(block_state) {
             // block is already passable, no need to mine it
             return false;
         }
@@ -134,6 +493,23 @@ impl ExecuteCtx<'_, '_, '_, '_, '_, '_, '_> {
         true
     }
 
+    /// Whether this block is passable but slows us down so much that breaking
+    /// it is faster, like a cobweb when we have shears or a sword.
+    ///
+    /// This should match the cost that the pathfinder used in
+    /// [`CachedWorld::cost_for_breaking_block`].
+    pub fn is_worth_breaking(&self, block_state: BlockState) -> bool {
+        if !self.allow_mining {
+            return false;
+        }
+        let penalty = passable_block_penalty(block_state);
+        if penalty == 0. {
+            return false;
+        }
+        let best_tool_result = best_tool_in_hotbar_for_block(block_state, &self.menu);
+        1. / best_tool_result.percentage_per_tick + BLOCK_BREAK_ADDITIONAL_PENALTY < penalty
+    }
+
     /// Mine the block at the given position. Returns whether the block is being
     /// mined.
     pub fn mine(&mut self, block: BlockPos) -> bool {
@@ -142,11 +518,23 @@ impl ExecuteCtx<'_, '_, '_, '_, '_, '_, '_> {
             .read()
             .get_block_state(block)
             .unwrap_or_default();
-        if is_block_state_passable(block_state) {
+        if is_block_state_passable(block_state) && !self.is_worth_breaking(block_state) {
             // block is already passable, no need to mine it
             return false;
         }
 
+        if is_falling_block(self.get_block_state(block.up(1))) {
+            *self.falling_block_wait = FALLING_BLOCK_WAIT_TICKS;
+        }
+        self.start_mining(block)
+    }
+
+    /// Mine the block at the given position, even if we could already move
+    /// through it. Returns whether the block is being mined.
+    ///
+    /// This is for blocks like kelp that slow us down.
+    pub fn start_mining(&mut self, block: BlockPos) -> bool {
+        let block_state = self.get_block_state(block);
         let best_tool_result = best_tool_in_hotbar_for_block(block_state, &self.menu);
 
         self.set_selected_hotbar_slot_events
@@ -155,10 +543,22 @@ impl ExecuteCtx<'_, '_, '_, '_, '_, '_, '_> {
                 slot: best_tool_result.index as u8,
             });
 
-        self.is_currently_mining = true;
-
         self.walk(WalkDirection::None);
-        self.look_at_exact(block.center());
+        let is_looking_at_block = self.look_at_exact(block.center());
+
+        if let (Some(humanize), Some(humanize_state)) = (self.humanize, &mut self.humanize_state) {
+            let is_new_break =
+                !self.is_currently_mining || humanize_state.last_break_pos != Some(block);
+            if is_new_break {
+                if !is_looking_at_block || !humanize_state.can_start_break(humanize) {
+                    // wait until we're allowed to start breaking it
+                    return true;
+                }
+                humanize_state.record_break_start(block);
+            }
+        }
+
+        self.is_currently_mining = true;
         self.start_mining_events.write(StartMiningBlockEvent {
             entity: self.entity,
             position: block,
@@ -169,6 +569,10 @@ impl ExecuteCtx<'_, '_, '_, '_, '_, '_, '_> {
 
     /// Mine the given block, but make sure the player is standing at the start
     /// of the current node first.
+    ///
+    /// If the block had sand or gravel on top of it, this also keeps us at the
+    /// start for a bit after it's broken, so whatever falls into the gap gets
+    /// mined too instead of landing on our head.
     pub fn mine_while_at_start(&mut self, block: BlockPos) -> bool {
         let horizontal_distance_from_start = (self.start.center() - self.position)
             .horizontal_distance_squared()
@@ -185,6 +589,9 @@ impl ExecuteCtx<'_, '_, '_, '_, '_, '_, '_> {
                 self.walk(WalkDirection::Forward);
             }
             true
+        } else if *self.falling_block_wait > 0 {
+            self.walk(WalkDirection::None);
+            true
         } else {
             false
         }
@@ -205,6 +612,8 @@ pub struct IsReachedCtx<'a> {
     pub start: BlockPos,
     pub position: Vec3,
     pub physics: &'a azalea_entity::Physics,
+    /// Whether we're riding an entity, like a boat or a horse.
+    pub is_riding: bool,
 }
 
 /// Returns whether the entity is at the node and should start going to the
@@ -231,6 +640,18 @@ pub struct PathfinderCtx<'a> {
     pub edges: &'a mut Vec<Edge>,
     pub world: &'a CachedWorld,
     pub mining_cache: &'a MiningCache,
+    /// See [`GotoOptions::careful_mode_multiplier`].
+    ///
+    /// [`GotoOptions::careful_mode_multiplier`]: crate::pathfinder::GotoOptions::careful_mode_multiplier
+    pub careful_mode_multiplier: f32,
+    /// See [`PathfinderDefaults::low_air_threshold`].
+    ///
+    /// [`PathfinderDefaults::low_air_threshold`]: crate::pathfinder::settings::PathfinderDefaults::low_air_threshold
+    pub low_air_threshold: i32,
+    /// Which moves we're allowed to use, and how much some of them cost.
+    pub move_settings: MoveSettings,
+    /// How long we've been swimming for when we got to the current node.
+    pub swimming: SwimmingState,
 
     pub custom_state: &'a CustomPathfinderStateRef,
 }
@@ -6,14 +6,28 @@ use super::{Edge, ExecuteCtx, IsReachedCtx, MoveData, PathfinderCtx};
 use crate::pathfinder::{astar, costs::*, rel_block_pos::RelBlockPos};
 
 pub fn parkour_move(ctx: &mut PathfinderCtx, node: RelBlockPos) {
+    if !ctx.move_settings.allow_parkour {
+        return;
+    }
     if !ctx.world.is_block_solid(node.down(1)) {
         // we can only parkour from solid blocks (not just standable blocks like slabs)
         return;
     }
+    if azalea_registry::Block::from(ctx.world.get_block_state(node.down(1)))
+        == azalea_registry::Block::SlimeBlock
+    {
+        // slime blocks slow us down too much to get a running start
+        return;
+    }
 
     parkour_forward_1_move(ctx, node);
+    parkour_head_hitter_move(ctx, node);
     parkour_forward_2_move(ctx, node);
     parkour_forward_3_move(ctx, node);
+    // jump boost lets us jump across wider gaps
+    for gap in 4..=ctx.world.equipment().effects.max_parkour_gap() {
+        parkour_forward_n_move(ctx, node, gap);
+    }
 }
 
 fn parkour_forward_1_move(ctx: &mut PathfinderCtx, pos: RelBlockPos) {
@@ -43,6 +57,10 @@ fn parkour_forward_1_move(ctx: &mut PathfinderCtx, pos: RelBlockPos) {
         if !ctx.world.is_block_passable((pos + gap_offset).up(2)) {
             continue;
         }
+        // jumping up onto a block needs the whole height of the jump
+        if ascend == 1 && has_head_hitter(ctx, pos + gap_offset) {
+            continue;
+        }
 
         // make sure there's not a block above us
         if !ctx.world.is_block_passable(pos.up(2)) {
@@ -61,6 +79,50 @@ fn parkour_forward_1_move(ctx: &mut PathfinderCtx, pos: RelBlockPos) {
                 data: MoveData {
                     execute: &execute_parkour_move,
                     is_reached: &parkour_is_reached,
+                    consumes: &[],
+                },
+            },
+            cost,
+        })
+    }
+}
+
+/// Jump over a 1 block gap while there's a ceiling right above our head.
+///
+/// The ceiling stops us from going more than 0.2 blocks up, but that also
+/// means we land sooner and keep all of our speed, so sprinting and jumping
+/// gets us across quickly.
+fn parkour_head_hitter_move(ctx: &mut PathfinderCtx, pos: RelBlockPos) {
+    // without a ceiling this is a normal jump
+    if ctx.world.is_block_passable(pos.up(2)) {
+        return;
+    }
+
+    for dir in CardinalDirection::iter() {
+        let gap_offset = RelBlockPos::new(dir.x(), 0, dir.z());
+        let offset = RelBlockPos::new(dir.x() * 2, 0, dir.z() * 2);
+
+        // make sure we actually have to jump
+        if ctx.world.is_block_solid((pos + gap_offset).down(1)) {
+            continue;
+        }
+        if !ctx.world.is_passable(pos + gap_offset) {
+            continue;
+        }
+        // we barely leave the ground, so we can't go up
+        if !ctx.world.is_standable(pos + offset) {
+            continue;
+        }
+
+        let cost = JUMP_PENALTY + SPRINT_ONE_BLOCK_COST * 2.;
+
+        ctx.edges.push(Edge {
+            movement: astar::Movement {
+                target: pos + offset,
+                data: MoveData {
+                    execute: &execute_head_hitter_move,
+                    is_reached: &parkour_is_reached,
+                    consumes: &[],
                 },
             },
             cost,
@@ -68,6 +130,14 @@ fn parkour_forward_1_move(ctx: &mut PathfinderCtx, pos: RelBlockPos) {
     }
 }
 
+/// Whether there's a block right above our head at the top of a jump through
+/// this position, which would cut the jump short.
+///
+/// We can still get across a 1 block gap like that, but not anything wider.
+fn has_head_hitter(ctx: &PathfinderCtx, pos: RelBlockPos) -> bool {
+    !ctx.world.is_block_passable(pos.up(3))
+}
+
 fn parkour_forward_2_move(ctx: &mut PathfinderCtx, pos: RelBlockPos) {
     'dir: for dir in CardinalDirection::iter() {
         let gap_1_offset = RelBlockPos::new(dir.x(), 0, dir.z());
@@ -97,6 +167,9 @@ fn parkour_forward_2_move(ctx: &mut PathfinderCtx, pos: RelBlockPos) {
             if !ctx.world.is_block_passable((pos + offset).up(2)) {
                 continue 'dir;
             }
+            if has_head_hitter(ctx, pos + offset) {
+                continue 'dir;
+            }
         }
         // make sure there's not a block above us
         if !ctx.world.is_block_passable(pos.up(2)) {
@@ -115,6 +188,7 @@ fn parkour_forward_2_move(ctx: &mut PathfinderCtx, pos: RelBlockPos) {
                 data: MoveData {
                     execute: &execute_parkour_move,
                     is_reached: &parkour_is_reached,
+                    consumes: &[],
                 },
             },
             cost,
@@ -149,6 +223,9 @@ fn parkour_forward_3_move(ctx: &mut PathfinderCtx, pos: RelBlockPos) {
             if !ctx.world.is_block_passable((pos + offset).up(2)) {
                 continue 'dir;
             }
+            if has_head_hitter(ctx, pos + offset) {
+                continue 'dir;
+            }
         }
         // make sure there's not a block above us
         if !ctx.world.is_block_passable(pos.up(2)) {
@@ -167,6 +244,57 @@ fn parkour_forward_3_move(ctx: &mut PathfinderCtx, pos: RelBlockPos) {
                 data: MoveData {
                     execute: &execute_parkour_move,
                     is_reached: &parkour_is_reached,
+                    consumes: &[],
+                },
+            },
+            cost,
+        })
+    }
+}
+
+fn parkour_forward_n_move(ctx: &mut PathfinderCtx, pos: RelBlockPos, gap: i32) {
+    'dir: for dir in CardinalDirection::iter() {
+        let offset = RelBlockPos::new(dir.x() * (gap as i16 + 1), 0, dir.z() * (gap as i16 + 1));
+
+        if !ctx.world.is_standable(pos + offset) {
+            continue;
+        };
+
+        for i in 1..=gap as i16 {
+            let gap_offset = RelBlockPos::new(dir.x() * i, 0, dir.z() * i);
+            // make sure we actually have to jump
+            if ctx.world.is_block_solid((pos + gap_offset).down(1)) {
+                continue 'dir;
+            }
+            // make sure we have space to jump
+            if !ctx.world.is_passable(pos + gap_offset) {
+                continue 'dir;
+            }
+            if !ctx.world.is_block_passable((pos + gap_offset).up(2)) {
+                continue 'dir;
+            }
+            if has_head_hitter(ctx, pos + gap_offset) {
+                continue 'dir;
+            }
+        }
+        // make sure there's not a block above us
+        if !ctx.world.is_block_passable(pos.up(2)) {
+            continue;
+        }
+        // make sure there's not a block above the target
+        if !ctx.world.is_block_passable((pos + offset).up(2)) {
+            continue;
+        }
+
+        let cost = JUMP_PENALTY + SPRINT_ONE_BLOCK_COST * (gap + 1) as f32 + CENTER_AFTER_FALL_COST;
+
+        ctx.edges.push(Edge {
+            movement: astar::Movement {
+                target: pos + offset,
+                data: MoveData {
+                    execute: &execute_parkour_move,
+                    is_reached: &parkour_is_reached,
+                    consumes: &[],
                 },
             },
             cost,
@@ -189,6 +317,12 @@ fn execute_parkour_move(mut ctx: ExecuteCtx) {
 
     let ascend: i32 = target.y - start.y;
 
+    let is_at_start_block = BlockPos::from(position) == start;
+    if is_at_start_block && ctx.brake_for_corner() {
+        ctx.look_at(target_center);
+        return;
+    }
+
     if jump_distance >= 4 || (ascend > 0 && jump_distance >= 3) {
         // 3 block gap OR 2 block gap with ascend
         ctx.sprint(SprintDirection::Forward);
@@ -201,7 +335,6 @@ fn execute_parkour_move(mut ctx: ExecuteCtx) {
     let dir = BlockPos::new(x_dir, 0, z_dir);
     let jump_at_pos = start + dir;
 
-    let is_at_start_block = BlockPos::from(position) == start;
     let is_at_jump_block = BlockPos::from(position) == jump_at_pos;
 
     let required_distance_from_center = if jump_distance <= 2 {
@@ -234,6 +367,28 @@ fn execute_parkour_move(mut ctx: ExecuteCtx) {
     }
 }
 
+fn execute_head_hitter_move(mut ctx: ExecuteCtx) {
+    let ExecuteCtx {
+        position,
+        target,
+        start,
+        physics,
+        ..
+    } = ctx;
+
+    ctx.look_at(target.center());
+    ctx.sprint(SprintDirection::Forward);
+
+    // jump once we're past the edge, since we'll only be in the air for a few
+    // ticks
+    let x_dir = (target.x - start.x).clamp(-1, 1);
+    let z_dir = (target.z - start.z).clamp(-1, 1);
+    let jump_at_pos = start + BlockPos::new(x_dir, 0, z_dir);
+    if BlockPos::from(position) == jump_at_pos && physics.on_ground() {
+        ctx.jump();
+    }
+}
+
 #[must_use]
 pub fn parkour_is_reached(
     IsReachedCtx {
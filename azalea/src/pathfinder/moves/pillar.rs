@@ -0,0 +1,105 @@
+//! The move for going straight up by jumping and placing a block under
+//! ourselves, which is only used when [`GotoOptions::allow_placing`] is true.
+//!
+//! [`GotoOptions::allow_placing`]: crate::pathfinder::GotoOptions::allow_placing
+
+use azalea_client::WalkDirection;
+use azalea_core::position::{BlockPos, Vec3};
+
+use super::{Edge, ExecuteCtx, IsReachedCtx, MoveData, PathfinderCtx};
+use crate::pathfinder::{
+    astar, costs::*, rel_block_pos::RelBlockPos, requirements::Consumable,
+    world::is_block_state_standable,
+};
+
+pub fn pillar_move(ctx: &mut PathfinderCtx, node: RelBlockPos) {
+    let consumables = &ctx.world.equipment().consumables;
+    if !consumables.has(Consumable::ThrowawayBlock) {
+        return;
+    }
+
+    // if there's air under us then we must've placed it in an earlier move
+    let floor = node.down(1);
+    if !ctx.world.is_block_standable(floor) && !ctx.world.get_block_state(floor).is_air() {
+        return;
+    }
+    // the block gets placed where our feet are now
+    if !ctx.world.get_block_state(node).is_air() {
+        return;
+    }
+
+    let target = node.up(1);
+    // don't pillar into the ceiling
+    if !ctx.world.is_block_passable(target.up(1)) {
+        return;
+    }
+    // sand or gravel above us could fall on our head if anything updates it, and
+    // we wouldn't be able to get out of the way
+    if ctx
+        .mining_cache
+        .is_falling_block(ctx.world.get_block_state(target.up(2)))
+    {
+        return;
+    }
+
+    let cost = *JUMP_ONE_BLOCK_COST + BLOCK_PLACE_PENALTY;
+
+    ctx.edges.push(Edge {
+        movement: astar::Movement {
+            target,
+            data: MoveData {
+                execute: &execute_pillar_move,
+                is_reached: &pillar_is_reached,
+                consumes: &[Consumable::ThrowawayBlock],
+            },
+        },
+        cost,
+    });
+}
+
+fn execute_pillar_move(mut ctx: ExecuteCtx) {
+    let support = ctx.start.down(1);
+    let new_block = ctx.start;
+
+    let center = ctx.start.center();
+    if (center - ctx.position).horizontal_distance_squared() > 0.2 * 0.2 {
+        // get into the middle of the block first so we don't land on the edge
+        ctx.look_at(center);
+        ctx.walk(WalkDirection::Forward);
+        return;
+    }
+    ctx.walk(WalkDirection::None);
+
+    if is_block_state_standable(ctx.get_block_state(new_block)) {
+        // wait until we land on it
+        return;
+    }
+
+    // the top of the block we're standing on
+    let face = support.center() + Vec3::new(0., 0.5, 0.);
+    if ctx.position.y < new_block.y as f64 + 1. {
+        // look down while we're still on the ground so we're already looking at
+        // it when we're high enough to place
+        ctx.look_at_exact(face);
+        if ctx.physics.on_ground() {
+            ctx.jump();
+        }
+        return;
+    }
+    // our feet are above where the block goes now, so place it before we fall back
+    // down
+    ctx.place_block_against(support, face);
+}
+
+/// We're already above the target while we're jumping, so wait until we've
+/// landed on the block that we placed.
+fn pillar_is_reached(
+    IsReachedCtx {
+        target,
+        position,
+        physics,
+        ..
+    }: IsReachedCtx,
+) -> bool {
+    BlockPos::from(position) == target && physics.on_ground()
+}
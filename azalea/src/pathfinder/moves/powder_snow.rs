@@ -0,0 +1,82 @@
+//! Moves for climbing up through powder snow.
+//!
+//! Without leather boots we sink into powder snow and start freezing, so it's
+//! treated as a wall (see [`is_block_state_passable`]). With leather boots on,
+//! holding jump inside of it makes us go up like we're on a ladder, so a
+//! column of it can be climbed to get onto whatever is at the top.
+//!
+//! [`is_block_state_passable`]: crate::pathfinder::world::is_block_state_passable
+
+use azalea_client::WalkDirection;
+use azalea_core::direction::CardinalDirection;
+
+use super::{Edge, ExecuteCtx, MoveData, PathfinderCtx, default_is_reached};
+use crate::pathfinder::{astar, costs::*, rel_block_pos::RelBlockPos};
+
+/// The furthest that we look up a column of powder snow for the top of it.
+pub const MAX_CLIMB_HEIGHT: i32 = 64;
+
+pub fn powder_snow_climb_move(ctx: &mut PathfinderCtx, node: RelBlockPos) {
+    if !ctx.world.equipment().has_leather_boots {
+        return;
+    }
+
+    for dir in CardinalDirection::iter() {
+        let column = node + RelBlockPos::new(dir.x(), 0, dir.z());
+        if !is_powder_snow(ctx, column) {
+            continue;
+        }
+
+        let mut top = column;
+        while top.y - column.y < MAX_CLIMB_HEIGHT && is_powder_snow(ctx, top.up(1)) {
+            top = top.up(1);
+        }
+        // a single block of powder snow can just be jumped onto
+        if top.y == column.y {
+            continue;
+        }
+
+        let target = top.up(1);
+        if !ctx.world.is_passable(target) {
+            continue;
+        }
+
+        let cost = WALK_ONE_BLOCK_COST
+            + POWDER_SNOW_CLIMB_ONE_BLOCK_COST * (target.y - node.y) as f32
+            + JUMP_PENALTY;
+
+        ctx.edges.push(Edge {
+            movement: astar::Movement {
+                target,
+                data: MoveData {
+                    execute: &execute_powder_snow_climb_move,
+                    is_reached: &default_is_reached,
+                    consumes: &[],
+                },
+            },
+            cost,
+        });
+    }
+}
+
+fn is_powder_snow(ctx: &PathfinderCtx, pos: RelBlockPos) -> bool {
+    azalea_registry::Block::from(ctx.world.get_block_state(pos))
+        == azalea_registry::Block::PowderSnow
+}
+
+fn execute_powder_snow_climb_move(mut ctx: ExecuteCtx) {
+    let center = ctx.target.center();
+
+    // walk into the column and stay in the middle of it
+    let offset = center - ctx.position;
+    if offset.horizontal_distance_squared() > 0.2 * 0.2 {
+        ctx.look_at(center);
+        ctx.walk(WalkDirection::Forward);
+    } else {
+        ctx.walk(WalkDirection::None);
+    }
+
+    if ctx.position.y < ctx.target.y as f64 {
+        ctx.jump();
+    }
+}
@@ -0,0 +1,60 @@
+//! Moves through chunks that aren't loaded.
+//!
+//! When the goal is further away than our render distance, we don't know what
+//! the terrain between us and it is like. Instead of treating it like a wall,
+//! we assume that it's flat and walkable (but expensive, see
+//! [`UNKNOWN_TERRAIN_PENALTY`]), so the search can still head towards the
+//! goal.
+//!
+//! We can't come back out of unknown terrain once we're in it, since that
+//! would let paths go around things like walls through chunks that might not
+//! even have a floor. So these moves only help when the goal is somewhere that
+//! isn't loaded.
+//!
+//! Paths are always cut off before the first one of these moves, and then we
+//! wait at the edge of the terrain that we know about until the chunk loads.
+//! So these are never actually executed.
+
+use azalea_client::WalkDirection;
+use azalea_core::direction::CardinalDirection;
+
+use super::{Edge, ExecuteCtx, MoveData, PathfinderCtx, default_is_reached};
+use crate::pathfinder::{astar, costs::*, rel_block_pos::RelBlockPos};
+
+/// How far from where the search started (horizontally, in blocks) we can go
+/// into unknown terrain.
+///
+/// Unknown terrain goes on forever, so without this a search for a goal that
+/// we can't get to would never run out of nodes.
+pub const MAX_UNKNOWN_TERRAIN_DISTANCE: i16 = 64;
+
+pub fn unknown_terrain_move(ctx: &mut PathfinderCtx, node: RelBlockPos) {
+    for dir in CardinalDirection::iter() {
+        let target = node + RelBlockPos::new(dir.x(), 0, dir.z());
+        // positions are relative to where the search started
+        if target.x.abs().max(target.z.abs()) > MAX_UNKNOWN_TERRAIN_DISTANCE {
+            continue;
+        }
+        if !ctx.world.is_block_unknown(target) {
+            continue;
+        }
+
+        ctx.edges.push(Edge {
+            movement: astar::Movement {
+                target,
+                data: MoveData {
+                    execute: &execute_unknown_terrain_move,
+                    is_reached: &default_is_reached,
+                    consumes: &[],
+                },
+            },
+            cost: WALK_ONE_BLOCK_COST + UNKNOWN_TERRAIN_PENALTY,
+        });
+    }
+}
+
+fn execute_unknown_terrain_move(mut ctx: ExecuteCtx) {
+    // the path should've been cut off before this, so don't walk into whatever
+    // might be there
+    ctx.walk(WalkDirection::None);
+}
@@ -1,9 +1,37 @@
-use azalea_block::BlockState;
-use azalea_client::WalkDirection;
-use azalea_core::direction::CardinalDirection;
+use std::collections::{HashSet, VecDeque};
 
-use super::{Edge, ExecuteCtx, MoveData, PathfinderCtx, default_is_reached};
-use crate::pathfinder::{astar, costs::*, rel_block_pos::RelBlockPos};
+use azalea_block::{BlockState, properties};
+use azalea_client::{SprintDirection, WalkDirection};
+use azalea_core::{
+    direction::CardinalDirection,
+    position::{BlockPos, Vec3},
+};
+
+use super::{
+    Edge, ExecuteCtx, IsReachedCtx, MoveData, PathfinderCtx, default_is_reached,
+    water_elevator::MAX_SHAFT_HEIGHT,
+};
+use crate::pathfinder::{
+    astar,
+    costs::*,
+    rel_block_pos::RelBlockPos,
+    world::{CachedWorld, PathEquipment},
+};
+
+/// How many moves in a row we have to be fully underwater for before we start
+/// sprint swimming.
+pub const SPRINT_SWIM_AFTER_MOVES: u32 = 3;
+/// The most air that a player can have, in ticks.
+pub const MAX_AIR_SUPPLY: i32 = 300;
+/// How long the Water Breathing from a turtle shell lasts after we go
+/// underwater, in ticks.
+pub const TURTLE_HELMET_BREATHING_TICKS: u32 = 200;
+/// The default for [`PathfinderDefaults::low_air_threshold`].
+///
+/// [`PathfinderDefaults::low_air_threshold`]: crate::pathfinder::settings::PathfinderDefaults::low_air_threshold
+pub const LOW_AIR_THRESHOLD: i32 = 100;
+/// How far [`has_nearby_air_access`] looks for air when swimming sideways.
+pub const AIR_POCKET_SEARCH_DISTANCE: i32 = 3;
 
 /// Types of water navigation scenarios
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -24,11 +52,12 @@ pub fn classify_water(block_state: BlockState) -> Option<WaterType> {
     
     // Direct water blocks
     if registry_block == azalea_registry::Block::Water {
-        // Check if it's a source block (level 0) or flowing
-        match block_state.property::<azalea_block::properties::WaterLevel>() {
-            Some(azalea_block::properties::WaterLevel::_0) => Some(WaterType::StillWater),
-            Some(_) => Some(WaterType::FlowingWater),
-            None => Some(WaterType::StillWater), // Default to still if no level property
+        // only source blocks are still. falling water (levels 8 and up) is a full
+        // block, but it's still flowing
+        if block_state.property::<properties::WaterLevel>() == Some(properties::WaterLevel::_0) {
+            Some(WaterType::StillWater)
+        } else {
+            Some(WaterType::FlowingWater)
         }
     } else if is_aquatic_plant(registry_block) {
         // All aquatic plants (seagrass, kelp, sea pickles) are treated as still water
@@ -56,27 +85,116 @@ pub fn is_water_navigable(water_type: WaterType) -> bool {
 
 /// Check if there are dangerous blocks adjacent to this water position
 pub fn is_water_safe(ctx: &PathfinderCtx, pos: RelBlockPos) -> bool {
-    // Check for lava adjacent to water
-    for dir in CardinalDirection::iter() {
-        let adjacent_pos = pos + RelBlockPos::new(dir.x(), 0, dir.z());
-        let block_state = ctx.world.get_block_state(adjacent_pos);
-        let registry_block = azalea_registry::Block::from(block_state);
-        
-        if registry_block == azalea_registry::Block::Lava {
-            return false;
+    !is_near_lava(ctx, pos)
+}
+
+/// Like [`classify_water`], but also looks at the blocks around `pos` and
+/// returns [`WaterType::Dangerous`] if swimming there would hurt us.
+///
+/// That's water within a block of lava (including diagonally), water next to
+/// fire, and water that's only one block deep on top of a magma block, which
+/// Frost Walker protects us from.
+pub fn classify_water_at(ctx: &PathfinderCtx, pos: RelBlockPos) -> Option<WaterType> {
+    let water_type = classify_water(ctx.world.get_block_state(pos))?;
+    if is_near_lava(ctx, pos) || is_next_to_fire(ctx, pos) || is_shallow_over_magma(ctx, pos) {
+        return Some(WaterType::Dangerous);
+    }
+    Some(water_type)
+}
+
+fn block_at(ctx: &PathfinderCtx, pos: RelBlockPos) -> azalea_registry::Block {
+    azalea_registry::Block::from(ctx.world.get_block_state(pos))
+}
+
+fn is_near_lava(ctx: &PathfinderCtx, pos: RelBlockPos) -> bool {
+    for x in -1..=1 {
+        for y in -1..=1 {
+            for z in -1..=1 {
+                if block_at(ctx, pos + RelBlockPos::new(x, y, z)) == azalea_registry::Block::Lava {
+                    return true;
+                }
+            }
         }
     }
-    
-    // Check above and below for lava
-    let up_block = ctx.world.get_block_state(pos.up(1));
-    let down_block = ctx.world.get_block_state(pos.down(1));
-    
-    if azalea_registry::Block::from(up_block) == azalea_registry::Block::Lava
-        || azalea_registry::Block::from(down_block) == azalea_registry::Block::Lava {
-        return false;
+    false
+}
+
+fn is_next_to_fire(ctx: &PathfinderCtx, pos: RelBlockPos) -> bool {
+    let is_fire = |pos| {
+        matches!(
+            block_at(ctx, pos),
+            azalea_registry::Block::Fire | azalea_registry::Block::SoulFire
+        )
+    };
+    // fire can't be in the water, but it can be on the blocks around it
+    is_fire(pos.up(1))
+        || CardinalDirection::iter().any(|dir| is_fire(pos + RelBlockPos::new(dir.x(), 0, dir.z())))
+}
+
+fn is_shallow_over_magma(ctx: &PathfinderCtx, pos: RelBlockPos) -> bool {
+    block_at(ctx, pos.down(1)) == azalea_registry::Block::MagmaBlock
+        && classify_water(ctx.world.get_block_state(pos.up(1))).is_none()
+        && ctx.world.equipment().frost_walker == 0
+}
+
+/// How much water is in this block, from 1 for the thinnest flowing water to 8
+/// for a full block. Returns `None` if there's no water here.
+fn water_amount(block_state: BlockState) -> Option<i32> {
+    if azalea_registry::Block::from(block_state) == azalea_registry::Block::Water {
+        let level = block_state
+            .property::<properties::WaterLevel>()
+            .map(|level| level as i32)
+            .unwrap_or_default();
+        // levels 8 and above are falling water, which is always full
+        Some(if level >= 8 { 8 } else { 8 - level })
+    } else if classify_water(block_state).is_some() {
+        Some(8)
+    } else {
+        None
     }
-    
-    true
+}
+
+/// The horizontal direction that the water at this position is flowing in, if
+/// any.
+///
+/// Water gets shallower as it spreads out, so it flows towards the neighbors
+/// that have less water in them. This is the same as how Minecraft pushes
+/// entities that are in water, but rounded to the nearest cardinal direction.
+pub fn flow_direction(ctx: &PathfinderCtx, pos: RelBlockPos) -> Option<CardinalDirection> {
+    let block_state = ctx.world.get_block_state(pos);
+    let amount = water_amount(block_state)?;
+    if block_state
+        .property::<properties::WaterLevel>()
+        .is_some_and(|level| level as i32 >= 8)
+    {
+        // falling water only flows down
+        return None;
+    }
+
+    let (mut x_flow, mut z_flow) = (0, 0);
+    for dir in CardinalDirection::iter() {
+        let neighbor_pos = pos + RelBlockPos::new(dir.x(), 0, dir.z());
+        let difference = match water_amount(ctx.world.get_block_state(neighbor_pos)) {
+            Some(neighbor_amount) => amount - neighbor_amount,
+            None if ctx.world.is_block_passable(neighbor_pos) => {
+                // water only pours into an empty space if there's more water below it
+                let Some(below_amount) =
+                    water_amount(ctx.world.get_block_state(neighbor_pos.down(1)))
+                else {
+                    continue;
+                };
+                amount - (below_amount - 8)
+            }
+            None => continue,
+        };
+        x_flow += dir.x() as i32 * difference;
+        z_flow += dir.z() as i32 * difference;
+    }
+
+    if x_flow == 0 && z_flow == 0 {
+        return None;
+    }
+    CardinalDirection::iter().max_by_key(|dir| dir.x() as i32 * x_flow + dir.z() as i32 * z_flow)
 }
 
 /// Add water traversal moves to the pathfinding context
@@ -88,6 +206,40 @@ pub fn water_moves(ctx: &mut PathfinderCtx, node: RelBlockPos) {
     
     // Water entry from land
     water_entry_moves(ctx, node);
+
+    bubble_column_moves(ctx, node);
+
+    frost_walker_move(ctx, node);
+}
+
+/// Apply [`MoveSettings::allow_water`] and
+/// [`MoveSettings::water_cost_multiplier`] to all of the edges that were added
+/// for `node`.
+///
+/// This has to happen after every move was added instead of in
+/// [`water_moves`], since moves like walking forward can also go into shallow
+/// water.
+///
+/// [`MoveSettings::allow_water`]: crate::pathfinder::settings::MoveSettings::allow_water
+/// [`MoveSettings::water_cost_multiplier`]: crate::pathfinder::settings::MoveSettings::water_cost_multiplier
+pub fn apply_water_settings(ctx: &mut PathfinderCtx, node: RelBlockPos) {
+    let settings = ctx.move_settings;
+    if settings.allow_water && settings.water_cost_multiplier == 1. {
+        return;
+    }
+
+    let world = ctx.world;
+    let is_water = |pos: RelBlockPos| classify_water(world.get_block_state(pos)).is_some();
+    if !settings.allow_water {
+        // moves out of the water are still allowed so we can't get stuck in it
+        ctx.edges.retain(|edge| !is_water(edge.movement.target));
+    }
+    let starts_in_water = is_water(node);
+    for edge in ctx.edges.iter_mut() {
+        if starts_in_water || is_water(edge.movement.target) {
+            edge.cost *= settings.water_cost_multiplier;
+        }
+    }
 }
 
 /// Horizontal movement through water
@@ -100,25 +252,20 @@ fn water_traverse_move(ctx: &mut PathfinderCtx, pos: RelBlockPos) {
         return;
     }
     
-    // Create swimming state for this path node
-    let mut swimming_state = SwimmingState::default();
-    
-    // TODO: In a real implementation, we'd track swimming state through the pathfinding
-    // For now, we'll estimate based on the local water environment
-    let current_above = ctx.world.get_block_state(pos.up(1));
-    if classify_water(current_above).is_some() {
-        swimming_state.consecutive_swim_moves = 4; // Assume we've been swimming
-    }
-    
+    let swimming_state = ctx.swimming;
+
     for dir in CardinalDirection::iter() {
         let offset = RelBlockPos::new(dir.x(), 0, dir.z());
         let target_pos = pos + offset;
         
         let target_block = ctx.world.get_block_state(target_pos);
-        let target_water = classify_water(target_block);
+        let target_water = classify_water_at(ctx, target_pos);
         
         // Handle different target types
         match target_water {
+            // we can swim through flowing water sideways, the cost depends on whether
+            // we're going with or against the current
+            Some(WaterType::FlowingWater) => {}
             Some(target_water_type) => {
                 if !is_water_navigable(target_water_type) {
                     continue;
@@ -153,18 +300,20 @@ fn water_traverse_move(ctx: &mut PathfinderCtx, pos: RelBlockPos) {
             WATER_EXIT_COST
         };
         
-        // Add flow resistance if moving against current
-        if let Some(WaterType::FlowingWater) = target_water {
-            // Only apply resistance if actually moving against the flow
-            // TODO: Implement proper flow direction checking
-            cost += FLOW_RESISTANCE_COST;
+        // Add flow resistance if moving against current, or a discount if moving with it
+        if let Some(WaterType::FlowingWater) = target_water
+            && let Some(flow) = flow_direction(ctx, target_pos)
+        {
+            if flow == dir {
+                cost -= FLOW_ASSIST_DISCOUNT;
+            } else if flow.x() == -dir.x() && flow.z() == -dir.z() {
+                cost += FLOW_RESISTANCE_COST;
+            }
         }
         
-        // Reduce cost for aquatic plants to encourage swimming through them
-        let target_block = ctx.world.get_block_state(target_pos);
-        if is_aquatic_plant(azalea_registry::Block::from(target_block)) {
-            cost *= 0.95; // Small preference for swimming through plants
-        }
+        // kelp and seagrass slow us down unless we break them
+        let (plant_cost, breaks_plants) = aquatic_plant_cost(ctx, target_pos);
+        cost += plant_cost;
         
         // Add directional bias to encourage straight-line swimming
         // This helps prevent the zigzag/bobbing behavior
@@ -172,7 +321,7 @@ fn water_traverse_move(ctx: &mut PathfinderCtx, pos: RelBlockPos) {
         cost += direction_bonus;
         
         // Reduce cost if we have good air access nearby
-        if has_nearby_air_access(ctx, target_pos, 3) {
+        if has_nearby_air_access(ctx, target_pos, AIR_POCKET_SEARCH_DISTANCE) {
             cost *= 0.9; // 10% reduction for having air access
         }
         
@@ -180,8 +329,17 @@ fn water_traverse_move(ctx: &mut PathfinderCtx, pos: RelBlockPos) {
             movement: astar::Movement {
                 target: target_pos,
                 data: MoveData {
-                    execute: &execute_water_traverse,
-                    is_reached: &default_is_reached,
+                    execute: if breaks_plants {
+                        &execute_water_traverse_through_plants
+                    } else {
+                        &execute_water_traverse
+                    },
+                    is_reached: if target_water.is_some() {
+                        &swimming_is_reached
+                    } else {
+                        &default_is_reached
+                    },
+                    consumes: &[],
                 },
             },
             cost,
@@ -196,19 +354,14 @@ fn water_ascend_move(ctx: &mut PathfinderCtx, pos: RelBlockPos) {
         return;
     }
     
-    // Swimming state for ascent
-    let mut swimming_state = SwimmingState::default();
-    let current_above = ctx.world.get_block_state(pos.up(1));
-    if classify_water(current_above).is_some() {
-        swimming_state.consecutive_swim_moves = 2; // Moderate swimming state
-    }
-    
+    let swimming_state = ctx.swimming;
+
     for dir in CardinalDirection::iter() {
         let offset = RelBlockPos::new(dir.x(), 1, dir.z());
         let target_pos = pos + offset;
         
         let target_block = ctx.world.get_block_state(target_pos);
-        let target_water = classify_water(target_block);
+        let target_water = classify_water_at(ctx, target_pos);
         
         // Target must be water or passable (air)
         match target_water {
@@ -243,13 +396,25 @@ fn water_ascend_move(ctx: &mut PathfinderCtx, pos: RelBlockPos) {
         if target_block.is_air() || has_nearby_air_access(ctx, target_pos, 2) {
             cost *= 0.7; // Strong incentive to reach air
         }
+
+        let (plant_cost, breaks_plants) = aquatic_plant_cost(ctx, target_pos);
+        cost += plant_cost;
         
         ctx.edges.push(Edge {
             movement: astar::Movement {
                 target: target_pos,
                 data: MoveData {
-                    execute: &execute_water_ascend,
-                    is_reached: &default_is_reached,
+                    execute: if breaks_plants {
+                        &execute_water_ascend_through_plants
+                    } else {
+                        &execute_water_ascend
+                    },
+                    is_reached: if target_water.is_some() {
+                        &swimming_is_reached
+                    } else {
+                        &default_is_reached
+                    },
+                    consumes: &[],
                 },
             },
             cost,
@@ -264,19 +429,13 @@ fn water_descend_move(ctx: &mut PathfinderCtx, pos: RelBlockPos) {
         return;
     }
     
-    // Swimming state for descent
-    let mut swimming_state = SwimmingState::default();
-    let current_above = ctx.world.get_block_state(pos.up(1));
-    if classify_water(current_above).is_some() {
-        swimming_state.consecutive_swim_moves = 3; // Assume deeper swimming
-    }
-    
+    let swimming_state = ctx.swimming;
+
     for dir in CardinalDirection::iter() {
         let offset = RelBlockPos::new(dir.x(), -1, dir.z());
         let target_pos = pos + offset;
         
-        let target_block = ctx.world.get_block_state(target_pos);
-        let target_water = classify_water(target_block);
+        let target_water = classify_water_at(ctx, target_pos);
         
         // Target must be water for descending
         if let Some(target_water_type) = target_water {
@@ -295,22 +454,32 @@ fn water_descend_move(ctx: &mut PathfinderCtx, pos: RelBlockPos) {
         let base_cost = calculate_swimming_cost(ctx, pos, target_pos, swimming_state);
         let mut cost = base_cost * 0.9; // Descent multiplier from costs.rs
         
-        // Penalize going deeper if air is getting low
-        if swimming_state.estimated_air < 100 {
-            cost *= 1.5; // Discourage going deeper when air is low
+        // going deeper when we're running out of air is how we drown
+        if !ctx.world.equipment().effects.water_breathing
+            && swimming_state.estimated_air < ctx.low_air_threshold
+        {
+            cost += DROWNING_AVOIDANCE_COST;
         }
         
         // Penalize moves that take us further from air access
         if !has_nearby_air_access(ctx, target_pos, 4) {
             cost *= 1.2; // Prefer staying near air access
         }
+
+        let (plant_cost, breaks_plants) = aquatic_plant_cost(ctx, target_pos);
+        cost += plant_cost;
         
         ctx.edges.push(Edge {
             movement: astar::Movement {
                 target: target_pos,
                 data: MoveData {
-                    execute: &execute_water_descend,
-                    is_reached: &default_is_reached,
+                    execute: if breaks_plants {
+                        &execute_water_descend_through_plants
+                    } else {
+                        &execute_water_descend
+                    },
+                    is_reached: &swimming_is_reached,
+                    consumes: &[],
                 },
             },
             cost,
@@ -331,8 +500,7 @@ pub fn water_entry_moves(ctx: &mut PathfinderCtx, pos: RelBlockPos) {
         let offset = RelBlockPos::new(dir.x(), 0, dir.z());
         let target_pos = pos + offset;
         
-        let target_block = ctx.world.get_block_state(target_pos);
-        let target_water = classify_water(target_block);
+        let target_water = classify_water_at(ctx, target_pos);
         
         // Target must be navigable water
         if let Some(target_water_type) = target_water {
@@ -356,6 +524,7 @@ pub fn water_entry_moves(ctx: &mut PathfinderCtx, pos: RelBlockPos) {
                 data: MoveData {
                     execute: &execute_water_entry,
                     is_reached: &default_is_reached,
+                    consumes: &[],
                 },
             },
             cost,
@@ -363,7 +532,174 @@ pub fn water_entry_moves(ctx: &mut PathfinderCtx, pos: RelBlockPos) {
     }
 }
 
-/// Swimming state tracking for consecutive underwater moves
+/// Which way a bubble column pushes us.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BubbleColumnDirection {
+    /// Soul sand columns push us up.
+    Up,
+    /// Magma block columns pull us down.
+    Down,
+}
+
+/// Which way the bubble column at this position goes, or `None` if there's no
+/// bubble column here.
+///
+/// Water above soul sand or a magma block counts too, since the server turns it
+/// into a bubble column anyway.
+pub fn bubble_column_direction(
+    ctx: &PathfinderCtx,
+    pos: RelBlockPos,
+) -> Option<BubbleColumnDirection> {
+    let block_state = ctx.world.get_block_state(pos);
+    let registry_block = azalea_registry::Block::from(block_state);
+    if registry_block == azalea_registry::Block::BubbleColumn {
+        let drag_down = block_state
+            .property::<properties::Drag>()
+            .unwrap_or_default();
+        return Some(if drag_down {
+            BubbleColumnDirection::Down
+        } else {
+            BubbleColumnDirection::Up
+        });
+    }
+    if classify_water(block_state) != Some(WaterType::StillWater)
+        || registry_block != azalea_registry::Block::Water
+    {
+        return None;
+    }
+
+    let mut below = pos.down(1);
+    for _ in 0..MAX_SHAFT_HEIGHT {
+        let block_below = ctx.world.get_block_state(below);
+        match azalea_registry::Block::from(block_below) {
+            azalea_registry::Block::SoulSand => return Some(BubbleColumnDirection::Up),
+            azalea_registry::Block::MagmaBlock => return Some(BubbleColumnDirection::Down),
+            azalea_registry::Block::Water => below = below.down(1),
+            _ => return None,
+        }
+    }
+    None
+}
+
+/// Riding a bubble column up to the surface, or down towards the bottom.
+///
+/// Going up, we only get out at the top, since the column launches us out of
+/// the water there. Getting out through the sides of enclosed columns is done
+/// by the [water elevator moves](super::water_elevator). Going down, we can
+/// get out at any height except for the bottom, since the magma block there
+/// would burn us.
+pub fn bubble_column_moves(ctx: &mut PathfinderCtx, pos: RelBlockPos) {
+    let Some(direction) = bubble_column_direction(ctx, pos) else {
+        return;
+    };
+
+    let mut targets = Vec::new();
+    match direction {
+        BubbleColumnDirection::Up => {
+            let mut top = pos;
+            while top.y - pos.y < MAX_SHAFT_HEIGHT
+                && bubble_column_direction(ctx, top.up(1)) == Some(BubbleColumnDirection::Up)
+            {
+                top = top.up(1);
+            }
+            let surface = top.up(1);
+            if ctx.world.is_block_passable(surface)
+                && classify_water(ctx.world.get_block_state(surface)).is_none()
+            {
+                targets.push(surface);
+            }
+        }
+        BubbleColumnDirection::Down => {
+            let mut target = pos.down(1);
+            while pos.y - target.y < MAX_SHAFT_HEIGHT
+                && bubble_column_direction(ctx, target) == Some(BubbleColumnDirection::Down)
+                && bubble_column_direction(ctx, target.down(1)) == Some(BubbleColumnDirection::Down)
+            {
+                targets.push(target);
+                target = target.down(1);
+            }
+        }
+    }
+
+    for target in targets {
+        if !is_water_safe(ctx, target) {
+            continue;
+        }
+
+        let cost_per_block = match direction {
+            BubbleColumnDirection::Up => WATER_ELEVATOR_UP_ONE_BLOCK_COST,
+            BubbleColumnDirection::Down => WATER_ELEVATOR_DOWN_ONE_BLOCK_COST,
+        };
+        ctx.edges.push(Edge {
+            movement: astar::Movement {
+                target,
+                data: MoveData {
+                    execute: &execute_bubble_column_move,
+                    is_reached: &default_is_reached,
+                    consumes: &[],
+                },
+            },
+            cost: cost_per_block * (target.y - pos.y).unsigned_abs() as f32,
+        });
+    }
+}
+
+/// Whether Frost Walker would let us walk on top of the water below `pos`.
+///
+/// Only still water freezes, and only if we'd be walking above it instead of
+/// swimming in it.
+pub fn can_walk_on_water(ctx: &PathfinderCtx, pos: RelBlockPos) -> bool {
+    if ctx.world.equipment().frost_walker == 0 {
+        return false;
+    }
+    let below = ctx.world.get_block_state(pos.down(1));
+    let is_still_water = azalea_registry::Block::from(below) == azalea_registry::Block::Water
+        && below.property::<properties::WaterLevel>() == Some(properties::WaterLevel::_0);
+    let is_open = |pos: RelBlockPos| {
+        let block_state = ctx.world.get_block_state(pos);
+        classify_water(block_state).is_none()
+            && crate::pathfinder::world::is_block_state_passable(block_state)
+    };
+    is_still_water && is_open(pos) && is_open(pos.up(1))
+}
+
+/// Walking across still water with Frost Walker boots, which freeze it into
+/// ice in front of us.
+fn frost_walker_move(ctx: &mut PathfinderCtx, pos: RelBlockPos) {
+    // we have to be standing on something, either the ground or ice that we froze
+    if !ctx.world.is_standable(pos) && !can_walk_on_water(ctx, pos) {
+        return;
+    }
+
+    for dir in CardinalDirection::iter() {
+        let target = pos + RelBlockPos::new(dir.x(), 0, dir.z());
+        if !can_walk_on_water(ctx, target) {
+            continue;
+        }
+
+        ctx.edges.push(Edge {
+            movement: astar::Movement {
+                target,
+                data: MoveData {
+                    execute: &execute_frost_walker_move,
+                    is_reached: &default_is_reached,
+                    consumes: &[],
+                },
+            },
+            cost: WALK_ONE_BLOCK_COST,
+        });
+    }
+}
+
+fn execute_frost_walker_move(mut ctx: ExecuteCtx) {
+    ctx.look_at(ctx.target.center());
+    ctx.walk(WalkDirection::Forward);
+}
+
+/// Swimming state tracking for consecutive underwater moves.
+///
+/// Every node in the pathfinder search has one of these, which is calculated
+/// with [`next_swimming_state`] from the node that we got to it from.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct SwimmingState {
     /// Number of consecutive underwater moves
@@ -378,12 +714,82 @@ impl Default for SwimmingState {
     fn default() -> Self {
         Self {
             consecutive_swim_moves: 0,
-            estimated_air: 300, // Default max air in Minecraft (20 seconds * 15 ticks/second)
+            estimated_air: MAX_AIR_SUPPLY,
             is_sprint_swimming: false,
         }
     }
 }
 
+impl SwimmingState {
+    /// The state that makes swimming as cheap as it can be.
+    ///
+    /// This is used when we don't know how long we've been swimming for, like
+    /// when checking whether the rest of a path is still possible, so the
+    /// costs can only be the same or lower than when the path was calculated.
+    pub fn best_case() -> Self {
+        Self {
+            consecutive_swim_moves: SPRINT_SWIM_AFTER_MOVES,
+            estimated_air: MAX_AIR_SUPPLY,
+            is_sprint_swimming: true,
+        }
+    }
+}
+
+/// Whether both this block and the one above it are water, so our head is
+/// underwater.
+pub fn is_submerged(world: &CachedWorld, pos: RelBlockPos) -> bool {
+    classify_water(world.get_block_state(pos)).is_some()
+        && classify_water(world.get_block_state(pos.up(1))).is_some()
+}
+
+/// How much air we have right after we go underwater, in ticks.
+///
+/// The Water Breathing from a turtle shell works the same as having that much
+/// more air.
+pub fn full_air_supply(equipment: PathEquipment) -> i32 {
+    if equipment.has_turtle_helmet {
+        MAX_AIR_SUPPLY + TURTLE_HELMET_BREATHING_TICKS as i32
+    } else {
+        MAX_AIR_SUPPLY
+    }
+}
+
+/// The swimming state after moving to `pos` from a node with the given state.
+///
+/// Coming up for air resets the state, since our air refills almost instantly.
+pub fn next_swimming_state(
+    world: &CachedWorld,
+    state: SwimmingState,
+    pos: RelBlockPos,
+) -> SwimmingState {
+    let equipment = world.equipment();
+    if !is_submerged(world, pos) {
+        return SwimmingState {
+            estimated_air: full_air_supply(equipment),
+            ..Default::default()
+        };
+    }
+
+    // we lose one air every tick that our head is underwater
+    let move_ticks = if state.is_sprint_swimming {
+        SPRINT_SWIMMING_COST
+    } else {
+        SWIMMING_COST
+    } * equipment.swimming_cost_multiplier();
+    let air_used = if equipment.effects.water_breathing {
+        0.
+    } else {
+        // respiration makes every tick only have a 1 in (level + 1) chance of using air
+        move_ticks / (equipment.respiration + 1) as f32
+    };
+    let consecutive_swim_moves = state.consecutive_swim_moves.saturating_add(1);
+    SwimmingState {
+        consecutive_swim_moves,
+        estimated_air: (state.estimated_air - air_used.round() as i32).max(0),
+        is_sprint_swimming: consecutive_swim_moves >= SPRINT_SWIM_AFTER_MOVES,
+    }
+}
+
 /// Calculate the optimal swimming cost based on state and conditions
 pub fn calculate_swimming_cost(
     ctx: &PathfinderCtx,
@@ -394,26 +800,29 @@ pub fn calculate_swimming_cost(
     let mut base_cost = SWIMMING_COST;
     
     // Check if both positions are fully underwater (submerged)
-    let current_above = ctx.world.get_block_state(current_pos.up(1));
-    let target_above = ctx.world.get_block_state(target_pos.up(1));
-    let current_submerged = classify_water(current_above).is_some();
-    let target_submerged = classify_water(target_above).is_some();
+    let current_submerged = is_submerged(ctx.world, current_pos);
+    let target_submerged = is_submerged(ctx.world, target_pos);
     
     // Sprint swimming when fully submerged for consecutive moves
-    if current_submerged && target_submerged && swimming_state.consecutive_swim_moves >= 3 {
+    if current_submerged
+        && target_submerged
+        && swimming_state.consecutive_swim_moves >= SPRINT_SWIM_AFTER_MOVES
+    {
         base_cost = SPRINT_SWIMMING_COST; // Much more efficient underwater
     }
+    base_cost *= ctx.world.equipment().swimming_cost_multiplier();
     
     // Air supply penalty - gets exponentially worse as air runs low
-    let air_ratio = swimming_state.estimated_air as f32 / 300.0; // Normalize to 0-1
-    if air_ratio < 0.3 {
+    let water_breathing = ctx.world.equipment().effects.water_breathing;
+    let air_ratio = swimming_state.estimated_air as f32 / MAX_AIR_SUPPLY as f32;
+    if !water_breathing && air_ratio < 0.3 {
         // Below 30% air, start adding heavy penalties
         let air_penalty = AIR_DEPLETION_PENALTY * (1.0 - air_ratio).powi(2);
         base_cost += air_penalty;
     }
     
     // Critical air level - avoid drowning at all costs
-    if swimming_state.estimated_air <= 20 {
+    if !water_breathing && swimming_state.estimated_air <= 20 {
         base_cost += DROWNING_AVOIDANCE_COST;
     }
     
@@ -430,67 +839,224 @@ pub fn calculate_swimming_cost(
     base_cost
 }
 
-/// Check if there's an air pocket (surface) within reasonable distance
+/// Check if there's an air pocket (surface) within `max_distance` blocks.
+///
+/// This searches through water and other passable blocks in every direction,
+/// so air pockets off to the side of an underwater cave count too.
 pub fn has_nearby_air_access(ctx: &PathfinderCtx, pos: RelBlockPos, max_distance: i32) -> bool {
-    // Check upward for air access
-    for y_offset in 1..=max_distance {
-        let check_pos = pos.up(y_offset);
-        let block_state = ctx.world.get_block_state(check_pos);
-        
-        // Found air or a block with air above
-        if block_state.is_air() {
-            return true;
+    ctx.world
+        .air_access(pos, max_distance, || find_air_pocket(ctx.world, pos, max_distance))
+}
+
+/// A breadth-first search outwards from `pos` for air that we could breathe in,
+/// which is air with water or a solid block below it.
+fn find_air_pocket(world: &CachedWorld, pos: RelBlockPos, max_distance: i32) -> bool {
+    let mut visited = HashSet::from([pos]);
+    let mut queue = VecDeque::from([(pos, 0)]);
+    while let Some((current, distance)) = queue.pop_front() {
+        if distance >= max_distance {
+            continue;
         }
-        
-        // If we hit a solid block, no air access this way
-        if !crate::pathfinder::world::is_block_state_passable(block_state) 
-            && classify_water(block_state).is_none() {
-            break;
+        let neighbors = CardinalDirection::iter()
+            .map(|dir| current + RelBlockPos::new(dir.x(), 0, dir.z()))
+            .chain([current.up(1), current.down(1)]);
+        for next in neighbors {
+            if !visited.insert(next) {
+                continue;
+            }
+            let block_state = world.get_block_state(next);
+            if block_state.is_air() {
+                let below = world.get_block_state(next.down(1));
+                if classify_water(below).is_some() || world.is_block_solid(next.down(1)) {
+                    return true;
+                }
+            }
+            if classify_water(block_state).is_some()
+                || crate::pathfinder::world::is_block_state_passable(block_state)
+            {
+                queue.push_back((next, distance + 1));
+            }
         }
     }
-    
     false
 }
 
-/// Estimate air consumption for a move (in ticks)
-pub fn estimate_air_consumption(current_pos: RelBlockPos, target_pos: RelBlockPos, ctx: &PathfinderCtx) -> i32 {
-    let current_above = ctx.world.get_block_state(current_pos.up(1));
-    let target_above = ctx.world.get_block_state(target_pos.up(1));
-    
-    // If either position has air above, no air consumption
-    if current_above.is_air() || target_above.is_air() {
+/// Estimate air consumption for a move (in ticks), starting from the
+/// swimming state in `ctx`.
+///
+/// This uses [`next_swimming_state`], so it always agrees with the air that
+/// the search keeps track of.
+pub fn estimate_air_consumption(
+    current_pos: RelBlockPos,
+    target_pos: RelBlockPos,
+    ctx: &PathfinderCtx,
+) -> i32 {
+    if !is_submerged(ctx.world, current_pos) {
         return 0;
     }
-    
-    // Both positions are underwater, consume air
-    // Roughly 1 air per tick when swimming underwater
-    1
+    let next_state = next_swimming_state(ctx.world, ctx.swimming, target_pos);
+    // surfacing refills our air, which doesn't count as using any
+    (ctx.swimming.estimated_air - next_state.estimated_air).max(0)
 }
 
 /// Execute horizontal water traversal
 fn execute_water_traverse(mut ctx: ExecuteCtx) {
-    let center = ctx.target.center();
-    
-    ctx.look_at(center);
-    ctx.jump(); // Swimming motion in water
-    ctx.walk(WalkDirection::Forward);
+    let feet_y = ctx.target.y as f64 + SWIMMING_FEET_HEIGHT;
+
+    ctx.look_at(ctx.target.center());
+    swim_forward(&mut ctx);
+    // we slowly sink if we don't hold jump
+    hold_jump_below(&mut ctx, feet_y);
 }
 
 /// Execute swimming upward
 fn execute_water_ascend(mut ctx: ExecuteCtx) {
     let center = ctx.target.center();
-    
-    ctx.look_at(center + azalea_core::position::Vec3::new(0.0, 0.5, 0.0)); // Look slightly upward
-    ctx.jump(); // Jump to swim upward
-    ctx.walk(WalkDirection::Forward);
+    let feet_y = ctx.target.y as f64 + SWIMMING_FEET_HEIGHT;
+
+    ctx.look_at(center + Vec3::new(0.0, 0.5, 0.0)); // Look slightly upward
+    swim_forward(&mut ctx);
+    hold_jump_below(&mut ctx, feet_y);
 }
 
-/// Execute swimming downward  
+/// Execute swimming downward
 fn execute_water_descend(mut ctx: ExecuteCtx) {
     let center = ctx.target.center();
-    
-    ctx.look_at(center + azalea_core::position::Vec3::new(0.0, -0.5, 0.0)); // Look slightly downward
-    ctx.walk(WalkDirection::Forward); // Move forward while looking down
+
+    ctx.look_at(center + Vec3::new(0.0, -0.5, 0.0)); // Look slightly downward
+    swim_forward(&mut ctx);
+}
+
+/// How far above the bottom of the target block we try to keep our feet while
+/// swimming, so we don't sink out of it.
+const SWIMMING_FEET_HEIGHT: f64 = 0.2;
+/// How far our feet can be from the bottom of the target block for a swimming
+/// move to count as reached. We bob up and down while swimming, and our hitbox
+/// is only 0.6 blocks tall while we're sprint swimming.
+const SWIMMING_REACHED_VERTICAL_TOLERANCE: f64 = 0.8;
+
+/// Keep pressing jump until our feet are at `y`, and then let go so we don't
+/// bob out of the water.
+fn hold_jump_below(ctx: &mut ExecuteCtx, y: f64) {
+    if ctx.position.y < y {
+        ctx.jump();
+    }
+}
+
+/// Swim forwards, and sprint if we're going to be underwater for long enough
+/// for it to be worth it. This matches when the costs assume that we're
+/// sprint swimming.
+fn swim_forward(ctx: &mut ExecuteCtx) {
+    if is_long_underwater_stretch(ctx) {
+        ctx.sprint(SprintDirection::Forward);
+    } else {
+        ctx.walk(WalkDirection::Forward);
+    }
+}
+
+/// Whether the start of the move, the target, and the blocks past the target
+/// in the same direction are all fully underwater, for a total of
+/// [`SPRINT_SWIM_AFTER_MOVES`] nodes.
+fn is_long_underwater_stretch(ctx: &ExecuteCtx) -> bool {
+    let direction = BlockPos::new(
+        (ctx.target.x - ctx.start.x).signum(),
+        0,
+        (ctx.target.z - ctx.start.z).signum(),
+    );
+    if direction == BlockPos::default() {
+        return false;
+    }
+
+    let instance = ctx.instance.read();
+    let is_water = |pos: BlockPos| {
+        instance
+            .get_block_state(pos)
+            .and_then(classify_water)
+            .is_some()
+    };
+    let nodes_ahead = (1..SPRINT_SWIM_AFTER_MOVES as i32 - 1).map(|i| ctx.target + direction * i);
+    [ctx.start, ctx.target]
+        .into_iter()
+        .chain(nodes_ahead)
+        .all(|pos| is_water(pos) && is_water(pos.up(1)))
+}
+
+/// Like [`default_is_reached`], but with more leeway vertically.
+#[must_use]
+pub fn swimming_is_reached(ctx: IsReachedCtx) -> bool {
+    let IsReachedCtx {
+        position, target, ..
+    } = ctx;
+    let block_pos = BlockPos::from(position);
+    let is_close_vertically =
+        (position.y - target.y as f64).abs() < SWIMMING_REACHED_VERTICAL_TOLERANCE;
+    (block_pos.x == target.x && block_pos.z == target.z && is_close_vertically)
+        || default_is_reached(ctx)
+}
+
+/// Kelp and seagrass that we can break to get them out of the way.
+fn is_breakable_aquatic_plant(block: azalea_registry::Block) -> bool {
+    matches!(
+        block,
+        azalea_registry::Block::Kelp
+            | azalea_registry::Block::KelpPlant
+            | azalea_registry::Block::Seagrass
+            | azalea_registry::Block::TallSeagrass
+    )
+}
+
+/// The extra cost of getting through the kelp or seagrass that's in the way of
+/// swimming to `target`, and whether we're going to break it.
+///
+/// They break instantly, so if we're allowed to mine then that's cheaper than
+/// pushing through them.
+fn aquatic_plant_cost(ctx: &PathfinderCtx, target: RelBlockPos) -> (f32, bool) {
+    let mut cost = 0.;
+    let mut breaks_plants = false;
+    for pos in [target, target.up(1)] {
+        let block_state = ctx.world.get_block_state(pos);
+        if !is_breakable_aquatic_plant(block_state.into()) {
+            continue;
+        }
+        let break_cost = ctx.mining_cache.cost_for(block_state);
+        if break_cost.is_finite() {
+            cost += break_cost;
+            breaks_plants = true;
+        } else {
+            cost += AQUATIC_PLANT_PENALTY;
+        }
+    }
+    (cost, breaks_plants)
+}
+
+/// Break the kelp or seagrass in the way of the target. Returns whether we're
+/// still breaking something.
+fn break_aquatic_plants(ctx: &mut ExecuteCtx) -> bool {
+    for pos in [ctx.target, ctx.target.up(1)] {
+        if is_breakable_aquatic_plant(ctx.get_block_state(pos).into()) {
+            ctx.start_mining(pos);
+            return true;
+        }
+    }
+    false
+}
+
+fn execute_water_traverse_through_plants(mut ctx: ExecuteCtx) {
+    if !break_aquatic_plants(&mut ctx) {
+        execute_water_traverse(ctx);
+    }
+}
+
+fn execute_water_ascend_through_plants(mut ctx: ExecuteCtx) {
+    if !break_aquatic_plants(&mut ctx) {
+        execute_water_ascend(ctx);
+    }
+}
+
+fn execute_water_descend_through_plants(mut ctx: ExecuteCtx) {
+    if !break_aquatic_plants(&mut ctx) {
+        execute_water_descend(ctx);
+    }
 }
 
 /// Execute water entry from land
@@ -501,6 +1067,24 @@ fn execute_water_entry(mut ctx: ExecuteCtx) {
     ctx.walk(WalkDirection::Forward); // Walk into water
 }
 
+/// Stay in the middle of the bubble column and let it carry us
+fn execute_bubble_column_move(mut ctx: ExecuteCtx) {
+    let column_center = ctx.start.center();
+
+    let offset = column_center - ctx.position;
+    if offset.horizontal_distance_squared() > 0.2 * 0.2 {
+        ctx.look_at(column_center);
+        ctx.walk(WalkDirection::Forward);
+    } else {
+        ctx.walk(WalkDirection::None);
+    }
+
+    if ctx.target.y > ctx.start.y {
+        // swimming up at the same time makes it a bit faster
+        ctx.jump();
+    }
+}
+
 /// Check if a block is an aquatic plant that should be swimmable
 fn is_aquatic_plant(block: azalea_registry::Block) -> bool {
     matches!(block,
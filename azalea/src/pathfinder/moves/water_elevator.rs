@@ -0,0 +1,191 @@
+//! Moves for riding player-built water elevators.
+//!
+//! A water elevator is an enclosed column of water with soul sand (which makes
+//! a bubble column that pushes up) or a magma block (which pulls down) at the
+//! bottom. The gaps that are used for getting in and out usually have signs in
+//! them, since those hold the water in without blocking players.
+
+use azalea_block::{BlockState, properties};
+use azalea_client::WalkDirection;
+use azalea_core::{direction::CardinalDirection, position::BlockPos};
+
+use super::{Edge, ExecuteCtx, MoveData, PathfinderCtx, default_is_reached};
+use crate::pathfinder::{astar, costs::*, rel_block_pos::RelBlockPos};
+
+/// The shortest column of water that counts as an elevator.
+pub const MIN_SHAFT_HEIGHT: i32 = 3;
+/// How far we look up and down for the ends of a shaft.
+pub const MAX_SHAFT_HEIGHT: i32 = 128;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ElevatorDirection {
+    Up,
+    Down,
+}
+
+pub fn water_elevator_move(ctx: &mut PathfinderCtx, node: RelBlockPos) {
+    // we can only get into a shaft from one of its gaps
+    if !is_gap(ctx.world.get_block_state(node)) {
+        return;
+    }
+
+    for dir in CardinalDirection::iter() {
+        let column = node + RelBlockPos::new(dir.x(), 0, dir.z());
+        if !is_shaft_water(ctx.world.get_block_state(column)) {
+            continue;
+        }
+        let Some((bottom, top, direction)) = find_shaft(ctx, column) else {
+            continue;
+        };
+
+        for y in bottom..=top {
+            let is_in_direction = match direction {
+                ElevatorDirection::Up => y > node.y,
+                ElevatorDirection::Down => y < node.y,
+            };
+            if !is_in_direction {
+                continue;
+            }
+
+            let cost_per_block = match direction {
+                ElevatorDirection::Up => WATER_ELEVATOR_UP_ONE_BLOCK_COST,
+                ElevatorDirection::Down => WATER_ELEVATOR_DOWN_ONE_BLOCK_COST,
+            };
+            let cost =
+                WATER_ELEVATOR_ENTER_COST + cost_per_block * (y - node.y).unsigned_abs() as f32;
+
+            let shaft_pos = RelBlockPos::new(column.x, y, column.z);
+            for exit_dir in CardinalDirection::iter() {
+                let exit = shaft_pos + RelBlockPos::new(exit_dir.x(), 0, exit_dir.z());
+                if !is_gap(ctx.world.get_block_state(exit)) || !ctx.world.is_standable(exit) {
+                    continue;
+                }
+
+                ctx.edges.push(Edge {
+                    movement: astar::Movement {
+                        target: exit,
+                        data: MoveData {
+                            execute: &execute_water_elevator_move,
+                            is_reached: &default_is_reached,
+                            consumes: &[],
+                        },
+                    },
+                    cost,
+                });
+            }
+        }
+    }
+}
+
+/// Find the bottom and top of the water in the shaft that contains `column`,
+/// and which way the elevator goes. Returns `None` if it's not an enclosed
+/// water elevator.
+fn find_shaft(ctx: &PathfinderCtx, column: RelBlockPos) -> Option<(i32, i32, ElevatorDirection)> {
+    let block_state_at = |y| {
+        ctx.world
+            .get_block_state(RelBlockPos::new(column.x, y, column.z))
+    };
+
+    let mut bottom = column.y;
+    while column.y - bottom < MAX_SHAFT_HEIGHT && is_shaft_water(block_state_at(bottom - 1)) {
+        bottom -= 1;
+    }
+    let mut top = column.y;
+    while top - column.y < MAX_SHAFT_HEIGHT && is_shaft_water(block_state_at(top + 1)) {
+        top += 1;
+    }
+    if top - bottom + 1 < MIN_SHAFT_HEIGHT {
+        return None;
+    }
+
+    let direction = match azalea_registry::Block::from(block_state_at(bottom - 1)) {
+        azalea_registry::Block::SoulSand => ElevatorDirection::Up,
+        azalea_registry::Block::MagmaBlock => ElevatorDirection::Down,
+        _ => return None,
+    };
+
+    // the water has to be held in on every side, or else it'd flow out and it
+    // wouldn't be an elevator anymore
+    for y in bottom..=top {
+        for dir in CardinalDirection::iter() {
+            let side = RelBlockPos::new(column.x + dir.x(), y, column.z + dir.z());
+            if ctx.world.is_passable(side) && !is_gap(ctx.world.get_block_state(side)) {
+                return None;
+            }
+        }
+    }
+
+    Some((bottom, top, direction))
+}
+
+/// Whether this is water that can be part of an elevator shaft.
+fn is_shaft_water(block_state: BlockState) -> bool {
+    match azalea_registry::Block::from(block_state) {
+        azalea_registry::Block::Water => block_state
+            .property::<properties::WaterLevel>()
+            .is_none_or(|level| level == properties::WaterLevel::_0),
+        azalea_registry::Block::BubbleColumn => true,
+        _ => false,
+    }
+}
+
+/// Whether this block holds in water but still lets players walk through it.
+fn is_gap(block_state: BlockState) -> bool {
+    azalea_registry::tags::blocks::ALL_SIGNS.contains(&azalea_registry::Block::from(block_state))
+}
+
+fn execute_water_elevator_move(mut ctx: ExecuteCtx) {
+    let Some(column) = find_column(&ctx) else {
+        // the elevator is gone, so just try to walk to the exit
+        ctx.look_at(ctx.target.center());
+        ctx.walk(WalkDirection::Forward);
+        return;
+    };
+    let column_center = column.center();
+
+    let is_in_column =
+        BlockPos::from(ctx.position).x == column.x && BlockPos::from(ctx.position).z == column.z;
+    let is_going_up = ctx.target.y > ctx.start.y;
+    let is_at_exit_level = if is_going_up {
+        ctx.position.y >= ctx.target.y as f64
+    } else {
+        ctx.position.y <= ctx.target.y as f64 + 0.1
+    };
+
+    if is_at_exit_level {
+        ctx.look_at(ctx.target.center());
+        if !is_going_up && ctx.position.y < ctx.target.y as f64 {
+            // we sank too far, swim back up to the exit
+            ctx.jump();
+        }
+        ctx.walk(WalkDirection::Forward);
+    } else if !is_in_column {
+        // walk in through the gap
+        ctx.look_at(column_center);
+        ctx.walk(WalkDirection::Forward);
+    } else {
+        // stay in the middle and let the bubbles carry us
+        let offset = column_center - ctx.position;
+        if offset.horizontal_distance_squared() > 0.2 * 0.2 {
+            ctx.look_at(column_center);
+            ctx.walk(WalkDirection::Forward);
+        } else {
+            ctx.walk(WalkDirection::None);
+        }
+    }
+}
+
+/// Find the shaft that's next to both the start and the target of the move.
+fn find_column(ctx: &ExecuteCtx) -> Option<BlockPos> {
+    let instance = ctx.instance.read();
+    CardinalDirection::iter()
+        .map(|dir| ctx.start + BlockPos::new(dir.x() as i32, 0, dir.z() as i32))
+        .find(|column| {
+            let horizontal_distance_to_target =
+                (column.x - ctx.target.x).abs() + (column.z - ctx.target.z).abs();
+            horizontal_distance_to_target == 1
+                && instance
+                    .get_block_state(*column)
+                    .is_some_and(is_shaft_water)
+        })
+}
@@ -0,0 +1,328 @@
+//! Going to goals in other dimensions through nether portals.
+//!
+//! When a [`GotoEvent`] has a [`GotoOptions::dimension`] that isn't the one
+//! that we're in, we pick a nether portal, walk into it, and wait for the
+//! server to send us to the other dimension. Then the path to the real goal is
+//! calculated from wherever we came out.
+//!
+//! Portals are like edges between the overworld and the nether. Going through
+//! one takes us to the portal in the other dimension that's closest to its
+//! position there (divided or multiplied by 8), or to a new portal at that
+//! position if there isn't one close enough. So the portal that we pick is the
+//! one where walking to it plus walking from where it comes out to the goal is
+//! the shortest.
+//!
+//! [`GotoEvent`]: super::GotoEvent
+//! [`GotoOptions::dimension`]: super::GotoOptions::dimension
+
+use std::collections::{HashMap, HashSet};
+
+use azalea_block::BlockStates;
+use azalea_client::StartWalkEvent;
+use azalea_core::{position::BlockPos, resource_location::ResourceLocation};
+use azalea_entity::Position;
+use azalea_world::{Instance, InstanceContainer, InstanceName};
+use bevy_ecs::prelude::*;
+use tracing::{debug, warn};
+
+use super::{
+    ExecutingPath, GoalUnreachableEvent, GotoEvent, GotoOutcome, Pathfinder, UnreachableReason,
+    goals::{BlockPosGoal, Goal},
+    world::SharedCachedWorld,
+};
+use crate::WalkDirection;
+
+/// How much further apart things are in the overworld than in the nether.
+pub const NETHER_COORDINATE_SCALE: i32 = 8;
+
+/// How far away (horizontally) from where we'd come out of a portal the server
+/// looks for an existing portal in the overworld, before it makes a new one.
+pub const OVERWORLD_PORTAL_SEARCH_RADIUS: i32 = 128;
+/// Like [`OVERWORLD_PORTAL_SEARCH_RADIUS`], but for coming out in the nether.
+pub const NETHER_PORTAL_SEARCH_RADIUS: i32 = 16;
+
+/// The most portal blocks that we look at every time we scan the world for
+/// them, so a huge portal field doesn't make planning slow.
+const MAX_SCANNED_PORTAL_BLOCKS: usize = 256;
+
+/// How long we stand in a portal for before giving up on it taking us to the
+/// other dimension. Players in survival mode have to be in one for 80 ticks.
+pub const PORTAL_WAIT_TICKS: u32 = 200;
+
+/// Whether the dimension is the nether.
+pub fn is_nether(dimension: &ResourceLocation) -> bool {
+    *dimension == ResourceLocation::new("minecraft:the_nether")
+}
+
+/// The nether portals that we know about in every dimension, so we can still
+/// plan routes through portals that aren't loaded anymore.
+///
+/// Only the bottom block of every portal column is kept, since that's where
+/// we stand when we go into it.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct KnownPortals {
+    pub portals: HashMap<ResourceLocation, HashSet<BlockPos>>,
+}
+impl KnownPortals {
+    /// Remember that there's a portal at the position.
+    pub fn insert(&mut self, dimension: &ResourceLocation, pos: BlockPos) {
+        self.portals
+            .entry(dimension.clone())
+            .or_default()
+            .insert(pos);
+    }
+
+    /// Find the portals in the loaded chunks near `near`, and forget the ones
+    /// that are loaded but aren't there anymore.
+    pub fn scan(&mut self, dimension: &ResourceLocation, instance: &Instance, near: BlockPos) {
+        let portals = self.portals.entry(dimension.clone()).or_default();
+        portals.retain(|&pos| {
+            // we can't tell whether portals in unloaded chunks are still there
+            instance
+                .get_block_state(pos)
+                .is_none_or(|state| is_portal(state.into()))
+        });
+
+        let portal_states = BlockStates::from(azalea_registry::Block::NetherPortal);
+        portals.extend(
+            instance
+                .find_blocks(near, &portal_states)
+                .filter(|&pos| {
+                    !instance
+                        .get_block_state(pos.down(1))
+                        .is_some_and(|state| is_portal(state.into()))
+                })
+                .take(MAX_SCANNED_PORTAL_BLOCKS),
+        );
+    }
+
+    /// Pick the portal in `from_dimension` that gets us to the goal in
+    /// `to_dimension` the fastest, starting at `from`.
+    ///
+    /// This returns `None` if we don't know about any portals, or if nether
+    /// portals don't go between the two dimensions.
+    pub fn best_portal(
+        &self,
+        from_dimension: &ResourceLocation,
+        from: BlockPos,
+        to_dimension: &ResourceLocation,
+        goal: &dyn Goal,
+    ) -> Option<BlockPos> {
+        // nether portals only go between the nether and somewhere else
+        if is_nether(from_dimension) == is_nether(to_dimension) {
+            return None;
+        }
+
+        self.portals
+            .get(from_dimension)?
+            .iter()
+            .map(|&portal| {
+                let exit = self.exit_position(from_dimension, portal, to_dimension);
+                let cost = BlockPosGoal(portal).heuristic(from) + goal.heuristic(exit);
+                (portal, cost)
+            })
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(portal, _)| portal)
+    }
+
+    /// Where we expect to come out in `to_dimension` after going through the
+    /// portal.
+    ///
+    /// This is the closest portal that we know about in the server's search
+    /// radius, or where a new portal would be made if there isn't one.
+    pub fn exit_position(
+        &self,
+        from_dimension: &ResourceLocation,
+        portal: BlockPos,
+        to_dimension: &ResourceLocation,
+    ) -> BlockPos {
+        let scaled = scale_position(portal, from_dimension, to_dimension);
+        let search_radius = if is_nether(to_dimension) {
+            NETHER_PORTAL_SEARCH_RADIUS
+        } else {
+            OVERWORLD_PORTAL_SEARCH_RADIUS
+        };
+
+        self.portals
+            .get(to_dimension)
+            .into_iter()
+            .flatten()
+            .filter(|pos| {
+                (pos.x - scaled.x).abs() <= search_radius
+                    && (pos.z - scaled.z).abs() <= search_radius
+            })
+            .min_by_key(|pos| pos.distance_squared_to(scaled))
+            .copied()
+            .unwrap_or(scaled)
+    }
+}
+
+/// Convert a position in one dimension to the same place in the other one,
+/// like going through a nether portal does.
+pub fn scale_position(
+    pos: BlockPos,
+    from_dimension: &ResourceLocation,
+    to_dimension: &ResourceLocation,
+) -> BlockPos {
+    match (is_nether(from_dimension), is_nether(to_dimension)) {
+        (false, true) => BlockPos::new(
+            pos.x.div_euclid(NETHER_COORDINATE_SCALE),
+            pos.y,
+            pos.z.div_euclid(NETHER_COORDINATE_SCALE),
+        ),
+        (true, false) => BlockPos::new(
+            pos.x * NETHER_COORDINATE_SCALE,
+            pos.y,
+            pos.z * NETHER_COORDINATE_SCALE,
+        ),
+        _ => pos,
+    }
+}
+
+fn is_portal(block: azalea_registry::Block) -> bool {
+    block == azalea_registry::Block::NetherPortal
+}
+
+/// A component that's present while we're going to a goal in another
+/// dimension, and have to go through a portal first.
+///
+/// [`Pathfinder::goal`] is still the goal in the other dimension, but the paths
+/// that are calculated go to the portal.
+#[derive(Component, Clone, Debug)]
+pub struct DimensionTravel {
+    /// The dimension that the goal is in.
+    pub dimension: ResourceLocation,
+    /// The bottom block of the portal that we're going into.
+    pub portal: BlockPos,
+}
+
+/// A component that's present while we're standing in a portal at the end of
+/// our path, waiting for the server to send us to the other dimension.
+#[derive(Component, Clone, Debug, Default)]
+pub struct WaitingInPortal {
+    pub ticks: u32,
+}
+
+/// Wait in the portal once we've walked into it, and give up if it doesn't
+/// take us anywhere after [`PORTAL_WAIT_TICKS`].
+#[allow(clippy::type_complexity)]
+pub fn wait_in_portal(
+    mut query: Query<(
+        Entity,
+        &mut Pathfinder,
+        &DimensionTravel,
+        &Position,
+        &InstanceName,
+        Option<&ExecutingPath>,
+        Option<&mut WaitingInPortal>,
+    )>,
+    instance_container: Res<InstanceContainer>,
+    mut walk_events: EventWriter<StartWalkEvent>,
+    mut goal_unreachable_events: EventWriter<GoalUnreachableEvent>,
+    mut commands: Commands,
+) {
+    for (entity, mut pathfinder, travel, position, instance_name, executing_path, waiting) in
+        &mut query
+    {
+        let is_in_portal = executing_path
+            .is_none_or(|executing_path| executing_path.path.is_empty())
+            && instance_container
+                .get(instance_name)
+                .and_then(|instance| instance.read().get_block_state(BlockPos::from(position)))
+                .is_some_and(|state| is_portal(state.into()));
+        if !is_in_portal {
+            if waiting.is_some() {
+                commands.entity(entity).remove::<WaitingInPortal>();
+            }
+            continue;
+        }
+
+        let Some(mut waiting) = waiting else {
+            debug!("waiting in the portal at {}", travel.portal);
+            walk_events.write(StartWalkEvent {
+                entity,
+                direction: WalkDirection::None,
+            });
+            commands.entity(entity).insert(WaitingInPortal { ticks: 1 });
+            continue;
+        };
+        waiting.ticks += 1;
+        if waiting.ticks < PORTAL_WAIT_TICKS {
+            continue;
+        }
+
+        warn!(
+            "the portal at {} didn't take us to {}, giving up",
+            travel.portal, travel.dimension
+        );
+        let closest = BlockPos::from(position);
+        let reason = UnreachableReason::NoPortal;
+        pathfinder.goal = None;
+        pathfinder.successors_fn = None;
+        pathfinder.outcome = Some(GotoOutcome::Unreachable { closest, reason });
+        commands
+            .entity(entity)
+            .remove::<(DimensionTravel, WaitingInPortal)>();
+        goal_unreachable_events.write(GoalUnreachableEvent {
+            entity,
+            closest,
+            reason,
+        });
+    }
+}
+
+/// Keep going to the goal once a portal took us to the dimension that it's
+/// in.
+pub fn continue_goto_in_new_dimension(
+    mut query: Query<
+        (
+            Entity,
+            &mut Pathfinder,
+            &DimensionTravel,
+            &InstanceName,
+            &Position,
+        ),
+        Changed<InstanceName>,
+    >,
+    instance_container: Res<InstanceContainer>,
+    mut known_portals: ResMut<KnownPortals>,
+    mut goto_events: EventWriter<GotoEvent>,
+    mut commands: Commands,
+) {
+    for (entity, mut pathfinder, travel, instance_name, position) in &mut query {
+        if **instance_name != travel.dimension {
+            // stop_pathfinding_on_instance_change handles this
+            continue;
+        }
+
+        // we come out inside of a portal, so remember it for going back
+        let exit = BlockPos::from(position);
+        if instance_container
+            .get(instance_name)
+            .and_then(|instance| instance.read().get_block_state(exit))
+            .is_some_and(|state| is_portal(state.into()))
+        {
+            known_portals.insert(instance_name, exit);
+        }
+
+        commands.entity(entity).remove::<(
+            DimensionTravel,
+            WaitingInPortal,
+            ExecutingPath,
+            SharedCachedWorld,
+        )>();
+        let Some(goal) = pathfinder.goal.clone() else {
+            continue;
+        };
+        debug!("arrived in {}, continuing to the goal", travel.dimension);
+        // the partial paths so far were going to the portal
+        pathfinder.closest_partial_heuristic = None;
+        pathfinder.stalled_partial_paths = 0;
+        goto_events.write(GotoEvent {
+            entity,
+            goal,
+            options: pathfinder.goto_options(),
+        });
+        pathfinder.is_calculating = true;
+    }
+}
@@ -0,0 +1,166 @@
+//! Keeping the pathfinder out of some parts of the world, like protected
+//! claims or spawn, or inside of one part of it.
+//!
+//! There can be a lot of regions, so instead of checking every one of them for
+//! every node, the regions that overlap each chunk section are found the first
+//! time that the section is needed.
+
+use std::{cell::RefCell, collections::HashMap};
+
+use azalea_core::{
+    aabb::AABB,
+    position::{BlockPos, ChunkSectionPos, Vec3},
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{astar::Edge, moves::MoveData, rel_block_pos::RelBlockPos, world::CachedWorld};
+
+/// What the pathfinder does with the positions that are inside of the
+/// [`GotoOptions::avoid_regions`].
+///
+/// [`GotoOptions::avoid_regions`]: super::GotoOptions::avoid_regions
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum RegionAvoidance {
+    /// Never go into the regions, even if it means that there's no path.
+    #[default]
+    Hard,
+    /// Add this cost for every position in a region that the path goes
+    /// through, so they're only crossed when going around would be a lot
+    /// longer.
+    Soft(f32),
+}
+
+/// The data that's needed to check whether positions are in any of the
+/// regions while we're calculating a path.
+#[derive(Debug, Default)]
+pub struct RegionLayer {
+    pub avoid: Vec<AABB>,
+    pub avoidance: RegionAvoidance,
+    pub confine_to: Option<AABB>,
+
+    sections: RefCell<HashMap<ChunkSectionPos, SectionRegions>>,
+}
+impl Clone for RegionLayer {
+    fn clone(&self) -> Self {
+        Self::new(self.avoid.clone(), self.avoidance, self.confine_to)
+    }
+}
+
+/// How the regions overlap a chunk section.
+#[derive(Debug)]
+struct SectionRegions {
+    /// Whether the whole section is inside of `confine_to`, or `None` if only
+    /// part of it is.
+    is_confined: Option<bool>,
+    /// Whether a region to avoid covers the whole section.
+    is_avoided: bool,
+    /// The indexes of the regions to avoid that cover part of the section.
+    partially_avoided: Vec<usize>,
+}
+
+impl RegionLayer {
+    pub fn new(avoid: Vec<AABB>, avoidance: RegionAvoidance, confine_to: Option<AABB>) -> Self {
+        Self {
+            avoid,
+            avoidance,
+            confine_to,
+            sections: Default::default(),
+        }
+    }
+
+    /// Whether there are no regions, so the layer doesn't do anything.
+    pub fn is_empty(&self) -> bool {
+        self.avoid.is_empty() && self.confine_to.is_none()
+    }
+
+    /// The extra cost for standing at this position, which is infinite if we
+    /// can't go there at all.
+    pub fn cost(&self, pos: BlockPos) -> f32 {
+        let section_pos = ChunkSectionPos::from(pos);
+        let mut sections = self.sections.borrow_mut();
+        let section = sections
+            .entry(section_pos)
+            .or_insert_with(|| self.regions_in_section(section_pos));
+
+        let center = pos.center();
+        let is_confined = section.is_confined.unwrap_or_else(|| {
+            self.confine_to
+                .is_none_or(|confine_to| confine_to.contains(center))
+        });
+        if !is_confined {
+            return f32::INFINITY;
+        }
+
+        let is_avoided = section.is_avoided
+            || section
+                .partially_avoided
+                .iter()
+                .any(|&i| self.avoid[i].contains(center));
+        if !is_avoided {
+            return 0.;
+        }
+        match self.avoidance {
+            RegionAvoidance::Hard => f32::INFINITY,
+            RegionAvoidance::Soft(penalty) => penalty,
+        }
+    }
+
+    fn regions_in_section(&self, section_pos: ChunkSectionPos) -> SectionRegions {
+        let min = Vec3::new(
+            section_pos.x as f64 * 16.,
+            section_pos.y as f64 * 16.,
+            section_pos.z as f64 * 16.,
+        );
+        let section = AABB {
+            min,
+            max: min + Vec3::new(16., 16., 16.),
+        };
+
+        let is_confined = match self.confine_to {
+            None => Some(true),
+            Some(confine_to) if covers(&confine_to, &section) => Some(true),
+            Some(confine_to) if !confine_to.intersects_aabb(&section) => Some(false),
+            Some(_) => None,
+        };
+
+        let mut is_avoided = false;
+        let mut partially_avoided = Vec::new();
+        for (i, region) in self.avoid.iter().enumerate() {
+            if covers(region, &section) {
+                is_avoided = true;
+                break;
+            }
+            if region.intersects_aabb(&section) {
+                partially_avoided.push(i);
+            }
+        }
+
+        SectionRegions {
+            is_confined,
+            is_avoided,
+            partially_avoided,
+        }
+    }
+}
+
+/// Whether `region` contains all of `section`.
+fn covers(region: &AABB, section: &AABB) -> bool {
+    region.min.x <= section.min.x
+        && region.min.y <= section.min.y
+        && region.min.z <= section.min.z
+        && region.max.x >= section.max.x
+        && region.max.y >= section.max.y
+        && region.max.z >= section.max.z
+}
+
+/// Add the cost of the regions to the edges that were just generated, and
+/// remove the ones that go somewhere that we can't be.
+pub fn apply_region_costs(world: &CachedWorld, edges: &mut Vec<Edge<RelBlockPos, MoveData>>) {
+    edges.retain_mut(|edge| {
+        let cost = world.region_cost(edge.movement.target);
+        edge.cost += cost;
+        cost.is_finite()
+    });
+}
@@ -1,4 +1,7 @@
-use std::ops::{Add, Mul};
+use std::{
+    hash::{Hash, Hasher},
+    ops::{Add, Mul},
+};
 
 use azalea_core::position::BlockPos;
 
@@ -7,7 +10,7 @@ use azalea_core::position::BlockPos;
 /// This fits in 64 bits, so it's more efficient than a BlockPos in some cases.
 ///
 /// The X and Z are limited to ±32k.
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
 #[repr(C)]
 pub struct RelBlockPos {
     pub x: i16,
@@ -16,6 +19,22 @@ pub struct RelBlockPos {
     pub y: i32,
 }
 
+impl Hash for RelBlockPos {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let packed = (self.x as u16 as u64)
+            | ((self.z as u16 as u64) << 16)
+            | ((self.y as u32 as u64) << 32);
+        // the bits are mixed here since the hash is used directly by nohash_hasher,
+        // and otherwise most of the positions close to each other would end up in
+        // the same bucket
+        state.write_u64(packed.wrapping_mul(0xf135_7aea_2e62_a9c5).rotate_left(26));
+    }
+}
+/// nohash_hasher lets us have IntMap<RelBlockPos, _>, which is what the A*
+/// search uses to look up nodes
+impl nohash_hasher::IsEnabled for RelBlockPos {}
+
 impl RelBlockPos {
     pub fn get_origin(origin: BlockPos) -> Self {
         Self::new(0, origin.y, 0)
@@ -0,0 +1,168 @@
+//! Keeping track of the items that a path uses up, so we can notice that the
+//! inventory doesn't have enough of them before we start executing it.
+
+use azalea_inventory::Menu;
+use azalea_registry::Item;
+
+use super::{astar, moves::MoveData};
+
+/// The blocks that are used for [`Consumable::ThrowawayBlock`] by default. This
+/// can be changed with [`GotoOptions::throwaway_blocks`].
+///
+/// [`GotoOptions::throwaway_blocks`]: super::GotoOptions::throwaway_blocks
+pub const DEFAULT_THROWAWAY_BLOCKS: [Item; 5] = [
+    Item::Cobblestone,
+    Item::CobbledDeepslate,
+    Item::Dirt,
+    Item::Netherrack,
+    Item::Stone,
+];
+
+/// An item that some moves use.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Consumable {
+    /// A cheap full block that can be placed while bridging or pillaring, like
+    /// cobblestone or dirt.
+    ThrowawayBlock,
+    WaterBucket,
+    Torch,
+    Scaffolding,
+    Boat,
+}
+impl Consumable {
+    pub const ALL: [Consumable; 5] = [
+        Consumable::ThrowawayBlock,
+        Consumable::WaterBucket,
+        Consumable::Torch,
+        Consumable::Scaffolding,
+        Consumable::Boat,
+    ];
+
+    /// Whether the item gets picked back up after it's used, so a path only
+    /// ever needs one of it.
+    pub fn is_reusable(self) -> bool {
+        matches!(self, Consumable::WaterBucket | Consumable::Boat)
+    }
+
+    /// Whether the given item can be used for this.
+    pub fn matches(self, item: Item) -> bool {
+        match self {
+            Consumable::ThrowawayBlock => DEFAULT_THROWAWAY_BLOCKS.contains(&item),
+            Consumable::WaterBucket => item == Item::WaterBucket,
+            Consumable::Torch => item == Item::Torch,
+            Consumable::Scaffolding => item == Item::Scaffolding,
+            Consumable::Boat => matches!(
+                item,
+                Item::OakBoat
+                    | Item::SpruceBoat
+                    | Item::BirchBoat
+                    | Item::JungleBoat
+                    | Item::AcaciaBoat
+                    | Item::CherryBoat
+                    | Item::DarkOakBoat
+                    | Item::PaleOakBoat
+                    | Item::MangroveBoat
+                    | Item::BambooRaft
+            ),
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// An amount of each [`Consumable`].
+///
+/// This is used both for what's in the bot's inventory and for what a path
+/// needs (in which case it's also called the path's requirements).
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ConsumableCounts([u32; Consumable::ALL.len()]);
+impl ConsumableCounts {
+    /// Count the consumables in the player's inventory.
+    pub fn from_menu(menu: &Menu) -> Self {
+        let mut counts = Self::default();
+        let slots = menu.slots();
+        for slot in &slots[menu.player_slots_range()] {
+            let item = slot.kind();
+            for consumable in Consumable::ALL {
+                if consumable.matches(item) {
+                    counts.0[consumable.index()] += slot.count().max(0) as u32;
+                }
+            }
+        }
+        counts
+    }
+
+    /// Count our throwaway blocks as the given items in our hotbar, instead of
+    /// the default ones anywhere in our inventory.
+    ///
+    /// We can only place blocks that are in our hotbar, so this is what should
+    /// be used when we're actually going to place them.
+    pub fn with_throwaway_blocks(mut self, menu: &Menu, throwaway_blocks: &[Item]) -> Self {
+        let count = menu.slots()[menu.hotbar_slots_range()]
+            .iter()
+            .filter(|slot| throwaway_blocks.contains(&slot.kind()))
+            .map(|slot| slot.count().max(0) as u32)
+            .sum();
+        self.set(Consumable::ThrowawayBlock, count);
+        self
+    }
+
+    /// Count the consumables that the moves in the path use.
+    pub fn from_path<'a, P: 'a + std::hash::Hash + Copy>(
+        path: impl IntoIterator<Item = &'a astar::Edge<P, MoveData>>,
+    ) -> Self {
+        let mut counts = Self::default();
+        for edge in path {
+            for &consumable in edge.movement.data.consumes {
+                if consumable.is_reusable() {
+                    counts.0[consumable.index()] = 1;
+                } else {
+                    counts.0[consumable.index()] += 1;
+                }
+            }
+        }
+        counts
+    }
+
+    pub fn get(&self, consumable: Consumable) -> u32 {
+        self.0[consumable.index()]
+    }
+    pub fn set(&mut self, consumable: Consumable, count: u32) {
+        self.0[consumable.index()] = count;
+    }
+
+    pub fn has(&self, consumable: Consumable) -> bool {
+        self.get(consumable) > 0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().all(|&count| count == 0)
+    }
+
+    /// The consumables that we need more of than are available.
+    pub fn shortfall(&self, available: &ConsumableCounts) -> Vec<Consumable> {
+        Consumable::ALL
+            .into_iter()
+            .filter(|&consumable| self.get(consumable) > available.get(consumable))
+            .collect()
+    }
+
+    /// What's left after a path with the given requirements uses up its
+    /// consumables.
+    ///
+    /// Reusable consumables get picked back up, so we still have as many of
+    /// them as before.
+    pub fn after_using(mut self, requirements: &ConsumableCounts) -> Self {
+        for consumable in Consumable::ALL {
+            if !consumable.is_reusable() {
+                let count = self
+                    .get(consumable)
+                    .saturating_sub(requirements.get(consumable));
+                self.set(consumable, count);
+            }
+        }
+        self
+    }
+}
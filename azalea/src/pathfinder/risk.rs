@@ -0,0 +1,200 @@
+//! An optional cost layer that makes the pathfinder prefer lit or covered
+//! routes at night, when hostile mobs spawn on the surface.
+//!
+//! Azalea doesn't keep track of light levels, so the light at a position is
+//! estimated from the light-emitting blocks around it, and whether it's open to
+//! the sky is estimated from the highest block in its column. Both of these are
+//! only calculated when they're first needed.
+
+use std::{cell::RefCell, collections::HashMap};
+
+use azalea_core::position::{BlockPos, ChunkSectionBlockPos, ChunkSectionPos};
+use azalea_registry::{Block, EntityKind};
+use bevy_ecs::component::Component;
+
+use super::{costs::WALK_ONE_BLOCK_COST, world::CachedWorld};
+
+/// Add this component to a bot to make its pathfinder avoid dark places that
+/// are open to the sky at night, and to stay away from hostile mobs.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct AvoidDarknessAtNight {
+    /// The cost that's added for every dark position that's open to the sky.
+    pub dark_penalty: f32,
+    /// The cost that's added for every position that's within
+    /// [`Self::hostile_radius`] blocks of a hostile mob. This is scaled by how
+    /// close the mob is.
+    pub hostile_penalty: f32,
+    pub hostile_radius: i32,
+}
+impl Default for AvoidDarknessAtNight {
+    fn default() -> Self {
+        Self {
+            dark_penalty: WALK_ONE_BLOCK_COST * 2.,
+            hostile_penalty: WALK_ONE_BLOCK_COST * 5.,
+            hostile_radius: 8,
+        }
+    }
+}
+
+/// Positions with an estimated block light level below this are considered
+/// dark.
+pub const MIN_LIGHT_LEVEL: u8 = 8;
+
+/// Whether mobs can spawn on the surface at this time of day.
+pub fn is_night(day_time: u64) -> bool {
+    (13_000..23_000).contains(&(day_time % 24_000))
+}
+
+/// The data that's needed to calculate the risk cost of positions while we're
+/// calculating a path.
+#[derive(Debug, Default)]
+pub struct RiskLayer {
+    pub settings: AvoidDarknessAtNight,
+    pub hostiles: Vec<BlockPos>,
+
+    /// The y coordinate of the highest block in each column that covers the
+    /// blocks under it from the sky.
+    sky_heights: RefCell<HashMap<(i32, i32), i32>>,
+    /// The light-emitting blocks in each section and how bright they are.
+    light_sources: RefCell<HashMap<ChunkSectionPos, Vec<(BlockPos, u8)>>>,
+}
+impl Clone for RiskLayer {
+    fn clone(&self) -> Self {
+        // the caches are only valid for the world they were made with
+        Self::new(self.settings, self.hostiles.clone())
+    }
+}
+
+impl RiskLayer {
+    pub fn new(settings: AvoidDarknessAtNight, hostiles: Vec<BlockPos>) -> Self {
+        Self {
+            settings,
+            hostiles,
+            sky_heights: Default::default(),
+            light_sources: Default::default(),
+        }
+    }
+
+    /// The extra cost for standing at this position.
+    pub fn cost(&self, world: &CachedWorld, pos: BlockPos) -> f32 {
+        let mut cost = 0.;
+
+        if self.is_exposed_to_sky(world, pos) && self.light_level(world, pos) < MIN_LIGHT_LEVEL {
+            cost += self.settings.dark_penalty;
+        }
+
+        let radius = self.settings.hostile_radius;
+        for hostile in &self.hostiles {
+            let distance = (hostile.distance_squared_to(pos) as f32).sqrt();
+            if distance < radius as f32 {
+                cost += self.settings.hostile_penalty * (1. - distance / radius as f32);
+            }
+        }
+
+        cost
+    }
+
+    fn is_exposed_to_sky(&self, world: &CachedWorld, pos: BlockPos) -> bool {
+        let mut sky_heights = self.sky_heights.borrow_mut();
+        let sky_height = *sky_heights
+            .entry((pos.x, pos.z))
+            .or_insert_with(|| world.highest_covering_block(pos.x, pos.z));
+        // the block that we're standing on doesn't count as cover
+        pos.y > sky_height
+    }
+
+    /// Estimate the block light level at the position from the light sources
+    /// around it.
+    fn light_level(&self, world: &CachedWorld, pos: BlockPos) -> u8 {
+        let center_section = ChunkSectionPos::from(pos);
+        let mut light_sources = self.light_sources.borrow_mut();
+
+        let mut light_level = 0;
+        // light can't go further than 15 blocks, so the adjacent sections are enough
+        for x in -1..=1 {
+            for y in -1..=1 {
+                for z in -1..=1 {
+                    let section_pos = center_section + ChunkSectionPos::new(x, y, z);
+                    let sources = light_sources
+                        .entry(section_pos)
+                        .or_insert_with(|| light_sources_in_section(world, section_pos));
+                    for &(source_pos, emission) in sources.iter() {
+                        let distance = (source_pos.x - pos.x).abs()
+                            + (source_pos.y - pos.y).abs()
+                            + (source_pos.z - pos.z).abs();
+                        let light = emission.saturating_sub(distance.min(15) as u8);
+                        light_level = light_level.max(light);
+                    }
+                }
+            }
+        }
+        light_level
+    }
+}
+
+fn light_sources_in_section(
+    world: &CachedWorld,
+    section_pos: ChunkSectionPos,
+) -> Vec<(BlockPos, u8)> {
+    let mut sources = Vec::new();
+    world.for_each_block_in_section(section_pos, |index, block_state| {
+        let emission = light_emission(Block::from(block_state));
+        if emission > 0 {
+            let section_block_pos = ChunkSectionBlockPos::new(
+                (index & 0xf) as u8,
+                ((index >> 8) & 0xf) as u8,
+                ((index >> 4) & 0xf) as u8,
+            );
+            sources.push((section_pos + section_block_pos, emission));
+        }
+    });
+    sources
+}
+
+/// How much light this block gives off. This only includes common light
+/// sources.
+pub fn light_emission(block: Block) -> u8 {
+    match block {
+        Block::Lantern
+        | Block::Glowstone
+        | Block::SeaLantern
+        | Block::JackOLantern
+        | Block::Shroomlight
+        | Block::Beacon
+        | Block::OchreFroglight
+        | Block::VerdantFroglight
+        | Block::PearlescentFroglight
+        | Block::Lava
+        | Block::Fire => 15,
+        Block::Torch | Block::WallTorch | Block::EndRod => 14,
+        Block::SoulTorch | Block::SoulWallTorch | Block::SoulLantern | Block::SoulFire => 10,
+        Block::RedstoneTorch | Block::RedstoneWallTorch => 7,
+        _ => 0,
+    }
+}
+
+/// Whether this is a mob that attacks players on sight.
+pub fn is_hostile(kind: EntityKind) -> bool {
+    matches!(
+        kind,
+        EntityKind::Zombie
+            | EntityKind::ZombieVillager
+            | EntityKind::Husk
+            | EntityKind::Drowned
+            | EntityKind::Skeleton
+            | EntityKind::Stray
+            | EntityKind::Bogged
+            | EntityKind::Creeper
+            | EntityKind::Spider
+            | EntityKind::CaveSpider
+            | EntityKind::Witch
+            | EntityKind::Phantom
+            | EntityKind::Pillager
+            | EntityKind::Vindicator
+            | EntityKind::Evoker
+            | EntityKind::Slime
+            | EntityKind::MagmaCube
+            | EntityKind::Blaze
+            | EntityKind::WitherSkeleton
+    )
+}
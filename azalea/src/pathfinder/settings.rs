@@ -0,0 +1,497 @@
+//! Pathfinder settings that can be set for every bot, for a single bot, or for
+//! a single goto.
+//!
+//! When a path is calculated, the [`GotoOptions`] from the [`GotoEvent`] take
+//! precedence, then the bot's [`PathfinderSettings`] component, and then the
+//! [`PathfinderDefaults`] resource.
+
+use std::time::Duration;
+
+use azalea_core::aabb::AABB;
+use azalea_registry::Item;
+use bevy_ecs::prelude::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{
+    GotoEvent, GotoOptions, astar::PathfinderTimeout, elytra::DEFAULT_ELYTRA_THRESHOLD,
+    long_distance::DEFAULT_LONG_DISTANCE_THRESHOLD, mobs::MobAvoidance,
+    moves::water::LOW_AIR_THRESHOLD, regions::RegionAvoidance,
+    requirements::DEFAULT_THROWAWAY_BLOCKS,
+};
+
+/// The pathfinder settings that are used for every bot, unless they're
+/// overridden by a [`PathfinderSettings`] component or the [`GotoEvent`].
+///
+/// See [`GotoOptions`] for what each of these do.
+#[derive(Resource, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize), serde(default))]
+pub struct PathfinderDefaults {
+    pub allow_mining: bool,
+    pub retry_on_no_path: bool,
+    pub min_timeout: PathfinderTimeout,
+    pub max_timeout: PathfinderTimeout,
+    pub careful_mode_multiplier: f32,
+    pub avoid_damage: bool,
+    pub allow_placing: bool,
+    pub throwaway_blocks: Vec<Item>,
+    pub max_fall_damage: f32,
+    pub smooth_path: bool,
+    pub avoid_regions: Vec<AABB>,
+    pub region_avoidance: RegionAvoidance,
+    pub confine_to: Option<AABB>,
+    /// How many partial paths in a row can end without getting any closer to
+    /// the goal before we give up on it, like when the goal is in chunks that
+    /// never load.
+    pub max_stalled_partial_paths: u32,
+    /// How much air we need to have left, in ticks, before we'll swim any
+    /// deeper without a heavy penalty. A player has 300 ticks of air.
+    pub low_air_threshold: i32,
+    /// How far away (in blocks) the goal has to be before we find a rough
+    /// route through chunks first and then only search for the path along it.
+    /// This is a lot faster for faraway goals, but the path might not be the
+    /// shortest one. Setting it to infinity disables it.
+    pub long_distance_threshold: f32,
+    /// Whether we can jump over gaps.
+    pub allow_parkour: bool,
+    /// Whether we can go into water. Moves that get us out of water are still
+    /// allowed, so we don't get stuck if we end up in it anyways.
+    pub allow_water: bool,
+    /// Whether we can walk diagonally between blocks.
+    pub allow_diagonal: bool,
+    /// How much more expensive moves that start or end in water should be.
+    /// Higher values make us prefer walking around water.
+    pub water_cost_multiplier: f32,
+    /// How much more expensive breaking blocks should be. Higher values make us
+    /// prefer walking around blocks over mining through them.
+    pub mining_cost_multiplier: f32,
+    /// Whether we can fly to faraway goals with an elytra. This only happens
+    /// if we're wearing one and have firework rockets in our hotbar.
+    pub allow_elytra: bool,
+    /// How far away (in blocks) the goal has to be before we fly to it instead
+    /// of walking, if [`Self::allow_elytra`] is true.
+    pub elytra_threshold: f32,
+    /// Whether we can cross large bodies of water in a boat. This only happens
+    /// if we have a boat in our hotbar.
+    pub allow_boats: bool,
+    /// The kinds of mobs that we stay away from, and how much we care about
+    /// it. This is empty by default, but [`DEFAULT_AVOIDED_MOBS`] has some
+    /// reasonable values for creepers and skeletons.
+    ///
+    /// When one of these mobs moves closer to the path that we're walking,
+    /// the path is rerouted around it.
+    ///
+    /// [`DEFAULT_AVOIDED_MOBS`]: super::mobs::DEFAULT_AVOIDED_MOBS
+    pub avoid_mobs: Vec<MobAvoidance>,
+    /// Whether we can sprint while walking along paths. We only sprint on
+    /// straight parts of the path and slow down before corners, but turning
+    /// this off makes overshooting even less likely. Jumps that can't be made
+    /// without sprinting still sprint.
+    pub sprint: bool,
+}
+impl Default for PathfinderDefaults {
+    fn default() -> Self {
+        Self {
+            allow_mining: true,
+            retry_on_no_path: true,
+            min_timeout: PathfinderTimeout::Time(Duration::from_secs(1)),
+            max_timeout: PathfinderTimeout::Time(Duration::from_secs(5)),
+            careful_mode_multiplier: 1.,
+            avoid_damage: false,
+            allow_placing: false,
+            throwaway_blocks: DEFAULT_THROWAWAY_BLOCKS.to_vec(),
+            max_fall_damage: 0.5,
+            smooth_path: true,
+            avoid_regions: Vec::new(),
+            region_avoidance: RegionAvoidance::Hard,
+            confine_to: None,
+            max_stalled_partial_paths: 10,
+            low_air_threshold: LOW_AIR_THRESHOLD,
+            long_distance_threshold: DEFAULT_LONG_DISTANCE_THRESHOLD,
+            allow_parkour: true,
+            allow_water: true,
+            allow_diagonal: true,
+            water_cost_multiplier: 1.,
+            mining_cost_multiplier: 1.,
+            allow_elytra: false,
+            elytra_threshold: DEFAULT_ELYTRA_THRESHOLD,
+            allow_boats: false,
+            avoid_mobs: Vec::new(),
+            sprint: true,
+        }
+    }
+}
+impl PathfinderDefaults {
+    /// The settings that are passed to the move generators.
+    pub fn move_settings(&self) -> MoveSettings {
+        MoveSettings {
+            allow_parkour: self.allow_parkour,
+            allow_water: self.allow_water,
+            allow_diagonal: self.allow_diagonal,
+            water_cost_multiplier: self.water_cost_multiplier,
+            mining_cost_multiplier: self.mining_cost_multiplier,
+            allow_boats: self.allow_boats,
+            sprint: self.sprint,
+        }
+    }
+}
+
+/// The settings that change which moves the pathfinder can use and how much
+/// they cost.
+///
+/// See [`PathfinderDefaults`] for what each of these do. Mining and placing
+/// blocks are controlled by [`GotoOptions::allow_mining`] and
+/// [`GotoOptions::allow_placing`] instead.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MoveSettings {
+    pub allow_parkour: bool,
+    pub allow_water: bool,
+    pub allow_diagonal: bool,
+    pub water_cost_multiplier: f32,
+    /// This is applied by the [`MiningCache`] instead of by the moves, since
+    /// that's where the cost of breaking blocks is calculated.
+    ///
+    /// [`MiningCache`]: super::mining::MiningCache
+    pub mining_cost_multiplier: f32,
+    pub allow_boats: bool,
+    /// This is used while executing the path instead of by the moves.
+    pub sprint: bool,
+}
+impl Default for MoveSettings {
+    fn default() -> Self {
+        PathfinderDefaults::default().move_settings()
+    }
+}
+
+/// A component that overrides the [`PathfinderDefaults`] for a single bot.
+///
+/// Settings that are `None` fall back to the defaults.
+///
+/// ```
+/// # use azalea::pathfinder::settings::PathfinderSettings;
+/// let settings = PathfinderSettings::default()
+///     .with_allow_mining(false)
+///     .with_careful_mode_multiplier(2.);
+/// ```
+#[derive(Component, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize), serde(default))]
+pub struct PathfinderSettings {
+    pub allow_mining: Option<bool>,
+    pub retry_on_no_path: Option<bool>,
+    pub min_timeout: Option<PathfinderTimeout>,
+    pub max_timeout: Option<PathfinderTimeout>,
+    pub careful_mode_multiplier: Option<f32>,
+    pub avoid_damage: Option<bool>,
+    pub allow_placing: Option<bool>,
+    pub throwaway_blocks: Option<Vec<Item>>,
+    pub max_fall_damage: Option<f32>,
+    pub smooth_path: Option<bool>,
+    pub avoid_regions: Option<Vec<AABB>>,
+    pub region_avoidance: Option<RegionAvoidance>,
+    pub confine_to: Option<AABB>,
+    pub max_stalled_partial_paths: Option<u32>,
+    pub low_air_threshold: Option<i32>,
+    pub long_distance_threshold: Option<f32>,
+    pub allow_parkour: Option<bool>,
+    pub allow_water: Option<bool>,
+    pub allow_diagonal: Option<bool>,
+    pub water_cost_multiplier: Option<f32>,
+    pub mining_cost_multiplier: Option<f32>,
+    pub allow_elytra: Option<bool>,
+    pub elytra_threshold: Option<f32>,
+    pub allow_boats: Option<bool>,
+    pub avoid_mobs: Option<Vec<MobAvoidance>>,
+    pub sprint: Option<bool>,
+}
+
+impl PathfinderSettings {
+    pub fn with_allow_mining(mut self, allow_mining: bool) -> Self {
+        self.allow_mining = Some(allow_mining);
+        self
+    }
+    pub fn with_retry_on_no_path(mut self, retry_on_no_path: bool) -> Self {
+        self.retry_on_no_path = Some(retry_on_no_path);
+        self
+    }
+    pub fn with_min_timeout(mut self, min_timeout: PathfinderTimeout) -> Self {
+        self.min_timeout = Some(min_timeout);
+        self
+    }
+    pub fn with_max_timeout(mut self, max_timeout: PathfinderTimeout) -> Self {
+        self.max_timeout = Some(max_timeout);
+        self
+    }
+    pub fn with_careful_mode_multiplier(mut self, careful_mode_multiplier: f32) -> Self {
+        self.careful_mode_multiplier = Some(careful_mode_multiplier);
+        self
+    }
+    pub fn with_avoid_damage(mut self, avoid_damage: bool) -> Self {
+        self.avoid_damage = Some(avoid_damage);
+        self
+    }
+    pub fn with_allow_placing(mut self, allow_placing: bool) -> Self {
+        self.allow_placing = Some(allow_placing);
+        self
+    }
+    pub fn with_throwaway_blocks(mut self, throwaway_blocks: Vec<Item>) -> Self {
+        self.throwaway_blocks = Some(throwaway_blocks);
+        self
+    }
+    pub fn with_max_fall_damage(mut self, max_fall_damage: f32) -> Self {
+        self.max_fall_damage = Some(max_fall_damage);
+        self
+    }
+    pub fn with_smooth_path(mut self, smooth_path: bool) -> Self {
+        self.smooth_path = Some(smooth_path);
+        self
+    }
+    pub fn with_avoid_regions(mut self, avoid_regions: Vec<AABB>) -> Self {
+        self.avoid_regions = Some(avoid_regions);
+        self
+    }
+    pub fn with_region_avoidance(mut self, region_avoidance: RegionAvoidance) -> Self {
+        self.region_avoidance = Some(region_avoidance);
+        self
+    }
+    pub fn with_confine_to(mut self, confine_to: AABB) -> Self {
+        self.confine_to = Some(confine_to);
+        self
+    }
+    pub fn with_max_stalled_partial_paths(mut self, max_stalled_partial_paths: u32) -> Self {
+        self.max_stalled_partial_paths = Some(max_stalled_partial_paths);
+        self
+    }
+    pub fn with_low_air_threshold(mut self, low_air_threshold: i32) -> Self {
+        self.low_air_threshold = Some(low_air_threshold);
+        self
+    }
+    pub fn with_long_distance_threshold(mut self, long_distance_threshold: f32) -> Self {
+        self.long_distance_threshold = Some(long_distance_threshold);
+        self
+    }
+    pub fn with_allow_parkour(mut self, allow_parkour: bool) -> Self {
+        self.allow_parkour = Some(allow_parkour);
+        self
+    }
+    pub fn with_allow_water(mut self, allow_water: bool) -> Self {
+        self.allow_water = Some(allow_water);
+        self
+    }
+    pub fn with_allow_diagonal(mut self, allow_diagonal: bool) -> Self {
+        self.allow_diagonal = Some(allow_diagonal);
+        self
+    }
+    pub fn with_water_cost_multiplier(mut self, water_cost_multiplier: f32) -> Self {
+        self.water_cost_multiplier = Some(water_cost_multiplier);
+        self
+    }
+    pub fn with_mining_cost_multiplier(mut self, mining_cost_multiplier: f32) -> Self {
+        self.mining_cost_multiplier = Some(mining_cost_multiplier);
+        self
+    }
+    pub fn with_allow_elytra(mut self, allow_elytra: bool) -> Self {
+        self.allow_elytra = Some(allow_elytra);
+        self
+    }
+    pub fn with_elytra_threshold(mut self, elytra_threshold: f32) -> Self {
+        self.elytra_threshold = Some(elytra_threshold);
+        self
+    }
+    pub fn with_allow_boats(mut self, allow_boats: bool) -> Self {
+        self.allow_boats = Some(allow_boats);
+        self
+    }
+    pub fn with_avoid_mobs(mut self, avoid_mobs: Vec<MobAvoidance>) -> Self {
+        self.avoid_mobs = Some(avoid_mobs);
+        self
+    }
+    pub fn with_sprint(mut self, sprint: bool) -> Self {
+        self.sprint = Some(sprint);
+        self
+    }
+
+    /// Fill in the settings that aren't set here with the ones from
+    /// `fallback`.
+    pub fn or(&self, fallback: &PathfinderSettings) -> PathfinderSettings {
+        PathfinderSettings {
+            allow_mining: self.allow_mining.or(fallback.allow_mining),
+            retry_on_no_path: self.retry_on_no_path.or(fallback.retry_on_no_path),
+            min_timeout: self.min_timeout.or(fallback.min_timeout),
+            max_timeout: self.max_timeout.or(fallback.max_timeout),
+            careful_mode_multiplier: self
+                .careful_mode_multiplier
+                .or(fallback.careful_mode_multiplier),
+            avoid_damage: self.avoid_damage.or(fallback.avoid_damage),
+            allow_placing: self.allow_placing.or(fallback.allow_placing),
+            throwaway_blocks: self
+                .throwaway_blocks
+                .clone()
+                .or_else(|| fallback.throwaway_blocks.clone()),
+            max_fall_damage: self.max_fall_damage.or(fallback.max_fall_damage),
+            smooth_path: self.smooth_path.or(fallback.smooth_path),
+            avoid_regions: self
+                .avoid_regions
+                .clone()
+                .or_else(|| fallback.avoid_regions.clone()),
+            region_avoidance: self.region_avoidance.or(fallback.region_avoidance),
+            confine_to: self.confine_to.or(fallback.confine_to),
+            max_stalled_partial_paths: self
+                .max_stalled_partial_paths
+                .or(fallback.max_stalled_partial_paths),
+            low_air_threshold: self.low_air_threshold.or(fallback.low_air_threshold),
+            long_distance_threshold: self
+                .long_distance_threshold
+                .or(fallback.long_distance_threshold),
+            allow_parkour: self.allow_parkour.or(fallback.allow_parkour),
+            allow_water: self.allow_water.or(fallback.allow_water),
+            allow_diagonal: self.allow_diagonal.or(fallback.allow_diagonal),
+            water_cost_multiplier: self
+                .water_cost_multiplier
+                .or(fallback.water_cost_multiplier),
+            mining_cost_multiplier: self
+                .mining_cost_multiplier
+                .or(fallback.mining_cost_multiplier),
+            allow_elytra: self.allow_elytra.or(fallback.allow_elytra),
+            elytra_threshold: self.elytra_threshold.or(fallback.elytra_threshold),
+            allow_boats: self.allow_boats.or(fallback.allow_boats),
+            avoid_mobs: self
+                .avoid_mobs
+                .clone()
+                .or_else(|| fallback.avoid_mobs.clone()),
+            sprint: self.sprint.or(fallback.sprint),
+        }
+    }
+
+    /// Fill in the settings that aren't set here with the defaults.
+    pub fn resolve(&self, defaults: &PathfinderDefaults) -> PathfinderDefaults {
+        PathfinderDefaults {
+            allow_mining: self.allow_mining.unwrap_or(defaults.allow_mining),
+            retry_on_no_path: self.retry_on_no_path.unwrap_or(defaults.retry_on_no_path),
+            min_timeout: self.min_timeout.unwrap_or(defaults.min_timeout),
+            max_timeout: self.max_timeout.unwrap_or(defaults.max_timeout),
+            careful_mode_multiplier: self
+                .careful_mode_multiplier
+                .unwrap_or(defaults.careful_mode_multiplier),
+            avoid_damage: self.avoid_damage.unwrap_or(defaults.avoid_damage),
+            allow_placing: self.allow_placing.unwrap_or(defaults.allow_placing),
+            throwaway_blocks: self
+                .throwaway_blocks
+                .clone()
+                .unwrap_or_else(|| defaults.throwaway_blocks.clone()),
+            max_fall_damage: self.max_fall_damage.unwrap_or(defaults.max_fall_damage),
+            smooth_path: self.smooth_path.unwrap_or(defaults.smooth_path),
+            avoid_regions: self
+                .avoid_regions
+                .clone()
+                .unwrap_or_else(|| defaults.avoid_regions.clone()),
+            region_avoidance: self.region_avoidance.unwrap_or(defaults.region_avoidance),
+            confine_to: self.confine_to.or(defaults.confine_to),
+            max_stalled_partial_paths: self
+                .max_stalled_partial_paths
+                .unwrap_or(defaults.max_stalled_partial_paths),
+            low_air_threshold: self.low_air_threshold.unwrap_or(defaults.low_air_threshold),
+            long_distance_threshold: self
+                .long_distance_threshold
+                .unwrap_or(defaults.long_distance_threshold),
+            allow_parkour: self.allow_parkour.unwrap_or(defaults.allow_parkour),
+            allow_water: self.allow_water.unwrap_or(defaults.allow_water),
+            allow_diagonal: self.allow_diagonal.unwrap_or(defaults.allow_diagonal),
+            water_cost_multiplier: self
+                .water_cost_multiplier
+                .unwrap_or(defaults.water_cost_multiplier),
+            mining_cost_multiplier: self
+                .mining_cost_multiplier
+                .unwrap_or(defaults.mining_cost_multiplier),
+            allow_elytra: self.allow_elytra.unwrap_or(defaults.allow_elytra),
+            elytra_threshold: self.elytra_threshold.unwrap_or(defaults.elytra_threshold),
+            allow_boats: self.allow_boats.unwrap_or(defaults.allow_boats),
+            avoid_mobs: self
+                .avoid_mobs
+                .clone()
+                .unwrap_or_else(|| defaults.avoid_mobs.clone()),
+            sprint: self.sprint.unwrap_or(defaults.sprint),
+        }
+    }
+}
+
+impl From<&GotoOptions> for PathfinderSettings {
+    fn from(options: &GotoOptions) -> Self {
+        Self {
+            allow_mining: options.allow_mining,
+            retry_on_no_path: options.retry_on_no_path,
+            min_timeout: options.min_timeout,
+            max_timeout: options.max_timeout,
+            careful_mode_multiplier: options.careful_mode_multiplier,
+            avoid_damage: options.avoid_damage,
+            allow_placing: options.allow_placing,
+            throwaway_blocks: options.throwaway_blocks.clone(),
+            max_fall_damage: options.max_fall_damage,
+            smooth_path: options.smooth_path,
+            avoid_regions: options.avoid_regions.clone(),
+            region_avoidance: options.region_avoidance,
+            confine_to: options.confine_to,
+            // these can't be set for a single goto
+            max_stalled_partial_paths: None,
+            low_air_threshold: None,
+            long_distance_threshold: None,
+            allow_parkour: None,
+            allow_water: None,
+            allow_diagonal: None,
+            water_cost_multiplier: None,
+            mining_cost_multiplier: None,
+            allow_elytra: None,
+            elytra_threshold: None,
+            allow_boats: None,
+            avoid_mobs: None,
+            sprint: None,
+        }
+    }
+}
+
+/// Resolve the settings that should be used for the goto, in order of
+/// precedence.
+pub fn resolve_settings(
+    event: &GotoEvent,
+    client_settings: Option<&PathfinderSettings>,
+    defaults: &PathfinderDefaults,
+) -> PathfinderDefaults {
+    let event_settings = PathfinderSettings::from(&event.options);
+    match client_settings {
+        Some(client_settings) => event_settings.or(client_settings),
+        None => event_settings,
+    }
+    .resolve(defaults)
+}
+
+#[cfg(test)]
+mod tests {
+    use azalea_core::position::BlockPos;
+
+    use super::*;
+    use crate::pathfinder::goals::BlockPosGoal;
+
+    #[test]
+    fn test_settings_precedence() {
+        let defaults = PathfinderDefaults {
+            careful_mode_multiplier: 3.,
+            ..Default::default()
+        };
+        let client_settings = PathfinderSettings::default()
+            .with_allow_mining(false)
+            .with_retry_on_no_path(false);
+        let event = GotoEvent::new(Entity::PLACEHOLDER, BlockPosGoal(BlockPos::new(0, 0, 0)))
+            .with_options(GotoOptions::default().retry_on_no_path(true));
+
+        let resolved = resolve_settings(&event, Some(&client_settings), &defaults);
+        // from the event
+        assert!(resolved.retry_on_no_path);
+        // from the client
+        assert!(!resolved.allow_mining);
+        // from the defaults
+        assert_eq!(resolved.careful_mode_multiplier, 3.);
+        assert_eq!(resolved.min_timeout, defaults.min_timeout);
+
+        let resolved = resolve_settings(&event, None, &defaults);
+        assert!(resolved.allow_mining);
+    }
+}
@@ -2,23 +2,43 @@
 
 use std::sync::Arc;
 
+use azalea_block::{BlockState, BlockStates, fluid_state::FluidKind, properties};
 use azalea_client::{
-    PhysicsState, interact::BlockStatePredictionHandler, inventory::Inventory,
-    local_player::LocalGameMode, mining::MineBundle, packet::game::SendPacketEvent,
+    PhysicsState,
+    block_update::QueuedServerBlockUpdates,
+    interact::BlockStatePredictionHandler,
+    inventory::Inventory,
+    local_player::LocalGameMode,
+    mining::{FinishMiningBlockEvent, MineBundle},
+    packet::game::SendPacketEvent,
 };
 use azalea_core::{
-    game_type::GameMode, position::Vec3, resource_location::ResourceLocation, tick::GameTick,
+    game_type::GameMode,
+    position::{BlockPos, Vec3},
+    resource_location::ResourceLocation,
+    tick::GameTick,
 };
 use azalea_entity::{
-    Attributes, EntityDimensions, LookDirection, Physics, Position, default_attributes,
+    Attributes, EntityDimensions, EyeHeight, LocalEntity, LookDirection, OptionalUnsignedInt,
+    Physics, Position, Riding, default_attributes,
+    metadata::{AttachedToTarget, FallFlying},
+    view_vector,
 };
+use azalea_protocol::packets::game::ServerboundGamePacket;
 use azalea_registry::EntityKind;
-use azalea_world::{ChunkStorage, Instance, InstanceContainer, MinecraftEntityId, PartialInstance};
+use azalea_world::{
+    ChunkStorage, Instance, InstanceContainer, InstanceName, MinecraftEntityId, PartialInstance,
+};
 use bevy_app::App;
 use bevy_ecs::prelude::*;
 use parking_lot::RwLock;
 use uuid::Uuid;
 
+use super::{
+    mining::is_falling_block,
+    world::{is_block_state_openable, is_block_state_passable, is_block_state_solid},
+};
+
 #[derive(Bundle, Clone)]
 pub struct SimulatedPlayerBundle {
     pub position: Position,
@@ -69,6 +89,8 @@ fn create_simulation_instance(chunks: ChunkStorage) -> (App, Arc<RwLock<Instance
         azalea_client::inventory::InventoryPlugin,
         azalea_client::mining::MiningPlugin,
         azalea_client::interact::InteractPlugin,
+        azalea_client::block_update::BlockUpdatePlugin,
+        azalea_client::vehicle::VehiclePlugin,
     ))
     .insert_resource(InstanceContainer {
         instances: [(instance_name.clone(), Arc::downgrade(&instance.clone()))]
@@ -78,6 +100,69 @@ fn create_simulation_instance(chunks: ChunkStorage) -> (App, Arc<RwLock<Instance
     })
     .add_event::<SendPacketEvent>();
 
+    // there's no server, so we have to open doors and place blocks ourselves when
+    // they're clicked
+    let interacted_instance = instance.clone();
+    app.add_observer(
+        move |trigger: Trigger<SendPacketEvent>, inventories: Query<&Inventory>| {
+            let event = trigger.event();
+            let ServerboundGamePacket::UseItemOn(p) = &event.packet else {
+                return;
+            };
+            if toggle_openable_block(&interacted_instance, p.block_hit.block_pos) {
+                return;
+            }
+            if let Ok(inventory) = inventories.get(event.sent_by) {
+                place_held_block(
+                    &interacted_instance,
+                    inventory,
+                    p.block_hit.block_pos + p.block_hit.direction.normal(),
+                );
+            }
+        },
+    );
+
+    // firework rockets are entities that the server spawns, so we make our own that
+    // boost the player like a real one would
+    app.add_observer(
+        |trigger: Trigger<SendPacketEvent>,
+         players: Query<(&Inventory, &MinecraftEntityId, &InstanceName, &FallFlying)>,
+         mut commands: Commands| {
+            let event = trigger.event();
+            let ServerboundGamePacket::UseItem(_) = &event.packet else {
+                return;
+            };
+            let Ok((inventory, entity_id, instance_name, fall_flying)) = players.get(event.sent_by)
+            else {
+                return;
+            };
+            if inventory.held_item().kind() == azalea_registry::Item::FireworkRocket
+                && **fall_flying
+            {
+                commands.spawn((
+                    SimulatedFirework {
+                        remaining_ticks: SIMULATED_FIREWORK_TICKS,
+                    },
+                    AttachedToTarget(OptionalUnsignedInt(Some(entity_id.0 as u32))),
+                    instance_name.clone(),
+                ));
+            }
+        },
+    )
+    .add_systems(GameTick, tick_simulated_fireworks);
+
+    // boats are also spawned by the server, and it's the one that decides when we
+    // get in and out of them
+    app.add_observer(place_simulated_boat)
+        .add_observer(mount_simulated_boat)
+        .add_observer(dismount_simulated_boat);
+
+    // sand and gravel fall when the block under them is broken, which is also
+    // done by the server
+    app.init_resource::<SimulatedFallingBlocks>()
+        .add_observer(queue_simulated_falling_blocks)
+        .add_systems(GameTick, drop_simulated_falling_blocks);
+
     app.edit_schedule(bevy_app::Main, |schedule| {
         schedule.set_executor_kind(bevy_ecs::schedule::ExecutorKind::SingleThreaded);
     });
@@ -85,6 +170,312 @@ fn create_simulation_instance(chunks: ChunkStorage) -> (App, Arc<RwLock<Instance
     (app, instance)
 }
 
+/// How long the firework rockets that the simulation makes boost the player
+/// for. Real ones last for a random amount of time around this.
+const SIMULATED_FIREWORK_TICKS: u32 = 20;
+
+/// A firework rocket that's boosting a player in the simulation.
+#[derive(Component)]
+struct SimulatedFirework {
+    remaining_ticks: u32,
+}
+
+fn tick_simulated_fireworks(
+    mut query: Query<(Entity, &mut SimulatedFirework)>,
+    mut commands: Commands,
+) {
+    for (entity, mut firework) in &mut query {
+        firework.remaining_ticks = firework.remaining_ticks.saturating_sub(1);
+        if firework.remaining_ticks == 0 {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+/// Gaps left by blocks that were broken in the simulation, which sand or
+/// gravel might fall into.
+#[derive(Resource, Default)]
+struct SimulatedFallingBlocks(Vec<(InstanceName, BlockPos)>);
+
+fn queue_simulated_falling_blocks(
+    trigger: Trigger<FinishMiningBlockEvent>,
+    players: Query<&InstanceName>,
+    mut falling_blocks: ResMut<SimulatedFallingBlocks>,
+) {
+    if let Ok(instance_name) = players.get(trigger.target()) {
+        falling_blocks
+            .0
+            .push((instance_name.clone(), trigger.event().position));
+    }
+}
+
+/// Move falling blocks down into the gaps under them, one block per tick. The
+/// real server turns them into entities that fall, but they end up in the same
+/// place.
+fn drop_simulated_falling_blocks(
+    mut falling_blocks: ResMut<SimulatedFallingBlocks>,
+    instance_container: Res<InstanceContainer>,
+) {
+    falling_blocks.0.retain_mut(|(instance_name, gap)| {
+        let Some(instance) = instance_container.get(instance_name) else {
+            return false;
+        };
+        let instance = instance.read();
+        let above = instance.get_block_state(gap.up(1)).unwrap_or_default();
+        if !instance.get_block_state(*gap).unwrap_or_default().is_air() || !is_falling_block(above)
+        {
+            return false;
+        }
+        instance.set_block_state(*gap, above);
+        instance.set_block_state(gap.up(1), BlockState::AIR);
+        // the block that was on top of it falls next
+        *gap = gap.up(1);
+        true
+    });
+}
+
+/// A boat that a player put on the water in the simulation.
+#[derive(Component)]
+struct SimulatedBoat;
+
+/// Put a boat on the water or block that the player is looking at when they
+/// use one, like the server would.
+fn place_simulated_boat(
+    trigger: Trigger<SendPacketEvent>,
+    players: Query<(
+        &Inventory,
+        &Position,
+        &EyeHeight,
+        &LookDirection,
+        &InstanceName,
+    )>,
+    boats: Query<(), With<SimulatedBoat>>,
+    instance_container: Res<InstanceContainer>,
+    mut commands: Commands,
+) {
+    let event = trigger.event();
+    let ServerboundGamePacket::UseItem(_) = &event.packet else {
+        return;
+    };
+    let Ok((inventory, position, eye_height, look_direction, instance_name)) =
+        players.get(event.sent_by)
+    else {
+        return;
+    };
+    if !azalea_registry::tags::items::BOATS.contains(&inventory.held_item().kind()) {
+        return;
+    }
+    let Some(instance) = instance_container.get(instance_name) else {
+        return;
+    };
+    let eye_position = position.up(eye_height.into());
+    let Some(location) = find_boat_location(&instance.read(), eye_position, *look_direction) else {
+        return;
+    };
+
+    let entity_id = MinecraftEntityId(1 + boats.iter().count() as i32);
+    commands.spawn((
+        SimulatedBoat,
+        entity_id,
+        azalea_entity::EntityBundle::new(
+            Uuid::from_u128(entity_id.0 as u128),
+            location,
+            EntityKind::OakBoat,
+            (**instance_name).clone(),
+        ),
+    ));
+}
+
+/// Find where a line of sight first goes into water or hits the top of a block,
+/// which is where the server puts boats.
+fn find_boat_location(
+    instance: &Instance,
+    eye_position: Vec3,
+    look_direction: LookDirection,
+) -> Option<Vec3> {
+    let view = view_vector(look_direction);
+    // about as far as players can reach
+    for step in 0..45 {
+        let point = eye_position + view * (step as f64 / 10.);
+        let pos = BlockPos::from(point);
+        if let Some(fluid) = instance.get_fluid_state(pos)
+            && fluid.kind == FluidKind::Water
+            && point.y <= pos.y as f64 + fluid.height() as f64
+        {
+            return Some(point);
+        }
+        if is_block_state_solid(instance.get_block_state(pos).unwrap_or_default()) {
+            return Some(Vec3::new(point.x, pos.y as f64 + 1., point.z));
+        }
+    }
+    None
+}
+
+/// Put the player in the boat that they right-clicked.
+fn mount_simulated_boat(
+    trigger: Trigger<SendPacketEvent>,
+    boats: Query<(Entity, &MinecraftEntityId), With<SimulatedBoat>>,
+    mut commands: Commands,
+) {
+    let event = trigger.event();
+    let ServerboundGamePacket::Interact(p) = &event.packet else {
+        return;
+    };
+    let boat = boats
+        .iter()
+        .find(|(_, entity_id)| **entity_id == p.entity_id);
+    if let Some((boat, _)) = boat {
+        commands.entity(event.sent_by).insert(Riding(boat));
+    }
+}
+
+/// Take the player out of their boat when they press shift, and put them on
+/// the closest block next to it that they can stand on.
+#[allow(clippy::type_complexity)]
+fn dismount_simulated_boat(
+    trigger: Trigger<SendPacketEvent>,
+    mut players: Query<
+        (
+            &Riding,
+            &azalea_client::local_player::InstanceHolder,
+            &mut Position,
+            &mut Physics,
+        ),
+        With<LocalEntity>,
+    >,
+    boats: Query<&Position, (With<SimulatedBoat>, Without<LocalEntity>)>,
+    mut commands: Commands,
+) {
+    let event = trigger.event();
+    let ServerboundGamePacket::PlayerInput(p) = &event.packet else {
+        return;
+    };
+    if !p.shift {
+        return;
+    }
+    let Ok((riding, instance_holder, mut position, mut physics)) = players.get_mut(event.sent_by)
+    else {
+        return;
+    };
+    let Ok(boat_position) = boats.get(riding.0) else {
+        return;
+    };
+    let Some(pos) = find_dismount_position(&instance_holder.instance.read(), **boat_position)
+    else {
+        return;
+    };
+
+    **position = pos.center_bottom();
+    physics.velocity = Vec3::ZERO;
+    commands.entity(event.sent_by).remove::<Riding>();
+}
+
+/// Find the closest position next to the boat that a player could stand in.
+fn find_dismount_position(instance: &Instance, boat_position: Vec3) -> Option<BlockPos> {
+    let boat_block = BlockPos::from(boat_position);
+    let block_state = |pos: BlockPos| instance.get_block_state(pos).unwrap_or_default();
+    let is_water = |pos: BlockPos| {
+        instance
+            .get_fluid_state(pos)
+            .is_some_and(|fluid| fluid.kind == FluidKind::Water)
+    };
+
+    let mut candidates = Vec::new();
+    for x in -2..=2 {
+        for z in -2..=2 {
+            for y in 0..=1 {
+                let pos = boat_block + BlockPos::new(x, y, z);
+                if is_block_state_solid(block_state(pos.down(1)))
+                    && !is_water(pos)
+                    && is_block_state_passable(block_state(pos))
+                    && is_block_state_passable(block_state(pos.up(1)))
+                {
+                    candidates.push(pos);
+                }
+            }
+        }
+    }
+    candidates.into_iter().min_by(|a, b| {
+        let distance = |pos: &BlockPos| pos.center_bottom().distance_squared_to(boat_position);
+        distance(a).total_cmp(&distance(b))
+    })
+}
+
+/// Open or close the door, fence gate, or trapdoor at the given position, like
+/// the server would when a player right-clicks it.
+///
+/// Returns whether the block was toggled.
+fn toggle_openable_block(instance: &RwLock<Instance>, pos: BlockPos) -> bool {
+    let instance = instance.write();
+    let Some(state) = instance.get_block_state(pos) else {
+        return false;
+    };
+    if !is_block_state_openable(state) {
+        return false;
+    }
+    let Some(toggled_state) = toggle_open(state) else {
+        return false;
+    };
+    instance.set_block_state(pos, toggled_state);
+
+    // doors are two blocks tall, and both halves open together
+    let other_half = match state.property::<properties::Half>() {
+        Some(properties::Half::Lower) => pos.up(1),
+        Some(properties::Half::Upper) => pos.down(1),
+        None => return true,
+    };
+    if let Some(other_state) = instance.get_block_state(other_half)
+        && let Some(toggled_other_state) = toggle_open(other_state)
+    {
+        instance.set_block_state(other_half, toggled_other_state);
+    }
+    true
+}
+
+/// Place the block that the player is holding at the given position, if it's
+/// air. The item isn't used up, since the simulation doesn't keep track of the
+/// inventory.
+fn place_held_block(instance: &RwLock<Instance>, inventory: &Inventory, pos: BlockPos) {
+    let Ok(block) = inventory
+        .held_item()
+        .kind()
+        .to_string()
+        .parse::<azalea_registry::Block>()
+    else {
+        return;
+    };
+    if block == azalea_registry::Block::Air {
+        return;
+    }
+
+    let instance = instance.write();
+    if instance.get_block_state(pos) == Some(BlockState::AIR) {
+        instance.set_block_state(pos, BlockState::from(block));
+    }
+}
+
+/// Get the same block state but with the `open` property flipped.
+fn toggle_open(state: BlockState) -> Option<BlockState> {
+    let open = state.property::<properties::Open>()?;
+    let has_same_properties = |other: BlockState| {
+        other.property::<properties::FacingCardinal>()
+            == state.property::<properties::FacingCardinal>()
+            && other.property::<properties::Half>() == state.property::<properties::Half>()
+            && other.property::<properties::TopBottom>()
+                == state.property::<properties::TopBottom>()
+            && other.property::<properties::Hinge>() == state.property::<properties::Hinge>()
+            && other.property::<properties::InWall>() == state.property::<properties::InWall>()
+            && other.property::<properties::Powered>() == state.property::<properties::Powered>()
+            && other.property::<properties::Waterlogged>()
+                == state.property::<properties::Waterlogged>()
+    };
+    BlockStates::from(azalea_registry::Block::from(state))
+        .into_iter()
+        .find(|&other| {
+            other.property::<properties::Open>() == Some(!open) && has_same_properties(other)
+        })
+}
+
 fn create_simulation_player_complete_bundle(
     instance: Arc<RwLock<Instance>>,
     player: &SimulatedPlayerBundle,
@@ -110,6 +501,7 @@ fn create_simulation_player_complete_bundle(
         LocalGameMode::from(GameMode::Survival),
         MineBundle::default(),
         BlockStatePredictionHandler::default(),
+        QueuedServerBlockUpdates::default(),
         azalea_client::local_player::PermissionLevel::default(),
         azalea_client::local_player::PlayerAbilities::default(),
     )
@@ -129,7 +521,7 @@ fn create_simulation_player(
 pub struct Simulation {
     pub app: App,
     pub entity: Entity,
-    _instance: Arc<RwLock<Instance>>,
+    instance: Arc<RwLock<Instance>>,
 }
 
 impl Simulation {
@@ -139,7 +531,7 @@ impl Simulation {
         Self {
             app,
             entity,
-            _instance: instance,
+            instance,
         }
     }
 
@@ -156,6 +548,66 @@ impl Simulation {
     pub fn position(&self) -> Vec3 {
         *self.component::<Position>()
     }
+    pub fn instance(&self) -> Arc<RwLock<Instance>> {
+        self.instance.clone()
+    }
+    /// Set a block in the simulated world, like if the server had sent a block
+    /// update.
+    ///
+    /// The update is applied at the start of the next tick.
+    pub fn set_block_state(&mut self, pos: BlockPos, state: BlockState) {
+        self.app
+            .world_mut()
+            .get_mut::<QueuedServerBlockUpdates>(self.entity)
+            .unwrap()
+            .list
+            .push((pos, state));
+    }
+    /// Move the player to a new instance with the given chunks, like if the
+    /// server had sent a respawn packet because we went through a portal.
+    pub fn change_instance(&mut self, name: &str, chunks: ChunkStorage, position: Vec3) {
+        let instance_name = ResourceLocation::new(name);
+        let instance = Arc::new(RwLock::new(Instance {
+            chunks,
+            ..Default::default()
+        }));
+
+        let world = self.app.world_mut();
+        world
+            .resource_mut::<InstanceContainer>()
+            .instances
+            .insert(instance_name.clone(), Arc::downgrade(&instance));
+        let dimensions = EntityDimensions::from(EntityKind::Player);
+        let mut entity = world.entity_mut(self.entity);
+        entity.insert((
+            InstanceName(instance_name),
+            Position::new(position),
+            Physics::new(dimensions, position),
+        ));
+        entity
+            .get_mut::<azalea_client::local_player::InstanceHolder>()
+            .unwrap()
+            .instance = instance.clone();
+
+        self.instance = instance;
+    }
+    /// Spawn another entity, like a mob, in the simulated world. It doesn't
+    /// move on its own.
+    pub fn spawn_entity(&mut self, kind: EntityKind, position: Vec3) -> Entity {
+        let world = self.app.world_mut();
+        let entity_id = MinecraftEntityId(1000 + world.entities().len() as i32);
+        world
+            .spawn((
+                entity_id,
+                azalea_entity::EntityBundle::new(
+                    Uuid::from_u128(entity_id.0 as u128),
+                    position,
+                    kind,
+                    simulation_instance_name(),
+                ),
+            ))
+            .id()
+    }
     pub fn is_mining(&self) -> bool {
         // return true if the component is present and Some
         self.get_component::<azalea_client::mining::MineBlockPos>()
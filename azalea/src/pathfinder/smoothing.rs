@@ -0,0 +1,180 @@
+//! Post-processing for paths that lets the bot walk in straight lines across
+//! open terrain, instead of following the path block by block.
+//!
+//! This only ever merges plain walking edges on solid ground. Anything that
+//! involves parkour, water, mining, or placing blocks is left alone.
+
+use std::collections::{HashSet, VecDeque};
+
+use azalea_core::position::{BlockPos, Vec3};
+
+use super::{
+    astar::{self, Edge},
+    moves::{ExecuteCtx, MoveData, default_is_reached, water::classify_water},
+    rel_block_pos::RelBlockPos,
+    world::{CachedWorld, passable_block_penalty},
+};
+
+/// The furthest that a single smoothed edge can go, in blocks.
+///
+/// Longer edges would take long enough to walk that the pathfinder thinks
+/// we're stuck.
+pub const MAX_SMOOTHED_DISTANCE: f64 = 6.;
+/// How far apart the points that we check along the line are, in blocks.
+const LINE_STEP: f64 = 0.1;
+/// Half of the width of our hitbox.
+const HALF_WIDTH: f64 = 0.3;
+
+/// The [`MoveData`] for the edges that [`smooth_path`] creates.
+static STRAIGHT_WALK: MoveData = MoveData {
+    execute: &execute_straight_walk,
+    is_reached: &default_is_reached,
+    consumes: &[],
+};
+
+/// Merge runs of walking edges where we could walk in a straight line from
+/// the start of the run to the end of it.
+///
+/// `world` has to be the one that was used to calculate the path, and `start`
+/// is the position that the path starts at.
+pub fn smooth_path(
+    world: &CachedWorld,
+    start: BlockPos,
+    path: VecDeque<Edge<BlockPos, MoveData>>,
+) -> VecDeque<Edge<BlockPos, MoveData>> {
+    let origin = world.origin();
+    let rel = |pos: BlockPos| RelBlockPos::from_origin(origin, pos);
+
+    let edges = Vec::from(path);
+    // nodes[i] is where edges[i] starts
+    let nodes = [start]
+        .into_iter()
+        .chain(edges.iter().map(|edge| edge.movement.target))
+        .collect::<Vec<_>>();
+    let is_walk = |i: usize| is_walking_edge(world, rel(nodes[i]), &edges[i]);
+
+    let mut smoothed = VecDeque::with_capacity(edges.len());
+    let mut i = 0;
+    while i < edges.len() {
+        if !is_walk(i) {
+            smoothed.push_back(edges[i].clone());
+            i += 1;
+            continue;
+        }
+
+        // find the last edge that we can walk straight to the end of
+        let mut end = i;
+        while end + 1 < edges.len()
+            && is_walk(end + 1)
+            && is_straight_walkable(world, rel(nodes[i]), rel(nodes[end + 2]))
+        {
+            end += 1;
+        }
+
+        if end == i {
+            smoothed.push_back(edges[i].clone());
+        } else {
+            smoothed.push_back(Edge {
+                movement: astar::Movement {
+                    target: nodes[end + 1],
+                    data: STRAIGHT_WALK.clone(),
+                },
+                cost: edges[i..=end].iter().map(|edge| edge.cost).sum(),
+            });
+        }
+        i = end + 1;
+    }
+    smoothed
+}
+
+/// Whether the edge is a step to a neighboring block on the same level that
+/// doesn't use up any items.
+fn is_walking_edge(
+    world: &CachedWorld,
+    from: RelBlockPos,
+    edge: &Edge<BlockPos, MoveData>,
+) -> bool {
+    let to = RelBlockPos::from_origin(world.origin(), edge.movement.target);
+    let (dx, dz) = (to.x - from.x, to.z - from.z);
+    to.y == from.y
+        && dx.abs() <= 1
+        && dz.abs() <= 1
+        && (dx, dz) != (0, 0)
+        && edge.movement.data.consumes.is_empty()
+        && is_straight_walkable(world, from, to)
+}
+
+/// Whether we could walk in a straight line from the middle of `from` to the
+/// middle of `to` without leaving solid ground or touching anything that
+/// would slow us down.
+///
+/// Both positions have to be on the same level, and they can't be more than
+/// [`MAX_SMOOTHED_DISTANCE`] apart.
+pub fn is_straight_walkable(world: &CachedWorld, from: RelBlockPos, to: RelBlockPos) -> bool {
+    if from.y != to.y {
+        return false;
+    }
+    let start = Vec3::new(from.x as f64 + 0.5, 0., from.z as f64 + 0.5);
+    let end = Vec3::new(to.x as f64 + 0.5, 0., to.z as f64 + 0.5);
+    let distance = (end - start).horizontal_distance_squared().sqrt();
+    if distance > MAX_SMOOTHED_DISTANCE {
+        return false;
+    }
+
+    // every block that our hitbox touches on the way
+    let steps = (distance / LINE_STEP).ceil() as usize;
+    let mut checked = HashSet::new();
+    for step in 0..=steps {
+        let t = if steps == 0 {
+            0.
+        } else {
+            step as f64 / steps as f64
+        };
+        let point = start + (end - start) * t;
+        for (x_offset, z_offset) in [
+            (-HALF_WIDTH, -HALF_WIDTH),
+            (-HALF_WIDTH, HALF_WIDTH),
+            (HALF_WIDTH, -HALF_WIDTH),
+            (HALF_WIDTH, HALF_WIDTH),
+        ] {
+            let pos = RelBlockPos::new(
+                (point.x + x_offset).floor() as i16,
+                from.y,
+                (point.z + z_offset).floor() as i16,
+            );
+            if checked.insert(pos) && !is_open_ground(world, pos) {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+/// Whether this is solid ground that's safe to walk across without thinking
+/// about it, which means no water, no damaging blocks, and no drops next to
+/// it.
+fn is_open_ground(world: &CachedWorld, pos: RelBlockPos) -> bool {
+    if !world.is_passable(pos) || !world.is_block_solid(pos.down(1)) {
+        return false;
+    }
+    for pos in [pos, pos.up(1)] {
+        let block = world.get_block_state(pos);
+        if classify_water(block).is_some() || passable_block_penalty(block) > 0. {
+            return false;
+        }
+    }
+    if world.damaging_surface_cost(pos) > 0.
+        || world.risk_cost(pos) > 0.
+        || world.region_cost(pos) > 0.
+        || world.mob_cost(pos) > 0.
+    {
+        return false;
+    }
+    // checking two perpendicular directions covers all four sides
+    !world.has_ledge_beside(pos, 1, 0) && !world.has_ledge_beside(pos, 0, 1)
+}
+
+fn execute_straight_walk(mut ctx: ExecuteCtx) {
+    ctx.look_at(ctx.target.center());
+    ctx.sprint_unless_near_ledge();
+}
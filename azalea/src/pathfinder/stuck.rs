@@ -0,0 +1,218 @@
+//! Noticing when we're physically stuck while executing a path.
+//!
+//! A failed parkour landing, getting pushed by another entity, or sand falling
+//! on us can leave us somewhere that the current move can't get us out of.
+//! Instead of walking into a wall until the move times out, we notice that we
+//! haven't gotten any closer to the next node and recalculate the path from
+//! where we actually are.
+
+use std::{cmp, sync::atomic, time::Instant};
+
+use azalea_client::{StartWalkEvent, WalkDirection, inventory::Inventory, mining::Mining};
+use azalea_core::position::BlockPos;
+use azalea_entity::{ActiveEffects, Position, metadata::Health};
+use azalea_world::{InstanceContainer, InstanceName};
+use bevy_ecs::prelude::*;
+use tracing::warn;
+
+use super::{
+    ExecutingPath, GoalUnreachableEvent, GotoOutcome, PathComputedEvent, Pathfinder,
+    UnreachableReason,
+    custom_state::CustomPathfinderState,
+    humanize::{HumanizeSettings, HumanizeState},
+    patch_path,
+};
+
+/// How many ticks we can go without getting any closer to the next node
+/// before we count as stuck.
+pub const STUCK_TICKS: u32 = 20;
+/// How many times in a row we try to get unstuck without reaching a node
+/// before we give up on the goal.
+pub const MAX_STUCK_RECOVERIES: u32 = 3;
+/// How much closer to the next node we have to get, in blocks, for it to count
+/// as progress.
+const MIN_PROGRESS: f64 = 0.05;
+
+/// Keeps track of how long it's been since we got any closer to the node that
+/// we're going to.
+///
+/// This is added automatically to bots that are executing a path.
+#[derive(Component, Clone, Debug, Default)]
+pub struct StuckDetector {
+    target: Option<BlockPos>,
+    closest_distance: f64,
+    ticks_without_progress: u32,
+    /// The last node that we reached, so we know when to reset `recoveries`.
+    last_reached_node: Option<BlockPos>,
+    /// How many times we've tried to get unstuck since we last reached a node.
+    recoveries: u32,
+}
+
+/// Sent when we stop making progress towards the next node in the path.
+///
+/// The path gets recalculated from where we are when this is sent, unless
+/// `gave_up` is true. That happens when we got stuck more than
+/// [`MAX_STUCK_RECOVERIES`] times in a row, and the goal gets removed with
+/// [`UnreachableReason::Stuck`] instead.
+#[derive(Event, Clone, Debug)]
+pub struct PathStuckEvent {
+    pub entity: Entity,
+    /// The block that we got stuck in.
+    pub position: BlockPos,
+    /// The node that we were trying to get to.
+    pub target: BlockPos,
+    pub gave_up: bool,
+}
+
+#[allow(clippy::type_complexity)]
+pub fn detect_stuck(
+    mut commands: Commands,
+    mut query: Query<(
+        Entity,
+        &mut Pathfinder,
+        &mut ExecutingPath,
+        &Position,
+        Option<&Mining>,
+        &InstanceName,
+        &Inventory,
+        Option<&ActiveEffects>,
+        Option<&Health>,
+        Option<&CustomPathfinderState>,
+        Option<&HumanizeSettings>,
+        Option<&HumanizeState>,
+        Option<&mut StuckDetector>,
+    )>,
+    instance_container: Res<InstanceContainer>,
+    mut walk_events: EventWriter<StartWalkEvent>,
+    mut stuck_events: EventWriter<PathStuckEvent>,
+    mut goal_unreachable_events: EventWriter<GoalUnreachableEvent>,
+    mut path_computed_events: EventWriter<PathComputedEvent>,
+) {
+    for (
+        entity,
+        mut pathfinder,
+        mut executing_path,
+        position,
+        mining,
+        instance_name,
+        inventory,
+        active_effects,
+        health,
+        custom_state,
+        humanize,
+        humanize_state,
+        detector,
+    ) in &mut query
+    {
+        let Some(mut detector) = detector else {
+            commands.entity(entity).insert(StuckDetector::default());
+            continue;
+        };
+        // we're not going anywhere that we could recalculate a path to
+        let Some(successors_fn) = pathfinder.successors_fn else {
+            continue;
+        };
+        let Some(target) = executing_path.path.front().map(|edge| edge.movement.target) else {
+            continue;
+        };
+
+        if detector.last_reached_node != Some(executing_path.last_reached_node) {
+            *detector = StuckDetector {
+                last_reached_node: Some(executing_path.last_reached_node),
+                ..Default::default()
+            };
+        }
+
+        // mining a block, waiting to be allowed to, or waiting for something that we
+        // interacted with (like a boat that we put on the water) doesn't get us any
+        // closer, but we're not stuck
+        let is_waiting = mining.is_some()
+            || executing_path.interact_cooldown > 0
+            || humanize
+                .zip(humanize_state)
+                .is_some_and(|(humanize, state)| !state.can_start_break(humanize));
+        let distance = position.distance_to(target.center());
+        if is_waiting
+            || detector.target != Some(target)
+            || distance < detector.closest_distance - MIN_PROGRESS
+            // check_node_reached is just waiting for us to settle down
+            || BlockPos::from(position) == target
+        {
+            detector.target = Some(target);
+            detector.closest_distance = distance;
+            detector.ticks_without_progress = 0;
+            continue;
+        }
+
+        detector.ticks_without_progress += 1;
+        if detector.ticks_without_progress < STUCK_TICKS || pathfinder.is_calculating {
+            continue;
+        }
+
+        let stuck_pos = BlockPos::from(position);
+        detector.target = None;
+        detector.ticks_without_progress = 0;
+        detector.recoveries += 1;
+        let gave_up = detector.recoveries > MAX_STUCK_RECOVERIES;
+        stuck_events.write(PathStuckEvent {
+            entity,
+            position: stuck_pos,
+            target,
+            gave_up,
+        });
+        // cancel the current move
+        walk_events.write(StartWalkEvent {
+            entity,
+            direction: WalkDirection::None,
+        });
+
+        if gave_up {
+            warn!("got stuck at {stuck_pos} too many times in a row, giving up on the goal");
+            // make sure the result of a path calculation that's still running gets ignored
+            pathfinder.goto_id.fetch_add(1, atomic::Ordering::SeqCst);
+            pathfinder.goal = None;
+            pathfinder.successors_fn = None;
+            pathfinder.outcome = Some(GotoOutcome::Unreachable {
+                closest: stuck_pos,
+                reason: UnreachableReason::Stuck,
+            });
+            goal_unreachable_events.write(GoalUnreachableEvent {
+                entity,
+                closest: stuck_pos,
+                reason: UnreachableReason::Stuck,
+            });
+            commands
+                .entity(entity)
+                .remove::<ExecutingPath>()
+                .remove::<StuckDetector>();
+            continue;
+        }
+
+        warn!("stuck at {stuck_pos} while going to {target}, recalculating from here");
+        let world_lock = instance_container
+            .get(instance_name)
+            .expect("Entity tried to pathfind but the entity isn't in a valid world");
+
+        // the path is patched from where we actually are, and the world is looked
+        // at again from scratch in case something changed around us
+        executing_path.queued_path = None;
+        executing_path.last_reached_node = stuck_pos;
+        detector.last_reached_node = Some(stuck_pos);
+        if let Some(path_computed_event) = patch_path(
+            0..=cmp::min(20, executing_path.path.len() - 1),
+            &mut executing_path,
+            &mut pathfinder,
+            inventory,
+            active_effects,
+            health,
+            humanize,
+            entity,
+            successors_fn,
+            world_lock,
+            custom_state.cloned().unwrap_or_default(),
+        ) {
+            path_computed_events.write(path_computed_event);
+        }
+        executing_path.last_node_reached_at = Instant::now();
+    }
+}
@@ -6,17 +6,45 @@ use std::{
 };
 
 use azalea_block::BlockState;
+use azalea_client::inventory::Inventory;
 use azalea_core::position::{BlockPos, ChunkPos, Vec3};
+use azalea_entity::Physics;
+use azalea_inventory::ItemStack;
 use azalea_world::{Chunk, ChunkStorage, PartialChunkStorage};
 
 use super::{
-    GotoEvent,
+    GotoEvent, GotoOptions, GotoOutcome, Pathfinder,
     astar::PathfinderTimeout,
     goals::BlockPosGoal,
-    moves,
+    hazard::EscapingHazard,
+    moves::{
+        self,
+        water::{LOW_AIR_THRESHOLD, MAX_AIR_SUPPLY},
+    },
+    settings::MoveSettings,
     simulation::{SimulatedPlayerBundle, Simulation},
 };
 
+/// The [`CalculatePathOpts`](super::CalculatePathOpts) that the tests start
+/// from. Paths aren't smoothed or split up for long distances, and the
+/// timeouts count nodes so the tests don't depend on how fast the machine is.
+fn test_path_opts(
+    start: BlockPos,
+    goal: Arc<dyn super::goals::Goal>,
+    world_lock: Arc<parking_lot::RwLock<azalea_world::Instance>>,
+) -> super::CalculatePathOpts {
+    super::CalculatePathOpts::new(
+        bevy_ecs::entity::Entity::PLACEHOLDER,
+        start,
+        goal,
+        world_lock,
+    )
+    .smooth_path(false)
+    .long_distance_threshold(f32::INFINITY)
+    .min_timeout(PathfinderTimeout::Nodes(1_000_000))
+    .max_timeout(PathfinderTimeout::Nodes(5_000_000))
+}
+
 fn setup_blockposgoal_simulation(
     partial_chunks: &mut PartialChunkStorage,
     start_pos: BlockPos,
@@ -32,15 +60,15 @@ fn setup_blockposgoal_simulation(
     //     ..Default::default()
     // });
 
-    simulation.app.world_mut().send_event(GotoEvent {
-        entity: simulation.entity,
-        goal: Arc::new(BlockPosGoal(end_pos)),
-        successors_fn: moves::default_move,
-        allow_mining: false,
-        retry_on_no_path: true,
-        min_timeout: PathfinderTimeout::Nodes(1_000_000),
-        max_timeout: PathfinderTimeout::Nodes(5_000_000),
-    });
+    simulation.app.world_mut().send_event(
+        GotoEvent::new(simulation.entity, BlockPosGoal(end_pos)).with_options(
+            GotoOptions::default()
+                .allow_mining(false)
+                .retry_on_no_path(true)
+                .min_timeout(PathfinderTimeout::Nodes(1_000_000))
+                .max_timeout(PathfinderTimeout::Nodes(5_000_000)),
+        ),
+    );
     simulation
 }
 
@@ -50,6 +78,20 @@ fn setup_simulation_world(
     solid_blocks: &[BlockPos],
     extra_blocks: &[(BlockPos, BlockState)],
 ) -> Simulation {
+    let chunks = setup_chunks(partial_chunks, solid_blocks, extra_blocks);
+    let player = SimulatedPlayerBundle::new(Vec3::new(
+        start_pos.x as f64 + 0.5,
+        start_pos.y as f64,
+        start_pos.z as f64 + 0.5,
+    ));
+    Simulation::new(chunks, player)
+}
+
+fn setup_chunks(
+    partial_chunks: &mut PartialChunkStorage,
+    solid_blocks: &[BlockPos],
+    extra_blocks: &[(BlockPos, BlockState)],
+) -> ChunkStorage {
     let mut chunk_positions = HashSet::new();
     for block_pos in solid_blocks {
         chunk_positions.insert(ChunkPos::from(block_pos));
@@ -68,13 +110,20 @@ fn setup_simulation_world(
     for (block_pos, block_state) in extra_blocks {
         chunks.set_block_state(*block_pos, *block_state);
     }
+    chunks
+}
 
-    let player = SimulatedPlayerBundle::new(Vec3::new(
-        start_pos.x as f64 + 0.5,
-        start_pos.y as f64,
-        start_pos.z as f64 + 0.5,
-    ));
-    Simulation::new(chunks, player)
+/// Put the item in the first slot of the bot's hotbar.
+fn give_hotbar_item(simulation: &mut Simulation, item: azalea_registry::Item, count: i32) {
+    let hotbar_slot = *azalea_inventory::Player::HOTBAR_SLOTS.start();
+    *simulation
+        .app
+        .world_mut()
+        .get_mut::<Inventory>(simulation.entity)
+        .unwrap()
+        .inventory_menu
+        .slot_mut(hotbar_slot)
+        .unwrap() = ItemStack::new(item, count);
 }
 
 pub fn assert_simulation_reaches(simulation: &mut Simulation, ticks: usize, end_pos: BlockPos) {
@@ -97,6 +146,17 @@ pub fn wait_until_bot_starts_moving(simulation: &mut Simulation) {
     }
 }
 
+/// Tick the simulation and return the position of the bot after every tick.
+pub fn record_trajectory(simulation: &mut Simulation, ticks: usize) -> Vec<Vec3> {
+    wait_until_bot_starts_moving(simulation);
+    let mut trajectory = Vec::with_capacity(ticks);
+    for _ in 0..ticks {
+        simulation.tick();
+        trajectory.push(simulation.position());
+    }
+    trajectory
+}
+
 #[test]
 fn test_simple_forward() {
     let mut partial_chunks = PartialChunkStorage::default();
@@ -161,6 +221,51 @@ fn test_parkour_2_block_gap() {
     assert_simulation_reaches(&mut simulation, 40, BlockPos::new(0, 71, 3));
 }
 
+#[test]
+fn test_avoid_parkour_with_head_hitters() {
+    let mut partial_chunks = PartialChunkStorage::default();
+    let mut blocks = vec![BlockPos::new(0, 70, 0), BlockPos::new(0, 70, 4)];
+    // blocks right above our head over the gap, which would cut the jump short
+    for x in 0..=1 {
+        for z in 1..=3 {
+            blocks.push(BlockPos::new(x, 74, z));
+        }
+    }
+    // so we have to walk around instead
+    blocks.push(BlockPos::new(1, 70, 0));
+    blocks.push(BlockPos::new(1, 70, 4));
+    for z in 0..=4 {
+        blocks.push(BlockPos::new(2, 70, z));
+    }
+    let mut simulation = setup_blockposgoal_simulation(
+        &mut partial_chunks,
+        BlockPos::new(0, 71, 0),
+        BlockPos::new(0, 71, 4),
+        &blocks,
+    );
+    assert_simulation_reaches(&mut simulation, 80, BlockPos::new(0, 71, 4));
+}
+
+#[test]
+fn test_parkour_under_low_ceiling() {
+    let mut partial_chunks = PartialChunkStorage::default();
+    let mut blocks = vec![
+        BlockPos::new(0, 70, 0),
+        BlockPos::new(0, 70, 2),
+        BlockPos::new(0, 70, 3),
+    ];
+    for z in 0..=3 {
+        blocks.push(BlockPos::new(0, 73, z));
+    }
+    let mut simulation = setup_blockposgoal_simulation(
+        &mut partial_chunks,
+        BlockPos::new(0, 71, 0),
+        BlockPos::new(0, 71, 3),
+        &blocks,
+    );
+    assert_simulation_reaches(&mut simulation, 40, BlockPos::new(0, 71, 3));
+}
+
 #[test]
 fn test_descend_and_parkour_2_block_gap() {
     let mut partial_chunks = PartialChunkStorage::default();
@@ -271,6 +376,357 @@ fn test_jumps_with_more_sideways_momentum() {
     assert_simulation_reaches(&mut simulation, 80, BlockPos::new(4, 74, 9));
 }
 
+#[test]
+fn test_jumps_with_more_sideways_momentum_from_random_offsets() {
+    use rand::{Rng, SeedableRng, rngs::StdRng};
+
+    let blocks = [
+        BlockPos::new(0, 70, 0),
+        BlockPos::new(0, 70, 1),
+        BlockPos::new(0, 70, 2),
+        BlockPos::new(0, 71, 3),
+        BlockPos::new(0, 72, 6),
+        BlockPos::new(0, 73, 9),
+        BlockPos::new(2, 73, 9),
+        BlockPos::new(4, 73, 9),
+    ];
+    let end = BlockPos::new(4, 74, 9);
+
+    let mut rng = StdRng::seed_from_u64(0);
+    for run in 0..100 {
+        let mut partial_chunks = PartialChunkStorage::default();
+        let chunks = setup_chunks(&mut partial_chunks, &blocks, &[]);
+        let start = Vec3::new(
+            0.5 + rng.random_range(-0.2..0.2),
+            71.,
+            0.5 + rng.random_range(-0.2..0.2),
+        );
+        let mut simulation = Simulation::new(chunks, SimulatedPlayerBundle::new(start));
+        simulation.app.world_mut().send_event(
+            GotoEvent::new(simulation.entity, BlockPosGoal(end)).with_options(
+                GotoOptions::default()
+                    .allow_mining(false)
+                    .retry_on_no_path(true)
+                    .min_timeout(PathfinderTimeout::Nodes(1_000_000))
+                    .max_timeout(PathfinderTimeout::Nodes(5_000_000)),
+            ),
+        );
+
+        wait_until_bot_starts_moving(&mut simulation);
+        for _ in 0..80 {
+            simulation.tick();
+        }
+        assert_eq!(
+            BlockPos::from(simulation.position()),
+            end,
+            "didn't reach the end on run {run} starting at {start:?}"
+        );
+    }
+}
+
+#[test]
+fn test_stops_sprinting_before_end_of_straight_path() {
+    use azalea_entity::metadata::Sprinting;
+
+    let mut partial_chunks = PartialChunkStorage::default();
+    let blocks = (0..=12)
+        .map(|x| BlockPos::new(x, 70, 0))
+        .collect::<Vec<_>>();
+    let end = BlockPos::new(12, 71, 0);
+    let mut simulation =
+        setup_blockposgoal_simulation(&mut partial_chunks, BlockPos::new(0, 71, 0), end, &blocks);
+
+    wait_until_bot_starts_moving(&mut simulation);
+    let mut has_sprinted = false;
+    for _ in 0..60 {
+        simulation.tick();
+        let is_sprinting = *simulation.component::<Sprinting>();
+        has_sprinted |= is_sprinting;
+        if simulation.position().x > end.x as f64 - 0.5 {
+            assert!(
+                !is_sprinting,
+                "still sprinting at {:?}",
+                simulation.position()
+            );
+        }
+    }
+    assert!(has_sprinted);
+    assert_eq!(BlockPos::from(simulation.position()), end);
+}
+
+#[test]
+fn test_walk_on_1_wide_cliff_path() {
+    let mut partial_chunks = PartialChunkStorage::default();
+    let path_blocks = (0..=10).map(|z| BlockPos::new(0, 70, z)).collect::<Vec<_>>();
+    let mut simulation = setup_blockposgoal_simulation(
+        &mut partial_chunks,
+        BlockPos::new(0, 71, 0),
+        BlockPos::new(0, 71, 10),
+        &path_blocks,
+    );
+
+    let trajectory = record_trajectory(&mut simulation, 80);
+    for position in &trajectory {
+        let block_pos = BlockPos::from(position);
+        assert!(
+            path_blocks.contains(&block_pos.down(1)),
+            "bot left the path at {position:?}"
+        );
+    }
+    assert_eq!(BlockPos::from(simulation.position()), BlockPos::new(0, 71, 10));
+}
+
+#[test]
+fn test_cross_1_wide_bridge_over_long_drop() {
+    use rand::{Rng, SeedableRng, rngs::StdRng};
+
+    let mut blocks = Vec::new();
+    for x in -1..=1 {
+        for z in -1..=1 {
+            // the platforms on both sides of the bridge
+            blocks.push(BlockPos::new(x, 89, z));
+            blocks.push(BlockPos::new(x + 13, 89, z));
+        }
+    }
+    for x in 2..=11 {
+        blocks.push(BlockPos::new(x, 89, 0));
+    }
+    // the ground that we'd fall onto
+    for x in -1..=14 {
+        for z in -3..=3 {
+            blocks.push(BlockPos::new(x, 69, z));
+        }
+    }
+    let end = BlockPos::new(13, 90, 0);
+
+    let mut rng = StdRng::seed_from_u64(0);
+    for run in 0..50 {
+        let mut partial_chunks = PartialChunkStorage::default();
+        let chunks = setup_chunks(&mut partial_chunks, &blocks, &[]);
+        let start = Vec3::new(
+            0.5 + rng.random_range(-0.25..0.25),
+            90.,
+            0.5 + rng.random_range(-0.25..0.25),
+        );
+        let mut simulation = Simulation::new(chunks, SimulatedPlayerBundle::new(start));
+        // so we don't start walking on the same tick every time
+        for _ in 0..rng.random_range(0..5) {
+            simulation.tick();
+        }
+        simulation.app.world_mut().send_event(
+            GotoEvent::new(simulation.entity, BlockPosGoal(end)).with_options(
+                GotoOptions::default()
+                    .allow_mining(false)
+                    .retry_on_no_path(true)
+                    .min_timeout(PathfinderTimeout::Nodes(1_000_000))
+                    .max_timeout(PathfinderTimeout::Nodes(5_000_000)),
+            ),
+        );
+
+        let trajectory = record_trajectory(&mut simulation, 200);
+        for position in &trajectory {
+            assert!(
+                position.y > 89.,
+                "bot fell off the bridge at {position:?} on run {run} starting at {start:?}"
+            );
+        }
+        assert_eq!(BlockPos::from(simulation.position()), end);
+    }
+}
+
+#[test]
+fn test_escape_lava_and_resume_goal() {
+    let mut partial_chunks = PartialChunkStorage::default();
+    let floor = (-1..=1)
+        .flat_map(|x| (0..=12).map(move |z| BlockPos::new(x, 70, z)))
+        .collect::<Vec<_>>();
+    let mut simulation = setup_blockposgoal_simulation(
+        &mut partial_chunks,
+        BlockPos::new(0, 71, 0),
+        BlockPos::new(0, 71, 12),
+        &floor,
+    );
+
+    wait_until_bot_starts_moving(&mut simulation);
+    for _ in 0..5 {
+        simulation.tick();
+    }
+
+    // lava flows into the block that the bot is standing in
+    let lava_pos = BlockPos::from(simulation.position());
+    simulation.set_block_state(lava_pos, azalea_registry::Block::Lava.into());
+    simulation.tick();
+    assert!(simulation.component::<Physics>().is_in_lava());
+
+    let mut escaped_after_ticks = None;
+    for i in 0..40 {
+        simulation.tick();
+        if !simulation.component::<Physics>().is_in_lava() {
+            escaped_after_ticks = Some(i);
+            break;
+        }
+    }
+    assert!(
+        escaped_after_ticks.is_some(),
+        "bot should've gotten out of the lava"
+    );
+
+    // the original goal should be resumed once we're out
+    for _ in 0..200 {
+        simulation.tick();
+    }
+    assert!(simulation.get_component::<EscapingHazard>().is_none());
+    assert_eq!(
+        BlockPos::from(simulation.position()),
+        BlockPos::new(0, 71, 12)
+    );
+}
+
+#[test]
+fn test_explore_goal_moves_outward() {
+    use super::goals::{ExploreGoal, Goal};
+
+    const VIEW_DISTANCE: i32 = 2;
+
+    // chunks are loaded lazily around the bot and unloaded when it walks away, like
+    // they would be on a real server
+    let mut current = ChunkPos::new(0, 0);
+    let mut explored = HashSet::new();
+    let mut last_distance = 0;
+    for _ in 0..10 {
+        let is_loaded = |chunk_pos: ChunkPos| {
+            (chunk_pos.x - current.x).abs() <= VIEW_DISTANCE
+                && (chunk_pos.z - current.z).abs() <= VIEW_DISTANCE
+        };
+        for x in -VIEW_DISTANCE..=VIEW_DISTANCE {
+            for z in -VIEW_DISTANCE..=VIEW_DISTANCE {
+                explored.insert(ChunkPos::new(current.x + x, current.z + z));
+            }
+        }
+
+        let goal = ExploreGoal::new_with_loaded_fn(
+            BlockPos::new(current.x * 16 + 8, 70, current.z * 16 + 8),
+            is_loaded,
+            &explored,
+            4,
+        )
+        .expect("there should always be something to explore");
+        assert!(is_loaded(goal.target));
+        assert!(!goal.success(BlockPos::new(current.x * 16, 70, current.z * 16)));
+
+        // walk to the target
+        current = goal.target;
+        let distance = current.x.abs().max(current.z.abs());
+        assert!(
+            distance > last_distance,
+            "explored chunks should grow outward, but went from distance {last_distance} to {distance}"
+        );
+        last_distance = distance;
+    }
+}
+
+#[test]
+fn test_explore_goal_in_simulation() {
+    use super::goals::{ExploreGoal, Goal};
+
+    const VIEW_DISTANCE: i32 = 1;
+
+    // load the chunks around the bot with a floor in them, like a server would
+    // when we walk into a new chunk
+    fn load_chunks_around(
+        simulation: &Simulation,
+        partial_chunks: &mut PartialChunkStorage,
+        explored: &mut HashSet<ChunkPos>,
+    ) {
+        let center = ChunkPos::from(simulation.position());
+        partial_chunks.update_view_center(center);
+        let instance = simulation.instance();
+        let mut instance = instance.write();
+        for x in -VIEW_DISTANCE..=VIEW_DISTANCE {
+            for z in -VIEW_DISTANCE..=VIEW_DISTANCE {
+                let chunk_pos = ChunkPos::new(center.x + x, center.z + z);
+                if !explored.insert(chunk_pos) {
+                    continue;
+                }
+                partial_chunks.set(&chunk_pos, Some(Chunk::default()), &mut instance.chunks);
+                for bx in 0..16 {
+                    for bz in 0..16 {
+                        instance.chunks.set_block_state(
+                            BlockPos::new(chunk_pos.x * 16 + bx, 70, chunk_pos.z * 16 + bz),
+                            azalea_registry::Block::Stone.into(),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    let mut partial_chunks = PartialChunkStorage::default();
+    let mut explored = HashSet::new();
+    let mut simulation = Simulation::new(
+        ChunkStorage::default(),
+        SimulatedPlayerBundle::new(Vec3::new(8.5, 71., 8.5)),
+    );
+    load_chunks_around(&simulation, &mut partial_chunks, &mut explored);
+
+    let explored_distance = |explored: &HashSet<ChunkPos>| {
+        explored
+            .iter()
+            .map(|chunk_pos| chunk_pos.x.abs().max(chunk_pos.z.abs()))
+            .max()
+            .unwrap()
+    };
+    let mut last_target_distance = 0;
+    let mut last_explored_distance = explored_distance(&explored);
+    for round in 0..4 {
+        let goal = ExploreGoal::new(
+            BlockPos::from(simulation.position()),
+            &simulation.instance().read().chunks,
+            &explored,
+            4,
+        )
+        .expect("there should always be something to explore");
+        let target_distance = goal.target.x.abs().max(goal.target.z.abs());
+        assert!(
+            target_distance > last_target_distance,
+            "round {round} went back from distance {last_target_distance} to {target_distance}"
+        );
+        last_target_distance = target_distance;
+
+        simulation.app.world_mut().send_event(
+            GotoEvent::new(simulation.entity, goal).with_options(
+                GotoOptions::default()
+                    .allow_mining(false)
+                    .retry_on_no_path(true)
+                    .min_timeout(PathfinderTimeout::Nodes(1_000_000))
+                    .max_timeout(PathfinderTimeout::Nodes(5_000_000)),
+            ),
+        );
+        let start_time = Instant::now();
+        loop {
+            assert!(
+                start_time.elapsed() < Duration::from_secs(10),
+                "never got to {:?} in round {round}",
+                goal.target
+            );
+            simulation.tick();
+            load_chunks_around(&simulation, &mut partial_chunks, &mut explored);
+            if simulation.component::<Pathfinder>().outcome == Some(GotoOutcome::Reached) {
+                break;
+            }
+            thread::sleep(Duration::from_millis(1));
+        }
+        assert!(goal.success(BlockPos::from(simulation.position())));
+
+        let explored_distance = explored_distance(&explored);
+        assert!(
+            explored_distance > last_explored_distance,
+            "explored chunks didn't grow outward in round {round}"
+        );
+        last_explored_distance = explored_distance;
+    }
+}
+
 #[test]
 fn test_mine_through_non_colliding_block() {
     let mut partial_chunks = PartialChunkStorage::default();
@@ -296,15 +752,15 @@ fn test_mine_through_non_colliding_block() {
         ],
     );
 
-    simulation.app.world_mut().send_event(GotoEvent {
-        entity: simulation.entity,
-        goal: Arc::new(BlockPosGoal(BlockPos::new(0, 69, 0))),
-        successors_fn: moves::default_move,
-        allow_mining: true,
-        retry_on_no_path: true,
-        min_timeout: PathfinderTimeout::Nodes(1_000_000),
-        max_timeout: PathfinderTimeout::Nodes(5_000_000),
-    });
+    simulation.app.world_mut().send_event(
+        GotoEvent::new(simulation.entity, BlockPosGoal(BlockPos::new(0, 69, 0))).with_options(
+            GotoOptions::default()
+                .allow_mining(true)
+                .retry_on_no_path(true)
+                .min_timeout(PathfinderTimeout::Nodes(1_000_000))
+                .max_timeout(PathfinderTimeout::Nodes(5_000_000)),
+        ),
+    );
 
     assert_simulation_reaches(&mut simulation, 200, BlockPos::new(0, 70, 0));
 }
@@ -321,6 +777,150 @@ fn test_water_classification() {
     // Test air (not water)
     let air = BlockState::AIR;
     assert_eq!(classify_water(air), None);
+
+    // Test flowing water
+    let flowing_water = BlockState::from(azalea_block::blocks::Water {
+        level: azalea_block::properties::WaterLevel::_3,
+    });
+    assert_eq!(classify_water(flowing_water), Some(WaterType::FlowingWater));
+    assert!(!moves::water::is_water_navigable(WaterType::FlowingWater));
+    assert!(moves::water::is_water_navigable(WaterType::StillWater));
+}
+
+#[test]
+fn test_classify_every_water_block_state() {
+    use azalea_block::{BlockStates, properties::WaterLevel};
+
+    use crate::pathfinder::moves::water::{WaterType, classify_water};
+
+    for block_state in BlockStates::from(azalea_registry::Block::Water) {
+        let level = block_state
+            .property::<WaterLevel>()
+            .expect("water should always have a level");
+        let expected = if level == WaterLevel::_0 {
+            WaterType::StillWater
+        } else {
+            WaterType::FlowingWater
+        };
+        assert_eq!(
+            classify_water(block_state),
+            Some(expected),
+            "water with level {level:?}"
+        );
+    }
+
+    // falling water is a full block, but it's not a source
+    let falling_water = BlockState::from(azalea_block::blocks::Water {
+        level: WaterLevel::_8,
+    });
+    assert_eq!(classify_water(falling_water), Some(WaterType::FlowingWater));
+}
+
+#[test]
+fn test_classify_dangerous_water() {
+    use super::{
+        custom_state::CustomPathfinderStateRef,
+        mining::MiningCache,
+        moves::{
+            PathfinderCtx,
+            water::{SwimmingState, WaterType, classify_water_at},
+        },
+        rel_block_pos::RelBlockPos,
+        world::{CachedWorld, PathEquipment},
+    };
+
+    let water_pos = BlockPos::new(1, 70, 1);
+    let rel_water_pos = RelBlockPos::from_origin(BlockPos::default(), water_pos);
+    // classify a single block of water that's on top of `floor`, with
+    // `extra_blocks` around it
+    let classify = |floor: azalea_registry::Block,
+                    extra_blocks: &[(BlockPos, BlockState)],
+                    equipment: PathEquipment| {
+        let mut blocks: Vec<(BlockPos, BlockState)> = vec![
+            (water_pos.down(1), floor.into()),
+            (water_pos, azalea_registry::Block::Water.into()),
+        ];
+        blocks.extend_from_slice(extra_blocks);
+        // the chunk storage only keeps weak references to the chunks
+        let mut partial_chunks = PartialChunkStorage::default();
+        let simulation = setup_simulation_world(&mut partial_chunks, water_pos, &[], &blocks);
+
+        let cached_world =
+            CachedWorld::new(simulation.instance(), BlockPos::default()).with_equipment(equipment);
+        let mining_cache = MiningCache::new(None);
+        let custom_state = CustomPathfinderStateRef::default();
+        let mut edges = Vec::new();
+        let ctx = PathfinderCtx {
+            edges: &mut edges,
+            world: &cached_world,
+            mining_cache: &mining_cache,
+            careful_mode_multiplier: 1.,
+            low_air_threshold: LOW_AIR_THRESHOLD,
+            move_settings: MoveSettings::default(),
+            swimming: SwimmingState::default(),
+            custom_state: &custom_state,
+        };
+        classify_water_at(&ctx, rel_water_pos)
+    };
+    let stone = azalea_registry::Block::Stone;
+    let block = |pos: BlockPos, block: azalea_registry::Block| (pos, BlockState::from(block));
+
+    assert_eq!(
+        classify(stone, &[], PathEquipment::default()),
+        Some(WaterType::StillWater)
+    );
+
+    // lava next to us, diagonally, and below
+    for lava_pos in [
+        water_pos.east(1),
+        water_pos.north(1).west(1).up(1),
+        water_pos.down(1),
+    ] {
+        let lava = [block(lava_pos, azalea_registry::Block::Lava)];
+        assert_eq!(
+            classify(stone, &lava, PathEquipment::default()),
+            Some(WaterType::Dangerous)
+        );
+    }
+    // lava that's two blocks away is fine
+    let far_lava = [block(water_pos.east(2), azalea_registry::Block::Lava)];
+    assert_eq!(
+        classify(stone, &far_lava, PathEquipment::default()),
+        Some(WaterType::StillWater)
+    );
+
+    // fire and soul fire on the blocks next to the water
+    for fire in [
+        azalea_registry::Block::Fire,
+        azalea_registry::Block::SoulFire,
+    ] {
+        let fire = [block(water_pos.south(1), fire)];
+        assert_eq!(
+            classify(stone, &fire, PathEquipment::default()),
+            Some(WaterType::Dangerous)
+        );
+    }
+
+    // magma right under shallow water burns us, unless we have frost walker
+    let magma = azalea_registry::Block::MagmaBlock;
+    assert_eq!(
+        classify(magma, &[], PathEquipment::default()),
+        Some(WaterType::Dangerous)
+    );
+    let frost_walker = PathEquipment {
+        frost_walker: 1,
+        ..Default::default()
+    };
+    assert_eq!(
+        classify(magma, &[], frost_walker),
+        Some(WaterType::StillWater)
+    );
+    // and it's fine if the water is deep enough that we can stay off of it
+    let deeper = [block(water_pos.up(1), azalea_registry::Block::Water)];
+    assert_eq!(
+        classify(magma, &deeper, PathEquipment::default()),
+        Some(WaterType::StillWater)
+    );
 }
 
 #[test]
@@ -422,18 +1022,18 @@ fn test_simple_water_pathfinding() {
         &water_blocks,
     );
 
-    simulation.app.world_mut().send_event(GotoEvent {
-        entity: simulation.entity,
-        goal: Arc::new(BlockPosGoal(end_pos)),
-        successors_fn: moves::default_move,
-        allow_mining: false,
-        retry_on_no_path: true,
-        min_timeout: PathfinderTimeout::Nodes(1_000_000),
-        max_timeout: PathfinderTimeout::Nodes(5_000_000),
-    });
+    simulation.app.world_mut().send_event(
+        GotoEvent::new(simulation.entity, BlockPosGoal(end_pos)).with_options(
+            GotoOptions::default()
+                .allow_mining(false)
+                .retry_on_no_path(true)
+                .min_timeout(PathfinderTimeout::Nodes(1_000_000))
+                .max_timeout(PathfinderTimeout::Nodes(5_000_000)),
+        ),
+    );
 
     // The bot should be able to swim through water to reach the destination
-    assert_simulation_reaches(&mut simulation, 300, end_pos);
+    assert_simulation_reaches(&mut simulation, 100, end_pos);
 }
 
 #[test]
@@ -465,15 +1065,15 @@ fn test_improved_water_pathfinding() {
         &water_blocks,
     );
 
-    simulation.app.world_mut().send_event(GotoEvent {
-        entity: simulation.entity,
-        goal: Arc::new(BlockPosGoal(end_pos)),
-        successors_fn: moves::default_move,
-        allow_mining: false,
-        retry_on_no_path: true,
-        min_timeout: PathfinderTimeout::Nodes(1_000_000),
-        max_timeout: PathfinderTimeout::Nodes(5_000_000),
-    });
+    simulation.app.world_mut().send_event(
+        GotoEvent::new(simulation.entity, BlockPosGoal(end_pos)).with_options(
+            GotoOptions::default()
+                .allow_mining(false)
+                .retry_on_no_path(true)
+                .min_timeout(PathfinderTimeout::Nodes(1_000_000))
+                .max_timeout(PathfinderTimeout::Nodes(5_000_000)),
+        ),
+    );
 
     // The bot should efficiently swim across the deep water
     // With improved costs, it should swim through rather than bob along the surface
@@ -495,111 +1095,638 @@ fn test_improved_water_pathfinding() {
 
 #[test]
 fn test_water_sprint_swimming_cost() {
-    use crate::pathfinder::moves::water::SwimmingState;
+    use parking_lot::RwLock;
 
-    // Mock pathfinder context would be needed for a full test
-    // This is a conceptual test showing how sprint swimming should work
-    
-    let normal_state = SwimmingState::default();
-    let mut sprint_state = SwimmingState::default();
-    sprint_state.consecutive_swim_moves = 5; // Should trigger sprint swimming
-    
-    // Sprint swimming should be more efficient than regular swimming
-    // when fully submerged for consecutive moves
-    assert!(sprint_state.consecutive_swim_moves >= 3);
-    assert_eq!(normal_state.consecutive_swim_moves, 0);
-}
+    use super::{
+        costs::DROWNING_AVOIDANCE_COST,
+        custom_state::CustomPathfinderStateRef,
+        mining::MiningCache,
+        moves::{
+            PathfinderCtx,
+            water::{SwimmingState, calculate_swimming_cost},
+        },
+        rel_block_pos::RelBlockPos,
+        world::CachedWorld,
+    };
 
-#[test]
-fn test_kelp_seagrass_navigation() {
     let mut partial_chunks = PartialChunkStorage::default();
-
-    // Create a path through water with kelp and seagrass obstacles
-    let start_pos = BlockPos::new(0, 70, 0);
-    let end_pos = BlockPos::new(6, 70, 0);
-
-    // Place stone foundation
-    let foundation: Vec<BlockPos> = (0..=6).map(|x| BlockPos::new(x, 69, 0)).collect();
-
-    // Create water blocks and aquatic plants
-    let mut water_blocks = Vec::new();
-    for x in 0..=6 {
-        // Water layer
-        water_blocks.push((BlockPos::new(x, 70, 0), azalea_registry::Block::Water.into()));
-        
-        // Add kelp and seagrass in the path to test navigation
-        if x == 2 {
-            water_blocks.push((BlockPos::new(x, 70, 0), azalea_registry::Block::Kelp.into()));
-        } else if x == 4 {
-            water_blocks.push((BlockPos::new(x, 70, 0), azalea_registry::Block::Seagrass.into()));
+    let mut world = ChunkStorage::default();
+    partial_chunks.set(&ChunkPos { x: 0, z: 0 }, Some(Chunk::default()), &mut world);
+    // water that's 4 blocks deep, with air above it
+    for x in 0..=2 {
+        for y in 69..=72 {
+            world.set_block_state(BlockPos::new(x, y, 0), azalea_registry::Block::Water.into());
         }
     }
 
-    let mut simulation = setup_simulation_world(
-        &mut partial_chunks,
-        start_pos,
-        &foundation,
-        &water_blocks,
-    );
+    let cached_world = CachedWorld::new(Arc::new(RwLock::new(world.into())), BlockPos::default());
+    let mining_cache = MiningCache::new(None);
+    let custom_state = CustomPathfinderStateRef::default();
+    let mut edges = Vec::new();
+    let ctx = PathfinderCtx {
+        edges: &mut edges,
+        world: &cached_world,
+        mining_cache: &mining_cache,
+        careful_mode_multiplier: 1.,
+        low_air_threshold: LOW_AIR_THRESHOLD,
+        move_settings: MoveSettings::default(),
+        swimming: SwimmingState::default(),
+        custom_state: &custom_state,
+    };
 
-    simulation.app.world_mut().send_event(GotoEvent {
-        entity: simulation.entity,
-        goal: Arc::new(BlockPosGoal(end_pos)),
-        successors_fn: moves::default_move,
-        allow_mining: false,
-        retry_on_no_path: true,
-        min_timeout: PathfinderTimeout::Nodes(1_000_000),
-        max_timeout: PathfinderTimeout::Nodes(5_000_000),
-    });
+    let sprint_state = SwimmingState {
+        consecutive_swim_moves: 5,
+        ..Default::default()
+    };
+    let low_air_state = SwimmingState {
+        estimated_air: 10,
+        ..Default::default()
+    };
 
-    // The bot should swim straight through kelp and seagrass to reach the destination
-    assert_simulation_reaches(&mut simulation, 250, end_pos);
+    // fully submerged
+    let (from, to) = (RelBlockPos::new(0, 70, 0), RelBlockPos::new(1, 70, 0));
+    let normal_cost = calculate_swimming_cost(&ctx, from, to, SwimmingState::default());
+    let sprint_cost = calculate_swimming_cost(&ctx, from, to, sprint_state);
+    assert!(sprint_cost < normal_cost);
+    // running out of air is much worse than swimming slowly
+    let low_air_cost = calculate_swimming_cost(&ctx, from, to, low_air_state);
+    assert!(low_air_cost > normal_cost + DROWNING_AVOIDANCE_COST / 2.);
+
+    // we can't sprint swim at the surface, since our head isn't underwater
+    let (from, to) = (RelBlockPos::new(0, 72, 0), RelBlockPos::new(1, 72, 0));
+    assert_eq!(
+        calculate_swimming_cost(&ctx, from, to, sprint_state),
+        calculate_swimming_cost(&ctx, from, to, SwimmingState::default())
+    );
 }
 
 #[test]
-fn test_straight_line_water_pathfinding() {
-    let mut partial_chunks = PartialChunkStorage::default();
-
-    // Create a longer water channel to test straight-line navigation
-    let start_pos = BlockPos::new(0, 70, 0);
-    let end_pos = BlockPos::new(10, 70, 0);
-
-    // Place stone foundation
-    let foundation: Vec<BlockPos> = (0..=10).map(|x| BlockPos::new(x, 69, 0)).collect();
+fn test_swimming_state_is_carried_along_path() {
+    use super::{costs::SPRINT_SWIMMING_COST, world::PathEquipment};
 
-    // Create water blocks - just pure water to test direct pathing
-    let water_blocks: Vec<(BlockPos, BlockState)> = (0..=10)
-        .map(|x| (BlockPos::new(x, 70, 0), azalea_registry::Block::Water.into()))
-        .collect();
+    let stone: BlockState = azalea_registry::Block::Stone.into();
+    let water: BlockState = azalea_registry::Block::Water.into();
 
-    let mut simulation = setup_simulation_world(
-        &mut partial_chunks,
-        start_pos,
-        &foundation,
-        &water_blocks,
-    );
+    // a 1 wide channel along the x axis with a ceiling at y=72. the water is 2
+    // blocks deep (so our head is underwater) wherever `is_deep` is true.
+    let channel = |length: i32, is_deep: &dyn Fn(i32) -> bool| {
+        let mut blocks = Vec::new();
+        for x in -1..=length + 1 {
+            blocks.push((BlockPos::new(x, 69, 0), stone));
+            blocks.push((BlockPos::new(x, 72, 0), stone));
+            for y in 70..=71 {
+                blocks.push((BlockPos::new(x, y, -1), stone));
+                blocks.push((BlockPos::new(x, y, 1), stone));
+            }
+        }
+        for y in 70..=71 {
+            blocks.push((BlockPos::new(-1, y, 0), stone));
+            blocks.push((BlockPos::new(length + 1, y, 0), stone));
+        }
+        for x in 0..=length {
+            blocks.push((BlockPos::new(x, 70, 0), water));
+            if is_deep(x) {
+                blocks.push((BlockPos::new(x, 71, 0), water));
+            }
+        }
+        blocks
+    };
+    let edge_costs = |length: i32, is_deep: &dyn Fn(i32) -> bool| {
+        let path = calculate_path_found_event(
+            BlockPos::new(0, 70, 0),
+            BlockPos::new(length, 70, 0),
+            &channel(length, is_deep),
+            false,
+            None,
+            PathEquipment::default(),
+        )
+        .path
+        .unwrap();
+        for (i, edge) in path.iter().enumerate() {
+            assert_eq!(edge.movement.target, BlockPos::new(i as i32 + 1, 70, 0));
+        }
+        path.iter().map(|edge| edge.cost).collect::<Vec<_>>()
+    };
 
-    simulation.app.world_mut().send_event(GotoEvent {
-        entity: simulation.entity,
-        goal: Arc::new(BlockPosGoal(end_pos)),
-        successors_fn: moves::default_move,
-        allow_mining: false,
-        retry_on_no_path: true,
-        min_timeout: PathfinderTimeout::Nodes(1_000_000),
-        max_timeout: PathfinderTimeout::Nodes(5_000_000),
-    });
+    // we start sprint swimming after we've been underwater for three nodes
+    for (i, cost) in edge_costs(30, &|_| true).into_iter().enumerate() {
+        let is_sprint_swimming = cost < SPRINT_SWIMMING_COST;
+        assert_eq!(is_sprint_swimming, i >= 3, "edge {i} costs {cost}");
+    }
 
-    // The bot should swim in a straight line without bobbing side to side
-    assert_simulation_reaches(&mut simulation, 300, end_pos);
+    // but a short dip isn't long enough for that
+    for cost in edge_costs(10, &|x| x == 4 || x == 5) {
+        assert!(cost > SPRINT_SWIMMING_COST, "{cost}");
+    }
 }
 
 #[test]
-fn test_aquatic_plant_classification() {
-    use crate::pathfinder::moves::water::{classify_water, WaterType};
+fn test_swimming_starts_with_our_air_supply() {
+    use parking_lot::RwLock;
 
-    // Test that all aquatic plants are properly classified as swimmable
-    let kelp = azalea_registry::Block::Kelp.into();
-    assert_eq!(classify_water(kelp), Some(WaterType::StillWater));
+    use super::{calculate_path, costs::DROWNING_AVOIDANCE_COST};
+
+    // an underwater tunnel with no way to come up for air
+    let mut partial_chunks = PartialChunkStorage::default();
+    let mut chunks = ChunkStorage::default();
+    partial_chunks.set(&ChunkPos::new(0, 0), Some(Chunk::default()), &mut chunks);
+    for x in 0..=8 {
+        for y in 69..=72 {
+            for z in 0..=2 {
+                let is_tunnel = (1..=7).contains(&x) && (70..=71).contains(&y) && z == 1;
+                let block = if is_tunnel {
+                    azalea_registry::Block::Water
+                } else {
+                    azalea_registry::Block::Stone
+                };
+                chunks.set_block_state(BlockPos::new(x, y, z), block.into());
+            }
+        }
+    }
+    let world_lock = Arc::new(RwLock::new(chunks.into()));
+
+    let first_edge_cost = |air_supply: i32| {
+        let path = calculate_path(
+            test_path_opts(
+                BlockPos::new(1, 70, 1),
+                Arc::new(BlockPosGoal(BlockPos::new(7, 70, 1))),
+                world_lock.clone(),
+            )
+            .successors_fn(moves::water::water_moves)
+            .air_supply(air_supply),
+        )
+        .unwrap()
+        .path
+        .unwrap();
+        path[0].cost
+    };
+
+    assert!(first_edge_cost(MAX_AIR_SUPPLY) < DROWNING_AVOIDANCE_COST);
+    // we're about to drown, so even the first move has to count that
+    assert!(first_edge_cost(10) > DROWNING_AVOIDANCE_COST);
+}
+
+#[test]
+fn test_long_dive_with_water_breathing() {
+    use super::{
+        costs::SWIMMING_COST,
+        world::{PathEffects, PathEquipment},
+    };
+
+    let stone: BlockState = azalea_registry::Block::Stone.into();
+    let water: BlockState = azalea_registry::Block::Water.into();
+
+    // a 40 block long underwater tunnel with no way to come up for air
+    let length = 40;
+    let mut blocks = Vec::new();
+    for x in 0..=length + 2 {
+        for y in 69..=72 {
+            for z in 0..=2 {
+                let is_tunnel = (1..=length + 1).contains(&x) && (70..=71).contains(&y) && z == 1;
+                let block_state = if is_tunnel { water } else { stone };
+                blocks.push((BlockPos::new(x, y, z), block_state));
+            }
+        }
+    }
+    let edge_costs = |equipment: PathEquipment| {
+        calculate_path_found_event(
+            BlockPos::new(1, 70, 1),
+            BlockPos::new(length + 1, 70, 1),
+            &blocks,
+            false,
+            None,
+            equipment,
+        )
+        .path
+        .unwrap()
+        .iter()
+        .map(|edge| edge.cost)
+        .collect::<Vec<_>>()
+    };
+
+    // we'd be almost out of air by the end, so the last moves get penalized
+    let without_help = edge_costs(PathEquipment::default());
+    assert!(without_help.iter().any(|&cost| cost > SWIMMING_COST));
+
+    let water_breathing = PathEquipment::default().with_effects(PathEffects {
+        water_breathing: true,
+        ..Default::default()
+    });
+    for cost in edge_costs(water_breathing) {
+        assert!(cost < SWIMMING_COST, "{cost}");
+    }
+
+    // respiration III makes our air last four times as long
+    let respiration = PathEquipment {
+        respiration: 3,
+        ..Default::default()
+    };
+    for cost in edge_costs(respiration) {
+        assert!(cost < SWIMMING_COST, "{cost}");
+    }
+}
+
+#[test]
+fn test_depth_strider_makes_swimming_worth_it() {
+    use parking_lot::RwLock;
+
+    use super::{
+        call_successors_fn,
+        costs::SPRINT_ONE_BLOCK_COST,
+        custom_state::CustomPathfinderStateRef,
+        mining::MiningCache,
+        moves::water::SwimmingState,
+        rel_block_pos::RelBlockPos,
+        world::{CachedWorld, PathEffects, PathEquipment},
+    };
+
+    let mut partial_chunks = PartialChunkStorage::default();
+    let mut chunks = ChunkStorage::default();
+    partial_chunks.set(&ChunkPos::new(0, 0), Some(Chunk::default()), &mut chunks);
+    for x in 0..=2 {
+        let stone = azalea_registry::Block::Stone.into();
+        let water = azalea_registry::Block::Water.into();
+        chunks.set_block_state(BlockPos::new(x, 69, 0), stone);
+        chunks.set_block_state(BlockPos::new(x, 70, 0), water);
+    }
+    let world_lock = Arc::new(RwLock::new(chunks.into()));
+
+    // the cost of swimming one block along the surface
+    let swim_cost = |equipment: PathEquipment| {
+        let cached_world =
+            CachedWorld::new(world_lock.clone(), BlockPos::default()).with_equipment(equipment);
+        let edges = call_successors_fn(
+            &cached_world,
+            &MiningCache::new(None),
+            1.,
+            LOW_AIR_THRESHOLD,
+            MoveSettings::default(),
+            &CustomPathfinderStateRef::default(),
+            moves::water::water_moves,
+            SwimmingState::default(),
+            RelBlockPos::new(1, 70, 0),
+        );
+        edges
+            .iter()
+            .find(|edge| edge.movement.target == RelBlockPos::new(2, 70, 0))
+            .unwrap()
+            .cost
+    };
+
+    // swimming 20 blocks across a lake vs sprinting 35 blocks around it
+    let land_detour = 35. * SPRINT_ONE_BLOCK_COST;
+    assert!(20. * swim_cost(PathEquipment::default()) > land_detour);
+    let depth_strider = PathEquipment {
+        depth_strider: 3,
+        ..Default::default()
+    };
+    assert!(20. * swim_cost(depth_strider) < land_detour);
+
+    let dolphins_grace = PathEquipment::default().with_effects(PathEffects {
+        dolphins_grace: true,
+        ..Default::default()
+    });
+    assert!(swim_cost(dolphins_grace) < swim_cost(depth_strider));
+}
+
+#[test]
+fn test_air_pocket_to_the_side_counts_as_air_access() {
+    use parking_lot::RwLock;
+
+    use super::{
+        call_successors_fn, custom_state::CustomPathfinderStateRef, mining::MiningCache,
+        moves::water::SwimmingState, rel_block_pos::RelBlockPos, world::CachedWorld,
+    };
+
+    // a flooded cave with a stone ceiling at y=71. when `has_pocket` is true,
+    // there's a single block of air in the ceiling at x=5, which is 2 blocks to
+    // the side of where we descend to
+    let descend_cost = |has_pocket: bool| {
+        let mut partial_chunks = PartialChunkStorage::default();
+        let mut chunks = ChunkStorage::default();
+        partial_chunks.set(&ChunkPos::new(0, 0), Some(Chunk::default()), &mut chunks);
+        for x in 0..=6 {
+            for y in 66..=72 {
+                for z in 0..=2 {
+                    let pos = BlockPos::new(x, y, z);
+                    let is_cave = (1..=5).contains(&x) && (67..=70).contains(&y) && z == 1;
+                    let is_pocket = has_pocket && pos == BlockPos::new(5, 71, 1);
+                    let block = if is_cave {
+                        azalea_registry::Block::Water
+                    } else if is_pocket {
+                        azalea_registry::Block::Air
+                    } else {
+                        azalea_registry::Block::Stone
+                    };
+                    chunks.set_block_state(pos, block.into());
+                }
+            }
+        }
+
+        let cached_world =
+            CachedWorld::new(Arc::new(RwLock::new(chunks.into())), BlockPos::default());
+        let edges = call_successors_fn(
+            &cached_world,
+            &MiningCache::new(None),
+            1.,
+            LOW_AIR_THRESHOLD,
+            MoveSettings::default(),
+            &CustomPathfinderStateRef::default(),
+            moves::water::water_moves,
+            SwimmingState::default(),
+            RelBlockPos::new(2, 70, 1),
+        );
+        edges
+            .iter()
+            .find(|edge| edge.movement.target == RelBlockPos::new(3, 69, 1))
+            .unwrap()
+            .cost
+    };
+
+    // the pocket isn't above us, so looking straight up wouldn't find it
+    let with_pocket = descend_cost(true);
+    let without_pocket = descend_cost(false);
+    assert!(with_pocket < without_pocket);
+    assert!((without_pocket / with_pocket - 1.2).abs() < 0.001);
+}
+
+#[test]
+fn test_frost_walker_walks_over_still_water() {
+    use super::world::PathEquipment;
+
+    // a lake that's too deep to walk along the bottom of, with a bank on each
+    // side
+    let stone = BlockState::from(azalea_registry::Block::Stone);
+    let water = BlockState::from(azalea_registry::Block::Water);
+    let mut blocks = Vec::new();
+    for z in 0..=8 {
+        blocks.push((BlockPos::new(0, 69, z), stone));
+        blocks.push((BlockPos::new(8, 69, z), stone));
+        for x in 1..=7 {
+            blocks.push((BlockPos::new(x, 64, z), stone));
+            for y in 65..=69 {
+                blocks.push((BlockPos::new(x, y, z), water));
+            }
+        }
+    }
+
+    let start = BlockPos::new(0, 70, 4);
+    let end = BlockPos::new(8, 70, 4);
+    let equipment = PathEquipment {
+        frost_walker: 1,
+        ..Default::default()
+    };
+    let path = calculate_path_found_event(start, end, &blocks, false, None, equipment);
+    assert!(!path.is_partial);
+    let targets = path
+        .path
+        .unwrap()
+        .iter()
+        .map(|edge| edge.movement.target)
+        .collect::<Vec<_>>();
+    // we never go into the water
+    let stays_on_surface = targets.iter().all(|pos| pos.y == 70 && pos.z == 4);
+    assert!(stays_on_surface, "{targets:?}");
+    assert_eq!(targets.len(), 8);
+
+    // flowing water can't be frozen
+    for (pos, block_state) in &mut blocks {
+        if pos.y == 69 && pos.x == 4 {
+            *block_state = azalea_block::blocks::Water {
+                level: azalea_block::properties::WaterLevel::_1,
+            }
+            .into();
+        }
+    }
+    let path = calculate_path_found_event(start, end, &blocks, false, None, equipment);
+    let path = path.path.unwrap();
+    assert!(
+        path.iter()
+            .all(|edge| !(edge.movement.target.x == 4 && edge.movement.target.y == 70))
+    );
+}
+
+#[test]
+fn test_prefers_swimming_downstream() {
+    use azalea_block::{blocks, properties::WaterLevel};
+
+    let stone: BlockState = azalea_registry::Block::Stone.into();
+    let flowing_water = |level: u16| {
+        BlockState::from(blocks::Water {
+            level: WaterLevel::from(level),
+        })
+    };
+
+    // two identical channels from x=0 to x=6 with a wall in between, except that
+    // the water in the z=0 one flows towards the goal and the z=2 one flows
+    // away from it
+    let mut blocks = Vec::new();
+    for x in -2..=8 {
+        for z in -1..=3 {
+            blocks.push((BlockPos::new(x, 69, z), stone));
+            blocks.push((BlockPos::new(x, 72, z), stone));
+            let is_wall =
+                x == -2 || x == 8 || z == -1 || z == 3 || (z == 1 && (0..=6).contains(&x));
+            if is_wall {
+                blocks.push((BlockPos::new(x, 70, z), stone));
+                blocks.push((BlockPos::new(x, 71, z), stone));
+            }
+        }
+    }
+    for x in 0..=6_u16 {
+        let x_pos = x as i32;
+        blocks.push((BlockPos::new(x_pos, 70, 0), flowing_water(x + 1)));
+        blocks.push((BlockPos::new(x_pos, 70, 2), flowing_water(7 - x)));
+    }
+
+    let path = calculate_path_targets(
+        BlockPos::new(-1, 70, 1),
+        BlockPos::new(7, 70, 1),
+        &blocks,
+        false,
+    );
+    assert!(path.contains(&BlockPos::new(3, 70, 0)), "{path:?}");
+    assert!(path.iter().all(|pos| pos.z != 2), "{path:?}");
+}
+
+#[test]
+fn test_kelp_seagrass_navigation() {
+    let mut partial_chunks = PartialChunkStorage::default();
+
+    // Create a path through water with kelp and seagrass obstacles
+    let start_pos = BlockPos::new(0, 70, 0);
+    let end_pos = BlockPos::new(6, 70, 0);
+
+    // Place stone foundation
+    let foundation: Vec<BlockPos> = (0..=6).map(|x| BlockPos::new(x, 69, 0)).collect();
+
+    // Create water blocks and aquatic plants
+    let mut water_blocks = Vec::new();
+    for x in 0..=6 {
+        // Water layer
+        water_blocks.push((BlockPos::new(x, 70, 0), azalea_registry::Block::Water.into()));
+        
+        // Add kelp and seagrass in the path to test navigation
+        if x == 2 {
+            water_blocks.push((BlockPos::new(x, 70, 0), azalea_registry::Block::Kelp.into()));
+        } else if x == 4 {
+            water_blocks.push((BlockPos::new(x, 70, 0), azalea_registry::Block::Seagrass.into()));
+        }
+    }
+
+    let mut simulation = setup_simulation_world(
+        &mut partial_chunks,
+        start_pos,
+        &foundation,
+        &water_blocks,
+    );
+
+    simulation.app.world_mut().send_event(
+        GotoEvent::new(simulation.entity, BlockPosGoal(end_pos)).with_options(
+            GotoOptions::default()
+                .allow_mining(false)
+                .retry_on_no_path(true)
+                .min_timeout(PathfinderTimeout::Nodes(1_000_000))
+                .max_timeout(PathfinderTimeout::Nodes(5_000_000)),
+        ),
+    );
+
+    // The bot should swim straight through kelp and seagrass to reach the destination
+    assert_simulation_reaches(&mut simulation, 250, end_pos);
+}
+
+#[test]
+fn test_break_kelp_in_tunnel() {
+    use azalea_inventory::Menu;
+    use parking_lot::RwLock;
+
+    use super::{
+        call_successors_fn, custom_state::CustomPathfinderStateRef, mining::MiningCache,
+        moves::water::SwimmingState, rel_block_pos::RelBlockPos, world::CachedWorld,
+    };
+
+    // a flooded tunnel that's 2 blocks tall, with kelp from x=2 to x=4
+    let water: BlockState = azalea_registry::Block::Water.into();
+    let mut solid_blocks = Vec::new();
+    let mut blocks = Vec::new();
+    for x in 0..=6 {
+        solid_blocks.push(BlockPos::new(x, 69, 1));
+        solid_blocks.push(BlockPos::new(x, 72, 1));
+        for y in 70..=71 {
+            solid_blocks.push(BlockPos::new(x, y, 0));
+            solid_blocks.push(BlockPos::new(x, y, 2));
+        }
+        let (bottom, top) = if (2..=4).contains(&x) {
+            (
+                azalea_registry::Block::KelpPlant.into(),
+                azalea_registry::Block::Kelp.into(),
+            )
+        } else {
+            (water, water)
+        };
+        blocks.push((BlockPos::new(x, 70, 1), bottom));
+        blocks.push((BlockPos::new(x, 71, 1), top));
+    }
+
+    // swimming into the kelp is cheaper when we can break it
+    let swim_cost = |mining_cache: MiningCache| {
+        let mut partial_chunks = PartialChunkStorage::default();
+        let mut chunks = ChunkStorage::default();
+        partial_chunks.set(&ChunkPos::new(0, 0), Some(Chunk::default()), &mut chunks);
+        for pos in &solid_blocks {
+            chunks.set_block_state(*pos, azalea_registry::Block::Stone.into());
+        }
+        for (pos, block_state) in &blocks {
+            chunks.set_block_state(*pos, *block_state);
+        }
+        let cached_world =
+            CachedWorld::new(Arc::new(RwLock::new(chunks.into())), BlockPos::default());
+        let edges = call_successors_fn(
+            &cached_world,
+            &mining_cache,
+            1.,
+            LOW_AIR_THRESHOLD,
+            MoveSettings::default(),
+            &CustomPathfinderStateRef::default(),
+            moves::water::water_moves,
+            SwimmingState::default(),
+            RelBlockPos::new(1, 70, 1),
+        );
+        edges
+            .iter()
+            .find(|edge| edge.movement.target == RelBlockPos::new(2, 70, 1))
+            .unwrap()
+            .cost
+    };
+    let breaking_cost = swim_cost(MiningCache::new(Some(Menu::Player(
+        azalea_inventory::Player::default(),
+    ))));
+    let pushing_through_cost = swim_cost(MiningCache::new(None));
+    assert!(breaking_cost < pushing_through_cost);
+
+    let mut partial_chunks = PartialChunkStorage::default();
+    let mut simulation = setup_simulation_world(
+        &mut partial_chunks,
+        BlockPos::new(0, 70, 1),
+        &solid_blocks,
+        &blocks,
+    );
+    simulation.app.world_mut().send_event(
+        GotoEvent::new(simulation.entity, BlockPosGoal(BlockPos::new(6, 70, 1))).with_options(
+            GotoOptions::default()
+                .allow_mining(true)
+                .retry_on_no_path(true)
+                .min_timeout(PathfinderTimeout::Nodes(1_000_000))
+                .max_timeout(PathfinderTimeout::Nodes(5_000_000)),
+        ),
+    );
+    assert_simulation_reaches(&mut simulation, 200, BlockPos::new(6, 70, 1));
+}
+
+#[test]
+fn test_straight_line_water_pathfinding() {
+    let mut partial_chunks = PartialChunkStorage::default();
+
+    // Create a longer water channel to test straight-line navigation
+    let start_pos = BlockPos::new(0, 70, 0);
+    let end_pos = BlockPos::new(10, 70, 0);
+
+    // Place stone foundation
+    let foundation: Vec<BlockPos> = (0..=10).map(|x| BlockPos::new(x, 69, 0)).collect();
+
+    // Create water blocks - just pure water to test direct pathing
+    let water_blocks: Vec<(BlockPos, BlockState)> = (0..=10)
+        .map(|x| (BlockPos::new(x, 70, 0), azalea_registry::Block::Water.into()))
+        .collect();
+
+    let mut simulation = setup_simulation_world(
+        &mut partial_chunks,
+        start_pos,
+        &foundation,
+        &water_blocks,
+    );
+
+    simulation.app.world_mut().send_event(
+        GotoEvent::new(simulation.entity, BlockPosGoal(end_pos)).with_options(
+            GotoOptions::default()
+                .allow_mining(false)
+                .retry_on_no_path(true)
+                .min_timeout(PathfinderTimeout::Nodes(1_000_000))
+                .max_timeout(PathfinderTimeout::Nodes(5_000_000)),
+        ),
+    );
+
+    // The bot should swim in a straight line without bobbing side to side
+    assert_simulation_reaches(&mut simulation, 300, end_pos);
+}
+
+#[test]
+fn test_aquatic_plant_classification() {
+    use crate::pathfinder::moves::water::{classify_water, WaterType};
+
+    // Test that all aquatic plants are properly classified as swimmable
+    let kelp = azalea_registry::Block::Kelp.into();
+    assert_eq!(classify_water(kelp), Some(WaterType::StillWater));
 
     let seagrass = azalea_registry::Block::Seagrass.into();
     assert_eq!(classify_water(seagrass), Some(WaterType::StillWater));
@@ -613,3 +1740,3992 @@ fn test_aquatic_plant_classification() {
     let sea_pickle = azalea_registry::Block::SeaPickle.into();
     assert_eq!(classify_water(sea_pickle), Some(WaterType::StillWater));
 }
+
+#[test]
+fn test_descend_onto_powder_snow_requires_boots() {
+    use parking_lot::RwLock;
+
+    use super::{
+        call_successors_fn,
+        custom_state::CustomPathfinderStateRef,
+        mining::MiningCache,
+        moves::water::SwimmingState,
+        rel_block_pos::RelBlockPos,
+        world::{CachedWorld, PathEquipment},
+    };
+
+    let descend_target = RelBlockPos::new(1, 1, 0);
+    let can_descend = |has_leather_boots: bool| {
+        let mut partial_chunks = PartialChunkStorage::default();
+        let mut world = ChunkStorage::default();
+        partial_chunks.set(&ChunkPos { x: 0, z: 0 }, Some(Chunk::default()), &mut world);
+        partial_chunks.set_block_state(
+            BlockPos::new(0, 2, 0),
+            azalea_registry::Block::Stone.into(),
+            &world,
+        );
+        partial_chunks.set_block_state(
+            BlockPos::new(1, 0, 0),
+            azalea_registry::Block::PowderSnow.into(),
+            &world,
+        );
+
+        let cached_world =
+            CachedWorld::new(Arc::new(RwLock::new(world.into())), BlockPos::default())
+                .with_equipment(PathEquipment {
+                    has_leather_boots,
+                    ..Default::default()
+                });
+        let mining_cache = MiningCache::new(None);
+        let custom_state = CustomPathfinderStateRef::default();
+        let edges = call_successors_fn(
+            &cached_world,
+            &mining_cache,
+            1.,
+            LOW_AIR_THRESHOLD,
+            MoveSettings::default(),
+            &custom_state,
+            moves::default_move,
+            SwimmingState::default(),
+            RelBlockPos::new(0, 3, 0),
+        );
+        edges
+            .iter()
+            .any(|edge| edge.movement.target == descend_target)
+    };
+
+    assert!(!can_descend(false));
+    assert!(can_descend(true));
+}
+
+#[test]
+fn test_avoid_powder_snow_without_boots() {
+    use super::world::PathEquipment;
+
+    // a straight corridor with powder snow in the floor halfway along it
+    let stone = BlockState::from(azalea_registry::Block::Stone);
+    let mut blocks = Vec::new();
+    for x in 0..=6 {
+        for z in -1..=1 {
+            blocks.push((BlockPos::new(x, 69, z), stone));
+        }
+    }
+    let powder_snow_pos = BlockPos::new(3, 69, 0);
+    for (pos, block_state) in &mut blocks {
+        if *pos == powder_snow_pos {
+            *block_state = azalea_registry::Block::PowderSnow.into();
+        }
+    }
+
+    let path_targets = |equipment: PathEquipment| {
+        let path = calculate_path_found_event(
+            BlockPos::new(0, 70, 0),
+            BlockPos::new(6, 70, 0),
+            &blocks,
+            false,
+            None,
+            equipment,
+        );
+        assert!(!path.is_partial);
+        path.path
+            .unwrap()
+            .iter()
+            .map(|edge| edge.movement.target)
+            .collect::<Vec<_>>()
+    };
+
+    let targets = path_targets(PathEquipment::default());
+    assert!(!targets.contains(&powder_snow_pos.up(1)), "{targets:?}");
+
+    let targets = path_targets(PathEquipment {
+        has_leather_boots: true,
+        ..Default::default()
+    });
+    assert!(targets.contains(&powder_snow_pos.up(1)), "{targets:?}");
+}
+
+#[test]
+fn test_climb_powder_snow_with_boots() {
+    use super::world::PathEquipment;
+
+    // a column of powder snow next to a ledge that's too high to jump up to
+    let stone = BlockState::from(azalea_registry::Block::Stone);
+    let powder_snow = BlockState::from(azalea_registry::Block::PowderSnow);
+    let mut blocks = vec![
+        (BlockPos::new(0, 69, 0), stone),
+        (BlockPos::new(1, 69, 0), stone),
+        (BlockPos::new(2, 73, 0), stone),
+    ];
+    for y in 70..=73 {
+        blocks.push((BlockPos::new(1, y, 0), powder_snow));
+    }
+
+    let start = BlockPos::new(0, 70, 0);
+    let end = BlockPos::new(2, 74, 0);
+
+    let without_boots = PathEquipment::default();
+    let path = calculate_path_found_event(start, end, &blocks, false, None, without_boots);
+    assert!(path.is_partial);
+
+    let with_boots = PathEquipment {
+        has_leather_boots: true,
+        ..Default::default()
+    };
+    let path = calculate_path_found_event(start, end, &blocks, false, None, with_boots);
+    assert!(!path.is_partial);
+    let targets = path
+        .path
+        .unwrap()
+        .iter()
+        .map(|edge| edge.movement.target)
+        .collect::<Vec<_>>();
+    assert_eq!(targets, vec![BlockPos::new(1, 74, 0), end]);
+}
+
+#[test]
+fn test_avoid_standing_on_magma() {
+    // a corridor with a strip of magma blocks across it, and optionally some dirt
+    // beside the strip that we can walk around it on
+    let setup = |partial_chunks: &mut PartialChunkStorage, with_detour: bool| {
+        let mut solid_blocks = Vec::new();
+        let mut extra_blocks = Vec::new();
+        for x in 0..=8 {
+            for z in -1..=1 {
+                let pos = BlockPos::new(x, 69, z);
+                if (3..=5).contains(&x) {
+                    extra_blocks.push((pos, azalea_registry::Block::MagmaBlock.into()));
+                } else {
+                    solid_blocks.push(pos);
+                }
+            }
+        }
+        if with_detour {
+            for x in 2..=6 {
+                extra_blocks.push((BlockPos::new(x, 69, 2), azalea_registry::Block::Dirt.into()));
+            }
+        }
+
+        let mut simulation = setup_simulation_world(
+            partial_chunks,
+            BlockPos::new(0, 70, 0),
+            &solid_blocks,
+            &extra_blocks,
+        );
+        simulation.app.world_mut().send_event(
+            GotoEvent::new(simulation.entity, BlockPosGoal(BlockPos::new(8, 70, 0))).with_options(
+                GotoOptions::default()
+                    .allow_mining(false)
+                    .retry_on_no_path(true)
+                    .min_timeout(PathfinderTimeout::Nodes(1_000_000))
+                    .max_timeout(PathfinderTimeout::Nodes(5_000_000))
+                    .avoid_damage(false),
+            ),
+        );
+        simulation
+    };
+    let is_over_magma = |pos: &Vec3| (3. ..6.).contains(&pos.x) && pos.z < 1.7;
+
+    let mut partial_chunks = PartialChunkStorage::default();
+    let mut simulation = setup(&mut partial_chunks, true);
+    let trajectory = record_trajectory(&mut simulation, 100);
+    assert!(!trajectory.iter().any(is_over_magma), "{trajectory:?}");
+    assert_eq!(
+        BlockPos::from(simulation.position()),
+        BlockPos::new(8, 70, 0)
+    );
+
+    // there's no other way, so we walk across it anyways
+    let mut partial_chunks = PartialChunkStorage::default();
+    let mut simulation = setup(&mut partial_chunks, false);
+    let trajectory = record_trajectory(&mut simulation, 100);
+    assert!(trajectory.iter().any(is_over_magma), "{trajectory:?}");
+    assert_eq!(
+        BlockPos::from(simulation.position()),
+        BlockPos::new(8, 70, 0)
+    );
+}
+
+#[test]
+fn test_soul_sand_costs_more_than_stone() {
+    use super::world::PathEquipment;
+
+    let path_cost = |floor: azalea_registry::Block, equipment: PathEquipment| {
+        let mut blocks = vec![(
+            BlockPos::new(0, 69, 0),
+            BlockState::from(azalea_registry::Block::Stone),
+        )];
+        for x in 1..=4 {
+            blocks.push((BlockPos::new(x, 69, 0), floor.into()));
+        }
+        let path = calculate_path_found_event(
+            BlockPos::new(0, 70, 0),
+            BlockPos::new(4, 70, 0),
+            &blocks,
+            false,
+            None,
+            equipment,
+        );
+        assert!(!path.is_partial);
+        path.path.unwrap().iter().map(|edge| edge.cost).sum::<f32>()
+    };
+
+    let stone = path_cost(azalea_registry::Block::Stone, PathEquipment::default());
+    let soul_sand = path_cost(azalea_registry::Block::SoulSand, PathEquipment::default());
+    assert!(soul_sand > stone * 1.5, "{soul_sand} vs {stone}");
+
+    // soul speed makes soul sand faster than stone instead
+    let soul_speed = PathEquipment {
+        soul_speed: 1,
+        ..Default::default()
+    };
+    let soul_sand = path_cost(azalea_registry::Block::SoulSand, soul_speed);
+    assert!(soul_sand < stone, "{soul_sand} vs {stone}");
+}
+
+#[test]
+fn test_walk_around_soul_sand() {
+    // two rows of floor next to each other, where most of the row that the start
+    // and goal are on is soul sand
+    let mut partial_chunks = PartialChunkStorage::default();
+    let mut solid_blocks = Vec::new();
+    let mut extra_blocks: Vec<(BlockPos, BlockState)> = Vec::new();
+    for x in 0..=10 {
+        if (3..=7).contains(&x) {
+            extra_blocks.push((
+                BlockPos::new(x, 69, 0),
+                azalea_registry::Block::SoulSand.into(),
+            ));
+        } else {
+            solid_blocks.push(BlockPos::new(x, 69, 0));
+        }
+        solid_blocks.push(BlockPos::new(x, 69, 1));
+    }
+
+    let end = BlockPos::new(10, 70, 0);
+    let mut simulation = setup_simulation_world(
+        &mut partial_chunks,
+        BlockPos::new(0, 70, 0),
+        &solid_blocks,
+        &extra_blocks,
+    );
+    simulation.app.world_mut().send_event(
+        GotoEvent::new(simulation.entity, BlockPosGoal(end)).with_options(
+            GotoOptions::default()
+                .allow_mining(false)
+                .retry_on_no_path(true)
+                .min_timeout(PathfinderTimeout::Nodes(1_000_000))
+                .max_timeout(PathfinderTimeout::Nodes(5_000_000)),
+        ),
+    );
+
+    let trajectory = record_trajectory(&mut simulation, 100);
+    let is_over_soul_sand = |pos: &Vec3| (3. ..8.).contains(&pos.x) && pos.z < 0.8;
+    assert!(!trajectory.iter().any(is_over_soul_sand), "{trajectory:?}");
+    assert_eq!(BlockPos::from(simulation.position()), end);
+}
+
+#[test]
+fn test_capture_round_trip_reproduces_path() {
+    use parking_lot::RwLock;
+
+    use super::{calculate_path, capture::PathfinderCapture};
+
+    // the same world as test_descend_and_parkour_2_block_gap
+    let start_pos = BlockPos::new(0, 71, 0);
+    let end_pos = BlockPos::new(3, 67, 4);
+    let solid_blocks = [
+        BlockPos::new(0, 70, 0),
+        BlockPos::new(0, 69, 1),
+        BlockPos::new(0, 68, 2),
+        BlockPos::new(0, 67, 3),
+        BlockPos::new(0, 66, 4),
+        BlockPos::new(3, 66, 4),
+    ];
+
+    let mut partial_chunks = PartialChunkStorage::default();
+    let mut chunks = ChunkStorage::default();
+    partial_chunks.set(
+        &ChunkPos { x: 0, z: 0 },
+        Some(Chunk::default()),
+        &mut chunks,
+    );
+    for block_pos in solid_blocks {
+        chunks.set_block_state(block_pos, azalea_registry::Block::Stone.into());
+    }
+
+    let goal = Arc::new(BlockPosGoal(end_pos));
+    let original = calculate_path(
+        test_path_opts(
+            start_pos,
+            goal.clone(),
+            Arc::new(RwLock::new(chunks.into())),
+        )
+        .capture(true),
+    )
+    .unwrap();
+    let original_path = original.path.unwrap();
+    assert!(!original_path.is_empty());
+
+    let mut buf = Vec::new();
+    original.capture.unwrap().write(&mut buf).unwrap();
+    let capture = PathfinderCapture::read(&buf).unwrap();
+    assert_eq!(capture.goal, format!("{goal:?}"));
+    // only the sections that the search looked at should be included
+    assert!(capture.sections.len() < 24);
+
+    let (_partial_chunks, chunks) = capture.load_chunks();
+    let replayed = calculate_path(capture.calculate_path_opts(
+        chunks.clone(),
+        goal.clone(),
+        moves::default_move,
+    ))
+    .unwrap();
+
+    let original_targets = original_path
+        .iter()
+        .map(|edge| edge.movement.target)
+        .collect::<Vec<_>>();
+    let replayed_targets = replayed
+        .path
+        .unwrap()
+        .iter()
+        .map(|edge| edge.movement.target)
+        .collect::<Vec<_>>();
+    assert_eq!(replayed_targets, original_targets);
+    assert_eq!(replayed.is_partial, original.is_partial);
+
+    let mut simulation = capture.simulation(chunks);
+    simulation.app.world_mut().send_event(capture.goto_event(
+        simulation.entity,
+        goal,
+        moves::default_move,
+    ));
+    assert_simulation_reaches(&mut simulation, 100, end_pos);
+}
+
+#[test]
+fn test_humanized_mining_is_paced() {
+    use azalea_entity::LookDirection;
+
+    use super::humanize::{HumanizeSettings, HumanizeState};
+
+    const MIN_TICKS_BETWEEN_BREAKS: u32 = 30;
+    const MAX_ROTATION_PER_TICK: f32 = 10.;
+
+    let mut partial_chunks = PartialChunkStorage::default();
+    // a tunnel that's blocked by dirt, so we have to mine through it
+    let mut solid_blocks = Vec::new();
+    for z in 0..=3 {
+        solid_blocks.push(BlockPos::new(0, 70, z));
+        solid_blocks.push(BlockPos::new(-1, 71, z));
+        solid_blocks.push(BlockPos::new(-1, 72, z));
+        solid_blocks.push(BlockPos::new(1, 71, z));
+        solid_blocks.push(BlockPos::new(1, 72, z));
+        solid_blocks.push(BlockPos::new(0, 73, z));
+    }
+    let dirt_blocks = [
+        BlockPos::new(0, 71, 1),
+        BlockPos::new(0, 72, 1),
+        BlockPos::new(0, 71, 2),
+        BlockPos::new(0, 72, 2),
+    ]
+    .map(|pos| (pos, azalea_registry::Block::Dirt.into()));
+    let mut simulation = setup_simulation_world(
+        &mut partial_chunks,
+        BlockPos::new(0, 71, 0),
+        &solid_blocks,
+        &dirt_blocks,
+    );
+    simulation
+        .app
+        .world_mut()
+        .entity_mut(simulation.entity)
+        .insert(HumanizeSettings {
+            min_ticks_between_breaks: MIN_TICKS_BETWEEN_BREAKS,
+            max_breaks_per_second: None,
+            max_rotation_per_tick: Some(MAX_ROTATION_PER_TICK),
+        });
+
+    simulation.app.world_mut().send_event(
+        GotoEvent::new(simulation.entity, BlockPosGoal(BlockPos::new(0, 71, 3))).with_options(
+            GotoOptions::default()
+                .allow_mining(true)
+                .retry_on_no_path(true)
+                .min_timeout(PathfinderTimeout::Nodes(1_000_000))
+                .max_timeout(PathfinderTimeout::Nodes(5_000_000)),
+        ),
+    );
+
+    let mut break_starts = Vec::new();
+    let mut last_look_direction = simulation.component::<LookDirection>();
+    for _ in 0..600 {
+        simulation.tick();
+
+        let look_direction = simulation.component::<LookDirection>();
+        let y_rot_delta =
+            ((look_direction.y_rot - last_look_direction.y_rot + 180.).rem_euclid(360.) - 180.)
+                .abs();
+        let x_rot_delta = (look_direction.x_rot - last_look_direction.x_rot).abs();
+        assert!(
+            y_rot_delta <= MAX_ROTATION_PER_TICK + 0.01
+                && x_rot_delta <= MAX_ROTATION_PER_TICK + 0.01,
+            "turned too fast, from {last_look_direction:?} to {look_direction:?}"
+        );
+        last_look_direction = look_direction;
+
+        if let Some(state) = simulation.get_component::<HumanizeState>()
+            && let Some(last_break_start) = state.last_break_start
+            && break_starts.last() != Some(&last_break_start)
+        {
+            break_starts.push(last_break_start);
+        }
+    }
+
+    assert_eq!(
+        BlockPos::from(simulation.position()),
+        BlockPos::new(0, 71, 3)
+    );
+    assert!(break_starts.len() >= dirt_blocks.len());
+    for gap in break_starts.windows(2).map(|w| w[1] - w[0]) {
+        assert!(
+            gap >= MIN_TICKS_BETWEEN_BREAKS as u64,
+            "only waited {gap} ticks between breaks"
+        );
+    }
+}
+
+/// Run the pathfinder synchronously and return the positions that the path goes
+/// through.
+fn calculate_path_targets(
+    start_pos: BlockPos,
+    end_pos: BlockPos,
+    blocks: &[(BlockPos, BlockState)],
+    allow_mining: bool,
+) -> Vec<BlockPos> {
+    calculate_path_targets_with_risk(start_pos, end_pos, blocks, allow_mining, None)
+}
+
+fn calculate_path_targets_with_risk(
+    start_pos: BlockPos,
+    end_pos: BlockPos,
+    blocks: &[(BlockPos, BlockState)],
+    allow_mining: bool,
+    risk: Option<super::risk::RiskLayer>,
+) -> Vec<BlockPos> {
+    let path = calculate_path_found_event(
+        start_pos,
+        end_pos,
+        blocks,
+        allow_mining,
+        risk,
+        super::world::PathEquipment::default(),
+    );
+    assert!(!path.is_partial);
+
+    path.path
+        .unwrap()
+        .iter()
+        .map(|edge| edge.movement.target)
+        .collect()
+}
+
+fn calculate_path_found_event(
+    start_pos: BlockPos,
+    end_pos: BlockPos,
+    blocks: &[(BlockPos, BlockState)],
+    allow_mining: bool,
+    risk: Option<super::risk::RiskLayer>,
+    equipment: super::world::PathEquipment,
+) -> super::PathFoundEvent {
+    calculate_goal_path_found_event(
+        start_pos,
+        Arc::new(BlockPosGoal(end_pos)),
+        blocks,
+        allow_mining,
+        risk,
+        equipment,
+    )
+}
+
+fn calculate_goal_path_found_event(
+    start_pos: BlockPos,
+    goal: Arc<dyn super::goals::Goal>,
+    blocks: &[(BlockPos, BlockState)],
+    allow_mining: bool,
+    risk: Option<super::risk::RiskLayer>,
+    equipment: super::world::PathEquipment,
+) -> super::PathFoundEvent {
+    use azalea_inventory::Menu;
+    use parking_lot::RwLock;
+
+    use super::{calculate_path, mining::MiningCache};
+
+    let mut partial_chunks = PartialChunkStorage::default();
+    let mut chunks = ChunkStorage::default();
+    for (block_pos, _) in blocks {
+        partial_chunks.set(
+            &ChunkPos::from(block_pos),
+            Some(Chunk::default()),
+            &mut chunks,
+        );
+    }
+    for (block_pos, block_state) in blocks {
+        chunks.set_block_state(*block_pos, *block_state);
+    }
+
+    let mut opts =
+        test_path_opts(start_pos, goal, Arc::new(RwLock::new(chunks.into()))).equipment(equipment);
+    if allow_mining {
+        opts = opts.mining(MiningCache::new(Some(Menu::Player(
+            azalea_inventory::Player::default(),
+        ))));
+    }
+    if let Some(risk) = risk {
+        opts = opts.risk(risk);
+    }
+    calculate_path(opts).unwrap()
+}
+
+#[test]
+fn test_walk_around_cobweb() {
+    let stone = azalea_registry::Block::Stone.into();
+    let mut blocks = Vec::new();
+    for x in 0..=4 {
+        for z in -1..=1 {
+            blocks.push((BlockPos::new(x, 69, z), stone));
+        }
+    }
+    let cobweb = BlockPos::new(2, 70, 0);
+    blocks.push((cobweb, azalea_registry::Block::Cobweb.into()));
+
+    let targets = calculate_path_targets(
+        BlockPos::new(0, 70, 0),
+        BlockPos::new(4, 70, 0),
+        &blocks,
+        false,
+    );
+    assert!(!targets.contains(&cobweb), "{targets:?}");
+}
+
+#[test]
+fn test_fall_into_cobweb() {
+    let stone = azalea_registry::Block::Stone.into();
+    let cobweb = azalea_registry::Block::Cobweb.into();
+    // the drop is too far without the cobweb at the bottom
+    let blocks = [
+        (BlockPos::new(0, 79, 0), stone),
+        (BlockPos::new(1, 69, 0), stone),
+        (BlockPos::new(1, 70, 0), cobweb),
+    ];
+
+    let end = BlockPos::new(1, 70, 0);
+    let targets = calculate_path_targets(BlockPos::new(0, 80, 0), end, &blocks, false);
+    assert_eq!(targets, vec![end]);
+}
+
+#[test]
+fn test_avoid_falling_onto_dripstone() {
+    let stone = azalea_registry::Block::Stone.into();
+    // the default pointed dripstone state is the tip of a stalagmite
+    let dripstone = azalea_registry::Block::PointedDripstone.into();
+
+    // we can drop onto either dripstone or stone, and then walk to the goal
+    let targets = calculate_path_targets(
+        BlockPos::new(0, 71, 0),
+        BlockPos::new(1, 68, 1),
+        &[
+            (BlockPos::new(0, 70, 0), stone),
+            (BlockPos::new(1, 67, 0), dripstone),
+            (BlockPos::new(0, 67, 1), stone),
+            (BlockPos::new(1, 67, 1), stone),
+        ],
+        false,
+    );
+
+    assert_eq!(targets.first(), Some(&BlockPos::new(0, 68, 1)));
+    assert!(!targets.contains(&BlockPos::new(1, 68, 0)));
+}
+
+#[test]
+fn test_avoid_mining_stalactite_anchor() {
+    use azalea_block::{blocks, properties};
+
+    let stone = azalea_registry::Block::Stone.into();
+    let bedrock = azalea_registry::Block::Bedrock.into();
+    let dirt = azalea_registry::Block::Dirt.into();
+    let dripstone = |vertical_direction| {
+        BlockState::from(blocks::PointedDripstone {
+            thickness: properties::Thickness::Tip,
+            vertical_direction,
+            waterlogged: false,
+        })
+    };
+
+    // a low corridor with a wall across it, and two gaps in the wall that we have
+    // to mine through. both gaps have the same blocks, but in one of them the
+    // dripstone is a stalactite hanging from the dirt.
+    let mut blocks = Vec::new();
+    for x in -1..=1 {
+        for z in 0..=4 {
+            blocks.push((BlockPos::new(x, 69, z), stone));
+            blocks.push((BlockPos::new(x, 72, z), bedrock));
+        }
+    }
+    blocks.extend([
+        (BlockPos::new(0, 70, 2), bedrock),
+        (BlockPos::new(0, 71, 2), bedrock),
+        (
+            BlockPos::new(-1, 70, 2),
+            dripstone(properties::VerticalDirection::Down),
+        ),
+        (BlockPos::new(-1, 71, 2), dirt),
+        (
+            BlockPos::new(1, 70, 2),
+            dripstone(properties::VerticalDirection::Up),
+        ),
+        (BlockPos::new(1, 71, 2), dirt),
+    ]);
+
+    let targets = calculate_path_targets(
+        BlockPos::new(0, 70, 0),
+        BlockPos::new(0, 70, 4),
+        &blocks,
+        true,
+    );
+
+    assert!(targets.contains(&BlockPos::new(1, 70, 2)));
+    assert!(!targets.contains(&BlockPos::new(-1, 70, 2)));
+}
+
+/// A corridor with a wall of dirt across it, and a column of gravel on top of
+/// the wall that falls into the gap when we mine through it.
+fn corridor_under_gravel(gravel_height: i32) -> Vec<(BlockPos, BlockState)> {
+    let stone = azalea_registry::Block::Stone.into();
+    let bedrock = azalea_registry::Block::Bedrock.into();
+    let dirt = azalea_registry::Block::Dirt.into();
+    let gravel = azalea_registry::Block::Gravel.into();
+
+    let mut blocks = Vec::new();
+    for z in 0..=4 {
+        blocks.push((BlockPos::new(0, 69, z), stone));
+        for y in 70..=71 {
+            blocks.push((BlockPos::new(-1, y, z), bedrock));
+            blocks.push((BlockPos::new(1, y, z), bedrock));
+        }
+        if z != 2 {
+            blocks.push((BlockPos::new(0, 72, z), bedrock));
+        }
+    }
+    blocks.push((BlockPos::new(0, 70, 2), dirt));
+    blocks.push((BlockPos::new(0, 71, 2), dirt));
+    for y in 72..72 + gravel_height {
+        blocks.push((BlockPos::new(0, y, 2), gravel));
+    }
+    blocks.push((BlockPos::new(0, 72 + gravel_height, 2), bedrock));
+    blocks
+}
+
+#[test]
+fn test_mine_under_gravel_column() {
+    let mut partial_chunks = PartialChunkStorage::default();
+    let mut simulation = setup_simulation_world(
+        &mut partial_chunks,
+        BlockPos::new(0, 70, 0),
+        &[],
+        &corridor_under_gravel(3),
+    );
+
+    simulation.app.world_mut().send_event(
+        GotoEvent::new(simulation.entity, BlockPosGoal(BlockPos::new(0, 70, 4))).with_options(
+            GotoOptions::default()
+                .allow_mining(true)
+                .retry_on_no_path(true)
+                .min_timeout(PathfinderTimeout::Nodes(1_000_000))
+                .max_timeout(PathfinderTimeout::Nodes(5_000_000)),
+        ),
+    );
+
+    assert_simulation_reaches(&mut simulation, 400, BlockPos::new(0, 70, 4));
+    // all of the gravel fell into the gap and was mined
+    for y in 71..75 {
+        assert!(
+            simulation
+                .instance()
+                .read()
+                .get_block_state(BlockPos::new(0, y, 2))
+                .unwrap()
+                .is_air()
+        );
+    }
+}
+
+#[test]
+fn test_dont_mine_under_tall_gravel_column() {
+    use super::costs::MAX_FALLING_COLUMN_HEIGHT;
+
+    let path = calculate_path_found_event(
+        BlockPos::new(0, 70, 0),
+        BlockPos::new(0, 70, 4),
+        &corridor_under_gravel(MAX_FALLING_COLUMN_HEIGHT + 1),
+        true,
+        None,
+        super::world::PathEquipment::default(),
+    );
+    assert!(path.is_partial);
+
+    let targets = calculate_path_targets(
+        BlockPos::new(0, 70, 0),
+        BlockPos::new(0, 70, 4),
+        &corridor_under_gravel(MAX_FALLING_COLUMN_HEIGHT),
+        true,
+    );
+    assert_eq!(targets.last(), Some(&BlockPos::new(0, 70, 4)));
+}
+
+/// Two corridors around a wall, where only the one at x=-4 is lit by torches.
+fn lit_and_dark_corridors() -> Vec<(BlockPos, BlockState)> {
+    let stone = azalea_registry::Block::Stone.into();
+    let torch = azalea_registry::Block::Torch.into();
+
+    let mut blocks = Vec::new();
+    for x in -4..=4 {
+        for z in 0..=10 {
+            blocks.push((BlockPos::new(x, 70, z), stone));
+        }
+    }
+    for x in -3..=3 {
+        for z in 2..=8 {
+            blocks.push((BlockPos::new(x, 71, z), stone));
+            blocks.push((BlockPos::new(x, 72, z), stone));
+        }
+    }
+    blocks.push((BlockPos::new(-5, 71, 2), torch));
+    blocks.push((BlockPos::new(-5, 71, 8), torch));
+    blocks
+}
+
+#[test]
+fn test_risk_layer_prefers_lit_corridor() {
+    use super::risk::{AvoidDarknessAtNight, RiskLayer};
+
+    let targets = calculate_path_targets_with_risk(
+        BlockPos::new(0, 71, 0),
+        BlockPos::new(0, 71, 10),
+        &lit_and_dark_corridors(),
+        false,
+        Some(RiskLayer::new(AvoidDarknessAtNight::default(), Vec::new())),
+    );
+
+    let corridor_targets = targets
+        .iter()
+        .filter(|pos| (3..=7).contains(&pos.z))
+        .collect::<Vec<_>>();
+    assert!(!corridor_targets.is_empty());
+    assert!(
+        corridor_targets.iter().all(|pos| pos.x < 0),
+        "expected the path to go through the lit corridor, got {targets:?}"
+    );
+}
+
+#[test]
+fn test_risk_layer_costs() {
+    use parking_lot::RwLock;
+
+    use super::{
+        rel_block_pos::RelBlockPos,
+        risk::{AvoidDarknessAtNight, RiskLayer, is_night},
+        world::CachedWorld,
+    };
+
+    let mut partial_chunks = PartialChunkStorage::default();
+    let mut chunks = ChunkStorage::default();
+    let blocks = lit_and_dark_corridors();
+    for (block_pos, _) in &blocks {
+        partial_chunks.set(
+            &ChunkPos::from(block_pos),
+            Some(Chunk::default()),
+            &mut chunks,
+        );
+    }
+    for (block_pos, block_state) in &blocks {
+        chunks.set_block_state(*block_pos, *block_state);
+    }
+
+    let origin = BlockPos::new(0, 71, 0);
+    let world_lock = Arc::new(RwLock::new(chunks.into()));
+    let settings = AvoidDarknessAtNight::default();
+
+    let day_world = CachedWorld::new(world_lock.clone(), origin);
+    assert_eq!(day_world.risk_cost(RelBlockPos::new(4, 0, 5)), 0.);
+
+    let night_world = CachedWorld::new(world_lock.clone(), origin)
+        .with_risk(Some(RiskLayer::new(settings, Vec::new())));
+    assert_eq!(night_world.risk_cost(RelBlockPos::new(-4, 0, 5)), 0.);
+    assert_eq!(
+        night_world.risk_cost(RelBlockPos::new(4, 0, 5)),
+        settings.dark_penalty
+    );
+    // standing under the wall means we're covered from the sky
+    assert_eq!(night_world.risk_cost(RelBlockPos::new(0, -2, 5)), 0.);
+
+    let hostile_world = CachedWorld::new(world_lock, origin).with_risk(Some(RiskLayer::new(
+        settings,
+        vec![BlockPos::new(-4, 71, 5)],
+    )));
+    assert!(hostile_world.risk_cost(RelBlockPos::new(-4, 0, 6)) > 0.);
+    assert_eq!(hostile_world.risk_cost(RelBlockPos::new(-4, 0, 20)), 0.);
+
+    assert!(!is_night(6_000));
+    assert!(is_night(18_000));
+    assert!(is_night(24_000 * 3 + 18_000));
+}
+
+/// Two corridors around a wall, where the one on the positive x side is the
+/// shorter way from `(2, 71, 0)` to `(2, 71, 10)`.
+fn parallel_corridors() -> Vec<BlockPos> {
+    let mut blocks = Vec::new();
+    for x in -4..=4 {
+        for z in 0..=10 {
+            blocks.push(BlockPos::new(x, 70, z));
+        }
+    }
+    for x in -3..=3 {
+        for z in 2..=8 {
+            blocks.push(BlockPos::new(x, 71, z));
+            blocks.push(BlockPos::new(x, 72, z));
+        }
+    }
+    blocks
+}
+
+/// Walk through the [`parallel_corridors`] while avoiding creepers, with a
+/// creeper standing in the shorter corridor. It's spawned after the path is
+/// found if `is_creeper_late` is true.
+///
+/// Returns where we were on every tick and how the goto ended.
+fn walk_past_creeper(is_creeper_late: bool) -> (Vec<Vec3>, Option<GotoOutcome>) {
+    use azalea_registry::EntityKind;
+
+    use super::{ExecutingPath, mobs::DEFAULT_AVOIDED_MOBS, settings::PathfinderSettings};
+
+    let creeper_position = Vec3::new(4.5, 71., 5.5);
+
+    let mut partial_chunks = PartialChunkStorage::default();
+    let mut simulation = setup_simulation_world(
+        &mut partial_chunks,
+        BlockPos::new(2, 71, 0),
+        &parallel_corridors(),
+        &[],
+    );
+    simulation
+        .app
+        .world_mut()
+        .entity_mut(simulation.entity)
+        .insert(PathfinderSettings::default().with_avoid_mobs(DEFAULT_AVOIDED_MOBS.to_vec()));
+    if !is_creeper_late {
+        simulation.spawn_entity(EntityKind::Creeper, creeper_position);
+    }
+    simulation.app.world_mut().send_event(
+        GotoEvent::new(simulation.entity, BlockPosGoal(BlockPos::new(2, 71, 10))).with_options(
+            GotoOptions::default()
+                .allow_mining(false)
+                .min_timeout(PathfinderTimeout::Nodes(1_000_000))
+                .max_timeout(PathfinderTimeout::Nodes(5_000_000)),
+        ),
+    );
+
+    let start_time = Instant::now();
+    while simulation.get_component::<ExecutingPath>().is_none()
+        && start_time.elapsed() < Duration::from_secs(2)
+    {
+        simulation.tick();
+        thread::yield_now();
+    }
+    if is_creeper_late {
+        simulation.spawn_entity(EntityKind::Creeper, creeper_position);
+    }
+
+    let mut trajectory = Vec::new();
+    while simulation.component::<Pathfinder>().outcome.is_none() && trajectory.len() < 400 {
+        simulation.tick();
+        trajectory.push(simulation.position());
+    }
+    (trajectory, simulation.component::<Pathfinder>().outcome)
+}
+
+#[test]
+fn test_takes_other_corridor_around_creeper() {
+    let (trajectory, outcome) = walk_past_creeper(false);
+    assert_eq!(outcome, Some(GotoOutcome::Reached));
+    assert!(
+        trajectory
+            .iter()
+            .filter(|position| (3. ..=8.).contains(&position.z))
+            .all(|position| position.x < 0.),
+        "expected the path to go through the corridor without the creeper, got {trajectory:?}"
+    );
+}
+
+#[test]
+fn test_reroutes_when_creeper_appears_on_path() {
+    let (trajectory, outcome) = walk_past_creeper(true);
+    assert_eq!(outcome, Some(GotoOutcome::Reached));
+    assert!(
+        trajectory
+            .iter()
+            .filter(|position| (3. ..=8.).contains(&position.z))
+            .all(|position| position.x < 0.),
+        "expected the path to be rerouted away from the creeper, got {trajectory:?}"
+    );
+}
+
+#[test]
+fn test_mob_layer_costs() {
+    use azalea_registry::EntityKind;
+    use bevy_ecs::entity::Entity;
+
+    use super::mobs::{DEFAULT_AVOIDED_MOBS, MobLayer, NearbyMob};
+
+    let creeper = |pos: BlockPos| NearbyMob {
+        entity: Entity::PLACEHOLDER,
+        kind: EntityKind::Creeper,
+        pos,
+    };
+    let layer = MobLayer::new(&DEFAULT_AVOIDED_MOBS, &[creeper(BlockPos::new(0, 70, 0))]);
+    assert_eq!(
+        layer.cost(BlockPos::new(0, 70, 0)),
+        DEFAULT_AVOIDED_MOBS[0].penalty
+    );
+    assert!(layer.cost(BlockPos::new(3, 70, 0)) > 0.);
+    assert!(layer.cost(BlockPos::new(3, 70, 0)) < layer.cost(BlockPos::new(1, 70, 0)));
+    assert_eq!(layer.cost(BlockPos::new(6, 70, 0)), 0.);
+
+    // the penalties of mobs next to each other add up
+    let layer = MobLayer::new(
+        &DEFAULT_AVOIDED_MOBS,
+        &[
+            creeper(BlockPos::new(0, 70, 0)),
+            creeper(BlockPos::new(2, 70, 0)),
+        ],
+    );
+    assert!(layer.cost(BlockPos::new(1, 70, 0)) > DEFAULT_AVOIDED_MOBS[0].penalty);
+
+    // mobs that we aren't avoiding are ignored
+    let zombie = NearbyMob {
+        kind: EntityKind::Zombie,
+        ..creeper(BlockPos::new(0, 70, 0))
+    };
+    assert!(MobLayer::new(&DEFAULT_AVOIDED_MOBS, &[zombie]).is_empty());
+}
+
+#[test]
+fn test_ice_highway_segment() {
+    use parking_lot::RwLock;
+
+    use super::{
+        call_successors_fn,
+        costs::WALK_ONE_BLOCK_COST,
+        custom_state::CustomPathfinderStateRef,
+        mining::MiningCache,
+        moves::water::SwimmingState,
+        rel_block_pos::RelBlockPos,
+        requirements::{Consumable, ConsumableCounts},
+        world::{CachedWorld, PathEquipment},
+    };
+
+    // a 100 block strip of blue ice, followed by a gap and then more ice
+    let mut partial_chunks = PartialChunkStorage::default();
+    let mut world = ChunkStorage::default();
+    for chunk_z in 0..=7 {
+        partial_chunks.set(
+            &ChunkPos { x: 0, z: chunk_z },
+            Some(Chunk::default()),
+            &mut world,
+        );
+    }
+    for z in (0..100).chain(103..120) {
+        world.set_block_state(
+            BlockPos::new(0, 69, z),
+            azalea_registry::Block::BlueIce.into(),
+        );
+    }
+    let world = Arc::new(RwLock::new(world.into()));
+
+    let edges_with_boats = |boats: u32| {
+        let mut consumables = ConsumableCounts::default();
+        consumables.set(Consumable::Boat, boats);
+        let cached_world =
+            CachedWorld::new(world.clone(), BlockPos::default()).with_equipment(PathEquipment {
+                consumables,
+                ..Default::default()
+            });
+        call_successors_fn(
+            &cached_world,
+            &MiningCache::new(None),
+            1.,
+            LOW_AIR_THRESHOLD,
+            MoveSettings::default(),
+            &CustomPathfinderStateRef::default(),
+            moves::ice_highway_move,
+            SwimmingState::default(),
+            RelBlockPos::new(0, 70, 0),
+        )
+    };
+
+    // we get out of the boat on the last block before the gap
+    let edges = edges_with_boats(1);
+    let segment = edges
+        .iter()
+        .find(|edge| edge.movement.target == RelBlockPos::new(0, 70, 99))
+        .expect("there should be an edge along the ice strip");
+    // which costs less than an eighth of walking there
+    assert!(segment.cost < WALK_ONE_BLOCK_COST * 99. / 8.);
+    assert_eq!(segment.movement.data.consumes, &[Consumable::Boat]);
+    // and we don't try to cross the gap
+    assert!(edges.iter().all(|edge| edge.movement.target.z <= 99));
+
+    // without a boat we have to walk
+    assert!(
+        edges_with_boats(0)
+            .iter()
+            .all(|edge| edge.movement.target.z < 99)
+    );
+}
+
+#[test]
+fn test_ride_boat_along_ice_highway() {
+    use azalea_entity::Riding;
+
+    use super::ExecutingPath;
+
+    // an 80 block strip of blue ice that's three blocks wide, followed by a gap and
+    // then more ice
+    let mut partial_chunks = PartialChunkStorage::default();
+    let mut extra_blocks: Vec<(BlockPos, BlockState)> = Vec::new();
+    for z in (0..=80).chain(84..=90) {
+        for x in -1..=1 {
+            extra_blocks.push((
+                BlockPos::new(x, 69, z),
+                azalea_registry::Block::BlueIce.into(),
+            ));
+        }
+    }
+
+    let start = BlockPos::new(0, 70, 0);
+    let end = BlockPos::new(0, 70, 80);
+    let mut simulation = setup_simulation_world(&mut partial_chunks, start, &[], &extra_blocks);
+    give_hotbar_item(&mut simulation, azalea_registry::Item::OakBoat, 1);
+    simulation.app.world_mut().send_event(
+        GotoEvent::new(simulation.entity, BlockPosGoal(end)).with_options(
+            GotoOptions::default()
+                .successors_fn(moves::ice_highway_move)
+                .allow_mining(false)
+                .min_timeout(PathfinderTimeout::Nodes(1_000_000))
+                .max_timeout(PathfinderTimeout::Nodes(5_000_000)),
+        ),
+    );
+
+    let start_time = Instant::now();
+    while simulation.get_component::<ExecutingPath>().is_none()
+        && start_time.elapsed() < Duration::from_secs(2)
+    {
+        simulation.tick();
+        thread::yield_now();
+    }
+    let mut ticks = 0;
+    let mut has_ridden = false;
+    let mut lowest_y = simulation.position().y;
+    while simulation.component::<Pathfinder>().outcome.is_none() && ticks < 2000 {
+        simulation.tick();
+        ticks += 1;
+        has_ridden |= simulation.get_component::<Riding>().is_some();
+        lowest_y = lowest_y.min(simulation.position().y);
+    }
+
+    assert!(has_ridden);
+    // we got out before the gap instead of falling into it
+    assert!(lowest_y > 69.9, "{lowest_y}");
+    assert_eq!(BlockPos::from(simulation.position()), end);
+    // sprinting that far takes almost 300 ticks
+    assert!(ticks < 200, "{ticks} ticks");
+}
+
+#[test]
+fn test_prefer_blue_ice_over_shorter_dirt_path() {
+    // a wall between the start and the goal, with a row of dirt on one side of
+    // it and a row of blue ice on the other. there's some extra floor around
+    // everything in case we slide a bit.
+    let mut partial_chunks = PartialChunkStorage::default();
+    let mut solid_blocks = Vec::new();
+    let mut extra_blocks: Vec<(BlockPos, BlockState)> = Vec::new();
+    for x in 0..=14 {
+        for z in 0..=3 {
+            let floor = BlockPos::new(x, 69, z);
+            if z == 0 && (2..=12).contains(&x) {
+                extra_blocks.push((floor, azalea_registry::Block::Dirt.into()));
+            } else if z == 2 && (2..=12).contains(&x) {
+                extra_blocks.push((floor, azalea_registry::Block::BlueIce.into()));
+            } else {
+                solid_blocks.push(floor);
+            }
+        }
+    }
+    for x in 3..=11 {
+        solid_blocks.push(BlockPos::new(x, 70, 1));
+        solid_blocks.push(BlockPos::new(x, 71, 1));
+    }
+
+    let start = BlockPos::new(2, 70, 1);
+    let end = BlockPos::new(12, 70, 1);
+    let mut simulation =
+        setup_simulation_world(&mut partial_chunks, start, &solid_blocks, &extra_blocks);
+    simulation.app.world_mut().send_event(
+        GotoEvent::new(simulation.entity, BlockPosGoal(end)).with_options(
+            GotoOptions::default()
+                .allow_mining(false)
+                .retry_on_no_path(true)
+                .min_timeout(PathfinderTimeout::Nodes(1_000_000))
+                .max_timeout(PathfinderTimeout::Nodes(5_000_000)),
+        ),
+    );
+
+    let trajectory = record_trajectory(&mut simulation, 150);
+    // we never went onto the dirt
+    assert!(trajectory.iter().all(|pos| pos.z > 1.), "{trajectory:?}");
+    assert_eq!(BlockPos::from(simulation.position()), end);
+}
+
+#[test]
+fn test_replan_without_missing_consumables() {
+    use parking_lot::RwLock;
+
+    use super::{
+        astar::{Edge, Movement},
+        calculate_path,
+        costs::SPRINT_ONE_BLOCK_COST,
+        moves::{ExecuteCtx, MoveData, PathfinderCtx, default_is_reached},
+        rel_block_pos::RelBlockPos,
+        requirements::{Consumable, ConsumableCounts},
+        world::PathEquipment,
+    };
+
+    // a simplified bridging move that places a block in front of us whenever
+    // there's nothing to stand on
+    fn bridge_move(ctx: &mut PathfinderCtx, node: RelBlockPos) {
+        moves::default_move(ctx, node);
+
+        if !ctx
+            .world
+            .equipment()
+            .consumables
+            .has(Consumable::ThrowawayBlock)
+        {
+            return;
+        }
+        let target = node.south(1);
+        if ctx.world.is_passable(target) && !ctx.world.is_standable(target) {
+            ctx.edges.push(Edge {
+                movement: Movement {
+                    target,
+                    data: MoveData {
+                        execute: &execute_bridge_move,
+                        is_reached: &default_is_reached,
+                        consumes: &[Consumable::ThrowawayBlock],
+                    },
+                },
+                cost: SPRINT_ONE_BLOCK_COST,
+            });
+        }
+    }
+    fn execute_bridge_move(_ctx: ExecuteCtx) {}
+
+    // a 6 block gap that we can either bridge over or walk around
+    let mut partial_chunks = PartialChunkStorage::default();
+    let mut chunks = ChunkStorage::default();
+    partial_chunks.set(
+        &ChunkPos { x: 0, z: 0 },
+        Some(Chunk::default()),
+        &mut chunks,
+    );
+    let mut solid_blocks = vec![
+        BlockPos::new(0, 69, 0),
+        BlockPos::new(0, 69, 1),
+        BlockPos::new(0, 69, 8),
+        BlockPos::new(0, 69, 9),
+    ];
+    for x in 1..=3 {
+        solid_blocks.push(BlockPos::new(x, 69, 1));
+        solid_blocks.push(BlockPos::new(x, 69, 8));
+    }
+    for z in 2..=7 {
+        solid_blocks.push(BlockPos::new(3, 69, z));
+    }
+    for block_pos in solid_blocks {
+        chunks.set_block_state(block_pos, azalea_registry::Block::Stone.into());
+    }
+    let world_lock = Arc::new(RwLock::new(chunks.into()));
+
+    let plan = |equipment: PathEquipment| {
+        calculate_path(
+            test_path_opts(
+                BlockPos::new(0, 70, 0),
+                Arc::new(BlockPosGoal(BlockPos::new(0, 70, 9))),
+                world_lock.clone(),
+            )
+            .successors_fn(bridge_move)
+            .equipment(equipment),
+        )
+        .unwrap()
+    };
+
+    let mut consumables = ConsumableCounts::default();
+    consumables.set(Consumable::ThrowawayBlock, 4);
+    let equipment = PathEquipment {
+        consumables,
+        ..Default::default()
+    };
+
+    // the planner doesn't know how many blocks we have, so it bridges anyways
+    let bridged = plan(equipment);
+    assert_eq!(bridged.requirements.get(Consumable::ThrowawayBlock), 6);
+    let shortfall = bridged.requirements.shortfall(&equipment.consumables);
+    assert_eq!(shortfall, vec![Consumable::ThrowawayBlock]);
+
+    // so we have to recalculate without placing blocks
+    let replanned = plan(equipment.without(&shortfall));
+    assert!(!replanned.is_partial);
+    assert!(replanned.requirements.is_empty());
+    assert!(
+        replanned
+            .path
+            .unwrap()
+            .iter()
+            .any(|edge| edge.movement.target.x == 3)
+    );
+}
+
+#[test]
+fn test_consumables_after_using_path() {
+    use super::requirements::{Consumable, ConsumableCounts};
+
+    let mut available = ConsumableCounts::default();
+    available.set(Consumable::ThrowawayBlock, 10);
+    available.set(Consumable::Torch, 1);
+    available.set(Consumable::WaterBucket, 1);
+    let mut requirements = ConsumableCounts::default();
+    requirements.set(Consumable::ThrowawayBlock, 4);
+    requirements.set(Consumable::Torch, 2);
+    requirements.set(Consumable::WaterBucket, 1);
+
+    let left = available.after_using(&requirements);
+    assert_eq!(left.get(Consumable::ThrowawayBlock), 6);
+    assert_eq!(left.get(Consumable::Torch), 0);
+    // we pick the water back up after placing it
+    assert_eq!(left.get(Consumable::WaterBucket), 1);
+}
+
+#[test]
+fn test_radius_goal_hysteresis_at_boundary() {
+    use super::{ComputePath, ExecutingPath, Pathfinder, goals::RadiusGoal};
+
+    const RADIUS: f32 = 3.;
+
+    let mut partial_chunks = PartialChunkStorage::default();
+    let mut solid_blocks = Vec::new();
+    for x in -5..=5 {
+        for z in -5..=5 {
+            solid_blocks.push(BlockPos::new(x, 70, z));
+        }
+    }
+    let start_pos = BlockPos::new(0, 71, 0);
+    let mut simulation = setup_simulation_world(&mut partial_chunks, start_pos, &solid_blocks, &[]);
+
+    // the target moves back and forth by half a block around the edge of the radius
+    for tick in 0..40 {
+        let offset = if tick % 2 == 0 { -0.5 } else { 0.5 };
+        let target = start_pos.center().with_x(0.5 + RADIUS as f64 + offset);
+        simulation.app.world_mut().send_event(
+            GotoEvent::new(simulation.entity, RadiusGoal::new(target, RADIUS))
+                .with_options(GotoOptions::default().allow_mining(false)),
+        );
+        simulation.tick();
+
+        let world = simulation.app.world();
+        assert!(world.get::<ComputePath>(simulation.entity).is_none());
+        assert!(simulation.get_component::<ExecutingPath>().is_none());
+        assert!(simulation.component::<Pathfinder>().is_at_goal);
+        assert_eq!(BlockPos::from(simulation.position()), start_pos);
+    }
+}
+
+#[test]
+fn test_stop_pathfinding_aborts_mining() {
+    use azalea_client::{
+        local_player::InstanceHolder,
+        mining::{MineProgress, Mining},
+        packet::game::SendPacketEvent,
+    };
+    use azalea_protocol::packets::game::{ServerboundGamePacket, s_player_action};
+    use bevy_ecs::prelude::*;
+
+    use super::{ExecutingPath, StopPathfindingEvent};
+
+    #[derive(Resource, Default)]
+    struct SentPlayerActions(Vec<s_player_action::Action>);
+
+    let mut partial_chunks = PartialChunkStorage::default();
+    // a tunnel that's blocked by stone, which takes a long time to mine by hand
+    let mut solid_blocks = Vec::new();
+    for z in 0..=3 {
+        solid_blocks.push(BlockPos::new(0, 70, z));
+        solid_blocks.push(BlockPos::new(-1, 71, z));
+        solid_blocks.push(BlockPos::new(-1, 72, z));
+        solid_blocks.push(BlockPos::new(1, 71, z));
+        solid_blocks.push(BlockPos::new(1, 72, z));
+        solid_blocks.push(BlockPos::new(0, 73, z));
+    }
+    solid_blocks.push(BlockPos::new(0, 71, 1));
+    solid_blocks.push(BlockPos::new(0, 72, 1));
+    let mut simulation = setup_simulation_world(
+        &mut partial_chunks,
+        BlockPos::new(0, 71, 0),
+        &solid_blocks,
+        &[],
+    );
+    simulation
+        .app
+        .init_resource::<SentPlayerActions>()
+        .add_observer(
+            |trigger: Trigger<SendPacketEvent>, mut sent: ResMut<SentPlayerActions>| {
+                if let ServerboundGamePacket::PlayerAction(p) = &trigger.event().packet {
+                    sent.0.push(p.action);
+                }
+            },
+        );
+
+    simulation.app.world_mut().send_event(
+        GotoEvent::new(simulation.entity, BlockPosGoal(BlockPos::new(0, 71, 3))).with_options(
+            GotoOptions::default()
+                .allow_mining(true)
+                .retry_on_no_path(true)
+                .min_timeout(PathfinderTimeout::Nodes(1_000_000))
+                .max_timeout(PathfinderTimeout::Nodes(5_000_000)),
+        ),
+    );
+    wait_until_bot_starts_moving(&mut simulation);
+    for _ in 0..20 {
+        simulation.tick();
+    }
+    assert!(simulation.is_mining());
+    assert!(*simulation.component::<MineProgress>() > 0.);
+
+    simulation.app.world_mut().send_event(StopPathfindingEvent {
+        entity: simulation.entity,
+        force: false,
+    });
+    simulation
+        .app
+        .world_mut()
+        .resource_mut::<SentPlayerActions>()
+        .0
+        .clear();
+    for _ in 0..40 {
+        simulation.tick();
+    }
+
+    let sent = &simulation.app.world().resource::<SentPlayerActions>().0;
+    assert!(
+        matches!(sent[..], [s_player_action::Action::AbortDestroyBlock]),
+        "expected only an abort to be sent, got {sent:?}"
+    );
+    assert!(
+        simulation
+            .app
+            .world()
+            .get::<Mining>(simulation.entity)
+            .is_none()
+    );
+    assert_eq!(*simulation.component::<MineProgress>(), 0.);
+    assert!(simulation.get_component::<ExecutingPath>().is_none());
+
+    let pathfinder = simulation.component::<Pathfinder>();
+    assert!(pathfinder.goal.is_none());
+    assert!(!pathfinder.is_calculating);
+    assert_eq!(pathfinder.outcome, Some(GotoOutcome::Cancelled));
+
+    let instance = simulation
+        .app
+        .world()
+        .get::<InstanceHolder>(simulation.entity)
+        .unwrap()
+        .instance
+        .clone();
+    for pos in [BlockPos::new(0, 71, 1), BlockPos::new(0, 72, 1)] {
+        assert_eq!(
+            instance.read().get_block_state(pos),
+            Some(azalea_registry::Block::Stone.into())
+        );
+    }
+}
+
+#[test]
+fn test_per_client_settings_only_affect_that_client() {
+    use super::{settings::PathfinderSettings, simulation::SimulationSet};
+
+    // two identical tunnels that are both blocked by dirt
+    let mut solid_blocks = Vec::new();
+    let mut dirt_blocks = Vec::new();
+    for tunnel_x in [0, 4] {
+        for z in 0..=3 {
+            solid_blocks.push(BlockPos::new(tunnel_x, 70, z));
+            solid_blocks.push(BlockPos::new(tunnel_x - 1, 71, z));
+            solid_blocks.push(BlockPos::new(tunnel_x - 1, 72, z));
+            solid_blocks.push(BlockPos::new(tunnel_x + 1, 71, z));
+            solid_blocks.push(BlockPos::new(tunnel_x + 1, 72, z));
+            solid_blocks.push(BlockPos::new(tunnel_x, 73, z));
+        }
+        dirt_blocks.push(BlockPos::new(tunnel_x, 71, 1));
+        dirt_blocks.push(BlockPos::new(tunnel_x, 72, 1));
+    }
+
+    let mut partial_chunks = PartialChunkStorage::default();
+    let mut chunks = ChunkStorage::default();
+    partial_chunks.set(&ChunkPos::new(0, 0), Some(Chunk::default()), &mut chunks);
+    for block_pos in &solid_blocks {
+        chunks.set_block_state(*block_pos, azalea_registry::Block::Stone.into());
+    }
+    for block_pos in &dirt_blocks {
+        chunks.set_block_state(*block_pos, azalea_registry::Block::Dirt.into());
+    }
+
+    let mut simulation = SimulationSet::new(chunks);
+    let no_mining_bot = simulation.spawn(SimulatedPlayerBundle::new(Vec3::new(0.5, 71., 0.5)));
+    let mining_bot = simulation.spawn(SimulatedPlayerBundle::new(Vec3::new(4.5, 71., 0.5)));
+    simulation
+        .app
+        .world_mut()
+        .entity_mut(no_mining_bot)
+        .insert(PathfinderSettings::default().with_allow_mining(false));
+
+    for (entity, goal) in [
+        (no_mining_bot, BlockPos::new(0, 71, 3)),
+        (mining_bot, BlockPos::new(4, 71, 3)),
+    ] {
+        simulation.app.world_mut().send_event(
+            GotoEvent::new(entity, BlockPosGoal(goal)).with_options(
+                GotoOptions::default()
+                    .min_timeout(PathfinderTimeout::Nodes(1_000_000))
+                    .max_timeout(PathfinderTimeout::Nodes(5_000_000)),
+            ),
+        );
+    }
+
+    let start_time = Instant::now();
+    for _ in 0..400 {
+        simulation.tick();
+        if BlockPos::from(simulation.position(mining_bot)) == BlockPos::new(4, 71, 3) {
+            break;
+        }
+        // give the path calculation some time to finish
+        if start_time.elapsed() < Duration::from_millis(500) {
+            thread::yield_now();
+        }
+    }
+
+    assert_eq!(
+        BlockPos::from(simulation.position(mining_bot)),
+        BlockPos::new(4, 71, 3)
+    );
+    assert_eq!(
+        BlockPos::from(simulation.position(no_mining_bot)),
+        BlockPos::new(0, 71, 0)
+    );
+}
+
+/// A 10 block high water elevator at x=0 z=0 with soul sand at the bottom. It
+/// has an entrance at y=71 on the -z side, and exits at y=75 on the +z side and
+/// at y=79 on the +x side, which both lead to a short platform.
+fn water_elevator_blocks() -> Vec<(BlockPos, BlockState)> {
+    use azalea_block::blocks;
+
+    let stone = azalea_registry::Block::Stone.into();
+    let soul_sand = azalea_registry::Block::SoulSand.into();
+    let sign = azalea_registry::Block::OakSign.into();
+    let bubble_column = blocks::BubbleColumn { drag: false }.into();
+
+    let gaps = [
+        BlockPos::new(0, 71, -1),
+        BlockPos::new(0, 72, -1),
+        BlockPos::new(0, 75, 1),
+        BlockPos::new(0, 76, 1),
+        BlockPos::new(1, 79, 0),
+        BlockPos::new(1, 80, 0),
+    ];
+
+    let mut blocks = vec![
+        (BlockPos::new(0, 70, 0), soul_sand),
+        (BlockPos::new(0, 81, 0), stone),
+        // the floor leading to the entrance
+        (BlockPos::new(0, 70, -1), stone),
+        (BlockPos::new(0, 70, -2), stone),
+    ];
+    for y in 71..=80 {
+        blocks.push((BlockPos::new(0, y, 0), bubble_column));
+        for side in [
+            BlockPos::new(1, y, 0),
+            BlockPos::new(-1, y, 0),
+            BlockPos::new(0, y, 1),
+            BlockPos::new(0, y, -1),
+        ] {
+            blocks.push((side, if gaps.contains(&side) { sign } else { stone }));
+        }
+    }
+    for i in 2..=3 {
+        blocks.push((BlockPos::new(0, 74, i), stone));
+        blocks.push((BlockPos::new(i, 78, 0), stone));
+    }
+    blocks
+}
+
+#[test]
+fn test_water_elevator_single_edge() {
+    let targets = calculate_path_targets(
+        BlockPos::new(0, 71, -2),
+        BlockPos::new(0, 75, 3),
+        &water_elevator_blocks(),
+        false,
+    );
+
+    // we should go straight from the entrance to the exit, without any nodes in
+    // the shaft
+    assert!(
+        targets
+            .windows(2)
+            .any(|w| w == [BlockPos::new(0, 71, -1), BlockPos::new(0, 75, 1)]),
+        "expected a single elevator edge, got {targets:?}"
+    );
+    assert!(!targets.iter().any(|pos| pos.x == 0 && pos.z == 0));
+    assert_eq!(targets.last(), Some(&BlockPos::new(0, 75, 3)));
+}
+
+#[test]
+fn test_water_elevator_picks_exit_floor() {
+    let targets = calculate_path_targets(
+        BlockPos::new(0, 71, -2),
+        BlockPos::new(3, 79, 0),
+        &water_elevator_blocks(),
+        false,
+    );
+
+    assert!(
+        targets
+            .windows(2)
+            .any(|w| w == [BlockPos::new(0, 71, -1), BlockPos::new(1, 79, 0)]),
+        "expected to ride the elevator to the top exit, got {targets:?}"
+    );
+    assert!(!targets.contains(&BlockPos::new(0, 75, 1)));
+    assert_eq!(targets.last(), Some(&BlockPos::new(3, 79, 0)));
+}
+
+#[test]
+fn test_ride_bubble_column_to_surface() {
+    use azalea_block::blocks;
+
+    let bubble_column: BlockState = blocks::BubbleColumn { drag: false }.into();
+    let mut solid_blocks = Vec::new();
+    let mut extra_blocks = vec![(
+        BlockPos::new(0, 69, 0),
+        azalea_registry::Block::SoulSand.into(),
+    )];
+    for y in 70..=79 {
+        extra_blocks.push((BlockPos::new(0, y, 0), bubble_column));
+        for (x, z) in [(1, 0), (-1, 0), (0, 1), (0, -1)] {
+            solid_blocks.push(BlockPos::new(x, y, z));
+        }
+    }
+
+    let mut partial_chunks = PartialChunkStorage::default();
+    let mut simulation = setup_simulation_world(
+        &mut partial_chunks,
+        BlockPos::new(0, 70, 0),
+        &solid_blocks,
+        &extra_blocks,
+    );
+    let surface = BlockPos::new(0, 80, 0);
+    simulation.app.world_mut().send_event(
+        GotoEvent::new(simulation.entity, BlockPosGoal(surface)).with_options(
+            GotoOptions::default()
+                .allow_mining(false)
+                .min_timeout(PathfinderTimeout::Nodes(1_000_000))
+                .max_timeout(PathfinderTimeout::Nodes(5_000_000)),
+        ),
+    );
+
+    // swimming up 10 blocks would take a lot longer than this
+    let reached_surface = (0..40).any(|_| {
+        simulation.tick();
+        BlockPos::from(simulation.position()).y >= surface.y
+    });
+    assert!(reached_surface, "ended up at {:?}", simulation.position());
+}
+
+fn path_with_effects(
+    start_pos: BlockPos,
+    end_pos: BlockPos,
+    blocks: &[(BlockPos, BlockState)],
+    effects: super::world::PathEffects,
+) -> Option<Vec<BlockPos>> {
+    let equipment = super::world::PathEquipment::default().with_effects(effects);
+    path_with_equipment(start_pos, end_pos, blocks, equipment)
+}
+
+fn path_with_equipment(
+    start_pos: BlockPos,
+    end_pos: BlockPos,
+    blocks: &[(BlockPos, BlockState)],
+    equipment: super::world::PathEquipment,
+) -> Option<Vec<BlockPos>> {
+    let path = calculate_path_found_event(start_pos, end_pos, blocks, false, None, equipment);
+    if path.is_partial {
+        return None;
+    }
+    Some(
+        path.path
+            .unwrap()
+            .iter()
+            .map(|edge| edge.movement.target)
+            .collect(),
+    )
+}
+
+#[test]
+fn test_jump_boost_climbs_two_block_wall() {
+    use super::world::PathEffects;
+
+    let stone: BlockState = azalea_registry::Block::Stone.into();
+    let mut blocks = Vec::new();
+    for x in -2..=2 {
+        // the lower floor
+        for z in 0..=1 {
+            blocks.push((BlockPos::new(x, 70, z), stone));
+        }
+        // and a platform that's 2 blocks higher
+        for z in 2..=4 {
+            for y in 70..=72 {
+                blocks.push((BlockPos::new(x, y, z), stone));
+            }
+        }
+    }
+    // a step on the side that lets us get up without jump boost
+    let step = BlockPos::new(2, 71, 1);
+    blocks.push((step, stone));
+
+    let start = BlockPos::new(0, 71, 0);
+    let end = BlockPos::new(0, 73, 3);
+
+    let jump_boost_2 = PathEffects {
+        jump_boost: Some(1),
+        ..Default::default()
+    };
+    let targets = path_with_effects(start, end, &blocks, jump_boost_2).unwrap();
+    assert!(!targets.contains(&step.up(1)));
+    let mut previous = start;
+    let mut jumped_two_blocks = false;
+    for &target in &targets {
+        jumped_two_blocks |= target.y - previous.y == 2;
+        previous = target;
+    }
+    assert!(jumped_two_blocks, "{targets:?}");
+
+    let targets = path_with_effects(start, end, &blocks, PathEffects::default()).unwrap();
+    assert!(targets.contains(&step.up(1)), "{targets:?}");
+}
+
+#[test]
+fn test_slow_falling_allows_long_drop() {
+    use super::world::PathEffects;
+
+    let stone: BlockState = azalea_registry::Block::Stone.into();
+    let blocks = [
+        (BlockPos::new(0, 100, 0), stone),
+        (BlockPos::new(0, 70, 1), stone),
+    ];
+    let start = BlockPos::new(0, 101, 0);
+    let end = BlockPos::new(0, 71, 1);
+
+    let slow_falling = PathEffects {
+        slow_falling: true,
+        ..Default::default()
+    };
+    let targets = path_with_effects(start, end, &blocks, slow_falling).unwrap();
+    assert_eq!(targets, vec![end]);
+
+    assert_eq!(
+        path_with_effects(start, end, &blocks, PathEffects::default()),
+        None
+    );
+}
+
+#[test]
+fn test_fall_damage_depends_on_health_and_feather_falling() {
+    use super::world::PathEquipment;
+
+    let stone: BlockState = azalea_registry::Block::Stone.into();
+    let blocks = |landing_block: BlockState| {
+        [
+            (BlockPos::new(0, 80, 0), stone),
+            (BlockPos::new(0, 70, 1), landing_block),
+        ]
+    };
+    let start = BlockPos::new(0, 81, 0);
+    let end = BlockPos::new(0, 71, 1);
+
+    // a 10 block drop does 7 damage, which feather falling IV brings down to
+    // less than 4
+    let full_health_with_boots = PathEquipment {
+        feather_falling: 4,
+        ..Default::default()
+    }
+    .with_fall_damage_budget(20., 0.5);
+    assert_eq!(
+        path_with_equipment(start, end, &blocks(stone), full_health_with_boots),
+        Some(vec![end])
+    );
+
+    let three_hearts = PathEquipment::default().with_fall_damage_budget(6., 0.5);
+    assert_eq!(
+        path_with_equipment(start, end, &blocks(stone), three_hearts),
+        None
+    );
+    // but landing on a hay bale only does 2 damage
+    let hay: BlockState = azalea_registry::Block::HayBlock.into();
+    assert_eq!(
+        path_with_equipment(start, end, &blocks(hay), three_hearts),
+        Some(vec![end])
+    );
+}
+
+#[test]
+fn test_fall_off_cliff_into_water() {
+    let stone: BlockState = azalea_registry::Block::Stone.into();
+    let water: BlockState = azalea_registry::Block::Water.into();
+
+    let mut blocks = Vec::new();
+    // the top of a 20 block cliff
+    for x in 0..=2 {
+        blocks.push((BlockPos::new(x, 89, 0), stone));
+    }
+    // a pool that's 2 blocks deep at the bottom of it, with the goal past it
+    for x in 3..=5 {
+        blocks.push((BlockPos::new(x, 67, 0), stone));
+        blocks.push((BlockPos::new(x, 68, 0), water));
+        blocks.push((BlockPos::new(x, 69, 0), water));
+    }
+    for x in 6..=10 {
+        blocks.push((BlockPos::new(x, 69, 0), stone));
+    }
+    // and a staircase that goes the long way around
+    for z in 1..=20 {
+        blocks.push((BlockPos::new(2, 89 - z, z), stone));
+    }
+    for x in 3..=10 {
+        blocks.push((BlockPos::new(x, 69, 20), stone));
+    }
+    for z in 1..20 {
+        blocks.push((BlockPos::new(10, 69, z), stone));
+    }
+
+    let start = BlockPos::new(0, 90, 0);
+    let targets = calculate_path_targets(start, BlockPos::new(10, 70, 0), &blocks, false);
+
+    // we jump into the pool instead of taking the stairs
+    assert!(targets.iter().all(|pos| pos.z == 0), "{targets:?}");
+    let jumped_into_pool = std::iter::once(start)
+        .chain(targets.iter().copied())
+        .zip(targets.iter())
+        .any(|(from, to)| from.y - to.y >= 15);
+    assert!(jumped_into_pool, "{targets:?}");
+}
+
+/// Tick the simulation until the goto ends, and return how it ended.
+fn wait_for_goto_outcome(simulation: &mut Simulation, timeout: Duration) -> Option<GotoOutcome> {
+    let start_time = Instant::now();
+    while start_time.elapsed() < timeout {
+        simulation.tick();
+        if let Some(outcome) = simulation.component::<Pathfinder>().outcome {
+            return Some(outcome);
+        }
+        thread::sleep(Duration::from_millis(1));
+    }
+    None
+}
+
+#[test]
+fn test_gives_up_on_goal_in_unloaded_chunks() {
+    use super::{UnreachableReason, settings::PathfinderSettings};
+
+    let mut partial_chunks = PartialChunkStorage::default();
+    // the goal is in a chunk that never loads
+    let solid_blocks = (0..16).map(|x| BlockPos::new(x, 70, 0)).collect::<Vec<_>>();
+    let mut simulation = setup_blockposgoal_simulation(
+        &mut partial_chunks,
+        BlockPos::new(0, 71, 0),
+        BlockPos::new(100, 71, 0),
+        &solid_blocks,
+    );
+    simulation
+        .app
+        .world_mut()
+        .entity_mut(simulation.entity)
+        .insert(PathfinderSettings::default().with_max_stalled_partial_paths(3));
+
+    let outcome = wait_for_goto_outcome(&mut simulation, Duration::from_secs(10));
+    let closest = BlockPos::new(15, 71, 0);
+    assert_eq!(
+        outcome,
+        Some(GotoOutcome::Unreachable {
+            closest,
+            reason: UnreachableReason::NoProgress
+        })
+    );
+    let pathfinder = simulation.component::<Pathfinder>();
+    assert!(pathfinder.goal.is_none());
+    assert_eq!(pathfinder.stalled_partial_paths, 3);
+
+    for _ in 0..100 {
+        simulation.tick();
+    }
+    assert_eq!(BlockPos::from(simulation.position()), closest);
+}
+
+#[test]
+fn test_waits_for_unloaded_chunk_before_continuing() {
+    use super::WaitingForChunk;
+
+    let mut partial_chunks = PartialChunkStorage::default();
+    // the goal is in a chunk that only loads once we're at the edge of the ones
+    // before it
+    let solid_blocks = (0..32).map(|x| BlockPos::new(x, 70, 0)).collect::<Vec<_>>();
+    let goal = BlockPos::new(40, 71, 0);
+    let mut simulation = setup_blockposgoal_simulation(
+        &mut partial_chunks,
+        BlockPos::new(0, 71, 0),
+        goal,
+        &solid_blocks,
+    );
+
+    let edge = BlockPos::new(31, 71, 0);
+    let start_time = Instant::now();
+    while BlockPos::from(simulation.position()) != edge
+        || simulation.get_component::<WaitingForChunk>().is_none()
+    {
+        assert!(
+            start_time.elapsed() < Duration::from_secs(10),
+            "never got to the edge of the loaded chunks"
+        );
+        simulation.tick();
+        thread::sleep(Duration::from_millis(1));
+    }
+    let waiting = simulation.component::<WaitingForChunk>();
+    assert_eq!(waiting.chunk, ChunkPos::new(2, 0));
+    assert_eq!(simulation.component::<Pathfinder>().outcome, None);
+
+    {
+        let instance = simulation.instance();
+        let mut instance = instance.write();
+        partial_chunks.set(
+            &ChunkPos::new(2, 0),
+            Some(Chunk::default()),
+            &mut instance.chunks,
+        );
+        for x in 32..=40 {
+            instance.chunks.set_block_state(
+                BlockPos::new(x, 70, 0),
+                azalea_registry::Block::Stone.into(),
+            );
+        }
+    }
+
+    let outcome = wait_for_goto_outcome(&mut simulation, Duration::from_secs(10));
+    assert_eq!(outcome, Some(GotoOutcome::Reached));
+    assert_eq!(BlockPos::from(simulation.position()), goal);
+    assert!(simulation.get_component::<WaitingForChunk>().is_none());
+}
+
+#[test]
+fn test_gives_up_on_goal_outside_world_border() {
+    use azalea_client::local_player::InstanceHolder;
+    use azalea_world::WorldBorder;
+
+    use super::UnreachableReason;
+
+    let mut partial_chunks = PartialChunkStorage::default();
+    let solid_blocks = (0..16).map(|x| BlockPos::new(x, 70, 0)).collect::<Vec<_>>();
+    let mut simulation = setup_blockposgoal_simulation(
+        &mut partial_chunks,
+        BlockPos::new(0, 71, 0),
+        BlockPos::new(14, 71, 0),
+        &solid_blocks,
+    );
+    // the border is at x=10, so the goal is loaded but outside of it
+    simulation
+        .app
+        .world()
+        .get::<InstanceHolder>(simulation.entity)
+        .unwrap()
+        .instance
+        .write()
+        .world_border = Some(WorldBorder {
+        center_x: 0.,
+        center_z: 0.,
+        size: 20.,
+    });
+
+    // this shouldn't wait for any stalled partial paths
+    let outcome = wait_for_goto_outcome(&mut simulation, Duration::from_secs(2));
+    let closest = BlockPos::new(9, 71, 0);
+    assert_eq!(
+        outcome,
+        Some(GotoOutcome::Unreachable {
+            closest,
+            reason: UnreachableReason::WorldBorder
+        })
+    );
+
+    for _ in 0..100 {
+        simulation.tick();
+    }
+    assert_eq!(BlockPos::from(simulation.position()), closest);
+}
+
+#[test]
+fn test_goes_through_portal_to_goal_in_other_dimension() {
+    use azalea_core::resource_location::ResourceLocation;
+
+    use super::portals::{DimensionTravel, WaitingInPortal};
+
+    let portal: BlockState = azalea_registry::Block::NetherPortal.into();
+    let mut partial_chunks = PartialChunkStorage::default();
+    let solid_blocks = (0..11).map(|x| BlockPos::new(x, 70, 0)).collect::<Vec<_>>();
+    let mut simulation = setup_simulation_world(
+        &mut partial_chunks,
+        BlockPos::new(0, 71, 0),
+        &solid_blocks,
+        &[
+            (BlockPos::new(8, 71, 0), portal),
+            (BlockPos::new(8, 72, 0), portal),
+        ],
+    );
+    let nether = ResourceLocation::new("minecraft:the_nether");
+    let goal = BlockPos::new(3, 71, 0);
+    simulation.app.world_mut().send_event(
+        GotoEvent::new(simulation.entity, BlockPosGoal(goal)).with_options(
+            GotoOptions::default()
+                .allow_mining(false)
+                .dimension(nether.clone()),
+        ),
+    );
+
+    // the server would send us to the nether after we've been in the portal for
+    // 80 ticks
+    let start_time = Instant::now();
+    while simulation
+        .get_component::<WaitingInPortal>()
+        .is_none_or(|waiting| waiting.ticks < 80)
+    {
+        assert!(
+            start_time.elapsed() < Duration::from_secs(10),
+            "never started waiting in the portal"
+        );
+        simulation.tick();
+        thread::sleep(Duration::from_millis(1));
+    }
+    assert_eq!(
+        BlockPos::from(simulation.position()),
+        BlockPos::new(8, 71, 0)
+    );
+    assert_eq!(simulation.component::<DimensionTravel>().dimension, nether);
+    assert_eq!(simulation.component::<Pathfinder>().outcome, None);
+
+    let nether_chunks = setup_chunks(
+        &mut PartialChunkStorage::default(),
+        &(0..=5).map(|x| BlockPos::new(x, 70, 0)).collect::<Vec<_>>(),
+        &[
+            (BlockPos::new(1, 71, 0), portal),
+            (BlockPos::new(1, 72, 0), portal),
+        ],
+    );
+    simulation.change_instance(
+        "minecraft:the_nether",
+        nether_chunks,
+        Vec3::new(1.5, 71., 0.5),
+    );
+
+    let outcome = wait_for_goto_outcome(&mut simulation, Duration::from_secs(10));
+    assert_eq!(outcome, Some(GotoOutcome::Reached));
+    assert_eq!(BlockPos::from(simulation.position()), goal);
+    assert!(simulation.get_component::<DimensionTravel>().is_none());
+}
+
+#[test]
+fn test_gives_up_on_goal_in_other_dimension_without_portal() {
+    use azalea_core::resource_location::ResourceLocation;
+
+    use super::UnreachableReason;
+
+    let mut partial_chunks = PartialChunkStorage::default();
+    let solid_blocks = (0..11).map(|x| BlockPos::new(x, 70, 0)).collect::<Vec<_>>();
+    let mut simulation = setup_simulation_world(
+        &mut partial_chunks,
+        BlockPos::new(0, 71, 0),
+        &solid_blocks,
+        &[],
+    );
+    simulation.app.world_mut().send_event(
+        GotoEvent::new(simulation.entity, BlockPosGoal(BlockPos::new(3, 71, 0))).with_options(
+            GotoOptions::default().dimension(ResourceLocation::new("minecraft:the_nether")),
+        ),
+    );
+
+    let outcome = wait_for_goto_outcome(&mut simulation, Duration::from_secs(2));
+    assert_eq!(
+        outcome,
+        Some(GotoOutcome::Unreachable {
+            closest: BlockPos::new(0, 71, 0),
+            reason: UnreachableReason::NoPortal
+        })
+    );
+}
+
+#[test]
+fn test_picks_portal_that_comes_out_closest_to_goal() {
+    use azalea_core::resource_location::ResourceLocation;
+
+    use super::portals::KnownPortals;
+
+    let overworld = ResourceLocation::new("minecraft:overworld");
+    let nether = ResourceLocation::new("minecraft:the_nether");
+    let mut portals = KnownPortals::default();
+    // the closer portal comes out far away from the goal in the nether
+    portals.insert(&overworld, BlockPos::new(10, 64, 0));
+    portals.insert(&overworld, BlockPos::new(-80, 64, 800));
+    portals.insert(&nether, BlockPos::new(-10, 64, 100));
+
+    let goal = BlockPosGoal(BlockPos::new(-10, 64, 110));
+    assert_eq!(
+        portals.best_portal(&overworld, BlockPos::new(0, 64, 0), &nether, &goal),
+        Some(BlockPos::new(-80, 64, 800))
+    );
+    assert_eq!(
+        portals.exit_position(&overworld, BlockPos::new(10, 64, 0), &nether),
+        BlockPos::new(1, 64, 0)
+    );
+    // portals don't go between the overworld and the end
+    assert_eq!(
+        portals.best_portal(
+            &overworld,
+            BlockPos::new(0, 64, 0),
+            &ResourceLocation::new("minecraft:the_end"),
+            &goal
+        ),
+        None
+    );
+}
+
+#[test]
+fn test_flies_to_faraway_goal_with_elytra() {
+    use azalea_entity::metadata::FallFlying;
+    use azalea_inventory::Menu;
+
+    use super::{elytra::ElytraFlight, settings::PathfinderSettings};
+
+    let mut partial_chunks = PartialChunkStorage::default();
+    // the floor keeps going past the goal so we can't fall off of it if we
+    // overshoot the landing
+    let solid_blocks = (-16..256)
+        .flat_map(|x| (-4..5).map(move |z| BlockPos::new(x, 70, z)))
+        .collect::<Vec<_>>();
+    let goal = BlockPos::new(140, 71, 0);
+    let mut simulation = setup_blockposgoal_simulation(
+        &mut partial_chunks,
+        BlockPos::new(0, 71, 0),
+        goal,
+        &solid_blocks,
+    );
+    give_hotbar_item(&mut simulation, azalea_registry::Item::FireworkRocket, 64);
+    let world = simulation.app.world_mut();
+    let mut inventory = world.get_mut::<Inventory>(simulation.entity).unwrap();
+    let Menu::Player(player) = &mut inventory.inventory_menu else {
+        unreachable!("the inventory menu is always a player menu");
+    };
+    player.armor[1] = ItemStack::new(azalea_registry::Item::Elytra, 1);
+    world
+        .entity_mut(simulation.entity)
+        .insert(PathfinderSettings::default().with_allow_elytra(true));
+
+    let mut has_glided = false;
+    let mut furthest_x_while_gliding = 0.;
+    let start_time = Instant::now();
+    for _ in 0..1000 {
+        simulation.tick();
+        if simulation.get_component::<ElytraFlight>().is_some()
+            && *simulation.component::<FallFlying>()
+        {
+            has_glided = true;
+            furthest_x_while_gliding = simulation.position().x.max(furthest_x_while_gliding);
+        }
+        if simulation.component::<Pathfinder>().outcome.is_some() {
+            break;
+        }
+        // give the path calculations some time to finish
+        if start_time.elapsed() < Duration::from_millis(500) {
+            thread::yield_now();
+        }
+    }
+
+    assert!(has_glided);
+    // most of the way there was flown instead of walked
+    assert!(furthest_x_while_gliding > 100.);
+    assert_eq!(
+        simulation.component::<Pathfinder>().outcome,
+        Some(GotoOutcome::Reached)
+    );
+    assert_eq!(BlockPos::from(simulation.position()), goal);
+}
+
+#[test]
+fn test_crosses_lake_in_boat() {
+    use azalea_entity::Riding;
+
+    use super::{ExecutingPath, settings::PathfinderSettings};
+
+    // a lake that's 40 blocks across and 4 blocks deep, with a shore on each side
+    let mut solid_blocks = Vec::new();
+    let mut water_blocks = Vec::new();
+    for x in -4..50 {
+        for z in -3..4 {
+            solid_blocks.push(BlockPos::new(x, 66, z));
+            for y in 67..71 {
+                let pos = BlockPos::new(x, y, z);
+                if (1..41).contains(&x) {
+                    water_blocks.push((pos, azalea_registry::Block::Water.into()));
+                } else {
+                    solid_blocks.push(pos);
+                }
+            }
+        }
+    }
+    let start = BlockPos::new(0, 71, 0);
+    let goal = BlockPos::new(45, 71, 0);
+
+    // returns how many ticks it took to finish the path after it was found, where
+    // we ended up, and whether we rode a boat
+    let cross = |with_boat: bool| {
+        let mut partial_chunks = PartialChunkStorage::default();
+        let mut simulation =
+            setup_simulation_world(&mut partial_chunks, start, &solid_blocks, &water_blocks);
+        if with_boat {
+            give_hotbar_item(&mut simulation, azalea_registry::Item::OakBoat, 1);
+            simulation
+                .app
+                .world_mut()
+                .entity_mut(simulation.entity)
+                .insert(PathfinderSettings::default().with_allow_boats(true));
+        }
+        simulation.app.world_mut().send_event(
+            GotoEvent::new(simulation.entity, BlockPosGoal(goal)).with_options(
+                GotoOptions::default()
+                    .allow_mining(false)
+                    .min_timeout(PathfinderTimeout::Nodes(1_000_000))
+                    .max_timeout(PathfinderTimeout::Nodes(5_000_000)),
+            ),
+        );
+
+        let start_time = Instant::now();
+        while simulation.get_component::<ExecutingPath>().is_none()
+            && start_time.elapsed() < Duration::from_secs(2)
+        {
+            simulation.tick();
+            thread::yield_now();
+        }
+        let mut ticks = 0;
+        let mut has_ridden = false;
+        while simulation.component::<Pathfinder>().outcome.is_none() && ticks < 2000 {
+            simulation.tick();
+            ticks += 1;
+            has_ridden |= simulation.get_component::<Riding>().is_some();
+        }
+        (ticks, BlockPos::from(simulation.position()), has_ridden)
+    };
+
+    let (boat_ticks, boat_end, has_ridden) = cross(true);
+    assert!(has_ridden);
+    assert_eq!(boat_end, goal);
+    let (swimming_ticks, _, _) = cross(false);
+    assert!(
+        boat_ticks * 2 < swimming_ticks,
+        "{boat_ticks} ticks in a boat, {swimming_ticks} ticks swimming"
+    );
+}
+
+#[test]
+fn test_dismounts_before_walking() {
+    use azalea_client::packet::game::SendPacketEvent;
+    use azalea_entity::Riding;
+    use azalea_protocol::packets::game::{ServerboundGamePacket, ServerboundPlayerInput};
+    use bevy_ecs::prelude::*;
+
+    #[derive(Resource, Default)]
+    struct SentInputs(Vec<ServerboundPlayerInput>);
+
+    let mut partial_chunks = PartialChunkStorage::default();
+    let mut simulation = setup_blockposgoal_simulation(
+        &mut partial_chunks,
+        BlockPos::new(0, 71, 0),
+        BlockPos::new(0, 71, 3),
+        &[
+            BlockPos::new(0, 70, 0),
+            BlockPos::new(0, 70, 1),
+            BlockPos::new(0, 70, 2),
+            BlockPos::new(0, 70, 3),
+        ],
+    );
+    let boat = simulation.app.world_mut().spawn_empty().id();
+    simulation
+        .app
+        .world_mut()
+        .entity_mut(simulation.entity)
+        .insert(Riding(boat));
+    // act like the server and make us get off when we press shift
+    simulation.app.init_resource::<SentInputs>().add_observer(
+        |trigger: Trigger<SendPacketEvent>,
+         mut commands: Commands,
+         mut sent: ResMut<SentInputs>| {
+            if let ServerboundGamePacket::PlayerInput(p) = &trigger.event().packet {
+                if p.shift {
+                    commands.entity(trigger.event().sent_by).remove::<Riding>();
+                }
+                sent.0.push(p.clone());
+            }
+        },
+    );
+
+    assert_simulation_reaches(&mut simulation, 40, BlockPos::new(0, 71, 3));
+
+    let sent = &simulation.app.world().resource::<SentInputs>().0;
+    let first_dismount = sent.iter().position(|input| input.shift);
+    let first_walk = sent.iter().position(|input| input.forward);
+    assert_eq!(first_dismount, Some(0), "{sent:?}");
+    assert!(first_walk.is_some_and(|i| i > 0), "{sent:?}");
+    assert!(
+        simulation
+            .app
+            .world()
+            .get::<Riding>(simulation.entity)
+            .is_none()
+    );
+}
+
+#[test]
+fn test_turn_cost() {
+    use super::{costs::TURN_PENALTY, rel_block_pos::RelBlockPos, turn_cost};
+
+    let cost = |a: (i16, i16), b: (i16, i16), c: (i16, i16)| {
+        turn_cost(
+            RelBlockPos::new(a.0, 0, a.1),
+            RelBlockPos::new(b.0, 0, b.1),
+            RelBlockPos::new(c.0, 0, c.1),
+        )
+    };
+    // straight
+    assert_eq!(cost((0, 0), (1, 0), (2, 0)), 0.);
+    // 45°
+    assert_eq!(cost((0, 0), (1, 0), (2, 1)), 0.);
+    // 90°
+    assert_eq!(cost((0, 0), (1, 0), (1, 1)), TURN_PENALTY);
+    // turning around
+    assert_eq!(cost((0, 0), (1, 0), (0, 0)), TURN_PENALTY);
+    // going straight up doesn't count as a direction
+    assert_eq!(
+        turn_cost(
+            RelBlockPos::new(0, 0, 0),
+            RelBlockPos::new(0, 1, 0),
+            RelBlockPos::new(1, 1, 0),
+        ),
+        0.
+    );
+}
+
+#[test]
+fn test_turn_penalty_on_open_field() {
+    use azalea_world::Instance;
+    use parking_lot::RwLock;
+
+    use super::{
+        ExecutingPath, astar,
+        custom_state::CustomPathfinderStateRef,
+        fill_successors,
+        goals::Goal,
+        mining::MiningCache,
+        moves::{
+            MoveData,
+            water::{SwimmingState, next_swimming_state},
+        },
+        rel_block_pos::RelBlockPos,
+        turn_cost,
+        world::CachedWorld,
+    };
+
+    type TurnCostFn = fn(RelBlockPos, RelBlockPos, RelBlockPos) -> f32;
+
+    let start = BlockPos::new(0, 71, 0);
+    let end = BlockPos::new(12, 71, 7);
+    let mut solid_blocks = Vec::new();
+    for x in 0..16 {
+        for z in 0..16 {
+            solid_blocks.push(BlockPos::new(x, 70, z));
+        }
+    }
+
+    // returns the path and how many sharp turns it has
+    let plan = |turn_cost: TurnCostFn| {
+        let mut partial_chunks = PartialChunkStorage::default();
+        let mut chunks = ChunkStorage::default();
+        partial_chunks.set(&ChunkPos::new(0, 0), Some(Chunk::default()), &mut chunks);
+        for block_pos in &solid_blocks {
+            chunks.set_block_state(*block_pos, azalea_registry::Block::Stone.into());
+        }
+        let cached_world = CachedWorld::new(Arc::new(RwLock::new(Instance::from(chunks))), start);
+        let mining_cache = MiningCache::new(None);
+        let custom_state = CustomPathfinderStateRef::default();
+        let successors = |pos: RelBlockPos, swimming: SwimmingState, edges: &mut Vec<_>| {
+            fill_successors(
+                &cached_world,
+                &mining_cache,
+                1.,
+                LOW_AIR_THRESHOLD,
+                MoveSettings::default(),
+                &custom_state,
+                moves::default_move,
+                swimming,
+                pos,
+                edges,
+            )
+        };
+
+        let path = astar::a_star(
+            RelBlockPos::get_origin(start),
+            SwimmingState::default(),
+            |n| BlockPosGoal(end).heuristic(n.apply(start)),
+            successors,
+            |n| n.apply(start) == end,
+            turn_cost,
+            |swimming, pos| next_swimming_state(&cached_world, swimming, pos),
+            PathfinderTimeout::Nodes(1_000_000),
+            PathfinderTimeout::Nodes(5_000_000),
+        );
+        assert!(!path.is_partial);
+
+        let mut nodes = vec![RelBlockPos::get_origin(start)];
+        nodes.extend(path.movements.iter().map(|movement| movement.target));
+        let sharp_turns = nodes
+            .windows(3)
+            .filter(|w| super::turn_cost(w[0], w[1], w[2]) > 0.)
+            .count();
+        let edges = path
+            .movements
+            .into_iter()
+            .map(|movement| astar::Edge {
+                movement: astar::Movement {
+                    target: movement.target.apply(start),
+                    data: movement.data,
+                },
+                cost: 0.,
+            })
+            .collect::<Vec<astar::Edge<BlockPos, MoveData>>>();
+        (edges, sharp_turns)
+    };
+
+    // returns how many ticks it took to follow the path
+    let execute = |path: Vec<astar::Edge<BlockPos, MoveData>>| {
+        let mut partial_chunks = PartialChunkStorage::default();
+        let mut simulation = setup_simulation_world(&mut partial_chunks, start, &solid_blocks, &[]);
+        simulation
+            .app
+            .world_mut()
+            .entity_mut(simulation.entity)
+            .insert(ExecutingPath {
+                path: path.into(),
+                queued_path: None,
+                last_reached_node: start,
+                last_node_reached_at: Instant::now(),
+                is_path_partial: false,
+                interact_cooldown: 0,
+                falling_block_wait: 0,
+            });
+        let mut ticks = 0;
+        while simulation.get_component::<ExecutingPath>().is_some() && ticks < 400 {
+            simulation.tick();
+            ticks += 1;
+        }
+        assert_eq!(BlockPos::from(simulation.position()), end);
+        ticks
+    };
+
+    let (path_without_penalty, turns_without_penalty) = plan(|_, _, _| 0.);
+    let (path_with_penalty, turns_with_penalty) = plan(turn_cost);
+    assert!(
+        turns_with_penalty <= turns_without_penalty,
+        "{turns_with_penalty} > {turns_without_penalty}"
+    );
+
+    let ticks_without_penalty = execute(path_without_penalty);
+    let ticks_with_penalty = execute(path_with_penalty);
+    assert!(
+        ticks_with_penalty <= ticks_without_penalty,
+        "{ticks_with_penalty} > {ticks_without_penalty}"
+    );
+}
+
+#[test]
+fn test_open_doorways_are_passable() {
+    use azalea_block::{blocks, properties};
+
+    use super::{
+        costs::{OPEN_DOOR_PENALTY, SPRINT_ONE_BLOCK_COST, WALK_ONE_BLOCK_COST},
+        world::is_block_state_passable,
+    };
+
+    let door = |iron: bool, half, open, powered| {
+        let (facing, hinge) = (properties::FacingCardinal::North, properties::Hinge::Left);
+        if iron {
+            BlockState::from(blocks::IronDoor {
+                facing,
+                half,
+                hinge,
+                open,
+                powered,
+            })
+        } else {
+            BlockState::from(blocks::OakDoor {
+                facing,
+                half,
+                hinge,
+                open,
+                powered,
+            })
+        }
+    };
+    let gate = |open, powered| {
+        BlockState::from(blocks::OakFenceGate {
+            facing: properties::FacingCardinal::North,
+            in_wall: false,
+            open,
+            powered,
+        })
+    };
+    let trapdoor = |iron: bool, open, powered| {
+        let (facing, half) = (
+            properties::FacingCardinal::North,
+            properties::TopBottom::Bottom,
+        );
+        if iron {
+            BlockState::from(blocks::IronTrapdoor {
+                facing,
+                half,
+                open,
+                powered,
+                waterlogged: false,
+            })
+        } else {
+            BlockState::from(blocks::OakTrapdoor {
+                facing,
+                half,
+                open,
+                powered,
+                waterlogged: false,
+            })
+        }
+    };
+
+    for iron in [false, true] {
+        let lower_door = |open, powered| door(iron, properties::Half::Lower, open, powered);
+        assert!(!is_block_state_passable(lower_door(false, false)));
+        assert!(is_block_state_passable(lower_door(true, false)));
+        assert!(is_block_state_passable(lower_door(false, true)));
+        assert!(is_block_state_passable(door(
+            iron,
+            properties::Half::Upper,
+            true,
+            false
+        )));
+
+        assert!(!is_block_state_passable(trapdoor(iron, false, false)));
+        assert!(is_block_state_passable(trapdoor(iron, true, false)));
+        assert!(is_block_state_passable(trapdoor(iron, false, true)));
+    }
+    assert!(!is_block_state_passable(gate(false, false)));
+    assert!(is_block_state_passable(gate(true, false)));
+    assert!(is_block_state_passable(gate(false, true)));
+
+    // a wall with a doorway in it, and no way around it
+    let stone = azalea_registry::Block::Stone.into();
+    let doorway_path = |lower: BlockState, upper: BlockState| {
+        let mut blocks = Vec::new();
+        for x in -3..=3 {
+            for z in 0..=2 {
+                blocks.push((BlockPos::new(x, 70, z), stone));
+            }
+            if x != 0 {
+                blocks.push((BlockPos::new(x, 71, 1), stone));
+                blocks.push((BlockPos::new(x, 72, 1), stone));
+            }
+        }
+        blocks.push((BlockPos::new(0, 71, 1), lower));
+        blocks.push((BlockPos::new(0, 72, 1), upper));
+        calculate_path_found_event(
+            BlockPos::new(0, 71, 0),
+            BlockPos::new(0, 71, 2),
+            &blocks,
+            false,
+            None,
+            super::world::PathEquipment::default(),
+        )
+    };
+
+    let door_halves = |iron, open, powered| {
+        (
+            door(iron, properties::Half::Lower, open, powered),
+            door(iron, properties::Half::Upper, open, powered),
+        )
+    };
+    // (lower, upper), whether it's open, and whether we can open it ourselves
+    for ((lower, upper), is_open, is_openable) in [
+        (door_halves(false, false, false), false, true),
+        (door_halves(false, true, false), true, true),
+        (door_halves(true, false, false), false, false),
+        (door_halves(true, false, true), true, false),
+        ((gate(false, false), BlockState::AIR), false, true),
+        ((gate(true, false), BlockState::AIR), true, true),
+    ] {
+        let event = doorway_path(lower, upper);
+        if is_open || is_openable {
+            assert!(!event.is_partial, "{lower:?}");
+            let path = event.path.unwrap();
+            let targets = path
+                .iter()
+                .map(|edge| edge.movement.target)
+                .collect::<Vec<_>>();
+            assert_eq!(
+                targets,
+                vec![BlockPos::new(0, 71, 1), BlockPos::new(0, 71, 2)],
+                "{lower:?}"
+            );
+            let cost = path.iter().map(|edge| edge.cost).sum::<f32>();
+            if is_open {
+                // walking through an open doorway doesn't cost anything extra
+                assert_eq!(cost, SPRINT_ONE_BLOCK_COST * 2., "{lower:?}");
+            } else {
+                assert_eq!(
+                    cost,
+                    WALK_ONE_BLOCK_COST + OPEN_DOOR_PENALTY + SPRINT_ONE_BLOCK_COST,
+                    "{lower:?}"
+                );
+            }
+        } else {
+            assert!(event.is_partial, "{lower:?}");
+        }
+    }
+}
+
+#[test]
+fn test_open_door_in_corridor() {
+    use azalea_block::{blocks, properties};
+
+    // a 1-wide corridor with a closed door in the middle of it
+    let door = |half| {
+        BlockState::from(blocks::OakDoor {
+            facing: properties::FacingCardinal::East,
+            half,
+            hinge: properties::Hinge::Left,
+            open: false,
+            powered: false,
+        })
+    };
+    let door_pos = BlockPos::new(3, 70, 0);
+    let mut solid_blocks = Vec::new();
+    for x in 0..=6 {
+        solid_blocks.push(BlockPos::new(x, 69, 0));
+        for y in 70..=71 {
+            solid_blocks.push(BlockPos::new(x, y, -1));
+            solid_blocks.push(BlockPos::new(x, y, 1));
+        }
+    }
+    let extra_blocks = [
+        (door_pos, door(properties::Half::Lower)),
+        (door_pos.up(1), door(properties::Half::Upper)),
+    ];
+
+    let mut partial_chunks = PartialChunkStorage::default();
+    let mut simulation = setup_simulation_world(
+        &mut partial_chunks,
+        BlockPos::new(0, 70, 0),
+        &solid_blocks,
+        &extra_blocks,
+    );
+    simulation.app.world_mut().send_event(
+        GotoEvent::new(simulation.entity, BlockPosGoal(BlockPos::new(6, 70, 0))).with_options(
+            GotoOptions::default()
+                .allow_mining(false)
+                .retry_on_no_path(true)
+                .min_timeout(PathfinderTimeout::Nodes(1_000_000))
+                .max_timeout(PathfinderTimeout::Nodes(5_000_000))
+                .avoid_damage(false),
+        ),
+    );
+    assert_simulation_reaches(&mut simulation, 80, BlockPos::new(6, 70, 0));
+
+    // both halves of the door got opened, and it wasn't closed again after
+    let instance = simulation.instance();
+    let instance = instance.read();
+    for pos in [door_pos, door_pos.up(1)] {
+        let state = instance.get_block_state(pos).unwrap();
+        assert_eq!(state.property::<properties::Open>(), Some(true));
+    }
+}
+
+#[test]
+fn test_bridge_over_gap() {
+    // two platforms with a gap between them that's too wide to jump over
+    let solid_blocks = (0..=1)
+        .chain(7..=8)
+        .map(|x| BlockPos::new(x, 69, 0))
+        .collect::<Vec<_>>();
+    let mut partial_chunks = PartialChunkStorage::default();
+    let mut simulation = setup_simulation_world(
+        &mut partial_chunks,
+        BlockPos::new(0, 70, 0),
+        &solid_blocks,
+        &[],
+    );
+    give_hotbar_item(&mut simulation, azalea_registry::Item::Cobblestone, 64);
+
+    simulation.app.world_mut().send_event(
+        GotoEvent::new(simulation.entity, BlockPosGoal(BlockPos::new(8, 70, 0))).with_options(
+            GotoOptions::default()
+                .allow_mining(false)
+                .retry_on_no_path(true)
+                .min_timeout(PathfinderTimeout::Nodes(1_000_000))
+                .max_timeout(PathfinderTimeout::Nodes(5_000_000))
+                .avoid_damage(false)
+                .allow_placing(true),
+        ),
+    );
+    assert_simulation_reaches(&mut simulation, 300, BlockPos::new(8, 70, 0));
+
+    let instance = simulation.instance();
+    let instance = instance.read();
+    for x in 2..=6 {
+        assert_eq!(
+            instance.get_block_state(BlockPos::new(x, 69, 0)),
+            Some(azalea_registry::Block::Cobblestone.into())
+        );
+    }
+}
+
+#[test]
+fn test_pillar_up() {
+    let solid_blocks = (-1..=1)
+        .flat_map(|x| (-1..=1).map(move |z| BlockPos::new(x, 69, z)))
+        .collect::<Vec<_>>();
+    let mut partial_chunks = PartialChunkStorage::default();
+    let mut simulation = setup_simulation_world(
+        &mut partial_chunks,
+        BlockPos::new(0, 70, 0),
+        &solid_blocks,
+        &[],
+    );
+    give_hotbar_item(&mut simulation, azalea_registry::Item::Dirt, 64);
+
+    simulation.app.world_mut().send_event(
+        GotoEvent::new(simulation.entity, BlockPosGoal(BlockPos::new(0, 76, 0))).with_options(
+            GotoOptions::default()
+                .allow_mining(false)
+                .retry_on_no_path(true)
+                .min_timeout(PathfinderTimeout::Nodes(1_000_000))
+                .max_timeout(PathfinderTimeout::Nodes(5_000_000))
+                .avoid_damage(false)
+                .allow_placing(true),
+        ),
+    );
+    assert_simulation_reaches(&mut simulation, 200, BlockPos::new(0, 76, 0));
+
+    let instance = simulation.instance();
+    let instance = instance.read();
+    for y in 70..76 {
+        assert_eq!(
+            instance.get_block_state(BlockPos::new(0, y, 0)),
+            Some(azalea_registry::Block::Dirt.into())
+        );
+    }
+}
+
+#[test]
+fn test_smooth_path_across_open_platform() {
+    use parking_lot::RwLock;
+
+    use super::calculate_path;
+
+    let mut platform = Vec::new();
+    for x in 0..20 {
+        for z in 0..20 {
+            platform.push(BlockPos::new(x, 70, z));
+        }
+    }
+    let start = BlockPos::new(2, 71, 2);
+    let end = BlockPos::new(17, 71, 14);
+
+    let mut partial_chunks = PartialChunkStorage::default();
+    let world_lock = Arc::new(RwLock::new(
+        setup_chunks(&mut partial_chunks, &platform, &[]).into(),
+    ));
+    let path_targets = |smooth_path: bool| {
+        calculate_path(
+            test_path_opts(start, Arc::new(BlockPosGoal(end)), world_lock.clone())
+                .smooth_path(smooth_path),
+        )
+        .unwrap()
+        .path
+        .unwrap()
+        .iter()
+        .map(|edge| edge.movement.target)
+        .collect::<Vec<_>>()
+    };
+
+    let unsmoothed = path_targets(false);
+    let smoothed = path_targets(true);
+    assert!(
+        smoothed.len() < unsmoothed.len(),
+        "smoothing should merge nodes, got {smoothed:?}"
+    );
+    assert_eq!(smoothed.last(), Some(&end));
+    assert_eq!(unsmoothed.last(), Some(&end));
+
+    // and it should still get there
+    let mut partial_chunks = PartialChunkStorage::default();
+    let mut simulation = setup_blockposgoal_simulation(&mut partial_chunks, start, end, &platform);
+    assert_simulation_reaches(&mut simulation, 100, end);
+}
+
+#[test]
+fn test_reroute_when_block_placed_in_front() {
+    let mut floor = Vec::new();
+    for x in -1..=14 {
+        for z in -2..=2 {
+            floor.push(BlockPos::new(x, 70, z));
+        }
+    }
+    let end = BlockPos::new(13, 71, 0);
+    let mut partial_chunks = PartialChunkStorage::default();
+    let mut simulation =
+        setup_blockposgoal_simulation(&mut partial_chunks, BlockPos::new(0, 71, 0), end, &floor);
+
+    wait_until_bot_starts_moving(&mut simulation);
+    for _ in 0..40 {
+        if simulation.position().x >= 3. {
+            break;
+        }
+        simulation.tick();
+    }
+
+    // someone builds a wall right in front of us, in the middle of the edge that
+    // we're executing
+    let wall = BlockPos::from(simulation.position()) + BlockPos::new(2, 0, 0);
+    simulation.set_block_state(wall, azalea_registry::Block::Stone.into());
+    simulation.set_block_state(wall.up(1), azalea_registry::Block::Stone.into());
+
+    // if we didn't reroute, we'd keep walking into the wall until the move timed
+    // out
+    for _ in 0..80 {
+        simulation.tick();
+    }
+    assert_eq!(BlockPos::from(simulation.position()), end);
+}
+
+#[test]
+fn test_recover_after_being_moved_behind_wall() {
+    use azalea_entity::Position;
+
+    let mut solid_blocks = Vec::new();
+    for x in -1..=14 {
+        for z in -3..=3 {
+            solid_blocks.push(BlockPos::new(x, 70, z));
+        }
+    }
+    // a wall beside the path, which we'll get moved to the other side of
+    for x in 2..=8 {
+        solid_blocks.push(BlockPos::new(x, 71, 1));
+        solid_blocks.push(BlockPos::new(x, 72, 1));
+    }
+    let end = BlockPos::new(13, 71, 0);
+    let mut partial_chunks = PartialChunkStorage::default();
+    let mut simulation = setup_blockposgoal_simulation(
+        &mut partial_chunks,
+        BlockPos::new(0, 71, 0),
+        end,
+        &solid_blocks,
+    );
+
+    wait_until_bot_starts_moving(&mut simulation);
+    for _ in 0..40 {
+        if simulation.position().x >= 4. {
+            break;
+        }
+        simulation.tick();
+    }
+
+    // get pushed 2 blocks sideways, so walking straight to the next node just
+    // walks into the wall
+    let world = simulation.app.world_mut();
+    let mut position = world.get_mut::<Position>(simulation.entity).unwrap();
+    **position = Vec3::new(position.x, 71., 2.5);
+
+    for _ in 0..200 {
+        simulation.tick();
+        if BlockPos::from(simulation.position()) == end {
+            break;
+        }
+    }
+    assert_eq!(BlockPos::from(simulation.position()), end);
+}
+
+#[test]
+fn test_give_up_when_stuck() {
+    use azalea_entity::{Attributes, attributes::AttributeInstance};
+    use bevy_ecs::event::Events;
+
+    use super::{
+        ExecutingPath, UnreachableReason,
+        stuck::{MAX_STUCK_RECOVERIES, PathStuckEvent},
+    };
+
+    let start = BlockPos::new(0, 71, 0);
+    let mut partial_chunks = PartialChunkStorage::default();
+    let solid_blocks = (0..=10)
+        .map(|x| BlockPos::new(x, 70, 0))
+        .collect::<Vec<_>>();
+    let mut simulation = setup_blockposgoal_simulation(
+        &mut partial_chunks,
+        start,
+        BlockPos::new(10, 71, 0),
+        &solid_blocks,
+    );
+    // the path is fine, but we can't move
+    simulation
+        .app
+        .world_mut()
+        .get_mut::<Attributes>(simulation.entity)
+        .unwrap()
+        .speed = AttributeInstance::new(0.);
+
+    let mut stuck_cursor = simulation
+        .app
+        .world()
+        .resource::<Events<PathStuckEvent>>()
+        .get_cursor();
+    let mut stuck_events = Vec::new();
+    for _ in 0..300 {
+        simulation.tick();
+        let events = simulation.app.world().resource::<Events<PathStuckEvent>>();
+        stuck_events.extend(stuck_cursor.read(events).cloned());
+    }
+
+    assert_eq!(stuck_events.len() as u32, MAX_STUCK_RECOVERIES + 1);
+    let (last, recoveries) = stuck_events.split_last().unwrap();
+    assert!(recoveries.iter().all(|event| !event.gave_up));
+    assert!(last.gave_up);
+    assert_eq!(last.position, start);
+
+    let pathfinder = simulation.component::<Pathfinder>();
+    assert!(pathfinder.goal.is_none());
+    assert_eq!(
+        pathfinder.outcome,
+        Some(GotoOutcome::Unreachable {
+            closest: start,
+            reason: UnreachableReason::Stuck
+        })
+    );
+    assert!(simulation.get_component::<ExecutingPath>().is_none());
+}
+
+#[test]
+fn test_path_events() {
+    use bevy_ecs::event::Events;
+
+    use super::{PathComputedEvent, PathExecutionFinishedEvent};
+
+    let start = BlockPos::new(0, 71, 0);
+    let end = BlockPos::new(6, 71, 2);
+    let mut solid_blocks = Vec::new();
+    for x in 0..=6 {
+        for z in 0..=2 {
+            solid_blocks.push(BlockPos::new(x, 70, z));
+        }
+    }
+    let mut partial_chunks = PartialChunkStorage::default();
+    let mut simulation =
+        setup_blockposgoal_simulation(&mut partial_chunks, start, end, &solid_blocks);
+
+    let mut computed_cursor = simulation
+        .app
+        .world()
+        .resource::<Events<PathComputedEvent>>()
+        .get_cursor();
+    let mut finished_cursor = simulation
+        .app
+        .world()
+        .resource::<Events<PathExecutionFinishedEvent>>()
+        .get_cursor();
+    let mut computed_events = Vec::new();
+    let mut finished_events = Vec::new();
+    for _ in 0..100 {
+        simulation.tick();
+        let world = simulation.app.world();
+        computed_events.extend(
+            computed_cursor
+                .read(world.resource::<Events<PathComputedEvent>>())
+                .cloned(),
+        );
+        finished_events.extend(
+            finished_cursor
+                .read(world.resource::<Events<PathExecutionFinishedEvent>>())
+                .cloned(),
+        );
+    }
+
+    let computed = computed_events.first().expect("no path was computed");
+    assert_eq!(computed.entity, simulation.entity);
+    assert_eq!(computed.start, start);
+    assert_eq!(computed.path.last().map(|node| node.pos), Some(end));
+    assert!(!computed.is_partial);
+    assert!(computed.nodes_expanded > 0);
+    let total_cost = computed.path.iter().map(|node| node.cost).sum::<f32>();
+    assert_eq!(computed.total_cost, total_cost);
+
+    assert_eq!(finished_events.len(), 1);
+    assert!(finished_events[0].success);
+    assert_eq!(finished_events[0].position, end);
+}
+
+#[test]
+fn test_no_parkour_when_disabled_in_settings() {
+    use super::settings::PathfinderSettings;
+
+    // the same world as test_parkour_2_block_gap
+    let mut partial_chunks = PartialChunkStorage::default();
+    let mut simulation = setup_blockposgoal_simulation(
+        &mut partial_chunks,
+        BlockPos::new(0, 71, 0),
+        BlockPos::new(0, 71, 3),
+        &[BlockPos::new(0, 70, 0), BlockPos::new(0, 70, 3)],
+    );
+    simulation
+        .app
+        .world_mut()
+        .entity_mut(simulation.entity)
+        .insert(PathfinderSettings::default().with_allow_parkour(false));
+
+    for _ in 0..100 {
+        simulation.tick();
+    }
+    assert_eq!(
+        BlockPos::from(simulation.position()),
+        BlockPos::new(0, 71, 0)
+    );
+}
+
+#[test]
+fn test_water_cost_multiplier_setting() {
+    use bevy_ecs::event::Events;
+
+    use super::{PathComputedEvent, settings::PathfinderSettings};
+
+    // a corridor with some shallow water in the middle, and a much longer way
+    // around it
+    let start = BlockPos::new(0, 71, 0);
+    let end = BlockPos::new(8, 71, 0);
+    let mut solid_blocks = Vec::new();
+    for i in 0..=8 {
+        solid_blocks.push(BlockPos::new(i, 70, 0));
+        solid_blocks.push(BlockPos::new(0, 70, i));
+        solid_blocks.push(BlockPos::new(i, 70, 8));
+        solid_blocks.push(BlockPos::new(8, 70, i));
+    }
+    let water = BlockState::from(azalea_registry::Block::Water);
+    let extra_blocks = (3..=5)
+        .map(|x| (BlockPos::new(x, 71, 0), water))
+        .collect::<Vec<_>>();
+
+    let goes_through_water = |settings: PathfinderSettings| {
+        let mut partial_chunks = PartialChunkStorage::default();
+        let mut simulation =
+            setup_simulation_world(&mut partial_chunks, start, &solid_blocks, &extra_blocks);
+        simulation
+            .app
+            .world_mut()
+            .entity_mut(simulation.entity)
+            .insert(settings);
+        simulation.app.world_mut().send_event(
+            GotoEvent::new(simulation.entity, BlockPosGoal(end)).with_options(
+                GotoOptions::default()
+                    .allow_mining(false)
+                    .timeout(PathfinderTimeout::Nodes(1_000_000)),
+            ),
+        );
+
+        let mut cursor = simulation
+            .app
+            .world()
+            .resource::<Events<PathComputedEvent>>()
+            .get_cursor();
+        for _ in 0..20 {
+            simulation.tick();
+            let events = simulation
+                .app
+                .world()
+                .resource::<Events<PathComputedEvent>>();
+            if let Some(event) = cursor.read(events).next() {
+                assert!(!event.is_partial);
+                return event
+                    .path
+                    .iter()
+                    .any(|node| (3..=5).contains(&node.pos.x) && node.pos.z == 0);
+            }
+        }
+        panic!("no path was computed");
+    };
+
+    assert!(goes_through_water(PathfinderSettings::default()));
+    assert!(!goes_through_water(
+        PathfinderSettings::default().with_water_cost_multiplier(10.)
+    ));
+}
+
+#[test]
+fn test_and_goal_of_y_level_and_xz() {
+    use super::goals::{AndGoal, Goal, XZGoal, YGoal};
+
+    // a floor with some stairs up to a block that's above the floor, so the
+    // column at x=4 can be reached from the floor or from the stairs
+    let mut solid_blocks = Vec::new();
+    for x in 0..=4 {
+        for z in -1..=1 {
+            solid_blocks.push(BlockPos::new(x, 70, z));
+        }
+    }
+    solid_blocks.extend([
+        BlockPos::new(2, 71, 0),
+        BlockPos::new(3, 71, 0),
+        BlockPos::new(3, 72, 0),
+        BlockPos::new(4, 73, 0),
+    ]);
+
+    let end_of_goto = |goal: Arc<dyn Goal>| {
+        let mut partial_chunks = PartialChunkStorage::default();
+        let mut simulation = setup_simulation_world(
+            &mut partial_chunks,
+            BlockPos::new(0, 71, 0),
+            &solid_blocks,
+            &[],
+        );
+        simulation.app.world_mut().send_event(
+            GotoEvent::new(simulation.entity, goal)
+                .with_options(GotoOptions::default().allow_mining(false)),
+        );
+        wait_for_goto_outcome(&mut simulation, Duration::from_secs(5));
+        BlockPos::from(simulation.position())
+    };
+
+    let xz = XZGoal { x: 4, z: 0 };
+    assert_eq!(end_of_goto(Arc::new(xz)), BlockPos::new(4, 71, 0));
+    assert_eq!(
+        end_of_goto(Arc::new(AndGoal(YGoal { y: 74 }, xz))),
+        BlockPos::new(4, 74, 0)
+    );
+}
+
+#[test]
+fn test_or_goals_heuristic_never_overestimates() {
+    use bevy_ecs::event::Events;
+
+    use super::{
+        PathComputedEvent,
+        goals::{Goal, OrGoals},
+    };
+
+    let start = BlockPos::new(0, 71, 0);
+    let near = BlockPos::new(-3, 71, 4);
+    let far = BlockPos::new(10, 71, 0);
+    let goal = OrGoals(vec![BlockPosGoal(far), BlockPosGoal(near)]);
+    let start_heuristic = goal.heuristic(start);
+
+    let mut solid_blocks = Vec::new();
+    for x in -4..=10 {
+        for z in -1..=5 {
+            solid_blocks.push(BlockPos::new(x, 70, z));
+        }
+    }
+    let mut partial_chunks = PartialChunkStorage::default();
+    let mut simulation = setup_simulation_world(&mut partial_chunks, start, &solid_blocks, &[]);
+    let mut cursor = simulation
+        .app
+        .world()
+        .resource::<Events<PathComputedEvent>>()
+        .get_cursor();
+    simulation.app.world_mut().send_event(
+        GotoEvent::new(simulation.entity, goal)
+            .with_options(GotoOptions::default().allow_mining(false)),
+    );
+
+    let mut computed = None;
+    for _ in 0..20 {
+        simulation.tick();
+        let events = simulation
+            .app
+            .world()
+            .resource::<Events<PathComputedEvent>>();
+        computed = cursor.read(events).next().cloned();
+        if computed.is_some() {
+            break;
+        }
+    }
+    let computed = computed.expect("no path was computed");
+    assert!(!computed.is_partial);
+    assert_eq!(computed.path.last().map(|node| node.pos), Some(near));
+    assert!(
+        start_heuristic <= computed.total_cost,
+        "{start_heuristic} > {}",
+        computed.total_cost
+    );
+}
+
+#[test]
+fn test_reach_block_embedded_in_wall() {
+    use super::goals::{Goal, ReachBlockPosGoal};
+
+    // a chest in a wall that's too tall to jump over, with only its north face
+    // exposed. the bot starts behind the wall.
+    let chest_pos = BlockPos::new(0, 71, 0);
+    let mut solid_blocks = Vec::new();
+    for x in -6..=6 {
+        for z in -4..=5 {
+            solid_blocks.push(BlockPos::new(x, 70, z));
+        }
+    }
+    for x in -3..=3 {
+        for y in 71..=72 {
+            for z in 0..=1 {
+                let pos = BlockPos::new(x, y, z);
+                if pos != chest_pos {
+                    solid_blocks.push(pos);
+                }
+            }
+        }
+    }
+    let chest = BlockState::from(azalea_registry::Block::Chest);
+
+    let mut partial_chunks = PartialChunkStorage::default();
+    let mut simulation = setup_simulation_world(
+        &mut partial_chunks,
+        BlockPos::new(0, 71, 4),
+        &solid_blocks,
+        &[(chest_pos, chest)],
+    );
+    let chunk_storage = simulation.instance().read().chunks.clone();
+    let goal = ReachBlockPosGoal::new(chest_pos, chunk_storage);
+    simulation.app.world_mut().send_event(
+        GotoEvent::new(simulation.entity, goal.clone())
+            .with_options(GotoOptions::default().allow_mining(false)),
+    );
+    wait_for_goto_outcome(&mut simulation, Duration::from_secs(5));
+
+    // we stopped in front of the chest, not on top of the wall or behind it
+    let end = BlockPos::from(simulation.position());
+    assert_eq!(end.y, 71);
+    assert!(end.z < 0, "ended at {end}");
+    assert!(goal.success(end), "can't reach the chest from {end}");
+    let distance = end.center().distance_to(chest_pos.center());
+    assert!(
+        (2. ..=4.).contains(&distance),
+        "ended {distance} blocks away"
+    );
+}
+
+#[test]
+fn test_follow_teleporting_entity() {
+    use azalea_entity::Position;
+    use azalea_world::InstanceName;
+
+    use super::follow::FollowEntity;
+
+    let mut solid_blocks = Vec::new();
+    for x in -10..=10 {
+        for z in -10..=10 {
+            solid_blocks.push(BlockPos::new(x, 70, z));
+        }
+    }
+    let mut partial_chunks = PartialChunkStorage::default();
+    let mut simulation = setup_simulation_world(
+        &mut partial_chunks,
+        BlockPos::new(0, 71, 0),
+        &solid_blocks,
+        &[],
+    );
+
+    // the entity that we follow teleports around every 40 ticks
+    let teleports = [(5, 0), (5, 6), (-1, 6), (-6, 2), (-2, -4), (4, -5)]
+        .map(|(x, z)| Vec3::new(x as f64 + 0.5, 71., z as f64 + 0.5));
+    let instance_name = simulation.component::<InstanceName>();
+    let target = simulation
+        .app
+        .world_mut()
+        .spawn((Position::new(teleports[0]), instance_name))
+        .id();
+    let follow_distance = 3.;
+    simulation
+        .app
+        .world_mut()
+        .entity_mut(simulation.entity)
+        .insert(
+            FollowEntity::new(target, follow_distance)
+                .with_options(GotoOptions::default().allow_mining(false)),
+        );
+
+    for i in 0..10 {
+        let target_pos = teleports[i % teleports.len()];
+        *simulation
+            .app
+            .world_mut()
+            .get_mut::<Position>(target)
+            .unwrap() = Position::new(target_pos);
+        for _ in 0..40 {
+            simulation.tick();
+            thread::sleep(Duration::from_millis(1));
+        }
+
+        let distance = (simulation.position() - target_pos)
+            .horizontal_distance_squared()
+            .sqrt();
+        assert!(
+            distance <= follow_distance as f64 + 1.,
+            "{distance} blocks away from {target_pos} after teleport {i}"
+        );
+    }
+    assert!(simulation.get_component::<FollowEntity>().is_some());
+
+    // we stay where we are once we're close enough instead of circling around it
+    let stopped_at = simulation.position();
+    for _ in 0..20 {
+        simulation.tick();
+    }
+    assert_eq!(simulation.position(), stopped_at);
+}
+
+#[test]
+fn test_flee_in_open_room() {
+    use super::goals::FleeGoal;
+
+    // a 40x40 room
+    let mut solid_blocks = Vec::new();
+    for x in -20..20 {
+        for z in -20..20 {
+            solid_blocks.push(BlockPos::new(x, 70, z));
+            if x == -20 || x == 19 || z == -20 || z == 19 {
+                solid_blocks.push(BlockPos::new(x, 71, z));
+                solid_blocks.push(BlockPos::new(x, 72, z));
+            }
+        }
+    }
+    let mut partial_chunks = PartialChunkStorage::default();
+    let mut simulation = setup_simulation_world(
+        &mut partial_chunks,
+        BlockPos::new(0, 71, 0),
+        &solid_blocks,
+        &[],
+    );
+    let from = simulation.position();
+    simulation.app.world_mut().send_event(
+        GotoEvent::new(
+            simulation.entity,
+            FleeGoal {
+                from,
+                min_distance: 15.,
+            },
+        )
+        .with_options(GotoOptions::default().allow_mining(false)),
+    );
+
+    let outcome = wait_for_goto_outcome(&mut simulation, Duration::from_secs(5));
+    assert_eq!(outcome, Some(GotoOutcome::Reached));
+    let distance = simulation.position().distance_to(from);
+    assert!(distance >= 14.5, "only got {distance} blocks away");
+}
+
+#[test]
+fn test_flee_in_small_cell_goes_to_farthest_corner() {
+    use super::goals::FleeGoal;
+
+    // a 5x5 cell with walls that are too tall to jump over
+    let stone = BlockState::from(azalea_registry::Block::Stone);
+    let mut blocks = Vec::new();
+    for x in -3..=3 {
+        for z in -3..=3 {
+            blocks.push((BlockPos::new(x, 70, z), stone));
+            if x.abs() == 3 || z.abs() == 3 {
+                for y in 71..=72 {
+                    blocks.push((BlockPos::new(x, y, z), stone));
+                }
+            }
+        }
+    }
+
+    // fleeing from one corner of the cell can only get us to the opposite one
+    let goal = FleeGoal {
+        from: BlockPos::new(2, 71, 2).center_bottom(),
+        min_distance: 15.,
+    };
+    let path = calculate_goal_path_found_event(
+        BlockPos::new(0, 71, 0),
+        Arc::new(goal),
+        &blocks,
+        false,
+        None,
+        super::world::PathEquipment::default(),
+    );
+    assert!(path.is_partial);
+    let end = path.path.unwrap().back().map(|edge| edge.movement.target);
+    assert_eq!(end, Some(BlockPos::new(-2, 71, -2)));
+}
+
+/// Calculate a path along a 1 block wide corridor from (0, 71, 0) to
+/// (10, 71, 0), which has a detour that goes `detour_width` blocks to the side
+/// and back, and return the nodes of the path.
+fn calculate_corridor_path(detour_width: i32, options: GotoOptions) -> Vec<BlockPos> {
+    use bevy_ecs::event::Events;
+
+    use super::PathComputedEvent;
+
+    let mut solid_blocks = Vec::new();
+    for x in 0..=10 {
+        solid_blocks.push(BlockPos::new(x, 70, 0));
+        solid_blocks.push(BlockPos::new(x, 70, detour_width));
+    }
+    for z in 1..detour_width {
+        solid_blocks.push(BlockPos::new(0, 70, z));
+        solid_blocks.push(BlockPos::new(10, 70, z));
+    }
+    let mut partial_chunks = PartialChunkStorage::default();
+    let mut simulation = setup_simulation_world(
+        &mut partial_chunks,
+        BlockPos::new(0, 71, 0),
+        &solid_blocks,
+        &[],
+    );
+    let mut cursor = simulation
+        .app
+        .world()
+        .resource::<Events<PathComputedEvent>>()
+        .get_cursor();
+    simulation.app.world_mut().send_event(
+        GotoEvent::new(simulation.entity, BlockPosGoal(BlockPos::new(10, 71, 0)))
+            .with_options(options),
+    );
+
+    for _ in 0..20 {
+        simulation.tick();
+        let events = simulation
+            .app
+            .world()
+            .resource::<Events<PathComputedEvent>>();
+        if let Some(computed) = cursor.read(events).next() {
+            assert!(!computed.is_partial);
+            return computed.path.iter().map(|node| node.pos).collect();
+        }
+    }
+    panic!("no path was computed");
+}
+
+/// The region that covers the middle of the short route in
+/// [`calculate_corridor_path`].
+fn corridor_region() -> azalea_core::aabb::AABB {
+    azalea_core::aabb::AABB {
+        min: Vec3::new(4., 60., -1.),
+        max: Vec3::new(7., 90., 1.),
+    }
+}
+
+#[test]
+fn test_hard_avoided_region_forces_long_way() {
+    let region = corridor_region();
+    let goes_through_region =
+        |path: &[BlockPos]| path.iter().any(|pos| region.contains(pos.center()));
+    let options = GotoOptions::default().allow_mining(false);
+
+    assert!(goes_through_region(&calculate_corridor_path(
+        4,
+        options.clone()
+    )));
+    let path = calculate_corridor_path(4, options.avoid_regions(vec![region]));
+    assert!(!goes_through_region(&path), "{path:?}");
+    assert_eq!(path.last(), Some(&BlockPos::new(10, 71, 0)));
+}
+
+#[test]
+fn test_soft_avoided_region_crossed_when_detour_is_long() {
+    use super::{costs::WALK_ONE_BLOCK_COST, regions::RegionAvoidance};
+
+    let region = corridor_region();
+    let goes_through_region =
+        |path: &[BlockPos]| path.iter().any(|pos| region.contains(pos.center()));
+    let options = GotoOptions::default()
+        .allow_mining(false)
+        .avoid_regions(vec![region])
+        .region_avoidance(RegionAvoidance::Soft(WALK_ONE_BLOCK_COST * 20.));
+
+    // going around is about twice as long
+    assert!(!goes_through_region(&calculate_corridor_path(
+        4,
+        options.clone()
+    )));
+    // going around is about 10 times as long
+    assert!(goes_through_region(&calculate_corridor_path(45, options)));
+}
+
+#[test]
+fn test_radius_goal_path_is_cheapest() {
+    use super::goals::{Goal, RadiusGoal};
+
+    /// A goal without a heuristic, so A* checks every cheaper position first
+    /// and always finds the cheapest path.
+    #[derive(Debug)]
+    struct ExhaustiveGoal<G>(G);
+    impl<G: Goal> Goal for ExhaustiveGoal<G> {
+        fn heuristic(&self, _: BlockPos) -> f32 {
+            0.
+        }
+        fn success(&self, n: BlockPos) -> bool {
+            self.0.success(n)
+        }
+    }
+
+    // going straight towards the goal is a dead end, so the shorter way starts by
+    // going away from it
+    let map = [
+        "###############",
+        "#.............#",
+        "#.###########.#",
+        "#S........#G..#",
+        "#########.###.#",
+        "#########.###.#",
+        "#########.###.#",
+        "#########.....#",
+        "###############",
+    ];
+    let stone: BlockState = azalea_registry::Block::Stone.into();
+    let mut blocks = Vec::new();
+    let mut start = None;
+    let mut goal_pos = None;
+    for (z, row) in map.iter().enumerate() {
+        for (x, c) in row.chars().enumerate() {
+            let pos = BlockPos::new(x as i32, 71, z as i32);
+            blocks.push((pos.down(1), stone));
+            match c {
+                '#' => {
+                    blocks.push((pos, stone));
+                    blocks.push((pos.up(1), stone));
+                }
+                'S' => start = Some(pos),
+                'G' => goal_pos = Some(pos),
+                _ => {}
+            }
+        }
+    }
+    let (start, goal_pos) = (start.unwrap(), goal_pos.unwrap());
+    let goal = RadiusGoal::new(goal_pos.center(), 0.5);
+
+    let path_cost = |goal: Arc<dyn Goal>| {
+        let path = calculate_goal_path_found_event(
+            start,
+            goal,
+            &blocks,
+            false,
+            None,
+            super::world::PathEquipment::default(),
+        )
+        .path
+        .unwrap();
+        assert_eq!(path.back().map(|edge| edge.movement.target), Some(goal_pos));
+        path.iter().map(|edge| edge.cost).sum::<f32>()
+    };
+    let cost = path_cost(Arc::new(goal));
+    let cheapest_cost = path_cost(Arc::new(ExhaustiveGoal(goal)));
+    assert_eq!(cost, cheapest_cost);
+}
+
+#[test]
+fn test_long_distance_path_expands_fewer_nodes() {
+    use parking_lot::RwLock;
+
+    use super::{calculate_path, long_distance::DEFAULT_LONG_DISTANCE_THRESHOLD};
+
+    // a flat world that's 1000 blocks long, with walls in the middle that make a
+    // dead end that's open towards us. the normal search has to look at
+    // everything in it before it goes around, but the route through chunks sees
+    // that the walls are in the way, since they're on the borders of chunks.
+    let mut solid_blocks = Vec::new();
+    for x in 0..1008 {
+        for z in -128..128 {
+            solid_blocks.push(BlockPos::new(x, 70, z));
+        }
+    }
+    for y in 71..=73 {
+        for z in -81..=80 {
+            solid_blocks.push(BlockPos::new(512, y, z));
+        }
+        for x in 400..=512 {
+            solid_blocks.push(BlockPos::new(x, y, -81));
+            solid_blocks.push(BlockPos::new(x, y, 80));
+        }
+    }
+    // the default storage is too small to keep all of the chunks loaded
+    let mut partial_chunks = PartialChunkStorage::new(64);
+    let world_lock = Arc::new(RwLock::new(
+        setup_chunks(&mut partial_chunks, &solid_blocks, &[]).into(),
+    ));
+
+    let start = BlockPos::new(0, 71, 0);
+    let end = BlockPos::new(1000, 71, 0);
+    let calculate = |long_distance_threshold: f32| {
+        calculate_path(
+            test_path_opts(start, Arc::new(BlockPosGoal(end)), world_lock.clone())
+                .long_distance_threshold(long_distance_threshold),
+        )
+        .unwrap()
+    };
+
+    let flat = calculate(f32::INFINITY);
+    let long_distance = calculate(DEFAULT_LONG_DISTANCE_THRESHOLD);
+    for path in [&flat, &long_distance] {
+        assert!(!path.is_partial);
+        let end_pos = path.path.as_ref().unwrap().back().unwrap().movement.target;
+        assert_eq!(end_pos, end);
+    }
+    assert!(
+        long_distance.nodes_expanded * 4 < flat.nodes_expanded,
+        "{} nodes with the route, {} without",
+        long_distance.nodes_expanded,
+        flat.nodes_expanded
+    );
+}
+
+#[test]
+fn test_start_walking_while_search_continues() {
+    use super::{ComputePath, ExecutingPath, Pathfinder, settings::PathfinderSettings};
+
+    let start = BlockPos::new(0, 71, 0);
+    let end = BlockPos::new(150, 71, 0);
+    let mut solid_blocks = Vec::new();
+    for x in 0..=150 {
+        for z in -8..8 {
+            solid_blocks.push(BlockPos::new(x, 70, z));
+        }
+    }
+    // the default storage is too small to keep all of the chunks loaded
+    let mut partial_chunks = PartialChunkStorage::new(64);
+    let mut simulation = setup_simulation_world(&mut partial_chunks, start, &solid_blocks, &[]);
+    // every part of the route through chunks goes over the min timeout, so the
+    // path is found in a few parts
+    simulation
+        .app
+        .world_mut()
+        .entity_mut(simulation.entity)
+        .insert(PathfinderSettings::default().with_long_distance_threshold(32.));
+    simulation.app.world_mut().send_event(
+        GotoEvent::new(simulation.entity, BlockPosGoal(end)).with_options(
+            GotoOptions::default()
+                .allow_mining(false)
+                .min_timeout(PathfinderTimeout::Nodes(1))
+                .max_timeout(PathfinderTimeout::Nodes(5_000_000)),
+        ),
+    );
+
+    let start_time = Instant::now();
+    while simulation
+        .app
+        .world()
+        .get::<ExecutingPath>(simulation.entity)
+        .is_none()
+    {
+        assert!(
+            start_time.elapsed() < Duration::from_secs(5),
+            "the bot never got a path"
+        );
+        simulation.tick();
+        thread::yield_now();
+    }
+    let world = simulation.app.world();
+    let executing_path = world.get::<ExecutingPath>(simulation.entity).unwrap();
+    assert!(executing_path.is_path_partial);
+    assert_ne!(executing_path.end(), Some(end));
+    let pathfinder = world.get::<Pathfinder>(simulation.entity).unwrap();
+    assert!(pathfinder.is_calculating);
+    assert!(world.get::<ComputePath>(simulation.entity).is_some());
+
+    assert_simulation_reaches(&mut simulation, 1000, end);
+    let world = simulation.app.world();
+    let pathfinder = world.get::<Pathfinder>(simulation.entity).unwrap();
+    assert!(!pathfinder.is_calculating);
+    assert!(world.get::<ComputePath>(simulation.entity).is_none());
+}
+
+#[test]
+fn test_a_star_matches_reference_on_random_terrain() {
+    use std::collections::BinaryHeap;
+
+    use indexmap::{IndexMap, map::Entry};
+    use parking_lot::RwLock;
+    use rand::{Rng, SeedableRng, rngs::StdRng};
+
+    use super::{
+        astar::{self, Edge, WeightedNode},
+        call_successors_fn,
+        custom_state::CustomPathfinderStateRef,
+        fill_successors,
+        goals::Goal,
+        mining::MiningCache,
+        moves::{
+            MoveData,
+            water::{SwimmingState, next_swimming_state},
+        },
+        rel_block_pos::RelBlockPos,
+        turn_cost,
+        world::CachedWorld,
+    };
+
+    /// The A* search from before its nodes were kept in a Vec, without the
+    /// timeouts or partial paths.
+    fn reference_a_star(
+        start: RelBlockPos,
+        heuristic: impl Fn(RelBlockPos) -> f32,
+        successors: impl Fn(RelBlockPos, SwimmingState) -> Vec<Edge<RelBlockPos, MoveData>>,
+        success: impl Fn(RelBlockPos) -> bool,
+        next_state: impl Fn(SwimmingState, RelBlockPos) -> SwimmingState,
+    ) -> Option<Vec<RelBlockPos>> {
+        let mut open_set = BinaryHeap::new();
+        open_set.push(WeightedNode {
+            f_score: 0.,
+            g_score: 0.,
+            index: 0,
+        });
+        // came_from, g_score, state
+        let mut nodes = IndexMap::new();
+        nodes.insert(start, (usize::MAX, 0., SwimmingState::default()));
+
+        while let Some(WeightedNode { index, g_score, .. }) = open_set.pop() {
+            let index = index as usize;
+            let (&node, &(came_from, node_g_score, state)) = nodes.get_index(index).unwrap();
+            if success(node) {
+                let mut path = Vec::new();
+                let mut current = index;
+                while let Some((&position, &(came_from, ..))) = nodes.get_index(current) {
+                    if came_from == usize::MAX {
+                        break;
+                    }
+                    path.push(position);
+                    current = came_from;
+                }
+                path.reverse();
+                return Some(path);
+            }
+            if g_score > node_g_score {
+                continue;
+            }
+            let previous_node =
+                (came_from != usize::MAX).then(|| *nodes.get_index(came_from).unwrap().0);
+
+            for neighbor in successors(node, state) {
+                let target = neighbor.movement.target;
+                let mut tentative_g_score = g_score + neighbor.cost;
+                if let Some(previous_node) = previous_node {
+                    tentative_g_score += turn_cost(previous_node, node, target);
+                }
+                let neighbor_state = next_state(state, target);
+                let neighbor_index = match nodes.entry(target) {
+                    Entry::Occupied(mut e) => {
+                        if e.get().1 <= tentative_g_score {
+                            continue;
+                        }
+                        e.insert((index, tentative_g_score, neighbor_state));
+                        e.index()
+                    }
+                    Entry::Vacant(e) => {
+                        let neighbor_index = e.index();
+                        e.insert((index, tentative_g_score, neighbor_state));
+                        neighbor_index
+                    }
+                };
+                open_set.push(WeightedNode {
+                    index: neighbor_index as u32,
+                    g_score: tentative_g_score,
+                    f_score: tentative_g_score + heuristic(target),
+                });
+            }
+        }
+        None
+    }
+
+    let start = BlockPos::new(1, 71, 1);
+    let end = BlockPos::new(30, 71, 30);
+    for seed in 0..5 {
+        // bumpy ground with some holes and pillars in it
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut solid_blocks = Vec::new();
+        for x in 0..32 {
+            for z in 0..32 {
+                let pos = BlockPos::new(x, 70, z);
+                if pos == start.down(1) || pos == end.down(1) {
+                    solid_blocks.push(pos);
+                    continue;
+                }
+                if rng.random_bool(0.1) {
+                    continue;
+                }
+                let height = if rng.random_bool(0.05) {
+                    3
+                } else {
+                    rng.random_range(0..=1)
+                };
+                for y in 70..=70 + height {
+                    solid_blocks.push(BlockPos::new(x, y, z));
+                }
+            }
+        }
+        let mut partial_chunks = PartialChunkStorage::default();
+        let chunks = setup_chunks(&mut partial_chunks, &solid_blocks, &[]);
+        let cached_world = CachedWorld::new(Arc::new(RwLock::new(chunks.into())), start);
+        let mining_cache = MiningCache::new(None);
+        let custom_state = CustomPathfinderStateRef::default();
+        let goal = BlockPosGoal(end);
+
+        let successors = |pos: RelBlockPos, swimming: SwimmingState, edges: &mut Vec<_>| {
+            fill_successors(
+                &cached_world,
+                &mining_cache,
+                1.,
+                LOW_AIR_THRESHOLD,
+                MoveSettings::default(),
+                &custom_state,
+                moves::default_move,
+                swimming,
+                pos,
+                edges,
+            )
+        };
+        let next_state = |swimming, pos| next_swimming_state(&cached_world, swimming, pos);
+
+        let path = astar::a_star(
+            RelBlockPos::get_origin(start),
+            SwimmingState::default(),
+            |n| goal.heuristic(n.apply(start)),
+            successors,
+            |n| goal.success(n.apply(start)),
+            turn_cost,
+            next_state,
+            PathfinderTimeout::Nodes(1_000_000),
+            PathfinderTimeout::Nodes(5_000_000),
+        );
+        let expected = reference_a_star(
+            RelBlockPos::get_origin(start),
+            |n| goal.heuristic(n.apply(start)),
+            |pos, swimming| {
+                call_successors_fn(
+                    &cached_world,
+                    &mining_cache,
+                    1.,
+                    LOW_AIR_THRESHOLD,
+                    MoveSettings::default(),
+                    &custom_state,
+                    moves::default_move,
+                    swimming,
+                    pos,
+                )
+            },
+            |n| goal.success(n.apply(start)),
+            next_state,
+        )
+        .expect("the goal should be reachable");
+
+        assert!(!path.is_partial, "seed {seed}");
+        let targets = path
+            .movements
+            .iter()
+            .map(|movement| movement.target)
+            .collect::<Vec<_>>();
+        assert_eq!(targets, expected, "seed {seed}");
+    }
+}
+
+#[test]
+fn test_pathfinder_metrics() {
+    use bevy_ecs::event::Events;
+
+    use super::{PathComputedEvent, metrics::PathfinderMetrics};
+
+    let start = BlockPos::new(0, 71, 0);
+    let end = BlockPos::new(10, 72, 3);
+    let mut solid_blocks = Vec::new();
+    for x in 0..=10 {
+        for z in 0..=3 {
+            solid_blocks.push(BlockPos::new(x, 70, z));
+        }
+    }
+    // a step up so there's something to look at
+    for z in 0..=3 {
+        solid_blocks.push(BlockPos::new(10, 71, z));
+    }
+    let mut partial_chunks = PartialChunkStorage::default();
+    let mut simulation =
+        setup_blockposgoal_simulation(&mut partial_chunks, start, end, &solid_blocks);
+
+    let mut computed_cursor = simulation
+        .app
+        .world()
+        .resource::<Events<PathComputedEvent>>()
+        .get_cursor();
+    let mut computed_events = Vec::new();
+    for _ in 0..100 {
+        simulation.tick();
+        let world = simulation.app.world();
+        computed_events.extend(
+            computed_cursor
+                .read(world.resource::<Events<PathComputedEvent>>())
+                .cloned(),
+        );
+    }
+    assert_eq!(BlockPos::from(simulation.position()), end);
+
+    let computed = computed_events.last().expect("no path was computed");
+    let metrics = computed.metrics;
+    assert_eq!(metrics.nodes_expanded, computed.nodes_expanded);
+    assert!(metrics.nodes_expanded > 0);
+    assert!(
+        metrics.nodes_expanded <= metrics.nodes_generated,
+        "{metrics:?}"
+    );
+    assert!(metrics.section_cache_hits > 0, "{metrics:?}");
+    assert!(metrics.section_cache_misses > 0, "{metrics:?}");
+    assert!(metrics.mining_cost_lookups > 0, "{metrics:?}");
+    // the first node is always one of the timed ones
+    assert!(metrics.world_access_time > Duration::ZERO, "{metrics:?}");
+
+    // the bot also keeps the metrics for the last path
+    let world = simulation.app.world();
+    assert_eq!(
+        world.get::<PathfinderMetrics>(simulation.entity),
+        Some(&metrics)
+    );
+}
+
+#[test]
+fn test_reuse_cached_world_after_mining_block() {
+    use parking_lot::RwLock;
+
+    use super::{calculate_path, world::SharedCachedWorld};
+
+    let stone = azalea_registry::Block::Stone.into();
+    let mut partial_chunks = PartialChunkStorage::default();
+    let mut chunks = ChunkStorage::default();
+    for chunk_x in -1..=0 {
+        for chunk_z in -1..=0 {
+            let chunk_pos = ChunkPos::new(chunk_x, chunk_z);
+            partial_chunks.set(&chunk_pos, Some(Chunk::default()), &mut chunks);
+        }
+    }
+    for x in -1..=9 {
+        for z in -5..=5 {
+            chunks.set_block_state(BlockPos::new(x, 69, z), stone);
+        }
+    }
+    // a wall that's too high to jump over, so we have to walk around it
+    for z in -3..=3 {
+        for y in 70..=71 {
+            chunks.set_block_state(BlockPos::new(4, y, z), stone);
+        }
+    }
+    let world_lock = Arc::new(RwLock::new(chunks.into()));
+    let shared_world = SharedCachedWorld::default();
+
+    let calculate = || {
+        calculate_path(
+            test_path_opts(
+                BlockPos::new(0, 70, 0),
+                Arc::new(BlockPosGoal(BlockPos::new(8, 70, 0))),
+                world_lock.clone(),
+            )
+            .shared_world(shared_world.clone()),
+        )
+        .unwrap()
+    };
+    let hole = BlockPos::new(4, 70, 0);
+    let goes_through_hole = |event: &super::PathFoundEvent| {
+        let path = event.path.as_ref().unwrap();
+        path.iter().any(|edge| edge.movement.target == hole)
+    };
+
+    let first = calculate();
+    assert!(!first.is_partial);
+    assert!(!goes_through_hole(&first));
+
+    // mine a hole through the wall
+    for pos in [hole, hole.up(1)] {
+        world_lock
+            .write()
+            .chunks
+            .set_block_state(pos, BlockState::AIR);
+        shared_world.invalidate_block(pos);
+    }
+
+    let second = calculate();
+    assert!(!second.is_partial);
+    assert!(goes_through_hole(&second));
+    // everything except for the section with the hole was still cached from the
+    // first search
+    assert!(first.metrics.section_cache_misses > 1);
+    assert_eq!(second.metrics.section_cache_misses, 1);
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_deprecated_goto_event_methods_set_options() {
+    let event = GotoEvent::new(
+        bevy_ecs::entity::Entity::PLACEHOLDER,
+        BlockPosGoal(BlockPos::new(0, 70, 0)),
+    )
+    .with_successors_fn(moves::default_move)
+    .with_allow_mining(false)
+    .with_retry_on_no_path(false)
+    .with_min_timeout(PathfinderTimeout::Nodes(10))
+    .with_max_timeout(PathfinderTimeout::Nodes(20));
+    assert_eq!(event.options.allow_mining, Some(false));
+    assert_eq!(event.options.retry_on_no_path, Some(false));
+    assert_eq!(
+        event.options.min_timeout,
+        Some(PathfinderTimeout::Nodes(10))
+    );
+    assert_eq!(
+        event.options.max_timeout,
+        Some(PathfinderTimeout::Nodes(20))
+    );
+    // the options that didn't exist yet are left to the bot's settings
+    assert_eq!(event.options.allow_placing, None);
+}
@@ -1,18 +1,46 @@
 use std::{
-    cell::{RefCell, UnsafeCell},
+    cell::{Cell, RefCell, UnsafeCell},
+    collections::HashSet,
+    mem,
+    ops::Range,
     sync::Arc,
 };
 
 use azalea_block::{BlockState, properties};
 use azalea_core::{
     bitset::FastFixedBitSet,
+    data_registry::ResolvableDataRegistry,
     position::{BlockPos, ChunkPos, ChunkSectionBlockPos, ChunkSectionPos},
+    registry_holder::RegistryHolder,
 };
+use azalea_entity::ActiveEffects;
+use azalea_inventory::{ItemStack, Menu, components::Enchantments};
 use azalea_physics::collision::BlockWithShape;
-use azalea_world::{Instance, palette::PalettedContainer};
-use parking_lot::RwLock;
-
-use super::{mining::MiningCache, rel_block_pos::RelBlockPos};
+use azalea_world::{
+    Instance, WorldBorder,
+    palette::{Palette, PalettedContainer},
+};
+use bevy_ecs::component::Component;
+use parking_lot::{MappedMutexGuard, Mutex, MutexGuard, RwLock};
+use rustc_hash::FxHashMap;
+
+use super::{
+    costs::{
+        COBWEB_PENALTY, DAMAGING_SURFACE_PENALTY, DEPTH_STRIDER_SWIMMING_MULTIPLIER,
+        DOLPHINS_GRACE_SWIMMING_MULTIPLIER, DRIPSTONE_STANDING_PENALTY, FALLING_BLOCK_WAIT_TICKS,
+        LEDGE_DROP_THRESHOLD, MAX_FALLING_COLUMN_HEIGHT, MAX_SAFE_FALL_DISTANCE,
+        MAX_SLOW_FALL_DISTANCE, STALACTITE_PENALTY, SWEET_BERRY_BUSH_PENALTY,
+        UNDER_POWDER_SNOW_PENALTY, jump_height,
+    },
+    metrics::PathfinderMetrics,
+    mining::MiningCache,
+    mobs::MobLayer,
+    moves::water::{TURTLE_HELMET_BREATHING_TICKS, classify_water},
+    regions::RegionLayer,
+    rel_block_pos::RelBlockPos,
+    requirements::{Consumable, ConsumableCounts},
+    risk::RiskLayer,
+};
 
 /// Check if a block is an aquatic plant that should be swimmable
 fn is_aquatic_plant(block: azalea_registry::Block) -> bool {
@@ -33,6 +61,8 @@ pub struct CachedWorld {
     origin: BlockPos,
 
     min_y: i32,
+    /// Blocks outside of the world border are treated as unbreakable.
+    world_border: Option<WorldBorder>,
     world_lock: Arc<RwLock<Instance>>,
 
     // we store `PalettedContainer`s instead of `Chunk`s or `Section`s because it doesn't contain
@@ -48,6 +78,373 @@ pub struct CachedWorld {
     cached_blocks: UnsafeCell<CachedSections>,
 
     cached_mining_costs: UnsafeCell<Box<[(RelBlockPos, f32)]>>,
+    /// How many times [`Self::cost_for_breaking_block`] was called, for the
+    /// [`PathfinderMetrics`].
+    mining_cost_lookups: Cell<usize>,
+
+    /// Whether there's breathable air near each position that's been checked,
+    /// keyed by the position and how far we searched.
+    cached_air_access: RefCell<FxHashMap<(RelBlockPos, i32), bool>>,
+
+    equipment: PathEquipment,
+    risk: Option<RiskLayer>,
+    regions: Option<RegionLayer>,
+    mobs: Option<MobLayer>,
+    /// See [`GotoOptions::avoid_damage`].
+    ///
+    /// [`GotoOptions::avoid_damage`]: crate::pathfinder::GotoOptions::avoid_damage
+    avoid_damage: bool,
+}
+
+/// A [`CachedWorld`] that's kept between the path calculations for the same
+/// goto, so the chunks that were already looked at don't have to be copied and
+/// checked again whenever the path is recalculated.
+///
+/// Blocks that change while it's kept have to be passed to
+/// [`Self::invalidate_block`]. The pathfinder does this for the blocks that we
+/// mine and for the block updates that the server sends us.
+#[derive(Component, Clone, Default)]
+pub struct SharedCachedWorld {
+    world: Arc<Mutex<Option<CachedWorld>>>,
+    /// The blocks that changed since the world was last used. A search that's
+    /// running has the world locked, so these are applied when the next one
+    /// starts.
+    invalidated: Arc<Mutex<Vec<BlockPos>>>,
+}
+impl SharedCachedWorld {
+    /// Mark the block as changed, so the next search looks at it again.
+    pub fn invalidate_block(&self, pos: BlockPos) {
+        self.invalidated.lock().push(pos);
+    }
+
+    /// Get the world to use for a new search, or `None` if another search is
+    /// still using it (in which case a new [`CachedWorld`] should be made).
+    ///
+    /// The world has the blocks that changed since it was last used
+    /// invalidated, and it's replaced if it was for a different instance. It
+    /// still has to be set up with [`CachedWorld::reuse_for_search`].
+    pub fn try_lock(
+        &self,
+        world_lock: &Arc<RwLock<Instance>>,
+        origin: BlockPos,
+    ) -> Option<MappedMutexGuard<'_, CachedWorld>> {
+        let mut world = self.world.try_lock()?;
+        let invalidated = mem::take(&mut *self.invalidated.lock());
+        if world
+            .as_ref()
+            .is_some_and(|world| !Arc::ptr_eq(&world.world_lock, world_lock))
+        {
+            *world = None;
+        }
+        Some(MutexGuard::map(world, |world| {
+            let world = world.get_or_insert_with(|| CachedWorld::new(world_lock.clone(), origin));
+            let sections = invalidated
+                .into_iter()
+                .map(ChunkSectionPos::from)
+                .collect::<HashSet<_>>();
+            // the position caches are cleared by `reuse_for_search` anyways
+            for section_pos in sections {
+                world.forget_section(section_pos);
+            }
+            world
+        }))
+    }
+}
+
+/// The parts of what the bot is wearing or holding that affect which blocks it
+/// can stand on and which moves it can do.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct PathEquipment {
+    /// Leather boots let us walk on top of powder snow.
+    pub has_leather_boots: bool,
+    /// A turtle shell gives us a few seconds of water breathing whenever we
+    /// go underwater.
+    pub has_turtle_helmet: bool,
+    /// The level of Respiration on our helmet, which makes us run out of air
+    /// more slowly.
+    pub respiration: u32,
+    /// The level of Depth Strider on our boots, which makes us swim faster.
+    pub depth_strider: u32,
+    /// The level of Frost Walker on our boots, which lets us walk on top of
+    /// still water.
+    pub frost_walker: u32,
+    /// The level of Soul Speed on our boots, which makes us walk faster on
+    /// soul sand and soul soil instead of slower.
+    pub soul_speed: u32,
+    /// The level of Feather Falling on our boots, which makes us take less
+    /// fall damage.
+    pub feather_falling: u32,
+    /// The most fall damage that we're willing to take from a single fall, in
+    /// health points. See [`GotoOptions::max_fall_damage`].
+    ///
+    /// [`GotoOptions::max_fall_damage`]: crate::pathfinder::GotoOptions::max_fall_damage
+    pub fall_damage_budget: u32,
+    /// The items in our inventory that moves can use up.
+    pub consumables: ConsumableCounts,
+    /// The status effects that change how high we can jump and how far we can
+    /// fall.
+    pub effects: PathEffects,
+}
+impl PathEquipment {
+    pub fn from_menu(menu: &Menu) -> Self {
+        let Menu::Player(player) = menu else {
+            return Self::default();
+        };
+        // the armor slots are ordered from head to feet
+        let head = &player.armor[0];
+        let feet = &player.armor[3];
+        Self {
+            has_leather_boots: feet.kind() == azalea_registry::Item::LeatherBoots,
+            has_turtle_helmet: head.kind() == azalea_registry::Item::TurtleHelmet,
+            // these need the registries, see `with_enchantments`
+            respiration: 0,
+            depth_strider: 0,
+            frost_walker: 0,
+            soul_speed: 0,
+            feather_falling: 0,
+            fall_damage_budget: 0,
+            consumables: ConsumableCounts::from_menu(menu),
+            effects: PathEffects::default(),
+        }
+    }
+
+    /// Count our throwaway blocks as the given items in our hotbar. See
+    /// [`ConsumableCounts::with_throwaway_blocks`].
+    pub fn with_throwaway_blocks(
+        mut self,
+        menu: &Menu,
+        throwaway_blocks: &[azalea_registry::Item],
+    ) -> Self {
+        self.consumables = self
+            .consumables
+            .with_throwaway_blocks(menu, throwaway_blocks);
+        self
+    }
+
+    pub fn with_effects(mut self, effects: PathEffects) -> Self {
+        self.effects = effects;
+        self
+    }
+
+    /// Let falls deal up to `max_fall_damage` of our current health in damage.
+    /// See [`GotoOptions::max_fall_damage`].
+    ///
+    /// [`GotoOptions::max_fall_damage`]: crate::pathfinder::GotoOptions::max_fall_damage
+    pub fn with_fall_damage_budget(mut self, health: f32, max_fall_damage: f32) -> Self {
+        let budget = (health * max_fall_damage).floor().max(0.) as u32;
+        // taking as much damage as we have health would kill us
+        self.fall_damage_budget = budget.min((health.ceil().max(0.) as u32).saturating_sub(1));
+        self
+    }
+
+    /// How much damage we'd take from falling this many blocks onto the given
+    /// block.
+    pub fn fall_damage(self, fall_distance: u32, landing_block: BlockState) -> f32 {
+        if self.effects.slow_falling {
+            return 0.;
+        }
+        let damage = (fall_distance.saturating_sub(MAX_SAFE_FALL_DISTANCE) as f32
+            * fall_damage_multiplier(landing_block))
+        .ceil();
+        // feather falling is 3 protection points per level, and each point takes off
+        // 4% of the damage up to a maximum of 80%
+        let protection = (self.feather_falling * 3).min(20);
+        damage * (1. - protection as f32 / 25.)
+    }
+
+    /// Whether we're willing to fall this many blocks onto the given block.
+    pub fn is_fall_acceptable(self, fall_distance: u32, landing_block: BlockState) -> bool {
+        if self.effects.slow_falling {
+            // slow falling stops fall damage, but it could run out during a long fall
+            return fall_distance <= MAX_SLOW_FALL_DISTANCE;
+        }
+        self.fall_damage(fall_distance, landing_block) <= self.fall_damage_budget as f32
+    }
+
+    /// The furthest that we're willing to fall onto a normal block.
+    pub fn max_fall_distance(self) -> u32 {
+        if self.effects.slow_falling {
+            return MAX_SLOW_FALL_DISTANCE;
+        }
+        let mut distance = MAX_SAFE_FALL_DISTANCE;
+        while distance < MAX_SLOW_FALL_DISTANCE
+            && self.is_fall_acceptable(distance + 1, azalea_registry::Block::Stone.into())
+        {
+            distance += 1;
+        }
+        distance
+    }
+
+    /// Whether standing on top of this block would hurt us.
+    ///
+    /// Magma blocks don't hurt players that are sneaking, but the pathfinder
+    /// only sneaks when it's close to a ledge, so we can't count on that.
+    pub fn is_hurt_by_standing_on(self, block: BlockState) -> bool {
+        match azalea_registry::Block::from(block) {
+            azalea_registry::Block::MagmaBlock => {
+                self.frost_walker == 0 && !self.effects.fire_resistance
+            }
+            azalea_registry::Block::Campfire | azalea_registry::Block::SoulCampfire => {
+                block.property::<properties::Lit>().unwrap_or_default()
+                    && !self.effects.fire_resistance
+            }
+            _ => false,
+        }
+    }
+
+    /// Read the enchantments on our armor. Enchantments are a data registry,
+    /// so this needs the registries that the server sent us.
+    pub fn with_enchantments(mut self, menu: &Menu, registries: &RegistryHolder) -> Self {
+        if let Menu::Player(player) = menu {
+            self.respiration = enchantment_level(&player.armor[0], "respiration", registries);
+            self.depth_strider = enchantment_level(&player.armor[3], "depth_strider", registries);
+            self.frost_walker = enchantment_level(&player.armor[3], "frost_walker", registries);
+            self.soul_speed = enchantment_level(&player.armor[3], "soul_speed", registries);
+            self.feather_falling =
+                enchantment_level(&player.armor[3], "feather_falling", registries);
+        }
+        self
+    }
+
+    /// What swimming costs get multiplied by because of Depth Strider and
+    /// Dolphin's Grace.
+    pub fn swimming_cost_multiplier(self) -> f32 {
+        // depth strider only works half as well when we're not touching the ground,
+        // which is most of the time when we're swimming
+        let depth_strider = self.depth_strider.min(3) as f32 / 3. * 0.5;
+        let multiplier = 1. + (DEPTH_STRIDER_SWIMMING_MULTIPLIER - 1.) * depth_strider;
+        if self.effects.dolphins_grace {
+            multiplier.min(DOLPHINS_GRACE_SWIMMING_MULTIPLIER)
+        } else {
+            multiplier
+        }
+    }
+
+    /// Pretend that we don't have any of the given consumables, so moves that
+    /// use them aren't considered.
+    pub fn without(mut self, excluded: &[Consumable]) -> Self {
+        for &consumable in excluded {
+            self.consumables.set(consumable, 0);
+        }
+        self
+    }
+}
+
+/// The level of the enchantment with the given name on the item, or 0 if it
+/// doesn't have it.
+fn enchantment_level(item: &ItemStack, name: &str, registries: &RegistryHolder) -> u32 {
+    let Some(enchantments) = item.get_component::<Enchantments>() else {
+        return 0;
+    };
+    enchantments
+        .levels
+        .iter()
+        .find(|(enchantment, _)| {
+            enchantment
+                .resolve_name(registries)
+                .is_some_and(|resolved| resolved.path == name)
+        })
+        .map_or(0, |(_, level)| *level)
+}
+
+/// The status effects on the bot that the pathfinder takes into account.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct PathEffects {
+    /// The level of Jump Boost that we have, where 0 is level I.
+    pub jump_boost: Option<u32>,
+    /// Slow Falling makes us take no fall damage, so we can drop down from
+    /// much higher.
+    pub slow_falling: bool,
+    /// Water Breathing and Conduit Power make us never run out of air.
+    pub water_breathing: bool,
+    /// Dolphin's Grace makes us swim much faster.
+    pub dolphins_grace: bool,
+    /// Fire Resistance stops magma blocks and campfires from hurting us.
+    pub fire_resistance: bool,
+}
+impl PathEffects {
+    pub fn from_active_effects(active_effects: &ActiveEffects) -> Self {
+        Self {
+            jump_boost: active_effects.get_level(azalea_registry::MobEffect::JumpBoost),
+            slow_falling: active_effects.has(azalea_registry::MobEffect::SlowFalling),
+            // a turtle shell keeps giving us a short Water Breathing effect while our head is
+            // out of the water, but it runs out partway into a dive
+            water_breathing: active_effects
+                .0
+                .get(&azalea_registry::MobEffect::WaterBreathing)
+                .is_some_and(|effect| effect.duration_ticks > TURTLE_HELMET_BREATHING_TICKS)
+                || active_effects.has(azalea_registry::MobEffect::ConduitPower),
+            dolphins_grace: active_effects.has(azalea_registry::MobEffect::DolphinsGrace),
+            fire_resistance: active_effects.has(azalea_registry::MobEffect::FireResistance),
+        }
+    }
+
+    /// The number of blocks that we can jump up onto.
+    pub fn max_ascend_height(self) -> i32 {
+        jump_height(self.jump_boost).floor() as i32
+    }
+
+    /// The widest gap that we can jump across while sprinting.
+    pub fn max_parkour_gap(self) -> i32 {
+        match self.jump_boost {
+            None => 3,
+            // jump boost makes us stay in the air for longer, but the extra distance is
+            // pretty small, so be conservative
+            Some(amplifier) => 3 + (amplifier as i32 + 1) / 2,
+        }
+    }
+}
+
+/// What the fall damage from landing on this block gets multiplied by.
+///
+/// Pointed dripstone makes fall damage worse, but that's handled separately
+/// since it depends on which way it's pointing.
+pub fn fall_damage_multiplier(landing_block: BlockState) -> f32 {
+    let registry_block = azalea_registry::Block::from(landing_block);
+    match registry_block {
+        // slime blocks bounce us back up instead
+        azalea_registry::Block::SlimeBlock => 0.,
+        azalea_registry::Block::HayBlock | azalea_registry::Block::HoneyBlock => 0.2,
+        _ if azalea_registry::tags::blocks::BEDS.contains(&registry_block) => 0.5,
+        _ => 1.,
+    }
+}
+
+/// Whether we can stand on top of a block.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BlockSupport {
+    /// We can always stand on this block.
+    Standable,
+    /// We can only stand on this block if the condition is met.
+    ConditionallyStandable(SupportCondition),
+    /// We can't stand on this block.
+    NotStandable,
+}
+impl BlockSupport {
+    pub fn is_standable(self, equipment: PathEquipment) -> bool {
+        match self {
+            BlockSupport::Standable => true,
+            BlockSupport::ConditionallyStandable(condition) => condition.is_met(equipment),
+            BlockSupport::NotStandable => false,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SupportCondition {
+    /// Scaffolding can be stood on as long as we're not sneaking. The
+    /// pathfinder only sneaks near ledges, and never while it's on scaffolding.
+    NotSneaking,
+    /// Powder snow can only be stood on while wearing leather boots.
+    LeatherBoots,
+}
+impl SupportCondition {
+    pub fn is_met(self, equipment: PathEquipment) -> bool {
+        match self {
+            SupportCondition::NotSneaking => true,
+            SupportCondition::LeatherBoots => equipment.has_leather_boots,
+        }
+    }
 }
 
 #[derive(Default)]
@@ -55,6 +452,10 @@ pub struct CachedSections {
     pub last_index: usize,
     pub second_last_index: usize,
     pub sections: Vec<CachedSection>,
+    /// How many times `get_mut` found the section.
+    pub hits: usize,
+    /// How many times `get_mut` didn't find the section.
+    pub misses: usize,
 }
 
 impl CachedSections {
@@ -62,10 +463,12 @@ impl CachedSections {
     pub fn get_mut(&mut self, pos: ChunkSectionPos) -> Option<&mut CachedSection> {
         if let Some(last_item) = self.sections.get(self.last_index) {
             if last_item.pos == pos {
+                self.hits += 1;
                 return Some(&mut self.sections[self.last_index]);
             } else if let Some(second_last_item) = self.sections.get(self.second_last_index)
                 && second_last_item.pos == pos
             {
+                self.hits += 1;
                 return Some(&mut self.sections[self.second_last_index]);
             }
         }
@@ -78,8 +481,10 @@ impl CachedSections {
         if let Some(index) = index {
             self.second_last_index = self.last_index;
             self.last_index = index;
+            self.hits += 1;
             return Some(&mut self.sections[index]);
         }
+        self.misses += 1;
         None
     }
 
@@ -93,6 +498,14 @@ impl CachedSections {
             .unwrap_or_else(|e| e);
         self.sections.insert(index, section);
     }
+
+    /// Forget the section, so it's copied from the world again the next time
+    /// that it's needed.
+    pub fn remove(&mut self, pos: ChunkSectionPos) {
+        if let Ok(index) = self.sections.binary_search_by(|s| s.pos.cmp(&pos)) {
+            self.sections.remove(index);
+        }
+    }
 }
 
 pub struct CachedSection {
@@ -103,14 +516,86 @@ pub struct CachedSection {
     pub solid_bitset: FastFixedBitSet<4096>,
     /// Blocks that we can stand on but might not be able to parkour from.
     pub standable_bitset: FastFixedBitSet<4096>,
+    /// Blocks that have water in them, including waterlogged blocks and
+    /// aquatic plants. These are checked for every position that we might
+    /// stand at, so the water moves can tell whether they need to look at the
+    /// block state.
+    pub water_bitset: FastFixedBitSet<4096>,
+    /// Whether the chunk that the section is in was loaded. If it wasn't, then
+    /// all of the bitsets are empty and we don't know what's actually there.
+    pub is_loaded: bool,
+}
+impl CachedSection {
+    fn new(pos: ChunkSectionPos) -> Self {
+        Self {
+            pos,
+            passable_bitset: FastFixedBitSet::new(),
+            solid_bitset: FastFixedBitSet::new(),
+            standable_bitset: FastFixedBitSet::new(),
+            water_bitset: FastFixedBitSet::new(),
+            is_loaded: true,
+        }
+    }
+
+    /// A section in a chunk that isn't loaded, which we treat like it's solid
+    /// except for [`CachedWorld::is_block_unknown`].
+    fn unloaded(pos: ChunkSectionPos) -> Self {
+        Self {
+            is_loaded: false,
+            ..Self::new(pos)
+        }
+    }
+
+    fn set(&mut self, index: usize, flags: BlockFlags) {
+        if flags.passable {
+            self.passable_bitset.set(index);
+        }
+        if flags.solid {
+            self.solid_bitset.set(index);
+        }
+        if flags.standable {
+            self.standable_bitset.set(index);
+        }
+        if flags.water {
+            self.water_bitset.set(index);
+        }
+    }
+
+    fn set_all(&mut self, flags: BlockFlags) {
+        if flags.passable {
+            self.passable_bitset.set_all();
+        }
+        if flags.solid {
+            self.solid_bitset.set_all();
+        }
+        if flags.standable {
+            self.standable_bitset.set_all();
+        }
+        if flags.water {
+            self.water_bitset.set_all();
+        }
+    }
+}
+
+/// Which of the [`CachedSection`] bitsets a block state is in.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+struct BlockFlags {
+    passable: bool,
+    solid: bool,
+    standable: bool,
+    water: bool,
 }
 
 impl CachedWorld {
     pub fn new(world_lock: Arc<RwLock<Instance>>, origin: BlockPos) -> Self {
-        let min_y = world_lock.read().chunks.min_y;
+        let (min_y, world_border) = {
+            let world = world_lock.read();
+            (world.chunks.min_y, world.world_border)
+        };
         Self {
             origin,
             min_y,
+            world_border,
             world_lock,
             cached_chunks: Default::default(),
             last_chunk_cache_index: Default::default(),
@@ -120,9 +605,277 @@ impl CachedWorld {
                 vec![(RelBlockPos::new(i16::MAX, i32::MAX, i16::MAX), 0.); 2usize.pow(20)]
                     .into_boxed_slice(),
             ),
+            mining_cost_lookups: Cell::new(0),
+            cached_air_access: Default::default(),
+            equipment: PathEquipment::default(),
+            risk: None,
+            regions: None,
+            mobs: None,
+            avoid_damage: false,
         }
     }
 
+    /// Set the equipment that's used for checking whether conditionally
+    /// standable blocks (like powder snow) can be stood on.
+    ///
+    /// This must be called before any blocks are checked, since the results are
+    /// cached.
+    pub fn with_equipment(mut self, equipment: PathEquipment) -> Self {
+        self.equipment = equipment;
+        self
+    }
+
+    pub fn equipment(&self) -> PathEquipment {
+        self.equipment
+    }
+
+    /// Set the [`RiskLayer`] that's used for adding extra costs to dark or
+    /// dangerous positions.
+    pub fn with_risk(mut self, risk: Option<RiskLayer>) -> Self {
+        self.risk = risk;
+        self
+    }
+
+    /// Set the [`RegionLayer`] that's used for keeping us out of (or inside
+    /// of) parts of the world.
+    pub fn with_regions(mut self, regions: Option<RegionLayer>) -> Self {
+        self.regions = regions.filter(|regions| !regions.is_empty());
+        self
+    }
+
+    /// Set the [`MobLayer`] that's used for keeping us away from hostile mobs.
+    pub fn with_mobs(mut self, mobs: Option<MobLayer>) -> Self {
+        self.mobs = mobs.filter(|mobs| !mobs.is_empty());
+        self
+    }
+
+    /// Don't stand on blocks that would hurt us at all, instead of just
+    /// penalizing them.
+    ///
+    /// Like [`Self::with_equipment`], this must be called before any blocks are
+    /// checked.
+    pub fn with_avoid_damage(mut self, avoid_damage: bool) -> Self {
+        self.avoid_damage = avoid_damage;
+        self
+    }
+
+    /// Get a world that was used for an earlier search ready for another one
+    /// from `origin`, with new settings.
+    ///
+    /// The blocks that were already cached are kept unless the new settings
+    /// change which of them we can stand on. Everything else that was cached
+    /// depends on where the last search started or on its settings, so it's
+    /// cleared.
+    pub fn reuse_for_search(
+        &mut self,
+        origin: BlockPos,
+        equipment: PathEquipment,
+        avoid_damage: bool,
+        risk: Option<RiskLayer>,
+        regions: Option<RegionLayer>,
+        mobs: Option<MobLayer>,
+    ) {
+        // the parts of the settings that the standable bitsets depend on
+        let standable_key = |equipment: PathEquipment, avoid_damage: bool| {
+            (
+                equipment.has_leather_boots,
+                avoid_damage,
+                equipment.frost_walker > 0,
+                equipment.effects.fire_resistance,
+            )
+        };
+        if standable_key(equipment, avoid_damage)
+            != standable_key(self.equipment, self.avoid_damage)
+        {
+            *self.cached_blocks.get_mut() = CachedSections::default();
+        } else {
+            // chunks might've loaded since the last search
+            self.cached_blocks
+                .get_mut()
+                .sections
+                .retain(|section| section.is_loaded);
+        }
+
+        self.origin = origin;
+        self.equipment = equipment;
+        self.avoid_damage = avoid_damage;
+        self.risk = risk;
+        self.regions = regions.filter(|regions| !regions.is_empty());
+        self.mobs = mobs.filter(|mobs| !mobs.is_empty());
+        self.clear_position_caches();
+
+        // the metrics are only for one search
+        let cached_blocks = self.cached_blocks.get_mut();
+        cached_blocks.hits = 0;
+        cached_blocks.misses = 0;
+        self.mining_cost_lookups.set(0);
+    }
+
+    /// Forget everything that we cached about the block, because it changed.
+    pub fn invalidate_block(&mut self, pos: BlockPos) {
+        self.invalidate_section(ChunkSectionPos::from(pos));
+    }
+
+    /// Forget everything that we cached about the blocks in the section,
+    /// because some of them changed.
+    ///
+    /// The costs that were cached for each position can depend on the blocks
+    /// around it too, so those are all cleared.
+    pub fn invalidate_section(&mut self, pos: ChunkSectionPos) {
+        self.forget_section(pos);
+        self.clear_position_caches();
+    }
+
+    /// Remove the section from the caches that are keyed by where blocks are
+    /// in the world.
+    fn forget_section(&mut self, pos: ChunkSectionPos) {
+        self.cached_blocks.get_mut().remove(pos);
+        // the whole chunk is copied at once, so the rest of it is copied again too
+        let chunk_pos = ChunkPos::from(pos);
+        self.cached_chunks
+            .get_mut()
+            .retain(|(cached_pos, _)| *cached_pos != chunk_pos);
+        *self.last_chunk_cache_index.get_mut() = None;
+    }
+
+    /// Clear the caches that are keyed by [`RelBlockPos`], which depend on the
+    /// origin and on the blocks around each position.
+    fn clear_position_caches(&mut self) {
+        self.cached_mining_costs
+            .get_mut()
+            .fill((RelBlockPos::new(i16::MAX, i32::MAX, i16::MAX), 0.));
+        self.cached_air_access.get_mut().clear();
+    }
+
+    /// The extra cost for standing at this position if the block below it
+    /// would hurt us, like a magma block.
+    ///
+    /// If we're avoiding damage then these blocks aren't standable at all, so
+    /// this is only checked when they're allowed.
+    pub fn damaging_surface_cost(&self, pos: RelBlockPos) -> f32 {
+        if self
+            .equipment
+            .is_hurt_by_standing_on(self.get_block_state(pos.down(1)))
+        {
+            DAMAGING_SURFACE_PENALTY
+        } else {
+            0.
+        }
+    }
+
+    /// The extra cost from the [`RiskLayer`] for standing at this position, or
+    /// 0 if there isn't one.
+    pub fn risk_cost(&self, pos: RelBlockPos) -> f32 {
+        let Some(risk) = &self.risk else {
+            return 0.;
+        };
+        risk.cost(self, pos.apply(self.origin))
+    }
+
+    /// The extra cost from the [`RegionLayer`] for standing at this position,
+    /// or 0 if there isn't one.
+    ///
+    /// This is infinite for positions that we can't go to at all.
+    pub fn region_cost(&self, pos: RelBlockPos) -> f32 {
+        let Some(regions) = &self.regions else {
+            return 0.;
+        };
+        regions.cost(pos.apply(self.origin))
+    }
+
+    pub fn has_regions(&self) -> bool {
+        self.regions.is_some()
+    }
+
+    /// The extra cost from the [`MobLayer`] for standing at this position, or 0
+    /// if there isn't one.
+    pub fn mob_cost(&self, pos: RelBlockPos) -> f32 {
+        let Some(mobs) = &self.mobs else {
+            return 0.;
+        };
+        mobs.cost(pos.apply(self.origin))
+    }
+
+    pub fn has_mobs(&self) -> bool {
+        self.mobs.is_some()
+    }
+
+    /// Whether there's air that we can breathe within `max_distance` blocks of
+    /// `pos`, using `find` to search for it if we haven't already.
+    ///
+    /// The same positions get checked by every neighbor that we swim from, so
+    /// this is cached for the whole path calculation.
+    pub fn air_access(
+        &self,
+        pos: RelBlockPos,
+        max_distance: i32,
+        find: impl FnOnce() -> bool,
+    ) -> bool {
+        if let Some(&has_air) = self.cached_air_access.borrow().get(&(pos, max_distance)) {
+            return has_air;
+        }
+        let has_air = find();
+        self.cached_air_access
+            .borrow_mut()
+            .insert((pos, max_distance), has_air);
+        has_air
+    }
+
+    /// Get the y coordinate of the highest block in the column that we can't
+    /// pass through, or the bottom of the world if there isn't one.
+    pub fn highest_covering_block(&self, x: i32, z: i32) -> i32 {
+        self.y_range()
+            .rev()
+            .find(|&y| !self.is_block_pos_passable(BlockPos::new(x, y, z)))
+            .unwrap_or(self.min_y - 1)
+    }
+
+    /// The y coordinates of the blocks in the world, from bottom to top.
+    pub fn y_range(&self) -> Range<i32> {
+        let height = self.world_lock.read().chunks.height as i32;
+        self.min_y..self.min_y + height
+    }
+
+    /// Whether the chunk is loaded, so we know what's in it.
+    pub fn is_chunk_loaded(&self, pos: ChunkPos) -> bool {
+        self.world_lock.read().chunks.get(&pos).is_some()
+    }
+
+    /// Call the function with the index and state of every block in the
+    /// section, if it's loaded.
+    pub fn for_each_block_in_section(
+        &self,
+        section_pos: ChunkSectionPos,
+        mut f: impl FnMut(usize, BlockState),
+    ) {
+        self.with_section(section_pos, |section| {
+            for index in 0..4096 {
+                f(index, section.get_at_index(index));
+            }
+        });
+    }
+
+    /// Fill in the parts of the [`PathfinderMetrics`] that are about how we
+    /// looked at the world.
+    pub fn record_metrics(&self, metrics: &mut PathfinderMetrics) {
+        // SAFETY: we're only accessing this from one thread
+        let cached_blocks = unsafe { &*self.cached_blocks.get() };
+        metrics.section_cache_hits = cached_blocks.hits;
+        metrics.section_cache_misses = cached_blocks.misses;
+        metrics.mining_cost_lookups = self.mining_cost_lookups.get();
+    }
+
+    /// The positions of the sections that have been looked at so far.
+    pub fn cached_section_positions(&self) -> Vec<ChunkSectionPos> {
+        // SAFETY: we're only accessing this from one thread
+        let cached_blocks = unsafe { &*self.cached_blocks.get() };
+        cached_blocks
+            .sections
+            .iter()
+            .map(|section| section.pos)
+            .collect()
+    }
+
     // ```
     // fn get_block_state(&self, pos: BlockPos) -> Option<BlockState> {
     //     self.with_section(ChunkSectionPos::from(pos), |section| {
@@ -210,29 +963,50 @@ impl CachedWorld {
     }
 
     fn calculate_bitsets_for_section(&self, section_pos: ChunkSectionPos) -> Option<CachedSection> {
-        self.with_section(section_pos, |section| {
-            let mut passable_bitset = FastFixedBitSet::<4096>::new();
-            let mut solid_bitset = FastFixedBitSet::<4096>::new();
-            let mut standable_bitset = FastFixedBitSet::<4096>::new();
-            for i in 0..4096 {
-                let block_state = section.get_at_index(i);
-                if is_block_state_passable(block_state) {
-                    passable_bitset.set(i);
+        let cached = self.with_section(section_pos, |section| {
+            let mut cached = CachedSection::new(section_pos);
+            // sections usually only have a few different block states, so we check each
+            // one once and then look up which one every block is
+            match &section.palette {
+                Palette::SingleValue(block_state) => {
+                    cached.set_all(self.block_flags(*block_state));
                 }
-                if is_block_state_solid(block_state) {
-                    solid_bitset.set(i);
+                Palette::Linear(block_states) | Palette::Hashmap(block_states) => {
+                    let flags = block_states
+                        .iter()
+                        .map(|&block_state| self.block_flags(block_state))
+                        .collect::<Vec<_>>();
+                    // ids that aren't in the palette are treated as air, like in `get_at_index`
+                    let default_flags = self.block_flags(BlockState::default());
+                    for i in 0..4096 {
+                        let id = section.storage.get(i) as usize;
+                        cached.set(i, flags.get(id).copied().unwrap_or(default_flags));
+                    }
                 }
-                if is_block_state_standable(block_state) {
-                    standable_bitset.set(i);
+                Palette::Global => {
+                    for i in 0..4096 {
+                        cached.set(i, self.block_flags(section.get_at_index(i)));
+                    }
                 }
             }
-            CachedSection {
-                pos: section_pos,
-                passable_bitset,
-                solid_bitset,
-                standable_bitset,
-            }
-        })
+            cached
+        });
+        if cached.is_none() && self.y_range().contains(&(section_pos.y * 16)) {
+            // the section is in the world but its chunk isn't loaded
+            return Some(CachedSection::unloaded(section_pos));
+        }
+        cached
+    }
+
+    /// Check which of the bitsets the block state should be in.
+    fn block_flags(&self, block_state: BlockState) -> BlockFlags {
+        BlockFlags {
+            passable: is_block_state_passable(block_state),
+            solid: is_block_state_solid(block_state),
+            standable: block_support(block_state).is_standable(self.equipment)
+                && !(self.avoid_damage && self.equipment.is_hurt_by_standing_on(block_state)),
+            water: classify_water(block_state).is_some(),
+        }
     }
 
     pub fn is_block_passable(&self, pos: RelBlockPos) -> bool {
@@ -240,6 +1014,10 @@ impl CachedWorld {
     }
 
     fn is_block_pos_passable(&self, pos: BlockPos) -> bool {
+        if !self.is_inside_world_border(pos) {
+            return false;
+        }
+
         let (section_pos, section_block_pos) =
             (ChunkSectionPos::from(pos), ChunkSectionBlockPos::from(pos));
         let index = u16::from(section_block_pos) as usize;
@@ -257,6 +1035,11 @@ impl CachedWorld {
         passable
     }
 
+    fn is_inside_world_border(&self, pos: BlockPos) -> bool {
+        self.world_border
+            .is_none_or(|world_border| world_border.contains_block(pos))
+    }
+
     /// Get the block state at the given position. This is relatively slow, so
     /// you should avoid it whenever possible.
     pub fn get_block_state(&self, pos: RelBlockPos) -> BlockState {
@@ -314,9 +1097,65 @@ impl CachedWorld {
         solid
     }
 
+    /// Whether the block is in a chunk that isn't loaded, so we don't know what
+    /// it is.
+    ///
+    /// The other checks treat these blocks like they're solid, but the
+    /// [`unknown`] moves can still go through them.
+    ///
+    /// [`unknown`]: super::moves::unknown
+    pub fn is_block_unknown(&self, pos: RelBlockPos) -> bool {
+        self.is_block_pos_unknown(pos.apply(self.origin))
+    }
+    fn is_block_pos_unknown(&self, pos: BlockPos) -> bool {
+        // nothing outside of the world border is ever going to load
+        if !self.is_inside_world_border(pos) {
+            return false;
+        }
+
+        let section_pos = ChunkSectionPos::from(pos);
+        // SAFETY: we're only accessing this from one thread
+        let cached_blocks = unsafe { &mut *self.cached_blocks.get() };
+        if let Some(cached) = cached_blocks.get_mut(section_pos) {
+            return !cached.is_loaded;
+        }
+
+        let Some(cached) = self.calculate_bitsets_for_section(section_pos) else {
+            return false;
+        };
+        let is_unknown = !cached.is_loaded;
+        cached_blocks.insert(cached);
+        is_unknown
+    }
+
+    /// Whether the block has water in it, which is any block that
+    /// [`classify_water`] returns something for.
+    pub fn is_block_water(&self, pos: RelBlockPos) -> bool {
+        self.is_block_pos_water(pos.apply(self.origin))
+    }
+    fn is_block_pos_water(&self, pos: BlockPos) -> bool {
+        let (section_pos, section_block_pos) =
+            (ChunkSectionPos::from(pos), ChunkSectionBlockPos::from(pos));
+        let index = u16::from(section_block_pos) as usize;
+        // SAFETY: we're only accessing this from one thread
+        let cached_blocks = unsafe { &mut *self.cached_blocks.get() };
+        if let Some(cached) = cached_blocks.get_mut(section_pos) {
+            return cached.water_bitset.index(index);
+        }
+
+        let Some(cached) = self.calculate_bitsets_for_section(section_pos) else {
+            return false;
+        };
+        let water = cached.water_bitset.index(index);
+        cached_blocks.insert(cached);
+        water
+    }
+
     /// Returns how much it costs to break this block. Returns 0 if the block is
     /// already passable.
     pub fn cost_for_breaking_block(&self, pos: RelBlockPos, mining_cache: &MiningCache) -> f32 {
+        self.mining_cost_lookups
+            .set(self.mining_cost_lookups.get() + 1);
         // SAFETY: pathfinding is single-threaded
         let cached_mining_costs = unsafe { &mut *self.cached_mining_costs.get() };
         // 20 bits total:
@@ -345,18 +1184,26 @@ impl CachedWorld {
         mining_cache: &MiningCache,
     ) -> f32 {
         if self.is_block_passable(pos) {
-            // if the block is passable then it doesn't need to be broken
-            return 0.;
+            // if the block is passable then it doesn't need to be broken, but some
+            // passable blocks slow us down so much that it's faster to break them anyways
+            let block_state = self.get_block_state(pos);
+            let penalty = passable_block_penalty(block_state);
+            if penalty == 0. {
+                return 0.;
+            }
+            return f32::min(penalty, mining_cache.cost_for(block_state));
         }
 
         let pos = pos.apply(self.origin);
+        if !self.is_inside_world_border(pos) {
+            return f32::INFINITY;
+        }
 
         let (section_pos, section_block_pos) =
             (ChunkSectionPos::from(pos), ChunkSectionBlockPos::from(pos));
 
         // we use this as an optimization to avoid getting the section again if the
         // block is in the same section
-        let up_is_in_same_section = section_block_pos.y != 15;
         let north_is_in_same_section = section_block_pos.z != 0;
         let east_is_in_same_section = section_block_pos.x != 15;
         let south_is_in_same_section = section_block_pos.z != 15;
@@ -371,14 +1218,6 @@ impl CachedWorld {
                 return f32::INFINITY;
             }
 
-            // if there's a falling block or liquid above this block, abort
-            if up_is_in_same_section {
-                let up_block = section.get_at_index(u16::from(section_block_pos.up(1)) as usize);
-                if mining_cache.is_liquid(up_block) || mining_cache.is_falling_block(up_block) {
-                    return f32::INFINITY;
-                }
-            }
-
             // if there's a liquid to the north of this block, abort
             if north_is_in_same_section {
                 let north_block =
@@ -444,13 +1283,6 @@ impl CachedWorld {
         };
 
         // check the adjacent blocks that weren't in the same section
-        if !up_is_in_same_section
-            && check_should_avoid_this_block(pos.up(1), &|b| {
-                mining_cache.is_liquid(b) || mining_cache.is_falling_block(b)
-            })
-        {
-            return f32::INFINITY;
-        }
         if !north_is_in_same_section
             && check_should_avoid_this_block(pos.north(1), &|b| mining_cache.is_liquid(b))
         {
@@ -472,9 +1304,37 @@ impl CachedWorld {
             return f32::INFINITY;
         }
 
+        // if there's a liquid above this block, abort. sand or gravel above it will
+        // fall into the gap, so we'll have to mine all of that too.
+        let mining_cost = mining_cost + self.cost_for_falling_column(pos.up(1), mining_cache);
+
+        // breaking the block that a stalactite is hanging from makes it fall
+        if is_block_state_stalactite(self.get_block_state_at_pos(pos.down(1))) {
+            return mining_cost + STALACTITE_PENALTY;
+        }
+
         mining_cost
     }
 
+    /// The cost of mining out the column of falling blocks (like sand and
+    /// gravel) that starts at `pos`, one block at a time as they fall. This is
+    /// infinite if there's a liquid on top of the column or it's taller than
+    /// [`MAX_FALLING_COLUMN_HEIGHT`].
+    fn cost_for_falling_column(&self, pos: BlockPos, mining_cache: &MiningCache) -> f32 {
+        let mut cost = 0.;
+        for y in 0..=MAX_FALLING_COLUMN_HEIGHT {
+            let block_state = self.get_block_state_at_pos(pos.up(y));
+            if mining_cache.is_liquid(block_state) {
+                return f32::INFINITY;
+            }
+            if !mining_cache.is_falling_block(block_state) {
+                return cost;
+            }
+            cost += mining_cache.cost_for(block_state) + FALLING_BLOCK_WAIT_TICKS as f32;
+        }
+        f32::INFINITY
+    }
+
     /// Whether this block and the block above are passable
     pub fn is_passable(&self, pos: RelBlockPos) -> bool {
         self.is_passable_at_block_pos(pos.apply(self.origin))
@@ -497,6 +1357,14 @@ impl CachedWorld {
         use crate::pathfinder::moves::water::{classify_water, WaterType};
         
         let block_pos = pos.apply(self.origin);
+        // most positions don't have any water, and lava isn't passable, so we can
+        // usually skip getting the block states
+        if self.is_block_pos_passable(block_pos)
+            && !self.is_block_pos_water(block_pos)
+            && !self.is_block_pos_water(block_pos.up(1))
+        {
+            return 0.;
+        }
         let current_block = self.get_block_state_at_pos(block_pos);
         let above_block = self.get_block_state_at_pos(block_pos.up(1));
         
@@ -546,6 +1414,9 @@ impl CachedWorld {
         use crate::pathfinder::moves::water::{classify_water, WaterType};
         
         // Check if current position is in water
+        if !self.is_block_pos_water(pos) {
+            return self.is_block_pos_standable(pos.down(1)) && self.is_passable_at_block_pos(pos);
+        }
         let current_block = self.get_block_state_at_pos(pos);
         let water_type = classify_water(current_block);
         
@@ -573,8 +1444,12 @@ impl CachedWorld {
         use crate::pathfinder::moves::water::{classify_water, WaterType};
         
         let block_pos = pos.apply(self.origin);
-        let current_block = self.get_block_state_at_pos(block_pos);
-        let water_type = classify_water(current_block);
+        // getting the block state is slow, so only do it if there's water here
+        let water_type = if self.is_block_pos_water(block_pos) {
+            classify_water(self.get_block_state_at_pos(block_pos))
+        } else {
+            None
+        };
         
         match water_type {
             Some(WaterType::StillWater) | Some(WaterType::Waterlogged) => {
@@ -594,16 +1469,44 @@ impl CachedWorld {
                 if !self.is_block_standable(pos.down(1)) {
                     return f32::INFINITY;
                 }
-                self.cost_for_passing(pos, mining_cache)
+                let mut cost = self.cost_for_passing(pos, mining_cache);
+                if self.is_dripstone_tip(pos.down(1)) {
+                    cost += DRIPSTONE_STANDING_PENALTY;
+                }
+                if self.is_under_powder_snow(pos) {
+                    cost += UNDER_POWDER_SNOW_PENALTY;
+                }
+                cost + self.damaging_surface_cost(pos) + self.risk_cost(pos)
             }
         }
     }
 
+    /// Whether the block at this position is the tip of upward-pointing
+    /// dripstone, which we can stand on but shouldn't fall onto.
+    pub fn is_dripstone_tip(&self, pos: RelBlockPos) -> bool {
+        // dripstone is standable but not solid, so we can usually avoid getting the
+        // block state
+        self.is_block_standable(pos)
+            && !self.is_block_solid(pos)
+            && is_block_state_dripstone_tip(self.get_block_state(pos))
+    }
+
+    /// Whether there's powder snow right above our head at this position that
+    /// we'd sink into if we jumped, and we don't have leather boots to climb
+    /// back out of it.
+    pub fn is_under_powder_snow(&self, pos: RelBlockPos) -> bool {
+        !self.equipment.has_leather_boots
+            && !self.is_block_passable(pos.up(2))
+            && azalea_registry::Block::from(self.get_block_state(pos.up(2)))
+                == azalea_registry::Block::PowderSnow
+    }
+
     /// Get the amount of air blocks until the next solid block below this one.
     pub fn fall_distance(&self, pos: RelBlockPos) -> u32 {
         let mut distance = 0;
         let mut current_pos = pos.down(1);
-        while self.is_block_passable(current_pos) {
+        // scaffolding is passable but we still land on top of it
+        while self.is_block_passable(current_pos) && !self.is_block_standable(current_pos) {
             distance += 1;
             current_pos = current_pos.down(1);
 
@@ -614,6 +1517,28 @@ impl CachedWorld {
         distance
     }
 
+    /// Whether there's a drop of more than [`LEDGE_DROP_THRESHOLD`] blocks
+    /// directly to the side of this position, relative to the direction we're
+    /// travelling in.
+    ///
+    /// The direction doesn't have to be cardinal, diagonal directions check the
+    /// two diagonal neighbors that are perpendicular to it.
+    pub fn has_ledge_beside(&self, pos: RelBlockPos, dx: i16, dz: i16) -> bool {
+        for side in [RelBlockPos::new(-dz, 0, dx), RelBlockPos::new(dz, 0, -dx)] {
+            let side_pos = pos + side;
+            if !self.is_passable(side_pos) {
+                // there's a wall here, so we can't fall off
+                continue;
+            }
+            let is_drop = (1..=LEDGE_DROP_THRESHOLD as i32 + 1)
+                .all(|y| self.is_block_passable(side_pos.down(y)));
+            if is_drop {
+                return true;
+            }
+        }
+        false
+    }
+
     pub fn origin(&self) -> BlockPos {
         self.origin
     }
@@ -628,8 +1553,14 @@ pub fn is_block_state_passable(block: BlockState) -> bool {
         // fast path
         return true;
     }
+    if azalea_registry::Block::from(block) == azalea_registry::Block::Scaffolding {
+        // we can walk through the sides of scaffolding
+        return true;
+    }
     if !block.is_collision_shape_empty() {
-        return false;
+        // open doors and trapdoors still have a collision shape on one side, but it
+        // doesn't get in the way
+        return is_block_state_open_doorway(block);
     }
     let registry_block = azalea_registry::Block::from(block);
     
@@ -666,12 +1597,54 @@ pub fn is_block_state_passable(block: BlockState) -> bool {
         return false;
     }
 
-    if registry_block == azalea_registry::Block::SweetBerryBush {
-        // these hurt us
+    true
+}
+
+/// The extra cost for moving through this block if it's passable but slows
+/// us down, like a cobweb.
+pub fn passable_block_penalty(block: BlockState) -> f32 {
+    match azalea_registry::Block::from(block) {
+        azalea_registry::Block::Cobweb => COBWEB_PENALTY,
+        azalea_registry::Block::SweetBerryBush => SWEET_BERRY_BUSH_PENALTY,
+        _ => 0.,
+    }
+}
+
+/// Whether this is a door, fence gate, or trapdoor that's open, so we can walk
+/// through it without having to interact with it. Powered doors count as open
+/// too, since that's what opens iron doors.
+pub fn is_block_state_open_doorway(block: BlockState) -> bool {
+    // checking the properties first is faster, since most blocks don't have them
+    let is_open = block.property::<properties::Open>().unwrap_or_default()
+        || block.property::<properties::Powered>().unwrap_or_default();
+    if !is_open {
+        return false;
+    }
+
+    let registry_block = azalea_registry::Block::from(block);
+    azalea_registry::tags::blocks::DOORS.contains(&registry_block)
+        || azalea_registry::tags::blocks::FENCE_GATES.contains(&registry_block)
+        || azalea_registry::tags::blocks::TRAPDOORS.contains(&registry_block)
+}
+
+/// Whether this is a door, fence gate, or trapdoor that we can open by
+/// right-clicking it. Iron doors and trapdoors can only be opened with
+/// redstone, so they don't count.
+pub fn is_block_state_openable(block: BlockState) -> bool {
+    if block.property::<properties::Open>().is_none() {
         return false;
     }
 
-    true
+    let registry_block = azalea_registry::Block::from(block);
+    if matches!(
+        registry_block,
+        azalea_registry::Block::IronDoor | azalea_registry::Block::IronTrapdoor
+    ) {
+        return false;
+    }
+    azalea_registry::tags::blocks::DOORS.contains(&registry_block)
+        || azalea_registry::tags::blocks::FENCE_GATES.contains(&registry_block)
+        || azalea_registry::tags::blocks::TRAPDOORS.contains(&registry_block)
 }
 
 /// whether this block has a solid hitbox at the top (i.e. we can stand on it
@@ -696,6 +1669,48 @@ pub fn is_block_state_solid(block: BlockState) -> bool {
     false
 }
 
+/// Whether we can stand on top of this block, including blocks where that
+/// depends on the bot's equipment.
+pub fn block_support(block: BlockState) -> BlockSupport {
+    match azalea_registry::Block::from(block) {
+        azalea_registry::Block::Scaffolding => {
+            BlockSupport::ConditionallyStandable(SupportCondition::NotSneaking)
+        }
+        azalea_registry::Block::PowderSnow => {
+            BlockSupport::ConditionallyStandable(SupportCondition::LeatherBoots)
+        }
+        // standing on dripstone is penalized in cost_for_standing
+        azalea_registry::Block::PointedDripstone if is_block_state_dripstone_tip(block) => {
+            BlockSupport::Standable
+        }
+        _ if is_block_state_standable(block) => BlockSupport::Standable,
+        _ => BlockSupport::NotStandable,
+    }
+}
+
+/// Whether this block is the tip of upward-pointing dripstone (a stalagmite).
+pub fn is_block_state_dripstone_tip(block: BlockState) -> bool {
+    azalea_registry::Block::from(block) == azalea_registry::Block::PointedDripstone
+        && matches!(
+            block.property::<properties::VerticalDirection>(),
+            Some(properties::VerticalDirection::Up)
+        )
+        && matches!(
+            block.property::<properties::Thickness>(),
+            Some(properties::Thickness::Tip | properties::Thickness::TipMerge)
+        )
+}
+
+/// Whether this block is downward-pointing dripstone (a stalactite), which
+/// falls when the block it's hanging from is broken.
+pub fn is_block_state_stalactite(block: BlockState) -> bool {
+    azalea_registry::Block::from(block) == azalea_registry::Block::PointedDripstone
+        && matches!(
+            block.property::<properties::VerticalDirection>(),
+            Some(properties::VerticalDirection::Down)
+        )
+}
+
 pub fn is_block_state_standable(block: BlockState) -> bool {
     if block.is_air() {
         // fast path
@@ -716,6 +1731,14 @@ pub fn is_block_state_standable(block: BlockState) -> bool {
         return true;
     }
 
+    // these are a bit shorter than a full block, but we only sink into them a
+    // little
+    if registry_block == azalea_registry::Block::SoulSand
+        || registry_block == azalea_registry::Block::HoneyBlock
+    {
+        return true;
+    }
+
     false
 }
 
@@ -796,4 +1819,284 @@ mod tests {
         assert!(!ctx.is_standable_at_block_pos(BlockPos::new(0, 0, 0)));
         assert!(!ctx.is_standable_at_block_pos(BlockPos::new(0, 2, 0)));
     }
+
+    #[test]
+    fn test_cost_for_passing_through_cobweb() {
+        let mut partial_world = PartialInstance::default();
+        let mut world = ChunkStorage::default();
+        partial_world
+            .chunks
+            .set(&ChunkPos { x: 0, z: 0 }, Some(Chunk::default()), &mut world);
+        partial_world.chunks.set_block_state(
+            BlockPos::new(0, 1, 0),
+            azalea_registry::Block::Cobweb.into(),
+            &world,
+        );
+
+        let world_lock: Arc<RwLock<Instance>> = Arc::new(RwLock::new(world.into()));
+        let cost = |mining_cache: MiningCache| {
+            CachedWorld::new(world_lock.clone(), BlockPos::default())
+                .cost_for_breaking_block(RelBlockPos::new(0, 1, 0), &mining_cache)
+        };
+
+        // we're not allowed to mine, so we have to walk through it
+        assert_eq!(cost(MiningCache::new(None)), COBWEB_PENALTY);
+        // breaking it by hand is even slower than walking through it
+        let mut menu = Menu::Player(azalea_inventory::Player::default());
+        assert_eq!(cost(MiningCache::new(Some(menu.clone()))), COBWEB_PENALTY);
+        // but shears make it fast
+        let hotbar_slot = *azalea_inventory::Player::HOTBAR_SLOTS.start();
+        *menu.slot_mut(hotbar_slot).unwrap() = azalea_registry::Item::Shears.into();
+        assert!(cost(MiningCache::new(Some(menu))) < COBWEB_PENALTY);
+    }
+
+    /// The costs of breaking the block at `target` and of passing through it
+    /// (which also breaks the block above it), with the given blocks placed.
+    fn mining_costs_with(
+        target: BlockPos,
+        blocks: &[(BlockPos, azalea_registry::Block)],
+    ) -> (f32, f32) {
+        let mut partial_world = PartialInstance::default();
+        let mut world = ChunkStorage::default();
+        for x in [0, 1] {
+            partial_world
+                .chunks
+                .set(&ChunkPos { x, z: 0 }, Some(Chunk::default()), &mut world);
+        }
+        for &(pos, block) in blocks {
+            partial_world
+                .chunks
+                .set_block_state(pos, block.into(), &world);
+        }
+
+        let world = CachedWorld::new(Arc::new(RwLock::new(world.into())), BlockPos::default());
+        let mining_cache =
+            MiningCache::new(Some(Menu::Player(azalea_inventory::Player::default())));
+        let target = RelBlockPos::from_origin(BlockPos::default(), target);
+        (
+            world.cost_for_breaking_block(target, &mining_cache),
+            world.cost_for_passing(target, &mining_cache),
+        )
+    }
+
+    #[test]
+    fn test_cost_for_breaking_block_next_to_lava() {
+        use azalea_registry::Block;
+
+        let target = BlockPos::new(1, 1, 1);
+        let stone = [(target, Block::Stone), (target.up(1), Block::Stone)];
+        let (breaking_cost, passing_cost) = mining_costs_with(target, &stone);
+        assert!(breaking_cost.is_finite());
+        assert!(passing_cost.is_finite());
+
+        // lava behind the block or on any other side of it would flow into the hole
+        for lava in [
+            target.north(1),
+            target.east(1),
+            target.south(1),
+            target.west(1),
+            target.up(1),
+        ] {
+            let mut blocks = vec![(target, Block::Stone), (lava, Block::Lava)];
+            if lava != target.up(1) {
+                blocks.push((target.up(1), Block::Stone));
+            }
+            let (breaking_cost, passing_cost) = mining_costs_with(target, &blocks);
+            assert_eq!(breaking_cost, f32::INFINITY, "lava at {lava:?}");
+            assert_eq!(passing_cost, f32::INFINITY, "lava at {lava:?}");
+        }
+
+        // the lava is in another chunk
+        let target_at_edge = BlockPos::new(15, 1, 1);
+        let (breaking_cost, _) = mining_costs_with(
+            target_at_edge,
+            &[
+                (target_at_edge, Block::Stone),
+                (target_at_edge.east(1), Block::Lava),
+            ],
+        );
+        assert_eq!(breaking_cost, f32::INFINITY);
+
+        // lava two blocks up only flows down once we break the block above the target
+        // too, which we always do when we go through it
+        let blocks = [
+            (target, Block::Stone),
+            (target.up(1), Block::Stone),
+            (target.up(2), Block::Lava),
+        ];
+        let (breaking_cost, passing_cost) = mining_costs_with(target, &blocks);
+        assert!(breaking_cost.is_finite());
+        assert_eq!(passing_cost, f32::INFINITY);
+
+        // unless the block above the target falls, in which case the lava comes down
+        // with it
+        let blocks = [
+            (target, Block::Stone),
+            (target.up(1), Block::Gravel),
+            (target.up(2), Block::Lava),
+        ];
+        let (breaking_cost, passing_cost) = mining_costs_with(target, &blocks);
+        assert_eq!(breaking_cost, f32::INFINITY);
+        assert_eq!(passing_cost, f32::INFINITY);
+    }
+
+    fn cached_world_on_top_of(
+        block: azalea_registry::Block,
+        equipment: PathEquipment,
+    ) -> CachedWorld {
+        let mut partial_world = PartialInstance::default();
+        let mut world = ChunkStorage::default();
+        partial_world
+            .chunks
+            .set(&ChunkPos { x: 0, z: 0 }, Some(Chunk::default()), &mut world);
+        partial_world
+            .chunks
+            .set_block_state(BlockPos::new(0, 0, 0), block.into(), &world);
+        partial_world
+            .chunks
+            .set_block_state(BlockPos::new(0, 1, 0), BlockState::AIR, &world);
+        partial_world
+            .chunks
+            .set_block_state(BlockPos::new(0, 2, 0), BlockState::AIR, &world);
+
+        CachedWorld::new(Arc::new(RwLock::new(world.into())), BlockPos::default())
+            .with_equipment(equipment)
+    }
+
+    #[test]
+    fn test_powder_snow_standable_with_boots() {
+        let ctx = cached_world_on_top_of(
+            azalea_registry::Block::PowderSnow,
+            PathEquipment {
+                has_leather_boots: true,
+                ..Default::default()
+            },
+        );
+        assert!(ctx.is_standable_at_block_pos(BlockPos::new(0, 1, 0)));
+    }
+
+    #[test]
+    fn test_powder_snow_not_standable_without_boots() {
+        let ctx = cached_world_on_top_of(
+            azalea_registry::Block::PowderSnow,
+            PathEquipment {
+                has_leather_boots: false,
+                ..Default::default()
+            },
+        );
+        assert!(!ctx.is_standable_at_block_pos(BlockPos::new(0, 1, 0)));
+    }
+
+    #[test]
+    fn test_scaffolding_standable_with_boots() {
+        let ctx = cached_world_on_top_of(
+            azalea_registry::Block::Scaffolding,
+            PathEquipment {
+                has_leather_boots: true,
+                ..Default::default()
+            },
+        );
+        assert!(ctx.is_standable_at_block_pos(BlockPos::new(0, 1, 0)));
+        assert!(ctx.is_block_pos_passable(BlockPos::new(0, 0, 0)));
+    }
+
+    #[test]
+    fn test_scaffolding_standable_without_boots() {
+        let ctx = cached_world_on_top_of(
+            azalea_registry::Block::Scaffolding,
+            PathEquipment {
+                has_leather_boots: false,
+                ..Default::default()
+            },
+        );
+        assert!(ctx.is_standable_at_block_pos(BlockPos::new(0, 1, 0)));
+        assert!(ctx.is_block_pos_passable(BlockPos::new(0, 0, 0)));
+    }
+
+    #[test]
+    fn test_bitsets_match_block_states() {
+        use azalea_world::chunk_storage::section_index;
+        use rand::{Rng, SeedableRng, rngs::StdRng};
+
+        let mut partial_world = PartialInstance::default();
+        let mut world = ChunkStorage::default();
+        let chunk_pos = ChunkPos::new(0, 0);
+        partial_world
+            .chunks
+            .set(&chunk_pos, Some(Chunk::default()), &mut world);
+
+        let mut rng = StdRng::seed_from_u64(0);
+        let few_states: [BlockState; 8] = [
+            BlockState::AIR,
+            azalea_registry::Block::Stone.into(),
+            azalea_registry::Block::Water.into(),
+            azalea_registry::Block::Kelp.into(),
+            azalea_registry::Block::PowderSnow.into(),
+            azalea_registry::Block::MagmaBlock.into(),
+            azalea_registry::Block::Scaffolding.into(),
+            azalea_registry::Block::OakStairs.into(),
+        ];
+        for x in 0..16 {
+            for z in 0..16 {
+                // a section with only a few block states, so it has a small palette
+                for y in 64..80 {
+                    let block_state = few_states[rng.random_range(0..few_states.len())];
+                    world.set_block_state(BlockPos::new(x, y, z), block_state);
+                }
+                // and one with so many that it uses the global palette
+                for y in 80..96 {
+                    let id = rng.random_range(0..=BlockState::MAX_STATE);
+                    let block_state = BlockState::try_from(id).unwrap();
+                    world.set_block_state(BlockPos::new(x, y, z), block_state);
+                }
+                // the section above that is left as only air
+            }
+        }
+
+        {
+            let chunk = world.get(&chunk_pos).unwrap();
+            let chunk = chunk.read();
+            let palette = |y| {
+                &chunk.sections[section_index(y, world.min_y) as usize]
+                    .states
+                    .palette
+            };
+            assert!(matches!(
+                palette(64),
+                Palette::Linear(_) | Palette::Hashmap(_)
+            ));
+            assert!(matches!(palette(80), Palette::Global));
+            assert!(matches!(palette(96), Palette::SingleValue(_)));
+        }
+
+        let ctx = CachedWorld::new(Arc::new(RwLock::new(world.into())), BlockPos::default());
+        for x in 0..16 {
+            for z in 0..16 {
+                for y in 64..112 {
+                    let pos = BlockPos::new(x, y, z);
+                    let block_state = ctx.get_block_state_at_pos(pos);
+                    assert_eq!(
+                        ctx.is_block_pos_passable(pos),
+                        is_block_state_passable(block_state),
+                        "{block_state:?} at {pos}"
+                    );
+                    assert_eq!(
+                        ctx.is_block_pos_solid(pos),
+                        is_block_state_solid(block_state),
+                        "{block_state:?} at {pos}"
+                    );
+                    assert_eq!(
+                        ctx.is_block_pos_standable(pos),
+                        block_support(block_state).is_standable(PathEquipment::default()),
+                        "{block_state:?} at {pos}"
+                    );
+                    assert_eq!(
+                        ctx.is_block_pos_water(pos),
+                        classify_water(block_state).is_some(),
+                        "{block_state:?} at {pos}"
+                    );
+                }
+            }
+        }
+    }
 }
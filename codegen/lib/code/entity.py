@@ -527,6 +527,11 @@ impl From<EntityDataValue> for UpdateMetadataError {
                             # in 1.19.4 TextOpacity is a -1 by default
                             if default < 0:
                                 default += 128
+                    if name == "air_supply":
+                        # burger doesn't get this one because it comes from
+                        # getMaxAirSupply, and the server never sends it if it's
+                        # still at the default
+                        default = "300"
                     if name in single_use_imported_types:
                         code.append(f"            {name}: {default},")
                     else: